@@ -22,7 +22,8 @@
 //! tree.find(&5, 3); // find 3 knn points from 5
 //!
 //! ```
-use std::fmt::Debug;
+use alloc::vec::Vec;
+use core::fmt::Debug;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -101,6 +102,11 @@ impl<T: Debug + PartialEq, F: RealNumber, D: Distance<T, F>> CoverTree<T, F, D>
         Ok(tree)
     }
 
+    /// Distance metric used for searching.
+    pub(crate) fn distance(&self) -> &D {
+        &self.distance
+    }
+
     /// Find k nearest neighbors of `p`
     /// * `p` - look for k nearest points to `p`
     /// * `k` - the number of nearest neighbors to return
@@ -292,7 +298,7 @@ impl<T: Debug + PartialEq, F: RealNumber, D: Distance<T, F>> CoverTree<T, F, D>
         } else {
             let max_dist = self.max(point_set);
             let next_scale = (max_scale - 1).min(self.get_scale(max_dist));
-            if next_scale == std::i64::MIN {
+            if next_scale == core::i64::MIN {
                 let mut children: Vec<Node<F>> = Vec::new();
                 let mut leaf = self.new_leaf(p);
                 children.push(leaf);
@@ -436,7 +442,7 @@ impl<T: Debug + PartialEq, F: RealNumber, D: Distance<T, F>> CoverTree<T, F, D>
 
     fn get_scale(&self, d: F) -> i64 {
         if d == F::zero() {
-            std::i64::MIN
+            core::i64::MIN
         } else {
             (self.inv_log_base * d.ln()).ceil().to_i64().unwrap()
         }
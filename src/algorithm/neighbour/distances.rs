@@ -4,7 +4,7 @@
 //! Representing distances as pairwise dissimilarities, so to build a
 //! graph of closest neighbours. This representation can be reused for
 //! different implementations (initially used in this library for FastPair).
-use std::cmp::{Eq, Ordering, PartialOrd};
+use core::cmp::{Eq, Ordering, PartialOrd};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
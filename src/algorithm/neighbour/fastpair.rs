@@ -23,9 +23,11 @@
 /// ```
 /// <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 /// <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
-use std::collections::HashMap;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 
 use crate::algorithm::neighbour::distances::PairwiseDistance;
+use crate::collections::HashMap;
 use crate::error::{Failed, FailedError};
 use crate::linalg::Matrix;
 use crate::math::distance::euclidian::Euclidian;
@@ -64,7 +66,7 @@ impl<'a, T: RealNumber, M: Matrix<T>> FastPair<'a, T, M> {
         let mut init = Self {
             samples: m,
             // to be computed in init(..)
-            distances: HashMap::with_capacity(m.shape().0),
+            distances: HashMap::new(),
             neighbours: Vec::with_capacity(m.shape().0 + 1),
         };
         init.init();
@@ -77,11 +79,11 @@ impl<'a, T: RealNumber, M: Matrix<T>> FastPair<'a, T, M> {
     ///
     fn init(&mut self) {
         // basic measures
-        let len = self.samples.shape().0;
-        let max_index = self.samples.shape().0 - 1;
+        let (len, n_features) = self.samples.shape();
+        let max_index = len - 1;
 
         // Store all closest neighbors
-        let _distances = Box::new(HashMap::with_capacity(len));
+        let _distances = Box::new(HashMap::new());
         let _neighbours = Box::new(Vec::with_capacity(len));
 
         let mut distances = *_distances;
@@ -102,8 +104,13 @@ impl<'a, T: RealNumber, M: Matrix<T>> FastPair<'a, T, M> {
             );
         }
 
+        // rows are copied into reusable buffers instead of allocating a vector per comparison
+        let mut row_i = vec![T::zero(); n_features];
+        let mut row_j = vec![T::zero(); n_features];
+
         // loop through indeces and neighbours
         for index_row_i in 0..(len) {
+            self.samples.copy_row_as_vec(index_row_i, &mut row_i);
             // start looking for the neighbour in the second element
             let mut index_closest = index_row_i + 1; // closest neighbour index
             let mut nbd: Option<T> = distances[&index_row_i].distance; // init neighbour distance
@@ -117,10 +124,8 @@ impl<'a, T: RealNumber, M: Matrix<T>> FastPair<'a, T, M> {
                     },
                 );
 
-                let d = Euclidian::squared_distance(
-                    &(self.samples.get_row_as_vec(index_row_i)),
-                    &(self.samples.get_row_as_vec(index_row_j)),
-                );
+                self.samples.copy_row_as_vec(index_row_j, &mut row_j);
+                let d = Euclidian::squared_distance(&row_i, &row_j);
                 if d < nbd.unwrap() {
                     // set this j-value to be the closest neighbour
                     index_closest = index_row_j;
@@ -176,18 +181,19 @@ impl<'a, T: RealNumber, M: Matrix<T>> FastPair<'a, T, M> {
     #[cfg(feature = "fp_bench")]
     pub fn closest_pair_brute(&self) -> PairwiseDistance<T> {
         use itertools::Itertools;
-        let m = self.samples.shape().0;
+        let (m, n_features) = self.samples.shape();
 
         let mut closest_pair = PairwiseDistance {
             node: 0,
             neighbour: None,
             distance: Some(T::max_value()),
         };
+        let mut row_i = vec![T::zero(); n_features];
+        let mut row_j = vec![T::zero(); n_features];
         for pair in (0..m).combinations(2) {
-            let d = Euclidian::squared_distance(
-                &(self.samples.get_row_as_vec(pair[0])),
-                &(self.samples.get_row_as_vec(pair[1])),
-            );
+            self.samples.copy_row_as_vec(pair[0], &mut row_i);
+            self.samples.copy_row_as_vec(pair[1], &mut row_j);
+            let d = Euclidian::squared_distance(&row_i, &row_j);
             if d < closest_pair.distance.unwrap() {
                 closest_pair.node = pair[0];
                 closest_pair.neighbour = Some(pair[1]);
@@ -203,16 +209,17 @@ impl<'a, T: RealNumber, M: Matrix<T>> FastPair<'a, T, M> {
     //
     #[allow(dead_code)]
     fn distances_from(&self, index_row: usize) -> Vec<PairwiseDistance<T>> {
-        let mut distances = Vec::<PairwiseDistance<T>>::with_capacity(self.samples.shape().0);
+        let (len, n_features) = self.samples.shape();
+        let mut distances = Vec::<PairwiseDistance<T>>::with_capacity(len);
+        let row = self.samples.get_row_as_vec(index_row);
+        let mut other_row = vec![T::zero(); n_features];
         for other in self.neighbours.iter() {
             if index_row != *other {
+                self.samples.copy_row_as_vec(*other, &mut other_row);
                 distances.push(PairwiseDistance {
                     node: index_row,
                     neighbour: Some(*other),
-                    distance: Some(Euclidian::squared_distance(
-                        &(self.samples.get_row_as_vec(index_row)),
-                        &(self.samples.get_row_as_vec(*other)),
-                    )),
+                    distance: Some(Euclidian::squared_distance(&row, &other_row)),
                 })
             }
         }
@@ -457,13 +464,15 @@ mod tests_fastpair {
 
         let expected: HashMap<_, _> = dissimilarities.into_iter().collect();
 
+        let mut input_node = vec![0.; x.shape().1];
+        let mut neighbour_node = vec![0.; x.shape().1];
         for i in 0..(x.shape().0 - 1) {
-            let input_node = result.samples.get_row_as_vec(i);
+            result.samples.copy_row_as_vec(i, &mut input_node);
             let input_neighbour: usize = expected.get(&i).unwrap().neighbour.unwrap();
-            let distance = Euclidian::squared_distance(
-                &input_node,
-                &result.samples.get_row_as_vec(input_neighbour),
-            );
+            result
+                .samples
+                .copy_row_as_vec(input_neighbour, &mut neighbour_node);
+            let distance = Euclidian::squared_distance(&input_node, &neighbour_node);
 
             assert_eq!(i, expected.get(&i).unwrap().node);
             assert_eq!(
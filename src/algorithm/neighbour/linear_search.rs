@@ -22,10 +22,11 @@
 //!
 //! ```
 
+use alloc::vec::Vec;
+use core::cmp::{Ordering, PartialOrd};
+use core::marker::PhantomData;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::cmp::{Ordering, PartialOrd};
-use std::marker::PhantomData;
 
 use crate::algorithm::sort::heap_select::HeapSelection;
 use crate::error::{Failed, FailedError};
@@ -53,6 +54,11 @@ impl<T, F: RealNumber, D: Distance<T, F>> LinearKNNSearch<T, F, D> {
         })
     }
 
+    /// Distance metric used for searching.
+    pub(crate) fn distance(&self) -> &D {
+        &self.distance
+    }
+
     /// Find k nearest neighbors
     /// * `from` - look for k nearest points to `from`
     /// * `k` - the number of nearest neighbors to return
@@ -35,6 +35,7 @@ use crate::algorithm::neighbour::linear_search::LinearKNNSearch;
 use crate::error::Failed;
 use crate::math::distance::Distance;
 use crate::math::num::RealNumber;
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -72,6 +73,26 @@ pub(crate) enum KNNAlgorithm<T: RealNumber, D: Distance<Vec<T>, T>> {
     CoverTree(CoverTree<Vec<T>, T, D>),
 }
 
+/// Distance over slices applied to points stored as vectors, lets estimators that keep rows as `Vec<T>`
+/// in a search structure accept any distance defined over `[T]`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub(crate) struct RowDistance<D>(pub(crate) D);
+
+impl<T: RealNumber, D: Distance<[T], T>> Distance<Vec<T>, T> for RowDistance<D> {
+    fn distance(&self, a: &Vec<T>, b: &Vec<T>) -> T {
+        self.0.distance(a, b)
+    }
+
+    fn supports_missing_values(&self) -> bool {
+        self.0.supports_missing_values()
+    }
+
+    fn is_euclidian(&self) -> bool {
+        self.0.is_euclidian()
+    }
+}
+
 impl KNNAlgorithmName {
     pub(crate) fn fit<T: RealNumber, D: Distance<Vec<T>, T>>(
         &self,
@@ -107,4 +128,11 @@ impl<T: RealNumber, D: Distance<Vec<T>, T>> KNNAlgorithm<T, D> {
             KNNAlgorithm::CoverTree(ref cover) => cover.find_radius(from, radius),
         }
     }
+
+    pub fn supports_missing_values(&self) -> bool {
+        match *self {
+            KNNAlgorithm::LinearSearch(ref linear) => linear.distance().supports_missing_values(),
+            KNNAlgorithm::CoverTree(ref cover) => cover.distance().supports_missing_values(),
+        }
+    }
 }
@@ -1,8 +1,9 @@
 //! # Heap Selection Algorithm
 //!
 //! The goal is to find the k smallest elements in a list or array.
-use std::cmp::Ordering;
-use std::fmt::Debug;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Debug;
 
 #[derive(Debug)]
 pub struct HeapSelection<T: PartialOrd + Debug> {
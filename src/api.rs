@@ -2,8 +2,46 @@
 //!
 //! This module provides interfaces and uniform API with simple conventions
 //! that are used in other modules for supervised and unsupervised learning.
+//!
+//! Every model is fitted with a static `fit` method that takes training data and hyperparameters:
+//! * supervised models, e.g. [SVC](../svm/svc/index.html), [trees](../tree/index.html) and [linear models](../linear/index.html), implement [`SupervisedEstimator`](trait.SupervisedEstimator.html),
+//! * clustering, decomposition and preprocessing, e.g. [KMeans](../cluster/kmeans/index.html) or [PCA](../decomposition/pca/index.html), implement [`UnsupervisedEstimator`](trait.UnsupervisedEstimator.html).
+//!
+//! Models that can keep learning after `fit`, e.g. [SGD](../linear/sgd/index.html), [naive Bayes](../naive_bayes/index.html) or [mini-batch k-means](../cluster/mini_batch_kmeans/index.html),
+//! also implement [`OnlineEstimator`](trait.OnlineEstimator.html) or [`UnsupervisedOnlineEstimator`](trait.UnsupervisedOnlineEstimator.html) to be updated one batch at a time with `partial_fit`.
+//!
+//! A fitted model then implements [`Predictor`](trait.Predictor.html), [`Transformer`](trait.Transformer.html) or both.
+//! Classifiers that estimate class probabilities also implement [`ProbabilisticClassifier`](trait.ProbabilisticClassifier.html).
+//! Code that only relies on these traits works with any model, this is how [pipelines](../pipeline/index.html),
+//! [cross-validation and grid search](../model_selection/index.html) and meta-estimators like [RANSAC](../linear/ransac/index.html) are written:
+//!
+//! ```
+//! use smartcore::api::{Predictor, SupervisedEstimator};
+//! use smartcore::error::Failed;
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::linear::linear_regression::{LinearRegression, LinearRegressionParameters};
+//! use smartcore::tree::decision_tree_regressor::{DecisionTreeRegressor, DecisionTreeRegressorParameters};
+//!
+//! // fits any regressor and returns its predictions for the training data
+//! fn fit_predict<E, P>(x: &DenseMatrix<f64>, y: &Vec<f64>, parameters: P) -> Result<Vec<f64>, Failed>
+//! where
+//!     E: SupervisedEstimator<DenseMatrix<f64>, Vec<f64>, P> + Predictor<DenseMatrix<f64>, Vec<f64>>,
+//!     P: Clone,
+//! {
+//!     E::fit(x, y, parameters)?.predict(x)
+//! }
+//!
+//! let x = DenseMatrix::from_2d_array(&[&[1., 2.], &[2., 1.], &[3., 4.], &[4., 3.], &[5., 6.]]);
+//! let y = vec![3., 3., 7., 7., 11.];
+//!
+//! let y_linear = fit_predict::<LinearRegression<f64, DenseMatrix<f64>>, _>(
+//!     &x, &y, LinearRegressionParameters::default()).unwrap();
+//! let y_tree = fit_predict::<DecisionTreeRegressor<f64>, _>(
+//!     &x, &y, DecisionTreeRegressorParameters::default()).unwrap();
+//! ```
 
 use crate::error::Failed;
+use alloc::vec::Vec;
 
 /// An estimator for unsupervised learning, that provides method `fit` to learn from data
 pub trait UnsupervisedEstimator<X, P> {
@@ -28,6 +66,34 @@ pub trait SupervisedEstimator<X, Y, P> {
         P: Clone;
 }
 
+/// An estimator for supervised learning that can learn from a training dataset where each observation has its own weight
+pub trait SupervisedEstimatorWeighted<X, Y, P> {
+    /// Fit a model to a weighted training dataset, estimate model's parameters.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target training values of size _N_.
+    /// * `sample_weight` - non-negative weights of size _N_.
+    /// * `parameters` - hyperparameters of an algorithm
+    fn fit_with_weights(x: &X, y: &Y, sample_weight: &Y, parameters: P) -> Result<Self, Failed>
+    where
+        Self: Sized,
+        P: Clone;
+}
+
+/// An estimator for supervised learning that learns incrementally, one batch of training data at a time
+pub trait OnlineEstimator<X, Y> {
+    /// Update a model with a batch of training data, e.g. a part of a dataset that does not fit in memory or data that arrives over time.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target training values of size _N_.
+    fn partial_fit(&mut self, x: &X, y: &Y) -> Result<(), Failed>;
+}
+
+/// An estimator for unsupervised learning that learns incrementally, one batch of training data at a time
+pub trait UnsupervisedOnlineEstimator<X> {
+    /// Update a model with a batch of training data, e.g. a part of a dataset that does not fit in memory or data that arrives over time.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    fn partial_fit(&mut self, x: &X) -> Result<(), Failed>;
+}
+
 /// Implements method predict that estimates target value from new data
 pub trait Predictor<X, Y> {
     /// Estimate target values from new data.
@@ -35,9 +101,57 @@ pub trait Predictor<X, Y> {
     fn predict(&self, x: &X) -> Result<Y, Failed>;
 }
 
+/// Implements method predict_proba that estimates class probabilities from new data
+pub trait PredictorProba<X> {
+    /// Estimate class probabilities from new data.
+    /// Returns _NxK_ matrix where _K_ is the number of classes, columns are ordered by class label.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    fn predict_proba(&self, x: &X) -> Result<X, Failed>;
+}
+
+/// A classifier that estimates the probability of every class, see [`PredictorProba`](trait.PredictorProba.html).
+/// Ensembles, calibration and probability metrics like [log loss](../metrics/fn.log_loss.html) can rely on this trait
+/// to get probabilities from any classifier and to match the columns of the probability matrix with class labels:
+///
+/// ```
+/// use smartcore::api::ProbabilisticClassifier;
+/// use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+/// use smartcore::linalg::BaseMatrix;
+/// use smartcore::linear::logistic_regression::LogisticRegression;
+/// use smartcore::naive_bayes::gaussian::GaussianNB;
+///
+/// // probability of class `label` for every observation in `x`
+/// fn class_probability<C: ProbabilisticClassifier<f64, DenseMatrix<f64>>>(
+///     classifier: &C,
+///     x: &DenseMatrix<f64>,
+///     label: f64,
+/// ) -> Vec<f64> {
+///     let column = classifier.classes().iter().position(|c| *c == label).unwrap();
+///     classifier.predict_proba(x).unwrap().get_col_as_vec(column)
+/// }
+///
+/// let x = DenseMatrix::from_2d_array(&[&[1., 2.], &[2., 1.], &[3., 1.], &[6., 5.], &[7., 6.], &[8., 8.]]);
+/// let y = vec![0., 0., 0., 1., 1., 1.];
+///
+/// let lr = LogisticRegression::fit(&x, &y, Default::default()).unwrap();
+/// let nb = GaussianNB::fit(&x, &y, Default::default()).unwrap();
+/// let p_lr = class_probability(&lr, &x, 1.);
+/// let p_nb = class_probability(&nb, &x, 1.);
+/// ```
+pub trait ProbabilisticClassifier<T, X>: PredictorProba<X> {
+    /// Class labels in the order of the columns of the matrix returned by `predict_proba`.
+    fn classes(&self) -> &Vec<T>;
+}
+
 /// Implements method transform that filters or modifies input data
 pub trait Transformer<X> {
     /// Transform data by modifying or filtering it
     /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
     fn transform(&self, x: &X) -> Result<X, Failed>;
 }
+
+/// Implements method feature_importance that scores how much every feature contributes to a fitted model
+pub trait FeatureImportance<T> {
+    /// Non-negative importance of every feature of the training set, larger values denote more important features.
+    fn feature_importance(&self) -> Vec<T>;
+}
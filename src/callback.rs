@@ -0,0 +1,199 @@
+//! # Training Callbacks
+//!
+//! Iterative estimators improve a model over many passes through the training data. Their `fit_with_callback` method calls a [`FitCallback`](trait.FitCallback.html)
+//! after every iteration with the [progress](struct.FitProgress.html) of the training, and the callback decides whether the training continues.
+//! When the callback returns [`FitAction::Stop`](enum.FitAction.html) the estimator stops and keeps the model fitted so far.
+//!
+//! | Estimator | Iteration | Loss | Validation score |
+//! |-|-|-|-|
+//! | [SGD](../linear/sgd/index.html) | epoch | mean training loss | - |
+//! | [MLP](../neural_network/index.html) | epoch, or iteration of L-BFGS | training loss | negative validation loss, with `early_stopping` |
+//! | [Histogram-based gradient boosting](../ensemble/hist_gradient_boosting/index.html) | boosting iteration | training loss | negative validation loss, with `n_iter_no_change` |
+//! | [SVC](../svm/svc/index.html) | epoch | negative dual objective | - |
+//!
+//! Any closure that takes a `&FitProgress<T>` and returns a `FitAction` is a callback, e.g. to log the progress or to stop after a time limit.
+//! [`EarlyStopping`](struct.EarlyStopping.html) stops the training once the validation score, or the loss when there is no validation set, stops improving.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::callback::{FitAction, FitProgress};
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::linear::sgd::{SGDRegressor, SGDRegressorParameters};
+//!
+//! let x = DenseMatrix::from_2d_array(&[&[1., 2.], &[2., 1.], &[3., 4.], &[4., 3.], &[5., 6.]]);
+//! let y = vec![3., 3., 7., 7., 11.];
+//!
+//! let mut losses = Vec::new();
+//! let regressor = SGDRegressor::fit_with_callback(
+//!     &x,
+//!     &y,
+//!     SGDRegressorParameters::default(),
+//!     &mut |progress: &FitProgress<f64>| {
+//!         losses.push(progress.loss.unwrap());
+//!         if progress.iteration < 3 {
+//!             FitAction::Continue
+//!         } else {
+//!             FitAction::Stop
+//!         }
+//!     },
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(regressor.n_iter(), 3);
+//! assert_eq!(losses.len(), 3);
+//! ```
+use crate::math::num::RealNumber;
+
+/// State of the training after an iteration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FitProgress<T: RealNumber> {
+    /// Number of completed iterations, starting with 1.
+    pub iteration: usize,
+    /// Loss on the training data, smaller values are better. `None` when the estimator does not track it.
+    pub loss: Option<T>,
+    /// Score on the validation data, greater values are better. `None` when the estimator is fitted without validation data.
+    pub validation_score: Option<T>,
+}
+
+/// Decision of a callback whether the training goes on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FitAction {
+    /// Run the next iteration, unless the estimator stops on its own.
+    Continue,
+    /// Stop the training and keep the model fitted so far.
+    Stop,
+}
+
+/// Monitors an iterative estimator and can stop its training, see [module documentation](index.html).
+pub trait FitCallback<T: RealNumber> {
+    /// Called after every iteration of the training.
+    /// * `progress` - state of the training after the iteration
+    fn on_iteration(&mut self, progress: &FitProgress<T>) -> FitAction;
+}
+
+impl<T: RealNumber, F: FnMut(&FitProgress<T>) -> FitAction> FitCallback<T> for F {
+    fn on_iteration(&mut self, progress: &FitProgress<T>) -> FitAction {
+        self(progress)
+    }
+}
+
+/// Callback that never stops the training, used by `fit`.
+pub(crate) struct NoCallback;
+
+impl<T: RealNumber> FitCallback<T> for NoCallback {
+    fn on_iteration(&mut self, _: &FitProgress<T>) -> FitAction {
+        FitAction::Continue
+    }
+}
+
+/// Stops the training when the validation score has not improved by more than `tol` for `patience` consecutive iterations.
+/// Without validation data the negative training loss is monitored instead.
+#[derive(Debug, Clone)]
+pub struct EarlyStopping<T: RealNumber> {
+    patience: usize,
+    tol: T,
+    best_score: Option<T>,
+    no_improvement: usize,
+}
+
+impl<T: RealNumber> EarlyStopping<T> {
+    /// Creates a new callback.
+    /// * `patience` - number of iterations without improvement before the training stops
+    /// * `tol` - minimum increase of the score that counts as an improvement
+    pub fn new(patience: usize, tol: T) -> Self {
+        EarlyStopping {
+            patience,
+            tol,
+            best_score: None,
+            no_improvement: 0,
+        }
+    }
+
+    /// Best score seen so far, `None` before the first iteration or when the estimator reports neither a validation score nor a loss.
+    pub fn best_score(&self) -> Option<T> {
+        self.best_score
+    }
+}
+
+impl<T: RealNumber> FitCallback<T> for EarlyStopping<T> {
+    fn on_iteration(&mut self, progress: &FitProgress<T>) -> FitAction {
+        let score = match progress
+            .validation_score
+            .or_else(|| progress.loss.map(|l| -l))
+        {
+            Some(score) => score,
+            None => return FitAction::Continue,
+        };
+        match self.best_score {
+            Some(best) if score <= best + self.tol => {
+                self.no_improvement += 1;
+                if score > best {
+                    self.best_score = Some(score);
+                }
+            }
+            _ => {
+                self.best_score = Some(score);
+                self.no_improvement = 0;
+            }
+        }
+        if self.no_improvement >= self.patience {
+            FitAction::Stop
+        } else {
+            FitAction::Continue
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(iteration: usize, loss: f64, validation_score: Option<f64>) -> FitProgress<f64> {
+        FitProgress {
+            iteration,
+            loss: Some(loss),
+            validation_score,
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn early_stopping_monitors_loss() {
+        let mut callback = EarlyStopping::new(2, 0.1);
+        assert_eq!(
+            callback.on_iteration(&progress(1, 3., None)),
+            FitAction::Continue
+        );
+        assert_eq!(
+            callback.on_iteration(&progress(2, 2., None)),
+            FitAction::Continue
+        );
+        // improvements smaller than `tol` do not count
+        assert_eq!(
+            callback.on_iteration(&progress(3, 1.95, None)),
+            FitAction::Continue
+        );
+        assert_eq!(
+            callback.on_iteration(&progress(4, 1.92, None)),
+            FitAction::Stop
+        );
+        assert_eq!(callback.best_score(), Some(-1.92));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn early_stopping_prefers_validation_score() {
+        let mut callback = EarlyStopping::new(1, 0.);
+        assert_eq!(
+            callback.on_iteration(&progress(1, 3., Some(-1.))),
+            FitAction::Continue
+        );
+        // the training loss improves, the validation score does not
+        assert_eq!(
+            callback.on_iteration(&progress(2, 2., Some(-1.5))),
+            FitAction::Stop
+        );
+        assert_eq!(callback.best_score(), Some(-1.));
+    }
+}
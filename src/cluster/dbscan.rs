@@ -40,8 +40,9 @@
 //! * ["A Density-Based Algorithm for Discovering Clusters in Large Spatial Databases with Noise", Ester M., Kriegel HP., Sander J., Xu X.](http://faculty.marshall.usc.edu/gareth-james/ISL/)
 //! * ["Density-Based Clustering in Spatial Databases: The Algorithm GDBSCAN and its Applications", Sander J., Ester M., Kriegel HP., Xu X.](https://citeseerx.ist.psu.edu/viewdoc/download?doi=10.1.1.63.1629&rep=rep1&type=pdf)
 
-use std::fmt::Debug;
-use std::iter::Sum;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::iter::Sum;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -54,6 +55,7 @@ use crate::math::distance::euclidian::Euclidian;
 use crate::math::distance::{Distance, Distances};
 use crate::math::num::RealNumber;
 use crate::tree::decision_tree_classifier::which_max;
+use crate::validation::check_x;
 
 /// DBSCAN clustering algorithm
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -260,6 +262,11 @@ impl<T: RealNumber + Sum, D: Distance<Vec<T>, T>> DBSCAN<T, D> {
         x: &M,
         parameters: DBSCANParameters<T, D>,
     ) -> Result<DBSCAN<T, D>, Failed> {
+        check_x(
+            x,
+            parameters.distance.supports_missing_values(),
+            Failed::fit,
+        )?;
         if parameters.min_samples < 1 {
             return Err(Failed::fit("Invalid minPts"));
         }
@@ -339,6 +346,11 @@ impl<T: RealNumber + Sum, D: Distance<Vec<T>, T>> DBSCAN<T, D> {
     /// Predict clusters for `x`
     /// * `x` - matrix with new data to transform of size _KxM_ , where _K_ is number of new samples and _M_ is number of features.
     pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_x(
+            x,
+            self.knn_algorithm.supports_missing_values(),
+            Failed::predict,
+        )?;
         let (n, m) = x.shape();
         let mut result = M::zeros(1, n);
         let mut row = vec![T::zero(); m];
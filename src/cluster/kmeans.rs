@@ -2,6 +2,9 @@
 //!
 //! K-means clustering partitions data into k clusters in a way that data points in the same cluster are similar and data points in the different clusters are farther apart.
 //! Similarity of two points is determined by the [Euclidian Distance](../../math/distance/euclidian/index.html) between them.
+//! Any other [distance](../../math/distance/index.html) can be set with `KMeansParameters::with_distance`, e.g. [Manhattan](../../math/distance/manhattan/index.html).
+//! Centroids are still the means of their clusters, and the Euclidian distance is the only one accelerated with a BBD tree: other distances
+//! fall back to the standard Lloyd's algorithm that compares every point with every centroid.
 //!
 //! K-means algorithm is not capable of determining the number of clusters. You need to choose this number yourself.
 //! One way to choose optimal number of clusters is to use [Elbow Method](https://en.wikipedia.org/wiki/Elbow_method_(clustering)).
@@ -12,6 +15,10 @@
 //! This iterative process continues until convergence is achieved and the clusters are considered settled.
 //!
 //! Initial choice of K data points is very important and has big effect on performance of the algorithm. SmartCore uses k-means++ algorithm to initialize cluster centers.
+//! Set `n_init` to run the algorithm several times from different k-means++ seeds and keep the solution with the lowest distortion.
+//!
+//! The assignment step can be computed with one of the engines listed in [`KMeansAlgorithm`](enum.KMeansAlgorithm.html). The default BBD tree filtering algorithm
+//! works well for low dimensional data, Elkan's algorithm uses the triangle inequality to skip most distance computations and is usually faster for moderate _k_.
 //!
 //! Example:
 //!
@@ -51,34 +58,43 @@
 //!
 //! * ["An Introduction to Statistical Learning", James G., Witten D., Hastie T., Tibshirani R., 10.3.1 K-Means Clustering](http://faculty.marshall.usc.edu/gareth-james/ISL/)
 //! * ["k-means++: The Advantages of Careful Seeding", Arthur D., Vassilvitskii S.](http://ilpubs.stanford.edu:8090/778/1/2006-13.pdf)
+//! * ["Using the Triangle Inequality to Accelerate k-Means", Elkan C., 2003](https://www.aaai.org/Papers/ICML/2003/ICML03-022.pdf)
 
-use std::fmt::Debug;
-use std::iter::Sum;
+#[cfg(feature = "pmml")]
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::iter::Sum;
 
 use ::rand::Rng;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::algorithm::neighbour::bbd_tree::BBDTree;
-use crate::api::{Predictor, UnsupervisedEstimator};
+use crate::api::{Predictor, Transformer, UnsupervisedEstimator};
 use crate::error::Failed;
 use crate::linalg::Matrix;
 use crate::math::distance::euclidian::*;
+use crate::math::distance::{Distance, Distances};
 use crate::math::num::RealNumber;
+#[cfg(feature = "pmml")]
+use crate::pmml::{cluster, FromPmml, ToPmml};
 use crate::rand::get_rng_impl;
+use crate::validation::{check_predict, check_transform, check_x};
 
 /// K-Means clustering algorithm
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug)]
-pub struct KMeans<T: RealNumber> {
+pub struct KMeans<T: RealNumber, D: Distance<[T], T> = Euclidian> {
     k: usize,
-    _y: Vec<usize>,
+    y: Vec<usize>,
     size: Vec<usize>,
-    _distortion: T,
+    distortion: T,
     centroids: Vec<Vec<T>>,
+    distance: D,
 }
 
-impl<T: RealNumber> PartialEq for KMeans<T> {
+impl<T: RealNumber, D: Distance<[T], T>> PartialEq for KMeans<T, D> {
     fn eq(&self, other: &Self) -> bool {
         if self.k != other.k
             || self.size != other.size
@@ -102,10 +118,25 @@ impl<T: RealNumber> PartialEq for KMeans<T> {
     }
 }
 
+/// Algorithm used to assign points to clusters on every iteration of k-means.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum KMeansAlgorithm {
+    /// Filtering algorithm that prunes candidate centroids for whole groups of points with a [BBD tree](../../algorithm/neighbour/bbd_tree/index.html).
+    /// Applies to distances for which [`Distance::is_euclidian`](../../math/distance/trait.Distance.html#method.is_euclidian) is `true`, other distances fall back to `Lloyd`.
+    #[default]
+    BBDTree,
+    /// Standard Lloyd's algorithm that compares every point with every centroid.
+    Lloyd,
+    /// Elkan's algorithm that skips most distance computations with upper and lower bounds derived from the triangle inequality.
+    /// The distance should be a metric. Uses _NxK_ memory for the bounds.
+    Elkan,
+}
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 /// K-Means clustering algorithm parameters
-pub struct KMeansParameters {
+pub struct KMeansParameters<D = Euclidian> {
     #[cfg_attr(feature = "serde", serde(default))]
     /// Number of clusters.
     pub k: usize,
@@ -116,9 +147,21 @@ pub struct KMeansParameters {
     /// Determines random number generation for centroid initialization.
     /// Use an int to make the randomness deterministic
     pub seed: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of times the k-means algorithm is run with different centroid seeds.
+    /// The final result is the run with the lowest distortion.
+    pub n_init: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// A function that defines a distance between a point and a centroid, Euclidian by default.
+    /// This function should extend [`Distance`](../../math/distance/trait.Distance.html) trait.
+    /// See [`Distances`](../../math/distance/struct.Distances.html) for a list of available functions.
+    pub distance: D,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Algorithm used to assign points to clusters.
+    pub algorithm: KMeansAlgorithm,
 }
 
-impl KMeansParameters {
+impl<D> KMeansParameters<D> {
     /// Number of clusters.
     pub fn with_k(mut self, k: usize) -> Self {
         self.k = k;
@@ -129,6 +172,34 @@ impl KMeansParameters {
         self.max_iter = max_iter;
         self
     }
+    /// Determines random number generation for centroid initialization.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+    /// Number of times the k-means algorithm is run with different centroid seeds.
+    pub fn with_n_init(mut self, n_init: usize) -> Self {
+        self.n_init = n_init;
+        self
+    }
+    /// A function that defines a distance between a point and a centroid.
+    /// This function should extend [`Distance`](../../math/distance/trait.Distance.html) trait.
+    /// See [`Distances`](../../math/distance/struct.Distances.html) for a list of available functions.
+    pub fn with_distance<DD>(self, distance: DD) -> KMeansParameters<DD> {
+        KMeansParameters {
+            k: self.k,
+            max_iter: self.max_iter,
+            seed: self.seed,
+            n_init: self.n_init,
+            distance,
+            algorithm: self.algorithm,
+        }
+    }
+    /// Algorithm used to assign points to clusters.
+    pub fn with_algorithm(mut self, algorithm: KMeansAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
 }
 
 impl Default for KMeansParameters {
@@ -137,6 +208,9 @@ impl Default for KMeansParameters {
             k: 2,
             max_iter: 100,
             seed: None,
+            n_init: 1,
+            distance: Distances::euclidian(),
+            algorithm: KMeansAlgorithm::default(),
         }
     }
 }
@@ -144,7 +218,7 @@ impl Default for KMeansParameters {
 /// KMeans grid search parameters
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct KMeansSearchParameters {
+pub struct KMeansSearchParameters<D = Euclidian> {
     #[cfg_attr(feature = "serde", serde(default))]
     /// Number of clusters.
     pub k: Vec<usize>,
@@ -155,19 +229,31 @@ pub struct KMeansSearchParameters {
     /// Determines random number generation for centroid initialization.
     /// Use an int to make the randomness deterministic
     pub seed: Vec<Option<u64>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of times the k-means algorithm is run with different centroid seeds.
+    pub n_init: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// A function that defines a distance between a point and a centroid.
+    pub distance: Vec<D>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Algorithm used to assign points to clusters.
+    pub algorithm: Vec<KMeansAlgorithm>,
 }
 
 /// KMeans grid search iterator
-pub struct KMeansSearchParametersIterator {
-    kmeans_search_parameters: KMeansSearchParameters,
+pub struct KMeansSearchParametersIterator<D = Euclidian> {
+    kmeans_search_parameters: KMeansSearchParameters<D>,
     current_k: usize,
     current_max_iter: usize,
     current_seed: usize,
+    current_n_init: usize,
+    current_distance: usize,
+    current_algorithm: usize,
 }
 
-impl IntoIterator for KMeansSearchParameters {
-    type Item = KMeansParameters;
-    type IntoIter = KMeansSearchParametersIterator;
+impl<D: Clone> IntoIterator for KMeansSearchParameters<D> {
+    type Item = KMeansParameters<D>;
+    type IntoIter = KMeansSearchParametersIterator<D>;
 
     fn into_iter(self) -> Self::IntoIter {
         KMeansSearchParametersIterator {
@@ -175,17 +261,23 @@ impl IntoIterator for KMeansSearchParameters {
             current_k: 0,
             current_max_iter: 0,
             current_seed: 0,
+            current_n_init: 0,
+            current_distance: 0,
+            current_algorithm: 0,
         }
     }
 }
 
-impl Iterator for KMeansSearchParametersIterator {
-    type Item = KMeansParameters;
+impl<D: Clone> Iterator for KMeansSearchParametersIterator<D> {
+    type Item = KMeansParameters<D>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.current_k == self.kmeans_search_parameters.k.len()
             && self.current_max_iter == self.kmeans_search_parameters.max_iter.len()
             && self.current_seed == self.kmeans_search_parameters.seed.len()
+            && self.current_n_init == self.kmeans_search_parameters.n_init.len()
+            && self.current_distance == self.kmeans_search_parameters.distance.len()
+            && self.current_algorithm == self.kmeans_search_parameters.algorithm.len()
         {
             return None;
         }
@@ -194,6 +286,9 @@ impl Iterator for KMeansSearchParametersIterator {
             k: self.kmeans_search_parameters.k[self.current_k],
             max_iter: self.kmeans_search_parameters.max_iter[self.current_max_iter],
             seed: self.kmeans_search_parameters.seed[self.current_seed],
+            n_init: self.kmeans_search_parameters.n_init[self.current_n_init],
+            distance: self.kmeans_search_parameters.distance[self.current_distance].clone(),
+            algorithm: self.kmeans_search_parameters.algorithm[self.current_algorithm].clone(),
         };
 
         if self.current_k + 1 < self.kmeans_search_parameters.k.len() {
@@ -205,10 +300,31 @@ impl Iterator for KMeansSearchParametersIterator {
             self.current_k = 0;
             self.current_max_iter = 0;
             self.current_seed += 1;
+        } else if self.current_n_init + 1 < self.kmeans_search_parameters.n_init.len() {
+            self.current_k = 0;
+            self.current_max_iter = 0;
+            self.current_seed = 0;
+            self.current_n_init += 1;
+        } else if self.current_distance + 1 < self.kmeans_search_parameters.distance.len() {
+            self.current_k = 0;
+            self.current_max_iter = 0;
+            self.current_seed = 0;
+            self.current_n_init = 0;
+            self.current_distance += 1;
+        } else if self.current_algorithm + 1 < self.kmeans_search_parameters.algorithm.len() {
+            self.current_k = 0;
+            self.current_max_iter = 0;
+            self.current_seed = 0;
+            self.current_n_init = 0;
+            self.current_distance = 0;
+            self.current_algorithm += 1;
         } else {
             self.current_k += 1;
             self.current_max_iter += 1;
             self.current_seed += 1;
+            self.current_n_init += 1;
+            self.current_distance += 1;
+            self.current_algorithm += 1;
         }
 
         Some(next)
@@ -223,29 +339,42 @@ impl Default for KMeansSearchParameters {
             k: vec![default_params.k],
             max_iter: vec![default_params.max_iter],
             seed: vec![default_params.seed],
+            n_init: vec![default_params.n_init],
+            distance: vec![default_params.distance],
+            algorithm: vec![default_params.algorithm],
         }
     }
 }
 
-impl<T: RealNumber + Sum, M: Matrix<T>> UnsupervisedEstimator<M, KMeansParameters> for KMeans<T> {
-    fn fit(x: &M, parameters: KMeansParameters) -> Result<Self, Failed> {
+impl<T: RealNumber + Sum, M: Matrix<T>, D: Distance<[T], T>>
+    UnsupervisedEstimator<M, KMeansParameters<D>> for KMeans<T, D>
+{
+    fn fit(x: &M, parameters: KMeansParameters<D>) -> Result<Self, Failed> {
         KMeans::fit(x, parameters)
     }
 }
 
-impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for KMeans<T> {
+impl<T: RealNumber, M: Matrix<T>, D: Distance<[T], T>> Predictor<M, M::RowVector> for KMeans<T, D> {
     fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
         self.predict(x)
     }
 }
 
-impl<T: RealNumber + Sum> KMeans<T> {
+impl<T: RealNumber, M: Matrix<T>, D: Distance<[T], T>> Transformer<M> for KMeans<T, D> {
+    fn transform(&self, x: &M) -> Result<M, Failed> {
+        self.transform(x)
+    }
+}
+
+impl<T: RealNumber + Sum, D: Distance<[T], T>> KMeans<T, D> {
     /// Fit algorithm to _NxM_ matrix where _N_ is number of samples and _M_ is number of features.
     /// * `data` - training instances to cluster    
     /// * `parameters` - cluster parameters
-    pub fn fit<M: Matrix<T>>(data: &M, parameters: KMeansParameters) -> Result<KMeans<T>, Failed> {
-        let bbd = BBDTree::new(data);
-
+    pub fn fit<M: Matrix<T>>(
+        data: &M,
+        parameters: KMeansParameters<D>,
+    ) -> Result<KMeans<T, D>, Failed> {
+        check_x(data, false, Failed::fit)?;
         if parameters.k < 2 {
             return Err(Failed::fit(&format!(
                 "invalid number of clusters: {}",
@@ -260,12 +389,78 @@ impl<T: RealNumber + Sum> KMeans<T> {
             )));
         }
 
+        if parameters.n_init == 0 {
+            return Err(Failed::fit(&format!(
+                "invalid number of initializations: {}",
+                parameters.n_init
+            )));
+        }
+
+        // BBD tree filtering relies on the geometry of the Euclidian distance
+        let bbd = if parameters.algorithm == KMeansAlgorithm::BBDTree
+            && parameters.distance.is_euclidian()
+        {
+            Some(BBDTree::new(data))
+        } else {
+            None
+        };
+
+        let mut best: Option<(Vec<usize>, Vec<usize>, T, Vec<Vec<T>>)> = None;
+        for run in 0..parameters.n_init {
+            let seed = parameters.seed.map(|seed| seed.wrapping_add(run as u64));
+            let (y, size, distortion, centroids) = KMeans::fit_once(
+                data,
+                bbd.as_ref(),
+                parameters.algorithm == KMeansAlgorithm::Elkan,
+                &parameters.distance,
+                parameters.k,
+                parameters.max_iter,
+                seed,
+            );
+            info!(
+                "KMeans run {}/{}: distortion {}",
+                run + 1,
+                parameters.n_init,
+                distortion
+            );
+            let improves = match best {
+                Some((_, _, best_distortion, _)) => distortion < best_distortion,
+                None => true,
+            };
+            if improves {
+                best = Some((y, size, distortion, centroids));
+            }
+        }
+        let (y, size, distortion, centroids) = best.unwrap();
+
+        Ok(KMeans {
+            k: parameters.k,
+            y,
+            size,
+            distortion,
+            centroids,
+            distance: parameters.distance,
+        })
+    }
+
+    /// Runs k-means once, starting from k-means++ centroids. Returns cluster labels, cluster sizes, distortion and centroids.
+    /// Assignments are computed with Elkan's algorithm when `elkan` is set, with the BBD tree when it is given
+    /// and by comparing every point with every centroid otherwise.
+    fn fit_once<M: Matrix<T>>(
+        data: &M,
+        bbd: Option<&BBDTree<T>>,
+        elkan: bool,
+        distance: &D,
+        k: usize,
+        max_iter: usize,
+        seed: Option<u64>,
+    ) -> (Vec<usize>, Vec<usize>, T, Vec<Vec<T>>) {
         let (n, d) = data.shape();
 
         let mut distortion = T::max_value();
-        let mut y = KMeans::kmeans_plus_plus(data, parameters.k, parameters.seed);
-        let mut size = vec![0; parameters.k];
-        let mut centroids = vec![vec![T::zero(); d]; parameters.k];
+        let mut y = KMeans::kmeans_plus_plus(data, distance, k, seed);
+        let mut size = vec![0; k];
+        let mut centroids = vec![vec![T::zero(); d]; k];
 
         for i in 0..n {
             size[y[i]] += 1;
@@ -277,66 +472,307 @@ impl<T: RealNumber + Sum> KMeans<T> {
             }
         }
 
-        for i in 0..parameters.k {
+        for i in 0..k {
             for j in 0..d {
                 centroids[i][j] /= T::from(size[i]).unwrap();
             }
         }
 
-        let mut sums = vec![vec![T::zero(); d]; parameters.k];
-        for _ in 1..=parameters.max_iter {
-            let dist = bbd.clustering(&centroids, &mut sums, &mut size, &mut y);
-            for i in 0..parameters.k {
-                if size[i] > 0 {
-                    for j in 0..d {
-                        centroids[i][j] = T::from(sums[i][j]).unwrap() / T::from(size[i]).unwrap();
+        let mut sums = vec![vec![T::zero(); d]; k];
+        let mut row = vec![T::zero(); d];
+        if elkan {
+            KMeans::elkan(data, distance, &mut centroids, &mut y, max_iter);
+        } else {
+            for iteration in 1..=max_iter {
+                let dist = match bbd {
+                    Some(bbd) => bbd.clustering(&centroids, &mut sums, &mut size, &mut y),
+                    None => {
+                        let mut dist = T::zero();
+                        for (sums_i, size_i) in sums.iter_mut().zip(size.iter_mut()) {
+                            sums_i.iter_mut().for_each(|s| *s = T::zero());
+                            *size_i = 0;
+                        }
+                        for (i, y_i) in y.iter_mut().enumerate() {
+                            data.copy_row_as_vec(i, &mut row);
+                            let (best_cluster, min_dist) =
+                                KMeans::closest_centroid(distance, &centroids, &row);
+                            *y_i = best_cluster;
+                            dist += min_dist;
+                            size[best_cluster] += 1;
+                            for j in 0..d {
+                                sums[best_cluster][j] += row[j];
+                            }
+                        }
+                        dist
+                    }
+                };
+                for i in 0..k {
+                    if size[i] > 0 {
+                        for j in 0..d {
+                            centroids[i][j] =
+                                T::from(sums[i][j]).unwrap() / T::from(size[i]).unwrap();
+                        }
                     }
                 }
+
+                debug!("KMeans iteration {}: distortion {}", iteration, dist);
+                if distortion <= dist {
+                    break;
+                } else {
+                    distortion = dist;
+                }
             }
+        }
 
-            if distortion <= dist {
-                break;
-            } else {
-                distortion = dist;
+        // assign every point to its closest final centroid, so that labels, sizes and distortion agree with the centroids
+        distortion = T::zero();
+        size.iter_mut().for_each(|size_i| *size_i = 0);
+        for (i, y_i) in y.iter_mut().enumerate() {
+            data.copy_row_as_vec(i, &mut row);
+            let (best_cluster, min_dist) = KMeans::closest_centroid(distance, &centroids, &row);
+            *y_i = best_cluster;
+            size[best_cluster] += 1;
+            distortion += min_dist;
+        }
+
+        (y, size, distortion, centroids)
+    }
+
+    /// Elkan's accelerated Lloyd iterations. Keeps an upper bound on the distance of every point to its centroid and
+    /// a lower bound on its distance to every other centroid, and only computes the distances the bounds can not rule out.
+    fn elkan<M: Matrix<T>>(
+        data: &M,
+        distance: &D,
+        centroids: &mut [Vec<T>],
+        y: &mut [usize],
+        max_iter: usize,
+    ) {
+        let (n, d) = data.shape();
+        let k = centroids.len();
+        let rows: Vec<Vec<T>> = (0..n).map(|i| data.get_row_as_vec(i)).collect();
+
+        let mut lower = vec![vec![T::zero(); k]; n];
+        let mut upper = vec![T::zero(); n];
+        for i in 0..n {
+            for j in 0..k {
+                lower[i][j] = distance.distance(&rows[i], &centroids[j]);
+                if lower[i][j] < lower[i][y[i]] {
+                    y[i] = j;
+                }
             }
+            upper[i] = lower[i][y[i]];
         }
 
-        Ok(KMeans {
-            k: parameters.k,
-            _y: y,
-            size,
-            _distortion: distortion,
-            centroids,
-        })
+        // half of the distance between every pair of centroids
+        let mut half_distances = vec![vec![T::zero(); k]; k];
+        let mut closest_half_distance = vec![T::zero(); k];
+        let mut sums = vec![vec![T::zero(); d]; k];
+        let mut size = vec![0; k];
+        let mut shift = vec![T::zero(); k];
+
+        for iteration in 1..=max_iter {
+            for j in 0..k {
+                sums[j].iter_mut().for_each(|s| *s = T::zero());
+                size[j] = 0;
+            }
+            for i in 0..n {
+                size[y[i]] += 1;
+                for l in 0..d {
+                    sums[y[i]][l] += rows[i][l];
+                }
+            }
+            for j in 0..k {
+                if size[j] > 0 {
+                    let mean: Vec<T> = sums[j]
+                        .iter()
+                        .map(|s| *s / T::from(size[j]).unwrap())
+                        .collect();
+                    shift[j] = distance.distance(&centroids[j], &mean);
+                    centroids[j] = mean;
+                } else {
+                    shift[j] = T::zero();
+                }
+            }
+
+            debug!(
+                "KMeans iteration {}: largest centroid shift {}",
+                iteration,
+                shift.iter().fold(T::zero(), |max, s| max.max(*s))
+            );
+            if shift.iter().all(|s| *s == T::zero()) {
+                break;
+            }
+
+            for i in 0..n {
+                for j in 0..k {
+                    lower[i][j] = (lower[i][j] - shift[j]).max(T::zero());
+                }
+                upper[i] += shift[y[i]];
+            }
+
+            for j in 0..k {
+                for l in (j + 1)..k {
+                    let half_distance = distance.distance(&centroids[j], &centroids[l]) * T::half();
+                    half_distances[j][l] = half_distance;
+                    half_distances[l][j] = half_distance;
+                }
+            }
+            for j in 0..k {
+                closest_half_distance[j] = (0..k)
+                    .filter(|l| *l != j)
+                    .fold(T::max_value(), |acc, l| acc.min(half_distances[j][l]));
+            }
+
+            for i in 0..n {
+                if upper[i] <= closest_half_distance[y[i]] {
+                    continue;
+                }
+                let mut tight = false;
+                for j in 0..k {
+                    if j == y[i] || upper[i] <= lower[i][j] || upper[i] <= half_distances[y[i]][j] {
+                        continue;
+                    }
+                    if !tight {
+                        upper[i] = distance.distance(&rows[i], &centroids[y[i]]);
+                        lower[i][y[i]] = upper[i];
+                        tight = true;
+                        if upper[i] <= lower[i][j] || upper[i] <= half_distances[y[i]][j] {
+                            continue;
+                        }
+                    }
+                    lower[i][j] = distance.distance(&rows[i], &centroids[j]);
+                    if lower[i][j] < upper[i] {
+                        y[i] = j;
+                        upper[i] = lower[i][j];
+                    }
+                }
+            }
+        }
     }
 
     /// Predict clusters for `x`
     /// * `x` - matrix with new data to transform of size _KxM_ , where _K_ is number of new samples and _M_ is number of features.
     pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
         let (n, m) = x.shape();
         let mut result = M::zeros(1, n);
 
         let mut row = vec![T::zero(); m];
 
         for i in 0..n {
-            let mut min_dist = T::max_value();
-            let mut best_cluster = 0;
-
-            for j in 0..self.k {
-                x.copy_row_as_vec(i, &mut row);
-                let dist = Euclidian::squared_distance(&row, &self.centroids[j]);
-                if dist < min_dist {
-                    min_dist = dist;
-                    best_cluster = j;
-                }
-            }
+            x.copy_row_as_vec(i, &mut row);
+            let (best_cluster, _) = KMeans::closest_centroid(&self.distance, &self.centroids, &row);
             result.set(0, i, T::from(best_cluster).unwrap());
         }
 
         Ok(result.to_row_vector())
     }
 
-    fn kmeans_plus_plus<M: Matrix<T>>(data: &M, k: usize, seed: Option<u64>) -> Vec<usize> {
+    /// Transforms `x` to the cluster-distance space: returns _KxC_ matrix with the distance of every sample to every one of the _C_ centroids.
+    /// * `x` - matrix with new data to transform of size _KxM_ , where _K_ is number of new samples and _M_ is number of features.
+    pub fn transform<M: Matrix<T>>(&self, x: &M) -> Result<M, Failed> {
+        check_transform(x)?;
+        let (n, m) = x.shape();
+        self.check_features(m)?;
+        let mut result = M::zeros(n, self.k);
+
+        let mut row = vec![T::zero(); m];
+
+        for i in 0..n {
+            x.copy_row_as_vec(i, &mut row);
+            for (j, centroid) in self.centroids.iter().enumerate() {
+                result.set(i, j, self.distance.distance(&row, centroid));
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Inertia of new data: sum of squared distances of the samples in `x` to their closest centroid.
+    /// * `x` - matrix with new data of size _KxM_ , where _K_ is number of new samples and _M_ is number of features.
+    pub fn score<M: Matrix<T>>(&self, x: &M) -> Result<T, Failed> {
+        check_predict(x)?;
+        let (n, m) = x.shape();
+        self.check_features(m)?;
+
+        let mut row = vec![T::zero(); m];
+        let mut inertia = T::zero();
+
+        for i in 0..n {
+            x.copy_row_as_vec(i, &mut row);
+            inertia += KMeans::closest_centroid(&self.distance, &self.centroids, &row).1;
+        }
+
+        Ok(inertia)
+    }
+
+    fn check_features(&self, m: usize) -> Result<(), Failed> {
+        if self.centroids[0].len() != m {
+            return Err(Failed::predict(&format!(
+                "Number of features of x ({}) does not match the number of features of the centroids ({})",
+                m,
+                self.centroids[0].len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Number of clusters.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Cluster centers, one vector of _M_ coordinates per cluster.
+    pub fn centroids(&self) -> &Vec<Vec<T>> {
+        &self.centroids
+    }
+
+    /// Index of the cluster of every training instance.
+    pub fn labels(&self) -> &Vec<usize> {
+        &self.y
+    }
+
+    /// Number of training instances in every cluster.
+    pub fn cluster_sizes(&self) -> &Vec<usize> {
+        &self.size
+    }
+
+    /// Sum of squared distances of the training instances to their closest centroid.
+    pub fn inertia(&self) -> T {
+        self.distortion
+    }
+
+    /// Squared distance between two points, the quantity minimized by k-means.
+    fn squared_distance(distance: &D, x: &[T], y: &[T]) -> T {
+        if distance.is_euclidian() {
+            Euclidian::squared_distance(x, y)
+        } else {
+            distance.distance(x, y).square()
+        }
+    }
+
+    /// Returns the index of the centroid closest to `row` and the squared distance to it.
+    fn closest_centroid(distance: &D, centroids: &[Vec<T>], row: &[T]) -> (usize, T) {
+        let mut min_dist = T::max_value();
+        let mut best_cluster = 0;
+
+        for (j, centroid) in centroids.iter().enumerate() {
+            let dist = KMeans::<T, D>::squared_distance(distance, row, centroid);
+            if dist < min_dist {
+                min_dist = dist;
+                best_cluster = j;
+            }
+        }
+
+        (best_cluster, min_dist)
+    }
+
+    pub(crate) fn kmeans_plus_plus<M: Matrix<T>>(
+        data: &M,
+        distance: &D,
+        k: usize,
+        seed: Option<u64>,
+    ) -> Vec<usize> {
         let mut rng = get_rng_impl(seed);
         let (n, m) = data.shape();
         let mut y = vec![0; n];
@@ -349,7 +785,7 @@ impl<T: RealNumber + Sum> KMeans<T> {
         for j in 1..k {
             for i in 0..n {
                 data.copy_row_as_vec(i, &mut row);
-                let dist = Euclidian::squared_distance(&row, &centroid);
+                let dist = KMeans::<T, D>::squared_distance(distance, &row, &centroid);
 
                 if dist < d[i] {
                     d[i] = dist;
@@ -377,7 +813,7 @@ impl<T: RealNumber + Sum> KMeans<T> {
 
         for i in 0..n {
             data.copy_row_as_vec(i, &mut row);
-            let dist = Euclidian::squared_distance(&row, &centroid);
+            let dist = KMeans::<T, D>::squared_distance(distance, &row, &centroid);
 
             if dist < d[i] {
                 d[i] = dist;
@@ -389,10 +825,35 @@ impl<T: RealNumber + Sum> KMeans<T> {
     }
 }
 
+/// Only models with the Euclidean distance can be written to PMML.
+#[cfg(feature = "pmml")]
+impl<T: RealNumber> ToPmml for KMeans<T> {
+    fn to_pmml(&self) -> Result<String, Failed> {
+        Ok(cluster::clustering_model(&self.centroids, &self.size))
+    }
+}
+
+/// The labels and the inertia of the training samples are not part of PMML, imported models have no labels and zero inertia.
+#[cfg(feature = "pmml")]
+impl<T: RealNumber> FromPmml for KMeans<T> {
+    fn from_pmml(pmml: &str) -> Result<Self, Failed> {
+        let (centroids, size) = cluster::read_clustering_model(pmml)?;
+        Ok(KMeans {
+            k: centroids.len(),
+            y: Vec::new(),
+            size,
+            distortion: T::zero(),
+            centroids,
+            distance: Euclidian {},
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::linalg::BaseMatrix;
 
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     #[test]
@@ -462,8 +923,194 @@ mod tests {
         let y = kmeans.predict(&x).unwrap();
 
         for i in 0..y.len() {
-            assert_eq!(y[i] as usize, kmeans._y[i]);
+            assert_eq!(y[i] as usize, kmeans.labels()[i]);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn n_init() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 1.],
+            &[1.5, 2.],
+            &[3., 4.],
+            &[5., 7.],
+            &[3.5, 5.],
+            &[4.5, 5.],
+            &[3.5, 4.5],
+            &[9., 1.],
+            &[9.5, 1.5],
+            &[8., 2.],
+        ]);
+
+        let parameters = KMeansParameters::default().with_k(3).with_seed(7);
+        let kmeans = KMeans::fit(&x, parameters.clone().with_n_init(10)).unwrap();
+
+        for seed in 7..17 {
+            let single_run = KMeans::fit(&x, parameters.clone().with_seed(seed)).unwrap();
+            assert!(kmeans.inertia() <= single_run.inertia());
+        }
+        assert_eq!(
+            kmeans,
+            KMeans::fit(&x, parameters.clone().with_n_init(10)).unwrap()
+        );
+        assert!(KMeans::fit(&x, parameters.with_n_init(0)).is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn accessors() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 1.],
+            &[1.5, 2.],
+            &[1., 1.5],
+            &[8., 8.],
+            &[9., 8.5],
+            &[8.5, 9.],
+            &[9., 9.],
+        ]);
+
+        let kmeans: KMeans<f64> =
+            KMeans::fit(&x, KMeansParameters::default().with_seed(1)).unwrap();
+
+        assert_eq!(kmeans.k(), 2);
+        assert_eq!(kmeans.labels().len(), 7);
+        assert_eq!(kmeans.centroids().len(), 2);
+        let mut sizes = kmeans.cluster_sizes().clone();
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![3, 4]);
+
+        let big = kmeans.labels()[3];
+        assert!((kmeans.centroids()[big][0] - 8.625).abs() < 1e-8);
+        assert!((kmeans.centroids()[big][1] - 8.625).abs() < 1e-8);
+
+        let mut inertia = 0.;
+        for i in 0..7 {
+            let centroid = &kmeans.centroids()[kmeans.labels()[i]];
+            inertia += Euclidian::squared_distance(&x.get_row_as_vec(i), centroid);
+        }
+        assert!((kmeans.inertia() - inertia).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn elkan() {
+        let mut values = Vec::new();
+        for i in 0..150 {
+            let center = (i % 3) as f64 * 4.;
+            let jitter = ((i * 31) % 17) as f64 / 8.;
+            values.push(vec![
+                center + jitter,
+                center - jitter / 2.,
+                (i % 5) as f64 / 4.,
+            ]);
+        }
+        let x = DenseMatrix::from_2d_vec(&values);
+
+        let parameters = KMeansParameters::default().with_k(3).with_seed(3);
+        let bbd: KMeans<f64> = KMeans::fit(&x, parameters.clone()).unwrap();
+        let lloyd: KMeans<f64> = KMeans::fit(
+            &x,
+            parameters.clone().with_algorithm(KMeansAlgorithm::Lloyd),
+        )
+        .unwrap();
+        let elkan: KMeans<f64> =
+            KMeans::fit(&x, parameters.with_algorithm(KMeansAlgorithm::Elkan)).unwrap();
+
+        assert_eq!(elkan.labels(), lloyd.labels());
+        assert_eq!(elkan.labels(), bbd.labels());
+        assert!((elkan.inertia() - lloyd.inertia()).abs() < 1e-8);
+        assert_eq!(elkan.predict(&x).unwrap(), lloyd.predict(&x).unwrap());
+
+        let manhattan = KMeans::fit(
+            &x,
+            KMeansParameters::default()
+                .with_k(3)
+                .with_seed(3)
+                .with_distance(Distances::manhattan())
+                .with_algorithm(KMeansAlgorithm::Elkan),
+        )
+        .unwrap();
+        assert_eq!(manhattan.cluster_sizes().iter().sum::<usize>(), 150);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn transform_and_score() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 1.],
+            &[1.5, 2.],
+            &[1., 1.5],
+            &[8., 8.],
+            &[9., 8.5],
+            &[8.5, 9.],
+            &[9., 9.],
+        ]);
+
+        let kmeans: KMeans<f64> =
+            KMeans::fit(&x, KMeansParameters::default().with_seed(1)).unwrap();
+        let distances = kmeans.transform(&x).unwrap();
+
+        assert_eq!(distances.shape(), (7, 2));
+        let y = kmeans.predict(&x).unwrap();
+        let mut inertia = 0.;
+        for i in 0..7 {
+            let closest = y[i] as usize;
+            let other = 1 - closest;
+            assert!(distances.get(i, closest) < distances.get(i, other));
+            let expected: f64 =
+                Euclidian::squared_distance(&x.get_row_as_vec(i), &kmeans.centroids()[closest]);
+            assert!((distances.get(i, closest) - expected.sqrt()).abs() < 1e-8);
+            inertia += expected;
+        }
+        assert!((kmeans.score(&x).unwrap() - inertia).abs() < 1e-8);
+        assert!((kmeans.score(&x).unwrap() - kmeans.inertia()).abs() < 1e-8);
+
+        let new_x = DenseMatrix::from_2d_array(&[&[1., 1., 1.]]);
+        assert!(kmeans.transform(&new_x).is_err());
+        assert!(kmeans.score(&new_x).is_err());
+
+        let missing_x = DenseMatrix::from_2d_array(&[&[1., f64::NAN]]);
+        assert_eq!(
+            kmeans.score(&missing_x).unwrap_err(),
+            Failed::predict(
+                "x contains NaN at row 0, column 1, impute missing values or use an estimator that supports them"
+            )
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn custom_distance() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 1.],
+            &[1.5, 2.],
+            &[1., 1.5],
+            &[2., 1.],
+            &[8., 8.],
+            &[9., 8.5],
+            &[8.5, 9.],
+            &[9., 9.],
+        ]);
+
+        let kmeans = KMeans::fit(
+            &x,
+            KMeansParameters::default()
+                .with_distance(Distances::manhattan())
+                .with_seed(1),
+        )
+        .unwrap();
+        let y = kmeans.predict(&x).unwrap();
+
+        for i in 0..y.len() {
+            assert_eq!(y[i] as usize, kmeans.labels()[i]);
         }
+        assert_eq!(kmeans.cluster_sizes(), &vec![4, 4]);
+        assert_eq!(y[0], y[3]);
+        assert_ne!(y[0], y[4]);
+
+        let euclidian = KMeans::fit(&x, KMeansParameters::default().with_seed(1)).unwrap();
+        assert_eq!(euclidian.predict(&x).unwrap(), y);
     }
 
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
@@ -499,5 +1146,7 @@ mod tests {
             serde_json::from_str(&serde_json::to_string(&kmeans).unwrap()).unwrap();
 
         assert_eq!(kmeans, deserialized_kmeans);
+        assert_eq!(kmeans.labels(), deserialized_kmeans.labels());
+        assert!((kmeans.inertia() - deserialized_kmeans.inertia()).abs() < 1e-8);
     }
 }
@@ -0,0 +1,408 @@
+//! # Mini-Batch K-Means Clustering
+//!
+//! [K-means](../kmeans/index.html) goes over the whole dataset in every iteration. Mini-batch k-means updates the centroids from small batches
+//! of observations instead, which is much faster on large datasets at the cost of a slightly higher inertia. Since every update needs a single batch only,
+//! the model can also learn from data that doesn't fit in memory or arrives over time: feed the batches one by one to `partial_fit`,
+//! or let `fit` split a matrix into shuffled batches of `batch_size` rows.
+//!
+//! Every observation of a batch is assigned to its closest centroid, then the centroid moves towards the observation by a step of \\(1 / n_c\\),
+//! where \\(n_c\\) is the number of observations assigned to the centroid so far. This way every centroid is the running mean of the observations assigned to it.
+//! The centroids are initialized with k-means++ from the first batch, which should have at least _k_ observations.
+//! Similarity of two points is determined by the [Euclidian Distance](../../math/distance/euclidian/index.html) between them.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::cluster::mini_batch_kmeans::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!            &[5.1, 3.5, 1.4, 0.2],
+//!            &[4.9, 3.0, 1.4, 0.2],
+//!            &[4.7, 3.2, 1.3, 0.2],
+//!            &[4.6, 3.1, 1.5, 0.2],
+//!            &[7.0, 3.2, 4.7, 1.4],
+//!            &[6.4, 3.2, 4.5, 1.5],
+//!            &[6.9, 3.1, 4.9, 1.5],
+//!            &[5.5, 2.3, 4.0, 1.3],
+//!            ]);
+//!
+//! let mut kmeans = MiniBatchKMeans::new(MiniBatchKMeansParameters::default().with_k(2).with_seed(42));
+//! // batches could be read from disk one by one
+//! kmeans.partial_fit(&x.slice(0..4, 0..4)).unwrap();
+//! kmeans.partial_fit(&x.slice(4..8, 0..4)).unwrap();
+//!
+//! let y_hat = kmeans.predict(&x).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Web-Scale K-Means Clustering", Sculley D., 2010](https://www.eecs.tufts.edu/~dsculley/papers/fastkmeans.pdf)
+//! * ["k-means++: The Advantages of Careful Seeding", Arthur D., Vassilvitskii S.](http://ilpubs.stanford.edu:8090/778/1/2006-13.pdf)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use rand::seq::SliceRandom;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, UnsupervisedEstimator, UnsupervisedOnlineEstimator};
+use crate::cluster::kmeans::KMeans;
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::distance::euclidian::Euclidian;
+use crate::math::num::RealNumber;
+use crate::rand::get_rng_impl;
+use crate::validation::{check_predict, check_x};
+
+/// Mini-batch k-means parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MiniBatchKMeansParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of clusters.
+    pub k: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of rows per batch used by `fit`.
+    pub batch_size: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of passes over the data made by `fit`.
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Determines random number generation for centroid initialization and for the batches of `fit`.
+    /// Use an int to make the randomness deterministic
+    pub seed: Option<u64>,
+}
+
+impl MiniBatchKMeansParameters {
+    /// Number of clusters.
+    pub fn with_k(mut self, k: usize) -> Self {
+        self.k = k;
+        self
+    }
+    /// Number of rows per batch used by `fit`.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+    /// Number of passes over the data made by `fit`.
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Determines random number generation for centroid initialization and for the batches of `fit`.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl Default for MiniBatchKMeansParameters {
+    fn default() -> Self {
+        MiniBatchKMeansParameters {
+            k: 2,
+            batch_size: 1024,
+            max_iter: 10,
+            seed: None,
+        }
+    }
+}
+
+/// Mini-batch k-means clustering algorithm
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct MiniBatchKMeans<T: RealNumber> {
+    parameters: MiniBatchKMeansParameters,
+    centroids: Vec<Vec<T>>,
+    size: Vec<usize>,
+}
+
+impl<T: RealNumber> PartialEq for MiniBatchKMeans<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size
+            && self.centroids.len() == other.centroids.len()
+            && self
+                .centroids
+                .iter()
+                .zip(other.centroids.iter())
+                .all(|(a, b)| {
+                    a.len() == b.len()
+                        && a.iter()
+                            .zip(b.iter())
+                            .all(|(a, b)| (*a - *b).abs() <= T::epsilon())
+                })
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> UnsupervisedEstimator<M, MiniBatchKMeansParameters>
+    for MiniBatchKMeans<T>
+{
+    fn fit(x: &M, parameters: MiniBatchKMeansParameters) -> Result<Self, Failed> {
+        MiniBatchKMeans::fit(x, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> UnsupervisedOnlineEstimator<M> for MiniBatchKMeans<T> {
+    fn partial_fit(&mut self, x: &M) -> Result<(), Failed> {
+        self.partial_fit(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for MiniBatchKMeans<T> {
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<T: RealNumber> MiniBatchKMeans<T> {
+    /// Creates a model that hasn't seen any observations yet, feed it with `partial_fit`.
+    pub fn new(parameters: MiniBatchKMeansParameters) -> MiniBatchKMeans<T> {
+        MiniBatchKMeans {
+            parameters,
+            centroids: Vec::new(),
+            size: Vec::new(),
+        }
+    }
+
+    /// Fit algorithm to _NxM_ matrix where _N_ is number of samples and _M_ is number of features,
+    /// with `max_iter` passes over shuffled batches of `batch_size` rows.
+    /// * `data` - training instances to cluster
+    /// * `parameters` - cluster parameters
+    pub fn fit<M: Matrix<T>>(
+        data: &M,
+        parameters: MiniBatchKMeansParameters,
+    ) -> Result<MiniBatchKMeans<T>, Failed> {
+        check_x(data, false, Failed::fit)?;
+        if parameters.batch_size == 0 {
+            return Err(Failed::fit("batch_size should be greater than 0"));
+        }
+        if parameters.max_iter == 0 {
+            return Err(Failed::fit(&format!(
+                "invalid maximum number of iterations: {}",
+                parameters.max_iter
+            )));
+        }
+
+        let mut rng = get_rng_impl(parameters.seed);
+        let mut kmeans = MiniBatchKMeans::new(parameters);
+        kmeans.initialize(data)?;
+
+        let (n, _) = data.shape();
+        let mut order: Vec<usize> = (0..n).collect();
+        for _ in 0..kmeans.parameters.max_iter {
+            order.shuffle(&mut rng);
+            for batch in order.chunks(kmeans.parameters.batch_size) {
+                let rows: Vec<Vec<T>> = batch.iter().map(|i| data.get_row_as_vec(*i)).collect();
+                kmeans.update(&rows);
+            }
+        }
+
+        Ok(kmeans)
+    }
+
+    /// Updates the centroids with a batch of observations, the centroids of a new model are initialized from the batch.
+    /// * `x` - _KxM_ batch with _K_ observations and _M_ features in each observation.
+    pub fn partial_fit<M: Matrix<T>>(&mut self, x: &M) -> Result<(), Failed> {
+        check_x(x, false, Failed::fit)?;
+        if self.centroids.is_empty() {
+            self.initialize(x)?;
+        } else {
+            self.check_features(x.shape().1, Failed::fit)?;
+        }
+        let (n, _) = x.shape();
+        let rows: Vec<Vec<T>> = (0..n).map(|i| x.get_row_as_vec(i)).collect();
+        self.update(&rows);
+        Ok(())
+    }
+
+    /// Predict clusters for `x`
+    /// * `x` - matrix with new data to transform of size _KxM_ , where _K_ is number of new samples and _M_ is number of features.
+    pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
+        if self.centroids.is_empty() {
+            return Err(Failed::predict(
+                "The model hasn't seen any observations, call partial_fit first",
+            ));
+        }
+        let (n, m) = x.shape();
+        self.check_features(m, Failed::predict)?;
+
+        let mut result = M::RowVector::zeros(n);
+        let mut row = vec![T::zero(); m];
+        for i in 0..n {
+            x.copy_row_as_vec(i, &mut row);
+            result.set(i, T::from_usize(self.closest_centroid(&row)).unwrap());
+        }
+        Ok(result)
+    }
+
+    /// Number of clusters.
+    pub fn k(&self) -> usize {
+        self.parameters.k
+    }
+
+    /// Cluster centers, one vector of _M_ coordinates per cluster.
+    pub fn centroids(&self) -> &Vec<Vec<T>> {
+        &self.centroids
+    }
+
+    /// Number of training instances assigned to every cluster so far.
+    pub fn cluster_sizes(&self) -> &Vec<usize> {
+        &self.size
+    }
+
+    /// Chooses the initial centroids among the rows of `data` with k-means++, every centroid is the mean of the rows closest to it.
+    fn initialize<M: Matrix<T>>(&mut self, data: &M) -> Result<(), Failed> {
+        let k = self.parameters.k;
+        let (n, d) = data.shape();
+        if k < 2 {
+            return Err(Failed::fit(&format!("invalid number of clusters: {}", k)));
+        }
+        if n < k {
+            return Err(Failed::fit(&format!(
+                "The first batch should have at least k = {} observations, found {}",
+                k, n
+            )));
+        }
+
+        let y = KMeans::<T>::kmeans_plus_plus(data, &Euclidian {}, k, self.parameters.seed);
+        let mut size = vec![0; k];
+        let mut centroids = vec![vec![T::zero(); d]; k];
+        for (i, y_i) in y.iter().enumerate() {
+            size[*y_i] += 1;
+            for (j, c_j) in centroids[*y_i].iter_mut().enumerate() {
+                *c_j += data.get(i, j);
+            }
+        }
+        for (centroid, size) in centroids.iter_mut().zip(size.iter()) {
+            centroid
+                .iter_mut()
+                .for_each(|c| *c /= T::from_usize(*size).unwrap());
+        }
+
+        self.centroids = centroids;
+        self.size = vec![0; k];
+        Ok(())
+    }
+
+    /// Assigns every row to its closest centroid, then moves the centroids towards their rows one row at a time.
+    fn update(&mut self, rows: &[Vec<T>]) {
+        let assignments: Vec<usize> = rows.iter().map(|row| self.closest_centroid(row)).collect();
+        for (row, c) in rows.iter().zip(assignments) {
+            self.size[c] += 1;
+            let eta = T::one() / T::from_usize(self.size[c]).unwrap();
+            for (c_j, x_j) in self.centroids[c].iter_mut().zip(row.iter()) {
+                *c_j += eta * (*x_j - *c_j);
+            }
+        }
+    }
+
+    fn closest_centroid(&self, row: &[T]) -> usize {
+        let mut min_dist = T::max_value();
+        let mut best_cluster = 0;
+        for (j, centroid) in self.centroids.iter().enumerate() {
+            let dist = Euclidian::squared_distance(row, centroid);
+            if dist < min_dist {
+                min_dist = dist;
+                best_cluster = j;
+            }
+        }
+        best_cluster
+    }
+
+    fn check_features(&self, m: usize, error: fn(&str) -> Failed) -> Result<(), Failed> {
+        if self.centroids[0].len() != m {
+            return Err(error(&format!(
+                "Number of features of x ({}) does not match the number of features of the centroids ({})",
+                m,
+                self.centroids[0].len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cluster::kmeans::KMeansParameters;
+    use crate::dataset::generator::make_blobs_with_seed;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::linalg::BaseMatrix;
+    use crate::metrics::adjusted_rand_score;
+
+    #[test]
+    fn invalid_parameters() {
+        let x = DenseMatrix::from_2d_array(&[&[1., 2.], &[3., 4.]]);
+        let fit = |parameters: MiniBatchKMeansParameters| MiniBatchKMeans::fit(&x, parameters);
+        assert!(fit(MiniBatchKMeansParameters::default().with_k(1)).is_err());
+        assert!(fit(MiniBatchKMeansParameters::default().with_k(3)).is_err());
+        assert!(fit(MiniBatchKMeansParameters::default().with_batch_size(0)).is_err());
+        assert!(fit(MiniBatchKMeansParameters::default().with_max_iter(0)).is_err());
+
+        let kmeans = MiniBatchKMeans::<f64>::new(Default::default());
+        assert!(kmeans.predict(&x).is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_predict_blobs() {
+        let blobs = make_blobs_with_seed(300, 2, 3, Some(7));
+        let x = DenseMatrix::from_vec(300, 2, &blobs.data);
+        let parameters = MiniBatchKMeansParameters::default()
+            .with_k(3)
+            .with_batch_size(32)
+            .with_seed(2);
+
+        let kmeans = MiniBatchKMeans::fit(&x, parameters).unwrap();
+        let y_hat = kmeans.predict(&x).unwrap();
+        assert_eq!(kmeans.cluster_sizes().iter().sum::<usize>(), 3000);
+        assert!(adjusted_rand_score(&blobs.target, &y_hat) > 0.9);
+
+        let full = KMeans::fit(&x, KMeansParameters::default().with_k(3).with_seed(2)).unwrap();
+        assert!(adjusted_rand_score(&full.predict(&x).unwrap(), &y_hat) > 0.9);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn partial_fit() {
+        let blobs = make_blobs_with_seed(300, 2, 3, Some(7));
+        let x = DenseMatrix::from_vec(300, 2, &blobs.data);
+
+        let mut kmeans =
+            MiniBatchKMeans::new(MiniBatchKMeansParameters::default().with_k(3).with_seed(3));
+        for start in (0..300).step_by(50) {
+            kmeans
+                .partial_fit(&x.slice(start..start + 50, 0..2))
+                .unwrap();
+        }
+        assert_eq!(kmeans.cluster_sizes().iter().sum::<usize>(), 300);
+        assert!(adjusted_rand_score(&blobs.target, &kmeans.predict(&x).unwrap()) > 0.9);
+
+        assert!(kmeans
+            .partial_fit(&DenseMatrix::from_2d_array(&[&[1., 2., 3.]]))
+            .is_err());
+        let mut empty = MiniBatchKMeans::<f32>::new(Default::default());
+        assert!(empty
+            .partial_fit(&DenseMatrix::from_2d_array(&[&[1., 2.]]))
+            .is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let blobs = make_blobs_with_seed(30, 2, 2, Some(1));
+        let x = DenseMatrix::from_vec(30, 2, &blobs.data);
+        let kmeans =
+            MiniBatchKMeans::fit(&x, MiniBatchKMeansParameters::default().with_seed(1)).unwrap();
+
+        let deserialized_kmeans: MiniBatchKMeans<f32> =
+            serde_json::from_str(&serde_json::to_string(&kmeans).unwrap()).unwrap();
+
+        assert_eq!(kmeans, deserialized_kmeans);
+    }
+}
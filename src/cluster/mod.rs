@@ -6,3 +6,5 @@
 pub mod dbscan;
 /// An iterative clustering algorithm that aims to find local maxima in each iteration.
 pub mod kmeans;
+/// K-means that updates the centroids from small batches of observations, fitted at once or incrementally.
+pub mod mini_batch_kmeans;
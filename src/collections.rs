@@ -0,0 +1,6 @@
+// Ordered maps and sets from `alloc` stand in for hash-based collections when `std` is not available,
+// so keys of these collections have to be `Ord`.
+#[cfg(not(feature = "std"))]
+pub(crate) use alloc::collections::{BTreeMap as HashMap, BTreeSet as HashSet};
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{HashMap, HashSet};
@@ -5,6 +5,7 @@
 //! | 506 | 13 | No | Regression |
 //!
 //! [The Boston house-price data](http://lib.stat.cmu.edu/datasets/boston) is derived from information collected by the U.S. Census Service concerning housing in the area of Boston, MA.
+//! The `B` attribute is derived from the racial composition of towns, prefer the [diabetes](../diabetes/index.html) or [Longley](../longley/index.html) data for new examples.
 //! The dataset has following attributes:
 //!
 //! | Predictor | Data Type | Target? |
@@ -0,0 +1,415 @@
+//! # Loading Datasets From CSV Files
+//!
+//! [`from_csv`](fn.from_csv.html) reads a CSV file into a matrix of observations and a vector of targets, ready to be passed to `fit`.
+//! Unlike the [csv reader](../../readers/csv/index.html), which expects a file of numbers only, it handles the files most datasets come in:
+//!
+//! * the target is picked by its position or, when the file has a header, by its name.
+//! * columns with values that are not numbers are categorical, their values are encoded by a [CategoryMapper](../../preprocessing/series_encoder/struct.CategoryMapper.html)
+//!   as the ordinals `0, 1, 2, ...` of the categories in lexicographic order. The encoded columns can be one-hot encoded
+//!   with the [OneHotEncoder](../../preprocessing/categorical/struct.OneHotEncoder.html) afterwards.
+//!   String class labels of the target are encoded the same way.
+//! * fields that match one of the missing value markers are read as `NaN`, that can be filled in with the [KNNImputer](../../preprocessing/impute/struct.KNNImputer.html),
+//!   or the rows with missing values can be dropped. Rows without a target are rejected unless they are dropped.
+//!
+//! Fields can be quoted with double quotes, quoted fields may contain the field separator.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::dataset::{from_csv, CSVOptions};
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use std::fs;
+//!
+//! fs::write(
+//!     "flowers.csv",
+//!     "petal length,petal width,color,species\n1.4,0.2,white,setosa\n4.7,NA,purple,versicolor\n1.3,0.2,purple,setosa",
+//! )
+//! .unwrap();
+//! let (x, y): (DenseMatrix<f64>, Vec<f64>) =
+//!     from_csv("flowers.csv", "species", CSVOptions::default().with_drop_missing(true)).unwrap();
+//! fs::remove_file("flowers.csv").unwrap();
+//!
+//! // "purple" is the first and "white" the second color, the row with a missing petal width is dropped
+//! assert_eq!(
+//!     x,
+//!     DenseMatrix::from_2d_array(&[&[1.4, 0.2, 1.0], &[1.3, 0.2, 0.0]])
+//! );
+//! assert_eq!(y, vec![0.0, 0.0]);
+//! ```
+use std::fs;
+use std::path::Path;
+
+use crate::linalg::naive::dense_matrix::DenseMatrix;
+use crate::math::num::RealNumber;
+use crate::preprocessing::series_encoder::CategoryMapper;
+use crate::readers::ReadingError;
+
+/// Column of a CSV file, selected by its position or by its name in the header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Column<'a> {
+    /// Position of the column, starting at 0
+    Index(usize),
+    /// Name of the column in the header
+    Name(&'a str),
+}
+
+impl<'a> From<usize> for Column<'a> {
+    fn from(index: usize) -> Self {
+        Column::Index(index)
+    }
+}
+
+impl<'a> From<&'a str> for Column<'a> {
+    fn from(name: &'a str) -> Self {
+        Column::Name(name)
+    }
+}
+
+/// Options of [`from_csv`](fn.from_csv.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CSVOptions<'a> {
+    /// Whether the first row of the file holds the names of the columns.
+    has_header: bool,
+    /// What seperates the fields in your csv-file?
+    field_seperator: &'a str,
+    /// Fields that mark a missing value.
+    missing_values: Vec<&'a str>,
+    /// Whether rows with missing values are dropped.
+    drop_missing: bool,
+}
+
+impl<'a> Default for CSVOptions<'a> {
+    fn default() -> Self {
+        Self {
+            has_header: true,
+            field_seperator: ",",
+            missing_values: vec!["", "NA", "N/A", "?"],
+            drop_missing: false,
+        }
+    }
+}
+
+impl<'a> CSVOptions<'a> {
+    /// Whether the first row of the file holds the names of the columns, `true` by default.
+    pub fn with_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+    /// What seperates the fields in the file, `","` by default.
+    pub fn with_field_seperator(mut self, field_seperator: &'a str) -> Self {
+        self.field_seperator = field_seperator;
+        self
+    }
+    /// Fields that mark a missing value, the empty field, `NA`, `N/A` and `?` by default. Fields are compared after surrounding whitespace is removed.
+    pub fn with_missing_values(mut self, missing_values: &[&'a str]) -> Self {
+        self.missing_values = missing_values.to_vec();
+        self
+    }
+    /// Whether rows with missing values are dropped, `false` by default. Otherwise missing features are read as `NaN`.
+    pub fn with_drop_missing(mut self, drop_missing: bool) -> Self {
+        self.drop_missing = drop_missing;
+        self
+    }
+}
+
+/// Reads a CSV file into a _NxM_ matrix with the observations and a vector with the targets.
+/// * `path` - path to the file
+/// * `target_column` - column with the targets, by position or by name, all other columns are features
+/// * `options` - structure of the file and handling of missing values, see [`CSVOptions`](struct.CSVOptions.html)
+pub fn from_csv<'a, T: RealNumber, P: AsRef<Path>>(
+    path: P,
+    target_column: impl Into<Column<'a>>,
+    options: CSVOptions<'_>,
+) -> Result<(DenseMatrix<T>, Vec<T>), ReadingError> {
+    let csv_text = fs::read_to_string(path)?;
+    xy_from_csv_text(&csv_text, target_column.into(), &options)
+}
+
+/// Given a string containing the contents of a csv file, extract the observations and the targets.
+fn xy_from_csv_text<T: RealNumber>(
+    csv_text: &str,
+    target_column: Column<'_>,
+    options: &CSVOptions<'_>,
+) -> Result<(DenseMatrix<T>, Vec<T>), ReadingError> {
+    let mut lines = csv_text.lines().filter(|line| !line.trim().is_empty());
+    let header = if options.has_header {
+        Some(split_csv_row(
+            lines.next().ok_or(ReadingError::NoRowsProvided)?,
+            options.field_seperator,
+        ))
+    } else {
+        None
+    };
+    let rows: Vec<Vec<&str>> = lines
+        .map(|line| split_csv_row(line, options.field_seperator))
+        .collect();
+    let n_fields = match (&header, rows.first()) {
+        (Some(names), _) => names.len(),
+        (None, Some(row)) => row.len(),
+        (None, None) => return Err(ReadingError::NoRowsProvided),
+    };
+    for (row_index, row) in rows.iter().enumerate() {
+        if row.len() != n_fields {
+            return Err(ReadingError::InvalidRow {
+                msg: format!(
+                    "{} fields found but expected {}, Row: {}.",
+                    row.len(),
+                    n_fields,
+                    row_index
+                ),
+            });
+        }
+    }
+    let target = target_index(target_column, header.as_deref(), n_fields)?;
+
+    let is_missing = |field: &str| options.missing_values.contains(&field);
+    let rows: Vec<Vec<&str>> = if options.drop_missing {
+        rows.into_iter()
+            .filter(|row| !row.iter().any(|field| is_missing(field)))
+            .collect()
+    } else {
+        rows
+    };
+    if rows.is_empty() {
+        return Err(ReadingError::NoRowsProvided);
+    }
+    if let Some(row_index) = rows.iter().position(|row| is_missing(row[target])) {
+        return Err(ReadingError::InvalidField {
+            msg: format!(
+                "Target value is missing. Column: {}, Row: {}.",
+                target, row_index
+            ),
+        });
+    }
+
+    // a column is categorical when one of its values is not a number
+    let encoders: Vec<Option<CategoryMapper<&str>>> = (0..n_fields)
+        .map(|column| {
+            let values = rows
+                .iter()
+                .map(|row| row[column])
+                .filter(|field| !is_missing(field));
+            if values.clone().all(|field| field.parse::<T>().is_ok()) {
+                None
+            } else {
+                let mut categories: Vec<&str> = values.collect();
+                categories.sort_unstable();
+                categories.dedup();
+                Some(CategoryMapper::from_positional_category_vec(categories))
+            }
+        })
+        .collect();
+    let value = |field: &str, column: usize| match &encoders[column] {
+        _ if is_missing(field) => T::nan(),
+        Some(encoder) => T::from_usize(*encoder.get_num(&field).unwrap()).unwrap(),
+        None => field.parse().ok().unwrap(),
+    };
+
+    let n_features = n_fields - 1;
+    let mut x = Vec::with_capacity(rows.len() * n_features);
+    let mut y = Vec::with_capacity(rows.len());
+    for row in rows.iter() {
+        for (column, field) in row.iter().enumerate() {
+            if column == target {
+                y.push(value(field, column));
+            } else {
+                x.push(value(field, column));
+            }
+        }
+    }
+    Ok((DenseMatrix::from_vec(rows.len(), n_features, &x), y))
+}
+
+/// Finds the position of the target column.
+fn target_index(
+    target_column: Column<'_>,
+    header: Option<&[&str]>,
+    n_fields: usize,
+) -> Result<usize, ReadingError> {
+    match (target_column, header) {
+        (Column::Index(index), _) if index < n_fields => Ok(index),
+        (Column::Index(index), _) => Err(ReadingError::InvalidRow {
+            msg: format!(
+                "Target column {} does not exist, rows have {} fields",
+                index, n_fields
+            ),
+        }),
+        (Column::Name(name), Some(header)) => header
+            .iter()
+            .position(|field| *field == name)
+            .ok_or_else(|| ReadingError::InvalidRow {
+                msg: format!("Header has no column named '{}'", name),
+            }),
+        (Column::Name(name), None) => Err(ReadingError::InvalidRow {
+            msg: format!(
+                "Target column '{}' can not be found in a file without header",
+                name
+            ),
+        }),
+    }
+}
+
+/// Splits a row into its fields, with surrounding whitespace and quotes removed.
+/// Separators inside of double quotes do not split fields.
+fn split_csv_row<'a>(row: &'a str, field_seperator: &str) -> Vec<&'a str> {
+    let mut fields = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut position = 0;
+    while position < row.len() {
+        let rest = &row[position..];
+        if rest.starts_with('"') {
+            quoted = !quoted;
+            position += 1;
+        } else if !quoted && rest.starts_with(field_seperator) {
+            fields.push(unquote(&row[start..position]));
+            position += field_seperator.len();
+            start = position;
+        } else {
+            position += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    fields.push(unquote(&row[start..]));
+    fields
+}
+
+fn unquote(field: &str) -> &str {
+    let field = field.trim();
+    if field.len() >= 2 && field.starts_with('"') && field.ends_with('"') {
+        &field[1..field.len() - 1]
+    } else {
+        field
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::BaseMatrix;
+    use crate::readers::io_testing::TemporaryTextFile;
+
+    #[test]
+    fn split_quoted_fields() {
+        assert_eq!(
+            split_csv_row(r#" 1.5,"a, b", c ,"#, ","),
+            vec!["1.5", "a, b", "c", ""]
+        );
+        assert_eq!(split_csv_row("1;;2", ";;"), vec!["1", "2"]);
+    }
+
+    #[test]
+    fn numerical_columns() {
+        let (x, y): (DenseMatrix<f64>, Vec<f64>) = xy_from_csv_text(
+            "a,b,c\n1,2,3\n4,5,6\n",
+            Column::Index(1),
+            &CSVOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(x, DenseMatrix::from_2d_array(&[&[1., 3.], &[4., 6.]]));
+        assert_eq!(y, vec![2., 5.]);
+
+        let (x, y): (DenseMatrix<f64>, Vec<f64>) = xy_from_csv_text(
+            "1;2\n3;4",
+            Column::Index(0),
+            &CSVOptions::default()
+                .with_header(false)
+                .with_field_seperator(";"),
+        )
+        .unwrap();
+        assert_eq!(x, DenseMatrix::from_2d_array(&[&[2.], &[4.]]));
+        assert_eq!(y, vec![1., 3.]);
+    }
+
+    #[test]
+    fn categorical_columns() {
+        let csv = "size,color,label\n1,red,\"yes\"\n2,blue,no\n3,red,yes\n4,green,maybe";
+        let (x, y): (DenseMatrix<f32>, Vec<f32>) =
+            xy_from_csv_text(csv, Column::Name("label"), &CSVOptions::default()).unwrap();
+        assert_eq!(
+            x,
+            DenseMatrix::from_2d_array(&[&[1., 2.], &[2., 0.], &[3., 2.], &[4., 1.]])
+        );
+        assert_eq!(y, vec![2., 1., 2., 0.]);
+    }
+
+    #[test]
+    fn missing_values() {
+        let csv = "a,b,y\n1,NA,0\n?,2,1\n3,4,0";
+        let (x, y): (DenseMatrix<f64>, Vec<f64>) =
+            xy_from_csv_text(csv, Column::Name("y"), &CSVOptions::default()).unwrap();
+        assert!(x.get(0, 1).is_nan() && x.get(1, 0).is_nan());
+        assert_eq!((x.get(0, 0), x.get(2, 1)), (1., 4.));
+        assert_eq!(y, vec![0., 1., 0.]);
+
+        let (x, y): (DenseMatrix<f64>, Vec<f64>) = xy_from_csv_text(
+            csv,
+            Column::Name("y"),
+            &CSVOptions::default().with_drop_missing(true),
+        )
+        .unwrap();
+        assert_eq!(x, DenseMatrix::from_2d_array(&[&[3., 4.]]));
+        assert_eq!(y, vec![0.]);
+
+        // categories are collected from the values that are present only
+        let (x, _): (DenseMatrix<f64>, Vec<f64>) = xy_from_csv_text(
+            "a,y\nb,1\n-,2\na,3",
+            Column::Index(1),
+            &CSVOptions::default().with_missing_values(&["-"]),
+        )
+        .unwrap();
+        assert_eq!(x.get(0, 0), 1.);
+        assert!(x.get(1, 0).is_nan());
+        assert_eq!(x.get(2, 0), 0.);
+    }
+
+    #[test]
+    fn invalid_files() {
+        let options = CSVOptions::default();
+        let read = |csv: &str, target: Column<'_>| {
+            xy_from_csv_text::<f64>(csv, target, &options).map(|_| ())
+        };
+        assert_eq!(
+            read("", Column::Index(0)),
+            Err(ReadingError::NoRowsProvided)
+        );
+        assert_eq!(
+            read("a,b\n1,2\n3", Column::Index(0)),
+            Err(ReadingError::InvalidRow {
+                msg: String::from("1 fields found but expected 2, Row: 1.")
+            })
+        );
+        assert_eq!(
+            read("a,b\n1,2", Column::Name("c")),
+            Err(ReadingError::InvalidRow {
+                msg: String::from("Header has no column named 'c'")
+            })
+        );
+        assert!(read("a,b\n1,2", Column::Index(2)).is_err());
+        assert_eq!(
+            read("a,b\n1,2\n3,", Column::Index(1)),
+            Err(ReadingError::InvalidField {
+                msg: String::from("Target value is missing. Column: 1, Row: 1.")
+            })
+        );
+        assert!(xy_from_csv_text::<f64>(
+            "1,2",
+            Column::Name("a"),
+            &CSVOptions::default().with_header(false)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn read_from_file() {
+        let file = TemporaryTextFile::new("x,class\n0.5,a\n1.5,b\n").unwrap();
+        let (x, y): (DenseMatrix<f64>, Vec<f64>) =
+            from_csv(file.path(), "class", CSVOptions::default()).unwrap();
+        assert_eq!(x, DenseMatrix::from_2d_array(&[&[0.5], &[1.5]]));
+        assert_eq!(y, vec![0., 1.]);
+
+        assert!(matches!(
+            from_csv::<f64, _>("missing-file.csv", 0, CSVOptions::default()),
+            Err(ReadingError::CouldNotReadFileSystem { .. })
+        ));
+    }
+}
@@ -5,18 +5,30 @@ use rand::prelude::*;
 use rand_distr::Normal;
 
 use crate::dataset::Dataset;
+use crate::rand::get_rng_impl;
 
 /// Generate `num_centers` clusters of normally distributed points
 pub fn make_blobs(
     num_samples: usize,
     num_features: usize,
     num_centers: usize,
+) -> Dataset<f32, f32> {
+    make_blobs_with_seed(num_samples, num_features, num_centers, None)
+}
+
+/// Generate `num_centers` clusters of normally distributed points.
+/// Pass a `seed` to get the same points on every call.
+pub fn make_blobs_with_seed(
+    num_samples: usize,
+    num_features: usize,
+    num_centers: usize,
+    seed: Option<u64>,
 ) -> Dataset<f32, f32> {
     let center_box = Uniform::from(-10.0..10.0);
     let cluster_std = 1.0;
     let mut centers: Vec<Vec<Normal<f32>>> = Vec::with_capacity(num_centers);
 
-    let mut rng = rand::thread_rng();
+    let mut rng = get_rng_impl(seed);
     for _ in 0..num_centers {
         centers.push(
             (0..num_features)
@@ -49,6 +61,17 @@ pub fn make_blobs(
 
 /// Make a large circle containing a smaller circle in 2d.
 pub fn make_circles(num_samples: usize, factor: f32, noise: f32) -> Dataset<f32, f32> {
+    make_circles_with_seed(num_samples, factor, noise, None)
+}
+
+/// Make a large circle containing a smaller circle in 2d.
+/// Pass a `seed` to get the same noise on every call.
+pub fn make_circles_with_seed(
+    num_samples: usize,
+    factor: f32,
+    noise: f32,
+    seed: Option<u64>,
+) -> Dataset<f32, f32> {
     if !(0.0..1.0).contains(&factor) {
         panic!("'factor' has to be between 0 and 1.");
     }
@@ -56,11 +79,11 @@ pub fn make_circles(num_samples: usize, factor: f32, noise: f32) -> Dataset<f32,
     let num_samples_out = num_samples / 2;
     let num_samples_in = num_samples - num_samples_out;
 
-    let linspace_out = linspace(0.0, 2.0 * std::f32::consts::PI, num_samples_out);
-    let linspace_in = linspace(0.0, 2.0 * std::f32::consts::PI, num_samples_in);
+    let linspace_out = linspace(0.0, 2.0 * core::f32::consts::PI, num_samples_out);
+    let linspace_in = linspace(0.0, 2.0 * core::f32::consts::PI, num_samples_in);
 
     let noise = Normal::new(0.0, noise).unwrap();
-    let mut rng = rand::thread_rng();
+    let mut rng = get_rng_impl(seed);
 
     let mut x: Vec<f32> = Vec::with_capacity(num_samples * 2);
     let mut y: Vec<f32> = Vec::with_capacity(num_samples);
@@ -90,14 +113,24 @@ pub fn make_circles(num_samples: usize, factor: f32, noise: f32) -> Dataset<f32,
 
 /// Make two interleaving half circles in 2d
 pub fn make_moons(num_samples: usize, noise: f32) -> Dataset<f32, f32> {
+    make_moons_with_seed(num_samples, noise, None)
+}
+
+/// Make two interleaving half circles in 2d.
+/// Pass a `seed` to get the same noise on every call.
+pub fn make_moons_with_seed(
+    num_samples: usize,
+    noise: f32,
+    seed: Option<u64>,
+) -> Dataset<f32, f32> {
     let num_samples_out = num_samples / 2;
     let num_samples_in = num_samples - num_samples_out;
 
-    let linspace_out = linspace(0.0, std::f32::consts::PI, num_samples_out);
-    let linspace_in = linspace(0.0, std::f32::consts::PI, num_samples_in);
+    let linspace_out = linspace(0.0, core::f32::consts::PI, num_samples_out);
+    let linspace_in = linspace(0.0, core::f32::consts::PI, num_samples_in);
 
     let noise = Normal::new(0.0, noise).unwrap();
-    let mut rng = rand::thread_rng();
+    let mut rng = get_rng_impl(seed);
 
     let mut x: Vec<f32> = Vec::with_capacity(num_samples * 2);
     let mut y: Vec<f32> = Vec::with_capacity(num_samples);
@@ -175,4 +208,25 @@ mod tests {
         assert_eq!(dataset.num_features, 2);
         assert_eq!(dataset.num_samples, 10);
     }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn test_generators_with_seed() {
+        assert_eq!(
+            make_blobs_with_seed(10, 2, 3, Some(42)).data,
+            make_blobs_with_seed(10, 2, 3, Some(42)).data
+        );
+        assert_eq!(
+            make_circles_with_seed(10, 0.5, 0.05, Some(42)).data,
+            make_circles_with_seed(10, 0.5, 0.05, Some(42)).data
+        );
+        assert_eq!(
+            make_moons_with_seed(10, 0.05, Some(42)).data,
+            make_moons_with_seed(10, 0.05, Some(42)).data
+        );
+        assert_ne!(
+            make_moons_with_seed(10, 0.05, Some(42)).data,
+            make_moons_with_seed(10, 0.05, Some(43)).data
+        );
+    }
 }
@@ -0,0 +1,88 @@
+//! # The Longley Dataset
+//!
+//! | Number of Instances | Number of Attributes | Missing Values? | Associated Tasks: |
+//! |-|-|-|-|
+//! | 16 | 6 | No | Regression |
+//!
+//! [Longley's economic data](https://www.itl.nist.gov/div898/strd/lls/data/Longley.shtml) covers the United States from 1947 to 1962.
+//! Its predictors are highly collinear, which makes it a classic benchmark for the numerical accuracy of least squares solvers
+//! and a small regression dataset without the ethical problems of the [Boston house-price data](../boston/index.html).
+//! The dataset has following attributes:
+//!
+//! | Predictor | Data Type | Target? |
+//! |-|-|-|
+//! | GNP, gross national product | Numerical | No |
+//! | Unemployed, number of unemployed | Numerical | No |
+//! | Armed Forces, number of people in the armed forces | Numerical | No |
+//! | Population, noninstitutionalized population 14 years of age and over | Numerical | No |
+//! | Year | Numerical | No |
+//! | Employed, number of people employed | Numerical | No |
+//! | GNP deflator, GNP implicit price deflator (1954 = 100) | Numerical | Yes |
+//!
+//! ## References:
+//! * ["An Appraisal of Least Squares Programs for the Electronic Computer from the Point of View of the User", Longley J. W., 1967, Journal of the American Statistical Association, 62, 819-841](https://www.jstor.org/stable/2283673)
+use crate::dataset::deserialize_data;
+use crate::dataset::Dataset;
+
+/// Get dataset
+pub fn load_dataset() -> Dataset<f32, f32> {
+    let (x, y, num_samples, num_features) =
+        match deserialize_data(std::include_bytes!("longley.xy")) {
+            Err(why) => panic!("Can't deserialize longley.xy. {}", why),
+            Ok((x, y, num_samples, num_features)) => (x, y, num_samples, num_features),
+        };
+
+    Dataset {
+        data: x,
+        target: y,
+        num_samples,
+        num_features,
+        feature_names: [
+            "GNP",
+            "Unemployed",
+            "Armed Forces",
+            "Population",
+            "Year",
+            "Employed",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect(),
+        target_names: vec!["GNP deflator".to_string()],
+        description:
+            "Longley's economic regression data: https://www.itl.nist.gov/div898/strd/lls/data/Longley.shtml"
+                .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[cfg(not(target_arch = "wasm32"))]
+    use super::super::*;
+    use super::*;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    #[ignore]
+    fn refresh_longley_dataset() {
+        // run this test to generate longley.xy file.
+        let dataset = load_dataset();
+        assert!(serialize_data(&dataset, "longley.xy").is_ok());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn longley_dataset() {
+        let dataset = load_dataset();
+        assert_eq!(
+            dataset.data.len(),
+            dataset.num_features * dataset.num_samples
+        );
+        assert_eq!(dataset.target.len(), dataset.num_samples);
+        assert_eq!(dataset.num_features, 6);
+        assert_eq!(dataset.num_samples, 16);
+        assert_eq!(dataset.data[4], 1947.);
+        assert_eq!(dataset.target[15], 116.9);
+    }
+}
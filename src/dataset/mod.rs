@@ -1,14 +1,36 @@
 //! Datasets
 //!
-//! In this module you will find small datasets that are used in SmartCore for demonstration purpose mostly.
+//! In this module you will find small datasets that are used in SmartCore for demonstration purpose mostly:
+//! [iris](iris/index.html), [breast cancer](breast_cancer/index.html) and [digits](digits/index.html) for classification,
+//! [diabetes](diabetes/index.html), [Longley](longley/index.html) and [Boston](boston/index.html) for regression.
+//! The data is embedded in the library, so loading a dataset needs neither files nor network access.
+//! Use [`to_xy`](struct.Dataset.html#method.to_xy) to get the observations and targets in the form estimators expect.
+//! Your own data can be loaded from a CSV file with [`from_csv`](fn.from_csv.html), see the [csv](csv/index.html) module.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::dataset::iris;
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::tree::decision_tree_classifier::*;
+//!
+//! let (x, y): (DenseMatrix<f64>, Vec<f64>) = iris::load_dataset().to_xy();
+//!
+//! let tree = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
+//! let y_hat = tree.predict(&x).unwrap();
+//! ```
 pub mod boston;
 pub mod breast_cancer;
+pub mod csv;
 pub mod diabetes;
 pub mod digits;
 pub mod generator;
 pub mod iris;
+pub mod longley;
 
-#[cfg(not(target_arch = "wasm32"))]
+pub use self::csv::{from_csv, CSVOptions, Column};
+
+use crate::linalg::naive::dense_matrix::DenseMatrix;
 use crate::math::num::RealNumber;
 #[cfg(not(target_arch = "wasm32"))]
 use std::fs::File;
@@ -52,6 +74,20 @@ impl<X, Y> Dataset<X, Y> {
     }
 }
 
+impl<X: RealNumber, Y: RealNumber> Dataset<X, Y> {
+    /// Returns the observations as a _NxM_ matrix and the targets as a vector, converted to `T`.
+    /// Values are converted through their shortest decimal representation, so the `f32` value `5.1` becomes the `f64` value `5.1`.
+    pub fn to_xy<T: RealNumber>(&self) -> (DenseMatrix<T>, Vec<T>) {
+        let convert = |value: String| T::from_f64(value.parse().unwrap()).unwrap();
+        let data: Vec<T> = self.data.iter().map(|v| convert(v.to_string())).collect();
+        let target = self.target.iter().map(|v| convert(v.to_string())).collect();
+        (
+            DenseMatrix::from_vec(self.num_samples, self.num_features, &data),
+            target,
+        )
+    }
+}
+
 // Running this in wasm throws: operation not supported on this platform.
 #[cfg(not(target_arch = "wasm32"))]
 #[allow(dead_code)]
@@ -87,7 +123,7 @@ pub(crate) fn deserialize_data(
     bytes: &[u8],
 ) -> Result<(Vec<f32>, Vec<f32>, usize, usize), io::Error> {
     // read the same file back into a Vec of bytes
-    const USIZE_SIZE: usize = std::mem::size_of::<usize>();
+    const USIZE_SIZE: usize = core::mem::size_of::<usize>();
     let (num_samples, num_features) = {
         let mut buffer = [0u8; USIZE_SIZE];
         buffer.copy_from_slice(&bytes[0..USIZE_SIZE]);
@@ -140,4 +176,26 @@ mod tests {
         assert_eq!(m[0].len(), 5);
         assert_eq!(*m[1][3], 9);
     }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn to_xy() {
+        let dataset = Dataset {
+            data: vec![5.1f32, 3.5, 1.4, 4.9, 3.0, 1.4],
+            target: vec![0f32, 1.],
+            num_samples: 2,
+            num_features: 3,
+            feature_names: vec![],
+            target_names: vec![],
+            description: "".to_string(),
+        };
+
+        let (x, y): (DenseMatrix<f64>, Vec<f64>) = dataset.to_xy();
+
+        assert_eq!(
+            x,
+            DenseMatrix::from_2d_array(&[&[5.1, 3.5, 1.4], &[4.9, 3.0, 1.4]])
+        );
+        assert_eq!(y, vec![0., 1.]);
+    }
 }
@@ -0,0 +1,460 @@
+//! # FastICA
+//!
+//! Independent component analysis (ICA) separates a multivariate signal into additive components that are statistically independent.
+//! It assumes that the observations \\(X\\) are linear mixtures \\(X = S A^T\\) of non-Gaussian sources \\(S\\) and estimates the unmixing
+//! matrix \\(W\\), so that \\(S = X W^T\\). A classic application is blind source separation, like recovering individual voices from recordings of several microphones.
+//! While [PCA](../pca/index.html) only finds uncorrelated directions, ICA looks for independent ones, which can be recovered only up to their order, sign and scale.
+//!
+//! FastICA first centers and whitens the data, so that its covariance is the identity matrix. It then maximizes the non-Gaussianity of all components at once
+//! with the fixed-point iteration
+//!
+//! \\[W \leftarrow \frac{1}{n} g(W X^T) X - \text{diag}\left(\frac{1}{n} \sum_i g'(W x_i)\right) W, \quad W \leftarrow (W W^T)^{-1/2} W\\]
+//!
+//! where the second step keeps the components decorrelated and \\(g\\) is the derivative of a contrast function, see [`ICAFunction`](enum.ICAFunction.html).
+//!
+//! Example:
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::decomposition::fastica::*;
+//!
+//! // two signals, a sine wave and a sawtooth, mixed together
+//! let x = DenseMatrix::from_2d_vec(
+//!     &(0..100)
+//!         .map(|i| {
+//!             let t = i as f64 / 10.0;
+//!             let s1 = (2.0 * t).sin();
+//!             let s2 = t % 1.0 - 0.5;
+//!             vec![s1 + s2, 0.5 * s1 + 2.0 * s2]
+//!         })
+//!         .collect(),
+//! );
+//!
+//! let ica = FastICA::fit(&x, FastICAParameters::default().with_seed(0)).unwrap();
+//!
+//! let sources = ica.transform(&x).unwrap();
+//! let unmixing = ica.components();
+//! ```
+//!
+//! ## References:
+//! * ["Independent Component Analysis: Algorithms and Applications", Hyvärinen A., Oja E., Neural Networks, 2000](https://doi.org/10.1016/S0893-6080(00)00026-5)
+//! * ["Fast and Robust Fixed-Point Algorithms for Independent Component Analysis", Hyvärinen A., IEEE Transactions on Neural Networks, 1999](https://doi.org/10.1109/72.761722)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Transformer, UnsupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::rand::get_rng_impl;
+use crate::validation::{check_transform, check_x};
+
+/// Contrast function that measures the non-Gaussianity of the components.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum ICAFunction {
+    #[default]
+    /// \\(\frac{1}{\alpha} \log \cosh(\alpha u)\\), a good general-purpose contrast.
+    LogCosh,
+    /// \\(-\exp(-u^2 / 2)\\), better for super-Gaussian sources or when robustness to outliers matters.
+    Exp,
+    /// \\(u^4 / 4\\), kurtosis based, fast but sensitive to outliers.
+    Cube,
+}
+
+/// FastICA parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct FastICAParameters<T: RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of components to keep, all by default.
+    pub n_components: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Contrast function.
+    pub fun: ICAFunction,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Scale \\(\alpha\\) of the `LogCosh` contrast, in [1, 2].
+    pub alpha: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Maximum number of iterations.
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Iterations stop when the components change less than this tolerance.
+    pub tol: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed of the random number generator used to initialize the unmixing matrix.
+    pub seed: Option<u64>,
+}
+
+impl<T: RealNumber> FastICAParameters<T> {
+    /// Number of components to keep.
+    pub fn with_n_components(mut self, n_components: usize) -> Self {
+        self.n_components = Some(n_components);
+        self
+    }
+    /// Contrast function.
+    pub fn with_fun(mut self, fun: ICAFunction) -> Self {
+        self.fun = fun;
+        self
+    }
+    /// Scale \\(\alpha\\) of the `LogCosh` contrast, in [1, 2].
+    pub fn with_alpha(mut self, alpha: T) -> Self {
+        self.alpha = alpha;
+        self
+    }
+    /// Maximum number of iterations.
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Iterations stop when the components change less than this tolerance.
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Seed of the random number generator used to initialize the unmixing matrix.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl<T: RealNumber> Default for FastICAParameters<T> {
+    fn default() -> Self {
+        FastICAParameters {
+            n_components: None,
+            fun: ICAFunction::default(),
+            alpha: T::one(),
+            max_iter: 200,
+            tol: T::from_f64(1e-4).unwrap(),
+            seed: None,
+        }
+    }
+}
+
+/// Independent component analysis with the FastICA algorithm
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct FastICA<T: RealNumber, M: Matrix<T>> {
+    components: M,
+    mixing: M,
+    mean: Vec<T>,
+    n_iter: usize,
+}
+
+impl<T: RealNumber, M: Matrix<T>> UnsupervisedEstimator<M, FastICAParameters<T>> for FastICA<T, M> {
+    fn fit(x: &M, parameters: FastICAParameters<T>) -> Result<Self, Failed> {
+        FastICA::fit(x, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Transformer<M> for FastICA<T, M> {
+    fn transform(&self, x: &M) -> Result<M, Failed> {
+        self.transform(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> FastICA<T, M> {
+    /// Fits FastICA to your data.
+    /// * `data` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(data: &M, parameters: FastICAParameters<T>) -> Result<FastICA<T, M>, Failed> {
+        check_x(data, false, Failed::fit)?;
+        let (n, p) = data.shape();
+        let k = parameters.n_components.unwrap_or(p);
+
+        if k == 0 || k > p {
+            return Err(Failed::fit(&format!(
+                "Number of components, n_components should be in [1, {}], got {}",
+                p, k
+            )));
+        }
+        if n < 2 {
+            return Err(Failed::fit("FastICA needs at least 2 observations"));
+        }
+        if parameters.tol <= T::zero() {
+            return Err(Failed::fit(&format!(
+                "tol should be > 0, got {}",
+                parameters.tol
+            )));
+        }
+
+        let mean = data.column_mean();
+        let mut x = data.clone();
+        for (c, mean_c) in mean.iter().enumerate() {
+            for r in 0..n {
+                x.sub_element_mut(r, c, *mean_c);
+            }
+        }
+
+        // whitening from the eigenvectors of the covariance matrix with the largest eigenvalues
+        let mut cov = x.ab(true, &x, false);
+        cov.div_scalar_mut(T::from_usize(n).unwrap());
+        let evd = cov.evd(true)?;
+        if evd.d[k - 1] <= T::epsilon() {
+            return Err(Failed::fit(
+                "The covariance matrix of the data is singular, reduce n_components",
+            ));
+        }
+        let mut whitening = M::zeros(k, p);
+        let mut dewhitening = M::zeros(p, k);
+        for c in 0..k {
+            let scale = evd.d[c].sqrt();
+            for j in 0..p {
+                whitening.set(c, j, evd.V.get(j, c) / scale);
+                dewhitening.set(j, c, evd.V.get(j, c) * scale);
+            }
+        }
+        let x_white = x.ab(false, &whitening, true);
+
+        let mut rng = get_rng_impl(parameters.seed);
+        let mut w = M::zeros(k, k);
+        for i in 0..k {
+            for j in 0..k {
+                w.set(i, j, T::from_f64(rng.gen_range(-1.0..1.0)).unwrap());
+            }
+        }
+        w = symmetric_decorrelation(&w)?;
+
+        let n_t = T::from_usize(n).unwrap();
+        let mut n_iter = 0;
+        for _ in 0..parameters.max_iter {
+            n_iter += 1;
+            let mut g = x_white.ab(false, &w, true);
+            let mut g_prime_mean = vec![T::zero(); k];
+            for r in 0..n {
+                for (c, g_prime_mean_c) in g_prime_mean.iter_mut().enumerate() {
+                    let (g_rc, g_prime) = contrast(parameters.fun, parameters.alpha, g.get(r, c));
+                    g.set(r, c, g_rc);
+                    *g_prime_mean_c += g_prime / n_t;
+                }
+            }
+
+            let mut w_new = g.ab(true, &x_white, false);
+            w_new.div_scalar_mut(n_t);
+            for (i, g_prime_mean_i) in g_prime_mean.iter().enumerate() {
+                for j in 0..k {
+                    w_new.sub_element_mut(i, j, *g_prime_mean_i * w.get(i, j));
+                }
+            }
+            let w_new = symmetric_decorrelation(&w_new)?;
+
+            // converged when every new component points in the same direction as the old one
+            let product = w_new.ab(false, &w, true);
+            let change = (0..k).fold(T::zero(), |change, i| {
+                change.max((product.get(i, i).abs() - T::one()).abs())
+            });
+            w = w_new;
+            if change < parameters.tol {
+                break;
+            }
+        }
+
+        Ok(FastICA {
+            components: w.matmul(&whitening),
+            mixing: dewhitening.ab(false, &w, true),
+            mean,
+            n_iter,
+        })
+    }
+
+    /// Recovers the independent sources of `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn transform(&self, x: &M) -> Result<M, Failed> {
+        check_transform(x)?;
+        let (nrows, ncols) = x.shape();
+        if ncols != self.mean.len() {
+            return Err(Failed::transform(&format!(
+                "Invalid input vector size: {}, expected: {}",
+                ncols,
+                self.mean.len()
+            )));
+        }
+
+        let mut centered = x.clone();
+        for r in 0..nrows {
+            for c in 0..ncols {
+                centered.sub_element_mut(r, c, self.mean[c]);
+            }
+        }
+        Ok(centered.ab(false, &self.components, true))
+    }
+
+    /// Mixes the `sources` back into the space of the observations.
+    pub fn inverse_transform(&self, sources: &M) -> Result<M, Failed> {
+        check_transform(sources)?;
+        let (nrows, ncols) = sources.shape();
+        let (_, n_components) = self.mixing.shape();
+        if ncols != n_components {
+            return Err(Failed::transform(&format!(
+                "Invalid input vector size: {}, expected: {}",
+                ncols, n_components
+            )));
+        }
+
+        let mut x = sources.ab(false, &self.mixing, true);
+        for r in 0..nrows {
+            for (c, mean_c) in self.mean.iter().enumerate() {
+                x.add_element_mut(r, c, *mean_c);
+            }
+        }
+        Ok(x)
+    }
+
+    /// Unmixing matrix, one component per row. Sources are the centered observations multiplied by its transpose.
+    pub fn components(&self) -> &M {
+        &self.components
+    }
+
+    /// Mixing matrix, the pseudo-inverse of the unmixing matrix, one component per column.
+    pub fn mixing(&self) -> &M {
+        &self.mixing
+    }
+
+    /// Mean of every feature of the training data.
+    pub fn mean(&self) -> &Vec<T> {
+        &self.mean
+    }
+
+    /// Number of iterations until convergence or `max_iter`.
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+}
+
+/// First and second derivatives of the contrast function at `u`.
+fn contrast<T: RealNumber>(fun: ICAFunction, alpha: T, u: T) -> (T, T) {
+    match fun {
+        ICAFunction::LogCosh => {
+            let g = (alpha * u).tanh();
+            (g, alpha * (T::one() - g * g))
+        }
+        ICAFunction::Exp => {
+            let e = (-u * u / T::two()).exp();
+            (u * e, (T::one() - u * u) * e)
+        }
+        ICAFunction::Cube => (u * u * u, T::from_usize(3).unwrap() * u * u),
+    }
+}
+
+/// Decorrelates the rows of `w`, \\((W W^T)^{-1/2} W\\).
+fn symmetric_decorrelation<T: RealNumber, M: Matrix<T>>(w: &M) -> Result<M, Failed> {
+    let (k, _) = w.shape();
+    let evd = w.ab(false, w, true).evd(true)?;
+    let mut scaled = evd.V.clone();
+    for c in 0..k {
+        let scale = T::one() / evd.d[c].max(T::epsilon()).sqrt();
+        for r in 0..k {
+            scaled.mul_element_mut(r, c, scale);
+        }
+    }
+    Ok(scaled.ab(false, &evd.V, true).matmul(w))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::high_order::HighOrderOperations;
+    use crate::linalg::naive::dense_matrix::*;
+
+    /// Sine wave and square wave mixed with `[[1, 1], [0.5, 2]]`.
+    fn mixed_signals() -> (DenseMatrix<f64>, Vec<Vec<f64>>) {
+        let sources: Vec<Vec<f64>> = (0..200)
+            .map(|i| {
+                let t = i as f64 * 8.0 / 199.0;
+                vec![(2.0 * t).sin(), (3.0 * t).sin().signum()]
+            })
+            .collect();
+        let x = sources
+            .iter()
+            .map(|s| vec![s[0] + s[1], 0.5 * s[0] + 2.0 * s[1]])
+            .collect();
+        (DenseMatrix::from_2d_vec(&x), sources)
+    }
+
+    fn correlation(a: &[f64], b: &[f64]) -> f64 {
+        let n = a.len() as f64;
+        let (ma, mb) = (a.iter().sum::<f64>() / n, b.iter().sum::<f64>() / n);
+        let cov: f64 = a.iter().zip(b).map(|(x, y)| (x - ma) * (y - mb)).sum();
+        let va: f64 = a.iter().map(|x| (x - ma).powi(2)).sum();
+        let vb: f64 = b.iter().map(|y| (y - mb).powi(2)).sum();
+        cov / (va * vb).sqrt()
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn separates_sources() {
+        let (x, sources) = mixed_signals();
+        for fun in [ICAFunction::LogCosh, ICAFunction::Exp, ICAFunction::Cube] {
+            let ica =
+                FastICA::fit(&x, FastICAParameters::default().with_fun(fun).with_seed(0)).unwrap();
+            assert!(ica.n_iter() < 200, "{:?}", fun);
+            let recovered = ica.transform(&x).unwrap();
+
+            // every true source is recovered by one of the components, up to order and sign
+            for s in 0..2 {
+                let truth: Vec<f64> = sources.iter().map(|row| row[s]).collect();
+                let best = (0..2)
+                    .map(|c| correlation(&truth, &recovered.get_col_as_vec(c)).abs())
+                    .fold(0.0, f64::max);
+                assert!(best > 0.99, "{:?}: {}", fun, best);
+            }
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn whitened_sources() {
+        let (x, _) = mixed_signals();
+        let ica = FastICA::fit(&x, FastICAParameters::default().with_seed(1)).unwrap();
+        let s = ica.transform(&x).unwrap();
+        let mut cov = s.ab(true, &s, false);
+        cov.div_scalar_mut(200.0);
+        assert!(cov.approximate_eq(&DenseMatrix::eye(2), 1e-8));
+
+        assert!(ica.inverse_transform(&s).unwrap().approximate_eq(&x, 1e-8));
+        assert!(ica
+            .components()
+            .matmul(ica.mixing())
+            .approximate_eq(&DenseMatrix::eye(2), 1e-8));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fewer_components() {
+        let (x, _) = mixed_signals();
+        let ica = FastICA::fit(
+            &x,
+            FastICAParameters::default()
+                .with_n_components(1)
+                .with_seed(2),
+        )
+        .unwrap();
+        assert_eq!(ica.components().shape(), (1, 2));
+        assert_eq!(ica.mixing().shape(), (2, 1));
+        assert_eq!(ica.transform(&x).unwrap().shape(), (200, 1));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn invalid_input() {
+        let (x, _) = mixed_signals();
+        assert!(FastICA::fit(&x, FastICAParameters::default().with_n_components(3)).is_err());
+        assert!(FastICA::fit(&x, FastICAParameters::default().with_tol(0.0)).is_err());
+
+        // second column is a copy of the first one
+        let singular = DenseMatrix::from_2d_array(&[&[1.0, 1.0], &[2.0, 2.0], &[3.0, 3.0]]);
+        assert!(FastICA::fit(&singular, FastICAParameters::default()).is_err());
+
+        let ica = FastICA::fit(&x, FastICAParameters::default().with_seed(0)).unwrap();
+        assert!(ica
+            .transform(&DenseMatrix::from_2d_array(&[&[1.0, 2.0, 3.0]]))
+            .is_err());
+    }
+}
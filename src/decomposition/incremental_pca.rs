@@ -0,0 +1,507 @@
+//! # Incremental PCA
+//!
+//! [PCA](../pca/index.html) needs the whole dataset to compute the principal components. Incremental principal components analysis
+//! updates the components from one mini-batch of observations at a time, so the dataset never has to fit in memory: feed the batches
+//! one by one to `partial_fit`, or let `fit` split a matrix into batches of `batch_size` rows.
+//!
+//! The current components \\(V_k\\) and their singular values \\(\Sigma_k\\) summarize all observations seen so far. A new batch \\(X_b\\) is merged
+//! by computing the singular value decomposition of
+//!
+//! \\[\begin{bmatrix} \Sigma_k V_k^T \\\\ X_b - \bar{x}_b \\\\ \sqrt{\frac{n_{seen} n_b}{n_{seen} + n_b}} (\bar{x}_{seen} - \bar{x}_b) \end{bmatrix}\\]
+//!
+//! where the last row corrects for the shift of the mean, and keeping the first \\(k\\) right singular vectors. Memory usage depends on the batch size
+//! rather than on the number of observations. The result is exact when the data has rank at most \\(k\\) and otherwise close to the components of PCA.
+//!
+//! With `whiten` enabled the projections are divided by the square roots of the explained variances, so every component has unit variance.
+//!
+//! Example:
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::decomposition::incremental_pca::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!                     &[5.1, 3.5, 1.4, 0.2],
+//!                     &[4.9, 3.0, 1.4, 0.2],
+//!                     &[4.7, 3.2, 1.3, 0.2],
+//!                     &[4.6, 3.1, 1.5, 0.2],
+//!                     &[7.0, 3.2, 4.7, 1.4],
+//!                     &[6.4, 3.2, 4.5, 1.5],
+//!                     &[6.9, 3.1, 4.9, 1.5],
+//!                     &[5.5, 2.3, 4.0, 1.3],
+//!                     ]);
+//!
+//! let mut ipca = IncrementalPCA::new(IncrementalPCAParameters::default().with_n_components(2));
+//! // batches could be read from disk one by one
+//! ipca.partial_fit(&x.slice(0..4, 0..4)).unwrap();
+//! ipca.partial_fit(&x.slice(4..8, 0..4)).unwrap();
+//!
+//! let x_reduced = ipca.transform(&x).unwrap();
+//! ```
+//!
+//! ## References:
+//! * ["Incremental Learning for Robust Visual Tracking", Ross D.A., Lim J., Lin R.-S., Yang M.-H., International Journal of Computer Vision, 2008](https://www.cs.toronto.edu/~dross/ivt/RossLimLinYang_ijcv.pdf)
+//! * ["Updating Formulae and a Pairwise Algorithm for Computing Sample Variances", Chan T.F., Golub G.H., LeVeque R.J., 1979](http://i.stanford.edu/pub/cstr/reports/cs/tr/79/773/CS-TR-79-773.pdf)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Transformer, UnsupervisedEstimator, UnsupervisedOnlineEstimator};
+use crate::error::Failed;
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::validation::{check_transform, check_x};
+
+/// Incremental PCA parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct IncrementalPCAParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of components to keep.
+    pub n_components: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Scale the projections to unit variance.
+    pub whiten: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of rows per batch used by `fit`, five times the number of features by default.
+    pub batch_size: Option<usize>,
+}
+
+impl IncrementalPCAParameters {
+    /// Number of components to keep.
+    pub fn with_n_components(mut self, n_components: usize) -> Self {
+        self.n_components = n_components;
+        self
+    }
+    /// Scale the projections to unit variance.
+    pub fn with_whiten(mut self, whiten: bool) -> Self {
+        self.whiten = whiten;
+        self
+    }
+    /// Number of rows per batch used by `fit`.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+}
+
+impl Default for IncrementalPCAParameters {
+    fn default() -> Self {
+        IncrementalPCAParameters {
+            n_components: 2,
+            whiten: false,
+            batch_size: None,
+        }
+    }
+}
+
+/// Incremental principal components analysis algorithm
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct IncrementalPCA<T: RealNumber, M: Matrix<T>> {
+    parameters: IncrementalPCAParameters,
+    components: M,
+    singular_values: Vec<T>,
+    explained_variance: Vec<T>,
+    mean: Vec<T>,
+    var: Vec<T>,
+    n_samples_seen: usize,
+}
+
+impl<T: RealNumber, M: Matrix<T>> UnsupervisedEstimator<M, IncrementalPCAParameters>
+    for IncrementalPCA<T, M>
+{
+    fn fit(x: &M, parameters: IncrementalPCAParameters) -> Result<Self, Failed> {
+        IncrementalPCA::fit(x, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Transformer<M> for IncrementalPCA<T, M> {
+    fn transform(&self, x: &M) -> Result<M, Failed> {
+        self.transform(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> UnsupervisedOnlineEstimator<M> for IncrementalPCA<T, M> {
+    fn partial_fit(&mut self, x: &M) -> Result<(), Failed> {
+        self.partial_fit(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> IncrementalPCA<T, M> {
+    /// Creates a model that hasn't seen any observations yet, feed it with `partial_fit`.
+    pub fn new(parameters: IncrementalPCAParameters) -> IncrementalPCA<T, M> {
+        IncrementalPCA {
+            parameters,
+            components: M::zeros(0, 0),
+            singular_values: Vec::new(),
+            explained_variance: Vec::new(),
+            mean: Vec::new(),
+            var: Vec::new(),
+            n_samples_seen: 0,
+        }
+    }
+
+    /// Fits incremental PCA to your data, processing `batch_size` rows at a time.
+    /// * `data` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(
+        data: &M,
+        parameters: IncrementalPCAParameters,
+    ) -> Result<IncrementalPCA<T, M>, Failed> {
+        let (n, p) = data.shape();
+        let batch_size = parameters.batch_size.unwrap_or(5 * p);
+        if batch_size < parameters.n_components {
+            return Err(Failed::fit(&format!(
+                "batch_size should be >= n_components ({}), got {}",
+                parameters.n_components, batch_size
+            )));
+        }
+
+        let mut ipca = IncrementalPCA::new(parameters);
+        let mut start = 0;
+        while start < n {
+            let mut end = (start + batch_size).min(n);
+            // a short last batch is merged with the previous one
+            if n - end < ipca.parameters.n_components {
+                end = n;
+            }
+            ipca.partial_fit(&data.slice(start..end, 0..p))?;
+            start = end;
+        }
+        Ok(ipca)
+    }
+
+    /// Updates the components with a batch of observations.
+    /// * `x` - _KxM_ batch with _K_ observations and _M_ features in each observation.
+    pub fn partial_fit(&mut self, x: &M) -> Result<(), Failed> {
+        check_x(x, false, Failed::fit)?;
+        let (n_batch, p) = x.shape();
+        let n_components = self.parameters.n_components;
+
+        if n_components == 0 || n_components > p {
+            return Err(Failed::fit(&format!(
+                "Number of components, n_components should be in [1, {}], got {}",
+                p, n_components
+            )));
+        }
+        if self.n_samples_seen == 0 {
+            if n_batch < n_components {
+                return Err(Failed::fit(&format!(
+                    "The first batch should have at least n_components ({}) rows, got {}",
+                    n_components, n_batch
+                )));
+            }
+        } else if p != self.mean.len() {
+            return Err(Failed::fit(&format!(
+                "Invalid number of features: {}, expected: {}",
+                p,
+                self.mean.len()
+            )));
+        }
+        if n_batch == 0 {
+            return Ok(());
+        }
+
+        let batch_mean = x.column_mean();
+        let mut batch_var = vec![T::zero(); p];
+        for (j, var_j) in batch_var.iter_mut().enumerate() {
+            for i in 0..n_batch {
+                *var_j += (x.get(i, j) - batch_mean[j]).square();
+            }
+            *var_j /= T::from_usize(n_batch).unwrap();
+        }
+
+        let n_seen = T::from_usize(self.n_samples_seen).unwrap();
+        let n_new = T::from_usize(n_batch).unwrap();
+        let n_total = n_seen + n_new;
+
+        let n_rows = if self.n_samples_seen == 0 {
+            n_batch
+        } else {
+            n_components + n_batch + 1
+        };
+        // the decomposition expects at least as many rows as columns, zero rows don't change the result
+        let mut stacked = M::zeros(n_rows.max(p), p);
+        let mut row = 0;
+        if self.n_samples_seen > 0 {
+            for k in 0..n_components {
+                for j in 0..p {
+                    stacked.set(row, j, self.singular_values[k] * self.components.get(k, j));
+                }
+                row += 1;
+            }
+        }
+        for i in 0..n_batch {
+            for (j, mean_j) in batch_mean.iter().enumerate() {
+                stacked.set(row, j, x.get(i, j) - *mean_j);
+            }
+            row += 1;
+        }
+        if self.n_samples_seen > 0 {
+            let correction = (n_seen * n_new / n_total).sqrt();
+            for (j, mean_j) in batch_mean.iter().enumerate() {
+                stacked.set(row, j, correction * (self.mean[j] - *mean_j));
+            }
+        }
+
+        let svd = stacked.svd()?;
+
+        let mut components = M::zeros(n_components, p);
+        for k in 0..n_components {
+            // flip signs so that the largest loading of every component is positive
+            let (largest, _) = (0..p).fold((T::zero(), T::zero()), |(largest, max_abs), j| {
+                let v = svd.V.get(j, k);
+                if v.abs() > max_abs {
+                    (v, v.abs())
+                } else {
+                    (largest, max_abs)
+                }
+            });
+            let sign = if largest < T::zero() {
+                -T::one()
+            } else {
+                T::one()
+            };
+            for j in 0..p {
+                components.set(k, j, sign * svd.V.get(j, k));
+            }
+        }
+
+        if self.n_samples_seen == 0 {
+            self.mean = batch_mean;
+            self.var = batch_var;
+        } else {
+            for j in 0..p {
+                let delta = batch_mean[j] - self.mean[j];
+                self.var[j] = (n_seen * self.var[j]
+                    + n_new * batch_var[j]
+                    + delta * delta * n_seen * n_new / n_total)
+                    / n_total;
+                self.mean[j] += delta * n_new / n_total;
+            }
+        }
+        self.n_samples_seen += n_batch;
+
+        let dof = if self.n_samples_seen > 1 {
+            T::from_usize(self.n_samples_seen - 1).unwrap()
+        } else {
+            T::one()
+        };
+        self.singular_values = svd.s[..n_components].to_vec();
+        self.explained_variance = self
+            .singular_values
+            .iter()
+            .map(|s| s.square() / dof)
+            .collect();
+        self.components = components;
+
+        Ok(())
+    }
+
+    /// Run dimensionality reduction for `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn transform(&self, x: &M) -> Result<M, Failed> {
+        check_transform(x)?;
+        let (nrows, ncols) = x.shape();
+        if self.n_samples_seen == 0 {
+            return Err(Failed::transform(
+                "The model hasn't seen any observations, call partial_fit first",
+            ));
+        }
+        if ncols != self.mean.len() {
+            return Err(Failed::transform(&format!(
+                "Invalid input vector size: {}, expected: {}",
+                ncols,
+                self.mean.len()
+            )));
+        }
+
+        let mut centered = x.clone();
+        for r in 0..nrows {
+            for c in 0..ncols {
+                centered.sub_element_mut(r, c, self.mean[c]);
+            }
+        }
+        let mut x_transformed = centered.ab(false, &self.components, true);
+        if self.parameters.whiten {
+            for (c, variance) in self.explained_variance.iter().enumerate() {
+                let scale = variance.sqrt();
+                for r in 0..nrows {
+                    x_transformed.div_element_mut(r, c, scale);
+                }
+            }
+        }
+        Ok(x_transformed)
+    }
+
+    /// Principal components, one component per row.
+    pub fn components(&self) -> &M {
+        &self.components
+    }
+
+    /// Singular values of the components.
+    pub fn singular_values(&self) -> &Vec<T> {
+        &self.singular_values
+    }
+
+    /// Variance explained by every component.
+    pub fn explained_variance(&self) -> &Vec<T> {
+        &self.explained_variance
+    }
+
+    /// Proportion of the total variance explained by every component.
+    pub fn explained_variance_ratio(&self) -> Vec<T> {
+        let n = T::from_usize(self.n_samples_seen).unwrap();
+        let dof = if self.n_samples_seen > 1 {
+            n - T::one()
+        } else {
+            T::one()
+        };
+        let total = self.var.iter().copied().sum::<T>() * n / dof;
+        self.explained_variance.iter().map(|v| *v / total).collect()
+    }
+
+    /// Mean of every feature over all observations seen so far.
+    pub fn mean(&self) -> &Vec<T> {
+        &self.mean
+    }
+
+    /// Number of observations seen so far.
+    pub fn n_samples_seen(&self) -> usize {
+        self.n_samples_seen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+
+    fn iris() -> DenseMatrix<f64> {
+        DenseMatrix::from_2d_array(&[
+            &[5.1, 3.5, 1.4, 0.2],
+            &[4.9, 3.0, 1.4, 0.2],
+            &[4.7, 3.2, 1.3, 0.2],
+            &[4.6, 3.1, 1.5, 0.2],
+            &[5.0, 3.6, 1.4, 0.2],
+            &[5.4, 3.9, 1.7, 0.4],
+            &[4.6, 3.4, 1.4, 0.3],
+            &[5.0, 3.4, 1.5, 0.2],
+            &[4.4, 2.9, 1.4, 0.2],
+            &[4.9, 3.1, 1.5, 0.1],
+            &[7.0, 3.2, 4.7, 1.4],
+            &[6.4, 3.2, 4.5, 1.5],
+            &[6.9, 3.1, 4.9, 1.5],
+            &[5.5, 2.3, 4.0, 1.3],
+            &[6.5, 2.8, 4.6, 1.5],
+            &[5.7, 2.8, 4.5, 1.3],
+            &[6.3, 3.3, 4.7, 1.6],
+            &[4.9, 2.4, 3.3, 1.0],
+            &[6.6, 2.9, 4.6, 1.3],
+            &[5.2, 2.7, 3.9, 1.4],
+        ])
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn batches_match_single_pass() {
+        let x = iris();
+        let parameters = IncrementalPCAParameters::default().with_n_components(4);
+        let full = IncrementalPCA::fit(&x, parameters.clone().with_batch_size(20)).unwrap();
+        let batched = IncrementalPCA::fit(&x, parameters.with_batch_size(6)).unwrap();
+
+        assert_eq!(batched.n_samples_seen(), 20);
+        assert!(batched.components().approximate_eq(full.components(), 1e-6));
+        for (a, b) in batched
+            .explained_variance()
+            .iter()
+            .zip(full.explained_variance())
+        {
+            assert!((a - b).abs() < 1e-6);
+        }
+        for (a, b) in batched.mean().iter().zip(x.column_mean().iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        // all components explain all of the variance
+        let ratio: f64 = batched.explained_variance_ratio().iter().sum();
+        assert!((ratio - 1.0).abs() < 1e-8);
+        assert!(batched
+            .transform(&x)
+            .unwrap()
+            .approximate_eq(&full.transform(&x).unwrap(), 1e-6));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn truncated_components() {
+        let x = iris();
+        let full = IncrementalPCA::fit(&x, IncrementalPCAParameters::default().with_batch_size(20))
+            .unwrap();
+        let mut batched = IncrementalPCA::new(IncrementalPCAParameters::default());
+        batched.partial_fit(&x.slice(0..5, 0..4)).unwrap();
+        batched.partial_fit(&x.slice(5..12, 0..4)).unwrap();
+        batched.partial_fit(&x.slice(12..20, 0..4)).unwrap();
+
+        assert_eq!(batched.components().shape(), (2, 4));
+        assert!(batched.components().approximate_eq(full.components(), 0.05));
+        assert!(batched.explained_variance_ratio()[0] > 0.9);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn whiten() {
+        let x = iris();
+        let ipca = IncrementalPCA::fit(
+            &x,
+            IncrementalPCAParameters::default()
+                .with_whiten(true)
+                .with_batch_size(20),
+        )
+        .unwrap();
+        let transformed = ipca.transform(&x).unwrap();
+        for c in 0..2 {
+            let column = transformed.get_col_as_vec(c);
+            let mean = column.iter().sum::<f64>() / 20.0;
+            let var = column.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / 19.0;
+            assert!(mean.abs() < 1e-8);
+            assert!((var - 1.0).abs() < 1e-8);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn invalid_input() {
+        let x = iris();
+        let mut ipca: IncrementalPCA<f64, DenseMatrix<f64>> =
+            IncrementalPCA::new(IncrementalPCAParameters::default().with_n_components(3));
+        assert!(ipca.transform(&x).is_err());
+        assert!(ipca.partial_fit(&x.slice(0..2, 0..4)).is_err());
+        ipca.partial_fit(&x.slice(0..5, 0..4)).unwrap();
+        assert!(ipca.partial_fit(&x.slice(5..10, 0..3)).is_err());
+        assert!(ipca.transform(&x.slice(0..5, 0..3)).is_err());
+        assert!(
+            IncrementalPCA::fit(&x, IncrementalPCAParameters::default().with_n_components(5))
+                .is_err()
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let x = iris();
+        let ipca = IncrementalPCA::fit(&x, Default::default()).unwrap();
+        let deserialized: IncrementalPCA<f64, DenseMatrix<f64>> =
+            serde_json::from_str(&serde_json::to_string(&ipca).unwrap()).unwrap();
+        assert!(deserialized
+            .transform(&x)
+            .unwrap()
+            .approximate_eq(&ipca.transform(&x).unwrap(), 1e-12));
+    }
+}
@@ -11,6 +11,10 @@
 //! ## References
 //! * ["An Introduction to Statistical Learning", James G., Witten D., Hastie T., Tibshirani R., 6.3 Dimension Reduction Methods](http://faculty.marshall.usc.edu/gareth-james/ISL/)
 
+/// Independent component analysis separates a multivariate signal into statistically independent components.
+pub mod fastica;
+/// Incremental PCA updates the principal components from mini-batches of observations.
+pub mod incremental_pca;
 /// PCA is a popular approach for deriving a low-dimensional set of features from a large set of variables.
 pub mod pca;
 pub mod svd;
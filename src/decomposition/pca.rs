@@ -45,7 +45,8 @@
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
-use std::fmt::Debug;
+use alloc::vec::Vec;
+use core::fmt::Debug;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -54,6 +55,7 @@ use crate::api::{Transformer, UnsupervisedEstimator};
 use crate::error::Failed;
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
+use crate::validation::{check_transform, check_x};
 
 /// Principal components analysis algorithm
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -214,6 +216,7 @@ impl<T: RealNumber, M: Matrix<T>> PCA<T, M> {
     /// * `n_components` - number of components to keep.
     /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
     pub fn fit(data: &M, parameters: PCAParameters) -> Result<PCA<T, M>, Failed> {
+        check_x(data, false, Failed::fit)?;
         let (m, n) = data.shape();
 
         if parameters.n_components > n {
@@ -321,6 +324,7 @@ impl<T: RealNumber, M: Matrix<T>> PCA<T, M> {
     /// Run dimensionality reduction for `x`
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn transform(&self, x: &M) -> Result<M, Failed> {
+        check_transform(x)?;
         let (nrows, ncols) = x.shape();
         let (_, n_components) = self.projection.shape();
         if ncols != self.mu.len() {
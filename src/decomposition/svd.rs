@@ -43,8 +43,9 @@
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
-use std::fmt::Debug;
-use std::marker::PhantomData;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::marker::PhantomData;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -53,6 +54,7 @@ use crate::api::{Transformer, UnsupervisedEstimator};
 use crate::error::Failed;
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
+use crate::validation::{check_transform, check_x};
 
 /// SVD
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -165,6 +167,7 @@ impl<T: RealNumber, M: Matrix<T>> SVD<T, M> {
     /// * `n_components` - number of components to keep.
     /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
     pub fn fit(x: &M, parameters: SVDParameters) -> Result<SVD<T, M>, Failed> {
+        check_x(x, false, Failed::fit)?;
         let (_, p) = x.shape();
 
         if parameters.n_components >= p {
@@ -187,6 +190,7 @@ impl<T: RealNumber, M: Matrix<T>> SVD<T, M> {
     /// Run dimensionality reduction for `x`
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn transform(&self, x: &M) -> Result<M, Failed> {
+        check_transform(x)?;
         let (n, p) = x.shape();
         let (p_c, k) = self.components.shape();
         if p_c != p {
@@ -0,0 +1,908 @@
+//! # AdaBoost
+//! AdaBoost is a boosting algorithm that fits a sequence of weak learners, e.g. [decision stumps](../../tree/index.html), to repeatedly reweighted
+//! versions of the training set. After every round the weights of the observations that were misclassified are increased, so that the next learner
+//! focuses on the hard cases. The final prediction combines the predictions of all learners with a weighted majority vote.
+//! See [ensemble models](../index.html) for more details.
+//!
+//! SmartCore implements two multiclass variants of the algorithm:
+//!
+//! * SAMME, that uses predicted class labels and weights every learner by \\(\alpha_m = \eta \left( \log \frac{1 - err_m}{err_m} + \log(K - 1) \right)\\),
+//!   where \\(err_m\\) is the weighted error of the learner, \\(\eta\\) is the learning rate and \\(K\\) is the number of classes.
+//!   With two classes it is equivalent to the original AdaBoost.M1.
+//! * SAMME.R, that uses class probabilities predicted by every learner. It typically converges faster than SAMME.
+//!
+//! Any classifier that implements [`SupervisedEstimatorWeighted`](../../api/trait.SupervisedEstimatorWeighted.html), [`Predictor`](../../api/trait.Predictor.html)
+//! and [`PredictorProba`](../../api/trait.PredictorProba.html) can be used as the base estimator. By default the base estimator is a decision tree with depth 1.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::ensemble::adaboost::*;
+//!
+//! // Iris dataset
+//! let x = DenseMatrix::from_2d_array(&[
+//!              &[5.1, 3.5, 1.4, 0.2],
+//!              &[4.9, 3.0, 1.4, 0.2],
+//!              &[4.7, 3.2, 1.3, 0.2],
+//!              &[4.6, 3.1, 1.5, 0.2],
+//!              &[5.0, 3.6, 1.4, 0.2],
+//!              &[5.4, 3.9, 1.7, 0.4],
+//!              &[4.6, 3.4, 1.4, 0.3],
+//!              &[5.0, 3.4, 1.5, 0.2],
+//!              &[4.4, 2.9, 1.4, 0.2],
+//!              &[4.9, 3.1, 1.5, 0.1],
+//!              &[7.0, 3.2, 4.7, 1.4],
+//!              &[6.4, 3.2, 4.5, 1.5],
+//!              &[6.9, 3.1, 4.9, 1.5],
+//!              &[5.5, 2.3, 4.0, 1.3],
+//!              &[6.5, 2.8, 4.6, 1.5],
+//!              &[5.7, 2.8, 4.5, 1.3],
+//!              &[6.3, 3.3, 4.7, 1.6],
+//!              &[4.9, 2.4, 3.3, 1.0],
+//!              &[6.6, 2.9, 4.6, 1.3],
+//!              &[5.2, 2.7, 3.9, 1.4],
+//!         ]);
+//! let y = vec![
+//!              0., 0., 0., 0., 0., 0., 0., 0.,
+//!              1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1.,
+//!         ];
+//!
+//! let classifier: AdaBoostClassifier<f64> =
+//!     AdaBoostClassifier::fit(&x, &y, AdaBoostClassifierParameters::default()).unwrap();
+//! let y_hat = classifier.predict(&x).unwrap(); // use the same data for prediction
+//! let alphas = classifier.estimator_weights();
+//! ```
+//!
+//! ## Early stopping
+//!
+//! Adding estimators eventually overfits the training set. Set `n_iter_no_change` to monitor the accuracy on a validation set after every boosting round
+//! and to stop when it has not improved by more than `tol` for `n_iter_no_change` rounds. The validation set is either a random `validation_fraction` of the
+//! training set, or is passed explicitly with `fit_with_validation`. The estimators fitted after the [best round](crate::ensemble::adaboost::AdaBoostClassifier::best_iteration) are discarded.
+//!
+//! ## References:
+//!
+//! * ["A Decision-Theoretic Generalization of On-Line Learning and an Application to Boosting", Freund Y., Schapire R. E., 1997](https://doi.org/10.1006/jcss.1997.1504)
+//! * ["Multi-class AdaBoost", Zhu J., Zou H., Rosset S., Hastie T., 2009](https://doi.org/10.4310/SII.2009.v2.n3.a8)
+//! * ["An Introduction to Statistical Learning", James G., Witten D., Hastie T., Tibshirani R., 8.2 Bagging, Random Forests, Boosting](http://faculty.marshall.usc.edu/gareth-james/ISL/)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use core::default::Default;
+use core::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{
+    FeatureImportance, Predictor, PredictorProba, SupervisedEstimator, SupervisedEstimatorWeighted,
+};
+use crate::ensemble::{aggregate_importances, aggregate_split_counts};
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::model_selection::train_test_split;
+use crate::tree::decision_tree_classifier::{
+    which_max, DecisionTreeClassifier, DecisionTreeClassifierParameters,
+};
+use crate::validation::check_fit;
+
+/// Variant of the multiclass AdaBoost algorithm
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum AdaBoostAlgorithm {
+    /// SAMME, boosting with predicted class labels
+    Samme,
+    /// SAMME.R, boosting with predicted class probabilities
+    #[default]
+    SammeR,
+}
+
+/// Parameters of the AdaBoost algorithm.
+/// `B` is the type of parameters of the base estimator.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct AdaBoostClassifierParameters<B> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of estimators. Boosting stops early if an estimator fits the training set perfectly.
+    pub n_estimators: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Shrinks the contribution of each estimator. There is a trade-off between `learning_rate` and `n_estimators`.
+    pub learning_rate: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Variant of the algorithm.
+    pub algorithm: AdaBoostAlgorithm,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Enables early stopping: boosting stops when the accuracy on the validation set has not improved for `n_iter_no_change` rounds.
+    /// See [early stopping](index.html#early-stopping).
+    pub n_iter_no_change: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of the training set set aside as a validation set for early stopping. Ignored when the validation set is passed explicitly.
+    pub validation_fraction: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Minimum increase of the validation accuracy that counts as an improvement.
+    pub tol: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used to split the validation set from the training set.
+    pub seed: Option<u64>,
+    /// Parameters of the base estimator.
+    pub base_estimator: B,
+}
+
+/// AdaBoost Classifier
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct AdaBoostClassifier<T: RealNumber, E = DecisionTreeClassifier<T>> {
+    algorithm: AdaBoostAlgorithm,
+    estimators: Vec<E>,
+    estimator_weights: Vec<T>,
+    estimator_errors: Vec<T>,
+    classes: Vec<T>,
+    validation_scores: Option<Vec<T>>,
+    best_iteration: usize,
+}
+
+impl<B> AdaBoostClassifierParameters<B> {
+    /// The maximum number of estimators.
+    pub fn with_n_estimators(mut self, n_estimators: usize) -> Self {
+        self.n_estimators = n_estimators;
+        self
+    }
+    /// Shrinks the contribution of each estimator.
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+    /// Variant of the algorithm.
+    pub fn with_algorithm(mut self, algorithm: AdaBoostAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+    /// Enables early stopping after `n_iter_no_change` rounds without improvement of the validation accuracy.
+    pub fn with_n_iter_no_change(mut self, n_iter_no_change: usize) -> Self {
+        self.n_iter_no_change = Some(n_iter_no_change);
+        self
+    }
+    /// Fraction of the training set set aside as a validation set for early stopping.
+    pub fn with_validation_fraction(mut self, validation_fraction: f32) -> Self {
+        self.validation_fraction = validation_fraction;
+        self
+    }
+    /// Minimum increase of the validation accuracy that counts as an improvement.
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Seed used to split the validation set from the training set.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+    /// Parameters of the base estimator.
+    pub fn with_base_estimator<C>(self, base_estimator: C) -> AdaBoostClassifierParameters<C> {
+        AdaBoostClassifierParameters {
+            n_estimators: self.n_estimators,
+            learning_rate: self.learning_rate,
+            algorithm: self.algorithm,
+            n_iter_no_change: self.n_iter_no_change,
+            validation_fraction: self.validation_fraction,
+            tol: self.tol,
+            seed: self.seed,
+            base_estimator,
+        }
+    }
+}
+
+impl Default for AdaBoostClassifierParameters<DecisionTreeClassifierParameters> {
+    fn default() -> Self {
+        AdaBoostClassifierParameters {
+            n_estimators: 50,
+            learning_rate: 1f64,
+            algorithm: AdaBoostAlgorithm::default(),
+            n_iter_no_change: None,
+            validation_fraction: 0.1,
+            tol: 0f64,
+            seed: None,
+            base_estimator: DecisionTreeClassifierParameters::default().with_max_depth(1),
+        }
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, B: Clone, E>
+    SupervisedEstimator<M, M::RowVector, AdaBoostClassifierParameters<B>>
+    for AdaBoostClassifier<T, E>
+where
+    E: SupervisedEstimatorWeighted<M, M::RowVector, B>
+        + Predictor<M, M::RowVector>
+        + PredictorProba<M>,
+{
+    fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: AdaBoostClassifierParameters<B>,
+    ) -> Result<Self, Failed> {
+        AdaBoostClassifier::fit(x, y, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, E> Predictor<M, M::RowVector> for AdaBoostClassifier<T, E>
+where
+    E: Predictor<M, M::RowVector> + PredictorProba<M>,
+{
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<T: RealNumber> FeatureImportance<T> for AdaBoostClassifier<T, DecisionTreeClassifier<T>> {
+    fn feature_importance(&self) -> Vec<T> {
+        self.feature_importances()
+    }
+}
+
+impl<T: RealNumber, E> AdaBoostClassifier<T, E> {
+    /// Build an AdaBoost classifier from the training data.
+    /// When early stopping is enabled with `n_iter_no_change`, a `validation_fraction` of the training data is set aside as a validation set.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target class values
+    /// * `parameters` - AdaBoost parameters, including parameters of the base estimator
+    pub fn fit<M: Matrix<T>, B: Clone>(
+        x: &M,
+        y: &M::RowVector,
+        parameters: AdaBoostClassifierParameters<B>,
+    ) -> Result<AdaBoostClassifier<T, E>, Failed>
+    where
+        E: SupervisedEstimatorWeighted<M, M::RowVector, B>
+            + Predictor<M, M::RowVector>
+            + PredictorProba<M>,
+    {
+        if parameters.n_iter_no_change.is_none() {
+            return AdaBoostClassifier::fit_boosting(x, y, None, parameters);
+        }
+
+        if !(parameters.validation_fraction > 0f32 && parameters.validation_fraction < 1f32) {
+            return Err(Failed::fit("validation_fraction should be between 0 and 1"));
+        }
+        let n = x.shape().0;
+        if y.len() != n {
+            return Err(Failed::fit(&format!(
+                "Size of x ({}) does not match size of y ({})",
+                n,
+                y.len()
+            )));
+        }
+        if ((n as f32) * parameters.validation_fraction) < 1f32 {
+            return Err(Failed::fit(&format!(
+                "Number of samples ({}) is too small to set aside a validation set",
+                n
+            )));
+        }
+
+        let (x_train, x_val, y_train, y_val) =
+            train_test_split(x, y, parameters.validation_fraction, true, parameters.seed);
+
+        AdaBoostClassifier::fit_boosting(&x_train, &y_train, Some((&x_val, &y_val)), parameters)
+    }
+
+    /// Build an AdaBoost classifier from the training data, monitoring the accuracy on the given validation set after every boosting round.
+    /// Boosting stops early when `n_iter_no_change` is set and the accuracy has not improved for `n_iter_no_change` rounds.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target class values
+    /// * `x_val` - _KxM_ matrix with _K_ validation observations.
+    /// * `y_val` - the validation class values
+    /// * `parameters` - AdaBoost parameters, including parameters of the base estimator
+    pub fn fit_with_validation<M: Matrix<T>, B: Clone>(
+        x: &M,
+        y: &M::RowVector,
+        x_val: &M,
+        y_val: &M::RowVector,
+        parameters: AdaBoostClassifierParameters<B>,
+    ) -> Result<AdaBoostClassifier<T, E>, Failed>
+    where
+        E: SupervisedEstimatorWeighted<M, M::RowVector, B>
+            + Predictor<M, M::RowVector>
+            + PredictorProba<M>,
+    {
+        if x_val.shape().0 != y_val.len() {
+            return Err(Failed::fit(&format!(
+                "Size of x_val ({}) does not match size of y_val ({})",
+                x_val.shape().0,
+                y_val.len()
+            )));
+        }
+        AdaBoostClassifier::fit_boosting(x, y, Some((x_val, y_val)), parameters)
+    }
+
+    fn fit_boosting<M: Matrix<T>, B: Clone>(
+        x: &M,
+        y: &M::RowVector,
+        validation: Option<(&M, &M::RowVector)>,
+        parameters: AdaBoostClassifierParameters<B>,
+    ) -> Result<AdaBoostClassifier<T, E>, Failed>
+    where
+        E: SupervisedEstimatorWeighted<M, M::RowVector, B>
+            + Predictor<M, M::RowVector>
+            + PredictorProba<M>,
+    {
+        let (n, _) = x.shape();
+        if y.len() != n {
+            return Err(Failed::fit(&format!(
+                "Size of x ({}) does not match size of y ({})",
+                n,
+                y.len()
+            )));
+        }
+        check_fit(x, y)?;
+        if let Some((x_val, y_val)) = validation {
+            check_fit(x_val, y_val)?;
+        }
+        if parameters.n_estimators < 1 {
+            return Err(Failed::fit("n_estimators should be > 0"));
+        }
+        if parameters.learning_rate <= 0f64 {
+            return Err(Failed::fit("learning_rate should be > 0"));
+        }
+
+        let classes = y.unique();
+        let k = classes.len();
+        if k < 2 {
+            return Err(Failed::fit(&format!(
+                "Incorrect number of classes: {}. Should be >= 2.",
+                k
+            )));
+        }
+        let k_t = T::from(k).unwrap();
+        let learning_rate = T::from_f64(parameters.learning_rate).unwrap();
+
+        let yi: Vec<usize> = (0..n)
+            .map(|i| {
+                let y_i = y.get(i);
+                classes.iter().position(|c| *c == y_i).unwrap()
+            })
+            .collect();
+
+        let mut sample_weight = vec![T::one() / T::from(n).unwrap(); n];
+        let mut estimators: Vec<E> = Vec::with_capacity(parameters.n_estimators);
+        let mut estimator_weights: Vec<T> = Vec::with_capacity(parameters.n_estimators);
+        let mut estimator_errors: Vec<T> = Vec::with_capacity(parameters.n_estimators);
+
+        let tol = T::from_f64(parameters.tol).unwrap();
+        let mut validation_scores: Vec<T> = Vec::new();
+        let mut validation_decision = validation.map(|(x_val, _)| M::zeros(x_val.shape().0, k));
+        let mut best_iteration = 0;
+
+        for m in 0..parameters.n_estimators {
+            let estimator = E::fit_with_weights(
+                x,
+                y,
+                &M::RowVector::from_array(&sample_weight),
+                parameters.base_estimator.clone(),
+            )?;
+
+            let (incorrect, probabilities) = match parameters.algorithm {
+                AdaBoostAlgorithm::Samme => {
+                    let y_hat = estimator.predict(x)?;
+                    let incorrect: Vec<bool> = (0..n).map(|i| y_hat.get(i) != y.get(i)).collect();
+                    (incorrect, None)
+                }
+                AdaBoostAlgorithm::SammeR => {
+                    let probabilities = clip_proba(estimator.predict_proba(x)?, k)?;
+                    let incorrect: Vec<bool> = (0..n)
+                        .map(|i| which_max(&probabilities.get_row_as_vec(i)) != yi[i])
+                        .collect();
+                    (incorrect, Some(probabilities))
+                }
+            };
+
+            let error = sample_weight
+                .iter()
+                .zip(incorrect.iter())
+                .filter(|(_, incorrect_i)| **incorrect_i)
+                .fold(T::zero(), |acc, (w_i, _)| acc + *w_i);
+
+            let perfect_fit = error <= T::zero();
+
+            let estimator_weight = match probabilities {
+                _ if perfect_fit => T::one(),
+                None => {
+                    if error >= T::one() - T::one() / k_t {
+                        if m == 0 {
+                            return Err(Failed::fit(
+                                "Base estimator is not better than random guessing, boosting cannot proceed",
+                            ));
+                        }
+                        break;
+                    }
+                    let alpha =
+                        learning_rate * (((T::one() - error) / error).ln() + (k_t - T::one()).ln());
+                    for (w_i, incorrect_i) in sample_weight.iter_mut().zip(incorrect.iter()) {
+                        if *incorrect_i {
+                            *w_i *= alpha.exp();
+                        }
+                    }
+                    alpha
+                }
+                Some(probabilities) => {
+                    let factor = -learning_rate * (k_t - T::one()) / k_t;
+                    for (i, w_i) in sample_weight.iter_mut().enumerate() {
+                        let mut loss = T::zero();
+                        for l in 0..k {
+                            let y_code = if l == yi[i] {
+                                T::one()
+                            } else {
+                                -T::one() / (k_t - T::one())
+                            };
+                            loss += y_code * probabilities.get(i, l).ln();
+                        }
+                        *w_i *= (factor * loss).exp();
+                    }
+                    T::one()
+                }
+            };
+
+            if let (Some((x_val, y_val)), Some(decision)) =
+                (validation, validation_decision.as_mut())
+            {
+                add_decision(
+                    &parameters.algorithm,
+                    &classes,
+                    &estimator,
+                    estimator_weight,
+                    x_val,
+                    decision,
+                )?;
+                let n_val = y_val.len();
+                let n_correct = (0..n_val)
+                    .filter(|i| classes[which_max(&decision.get_row_as_vec(*i))] == y_val.get(*i))
+                    .count();
+                let score = T::from(n_correct).unwrap() / T::from(n_val).unwrap();
+                if m == 0 || score > validation_scores[best_iteration] + tol {
+                    best_iteration = m;
+                }
+                validation_scores.push(score);
+            }
+
+            estimators.push(estimator);
+            estimator_weights.push(estimator_weight);
+            estimator_errors.push(error);
+            debug!(
+                "AdaBoost round {}/{}: training error {}, estimator weight {}, validation accuracy {:?}",
+                m + 1,
+                parameters.n_estimators,
+                error,
+                estimator_weight,
+                validation_scores.last()
+            );
+
+            if perfect_fit {
+                break;
+            }
+            if let Some(n_iter_no_change) = parameters.n_iter_no_change {
+                if validation.is_some() && m - best_iteration >= n_iter_no_change {
+                    break;
+                }
+            }
+
+            let total = sample_weight.iter().fold(T::zero(), |acc, w_i| acc + *w_i);
+            if !total.is_finite() || total <= T::zero() {
+                break;
+            }
+            for w_i in sample_weight.iter_mut() {
+                *w_i /= total;
+            }
+        }
+
+        let validation_scores = if validation.is_some() {
+            if parameters.n_iter_no_change.is_some() {
+                estimators.truncate(best_iteration + 1);
+                estimator_weights.truncate(best_iteration + 1);
+                estimator_errors.truncate(best_iteration + 1);
+            }
+            Some(validation_scores)
+        } else {
+            best_iteration = estimators.len() - 1;
+            None
+        };
+
+        Ok(AdaBoostClassifier {
+            algorithm: parameters.algorithm,
+            estimators,
+            estimator_weights,
+            estimator_errors,
+            classes,
+            validation_scores,
+            best_iteration,
+        })
+    }
+
+    /// Predict class value for `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed>
+    where
+        E: Predictor<M, M::RowVector> + PredictorProba<M>,
+    {
+        let (n, _) = x.shape();
+        let mut scores = M::zeros(n, self.classes.len());
+
+        for (estimator, estimator_weight) in
+            self.estimators.iter().zip(self.estimator_weights.iter())
+        {
+            add_decision(
+                &self.algorithm,
+                &self.classes,
+                estimator,
+                *estimator_weight,
+                x,
+                &mut scores,
+            )?;
+        }
+
+        let mut result = M::RowVector::zeros(n);
+        for i in 0..n {
+            result.set(i, self.classes[which_max(&scores.get_row_as_vec(i))]);
+        }
+
+        Ok(result)
+    }
+
+    /// Weight of every fitted estimator in the final vote. All weights are equal to 1 with SAMME.R.
+    pub fn estimator_weights(&self) -> &Vec<T> {
+        &self.estimator_weights
+    }
+
+    /// Weighted training error of every fitted estimator, computed at the boosting round it was fitted in.
+    pub fn estimator_errors(&self) -> &Vec<T> {
+        &self.estimator_errors
+    }
+
+    /// Fitted estimators, in the order they were fitted.
+    pub fn estimators(&self) -> &Vec<E> {
+        &self.estimators
+    }
+
+    /// Index of the boosting round with the best validation accuracy. With early stopping the estimators fitted after this round are discarded.
+    /// Without a validation set this is the index of the last round.
+    pub fn best_iteration(&self) -> usize {
+        self.best_iteration
+    }
+
+    /// Accuracy on the validation set after every boosting round, including the rounds discarded by early stopping.
+    /// `None` when the classifier was fitted without a validation set.
+    pub fn validation_scores(&self) -> Option<&Vec<T>> {
+        self.validation_scores.as_ref()
+    }
+}
+
+impl<T: RealNumber> AdaBoostClassifier<T, DecisionTreeClassifier<T>> {
+    /// Returns the impurity-based importance of every feature averaged over all trees, weighted by [estimator weights](AdaBoostClassifier::estimator_weights)
+    /// and normalized to sum to 1. See [tree inspection](../../tree/node/index.html).
+    pub fn feature_importances(&self) -> Vec<T> {
+        aggregate_importances(
+            self.estimators
+                .iter()
+                .zip(self.estimator_weights.iter())
+                .map(|(tree, weight)| (*weight, tree.feature_importances())),
+        )
+    }
+
+    /// Returns the number of nodes split on every feature, summed over all trees.
+    pub fn split_counts(&self) -> Vec<usize> {
+        aggregate_split_counts(self.estimators.iter().map(|tree| tree.split_counts()))
+    }
+}
+
+/// Adds the contribution of a single estimator to the decision function of every class.
+fn add_decision<T: RealNumber, M: Matrix<T>, E>(
+    algorithm: &AdaBoostAlgorithm,
+    classes: &[T],
+    estimator: &E,
+    estimator_weight: T,
+    x: &M,
+    decision: &mut M,
+) -> Result<(), Failed>
+where
+    E: Predictor<M, M::RowVector> + PredictorProba<M>,
+{
+    let (n, _) = x.shape();
+    let k = classes.len();
+    let k_t = T::from(k).unwrap();
+
+    match algorithm {
+        AdaBoostAlgorithm::Samme => {
+            let y_hat = estimator.predict(x)?;
+            for i in 0..n {
+                let y_hat_i = y_hat.get(i);
+                if let Some(l) = classes.iter().position(|c| *c == y_hat_i) {
+                    decision.add_element_mut(i, l, estimator_weight);
+                }
+            }
+        }
+        AdaBoostAlgorithm::SammeR => {
+            let probabilities = clip_proba(estimator.predict_proba(x)?, k)?;
+            for i in 0..n {
+                let log_p: Vec<T> = (0..k).map(|l| probabilities.get(i, l).ln()).collect();
+                let mean_log_p = log_p.iter().fold(T::zero(), |acc, p| acc + *p) / k_t;
+                for (l, log_p_l) in log_p.iter().enumerate() {
+                    decision.add_element_mut(i, l, (k_t - T::one()) * (*log_p_l - mean_log_p));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates probabilities predicted by a base estimator and bounds them away from zero, so that their logarithm is finite.
+fn clip_proba<T: RealNumber, M: Matrix<T>>(mut probabilities: M, k: usize) -> Result<M, Failed> {
+    let (n, n_classes) = probabilities.shape();
+    if n_classes != k {
+        return Err(Failed::fit(&format!(
+            "Base estimator predicted probabilities for {} classes, expected {}",
+            n_classes, k
+        )));
+    }
+    for i in 0..n {
+        for l in 0..k {
+            probabilities.set(i, l, probabilities.get(i, l).max(T::epsilon()));
+        }
+    }
+    Ok(probabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::metrics::*;
+
+    fn iris() -> (DenseMatrix<f64>, Vec<f64>) {
+        let x = DenseMatrix::from_2d_array(&[
+            &[5.1, 3.5, 1.4, 0.2],
+            &[4.9, 3.0, 1.4, 0.2],
+            &[4.7, 3.2, 1.3, 0.2],
+            &[4.6, 3.1, 1.5, 0.2],
+            &[5.0, 3.6, 1.4, 0.2],
+            &[5.4, 3.9, 1.7, 0.4],
+            &[4.6, 3.4, 1.4, 0.3],
+            &[5.0, 3.4, 1.5, 0.2],
+            &[4.4, 2.9, 1.4, 0.2],
+            &[4.9, 3.1, 1.5, 0.1],
+            &[7.0, 3.2, 4.7, 1.4],
+            &[6.4, 3.2, 4.5, 1.5],
+            &[6.9, 3.1, 4.9, 1.5],
+            &[5.5, 2.3, 4.0, 1.3],
+            &[6.5, 2.8, 4.6, 1.5],
+            &[5.7, 2.8, 4.5, 1.3],
+            &[6.3, 3.3, 4.7, 1.6],
+            &[4.9, 2.4, 3.3, 1.0],
+            &[6.6, 2.9, 4.6, 1.3],
+            &[5.2, 2.7, 3.9, 1.4],
+            &[6.3, 3.3, 6.0, 2.5],
+            &[5.8, 2.7, 5.1, 1.9],
+            &[7.1, 3.0, 5.9, 2.1],
+            &[6.3, 2.9, 5.6, 1.8],
+            &[6.5, 3.0, 5.8, 2.2],
+            &[4.9, 2.5, 4.5, 1.7],
+            &[6.7, 2.5, 5.8, 1.8],
+            &[6.0, 2.2, 5.0, 1.5],
+            &[6.9, 3.2, 5.7, 2.3],
+            &[6.3, 2.8, 5.1, 1.5],
+        ]);
+        let y = vec![
+            0., 0., 0., 0., 0., 0., 0., 0., 0., 0., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 2., 2.,
+            2., 2., 2., 2., 2., 2., 2., 2.,
+        ];
+        (x, y)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_predict_iris_samme() {
+        let (x, y) = iris();
+
+        let classifier: AdaBoostClassifier<f64> = AdaBoostClassifier::fit(
+            &x,
+            &y,
+            AdaBoostClassifierParameters::default().with_algorithm(AdaBoostAlgorithm::Samme),
+        )
+        .unwrap();
+
+        let y_hat = classifier.predict(&x).unwrap();
+
+        assert!(accuracy(&y, &y_hat) >= 0.9);
+        assert_eq!(
+            classifier.estimator_weights().len(),
+            classifier.estimators().len()
+        );
+        assert!(classifier.estimator_weights().iter().all(|w| *w > 0.));
+        assert!(classifier.estimator_errors().iter().all(|e| *e < 2. / 3.));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_predict_iris_samme_r() {
+        let (x, y) = iris();
+
+        let classifier: AdaBoostClassifier<f64> =
+            AdaBoostClassifier::fit(&x, &y, AdaBoostClassifierParameters::default()).unwrap();
+
+        let y_hat = classifier.predict(&x).unwrap();
+
+        assert!(accuracy(&y, &y_hat) >= 0.9);
+        assert!(classifier.estimator_weights().iter().all(|w| *w == 1.));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn boosting_improves_stump() {
+        let (x, y) = iris();
+
+        let stump = DecisionTreeClassifier::fit(
+            &x,
+            &y,
+            DecisionTreeClassifierParameters::default().with_max_depth(1),
+        )
+        .unwrap();
+
+        let classifier: AdaBoostClassifier<f64> = AdaBoostClassifier::fit(
+            &x,
+            &y,
+            AdaBoostClassifierParameters::default()
+                .with_algorithm(AdaBoostAlgorithm::Samme)
+                .with_n_estimators(20),
+        )
+        .unwrap();
+
+        assert!(
+            accuracy(&y, &classifier.predict(&x).unwrap())
+                > accuracy(&y, &stump.predict(&x).unwrap())
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn stops_on_perfect_fit() {
+        let x = DenseMatrix::from_2d_array(&[&[1., 1.], &[2., 1.], &[3., 1.], &[4., 1.]]);
+        let y = vec![0., 0., 1., 1.];
+
+        let classifier: AdaBoostClassifier<f64> =
+            AdaBoostClassifier::fit(&x, &y, AdaBoostClassifierParameters::default()).unwrap();
+
+        assert_eq!(classifier.estimators().len(), 1);
+        assert_eq!(classifier.estimator_errors(), &vec![0.]);
+        assert_eq!(classifier.predict(&x).unwrap(), y);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn early_stopping_with_validation_set() {
+        let (x, y) = iris();
+        let x_val = DenseMatrix::from_2d_array(&[
+            &[5.0, 3.3, 1.4, 0.2],
+            &[5.9, 3.0, 4.2, 1.5],
+            &[6.1, 2.6, 5.6, 1.4],
+            &[7.7, 3.0, 6.1, 2.3],
+        ]);
+        let y_val = vec![0., 1., 2., 2.];
+
+        let classifier: AdaBoostClassifier<f64> = AdaBoostClassifier::fit_with_validation(
+            &x,
+            &y,
+            &x_val,
+            &y_val,
+            AdaBoostClassifierParameters::default()
+                .with_algorithm(AdaBoostAlgorithm::Samme)
+                .with_n_iter_no_change(3),
+        )
+        .unwrap();
+
+        let scores = classifier.validation_scores().unwrap();
+        let best = classifier.best_iteration();
+        assert_eq!(classifier.estimators().len(), best + 1);
+        assert!(scores.len() <= best + 4);
+        assert!(scores.iter().all(|score| *score <= scores[best]));
+        assert!(scores.len() < 50);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn early_stopping_with_validation_fraction() {
+        let (x, y) = iris();
+
+        let classifier: AdaBoostClassifier<f64> = AdaBoostClassifier::fit(
+            &x,
+            &y,
+            AdaBoostClassifierParameters::default()
+                .with_n_iter_no_change(2)
+                .with_validation_fraction(0.2)
+                .with_seed(7),
+        )
+        .unwrap();
+
+        assert!(classifier.validation_scores().is_some());
+        assert_eq!(
+            classifier.estimators().len(),
+            classifier.best_iteration() + 1
+        );
+
+        let classifier: AdaBoostClassifier<f64> =
+            AdaBoostClassifier::fit(&x, &y, AdaBoostClassifierParameters::default()).unwrap();
+        assert!(classifier.validation_scores().is_none());
+        assert_eq!(
+            classifier.best_iteration(),
+            classifier.estimators().len() - 1
+        );
+
+        let result: Result<AdaBoostClassifier<f64>, Failed> = AdaBoostClassifier::fit(
+            &x,
+            &y,
+            AdaBoostClassifierParameters::default()
+                .with_n_iter_no_change(2)
+                .with_validation_fraction(1.5),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let (x, y) = iris();
+
+        let result: Result<AdaBoostClassifier<f64>, Failed> = AdaBoostClassifier::fit(
+            &x,
+            &y,
+            AdaBoostClassifierParameters::default().with_n_estimators(0),
+        );
+        assert!(result.is_err());
+
+        let result: Result<AdaBoostClassifier<f64>, Failed> = AdaBoostClassifier::fit(
+            &x,
+            &y,
+            AdaBoostClassifierParameters::default().with_learning_rate(0.),
+        );
+        assert!(result.is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn feature_importances() {
+        let (x, y) = iris();
+
+        let classifier: AdaBoostClassifier<f64> = AdaBoostClassifier::fit(
+            &x,
+            &y,
+            AdaBoostClassifierParameters::default().with_algorithm(AdaBoostAlgorithm::Samme),
+        )
+        .unwrap();
+        let importances = classifier.feature_importances();
+
+        assert_eq!(importances.len(), 4);
+        assert!((importances.iter().sum::<f64>() - 1.).abs() < 1e-8);
+        assert_eq!(
+            classifier.split_counts().iter().sum::<usize>(),
+            classifier.estimators().len()
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = iris();
+
+        let classifier: AdaBoostClassifier<f64> =
+            AdaBoostClassifier::fit(&x, &y, AdaBoostClassifierParameters::default()).unwrap();
+
+        let deserialized_classifier: AdaBoostClassifier<f64> =
+            serde_json::from_str(&serde_json::to_string(&classifier).unwrap()).unwrap();
+
+        assert_eq!(
+            classifier.predict(&x).unwrap(),
+            deserialized_classifier.predict(&x).unwrap()
+        );
+        assert_eq!(
+            classifier.estimator_weights(),
+            deserialized_classifier.estimator_weights()
+        );
+    }
+}
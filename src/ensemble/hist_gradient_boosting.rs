@@ -0,0 +1,1836 @@
+//! # Histogram-Based Gradient Boosting
+//! Gradient boosting builds an additive model in a forward stage-wise fashion: every iteration fits a regression tree to the gradient of the loss
+//! with respect to the current predictions, and adds a scaled-down version of the tree to the ensemble.
+//!
+//! Finding the best split of a node of an exact [decision tree](../../tree/index.html) requires sorting the samples along every feature, which becomes
+//! the bottleneck on large datasets. The histogram-based variant, popularized by LightGBM, bins every feature into at most 255 buckets once, before
+//! training. The split of a node is then found by accumulating gradient and hessian statistics into a histogram with one entry per bucket and scanning
+//! the histogram, which costs \\(O(n)\\) per node and feature instead of \\(O(n \log n)\\). The histogram of one child is obtained by subtracting the histogram of its
+//! sibling from the histogram of the parent, so only the smaller child is ever scanned.
+//!
+//! Trees are grown best-first: the leaf with the largest decrease of the loss is split next until `max_leaf_nodes` leaves are reached.
+//! The value of every leaf is a Newton step \\(-\eta \frac{\sum g_i}{\sum h_i + \lambda}\\) where \\(g_i\\) and \\(h_i\\) are the first and second derivatives of the loss,
+//! \\(\eta\\) is the learning rate and \\(\lambda\\) is the L2 regularization strength.
+//!
+//! Every iteration can be fitted to a random `subsample` of the rows and a random `colsample` of the features, which speeds up training and often improves
+//! generalization. Set `n_iter_no_change` to stop boosting when the loss on a validation set has not improved for `n_iter_no_change` iterations,
+//! the same way as [AdaBoost early stopping](../adaboost/index.html#early-stopping).
+//!
+//! Missing values, marked as `NaN`, don't need to be [imputed](../../preprocessing/impute/index.html): they are collected in a bin of their own
+//! and every split sends them to the child with the larger decrease of the loss. When a feature had no missing values in a node during training,
+//! missing values of the feature go to the child with more training samples.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::ensemble::hist_gradient_boosting::*;
+//!
+//! // Longley dataset (https://www.statsmodels.org/stable/datasets/generated/longley.html)
+//! let x = DenseMatrix::from_2d_array(&[
+//!             &[234.289, 235.6, 159., 107.608, 1947., 60.323],
+//!             &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+//!             &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+//!             &[284.599, 335.1, 165., 110.929, 1950., 61.187],
+//!             &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+//!             &[346.999, 193.2, 359.4, 113.27, 1952., 63.639],
+//!             &[365.385, 187., 354.7, 115.094, 1953., 64.989],
+//!             &[363.112, 357.8, 335., 116.219, 1954., 63.761],
+//!             &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+//!             &[419.18, 282.2, 285.7, 118.734, 1956., 67.857],
+//!             &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+//!             &[444.546, 468.1, 263.7, 121.95, 1958., 66.513],
+//!             &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+//!             &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+//!             &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+//!             &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+//!         ]);
+//! let y = vec![
+//!             83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0, 100.0, 101.2,
+//!             104.6, 108.4, 110.8, 112.6, 114.2, 115.7, 116.9,
+//!         ];
+//!
+//! let parameters = HistGradientBoostingRegressorParameters::default().with_min_samples_leaf(2);
+//! let regressor = HistGradientBoostingRegressor::fit(&x, &y, parameters).unwrap();
+//!
+//! let y_hat = regressor.predict(&x).unwrap(); // use the same data for prediction
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Greedy Function Approximation: A Gradient Boosting Machine", Friedman J. H., 2001](https://doi.org/10.1214/aos/1013203451)
+//! * ["LightGBM: A Highly Efficient Gradient Boosting Decision Tree", Ke G. et al., 2017](https://papers.nips.cc/paper/2017/hash/6449f44a102fde848669bdd9eb6b76fa-Abstract.html)
+//! * ["XGBoost: A Scalable Tree Boosting System", Chen T., Guestrin C., 2016](https://doi.org/10.1145/2939672.2939785)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::collections::LinkedList;
+use alloc::vec::Vec;
+use core::default::Default;
+use core::fmt::Debug;
+
+use rand::seq::SliceRandom;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::api::{Predictor, PredictorProba, ProbabilisticClassifier, SupervisedEstimator};
+use crate::callback::{FitAction, FitCallback, FitProgress, NoCallback};
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::model_selection::train_test_split;
+#[cfg(feature = "onnx")]
+use crate::onnx::{self, tree::TreeEnsemble, ToOnnx};
+use crate::rand::get_rng_impl;
+use crate::tree::decision_tree_classifier::which_max;
+use crate::tree::pop_best;
+use crate::validation::{check_x, check_y};
+
+/// Parameters of the histogram-based gradient boosting regressor.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct HistGradientBoostingRegressorParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of boosting iterations, one tree is fitted per iteration.
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Shrinks the contribution of each tree.
+    pub learning_rate: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of leaves of each tree.
+    pub max_leaf_nodes: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum depth of each tree.
+    pub max_depth: Option<u16>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The minimum number of samples required to be at a leaf node.
+    pub min_samples_leaf: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// L2 regularization of leaf values.
+    pub l2_regularization: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of bins of every feature, at most 255. Missing values get a bin of their own.
+    pub max_bins: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of rows sampled without replacement to fit each tree.
+    pub subsample: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of features sampled without replacement to fit each tree.
+    pub colsample: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Enables early stopping: boosting stops when the validation loss has not improved for `n_iter_no_change` iterations.
+    pub n_iter_no_change: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of the training set set aside as a validation set for early stopping. Ignored when the validation set is passed explicitly.
+    pub validation_fraction: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Minimum decrease of the validation loss that counts as an improvement.
+    pub tol: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used for row and feature subsampling and to split the validation set.
+    pub seed: Option<u64>,
+}
+
+/// Parameters of the histogram-based gradient boosting classifier.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct HistGradientBoostingClassifierParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of boosting iterations. One tree is fitted per iteration for binary problems, one tree per class otherwise.
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Shrinks the contribution of each tree.
+    pub learning_rate: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of leaves of each tree.
+    pub max_leaf_nodes: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum depth of each tree.
+    pub max_depth: Option<u16>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The minimum number of samples required to be at a leaf node.
+    pub min_samples_leaf: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// L2 regularization of leaf values.
+    pub l2_regularization: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of bins of every feature, at most 255. Missing values get a bin of their own.
+    pub max_bins: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of rows sampled without replacement to fit each tree.
+    pub subsample: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of features sampled without replacement to fit each tree.
+    pub colsample: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Enables early stopping: boosting stops when the validation loss has not improved for `n_iter_no_change` iterations.
+    pub n_iter_no_change: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of the training set set aside as a validation set for early stopping. Ignored when the validation set is passed explicitly.
+    pub validation_fraction: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Minimum decrease of the validation loss that counts as an improvement.
+    pub tol: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used for row and feature subsampling and to split the validation set.
+    pub seed: Option<u64>,
+}
+
+/// Histogram-based gradient boosting regressor, fitted with the squared error loss.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct HistGradientBoostingRegressor<T: RealNumber> {
+    ensemble: Ensemble<T>,
+}
+
+/// Histogram-based gradient boosting classifier, fitted with the log loss.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct HistGradientBoostingClassifier<T: RealNumber> {
+    ensemble: Ensemble<T>,
+    classes: Vec<T>,
+}
+
+impl HistGradientBoostingRegressorParameters {
+    /// The maximum number of boosting iterations.
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Shrinks the contribution of each tree.
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+    /// The maximum number of leaves of each tree.
+    pub fn with_max_leaf_nodes(mut self, max_leaf_nodes: usize) -> Self {
+        self.max_leaf_nodes = Some(max_leaf_nodes);
+        self
+    }
+    /// The maximum depth of each tree.
+    pub fn with_max_depth(mut self, max_depth: u16) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+    /// The minimum number of samples required to be at a leaf node.
+    pub fn with_min_samples_leaf(mut self, min_samples_leaf: usize) -> Self {
+        self.min_samples_leaf = min_samples_leaf;
+        self
+    }
+    /// L2 regularization of leaf values.
+    pub fn with_l2_regularization(mut self, l2_regularization: f64) -> Self {
+        self.l2_regularization = l2_regularization;
+        self
+    }
+    /// The maximum number of bins of every feature, at most 255. Missing values get a bin of their own.
+    pub fn with_max_bins(mut self, max_bins: usize) -> Self {
+        self.max_bins = max_bins;
+        self
+    }
+    /// Fraction of rows sampled without replacement to fit each tree.
+    pub fn with_subsample(mut self, subsample: f64) -> Self {
+        self.subsample = subsample;
+        self
+    }
+    /// Fraction of features sampled without replacement to fit each tree.
+    pub fn with_colsample(mut self, colsample: f64) -> Self {
+        self.colsample = colsample;
+        self
+    }
+    /// Enables early stopping after `n_iter_no_change` iterations without improvement of the validation loss.
+    pub fn with_n_iter_no_change(mut self, n_iter_no_change: usize) -> Self {
+        self.n_iter_no_change = Some(n_iter_no_change);
+        self
+    }
+    /// Fraction of the training set set aside as a validation set for early stopping.
+    pub fn with_validation_fraction(mut self, validation_fraction: f32) -> Self {
+        self.validation_fraction = validation_fraction;
+        self
+    }
+    /// Minimum decrease of the validation loss that counts as an improvement.
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Seed used for row and feature subsampling and to split the validation set.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl HistGradientBoostingClassifierParameters {
+    /// The maximum number of boosting iterations.
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Shrinks the contribution of each tree.
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+    /// The maximum number of leaves of each tree.
+    pub fn with_max_leaf_nodes(mut self, max_leaf_nodes: usize) -> Self {
+        self.max_leaf_nodes = Some(max_leaf_nodes);
+        self
+    }
+    /// The maximum depth of each tree.
+    pub fn with_max_depth(mut self, max_depth: u16) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+    /// The minimum number of samples required to be at a leaf node.
+    pub fn with_min_samples_leaf(mut self, min_samples_leaf: usize) -> Self {
+        self.min_samples_leaf = min_samples_leaf;
+        self
+    }
+    /// L2 regularization of leaf values.
+    pub fn with_l2_regularization(mut self, l2_regularization: f64) -> Self {
+        self.l2_regularization = l2_regularization;
+        self
+    }
+    /// The maximum number of bins of every feature, at most 255. Missing values get a bin of their own.
+    pub fn with_max_bins(mut self, max_bins: usize) -> Self {
+        self.max_bins = max_bins;
+        self
+    }
+    /// Fraction of rows sampled without replacement to fit each tree.
+    pub fn with_subsample(mut self, subsample: f64) -> Self {
+        self.subsample = subsample;
+        self
+    }
+    /// Fraction of features sampled without replacement to fit each tree.
+    pub fn with_colsample(mut self, colsample: f64) -> Self {
+        self.colsample = colsample;
+        self
+    }
+    /// Enables early stopping after `n_iter_no_change` iterations without improvement of the validation loss.
+    pub fn with_n_iter_no_change(mut self, n_iter_no_change: usize) -> Self {
+        self.n_iter_no_change = Some(n_iter_no_change);
+        self
+    }
+    /// Fraction of the training set set aside as a validation set for early stopping.
+    pub fn with_validation_fraction(mut self, validation_fraction: f32) -> Self {
+        self.validation_fraction = validation_fraction;
+        self
+    }
+    /// Minimum decrease of the validation loss that counts as an improvement.
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Seed used for row and feature subsampling and to split the validation set.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl Default for HistGradientBoostingRegressorParameters {
+    fn default() -> Self {
+        HistGradientBoostingRegressorParameters {
+            max_iter: 100,
+            learning_rate: 0.1,
+            max_leaf_nodes: Some(31),
+            max_depth: None,
+            min_samples_leaf: 20,
+            l2_regularization: 0f64,
+            max_bins: 255,
+            subsample: 1f64,
+            colsample: 1f64,
+            n_iter_no_change: None,
+            validation_fraction: 0.1,
+            tol: 1e-7,
+            seed: None,
+        }
+    }
+}
+
+impl Default for HistGradientBoostingClassifierParameters {
+    fn default() -> Self {
+        HistGradientBoostingClassifierParameters {
+            max_iter: 100,
+            learning_rate: 0.1,
+            max_leaf_nodes: Some(31),
+            max_depth: None,
+            min_samples_leaf: 20,
+            l2_regularization: 0f64,
+            max_bins: 255,
+            subsample: 1f64,
+            colsample: 1f64,
+            n_iter_no_change: None,
+            validation_fraction: 0.1,
+            tol: 1e-7,
+            seed: None,
+        }
+    }
+}
+
+impl From<&HistGradientBoostingRegressorParameters> for BoostingParameters {
+    fn from(parameters: &HistGradientBoostingRegressorParameters) -> Self {
+        BoostingParameters {
+            max_iter: parameters.max_iter,
+            learning_rate: parameters.learning_rate,
+            max_leaf_nodes: parameters.max_leaf_nodes,
+            max_depth: parameters.max_depth,
+            min_samples_leaf: parameters.min_samples_leaf,
+            l2_regularization: parameters.l2_regularization,
+            max_bins: parameters.max_bins,
+            subsample: parameters.subsample,
+            colsample: parameters.colsample,
+            n_iter_no_change: parameters.n_iter_no_change,
+            validation_fraction: parameters.validation_fraction,
+            tol: parameters.tol,
+            seed: parameters.seed,
+        }
+    }
+}
+
+impl From<&HistGradientBoostingClassifierParameters> for BoostingParameters {
+    fn from(parameters: &HistGradientBoostingClassifierParameters) -> Self {
+        BoostingParameters {
+            max_iter: parameters.max_iter,
+            learning_rate: parameters.learning_rate,
+            max_leaf_nodes: parameters.max_leaf_nodes,
+            max_depth: parameters.max_depth,
+            min_samples_leaf: parameters.min_samples_leaf,
+            l2_regularization: parameters.l2_regularization,
+            max_bins: parameters.max_bins,
+            subsample: parameters.subsample,
+            colsample: parameters.colsample,
+            n_iter_no_change: parameters.n_iter_no_change,
+            validation_fraction: parameters.validation_fraction,
+            tol: parameters.tol,
+            seed: parameters.seed,
+        }
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>>
+    SupervisedEstimator<M, M::RowVector, HistGradientBoostingRegressorParameters>
+    for HistGradientBoostingRegressor<T>
+{
+    fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: HistGradientBoostingRegressorParameters,
+    ) -> Result<Self, Failed> {
+        HistGradientBoostingRegressor::fit(x, y, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for HistGradientBoostingRegressor<T> {
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>>
+    SupervisedEstimator<M, M::RowVector, HistGradientBoostingClassifierParameters>
+    for HistGradientBoostingClassifier<T>
+{
+    fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: HistGradientBoostingClassifierParameters,
+    ) -> Result<Self, Failed> {
+        HistGradientBoostingClassifier::fit(x, y, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for HistGradientBoostingClassifier<T> {
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> PredictorProba<M> for HistGradientBoostingClassifier<T> {
+    fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.predict_proba(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> ProbabilisticClassifier<T, M>
+    for HistGradientBoostingClassifier<T>
+{
+    fn classes(&self) -> &Vec<T> {
+        self.classes()
+    }
+}
+
+impl<T: RealNumber> HistGradientBoostingRegressor<T> {
+    /// Build a histogram-based gradient boosting regressor from the training data.
+    /// When early stopping is enabled with `n_iter_no_change`, a `validation_fraction` of the training data is set aside as a validation set.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target values
+    /// * `parameters` - boosting parameters
+    pub fn fit<M: Matrix<T>>(
+        x: &M,
+        y: &M::RowVector,
+        parameters: HistGradientBoostingRegressorParameters,
+    ) -> Result<HistGradientBoostingRegressor<T>, Failed> {
+        HistGradientBoostingRegressor::fit_with_callback(x, y, parameters, &mut NoCallback)
+    }
+
+    /// Build a histogram-based gradient boosting regressor like `fit` and call `callback` after every boosting iteration, see [callbacks](../../callback/index.html).
+    /// The callback receives the training loss and, when early stopping is enabled, the negative loss on the validation set.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target values
+    /// * `parameters` - boosting parameters
+    /// * `callback` - monitors the training and can stop it
+    pub fn fit_with_callback<M: Matrix<T>>(
+        x: &M,
+        y: &M::RowVector,
+        parameters: HistGradientBoostingRegressorParameters,
+        callback: &mut dyn FitCallback<T>,
+    ) -> Result<HistGradientBoostingRegressor<T>, Failed> {
+        check_y(y, Failed::fit)?;
+        let parameters = BoostingParameters::from(&parameters);
+        let ensemble = match split_validation(x, y, &parameters)? {
+            Some((x_train, x_val, y_train, y_val)) => Ensemble::fit(
+                &x_train,
+                &y_train.to_vec(),
+                Some((&x_val, &y_val.to_vec())),
+                Loss::SquaredError,
+                &parameters,
+                callback,
+            )?,
+            None => Ensemble::fit(
+                x,
+                &y.to_vec(),
+                None,
+                Loss::SquaredError,
+                &parameters,
+                callback,
+            )?,
+        };
+        Ok(HistGradientBoostingRegressor { ensemble })
+    }
+
+    /// Build a histogram-based gradient boosting regressor from the training data, monitoring the loss on the given validation set after every iteration.
+    /// Boosting stops early when `n_iter_no_change` is set and the loss has not improved for `n_iter_no_change` iterations.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target values
+    /// * `x_val` - _KxM_ matrix with _K_ validation observations.
+    /// * `y_val` - the validation target values
+    /// * `parameters` - boosting parameters
+    pub fn fit_with_validation<M: Matrix<T>>(
+        x: &M,
+        y: &M::RowVector,
+        x_val: &M,
+        y_val: &M::RowVector,
+        parameters: HistGradientBoostingRegressorParameters,
+    ) -> Result<HistGradientBoostingRegressor<T>, Failed> {
+        check_y(y, Failed::fit)?;
+        check_y(y_val, Failed::fit)?;
+        let ensemble = Ensemble::fit(
+            x,
+            &y.to_vec(),
+            Some((x_val, &y_val.to_vec())),
+            Loss::SquaredError,
+            &BoostingParameters::from(&parameters),
+            &mut NoCallback,
+        )?;
+        Ok(HistGradientBoostingRegressor { ensemble })
+    }
+
+    /// Predict target values for `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed> {
+        let raw = self.ensemble.decision_function(x)?;
+        Ok(M::RowVector::from_array(&raw[0]))
+    }
+
+    /// Number of fitted boosting iterations.
+    pub fn n_iter(&self) -> usize {
+        self.ensemble.trees.len()
+    }
+
+    /// Index of the iteration with the lowest validation loss. With early stopping the trees fitted after this iteration are discarded.
+    /// Without a validation set this is the index of the last iteration.
+    pub fn best_iteration(&self) -> usize {
+        self.ensemble.best_iteration
+    }
+
+    /// Negative validation loss after every iteration, including the iterations discarded by early stopping.
+    /// `None` when the regressor was fitted without a validation set.
+    pub fn validation_scores(&self) -> Option<&Vec<T>> {
+        self.ensemble.validation_scores.as_ref()
+    }
+}
+
+impl<T: RealNumber> HistGradientBoostingClassifier<T> {
+    /// Build a histogram-based gradient boosting classifier from the training data.
+    /// When early stopping is enabled with `n_iter_no_change`, a `validation_fraction` of the training data is set aside as a validation set.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target class values
+    /// * `parameters` - boosting parameters
+    pub fn fit<M: Matrix<T>>(
+        x: &M,
+        y: &M::RowVector,
+        parameters: HistGradientBoostingClassifierParameters,
+    ) -> Result<HistGradientBoostingClassifier<T>, Failed> {
+        HistGradientBoostingClassifier::fit_with_callback(x, y, parameters, &mut NoCallback)
+    }
+
+    /// Build a histogram-based gradient boosting classifier like `fit` and call `callback` after every boosting iteration, see [callbacks](../../callback/index.html).
+    /// The callback receives the training loss and, when early stopping is enabled, the negative loss on the validation set.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target class values
+    /// * `parameters` - boosting parameters
+    /// * `callback` - monitors the training and can stop it
+    pub fn fit_with_callback<M: Matrix<T>>(
+        x: &M,
+        y: &M::RowVector,
+        parameters: HistGradientBoostingClassifierParameters,
+        callback: &mut dyn FitCallback<T>,
+    ) -> Result<HistGradientBoostingClassifier<T>, Failed> {
+        check_y(y, Failed::fit)?;
+        let parameters = BoostingParameters::from(&parameters);
+        let classes = y.unique();
+        let loss = HistGradientBoostingClassifier::loss(&classes)?;
+        let ensemble = match split_validation(x, y, &parameters)? {
+            Some((x_train, x_val, y_train, y_val)) => Ensemble::fit(
+                &x_train,
+                &encode_classes(&classes, &y_train),
+                Some((&x_val, &encode_classes(&classes, &y_val))),
+                loss,
+                &parameters,
+                callback,
+            )?,
+            None => Ensemble::fit(
+                x,
+                &encode_classes(&classes, y),
+                None,
+                loss,
+                &parameters,
+                callback,
+            )?,
+        };
+        Ok(HistGradientBoostingClassifier { ensemble, classes })
+    }
+
+    /// Build a histogram-based gradient boosting classifier from the training data, monitoring the loss on the given validation set after every iteration.
+    /// Boosting stops early when `n_iter_no_change` is set and the loss has not improved for `n_iter_no_change` iterations.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target class values
+    /// * `x_val` - _KxM_ matrix with _K_ validation observations.
+    /// * `y_val` - the validation class values, every class should also be present in `y`.
+    /// * `parameters` - boosting parameters
+    pub fn fit_with_validation<M: Matrix<T>>(
+        x: &M,
+        y: &M::RowVector,
+        x_val: &M,
+        y_val: &M::RowVector,
+        parameters: HistGradientBoostingClassifierParameters,
+    ) -> Result<HistGradientBoostingClassifier<T>, Failed> {
+        check_y(y, Failed::fit)?;
+        check_y(y_val, Failed::fit)?;
+        let classes = y.unique();
+        if y_val.unique().iter().any(|c| !classes.contains(c)) {
+            return Err(Failed::fit(
+                "Validation set contains classes that are not present in the training set",
+            ));
+        }
+        let loss = HistGradientBoostingClassifier::loss(&classes)?;
+        let ensemble = Ensemble::fit(
+            x,
+            &encode_classes(&classes, y),
+            Some((x_val, &encode_classes(&classes, y_val))),
+            loss,
+            &BoostingParameters::from(&parameters),
+            &mut NoCallback,
+        )?;
+        Ok(HistGradientBoostingClassifier { ensemble, classes })
+    }
+
+    /// Predict class value for `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed> {
+        let probabilities = self.predict_proba(x)?;
+        let (n, _) = probabilities.shape();
+        let mut result = M::RowVector::zeros(n);
+        for i in 0..n {
+            result.set(i, self.classes[which_max(&probabilities.get_row_as_vec(i))]);
+        }
+        Ok(result)
+    }
+
+    /// Predict class probabilities for `x`. Returns _KxC_ matrix where _C_ is the number of classes, columns follow the order of sorted class labels.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_proba<M: Matrix<T>>(&self, x: &M) -> Result<M, Failed> {
+        let raw = self.ensemble.decision_function(x)?;
+        let (n, _) = x.shape();
+        let k = self.classes.len();
+        let mut result = M::zeros(n, k);
+        for i in 0..n {
+            let p = self.ensemble.loss.probabilities(&raw, i);
+            for (l, p_l) in p.iter().enumerate() {
+                result.set(i, l, *p_l);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Class labels, sorted.
+    pub fn classes(&self) -> &Vec<T> {
+        &self.classes
+    }
+
+    /// Number of fitted boosting iterations.
+    pub fn n_iter(&self) -> usize {
+        self.ensemble.trees.len()
+    }
+
+    /// Index of the iteration with the lowest validation loss. With early stopping the trees fitted after this iteration are discarded.
+    /// Without a validation set this is the index of the last iteration.
+    pub fn best_iteration(&self) -> usize {
+        self.ensemble.best_iteration
+    }
+
+    /// Negative validation loss after every iteration, including the iterations discarded by early stopping.
+    /// `None` when the classifier was fitted without a validation set.
+    pub fn validation_scores(&self) -> Option<&Vec<T>> {
+        self.ensemble.validation_scores.as_ref()
+    }
+
+    fn loss(classes: &[T]) -> Result<Loss, Failed> {
+        match classes.len() {
+            0 | 1 => Err(Failed::fit(&format!(
+                "Incorrect number of classes: {}. Should be >= 2.",
+                classes.len()
+            ))),
+            2 => Ok(Loss::Binomial),
+            k => Ok(Loss::Multinomial { n_classes: k }),
+        }
+    }
+}
+
+/// Replaces class labels with their index in `classes`.
+fn encode_classes<T: RealNumber, V: BaseVector<T>>(classes: &[T], y: &V) -> Vec<T> {
+    (0..y.len())
+        .map(|i| {
+            let y_i = y.get(i);
+            T::from(classes.iter().position(|c| *c == y_i).unwrap()).unwrap()
+        })
+        .collect()
+}
+
+/// Splits a random validation set off the training set when early stopping is enabled.
+#[allow(clippy::type_complexity)]
+fn split_validation<T: RealNumber, M: Matrix<T>>(
+    x: &M,
+    y: &M::RowVector,
+    parameters: &BoostingParameters,
+) -> Result<Option<(M, M, M::RowVector, M::RowVector)>, Failed> {
+    if parameters.n_iter_no_change.is_none() {
+        return Ok(None);
+    }
+    if !(parameters.validation_fraction > 0f32 && parameters.validation_fraction < 1f32) {
+        return Err(Failed::fit("validation_fraction should be between 0 and 1"));
+    }
+    let n = x.shape().0;
+    if y.len() != n {
+        return Err(Failed::fit(&format!(
+            "Size of x ({}) does not match size of y ({})",
+            n,
+            y.len()
+        )));
+    }
+    if ((n as f32) * parameters.validation_fraction) < 1f32 {
+        return Err(Failed::fit(&format!(
+            "Number of samples ({}) is too small to set aside a validation set",
+            n
+        )));
+    }
+    Ok(Some(train_test_split(
+        x,
+        y,
+        parameters.validation_fraction,
+        true,
+        parameters.seed,
+    )))
+}
+
+/// Parameters shared by the regressor and the classifier.
+struct BoostingParameters {
+    max_iter: usize,
+    learning_rate: f64,
+    max_leaf_nodes: Option<usize>,
+    max_depth: Option<u16>,
+    min_samples_leaf: usize,
+    l2_regularization: f64,
+    max_bins: usize,
+    subsample: f64,
+    colsample: f64,
+    n_iter_no_change: Option<usize>,
+    validation_fraction: f32,
+    tol: f64,
+    seed: Option<u64>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+enum Loss {
+    SquaredError,
+    Binomial,
+    Multinomial { n_classes: usize },
+}
+
+impl Loss {
+    fn n_outputs(&self) -> usize {
+        match self {
+            Loss::Multinomial { n_classes } => *n_classes,
+            _ => 1,
+        }
+    }
+
+    /// Constant prediction that minimizes the loss, for every output.
+    fn baseline<T: RealNumber>(&self, y: &[T]) -> Vec<T> {
+        let n = T::from(y.len()).unwrap();
+        match self {
+            Loss::SquaredError => vec![y.iter().fold(T::zero(), |acc, y_i| acc + *y_i) / n],
+            Loss::Binomial => {
+                let p = (y.iter().fold(T::zero(), |acc, y_i| acc + *y_i) / n)
+                    .max(T::epsilon())
+                    .min(T::one() - T::epsilon());
+                vec![(p / (T::one() - p)).ln()]
+            }
+            Loss::Multinomial { n_classes } => {
+                let mut count = vec![T::zero(); *n_classes];
+                for y_i in y.iter() {
+                    count[y_i.to_usize().unwrap()] += T::one();
+                }
+                count
+                    .iter()
+                    .map(|c| (*c / n).max(T::epsilon()).ln())
+                    .collect()
+            }
+        }
+    }
+
+    fn probabilities<T: RealNumber>(&self, raw: &[Vec<T>], i: usize) -> Vec<T> {
+        match self {
+            Loss::SquaredError => vec![raw[0][i]],
+            Loss::Binomial => {
+                let p = raw[0][i].sigmoid();
+                vec![T::one() - p, p]
+            }
+            Loss::Multinomial { n_classes } => {
+                let max = (0..*n_classes).fold(T::neg_infinity(), |acc, k| acc.max(raw[k][i]));
+                let exp: Vec<T> = (0..*n_classes).map(|k| (raw[k][i] - max).exp()).collect();
+                let total = exp.iter().fold(T::zero(), |acc, e| acc + *e);
+                exp.iter().map(|e| *e / total).collect()
+            }
+        }
+    }
+
+    /// Computes gradients and hessians of the loss for output `k`.
+    fn gradients<T: RealNumber>(
+        &self,
+        y: &[T],
+        raw: &[Vec<T>],
+        k: usize,
+        gradients: &mut [T],
+        hessians: &mut [T],
+    ) {
+        for i in 0..y.len() {
+            let (g, h) = match self {
+                Loss::SquaredError => (raw[0][i] - y[i], T::one()),
+                Loss::Binomial => {
+                    let p = raw[0][i].sigmoid();
+                    (p - y[i], (p * (T::one() - p)).max(T::epsilon()))
+                }
+                Loss::Multinomial { .. } => {
+                    let p = self.probabilities(raw, i)[k];
+                    let y_k = if y[i].to_usize().unwrap() == k {
+                        T::one()
+                    } else {
+                        T::zero()
+                    };
+                    (p - y_k, (p * (T::one() - p)).max(T::epsilon()))
+                }
+            };
+            gradients[i] = g;
+            hessians[i] = h;
+        }
+    }
+
+    /// Average loss of the predictions `raw`.
+    fn loss<T: RealNumber>(&self, y: &[T], raw: &[Vec<T>]) -> T {
+        let n = T::from(y.len()).unwrap();
+        let total = (0..y.len()).fold(T::zero(), |acc, i| {
+            acc + match self {
+                Loss::SquaredError => (raw[0][i] - y[i]).square() * T::half(),
+                Loss::Binomial => raw[0][i].ln_1pe() - y[i] * raw[0][i],
+                Loss::Multinomial { .. } => {
+                    let p = self.probabilities(raw, i)[y[i].to_usize().unwrap()];
+                    -p.max(T::epsilon()).ln()
+                }
+            }
+        });
+        total / n
+    }
+}
+
+/// Maps every feature value to one of at most 255 bins, missing values go to `MISSING_BIN`.
+#[derive(Debug)]
+struct BinMapper<T: RealNumber> {
+    /// Upper bounds of every bin except the last one, for every feature.
+    thresholds: Vec<Vec<T>>,
+}
+
+impl<T: RealNumber> BinMapper<T> {
+    fn fit<M: Matrix<T>>(x: &M, max_bins: usize) -> BinMapper<T> {
+        let m = x.shape().1;
+        let mut thresholds = Vec::with_capacity(m);
+        for j in 0..m {
+            let mut values: Vec<T> = x
+                .get_col_as_vec(j)
+                .into_iter()
+                .filter(|v| !v.is_nan())
+                .collect();
+            let n = values.len();
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let mut distinct = values.clone();
+            distinct.dedup();
+            let mut feature_thresholds: Vec<T> = if distinct.len() <= max_bins {
+                distinct
+                    .windows(2)
+                    .map(|w| (w[0] + w[1]) / T::two())
+                    .collect()
+            } else {
+                (1..max_bins)
+                    .map(|b| values[b * n / max_bins])
+                    .filter(|t| *t < distinct[distinct.len() - 1])
+                    .collect()
+            };
+            feature_thresholds.dedup();
+            thresholds.push(feature_thresholds);
+        }
+        BinMapper { thresholds }
+    }
+
+    /// Number of bins of feature `j` without the bin of missing values.
+    fn n_bins(&self, j: usize) -> usize {
+        self.thresholds[j].len() + 1
+    }
+
+    /// Upper bound of `bin` of feature `j`, the largest finite number for the last bin.
+    fn threshold(&self, j: usize, bin: usize) -> T {
+        if bin < self.thresholds[j].len() {
+            self.thresholds[j][bin]
+        } else {
+            T::max_value()
+        }
+    }
+
+    /// Returns binned features, one vector per feature.
+    fn transform<M: Matrix<T>>(&self, x: &M) -> Vec<Vec<u8>> {
+        let (n, m) = x.shape();
+        (0..m)
+            .map(|j| {
+                (0..n)
+                    .map(|i| {
+                        let x_ij = x.get(i, j);
+                        if x_ij.is_nan() {
+                            MISSING_BIN
+                        } else {
+                            self.thresholds[j].partition_point(|t| *t < x_ij) as u8
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Bin of missing values, after the bins of all other values.
+const MISSING_BIN: u8 = u8::MAX;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+struct HistNode<T: RealNumber> {
+    feature: usize,
+    bin: u8,
+    threshold: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    missing_left: bool,
+    value: T,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl<T: RealNumber> HistNode<T> {
+    fn go_left(&self, x: T) -> bool {
+        if x.is_nan() {
+            self.missing_left
+        } else {
+            x <= self.threshold
+        }
+    }
+
+    fn go_left_binned(&self, bin: u8) -> bool {
+        if bin == MISSING_BIN {
+            self.missing_left
+        } else {
+            bin <= self.bin
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+struct HistTree<T: RealNumber> {
+    nodes: Vec<HistNode<T>>,
+}
+
+impl<T: RealNumber> HistTree<T> {
+    fn leaf(&self, go_left: impl Fn(&HistNode<T>) -> bool) -> &HistNode<T> {
+        let mut node = &self.nodes[0];
+        while let (Some(left), Some(right)) = (node.left, node.right) {
+            node = if go_left(node) {
+                &self.nodes[left]
+            } else {
+                &self.nodes[right]
+            };
+        }
+        node
+    }
+
+    fn predict_row<M: Matrix<T>>(&self, x: &M, row: usize) -> T {
+        self.leaf(|node| node.go_left(x.get(row, node.feature)))
+            .value
+    }
+
+    fn predict_binned_row(&self, binned: &[Vec<u8>], row: usize) -> T {
+        self.leaf(|node| node.go_left_binned(binned[node.feature][row]))
+            .value
+    }
+}
+
+/// Sums of gradients, hessians and sample counts of the samples in every bin of every feature,
+/// the last bin of a feature holds the missing values.
+type Histogram<T> = Vec<Vec<(T, T, usize)>>;
+
+struct SplitInfo<T: RealNumber> {
+    gain: T,
+    feature: usize,
+    bin: usize,
+    missing_left: bool,
+    left: (T, T, usize),
+}
+
+struct GrowingNode<T: RealNumber> {
+    node: usize,
+    rows: Vec<usize>,
+    histogram: Histogram<T>,
+    sums: (T, T, usize),
+    depth: u16,
+    split: SplitInfo<T>,
+}
+
+struct TreeGrower<'a, T: RealNumber> {
+    binned: &'a [Vec<u8>],
+    bin_mapper: &'a BinMapper<T>,
+    gradients: &'a [T],
+    hessians: &'a [T],
+    features: &'a [usize],
+    parameters: &'a BoostingParameters,
+    learning_rate: T,
+    l2_regularization: T,
+}
+
+impl<'a, T: RealNumber> TreeGrower<'a, T> {
+    fn grow(&self, rows: Vec<usize>) -> HistTree<T> {
+        let histogram = self.histogram(&rows);
+        let sums = self.sums(&rows);
+        let mut tree = HistTree {
+            nodes: vec![self.new_node(sums)],
+        };
+
+        let max_leaf_nodes = self.parameters.max_leaf_nodes.unwrap_or(usize::MAX);
+        let max_depth = self.parameters.max_depth.unwrap_or(u16::MAX);
+
+        let mut splittable: LinkedList<GrowingNode<T>> = LinkedList::new();
+        if let Some(node) = self.splittable(0, rows, histogram, sums, 1, max_depth) {
+            splittable.push_back(node);
+        }
+
+        let mut n_leaves = 1;
+        while n_leaves < max_leaf_nodes {
+            let parent = match pop_best(&mut splittable, |node| node.split.gain) {
+                Some(parent) => parent,
+                None => break,
+            };
+
+            let feature = parent.split.feature;
+            let bin = parent.split.bin as u8;
+            let missing_left = parent.split.missing_left;
+            let parent_node = &mut tree.nodes[parent.node];
+            parent_node.feature = feature;
+            parent_node.bin = bin;
+            parent_node.threshold = self.bin_mapper.threshold(feature, parent.split.bin);
+            parent_node.missing_left = missing_left;
+            let (left_rows, right_rows): (Vec<usize>, Vec<usize>) = parent
+                .rows
+                .iter()
+                .partition(|i| tree.nodes[parent.node].go_left_binned(self.binned[feature][**i]));
+            let left_sums = parent.split.left;
+            let right_sums = (
+                parent.sums.0 - left_sums.0,
+                parent.sums.1 - left_sums.1,
+                parent.sums.2 - left_sums.2,
+            );
+
+            let left = tree.nodes.len();
+            tree.nodes.push(self.new_node(left_sums));
+            let right = tree.nodes.len();
+            tree.nodes.push(self.new_node(right_sums));
+            let parent_node = &mut tree.nodes[parent.node];
+            parent_node.left = Some(left);
+            parent_node.right = Some(right);
+            n_leaves += 1;
+
+            let (left_histogram, right_histogram) = if left_rows.len() <= right_rows.len() {
+                let small = self.histogram(&left_rows);
+                let large = subtract(&parent.histogram, &small);
+                (small, large)
+            } else {
+                let small = self.histogram(&right_rows);
+                let large = subtract(&parent.histogram, &small);
+                (large, small)
+            };
+
+            let depth = parent.depth + 1;
+            for (node, rows, histogram, sums) in [
+                (left, left_rows, left_histogram, left_sums),
+                (right, right_rows, right_histogram, right_sums),
+            ] {
+                if let Some(node) = self.splittable(node, rows, histogram, sums, depth, max_depth) {
+                    splittable.push_back(node);
+                }
+            }
+        }
+
+        tree
+    }
+
+    fn new_node(&self, sums: (T, T, usize)) -> HistNode<T> {
+        HistNode {
+            feature: 0,
+            bin: 0,
+            threshold: T::zero(),
+            missing_left: false,
+            value: -self.learning_rate * sums.0 / (sums.1 + self.l2_regularization),
+            left: None,
+            right: None,
+        }
+    }
+
+    fn sums(&self, rows: &[usize]) -> (T, T, usize) {
+        rows.iter().fold((T::zero(), T::zero(), 0), |acc, i| {
+            (
+                acc.0 + self.gradients[*i],
+                acc.1 + self.hessians[*i],
+                acc.2 + 1,
+            )
+        })
+    }
+
+    fn histogram(&self, rows: &[usize]) -> Histogram<T> {
+        self.features
+            .iter()
+            .map(|j| {
+                let n_bins = self.bin_mapper.n_bins(*j);
+                let mut bins = vec![(T::zero(), T::zero(), 0); n_bins + 1];
+                for i in rows.iter() {
+                    let bin = match self.binned[*j][*i] {
+                        MISSING_BIN => &mut bins[n_bins],
+                        b => &mut bins[b as usize],
+                    };
+                    bin.0 += self.gradients[*i];
+                    bin.1 += self.hessians[*i];
+                    bin.2 += 1;
+                }
+                bins
+            })
+            .collect()
+    }
+
+    fn splittable(
+        &self,
+        node: usize,
+        rows: Vec<usize>,
+        histogram: Histogram<T>,
+        sums: (T, T, usize),
+        depth: u16,
+        max_depth: u16,
+    ) -> Option<GrowingNode<T>> {
+        if depth >= max_depth || rows.len() < 2 * self.parameters.min_samples_leaf.max(1) {
+            return None;
+        }
+        let split = self.find_best_split(&histogram, sums)?;
+        Some(GrowingNode {
+            node,
+            rows,
+            histogram,
+            sums,
+            depth,
+            split,
+        })
+    }
+
+    fn score(&self, g: T, h: T) -> T {
+        g * g / (h + self.l2_regularization)
+    }
+
+    fn find_best_split(
+        &self,
+        histogram: &Histogram<T>,
+        sums: (T, T, usize),
+    ) -> Option<SplitInfo<T>> {
+        let min_samples_leaf = self.parameters.min_samples_leaf.max(1);
+        let parent_score = self.score(sums.0, sums.1);
+        let mut best: Option<SplitInfo<T>> = None;
+
+        for (f, bins) in histogram.iter().enumerate() {
+            let (present, missing) = bins.split_at(bins.len() - 1);
+            let missing = missing[0];
+            let mut present_left = (T::zero(), T::zero(), 0);
+            for (bin, (g, h, c)) in present.iter().enumerate() {
+                present_left = (
+                    present_left.0 + *g,
+                    present_left.1 + *h,
+                    present_left.2 + *c,
+                );
+                // without missing values in the node, they go to the child with more samples
+                let candidates: &[bool] = if missing.2 > 0 {
+                    &[false, true]
+                } else if 2 * present_left.2 >= sums.2 {
+                    &[true]
+                } else {
+                    &[false]
+                };
+                for missing_left in candidates.iter().copied() {
+                    let left = if missing_left {
+                        (
+                            present_left.0 + missing.0,
+                            present_left.1 + missing.1,
+                            present_left.2 + missing.2,
+                        )
+                    } else {
+                        present_left
+                    };
+                    let right = (sums.0 - left.0, sums.1 - left.1, sums.2 - left.2);
+                    if left.2 < min_samples_leaf || right.2 < min_samples_leaf {
+                        continue;
+                    }
+                    let gain =
+                        self.score(left.0, left.1) + self.score(right.0, right.1) - parent_score;
+                    let improves = match best {
+                        Some(ref b) => gain > b.gain,
+                        None => true,
+                    };
+                    if gain > T::epsilon() && improves {
+                        best = Some(SplitInfo {
+                            gain,
+                            feature: self.features[f],
+                            bin,
+                            missing_left,
+                            left,
+                        });
+                    }
+                }
+            }
+        }
+
+        best
+    }
+}
+
+fn subtract<T: RealNumber>(parent: &Histogram<T>, child: &Histogram<T>) -> Histogram<T> {
+    parent
+        .iter()
+        .zip(child.iter())
+        .map(|(p, c)| {
+            p.iter()
+                .zip(c.iter())
+                .map(|(p, c)| (p.0 - c.0, p.1 - c.1, p.2 - c.2))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+struct Ensemble<T: RealNumber> {
+    loss: Loss,
+    baseline: Vec<T>,
+    /// Trees of every iteration, one tree per output.
+    trees: Vec<Vec<HistTree<T>>>,
+    num_features: usize,
+    validation_scores: Option<Vec<T>>,
+    best_iteration: usize,
+}
+
+impl<T: RealNumber> Ensemble<T> {
+    fn fit<M: Matrix<T>>(
+        x: &M,
+        y: &[T],
+        validation: Option<(&M, &Vec<T>)>,
+        loss: Loss,
+        parameters: &BoostingParameters,
+        callback: &mut dyn FitCallback<T>,
+    ) -> Result<Ensemble<T>, Failed> {
+        let (n, num_features) = x.shape();
+        if y.len() != n {
+            return Err(Failed::fit(&format!(
+                "Size of x ({}) does not match size of y ({})",
+                n,
+                y.len()
+            )));
+        }
+        if let Some((x_val, y_val)) = validation {
+            if x_val.shape().0 != y_val.len() || x_val.shape().1 != num_features {
+                return Err(Failed::fit(
+                    "Shape of the validation set does not match the training set",
+                ));
+            }
+        }
+        if n == 0 {
+            return Err(Failed::fit("Training set is empty"));
+        }
+        check_x(x, true, Failed::fit)?;
+        if let Some((x_val, _)) = validation {
+            check_x(x_val, true, Failed::fit)?;
+        }
+        if parameters.max_iter < 1 {
+            return Err(Failed::fit("max_iter should be > 0"));
+        }
+        if parameters.learning_rate <= 0f64 {
+            return Err(Failed::fit("learning_rate should be > 0"));
+        }
+        if parameters.max_bins < 2 || parameters.max_bins > 255 {
+            return Err(Failed::fit("max_bins should be between 2 and 255"));
+        }
+        if matches!(parameters.max_leaf_nodes, Some(l) if l < 2) {
+            return Err(Failed::fit("max_leaf_nodes should be >= 2"));
+        }
+        if !(parameters.subsample > 0f64
+            && parameters.subsample <= 1f64
+            && parameters.colsample > 0f64
+            && parameters.colsample <= 1f64)
+        {
+            return Err(Failed::fit(
+                "subsample and colsample should be in the interval (0, 1]",
+            ));
+        }
+
+        let mut rng = get_rng_impl(parameters.seed);
+        let bin_mapper = BinMapper::fit(x, parameters.max_bins);
+        let binned = bin_mapper.transform(x);
+
+        let n_outputs = loss.n_outputs();
+        let baseline = loss.baseline(y);
+        let mut raw: Vec<Vec<T>> = baseline.iter().map(|b| vec![*b; n]).collect();
+        let mut validation_raw: Option<Vec<Vec<T>>> = validation
+            .map(|(x_val, _)| baseline.iter().map(|b| vec![*b; x_val.shape().0]).collect());
+
+        let n_rows = ((n as f64 * parameters.subsample).ceil() as usize).clamp(1, n);
+        let n_columns =
+            ((num_features as f64 * parameters.colsample).ceil() as usize).clamp(1, num_features);
+        let learning_rate = T::from_f64(parameters.learning_rate).unwrap();
+        let l2_regularization = T::from_f64(parameters.l2_regularization).unwrap();
+        let tol = T::from_f64(parameters.tol).unwrap();
+
+        let mut gradients = vec![T::zero(); n];
+        let mut hessians = vec![T::zero(); n];
+        let mut all_rows: Vec<usize> = (0..n).collect();
+        let mut all_features: Vec<usize> = (0..num_features).collect();
+
+        let mut trees: Vec<Vec<HistTree<T>>> = Vec::with_capacity(parameters.max_iter);
+        let mut validation_scores: Vec<T> = Vec::new();
+        let mut best_iteration = 0;
+
+        for iteration in 0..parameters.max_iter {
+            let mut rows = if n_rows < n {
+                all_rows.shuffle(&mut rng);
+                all_rows[..n_rows].to_vec()
+            } else {
+                all_rows.clone()
+            };
+            rows.sort_unstable();
+            let mut features = if n_columns < num_features {
+                all_features.shuffle(&mut rng);
+                all_features[..n_columns].to_vec()
+            } else {
+                all_features.clone()
+            };
+            features.sort_unstable();
+
+            let mut iteration_trees = Vec::with_capacity(n_outputs);
+            for k in 0..n_outputs {
+                loss.gradients(y, &raw, k, &mut gradients, &mut hessians);
+                let grower = TreeGrower {
+                    binned: &binned,
+                    bin_mapper: &bin_mapper,
+                    gradients: &gradients,
+                    hessians: &hessians,
+                    features: &features,
+                    parameters,
+                    learning_rate,
+                    l2_regularization,
+                };
+                iteration_trees.push(grower.grow(rows.clone()));
+            }
+
+            for (k, tree) in iteration_trees.iter().enumerate() {
+                for (i, raw_i) in raw[k].iter_mut().enumerate() {
+                    *raw_i += tree.predict_binned_row(&binned, i);
+                }
+            }
+
+            if let (Some((x_val, y_val)), Some(validation_raw)) =
+                (validation, validation_raw.as_mut())
+            {
+                for (k, tree) in iteration_trees.iter().enumerate() {
+                    for (i, raw_i) in validation_raw[k].iter_mut().enumerate() {
+                        *raw_i += tree.predict_row(x_val, i);
+                    }
+                }
+                let score = -loss.loss(y_val, validation_raw);
+                if iteration == 0 || score > validation_scores[best_iteration] + tol {
+                    best_iteration = iteration;
+                }
+                validation_scores.push(score);
+            }
+
+            trees.push(iteration_trees);
+            let training_loss = loss.loss(y, &raw);
+            debug!(
+                "Boosting iteration {}/{}: training loss {}, validation loss {:?}",
+                iteration + 1,
+                parameters.max_iter,
+                training_loss,
+                validation_scores.last().map(|score| -*score)
+            );
+
+            let progress = FitProgress {
+                iteration: iteration + 1,
+                loss: Some(training_loss),
+                validation_score: validation_scores.last().copied(),
+            };
+            if callback.on_iteration(&progress) == FitAction::Stop {
+                break;
+            }
+            if let Some(n_iter_no_change) = parameters.n_iter_no_change {
+                if validation.is_some() && iteration - best_iteration >= n_iter_no_change {
+                    break;
+                }
+            }
+        }
+
+        let validation_scores = if validation.is_some() {
+            if parameters.n_iter_no_change.is_some() {
+                trees.truncate(best_iteration + 1);
+            }
+            info!(
+                "Boosting stopped after {} iterations, best validation loss {} at iteration {}",
+                validation_scores.len(),
+                -validation_scores[best_iteration],
+                best_iteration + 1
+            );
+            Some(validation_scores)
+        } else {
+            best_iteration = trees.len() - 1;
+            None
+        };
+
+        Ok(Ensemble {
+            loss,
+            baseline,
+            trees,
+            num_features,
+            validation_scores,
+            best_iteration,
+        })
+    }
+
+    /// Raw predictions of the ensemble, one vector per output.
+    fn decision_function<M: Matrix<T>>(&self, x: &M) -> Result<Vec<Vec<T>>, Failed> {
+        let (n, num_features) = x.shape();
+        if num_features != self.num_features {
+            return Err(Failed::predict(&format!(
+                "Number of features of x ({}) does not match the number of features of the training set ({})",
+                num_features, self.num_features
+            )));
+        }
+        check_x(x, true, Failed::predict)?;
+        let mut raw: Vec<Vec<T>> = self.baseline.iter().map(|b| vec![*b; n]).collect();
+        for iteration_trees in self.trees.iter() {
+            for (k, tree) in iteration_trees.iter().enumerate() {
+                for (i, raw_i) in raw[k].iter_mut().enumerate() {
+                    *raw_i += tree.predict_row(x, i);
+                }
+            }
+        }
+        Ok(raw)
+    }
+}
+
+#[cfg(feature = "onnx")]
+impl<T: RealNumber> Ensemble<T> {
+    /// Adds all trees to an ONNX tree ensemble, `leaf_weights` maps the output of a tree and the value of a leaf to the weights of the leaf.
+    fn to_tree_ensemble(
+        &self,
+        leaf_weights: impl Fn(usize, f32) -> Vec<(usize, f32)>,
+    ) -> TreeEnsemble {
+        let n_outputs = self.loss.n_outputs();
+        let mut ensemble = TreeEnsemble::default();
+        for (iteration, iteration_trees) in self.trees.iter().enumerate() {
+            for (k, tree) in iteration_trees.iter().enumerate() {
+                let tree_id = iteration * n_outputs + k;
+                for (node_id, node) in tree.nodes.iter().enumerate() {
+                    match (node.left, node.right) {
+                        (Some(left), Some(right)) => ensemble.add_split(
+                            tree_id,
+                            node_id,
+                            node.feature,
+                            node.threshold,
+                            left,
+                            right,
+                            node.missing_left,
+                        ),
+                        _ => ensemble.add_leaf(
+                            tree_id,
+                            node_id,
+                            &leaf_weights(k, node.value.to_f32().unwrap()),
+                        ),
+                    }
+                }
+            }
+        }
+        ensemble
+    }
+}
+
+#[cfg(feature = "onnx")]
+impl<T: RealNumber> ToOnnx for HistGradientBoostingRegressor<T> {
+    fn to_onnx(&self) -> Result<Vec<u8>, Failed> {
+        Ok(self
+            .ensemble
+            .to_tree_ensemble(|_, value| vec![(0, value)])
+            .regressor("SUM", self.ensemble.baseline[0].to_f32().unwrap())
+            .regressor_model(self.ensemble.num_features))
+    }
+}
+
+/// Binary classifiers are exported with the raw prediction as the score of the second class and its negation as the score of the first class,
+/// so that the logistic function maps the scores to the probabilities of both classes.
+#[cfg(feature = "onnx")]
+impl<T: RealNumber> ToOnnx for HistGradientBoostingClassifier<T> {
+    fn to_onnx(&self) -> Result<Vec<u8>, Failed> {
+        let labels = onnx::class_labels(&self.classes)?;
+        let operator = match self.ensemble.loss {
+            Loss::Binomial => {
+                let baseline = self.ensemble.baseline[0];
+                self.ensemble
+                    .to_tree_ensemble(|_, value| vec![(0, -value), (1, value)])
+                    .classifier(labels, &[-baseline, baseline], "LOGISTIC")
+            }
+            _ => self
+                .ensemble
+                .to_tree_ensemble(|k, value| vec![(k, value)])
+                .classifier(labels, &self.ensemble.baseline, "SOFTMAX"),
+        };
+        Ok(operator.classifier_model(self.ensemble.num_features, self.classes.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::callback::{EarlyStopping, FitAction, FitProgress};
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::linalg::BaseMatrix;
+    use crate::metrics::*;
+
+    fn sine(n: usize) -> (DenseMatrix<f64>, Vec<f64>) {
+        let values: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                let t = i as f64 / n as f64 * 6.;
+                vec![t, (i % 7) as f64]
+            })
+            .collect();
+        let y = values.iter().map(|v| v[0].sin()).collect();
+        (DenseMatrix::from_2d_vec(&values), y)
+    }
+
+    fn blobs() -> (DenseMatrix<f64>, Vec<f64>) {
+        let mut values = Vec::new();
+        let mut y = Vec::new();
+        for i in 0..90 {
+            let class = i % 3;
+            let jitter = ((i * 37) % 11) as f64 / 10.;
+            values.push(vec![class as f64 * 3. + jitter, jitter * 2. - class as f64]);
+            y.push(class as f64);
+        }
+        (DenseMatrix::from_2d_vec(&values), y)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn bin_mapper() {
+        let x = DenseMatrix::from_2d_array(&[&[1., 5.], &[2., 5.], &[3., 5.], &[4., 5.]]);
+
+        let bin_mapper = BinMapper::fit(&x, 255);
+        assert_eq!(bin_mapper.thresholds[0], vec![1.5, 2.5, 3.5]);
+        assert!(bin_mapper.thresholds[1].is_empty());
+        assert_eq!(bin_mapper.transform(&x), vec![vec![0, 1, 2, 3], vec![0; 4]]);
+
+        let bin_mapper = BinMapper::fit(&x, 2);
+        assert_eq!(bin_mapper.n_bins(0), 2);
+        let binned = bin_mapper.transform(&x);
+        assert!(binned[0][0] <= binned[0][3]);
+
+        let x = DenseMatrix::from_2d_array(&[&[1., f64::NAN], &[f64::NAN, f64::NAN], &[3., 5.]]);
+        let bin_mapper = BinMapper::fit(&x, 255);
+        assert_eq!(bin_mapper.thresholds[0], vec![2.]);
+        assert!(bin_mapper.thresholds[1].is_empty());
+        assert_eq!(
+            bin_mapper.transform(&x),
+            vec![vec![0, MISSING_BIN, 1], vec![MISSING_BIN, MISSING_BIN, 0]]
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn missing_values() {
+        let (mut x, y) = blobs();
+        // the first feature of half of the third class is missing
+        for i in (2..90).step_by(6) {
+            x.set(i, 0, f64::NAN);
+        }
+
+        let classifier = HistGradientBoostingClassifier::fit(
+            &x,
+            &y,
+            HistGradientBoostingClassifierParameters::default()
+                .with_max_iter(20)
+                .with_min_samples_leaf(5),
+        )
+        .unwrap();
+        assert!(accuracy(&y, &classifier.predict(&x).unwrap()) > 0.95);
+
+        // missing values of a feature without missing values in the training set
+        let mut x_test = DenseMatrix::from_2d_array(&[&[0., 0.], &[6.5, -1.]]);
+        x_test.set(0, 1, f64::NAN);
+        assert_eq!(classifier.predict(&x_test).unwrap(), vec![0., 2.]);
+
+        x_test.set(1, 0, f64::INFINITY);
+        assert_eq!(
+            classifier.predict(&x_test).unwrap_err(),
+            Failed::predict("x contains inf at row 1, column 0")
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_predict_regressor() {
+        let (x, y) = sine(200);
+
+        let regressor = HistGradientBoostingRegressor::fit(&x, &y, Default::default()).unwrap();
+        let y_hat = regressor.predict(&x).unwrap();
+
+        assert!(mean_squared_error(&y, &y_hat) < 0.01);
+        assert_eq!(regressor.n_iter(), 100);
+        assert!(regressor.validation_scores().is_none());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_predict_regressor_subsample() {
+        let (x, y) = sine(200);
+
+        let parameters = HistGradientBoostingRegressorParameters::default()
+            .with_max_bins(16)
+            .with_subsample(0.5)
+            .with_colsample(0.5)
+            .with_seed(42);
+        let regressor = HistGradientBoostingRegressor::fit(&x, &y, parameters.clone()).unwrap();
+        let y_hat = regressor.predict(&x).unwrap();
+
+        assert!(mean_squared_error(&y, &y_hat) < 0.05);
+        assert_eq!(
+            y_hat,
+            HistGradientBoostingRegressor::fit(&x, &y, parameters)
+                .unwrap()
+                .predict(&x)
+                .unwrap()
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_predict_binary_classifier() {
+        let (x, y) = sine(200);
+        let y: Vec<f64> = y
+            .iter()
+            .map(|y_i| if *y_i > 0. { 1. } else { 0. })
+            .collect();
+
+        let classifier = HistGradientBoostingClassifier::fit(&x, &y, Default::default()).unwrap();
+        let y_hat = classifier.predict(&x).unwrap();
+        let y_proba = classifier.predict_proba(&x).unwrap();
+
+        assert!(accuracy(&y, &y_hat) > 0.95);
+        for i in 0..y.len() {
+            assert!((y_proba.get(i, 0) + y_proba.get(i, 1) - 1.).abs() < 1e-8);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_predict_multiclass_classifier() {
+        let (x, y) = blobs();
+
+        let classifier = HistGradientBoostingClassifier::fit(
+            &x,
+            &y,
+            HistGradientBoostingClassifierParameters::default().with_max_iter(20),
+        )
+        .unwrap();
+        let y_hat = classifier.predict(&x).unwrap();
+
+        assert!(accuracy(&y, &y_hat) > 0.95);
+        assert_eq!(classifier.predict_proba(&x).unwrap().shape(), (90, 3));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn early_stopping() {
+        let (x, y) = sine(200);
+
+        let regressor = HistGradientBoostingRegressor::fit(
+            &x,
+            &y,
+            HistGradientBoostingRegressorParameters::default()
+                .with_max_iter(1000)
+                .with_n_iter_no_change(5)
+                .with_tol(1e-4)
+                .with_seed(1),
+        )
+        .unwrap();
+
+        let scores = regressor.validation_scores().unwrap();
+        assert!(regressor.n_iter() < 1000);
+        assert_eq!(regressor.n_iter(), regressor.best_iteration() + 1);
+        assert!(scores.len() <= regressor.best_iteration() + 6);
+
+        let (x_val, y_val) = sine(50);
+        let regressor = HistGradientBoostingRegressor::fit_with_validation(
+            &x,
+            &y,
+            &x_val,
+            &y_val,
+            HistGradientBoostingRegressorParameters::default().with_max_iter(10),
+        )
+        .unwrap();
+        assert_eq!(regressor.validation_scores().unwrap().len(), 10);
+        assert_eq!(regressor.n_iter(), 10);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_with_callback() {
+        let (x, y) = sine(200);
+
+        let mut losses = Vec::new();
+        let regressor = HistGradientBoostingRegressor::fit_with_callback(
+            &x,
+            &y,
+            HistGradientBoostingRegressorParameters::default().with_max_iter(100),
+            &mut |progress: &FitProgress<f64>| {
+                assert!(progress.validation_score.is_none());
+                losses.push(progress.loss.unwrap());
+                if progress.iteration < 5 {
+                    FitAction::Continue
+                } else {
+                    FitAction::Stop
+                }
+            },
+        )
+        .unwrap();
+        assert_eq!(regressor.n_iter(), 5);
+        assert!(losses[4] < losses[0]);
+
+        let (x, y) = blobs();
+        let mut early_stopping = EarlyStopping::new(3, 1e-3);
+        let classifier = HistGradientBoostingClassifier::fit_with_callback(
+            &x,
+            &y,
+            HistGradientBoostingClassifierParameters::default()
+                .with_max_iter(1000)
+                .with_n_iter_no_change(1000)
+                .with_seed(1),
+            &mut early_stopping,
+        )
+        .unwrap();
+        assert!(classifier.n_iter() < 1000);
+        assert!(early_stopping.best_score().is_some());
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let (x, y) = sine(20);
+
+        assert!(HistGradientBoostingRegressor::fit(
+            &x,
+            &y,
+            HistGradientBoostingRegressorParameters::default().with_max_bins(256)
+        )
+        .is_err());
+        let mut y_nan = y.clone();
+        y_nan[3] = f64::NAN;
+        assert_eq!(
+            HistGradientBoostingRegressor::fit(&x, &y_nan, Default::default()).unwrap_err(),
+            Failed::fit("y contains NaN at index 3")
+        );
+        assert!(HistGradientBoostingRegressor::fit(
+            &x,
+            &y,
+            HistGradientBoostingRegressorParameters::default().with_subsample(0.)
+        )
+        .is_err());
+        assert!(
+            HistGradientBoostingClassifier::fit(&x, &vec![1.; 20], Default::default()).is_err()
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = blobs();
+
+        let classifier = HistGradientBoostingClassifier::fit(
+            &x,
+            &y,
+            HistGradientBoostingClassifierParameters::default().with_max_iter(5),
+        )
+        .unwrap();
+
+        let deserialized_classifier: HistGradientBoostingClassifier<f64> =
+            serde_json::from_str(&serde_json::to_string(&classifier).unwrap()).unwrap();
+
+        assert_eq!(
+            classifier.predict_proba(&x).unwrap(),
+            deserialized_classifier.predict_proba(&x).unwrap()
+        );
+    }
+}
@@ -7,6 +7,10 @@
 //! set and then aggregate their individual predictions to form a final prediction. In classification setting the overall prediction is the most commonly
 //! occurring majority class among the individual predictions.
 //!
+//! Boosting, on the other hand, grows the ensemble sequentially: every new estimator is fitted to a reweighted version of the training set
+//! that emphasizes the observations misclassified by the previous estimators. See [AdaBoost](adaboost/index.html).
+//! Gradient boosting fits every new tree to the gradient of the loss of the current ensemble, see [histogram-based gradient boosting](hist_gradient_boosting/index.html).
+//!
 //! In SmartCore you will find implementation of RandomForest - a popular averaging algorithms based on randomized [decision trees](../tree/index.html).
 //! Random forests provide an improvement over bagged trees by way of a small tweak that decorrelates the trees. As in bagging, we build a number of
 //! decision trees on bootstrapped training samples. But when building these decision trees, each time a split in a tree is considered,
@@ -16,7 +20,52 @@
 //!
 //! * ["An Introduction to Statistical Learning", James G., Witten D., Hastie T., Tibshirani R., 8.2 Bagging, Random Forests, Boosting](http://faculty.marshall.usc.edu/gareth-james/ISL/)
 
+use crate::math::num::RealNumber;
+use alloc::vec::Vec;
+
+/// AdaBoost classifier
+pub mod adaboost;
+/// Histogram-based gradient boosting
+pub mod hist_gradient_boosting;
 /// Random forest classifier
 pub mod random_forest_classifier;
 /// Random forest regressor
 pub mod random_forest_regressor;
+
+/// Aggregates feature importances of individual estimators into a single vector: the weighted mean of the importances, normalized to sum to 1.
+pub(crate) fn aggregate_importances<T: RealNumber>(
+    importances: impl Iterator<Item = (T, Vec<T>)>,
+) -> Vec<T> {
+    let mut result: Vec<T> = Vec::new();
+    for (weight, estimator_importances) in importances {
+        if result.is_empty() {
+            result = vec![T::zero(); estimator_importances.len()];
+        }
+        for (r, i) in result.iter_mut().zip(estimator_importances.iter()) {
+            *r += weight * *i;
+        }
+    }
+
+    let total = result.iter().fold(T::zero(), |acc, r| acc + *r);
+    if total > T::zero() {
+        for r in result.iter_mut() {
+            *r /= total;
+        }
+    }
+
+    result
+}
+
+/// Sums the number of splits on every feature over all trees of an ensemble.
+pub(crate) fn aggregate_split_counts(split_counts: impl Iterator<Item = Vec<usize>>) -> Vec<usize> {
+    let mut result: Vec<usize> = Vec::new();
+    for estimator_split_counts in split_counts {
+        if result.is_empty() {
+            result = vec![0; estimator_split_counts.len()];
+        }
+        for (r, c) in result.iter_mut().zip(estimator_split_counts.iter()) {
+            *r += c;
+        }
+    }
+    result
+}
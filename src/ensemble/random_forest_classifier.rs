@@ -45,22 +45,37 @@
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+#[cfg(feature = "pmml")]
+use alloc::string::String;
+use alloc::vec::Vec;
 use rand::Rng;
 
-use std::default::Default;
-use std::fmt::Debug;
+use core::default::Default;
+use core::fmt::Debug;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::api::{Predictor, SupervisedEstimator};
+use crate::api::{
+    FeatureImportance, Predictor, PredictorProba, ProbabilisticClassifier, SupervisedEstimator,
+};
+use crate::ensemble::{aggregate_importances, aggregate_split_counts};
 use crate::error::{Failed, FailedError};
-use crate::linalg::Matrix;
+use crate::linalg::{BaseVector, Matrix};
 use crate::math::num::RealNumber;
+use crate::metrics::ClassificationMetrics;
+#[cfg(feature = "onnx")]
+use crate::onnx::{self, tree::TreeEnsemble, ToOnnx};
+use crate::parallel::map_slice;
+#[cfg(feature = "pmml")]
+use crate::pmml::{self, tree, Document, FromPmml, Target, ToPmml};
 use crate::rand::get_rng_impl;
 use crate::tree::decision_tree_classifier::{
     which_max, DecisionTreeClassifier, DecisionTreeClassifierParameters, SplitCriterion,
 };
+use crate::tree::oblique::SplitType;
+use crate::tree::GrowthStrategy;
+use crate::validation::{check_fit, check_predict};
 
 /// Parameters of the Random Forest algorithm.
 /// Some parameters here are passed directly into base estimator.
@@ -101,6 +116,8 @@ pub struct RandomForestClassifier<T: RealNumber> {
     trees: Vec<DecisionTreeClassifier<T>>,
     classes: Vec<T>,
     samples: Option<Vec<Vec<bool>>>,
+    oob_predictions: Option<Vec<T>>,
+    oob_score: Option<T>,
 }
 
 impl RandomForestClassifierParameters {
@@ -202,6 +219,24 @@ impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for RandomForestCla
     }
 }
 
+impl<T: RealNumber, M: Matrix<T>> PredictorProba<M> for RandomForestClassifier<T> {
+    fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.predict_proba(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> ProbabilisticClassifier<T, M> for RandomForestClassifier<T> {
+    fn classes(&self) -> &Vec<T> {
+        self.classes()
+    }
+}
+
+impl<T: RealNumber> FeatureImportance<T> for RandomForestClassifier<T> {
+    fn feature_importance(&self) -> Vec<T> {
+        self.feature_importances()
+    }
+}
+
 /// RandomForestClassifier grid search parameters
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
@@ -439,17 +474,87 @@ impl<T: RealNumber> RandomForestClassifier<T> {
         y: &M::RowVector,
         parameters: RandomForestClassifierParameters,
     ) -> Result<RandomForestClassifier<T>, Failed> {
-        let (_, num_attributes) = x.shape();
-        let y_m = M::from_row_vector(y.clone());
-        let (_, y_ncols) = y_m.shape();
-        let mut yi: Vec<usize> = vec![0; y_ncols];
-        let classes = y_m.unique();
-
-        for (i, yi_i) in yi.iter_mut().enumerate().take(y_ncols) {
-            let yc = y_m.get(0, i);
-            *yi_i = classes.iter().position(|c| yc == *c).unwrap();
+        check_fit(x, y)?;
+        let classes = y.unique();
+        let n_trees = parameters.n_trees;
+        let samples = if parameters.keep_samples {
+            Some(Vec::new())
+        } else {
+            None
+        };
+
+        let mut forest = RandomForestClassifier {
+            _parameters: parameters,
+            trees: Vec::new(),
+            classes,
+            samples,
+            oob_predictions: None,
+            oob_score: None,
+        };
+        forest.fit_trees(x, y, n_trees)?;
+
+        Ok(forest)
+    }
+
+    /// Resumes fitting of the forest: keeps the existing trees and grows `n_additional_trees` new trees on the training set.
+    /// Use it to tune the size of the ensemble incrementally, e.g. watching the [out-of-bag score](RandomForestClassifier::oob_score).
+    /// When `x` and `y` are the data the forest was fitted on, the result is the same as fitting a forest of the combined size from scratch.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target class values, every class should also be present in the data the forest was fitted on.
+    /// * `n_additional_trees` - the number of trees to add to the forest.
+    pub fn fit_more<M: Matrix<T>>(
+        &mut self,
+        x: &M,
+        y: &M::RowVector,
+        n_additional_trees: u16,
+    ) -> Result<(), Failed> {
+        self.fit_trees(x, y, n_additional_trees)?;
+        self._parameters.n_trees = self._parameters.n_trees.saturating_add(n_additional_trees);
+        Ok(())
+    }
+
+    fn fit_trees<M: Matrix<T>>(
+        &mut self,
+        x: &M,
+        y: &M::RowVector,
+        n_trees: u16,
+    ) -> Result<(), Failed> {
+        let (n_rows, num_attributes) = x.shape();
+        if y.len() != n_rows {
+            return Err(Failed::fit(&format!(
+                "Size of x ({}) does not match size of y ({})",
+                n_rows,
+                y.len()
+            )));
+        }
+        if let Some(tree) = self.trees.first() {
+            if tree.num_features() != num_attributes {
+                return Err(Failed::fit(&format!(
+                    "Number of features of x ({}) does not match the number of features the forest was fitted on ({})",
+                    num_attributes,
+                    tree.num_features()
+                )));
+            }
+        }
+        if let Some(tree_samples) = self.samples.as_ref().and_then(|s| s.first()) {
+            if tree_samples.len() != n_rows {
+                return Err(Failed::fit(
+                    "The forest keeps its bootstrap samples and can only be extended on the data it was fitted on",
+                ));
+            }
+        }
+
+        let mut yi: Vec<usize> = vec![0; n_rows];
+        for (i, yi_i) in yi.iter_mut().enumerate() {
+            let yc = y.get(i);
+            *yi_i = self
+                .classes
+                .iter()
+                .position(|c| yc == *c)
+                .ok_or_else(|| Failed::fit(&format!("Unknown class: {}", yc)))?;
         }
 
+        let parameters = &self._parameters;
         let mtry = parameters.m.unwrap_or_else(|| {
             (T::from(num_attributes).unwrap())
                 .sqrt()
@@ -459,43 +564,115 @@ impl<T: RealNumber> RandomForestClassifier<T> {
         });
 
         let mut rng = get_rng_impl(Some(parameters.seed));
-        let classes = y_m.unique();
-        let k = classes.len();
-        let mut trees: Vec<DecisionTreeClassifier<T>> = Vec::new();
+        let k = self.classes.len();
 
-        let mut maybe_all_samples: Option<Vec<Vec<bool>>> = Option::None;
-        if parameters.keep_samples {
-            maybe_all_samples = Some(Vec::new());
+        // replay the bootstrap samples of the existing trees, so that the new trees continue the same random sequence
+        for _ in 0..self.trees.len() {
+            RandomForestClassifier::<T>::sample_with_replacement(&yi, k, &mut rng);
         }
 
-        for _ in 0..parameters.n_trees {
-            let samples = RandomForestClassifier::<T>::sample_with_replacement(&yi, k, &mut rng);
-            if let Some(ref mut all_samples) = maybe_all_samples {
+        // bootstrap samples are drawn upfront, so that trees can be fitted in any order
+        let tree_samples: Vec<Vec<usize>> = (0..n_trees)
+            .map(|_| RandomForestClassifier::<T>::sample_with_replacement(&yi, k, &mut rng))
+            .collect();
+        if let Some(ref mut all_samples) = self.samples {
+            for samples in tree_samples.iter() {
                 all_samples.push(samples.iter().map(|x| *x != 0).collect())
             }
+        }
 
+        let trees = map_slice(&tree_samples, |samples| {
             let params = DecisionTreeClassifierParameters {
                 criterion: parameters.criterion.clone(),
                 max_depth: parameters.max_depth,
                 min_samples_leaf: parameters.min_samples_leaf,
                 min_samples_split: parameters.min_samples_split,
                 seed: Some(parameters.seed),
+                split_type: SplitType::AxisAligned,
+                max_leaf_nodes: None,
+                growth_strategy: GrowthStrategy::BreadthFirst,
             };
-            let tree = DecisionTreeClassifier::fit_weak_learner(x, y, samples, mtry, params)?;
-            trees.push(tree);
+            DecisionTreeClassifier::fit_weak_learner(x, y, samples.clone(), mtry, params)
+        });
+        for tree in trees {
+            self.trees.push(tree?);
         }
 
-        Ok(RandomForestClassifier {
-            _parameters: parameters,
-            trees,
-            classes,
-            samples: maybe_all_samples,
+        if self.samples.is_some() {
+            let (oob_predictions, oob_score) = self.oob_fit(x, &y.to_vec());
+            self.oob_predictions = Some(oob_predictions);
+            self.oob_score = Some(oob_score);
+        }
+
+        Ok(())
+    }
+
+    /// Returns out-of-bag predictions for the training set. The prediction for every observation is made only by the trees
+    /// that did not see the observation in their bootstrap sample. Observations that were used to build every tree get `NaN`.
+    /// Requires `keep_samples` to be set to `true`.
+    pub fn oob_predictions(&self) -> Result<&Vec<T>, Failed> {
+        self.oob_predictions.as_ref().ok_or_else(|| {
+            Failed::because(
+                FailedError::PredictFailed,
+                "Need samples=true for OOB predictions.",
+            )
+        })
+    }
+
+    /// Returns the accuracy of the [out-of-bag predictions](RandomForestClassifier::oob_predictions) on the training set.
+    /// This is an estimate of the generalization accuracy of the forest that does not require a held-out set.
+    /// Requires `keep_samples` to be set to `true`.
+    pub fn oob_score(&self) -> Result<T, Failed> {
+        self.oob_score.ok_or_else(|| {
+            Failed::because(
+                FailedError::PredictFailed,
+                "Need samples=true for OOB score.",
+            )
         })
     }
 
+    /// Returns the impurity-based importance of every feature averaged over all trees of the forest and normalized to sum to 1.
+    /// See [tree inspection](../../tree/node/index.html).
+    pub fn feature_importances(&self) -> Vec<T> {
+        aggregate_importances(
+            self.trees
+                .iter()
+                .map(|tree| (T::one(), tree.feature_importances())),
+        )
+    }
+
+    /// Returns the number of nodes split on every feature, summed over all trees of the forest.
+    pub fn split_counts(&self) -> Vec<usize> {
+        aggregate_split_counts(self.trees.iter().map(|tree| tree.split_counts()))
+    }
+
+    fn oob_fit<M: Matrix<T>>(&self, x: &M, y: &[T]) -> (Vec<T>, T) {
+        let samples = self.samples.as_ref().unwrap();
+        let mut oob_predictions = vec![T::nan(); y.len()];
+        let mut y_true: Vec<T> = Vec::new();
+        let mut y_pred: Vec<T> = Vec::new();
+
+        for (i, oob_prediction) in oob_predictions.iter_mut().enumerate() {
+            if samples.iter().any(|tree_samples| !tree_samples[i]) {
+                *oob_prediction = self.classes[self.predict_for_row_oob(x, i)];
+                y_true.push(y[i]);
+                y_pred.push(*oob_prediction);
+            }
+        }
+
+        let oob_score = if y_true.is_empty() {
+            T::nan()
+        } else {
+            ClassificationMetrics::accuracy().get_score(&y_true, &y_pred)
+        };
+
+        (oob_predictions, oob_score)
+    }
+
     /// Predict class for `x`
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
         let mut result = M::zeros(1, x.shape().0);
 
         let (n, _) = x.shape();
@@ -507,6 +684,31 @@ impl<T: RealNumber> RandomForestClassifier<T> {
         Ok(result.to_row_vector())
     }
 
+    /// Predict class probabilities for `x`, the probability of a class is the fraction of trees that vote for it.
+    /// Returns _KxC_ matrix where _C_ is the number of classes, columns follow the order of sorted class labels.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_proba<M: Matrix<T>>(&self, x: &M) -> Result<M, Failed> {
+        check_predict(x)?;
+        let (n, _) = x.shape();
+        let mut result = M::zeros(n, self.classes.len());
+        let n_trees = T::from_usize(self.trees.len()).unwrap();
+
+        for tree in self.trees.iter() {
+            for i in 0..n {
+                let class_index = tree.predict_for_row(x, i);
+                result.add_element_mut(i, class_index, T::one());
+            }
+        }
+        result.div_scalar_mut(n_trees);
+
+        Ok(result)
+    }
+
+    /// Class labels, sorted.
+    pub fn classes(&self) -> &Vec<T> {
+        &self.classes
+    }
+
     fn predict_for_row<M: Matrix<T>>(&self, x: &M, row: usize) -> usize {
         let mut result = vec![0; self.classes.len()];
 
@@ -577,10 +779,69 @@ impl<T: RealNumber> RandomForestClassifier<T> {
     }
 }
 
+/// Every tree votes for the majority class of its leaf, the scores of the classes are the fractions of votes.
+#[cfg(feature = "onnx")]
+impl<T: RealNumber> ToOnnx for RandomForestClassifier<T> {
+    fn to_onnx(&self) -> Result<Vec<u8>, Failed> {
+        let labels = onnx::class_labels(&self.classes)?;
+        let vote = 1. / self.trees.len() as f32;
+        let mut ensemble = TreeEnsemble::default();
+        for (tree_id, tree) in self.trees.iter().enumerate() {
+            tree.add_to_ensemble(&mut ensemble, tree_id, |probabilities| {
+                vec![(which_max(probabilities), vote)]
+            })?;
+        }
+        Ok(ensemble
+            .classifier::<T>(labels, &[], "NONE")
+            .classifier_model(self.trees[0].num_features(), self.classes.len()))
+    }
+}
+
+/// Every tree is a segment of a mining model, the predicted class is the majority vote of the trees.
+#[cfg(feature = "pmml")]
+impl<T: RealNumber> ToPmml for RandomForestClassifier<T> {
+    fn to_pmml(&self) -> Result<String, Failed> {
+        let n_features = self.trees[0].num_features();
+        let target = Target::Categorical(&self.classes);
+        let trees = self
+            .trees
+            .iter()
+            .map(|t| Ok(tree::tree_model(&t.pmml_nodes()?, n_features, &target)))
+            .collect::<Result<Vec<_>, Failed>>()?;
+        let model = tree::forest_model(trees, n_features, &target, "majorityVote");
+        Ok(pmml::document(n_features, &target, model))
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber> FromPmml for RandomForestClassifier<T> {
+    fn from_pmml(pmml: &str) -> Result<Self, Failed> {
+        let document = Document::parse(pmml)?;
+        let (model, schema) = document.model("MiningModel", "classification")?;
+        let classes: Vec<T> = document.classes(&schema)?;
+        let trees = tree::read_forest(model, "majorityVote")?
+            .into_iter()
+            .map(|model| {
+                let nodes = tree::read_tree(model, &schema, &classes)?;
+                DecisionTreeClassifier::from_pmml_nodes(nodes, classes.clone(), schema.n_features())
+            })
+            .collect::<Result<Vec<_>, Failed>>()?;
+        Ok(RandomForestClassifier {
+            _parameters: RandomForestClassifierParameters::default(),
+            trees,
+            classes,
+            samples: None,
+            oob_predictions: None,
+            oob_score: None,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::linalg::BaseMatrix;
     use crate::metrics::*;
 
     #[test]
@@ -651,7 +912,14 @@ mod tests {
         )
         .unwrap();
 
-        assert!(accuracy(&y, &classifier.predict(&x).unwrap()) >= 0.95);
+        let y_hat = classifier.predict(&x).unwrap();
+        assert!(accuracy(&y, &y_hat) >= 0.95);
+
+        let proba: DenseMatrix<f64> = classifier.predict_proba(&x).unwrap();
+        for (i, class_index) in proba.argmax().iter().enumerate() {
+            assert!((proba.get(i, 0) + proba.get(i, 1) - 1.).abs() < 1e-8);
+            assert_eq!(classifier.classes()[*class_index], y_hat[i]);
+        }
     }
 
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
@@ -703,6 +971,100 @@ mod tests {
             accuracy(&y, &classifier.predict_oob(&x).unwrap())
                 < accuracy(&y, &classifier.predict(&x).unwrap())
         );
+
+        let y_hat_oob: &Vec<f64> = classifier.oob_predictions().unwrap();
+        assert_eq!(y_hat_oob, &classifier.predict_oob(&x).unwrap());
+        assert!((classifier.oob_score().unwrap() - accuracy(&y, y_hat_oob)).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn oob_score_requires_samples() {
+        let x = DenseMatrix::from_2d_array(&[&[1., 1.], &[1., 2.], &[5., 1.], &[5., 2.]]);
+        let y = vec![0., 0., 1., 1.];
+
+        let classifier =
+            RandomForestClassifier::fit(&x, &y, RandomForestClassifierParameters::default())
+                .unwrap();
+
+        assert!(classifier.oob_score().is_err());
+        assert!(classifier.oob_predictions().is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_more() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[5.1, 3.5, 1.4, 0.2],
+            &[4.9, 3.0, 1.4, 0.2],
+            &[4.7, 3.2, 1.3, 0.2],
+            &[4.6, 3.1, 1.5, 0.2],
+            &[5.0, 3.6, 1.4, 0.2],
+            &[5.4, 3.9, 1.7, 0.4],
+            &[4.6, 3.4, 1.4, 0.3],
+            &[5.0, 3.4, 1.5, 0.2],
+            &[4.4, 2.9, 1.4, 0.2],
+            &[4.9, 3.1, 1.5, 0.1],
+            &[7.0, 3.2, 4.7, 1.4],
+            &[6.4, 3.2, 4.5, 1.5],
+            &[6.9, 3.1, 4.9, 1.5],
+            &[5.5, 2.3, 4.0, 1.3],
+            &[6.5, 2.8, 4.6, 1.5],
+            &[5.7, 2.8, 4.5, 1.3],
+            &[6.3, 3.3, 4.7, 1.6],
+            &[4.9, 2.4, 3.3, 1.0],
+            &[6.6, 2.9, 4.6, 1.3],
+            &[5.2, 2.7, 3.9, 1.4],
+        ]);
+        let y = vec![
+            0., 0., 0., 0., 0., 0., 0., 0., 0., 0., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1.,
+        ];
+        let parameters = RandomForestClassifierParameters::default()
+            .with_n_trees(10)
+            .with_keep_samples(true);
+
+        let mut classifier = RandomForestClassifier::fit(&x, &y, parameters.clone()).unwrap();
+        classifier.fit_more(&x, &y, 20).unwrap();
+        let expected = RandomForestClassifier::fit(&x, &y, parameters.with_n_trees(30)).unwrap();
+
+        assert_eq!(classifier, expected);
+        assert_eq!(
+            classifier.oob_predictions().unwrap(),
+            expected.oob_predictions().unwrap()
+        );
+
+        let y_unknown_class = vec![2.; 20];
+        assert!(classifier.fit_more(&x, &y_unknown_class, 1).is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn feature_importances() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 5., 3.],
+            &[2., 5., 1.],
+            &[3., 5., 2.],
+            &[4., 5., 3.],
+            &[5., 5., 1.],
+            &[6., 5., 2.],
+            &[7., 5., 3.],
+            &[8., 5., 1.],
+        ]);
+        let y = vec![0., 0., 0., 0., 1., 1., 1., 1.];
+
+        let classifier = RandomForestClassifier::fit(
+            &x,
+            &y,
+            RandomForestClassifierParameters::default().with_n_trees(10),
+        )
+        .unwrap();
+        let importances = classifier.feature_importances();
+
+        assert_eq!(importances.len(), 3);
+        assert_eq!(importances[1], 0.);
+        assert!((importances.iter().sum::<f64>() - 1.).abs() < 1e-8);
+        assert!(importances[0] > importances[2]);
+        assert_eq!(classifier.split_counts()[1], 0);
     }
 
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
@@ -43,22 +43,38 @@
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 
+#[cfg(feature = "pmml")]
+use alloc::string::String;
+use alloc::vec::Vec;
 use rand::Rng;
 
-use std::default::Default;
-use std::fmt::Debug;
+use core::default::Default;
+use core::fmt::Debug;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::api::{Predictor, SupervisedEstimator};
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::api::{FeatureImportance, Predictor, SupervisedEstimator};
+use crate::ensemble::{aggregate_importances, aggregate_split_counts};
 use crate::error::{Failed, FailedError};
-use crate::linalg::Matrix;
+use crate::linalg::{BaseVector, Matrix};
 use crate::math::num::RealNumber;
+use crate::metrics::RegressionMetrics;
+#[cfg(feature = "onnx")]
+use crate::onnx::{tree::TreeEnsemble, ToOnnx};
+use crate::parallel::map_slice;
+#[cfg(feature = "pmml")]
+use crate::pmml::{self, tree, Document, FromPmml, Target, ToPmml};
 use crate::rand::get_rng_impl;
 use crate::tree::decision_tree_regressor::{
-    DecisionTreeRegressor, DecisionTreeRegressorParameters,
+    weighted_quantile, DecisionTreeRegressor, DecisionTreeRegressorParameters,
 };
+use crate::tree::oblique::SplitType;
+use crate::tree::GrowthStrategy;
+use crate::validation::{check_fit, check_predict};
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
@@ -86,6 +102,9 @@ pub struct RandomForestRegressorParameters {
     #[cfg_attr(feature = "serde", serde(default))]
     /// Seed used for bootstrap sampling and feature selection for each tree.
     pub seed: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to keep the training response values in every leaf. This is required for quantile predictions.
+    pub keep_leaf_values: bool,
 }
 
 /// Random Forest Regressor
@@ -95,6 +114,8 @@ pub struct RandomForestRegressor<T: RealNumber> {
     _parameters: RandomForestRegressorParameters,
     trees: Vec<DecisionTreeRegressor<T>>,
     samples: Option<Vec<Vec<bool>>>,
+    oob_predictions: Option<Vec<T>>,
+    oob_score: Option<T>,
 }
 
 impl RandomForestRegressorParameters {
@@ -135,6 +156,12 @@ impl RandomForestRegressorParameters {
         self.seed = seed;
         self
     }
+
+    /// Whether to keep the training response values in every leaf. This is required for quantile predictions.
+    pub fn with_keep_leaf_values(mut self, keep_leaf_values: bool) -> Self {
+        self.keep_leaf_values = keep_leaf_values;
+        self
+    }
 }
 impl Default for RandomForestRegressorParameters {
     fn default() -> Self {
@@ -146,6 +173,7 @@ impl Default for RandomForestRegressorParameters {
             m: Option::None,
             keep_samples: false,
             seed: 0,
+            keep_leaf_values: false,
         }
     }
 }
@@ -184,6 +212,12 @@ impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for RandomForestReg
     }
 }
 
+impl<T: RealNumber> FeatureImportance<T> for RandomForestRegressor<T> {
+    fn feature_importance(&self) -> Vec<T> {
+        self.feature_importances()
+    }
+}
+
 /// RandomForestRegressor grid search parameters
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
@@ -209,6 +243,9 @@ pub struct RandomForestRegressorSearchParameters {
     #[cfg_attr(feature = "serde", serde(default))]
     /// Seed used for bootstrap sampling and feature selection for each tree.
     pub seed: Vec<u64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to keep the training response values in every leaf. This is required for quantile predictions.
+    pub keep_leaf_values: Vec<bool>,
 }
 
 /// RandomForestRegressor grid search iterator
@@ -221,6 +258,7 @@ pub struct RandomForestRegressorSearchParametersIterator {
     current_m: usize,
     current_keep_samples: usize,
     current_seed: usize,
+    current_keep_leaf_values: usize,
 }
 
 impl IntoIterator for RandomForestRegressorSearchParameters {
@@ -237,6 +275,7 @@ impl IntoIterator for RandomForestRegressorSearchParameters {
             current_m: 0,
             current_keep_samples: 0,
             current_seed: 0,
+            current_keep_leaf_values: 0,
         }
     }
 }
@@ -268,6 +307,11 @@ impl Iterator for RandomForestRegressorSearchParametersIterator {
                     .keep_samples
                     .len()
             && self.current_seed == self.random_forest_regressor_search_parameters.seed.len()
+            && self.current_keep_leaf_values
+                == self
+                    .random_forest_regressor_search_parameters
+                    .keep_leaf_values
+                    .len()
         {
             return None;
         }
@@ -286,6 +330,9 @@ impl Iterator for RandomForestRegressorSearchParametersIterator {
             keep_samples: self.random_forest_regressor_search_parameters.keep_samples
                 [self.current_keep_samples],
             seed: self.random_forest_regressor_search_parameters.seed[self.current_seed],
+            keep_leaf_values: self
+                .random_forest_regressor_search_parameters
+                .keep_leaf_values[self.current_keep_leaf_values],
         };
 
         if self.current_max_depth + 1
@@ -346,6 +393,20 @@ impl Iterator for RandomForestRegressorSearchParametersIterator {
             self.current_m = 0;
             self.current_keep_samples = 0;
             self.current_seed += 1;
+        } else if self.current_keep_leaf_values + 1
+            < self
+                .random_forest_regressor_search_parameters
+                .keep_leaf_values
+                .len()
+        {
+            self.current_max_depth = 0;
+            self.current_min_samples_leaf = 0;
+            self.current_min_samples_split = 0;
+            self.current_n_trees = 0;
+            self.current_m = 0;
+            self.current_keep_samples = 0;
+            self.current_seed = 0;
+            self.current_keep_leaf_values += 1;
         } else {
             self.current_max_depth += 1;
             self.current_min_samples_leaf += 1;
@@ -354,6 +415,7 @@ impl Iterator for RandomForestRegressorSearchParametersIterator {
             self.current_m += 1;
             self.current_keep_samples += 1;
             self.current_seed += 1;
+            self.current_keep_leaf_values += 1;
         }
 
         Some(next)
@@ -372,6 +434,7 @@ impl Default for RandomForestRegressorSearchParameters {
             m: vec![default_params.m],
             keep_samples: vec![default_params.keep_samples],
             seed: vec![default_params.seed],
+            keep_leaf_values: vec![default_params.keep_leaf_values],
         }
     }
 }
@@ -385,45 +448,188 @@ impl<T: RealNumber> RandomForestRegressor<T> {
         y: &M::RowVector,
         parameters: RandomForestRegressorParameters,
     ) -> Result<RandomForestRegressor<T>, Failed> {
+        check_fit(x, y)?;
+        let n_trees = parameters.n_trees;
+        let samples = if parameters.keep_samples {
+            Some(Vec::new())
+        } else {
+            None
+        };
+
+        let mut forest = RandomForestRegressor {
+            _parameters: parameters,
+            trees: Vec::new(),
+            samples,
+            oob_predictions: None,
+            oob_score: None,
+        };
+        forest.fit_trees(x, y, n_trees)?;
+
+        Ok(forest)
+    }
+
+    /// Resumes fitting of the forest: keeps the existing trees and grows `n_additional_trees` new trees on the training set.
+    /// Use it to tune the size of the ensemble incrementally, e.g. watching the [out-of-bag score](RandomForestRegressor::oob_score).
+    /// When `x` and `y` are the data the forest was fitted on, the result is the same as fitting a forest of the combined size from scratch.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target values
+    /// * `n_additional_trees` - the number of trees to add to the forest.
+    pub fn fit_more<M: Matrix<T>>(
+        &mut self,
+        x: &M,
+        y: &M::RowVector,
+        n_additional_trees: usize,
+    ) -> Result<(), Failed> {
+        self.fit_trees(x, y, n_additional_trees)?;
+        self._parameters.n_trees += n_additional_trees;
+        Ok(())
+    }
+
+    fn fit_trees<M: Matrix<T>>(
+        &mut self,
+        x: &M,
+        y: &M::RowVector,
+        n_trees: usize,
+    ) -> Result<(), Failed> {
         let (n_rows, num_attributes) = x.shape();
+        if y.len() != n_rows {
+            return Err(Failed::fit(&format!(
+                "Size of x ({}) does not match size of y ({})",
+                n_rows,
+                y.len()
+            )));
+        }
+        if let Some(tree) = self.trees.first() {
+            if tree.num_features() != num_attributes {
+                return Err(Failed::fit(&format!(
+                    "Number of features of x ({}) does not match the number of features the forest was fitted on ({})",
+                    num_attributes,
+                    tree.num_features()
+                )));
+            }
+        }
+        if let Some(tree_samples) = self.samples.as_ref().and_then(|s| s.first()) {
+            if tree_samples.len() != n_rows {
+                return Err(Failed::fit(
+                    "The forest keeps its bootstrap samples and can only be extended on the data it was fitted on",
+                ));
+            }
+        }
 
+        let parameters = &self._parameters;
         let mtry = parameters
             .m
             .unwrap_or((num_attributes as f64).sqrt().floor() as usize);
 
         let mut rng = get_rng_impl(Some(parameters.seed));
-        let mut trees: Vec<DecisionTreeRegressor<T>> = Vec::new();
 
-        let mut maybe_all_samples: Option<Vec<Vec<bool>>> = Option::None;
-        if parameters.keep_samples {
-            maybe_all_samples = Some(Vec::new());
+        // replay the bootstrap samples of the existing trees, so that the new trees continue the same random sequence
+        for _ in 0..self.trees.len() {
+            RandomForestRegressor::<T>::sample_with_replacement(n_rows, &mut rng);
         }
 
-        for _ in 0..parameters.n_trees {
-            let samples = RandomForestRegressor::<T>::sample_with_replacement(n_rows, &mut rng);
-            if let Some(ref mut all_samples) = maybe_all_samples {
+        // bootstrap samples are drawn upfront, so that trees can be fitted in any order
+        let tree_samples: Vec<Vec<usize>> = (0..n_trees)
+            .map(|_| RandomForestRegressor::<T>::sample_with_replacement(n_rows, &mut rng))
+            .collect();
+        if let Some(ref mut all_samples) = self.samples {
+            for samples in tree_samples.iter() {
                 all_samples.push(samples.iter().map(|x| *x != 0).collect())
             }
+        }
+
+        let trees = map_slice(&tree_samples, |samples| {
             let params = DecisionTreeRegressorParameters {
                 max_depth: parameters.max_depth,
                 min_samples_leaf: parameters.min_samples_leaf,
                 min_samples_split: parameters.min_samples_split,
                 seed: Some(parameters.seed),
+                split_type: SplitType::AxisAligned,
+                max_leaf_nodes: None,
+                growth_strategy: GrowthStrategy::BreadthFirst,
+                keep_leaf_values: parameters.keep_leaf_values,
             };
-            let tree = DecisionTreeRegressor::fit_weak_learner(x, y, samples, mtry, params)?;
-            trees.push(tree);
+            DecisionTreeRegressor::fit_weak_learner(x, y, samples.clone(), mtry, params)
+        });
+        for tree in trees {
+            self.trees.push(tree?);
         }
 
-        Ok(RandomForestRegressor {
-            _parameters: parameters,
-            trees,
-            samples: maybe_all_samples,
+        if self.samples.is_some() {
+            let (oob_predictions, oob_score) = self.oob_fit(x, &y.to_vec());
+            self.oob_predictions = Some(oob_predictions);
+            self.oob_score = Some(oob_score);
+        }
+
+        Ok(())
+    }
+
+    /// Returns out-of-bag predictions for the training set. The prediction for every observation is made only by the trees
+    /// that did not see the observation in their bootstrap sample. Observations that were used to build every tree get `NaN`.
+    /// Requires `keep_samples` to be set to `true`.
+    pub fn oob_predictions(&self) -> Result<&Vec<T>, Failed> {
+        self.oob_predictions.as_ref().ok_or_else(|| {
+            Failed::because(
+                FailedError::PredictFailed,
+                "Need samples=true for OOB predictions.",
+            )
         })
     }
 
+    /// Returns the coefficient of determination \\(R^2\\) of the [out-of-bag predictions](RandomForestRegressor::oob_predictions) on the training set.
+    /// This is an estimate of the generalization performance of the forest that does not require a held-out set.
+    /// Requires `keep_samples` to be set to `true`.
+    pub fn oob_score(&self) -> Result<T, Failed> {
+        self.oob_score.ok_or_else(|| {
+            Failed::because(
+                FailedError::PredictFailed,
+                "Need samples=true for OOB score.",
+            )
+        })
+    }
+
+    /// Returns the impurity-based importance of every feature averaged over all trees of the forest and normalized to sum to 1.
+    /// See [tree inspection](../../tree/node/index.html).
+    pub fn feature_importances(&self) -> Vec<T> {
+        aggregate_importances(
+            self.trees
+                .iter()
+                .map(|tree| (T::one(), tree.feature_importances())),
+        )
+    }
+
+    /// Returns the number of nodes split on every feature, summed over all trees of the forest.
+    pub fn split_counts(&self) -> Vec<usize> {
+        aggregate_split_counts(self.trees.iter().map(|tree| tree.split_counts()))
+    }
+
+    fn oob_fit<M: Matrix<T>>(&self, x: &M, y: &[T]) -> (Vec<T>, T) {
+        let samples = self.samples.as_ref().unwrap();
+        let mut oob_predictions = vec![T::nan(); y.len()];
+        let mut y_true: Vec<T> = Vec::new();
+        let mut y_pred: Vec<T> = Vec::new();
+
+        for (i, oob_prediction) in oob_predictions.iter_mut().enumerate() {
+            if samples.iter().any(|tree_samples| !tree_samples[i]) {
+                *oob_prediction = self.predict_for_row_oob(x, i);
+                y_true.push(y[i]);
+                y_pred.push(*oob_prediction);
+            }
+        }
+
+        let oob_score = if y_true.is_empty() {
+            T::nan()
+        } else {
+            RegressionMetrics::r2().get_score(&y_true, &y_pred)
+        };
+
+        (oob_predictions, oob_score)
+    }
+
     /// Predict class for `x`
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
         let mut result = M::zeros(1, x.shape().0);
 
         let (n, _) = x.shape();
@@ -447,6 +653,43 @@ impl<T: RealNumber> RandomForestRegressor<T> {
         result / T::from(n_trees).unwrap()
     }
 
+    /// Predict quantile `q` of the response for `x` with a [quantile regression forest](https://www.jmlr.org/papers/v7/meinshausen06a.html).
+    /// The conditional distribution of the response is estimated by weighting the training observations found in the same leaf as `x` in every tree.
+    /// The forest must be fitted with `keep_leaf_values` set to `true`.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    /// * `q` - quantile to predict, a number between 0 and 1.
+    pub fn predict_quantile<M: Matrix<T>>(&self, x: &M, q: T) -> Result<M::RowVector, Failed> {
+        if !(T::zero()..=T::one()).contains(&q) {
+            return Err(Failed::predict(&format!(
+                "Quantile should be between 0 and 1, got {}",
+                q
+            )));
+        }
+        if !self._parameters.keep_leaf_values {
+            return Err(Failed::because(
+                FailedError::PredictFailed,
+                "Need keep_leaf_values=true for quantile predictions.",
+            ));
+        }
+
+        let (n, _) = x.shape();
+        let mut result = M::zeros(1, n);
+
+        for i in 0..n {
+            let mut values: Vec<(T, T)> = Vec::new();
+            for tree in self.trees.iter() {
+                let leaf_values = tree.leaf_values_for_row(x, i);
+                if !leaf_values.is_empty() {
+                    let weight = T::one() / T::from(leaf_values.len()).unwrap();
+                    values.extend(leaf_values.iter().map(|v| (*v, weight)));
+                }
+            }
+            result.set(0, i, weighted_quantile(&mut values, q));
+        }
+
+        Ok(result.to_row_vector())
+    }
+
     /// Predict OOB classes for `x`. `x` is expected to be equal to the dataset used in training.
     pub fn predict_oob<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed> {
         let (n, _) = x.shape();
@@ -496,11 +739,67 @@ impl<T: RealNumber> RandomForestRegressor<T> {
     }
 }
 
+#[cfg(feature = "onnx")]
+impl<T: RealNumber> ToOnnx for RandomForestRegressor<T> {
+    fn to_onnx(&self) -> Result<Vec<u8>, Failed> {
+        let mut ensemble = TreeEnsemble::default();
+        for (tree_id, tree) in self.trees.iter().enumerate() {
+            ensemble.add_tree(tree_id, tree.nodes(), |node| {
+                vec![(0, node.output.to_f32().unwrap())]
+            })?;
+        }
+        Ok(ensemble
+            .regressor("AVERAGE", 0.)
+            .regressor_model(self.trees[0].num_features()))
+    }
+}
+
+/// Every tree is a segment of a mining model, the prediction is the average of the trees.
+#[cfg(feature = "pmml")]
+impl<T: RealNumber> ToPmml for RandomForestRegressor<T> {
+    fn to_pmml(&self) -> Result<String, Failed> {
+        let n_features = self.trees[0].num_features();
+        let target = Target::Continuous;
+        let trees = self
+            .trees
+            .iter()
+            .map(|t| Ok(tree::tree_model(&t.pmml_nodes()?, n_features, &target)))
+            .collect::<Result<Vec<_>, Failed>>()?;
+        let model = tree::forest_model(trees, n_features, &target, "average");
+        Ok(pmml::document(n_features, &target, model))
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber> FromPmml for RandomForestRegressor<T> {
+    fn from_pmml(pmml: &str) -> Result<Self, Failed> {
+        let document = Document::parse(pmml)?;
+        let (model, schema) = document.model("MiningModel", "regression")?;
+        let trees = tree::read_forest(model, "average")?
+            .into_iter()
+            .map(|model| {
+                let nodes = tree::read_tree(model, &schema, &[])?;
+                Ok(DecisionTreeRegressor::from_pmml_nodes(
+                    nodes,
+                    schema.n_features(),
+                ))
+            })
+            .collect::<Result<Vec<_>, Failed>>()?;
+        Ok(RandomForestRegressor {
+            _parameters: RandomForestRegressorParameters::default(),
+            trees,
+            samples: None,
+            oob_predictions: None,
+            oob_score: None,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::linalg::naive::dense_matrix::DenseMatrix;
-    use crate::metrics::mean_absolute_error;
+    use crate::metrics::{mean_absolute_error, r2};
 
     #[test]
     fn search_parameters() {
@@ -562,6 +861,7 @@ mod tests {
                 m: Option::None,
                 keep_samples: false,
                 seed: 87,
+                keep_leaf_values: false,
             },
         )
         .and_then(|rf| rf.predict(&x))
@@ -607,6 +907,7 @@ mod tests {
                 m: Option::None,
                 keep_samples: true,
                 seed: 87,
+                keep_leaf_values: false,
             },
         )
         .unwrap();
@@ -617,6 +918,181 @@ mod tests {
         assert!(mean_absolute_error(&y, &y_hat) < mean_absolute_error(&y, &y_hat_oob));
     }
 
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn oob_score_longley() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[234.289, 235.6, 159., 107.608, 1947., 60.323],
+            &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+            &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+            &[284.599, 335.1, 165., 110.929, 1950., 61.187],
+            &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+            &[346.999, 193.2, 359.4, 113.27, 1952., 63.639],
+            &[365.385, 187., 354.7, 115.094, 1953., 64.989],
+            &[363.112, 357.8, 335., 116.219, 1954., 63.761],
+            &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+            &[419.18, 282.2, 285.7, 118.734, 1956., 67.857],
+            &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+            &[444.546, 468.1, 263.7, 121.95, 1958., 66.513],
+            &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+            &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+            &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+            &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+        ]);
+        let y = vec![
+            83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0, 100.0, 101.2, 104.6, 108.4, 110.8, 112.6,
+            114.2, 115.7, 116.9,
+        ];
+
+        let regressor = RandomForestRegressor::fit(
+            &x,
+            &y,
+            RandomForestRegressorParameters::default()
+                .with_n_trees(100)
+                .with_keep_samples(true),
+        )
+        .unwrap();
+
+        let y_hat_oob: &Vec<f64> = regressor.oob_predictions().unwrap();
+        assert_eq!(y_hat_oob, &regressor.predict_oob(&x).unwrap());
+        assert!((regressor.oob_score().unwrap() - r2(&y, y_hat_oob)).abs() < 1e-8);
+        assert!(regressor.oob_score().unwrap() > 0.5);
+
+        let regressor =
+            RandomForestRegressor::fit(&x, &y, RandomForestRegressorParameters::default()).unwrap();
+        assert!(regressor.oob_score().is_err());
+        assert!(regressor.oob_predictions().is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn predict_quantile_longley() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[234.289, 235.6, 159., 107.608, 1947., 60.323],
+            &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+            &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+            &[284.599, 335.1, 165., 110.929, 1950., 61.187],
+            &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+            &[346.999, 193.2, 359.4, 113.27, 1952., 63.639],
+            &[365.385, 187., 354.7, 115.094, 1953., 64.989],
+            &[363.112, 357.8, 335., 116.219, 1954., 63.761],
+            &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+            &[419.18, 282.2, 285.7, 118.734, 1956., 67.857],
+            &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+            &[444.546, 468.1, 263.7, 121.95, 1958., 66.513],
+            &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+            &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+            &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+            &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+        ]);
+        let y = vec![
+            83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0, 100.0, 101.2, 104.6, 108.4, 110.8, 112.6,
+            114.2, 115.7, 116.9,
+        ];
+
+        let regressor = RandomForestRegressor::fit(
+            &x,
+            &y,
+            RandomForestRegressorParameters::default()
+                .with_n_trees(100)
+                .with_min_samples_leaf(3)
+                .with_keep_leaf_values(true),
+        )
+        .unwrap();
+
+        let lower = regressor.predict_quantile(&x, 0.05).unwrap();
+        let median = regressor.predict_quantile(&x, 0.5).unwrap();
+        let upper = regressor.predict_quantile(&x, 0.95).unwrap();
+
+        for i in 0..y.len() {
+            assert!(lower[i] <= median[i] && median[i] <= upper[i]);
+            assert!(lower[i] <= y[i] && y[i] <= upper[i]);
+        }
+        assert!(mean_absolute_error(&y, &median) < 5.);
+
+        let regressor =
+            RandomForestRegressor::fit(&x, &y, RandomForestRegressorParameters::default()).unwrap();
+        assert!(regressor.predict_quantile(&x, 0.5).is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_more() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[234.289, 235.6, 159., 107.608, 1947., 60.323],
+            &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+            &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+            &[284.599, 335.1, 165., 110.929, 1950., 61.187],
+            &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+            &[346.999, 193.2, 359.4, 113.27, 1952., 63.639],
+            &[365.385, 187., 354.7, 115.094, 1953., 64.989],
+            &[363.112, 357.8, 335., 116.219, 1954., 63.761],
+            &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+            &[419.18, 282.2, 285.7, 118.734, 1956., 67.857],
+            &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+            &[444.546, 468.1, 263.7, 121.95, 1958., 66.513],
+            &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+            &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+            &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+            &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+        ]);
+        let y = vec![
+            83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0, 100.0, 101.2, 104.6, 108.4, 110.8, 112.6,
+            114.2, 115.7, 116.9,
+        ];
+        let parameters = RandomForestRegressorParameters::default()
+            .with_n_trees(5)
+            .with_keep_samples(true);
+
+        let mut regressor = RandomForestRegressor::fit(&x, &y, parameters.clone()).unwrap();
+        regressor.fit_more(&x, &y, 15).unwrap();
+        let expected = RandomForestRegressor::fit(&x, &y, parameters.with_n_trees(20)).unwrap();
+
+        assert_eq!(regressor, expected);
+        assert_eq!(
+            regressor.oob_score().unwrap(),
+            expected.oob_score().unwrap()
+        );
+        assert_eq!(
+            regressor.predict(&x).unwrap(),
+            expected.predict(&x).unwrap()
+        );
+
+        assert!(regressor
+            .fit_more(&DenseMatrix::from_2d_array(&[&[1., 2.]]), &vec![1.], 1)
+            .is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn feature_importances() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 5., 3.],
+            &[2., 5., 1.],
+            &[3., 5., 2.],
+            &[4., 5., 3.],
+            &[5., 5., 1.],
+            &[6., 5., 2.],
+            &[7., 5., 3.],
+            &[8., 5., 1.],
+        ]);
+        let y = vec![1., 1., 1., 1., 5., 5., 5., 5.];
+
+        let regressor = RandomForestRegressor::fit(
+            &x,
+            &y,
+            RandomForestRegressorParameters::default().with_n_trees(10),
+        )
+        .unwrap();
+        let importances = regressor.feature_importances();
+
+        assert_eq!(importances.len(), 3);
+        assert_eq!(importances[1], 0.);
+        assert!((importances.iter().sum::<f64>() - 1.).abs() < 1e-8);
+        assert!(importances[0] > importances[2]);
+        assert_eq!(regressor.split_counts()[1], 0);
+    }
+
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     #[test]
     #[cfg(feature = "serde")]
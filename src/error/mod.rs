@@ -1,6 +1,8 @@
 //! # Custom warnings and errors
+use alloc::string::{String, ToString};
+use core::fmt;
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -30,6 +32,14 @@ pub enum FailedError {
     DecompositionFailed,
     /// Can't solve for x
     SolutionFailed,
+    /// Shapes of matrices or vectors don't match
+    ShapeMismatch,
+    /// Parameters are out of their valid range
+    InvalidParameters,
+    /// Can't export a model to another format
+    ExportFailed,
+    /// Can't import a model from another format
+    ImportFailed,
 }
 
 impl Failed {
@@ -62,6 +72,38 @@ impl Failed {
         }
     }
 
+    /// new instance of `FailedError::ShapeMismatch`
+    pub fn shape_mismatch(msg: &str) -> Self {
+        Failed {
+            err: FailedError::ShapeMismatch,
+            msg: msg.to_string(),
+        }
+    }
+
+    /// new instance of `FailedError::InvalidParameters`
+    pub fn invalid_parameters(msg: &str) -> Self {
+        Failed {
+            err: FailedError::InvalidParameters,
+            msg: msg.to_string(),
+        }
+    }
+
+    /// new instance of `FailedError::ExportFailed`
+    pub fn export(msg: &str) -> Self {
+        Failed {
+            err: FailedError::ExportFailed,
+            msg: msg.to_string(),
+        }
+    }
+
+    /// new instance of `FailedError::ImportFailed`
+    pub fn import(msg: &str) -> Self {
+        Failed {
+            err: FailedError::ImportFailed,
+            msg: msg.to_string(),
+        }
+    }
+
     /// new instance of `err`
     pub fn because(err: FailedError, msg: &str) -> Self {
         Failed {
@@ -94,6 +136,10 @@ impl fmt::Display for FailedError {
             FailedError::FindFailed => "Find failed",
             FailedError::DecompositionFailed => "Decomposition failed",
             FailedError::SolutionFailed => "Can't find solution",
+            FailedError::ShapeMismatch => "Shape mismatch",
+            FailedError::InvalidParameters => "Invalid parameters",
+            FailedError::ExportFailed => "Export failed",
+            FailedError::ImportFailed => "Import failed",
         };
         write!(f, "{}", failed_err_str)
     }
@@ -105,4 +151,5 @@ impl fmt::Display for Failed {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for Failed {}
@@ -0,0 +1,8 @@
+//! # Feature Extraction
+//!
+//! Most estimators in SmartCore expect numerical matrices. This module turns raw data, such as text documents, into numerical features.
+//!
+//! * [Text](text/index.html), token counts and TF-IDF weights of text documents.
+
+/// Token counts and TF-IDF weights of text documents
+pub mod text;
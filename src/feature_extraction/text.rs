@@ -0,0 +1,636 @@
+//! # Text Feature Extraction
+//!
+//! A collection of text documents is turned into a matrix with one row per document and one column per term of a vocabulary.
+//!
+//! The [`Tokenizer`](struct.Tokenizer.html) splits a document into words, sequences of letters, digits and underscores, and combines
+//! consecutive words into n-grams. The [`CountVectorizer`](struct.CountVectorizer.html) learns the vocabulary of all n-grams of the training documents,
+//! optionally dropping terms that appear in too few or too many documents, and counts how many times every term appears in a document.
+//! Most entries of the resulting matrix are zeros, so it is returned as a [`CSRMatrix`](../../linalg/sparse/struct.CSRMatrix.html).
+//!
+//! Raw counts overweight terms that are frequent in all documents. The [`TfidfTransformer`](struct.TfidfTransformer.html) multiplies the count
+//! of term \\(t\\) by its inverse document frequency
+//!
+//! \\[idf(t) = \ln \frac{1 + n}{1 + df(t)} + 1\\]
+//!
+//! where \\(n\\) is the number of documents and \\(df(t)\\) is the number of documents that contain \\(t\\), and scales every row to unit norm.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::api::{Transformer, UnsupervisedEstimator};
+//! use smartcore::feature_extraction::text::*;
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::naive_bayes::multinomial::MultinomialNB;
+//!
+//! let documents = vec![
+//!     "Chinese Beijing Chinese",
+//!     "Chinese Chinese Shanghai",
+//!     "Chinese Macao",
+//!     "Tokyo Japan Chinese",
+//! ];
+//! let y = vec![0., 0., 0., 1.];
+//!
+//! let vectorizer = CountVectorizer::fit(&documents, Default::default()).unwrap();
+//! assert_eq!(
+//!     vectorizer.feature_names(),
+//!     &vec!["beijing", "chinese", "japan", "macao", "shanghai", "tokyo"]
+//! );
+//! let counts = vectorizer.transform::<f64, _>(&documents).unwrap();
+//! let tfidf = TfidfTransformer::fit(&counts, Default::default()).unwrap();
+//! let x: DenseMatrix<f64> = tfidf.transform(&counts).unwrap().to_dense();
+//!
+//! let nb = MultinomialNB::fit(&x, &y, Default::default()).unwrap();
+//!
+//! let test = vectorizer
+//!     .transform::<f64, _>(&["Chinese Chinese Chinese Tokyo Japan"])
+//!     .unwrap();
+//! let y_hat = nb.predict(&tfidf.transform(&test).unwrap().to_dense()).unwrap();
+//! ```
+//!
+//! ## References:
+//! * ["Introduction to Information Retrieval", Manning C.D., Raghavan P., Schütze H., Chapter 6 Scoring, term weighting and the vector space model](https://nlp.stanford.edu/IR-book/)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Transformer, UnsupervisedEstimator};
+use crate::collections::{HashMap, HashSet};
+use crate::error::{Failed, FailedError};
+use crate::linalg::sparse::CSRMatrix;
+use crate::math::num::RealNumber;
+use crate::preprocessing::numerical::NormalizerNorm;
+
+/// Splits documents into words and combines consecutive words into n-grams.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tokenizer {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Convert all characters to lowercase before tokenizing. Default value is `true`.
+    pub lowercase: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Words shorter than this number of characters are dropped. Default value is 2.
+    pub min_token_length: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Smallest and largest number of consecutive words in a term. Default value is `(1, 1)`, single words.
+    pub ngram_range: (usize, usize),
+}
+
+impl Default for Tokenizer {
+    fn default() -> Self {
+        Tokenizer {
+            lowercase: true,
+            min_token_length: 2,
+            ngram_range: (1, 1),
+        }
+    }
+}
+
+impl Tokenizer {
+    /// Convert all characters to lowercase before tokenizing.
+    pub fn with_lowercase(mut self, lowercase: bool) -> Self {
+        self.lowercase = lowercase;
+        self
+    }
+    /// Words shorter than this number of characters are dropped.
+    pub fn with_min_token_length(mut self, min_token_length: usize) -> Self {
+        self.min_token_length = min_token_length;
+        self
+    }
+    /// Smallest and largest number of consecutive words in a term.
+    pub fn with_ngram_range(mut self, min_n: usize, max_n: usize) -> Self {
+        self.ngram_range = (min_n, max_n);
+        self
+    }
+
+    /// Terms of `document`, n-grams are separated by a single space.
+    pub fn tokenize(&self, document: &str) -> Vec<String> {
+        let document = if self.lowercase {
+            document.to_lowercase()
+        } else {
+            document.to_string()
+        };
+        let words: Vec<&str> = document
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .filter(|word| !word.is_empty() && word.chars().count() >= self.min_token_length)
+            .collect();
+
+        let (min_n, max_n) = self.ngram_range;
+        let mut terms = Vec::new();
+        for n in min_n..=max_n {
+            terms.extend(words.windows(n).map(|ngram| ngram.join(" ")));
+        }
+        terms
+    }
+
+    fn validate(&self) -> Result<(), Failed> {
+        let (min_n, max_n) = self.ngram_range;
+        if min_n == 0 || min_n > max_n {
+            return Err(Failed::fit(&format!(
+                "Invalid n-gram range ({}, {}), expected 0 < min_n <= max_n",
+                min_n, max_n
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// `CountVectorizer` parameters. Use `Default::default()` for default values.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct CountVectorizerParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Splits documents into terms.
+    pub tokenizer: Tokenizer,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Terms that appear in fewer documents are dropped from the vocabulary. Default value is 1.
+    pub min_df: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Terms that appear in a larger proportion of documents are dropped from the vocabulary, in (0, 1]. Default value is 1.
+    pub max_df: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Keep only this number of terms with the largest counts across all documents. All terms are kept by default.
+    pub max_features: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Indicate presence of a term by one instead of counting it. Default value is `false`.
+    pub binary: bool,
+}
+
+impl Default for CountVectorizerParameters {
+    fn default() -> Self {
+        CountVectorizerParameters {
+            tokenizer: Tokenizer::default(),
+            min_df: 1,
+            max_df: 1.0,
+            max_features: None,
+            binary: false,
+        }
+    }
+}
+
+impl CountVectorizerParameters {
+    /// Splits documents into terms.
+    pub fn with_tokenizer(mut self, tokenizer: Tokenizer) -> Self {
+        self.tokenizer = tokenizer;
+        self
+    }
+    /// Terms that appear in fewer documents are dropped from the vocabulary.
+    pub fn with_min_df(mut self, min_df: usize) -> Self {
+        self.min_df = min_df;
+        self
+    }
+    /// Terms that appear in a larger proportion of documents are dropped from the vocabulary, in (0, 1].
+    pub fn with_max_df(mut self, max_df: f64) -> Self {
+        self.max_df = max_df;
+        self
+    }
+    /// Keep only this number of terms with the largest counts across all documents.
+    pub fn with_max_features(mut self, max_features: usize) -> Self {
+        self.max_features = Some(max_features);
+        self
+    }
+    /// Indicate presence of a term by one instead of counting it.
+    pub fn with_binary(mut self, binary: bool) -> Self {
+        self.binary = binary;
+        self
+    }
+}
+
+/// Converts text documents to a sparse matrix of term counts.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CountVectorizer {
+    tokenizer: Tokenizer,
+    vocabulary: BTreeMap<String, usize>,
+    feature_names: Vec<String>,
+    binary: bool,
+}
+
+impl CountVectorizer {
+    /// Learns the vocabulary of the documents.
+    /// * `documents` - training documents.
+    /// * `parameters` - vectorizer parameters.
+    pub fn fit<S: AsRef<str>>(
+        documents: &[S],
+        parameters: CountVectorizerParameters,
+    ) -> Result<CountVectorizer, Failed> {
+        parameters.tokenizer.validate()?;
+        if !(parameters.max_df > 0.0 && parameters.max_df <= 1.0) {
+            return Err(Failed::fit(&format!(
+                "max_df should be in (0, 1], got {}",
+                parameters.max_df
+            )));
+        }
+
+        let mut document_frequency: HashMap<String, usize> = HashMap::new();
+        let mut term_frequency: HashMap<String, usize> = HashMap::new();
+        for document in documents {
+            let terms = parameters.tokenizer.tokenize(document.as_ref());
+            let mut unique = HashSet::new();
+            for term in terms {
+                *term_frequency.entry(term.clone()).or_insert(0) += 1;
+                if !unique.contains(&term) {
+                    *document_frequency.entry(term.clone()).or_insert(0) += 1;
+                    unique.insert(term);
+                }
+            }
+        }
+
+        let max_df = parameters.max_df * documents.len() as f64;
+        let mut terms: Vec<String> = document_frequency
+            .into_iter()
+            .filter(|&(_, df)| df >= parameters.min_df && df as f64 <= max_df)
+            .map(|(term, _)| term)
+            .collect();
+
+        if let Some(max_features) = parameters.max_features {
+            // most frequent terms first, ties are broken alphabetically
+            terms.sort_by(|a, b| term_frequency[b].cmp(&term_frequency[a]).then(a.cmp(b)));
+            terms.truncate(max_features);
+        }
+        terms.sort();
+
+        if terms.is_empty() {
+            return Err(Failed::fit(
+                "Vocabulary is empty, the documents contain no terms that satisfy min_df and max_df",
+            ));
+        }
+
+        Ok(CountVectorizer {
+            tokenizer: parameters.tokenizer,
+            vocabulary: terms
+                .iter()
+                .enumerate()
+                .map(|(i, term)| (term.clone(), i))
+                .collect(),
+            feature_names: terms,
+            binary: parameters.binary,
+        })
+    }
+
+    /// Counts terms of the vocabulary in every document, terms that are not in the vocabulary are ignored.
+    pub fn transform<T: RealNumber, S: AsRef<str>>(
+        &self,
+        documents: &[S],
+    ) -> Result<CSRMatrix<T>, Failed> {
+        let rows = documents
+            .iter()
+            .map(|document| {
+                let mut counts: HashMap<usize, usize> = HashMap::new();
+                for term in self.tokenizer.tokenize(document.as_ref()) {
+                    if let Some(&index) = self.vocabulary.get(&term) {
+                        *counts.entry(index).or_insert(0) += 1;
+                    }
+                }
+                counts
+                    .into_iter()
+                    .map(|(index, count)| {
+                        let count = if self.binary { 1 } else { count };
+                        (index, T::from_usize(count).unwrap())
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(CSRMatrix::from_rows(self.feature_names.len(), rows))
+    }
+
+    /// Learns the vocabulary of the documents and counts its terms in every document.
+    pub fn fit_transform<T: RealNumber, S: AsRef<str>>(
+        documents: &[S],
+        parameters: CountVectorizerParameters,
+    ) -> Result<(CountVectorizer, CSRMatrix<T>), Failed> {
+        let vectorizer = CountVectorizer::fit(documents, parameters)?;
+        let counts = vectorizer.transform(documents)?;
+        Ok((vectorizer, counts))
+    }
+
+    /// Maps every term of the vocabulary to its column, terms are in alphabetical order.
+    pub fn vocabulary(&self) -> &BTreeMap<String, usize> {
+        &self.vocabulary
+    }
+
+    /// Terms of the vocabulary ordered by column.
+    pub fn feature_names(&self) -> &Vec<String> {
+        &self.feature_names
+    }
+}
+
+/// `TfidfTransformer` parameters. Use `Default::default()` for default values.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TfidfTransformerParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Norm every row is divided by, `None` leaves the rows unnormalized. Default value is `Some(NormalizerNorm::L2)`.
+    pub norm: Option<NormalizerNorm>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Multiply term counts by their inverse document frequency. Default value is `true`.
+    pub use_idf: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Add one to all document frequencies, as if a document containing every term was seen once, which prevents division by zero. Default value is `true`.
+    pub smooth_idf: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Replace term counts \\(tf\\) with \\(1 + \ln tf\\). Default value is `false`.
+    pub sublinear_tf: bool,
+}
+
+impl Default for TfidfTransformerParameters {
+    fn default() -> Self {
+        TfidfTransformerParameters {
+            norm: Some(NormalizerNorm::L2),
+            use_idf: true,
+            smooth_idf: true,
+            sublinear_tf: false,
+        }
+    }
+}
+
+impl TfidfTransformerParameters {
+    /// Norm every row is divided by, `None` leaves the rows unnormalized.
+    pub fn with_norm(mut self, norm: Option<NormalizerNorm>) -> Self {
+        self.norm = norm;
+        self
+    }
+    /// Multiply term counts by their inverse document frequency.
+    pub fn with_use_idf(mut self, use_idf: bool) -> Self {
+        self.use_idf = use_idf;
+        self
+    }
+    /// Add one to all document frequencies.
+    pub fn with_smooth_idf(mut self, smooth_idf: bool) -> Self {
+        self.smooth_idf = smooth_idf;
+        self
+    }
+    /// Replace term counts \\(tf\\) with \\(1 + \ln tf\\).
+    pub fn with_sublinear_tf(mut self, sublinear_tf: bool) -> Self {
+        self.sublinear_tf = sublinear_tf;
+        self
+    }
+}
+
+/// Reweights a matrix of term counts by inverse document frequencies.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TfidfTransformer<T: RealNumber> {
+    idf: Vec<T>,
+    norm: Option<NormalizerNorm>,
+    sublinear_tf: bool,
+}
+
+impl<T: RealNumber> TfidfTransformer<T> {
+    /// Inverse document frequency of every term, ones when `use_idf` is `false`.
+    pub fn idf(&self) -> &Vec<T> {
+        &self.idf
+    }
+}
+
+impl<T: RealNumber> UnsupervisedEstimator<CSRMatrix<T>, TfidfTransformerParameters>
+    for TfidfTransformer<T>
+{
+    fn fit(x: &CSRMatrix<T>, parameters: TfidfTransformerParameters) -> Result<Self, Failed> {
+        let (n_documents, n_terms) = x.shape();
+
+        let idf = if parameters.use_idf {
+            let mut document_frequency = vec![0; n_terms];
+            for &term in x.indices() {
+                document_frequency[term] += 1;
+            }
+            let smooth = usize::from(parameters.smooth_idf);
+            document_frequency
+                .iter()
+                .map(|&df| {
+                    let n = T::from_usize(n_documents + smooth).unwrap();
+                    let df = T::from_usize(df + smooth).unwrap();
+                    (n / df).ln() + T::one()
+                })
+                .collect()
+        } else {
+            vec![T::one(); n_terms]
+        };
+
+        if idf.iter().any(|v: &T| !v.is_finite()) {
+            return Err(Failed::fit(
+                "Some terms appear in no document, enable smooth_idf to avoid division by zero",
+            ));
+        }
+
+        Ok(TfidfTransformer {
+            idf,
+            norm: parameters.norm,
+            sublinear_tf: parameters.sublinear_tf,
+        })
+    }
+}
+
+impl<T: RealNumber> Transformer<CSRMatrix<T>> for TfidfTransformer<T> {
+    fn transform(&self, x: &CSRMatrix<T>) -> Result<CSRMatrix<T>, Failed> {
+        let (n_documents, n_terms) = x.shape();
+        if n_terms != self.idf.len() {
+            return Err(Failed::because(
+                FailedError::TransformFailed,
+                &format!(
+                    "Expected {} columns, but got {} columns instead.",
+                    self.idf.len(),
+                    n_terms
+                ),
+            ));
+        }
+
+        let weighted = x.map_nonzero(|_, term, tf| {
+            let tf = if self.sublinear_tf {
+                T::one() + tf.ln()
+            } else {
+                tf
+            };
+            tf * self.idf[term]
+        });
+
+        let norms: Vec<T> = (0..n_documents)
+            .map(|document| {
+                let (_, values) = weighted.row(document);
+                match self.norm {
+                    None => T::one(),
+                    Some(NormalizerNorm::L1) => values.iter().map(|v| v.abs()).sum(),
+                    Some(NormalizerNorm::L2) => values.iter().map(|v| *v * *v).sum::<T>().sqrt(),
+                    Some(NormalizerNorm::Max) => {
+                        values.iter().fold(T::zero(), |norm, v| norm.max(v.abs()))
+                    }
+                }
+            })
+            .collect();
+
+        Ok(weighted.map_nonzero(|document, _, v| {
+            if norms[document] > T::zero() {
+                v / norms[document]
+            } else {
+                v
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::linalg::BaseMatrix;
+
+    const DOCUMENTS: [&str; 3] = ["The cat sat.", "The dog sat!", "the CAT, the cat"];
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn tokenizer() {
+        let tokenizer = Tokenizer::default();
+        assert_eq!(
+            tokenizer.tokenize("A quick_brown fox, 42 times!"),
+            vec!["quick_brown", "fox", "42", "times"]
+        );
+
+        let tokenizer = Tokenizer::default()
+            .with_lowercase(false)
+            .with_min_token_length(1)
+            .with_ngram_range(1, 2);
+        assert_eq!(
+            tokenizer.tokenize("New York is big"),
+            vec!["New", "York", "is", "big", "New York", "York is", "is big"]
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn count_vectorizer() {
+        let (vectorizer, counts) =
+            CountVectorizer::fit_transform::<f64, _>(&DOCUMENTS, Default::default()).unwrap();
+        assert_eq!(
+            vectorizer.feature_names(),
+            &vec!["cat", "dog", "sat", "the"]
+        );
+        assert_eq!(vectorizer.vocabulary()["sat"], 2);
+        assert_eq!(counts.nnz(), 8);
+        assert_eq!(
+            counts.to_dense::<DenseMatrix<f64>>(),
+            DenseMatrix::from_2d_array(&[&[1., 0., 1., 1.], &[0., 1., 1., 1.], &[2., 0., 0., 2.],])
+        );
+
+        // unknown terms are ignored
+        let counts = vectorizer
+            .transform::<f64, _>(&["a bird sat on the dog"])
+            .unwrap();
+        assert_eq!(
+            counts.to_dense::<DenseMatrix<f64>>(),
+            DenseMatrix::from_2d_array(&[&[0., 1., 1., 1.]])
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn vocabulary_pruning() {
+        let fit = |parameters| {
+            CountVectorizer::fit(&DOCUMENTS, parameters)
+                .unwrap()
+                .feature_names()
+                .clone()
+        };
+        let parameters = CountVectorizerParameters::default();
+        assert_eq!(
+            fit(parameters.clone().with_min_df(2)),
+            vec!["cat", "sat", "the"]
+        );
+        assert_eq!(
+            fit(parameters.clone().with_max_df(0.9)),
+            vec!["cat", "dog", "sat"]
+        );
+        assert_eq!(
+            fit(parameters.clone().with_max_features(2)),
+            vec!["cat", "the"]
+        );
+
+        let vectorizer = CountVectorizer::fit(&DOCUMENTS, parameters.with_binary(true)).unwrap();
+        let counts: CSRMatrix<f64> = vectorizer.transform(&DOCUMENTS).unwrap();
+        assert_eq!(counts.get(2, 0), 1.0);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn invalid_parameters() {
+        let parameters = CountVectorizerParameters::default();
+        assert!(CountVectorizer::fit(&DOCUMENTS, parameters.clone().with_max_df(0.0)).is_err());
+        assert!(CountVectorizer::fit(&DOCUMENTS, parameters.clone().with_min_df(4)).is_err());
+        assert!(CountVectorizer::fit(
+            &DOCUMENTS,
+            parameters.with_tokenizer(Tokenizer::default().with_ngram_range(2, 1))
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn tfidf_transformer() {
+        let (_, counts) =
+            CountVectorizer::fit_transform::<f64, _>(&DOCUMENTS, Default::default()).unwrap();
+        let tfidf = TfidfTransformer::fit(&counts, Default::default()).unwrap();
+        let expected_idf = [
+            1.2876820724517808,
+            1.6931471805599454,
+            1.2876820724517808,
+            1.0,
+        ];
+        for (idf, expected) in tfidf.idf().iter().zip(expected_idf.iter()) {
+            assert!((idf - expected).abs() < 1e-8);
+        }
+
+        let weights: DenseMatrix<f64> = tfidf.transform(&counts).unwrap().to_dense();
+        let expected = DenseMatrix::from_2d_array(&[
+            &[
+                0.6198053799406072,
+                0.0,
+                0.6198053799406072,
+                0.48133416873660545,
+            ],
+            &[
+                0.0,
+                0.7203334490549893,
+                0.5478321549274363,
+                0.4254405389711991,
+            ],
+            &[0.7898069290660905, 0.0, 0.0, 0.6133555370249717],
+        ]);
+        assert!(weights.approximate_eq(&expected, 1e-8));
+
+        let tfidf = TfidfTransformer::fit(
+            &counts,
+            TfidfTransformerParameters::default()
+                .with_use_idf(false)
+                .with_sublinear_tf(true)
+                .with_norm(None),
+        )
+        .unwrap();
+        let weights: DenseMatrix<f64> = tfidf.transform(&counts).unwrap().to_dense();
+        assert!((weights.get(2, 0) - (1.0 + 2f64.ln())).abs() < 1e-8);
+        assert_eq!(weights.get(0, 0), 1.0);
+
+        assert!(tfidf
+            .transform(&CSRMatrix::from_rows(2, vec![vec![(0, 1.0)]]))
+            .is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (vectorizer, counts) =
+            CountVectorizer::fit_transform::<f64, _>(&DOCUMENTS, Default::default()).unwrap();
+        let deserialized: CountVectorizer =
+            serde_json::from_str(&serde_json::to_string(&vectorizer).unwrap()).unwrap();
+        assert_eq!(vectorizer, deserialized);
+
+        let tfidf = TfidfTransformer::fit(&counts, Default::default()).unwrap();
+        let deserialized: TfidfTransformer<f64> =
+            serde_json::from_str(&serde_json::to_string(&tfidf).unwrap()).unwrap();
+        assert_eq!(tfidf.idf().len(), deserialized.idf().len());
+    }
+}
@@ -0,0 +1,13 @@
+//! # Feature Selection
+//!
+//! Feature selection removes features that do not help a model to predict the target values.
+//! A smaller set of features makes models faster to fit, easier to interpret and less prone to overfitting.
+//!
+//! * [Recursive feature elimination](rfe/index.html) repeatedly fits an estimator that implements
+//!   [`FeatureImportance`](../api/trait.FeatureImportance.html) and drops the weakest features,
+//!   optionally choosing the number of features by cross-validation.
+//! * [Mutual information](mutual_info/index.html) scores every feature by the information it carries about the target,
+//!   independently of any model.
+
+pub mod mutual_info;
+pub mod rfe;
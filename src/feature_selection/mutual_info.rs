@@ -0,0 +1,426 @@
+//! # Mutual Information
+//!
+//! The mutual information between a feature \\(X\\) and the target \\(Y\\)
+//!
+//! \\[I(X; Y) = \int\int p(x, y) \ln \frac{p(x, y)}{p(x)p(y)} dx dy\\]
+//!
+//! is zero when the feature and the target are independent and grows with the amount of information the feature carries about the target.
+//! Unlike correlation, mutual information also captures non-linear and non-monotonic dependencies, which makes it a model-agnostic score for feature selection.
+//!
+//! `mutual_info_classif` scores features against discrete class labels, `mutual_info_regression` scores them against a continuous target.
+//! The estimator depends on the kind of the feature and of the target:
+//!
+//! * both continuous: the estimator of Kraskov et al., based on the distances to the `n_neighbors`-th nearest neighbour in the joint space,
+//! * one continuous, one discrete: the estimator of Ross, based on the distances to the `n_neighbors`-th nearest neighbour with the same discrete value,
+//! * both discrete: the exact mutual information of the contingency table.
+//!
+//! Features are treated as continuous unless their indices are listed in `discrete_features`. Continuous features and targets are scaled to unit variance
+//! and a tiny amount of noise is added to them to break ties between repeated values, so the estimates of continuous features depend slightly on `seed`.
+//! The nearest neighbours are found with a [cover tree](../../algorithm/neighbour/cover_tree/index.html).
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::feature_selection::mutual_info::*;
+//! use smartcore::linalg::naive::dense_matrix::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[0.1, 4.2, 0.],
+//!     &[0.3, 1.5, 0.],
+//!     &[0.2, 3.3, 1.],
+//!     &[0.4, 2.4, 1.],
+//!     &[2.1, 4.1, 0.],
+//!     &[2.3, 1.7, 0.],
+//!     &[2.2, 3.2, 1.],
+//!     &[2.4, 2.6, 1.],
+//! ]);
+//! let y: Vec<f64> = vec![0., 0., 0., 0., 1., 1., 1., 1.];
+//!
+//! let mi = mutual_info_classif(
+//!     &x,
+//!     &y,
+//!     MutualInfoParameters::default()
+//!         .with_n_neighbors(2)
+//!         .with_discrete_features(vec![2])
+//!         .with_seed(42),
+//! )
+//! .unwrap();
+//! // only the first feature separates the classes
+//! assert!(mi[0] > mi[1] && mi[0] > mi[2]);
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Estimating mutual information", Kraskov A., Stögbauer H., Grassberger P., 2004](https://doi.org/10.1103/PhysRevE.69.066138)
+//! * ["Mutual Information between Discrete and Continuous Data Sets", Ross B. C., 2014](https://doi.org/10.1371/journal.pone.0087357)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::algorithm::neighbour::{KNNAlgorithm, KNNAlgorithmName};
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::distance::Distance;
+use crate::math::num::RealNumber;
+use crate::math::special::digamma;
+use crate::math::vector::RealNumberVector;
+use crate::metrics::cluster_helpers::{contingency_matrix, mutual_info_score};
+use crate::rand::get_rng_impl;
+
+/// Relative amount of noise added to continuous values to break ties.
+const NOISE_SCALE: f64 = 1e-10;
+
+/// Parameters of the mutual information estimators.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct MutualInfoParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of neighbours used by the estimators of continuous variables. Larger values reduce the variance of the estimates but increase their bias.
+    pub n_neighbors: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Indices of the features with discrete values, all other features are continuous.
+    pub discrete_features: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed of the noise added to continuous values.
+    pub seed: Option<u64>,
+}
+
+impl MutualInfoParameters {
+    /// Number of neighbours used by the estimators of continuous variables.
+    pub fn with_n_neighbors(mut self, n_neighbors: usize) -> Self {
+        self.n_neighbors = n_neighbors;
+        self
+    }
+    /// Indices of the features with discrete values.
+    pub fn with_discrete_features(mut self, discrete_features: Vec<usize>) -> Self {
+        self.discrete_features = discrete_features;
+        self
+    }
+    /// Seed of the noise added to continuous values.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl Default for MutualInfoParameters {
+    fn default() -> Self {
+        MutualInfoParameters {
+            n_neighbors: 3,
+            discrete_features: Vec::new(),
+            seed: None,
+        }
+    }
+}
+
+/// Chebyshev (maximum coordinate) distance, the metric of the joint space in the estimator of Kraskov et al.
+#[derive(Debug, Clone)]
+struct MaxNorm {}
+
+impl<T: RealNumber> Distance<Vec<T>, T> for MaxNorm {
+    fn distance(&self, a: &Vec<T>, b: &Vec<T>) -> T {
+        a.iter()
+            .zip(b.iter())
+            .fold(T::zero(), |d, (a, b)| d.max((*a - *b).abs()))
+    }
+}
+
+/// Estimates the mutual information between every feature and discrete class labels.
+/// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+/// * `y` - class labels of size _N_
+/// * `parameters` - parameters of the estimators
+pub fn mutual_info_classif<T: RealNumber, M: Matrix<T>>(
+    x: &M,
+    y: &M::RowVector,
+    parameters: MutualInfoParameters,
+) -> Result<Vec<T>, Failed> {
+    mutual_info(x, y, true, parameters)
+}
+
+/// Estimates the mutual information between every feature and a continuous target.
+/// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+/// * `y` - target values of size _N_
+/// * `parameters` - parameters of the estimators
+pub fn mutual_info_regression<T: RealNumber, M: Matrix<T>>(
+    x: &M,
+    y: &M::RowVector,
+    parameters: MutualInfoParameters,
+) -> Result<Vec<T>, Failed> {
+    mutual_info(x, y, false, parameters)
+}
+
+fn mutual_info<T: RealNumber, M: Matrix<T>>(
+    x: &M,
+    y: &M::RowVector,
+    discrete_target: bool,
+    parameters: MutualInfoParameters,
+) -> Result<Vec<T>, Failed> {
+    let (n, m) = x.shape();
+    if y.len() != n {
+        return Err(Failed::fit(&format!(
+            "Size of x should equal size of y; |x|=[{}], |y|=[{}]",
+            n,
+            y.len()
+        )));
+    }
+    if parameters.n_neighbors == 0 || parameters.n_neighbors >= n {
+        return Err(Failed::fit(&format!(
+            "n_neighbors should be in [1, {}), got {}",
+            n, parameters.n_neighbors
+        )));
+    }
+    if let Some(j) = parameters.discrete_features.iter().find(|j| **j >= m) {
+        return Err(Failed::fit(&format!(
+            "Discrete feature {} is out of range, x has {} features",
+            j, m
+        )));
+    }
+
+    let mut rng = get_rng_impl(parameters.seed);
+    let mut add_noise = |values: &mut Vec<T>| {
+        let n = T::from_usize(values.len()).unwrap();
+        let mean = values.iter().fold(T::zero(), |s, v| s + *v) / n;
+        let std = (values
+            .iter()
+            .fold(T::zero(), |s, v| s + (*v - mean) * (*v - mean))
+            / n)
+            .sqrt();
+        if std > T::zero() {
+            values.iter_mut().for_each(|v| *v /= std);
+        }
+        let scale = T::from_f64(NOISE_SCALE).unwrap()
+            * (values.iter().fold(T::zero(), |s, v| s + v.abs()) / n).max(T::one());
+        for v in values.iter_mut() {
+            *v += scale * T::from_f64(rng.gen::<f64>() - 0.5).unwrap();
+        }
+    };
+
+    let mut target: Vec<T> = (0..n).map(|i| y.get(i)).collect();
+    if !discrete_target {
+        add_noise(&mut target);
+    }
+
+    let mut scores = Vec::with_capacity(m);
+    for j in 0..m {
+        let mut feature: Vec<T> = (0..n).map(|i| x.get(i, j)).collect();
+        let discrete_feature = parameters.discrete_features.contains(&j);
+        if !discrete_feature {
+            add_noise(&mut feature);
+        }
+
+        let mi = match (discrete_feature, discrete_target) {
+            (false, false) => mi_continuous(&feature, &target, parameters.n_neighbors)?,
+            (false, true) => mi_mixed(&feature, &target, parameters.n_neighbors)?,
+            (true, false) => mi_mixed(&target, &feature, parameters.n_neighbors)?,
+            (true, true) => mutual_info_score(&contingency_matrix(&feature, &target)),
+        };
+        scores.push(mi.max(T::zero()));
+    }
+
+    Ok(scores)
+}
+
+/// Number of points strictly closer than `radius` to `point`, including the point itself.
+fn count_within<T: RealNumber>(
+    search: &KNNAlgorithm<T, MaxNorm>,
+    point: &Vec<T>,
+    radius: T,
+) -> Result<usize, Failed> {
+    if radius <= T::zero() {
+        return Ok(1);
+    }
+    Ok(search
+        .find_radius(point, radius)?
+        .iter()
+        .filter(|(_, d, _)| *d < radius)
+        .count()
+        .max(1))
+}
+
+/// Distance from `point` to its `k`-th nearest neighbour, not counting the point itself.
+fn kth_distance<T: RealNumber>(
+    search: &KNNAlgorithm<T, MaxNorm>,
+    point: &Vec<T>,
+    k: usize,
+) -> Result<T, Failed> {
+    Ok(search
+        .find(point, k + 1)?
+        .iter()
+        .fold(T::zero(), |r, (_, d, _)| r.max(*d)))
+}
+
+/// Mutual information between two continuous variables, estimator of Kraskov et al.
+fn mi_continuous<T: RealNumber>(x: &[T], y: &[T], k: usize) -> Result<T, Failed> {
+    let n = x.len();
+    let joint: Vec<Vec<T>> = (0..n).map(|i| vec![x[i], y[i]]).collect();
+    let x: Vec<Vec<T>> = x.iter().map(|v| vec![*v]).collect();
+    let y: Vec<Vec<T>> = y.iter().map(|v| vec![*v]).collect();
+
+    let algorithm = KNNAlgorithmName::CoverTree;
+    let joint_search = algorithm.fit(joint.clone(), MaxNorm {})?;
+    let x_search = algorithm.fit(x.clone(), MaxNorm {})?;
+    let y_search = algorithm.fit(y.clone(), MaxNorm {})?;
+
+    let mut digamma_nx = T::zero();
+    let mut digamma_ny = T::zero();
+    for i in 0..n {
+        let radius = kth_distance(&joint_search, &joint[i], k)?;
+        digamma_nx += digamma(T::from_usize(count_within(&x_search, &x[i], radius)?).unwrap());
+        digamma_ny += digamma(T::from_usize(count_within(&y_search, &y[i], radius)?).unwrap());
+    }
+
+    let n = T::from_usize(n).unwrap();
+    Ok(digamma(n) + digamma(T::from_usize(k).unwrap()) - (digamma_nx + digamma_ny) / n)
+}
+
+/// Mutual information between a continuous and a discrete variable, estimator of Ross.
+fn mi_mixed<T: RealNumber>(c: &[T], d: &[T], k: usize) -> Result<T, Failed> {
+    let (unique, labels) = d.to_vec().unique_with_indices();
+    let mut label_counts = vec![0; unique.len()];
+    for l in labels.iter() {
+        label_counts[*l] += 1;
+    }
+
+    let algorithm = KNNAlgorithmName::CoverTree;
+    let points: Vec<Vec<T>> = c.iter().map(|v| vec![*v]).collect();
+    let mut radius = vec![T::zero(); c.len()];
+    let mut label_k = vec![0; c.len()];
+    for (label, count) in label_counts.iter().enumerate() {
+        // observations with a unique discrete value carry no information about the neighbourhood
+        if *count < 2 {
+            continue;
+        }
+        let idx: Vec<usize> = (0..c.len()).filter(|i| labels[*i] == label).collect();
+        let k = k.min(count - 1);
+        let search = algorithm.fit(idx.iter().map(|i| points[*i].clone()).collect(), MaxNorm {})?;
+        for i in idx {
+            radius[i] = kth_distance(&search, &points[i], k)?;
+            label_k[i] = k;
+        }
+    }
+
+    let idx: Vec<usize> = (0..c.len())
+        .filter(|i| label_counts[labels[*i]] > 1)
+        .collect();
+    if idx.is_empty() {
+        return Ok(T::zero());
+    }
+    let search = algorithm.fit(idx.iter().map(|i| points[*i].clone()).collect(), MaxNorm {})?;
+
+    let mut digamma_k = T::zero();
+    let mut digamma_label_counts = T::zero();
+    let mut digamma_m = T::zero();
+    for i in idx.iter() {
+        digamma_k += digamma(T::from_usize(label_k[*i]).unwrap());
+        digamma_label_counts += digamma(T::from_usize(label_counts[labels[*i]]).unwrap());
+        digamma_m +=
+            digamma(T::from_usize(count_within(&search, &points[*i], radius[*i])?).unwrap());
+    }
+
+    let n = T::from_usize(idx.len()).unwrap();
+    Ok(digamma(n) + (digamma_k - digamma_label_counts - digamma_m) / n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+
+    fn uniform(n: usize, m: usize, seed: u64) -> DenseMatrix<f64> {
+        let mut rng = get_rng_impl(Some(seed));
+        let values: Vec<f64> = (0..n * m).map(|_| rng.gen()).collect();
+        DenseMatrix::from_vec(n, m, &values)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn mutual_info_regression_dependent_feature() {
+        let n = 200;
+        let x = uniform(n, 3, 11);
+        let y: Vec<f64> = (0..n)
+            .map(|i| (6. * x.get(i, 0)).sin() + 0.05 * x.get(i, 2))
+            .collect();
+
+        let mi =
+            mutual_info_regression(&x, &y, MutualInfoParameters::default().with_seed(1)).unwrap();
+
+        assert!(mi[0] > 1.0);
+        assert!(mi[1] < 0.1);
+        assert!(mi[2] < mi[0]);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn mutual_info_classif_mixed_features() {
+        let n = 200;
+        let noise = uniform(n, 2, 12);
+        let y: Vec<f64> = (0..n).map(|i| (i % 2) as f64).collect();
+        let values: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                vec![
+                    y[i] + 0.3 * noise.get(i, 0),
+                    noise.get(i, 1),
+                    y[i],
+                    ((i / 2) % 3) as f64,
+                ]
+            })
+            .collect();
+        let x = DenseMatrix::from_2d_vec(&values);
+
+        let mi = mutual_info_classif(
+            &x,
+            &y,
+            MutualInfoParameters::default()
+                .with_discrete_features(vec![2, 3])
+                .with_seed(1),
+        )
+        .unwrap();
+
+        // the classes are balanced and the third feature equals the label, so its mutual information is ln 2
+        assert!((mi[2] - 2f64.ln()).abs() < 1e-10);
+        assert!((mi[0] - 2f64.ln()).abs() < 0.1);
+        assert!(mi[1] < 0.1);
+        assert!(mi[3] < 0.05);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn mutual_info_discrete_feature_continuous_target() {
+        let n = 120;
+        let noise = uniform(n, 1, 13);
+        let labels: Vec<f64> = (0..n).map(|i| (i % 3) as f64).collect();
+        let x = DenseMatrix::from_2d_vec(&labels.iter().map(|l| vec![*l]).collect());
+        let y: Vec<f64> = (0..n).map(|i| 5. * labels[i] + noise.get(i, 0)).collect();
+
+        let mi = mutual_info_regression(
+            &x,
+            &y,
+            MutualInfoParameters::default()
+                .with_discrete_features(vec![0])
+                .with_seed(1),
+        )
+        .unwrap();
+
+        assert!((mi[0] - 3f64.ln()).abs() < 0.1);
+    }
+
+    #[test]
+    fn mutual_info_invalid_parameters() {
+        let x = DenseMatrix::from_2d_array(&[&[1., 2.], &[2., 3.], &[3., 4.]]);
+        let y = vec![0., 1., 0.];
+
+        assert!(mutual_info_classif(&x, &y, MutualInfoParameters::default()).is_err());
+        assert!(mutual_info_classif(
+            &x,
+            &y,
+            MutualInfoParameters::default()
+                .with_n_neighbors(1)
+                .with_discrete_features(vec![2])
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,655 @@
+//! # Recursive Feature Elimination
+//!
+//! Recursive feature elimination (RFE) selects features by repeatedly
+//!
+//! 1. fitting the base estimator to the features that are still selected,
+//! 2. ranking the features by the [importance](../../api/trait.FeatureImportance.html) reported by the fitted estimator,
+//! 3. eliminating the `step` weakest features,
+//!
+//! until only `n_features_to_select` features are left. Tree-based estimators report impurity-based importances,
+//! linear models report the magnitudes of their coefficients. Since the magnitude of a coefficient depends on the scale of its feature,
+//! features should be standardized before they are passed to a linear model.
+//!
+//! When the number of features to keep is not known in advance, `RFECV` runs the elimination on every training split of a cross-validator,
+//! scores the estimator fitted at every step on the corresponding test split and keeps the number of features with the best average score.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::api::Transformer;
+//! use smartcore::feature_selection::rfe::*;
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::linear::linear_regression::{LinearRegression, LinearRegressionParameters};
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[1., 0.3, 4., 0.1],
+//!     &[2., 0.1, 3., 0.4],
+//!     &[3., 0.4, 5., 0.2],
+//!     &[4., 0.2, 1., 0.3],
+//!     &[5., 0.5, 2., 0.1],
+//!     &[6., 0.1, 6., 0.5],
+//!     &[7., 0.3, 3., 0.2],
+//!     &[8., 0.2, 4., 0.4],
+//! ]);
+//! // y depends on the first and the third feature only
+//! let y: Vec<f64> = vec![9., 8., 13., 6., 9., 18., 13., 16.];
+//!
+//! let rfe: RFE<f64, LinearRegression<f64, DenseMatrix<f64>>> = RFE::fit(
+//!     &x,
+//!     &y,
+//!     RFEParameters::<LinearRegressionParameters>::default().with_n_features_to_select(2),
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(rfe.support(), &vec![true, false, true, false]);
+//! let x_selected = rfe.transform(&x).unwrap(); // 8x2 matrix
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Gene Selection for Cancer Classification using Support Vector Machines", Guyon I., Weston J., Barnhill S., Vapnik V., 2002](https://doi.org/10.1023/A:1012487302797)
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{FeatureImportance, Predictor, SupervisedEstimator, Transformer};
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::metrics::scorer::Scorer;
+use crate::model_selection::BaseKFold;
+
+/// Parameters of recursive feature elimination.
+/// `B` is the type of parameters of the base estimator.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RFEParameters<B> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of features to select. Defaults to half of the features.
+    pub n_features_to_select: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of features to eliminate at every step.
+    pub step: usize,
+    /// Parameters of the base estimator.
+    pub base_estimator: B,
+}
+
+/// Parameters of recursive feature elimination with cross-validation.
+/// `B` is the type of parameters of the base estimator.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RFECVParameters<B> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Minimum number of features to select.
+    pub min_features_to_select: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of features to eliminate at every step.
+    pub step: usize,
+    /// Parameters of the base estimator.
+    pub base_estimator: B,
+}
+
+/// Recursive feature elimination
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct RFE<T: RealNumber, E> {
+    estimator: E,
+    support: Vec<bool>,
+    ranking: Vec<usize>,
+    _phantom: PhantomData<T>,
+}
+
+/// Recursive feature elimination with the number of features chosen by cross-validation
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct RFECV<T: RealNumber, E> {
+    rfe: RFE<T, E>,
+    n_features: Vec<usize>,
+    mean_test_scores: Vec<T>,
+}
+
+impl<B> RFEParameters<B> {
+    /// Number of features to select.
+    pub fn with_n_features_to_select(mut self, n_features_to_select: usize) -> Self {
+        self.n_features_to_select = Some(n_features_to_select);
+        self
+    }
+    /// Number of features to eliminate at every step.
+    pub fn with_step(mut self, step: usize) -> Self {
+        self.step = step;
+        self
+    }
+    /// Parameters of the base estimator.
+    pub fn with_base_estimator<C>(self, base_estimator: C) -> RFEParameters<C> {
+        RFEParameters {
+            n_features_to_select: self.n_features_to_select,
+            step: self.step,
+            base_estimator,
+        }
+    }
+}
+
+impl<B: Default> Default for RFEParameters<B> {
+    fn default() -> Self {
+        RFEParameters {
+            n_features_to_select: None,
+            step: 1,
+            base_estimator: B::default(),
+        }
+    }
+}
+
+impl<B> RFECVParameters<B> {
+    /// Minimum number of features to select.
+    pub fn with_min_features_to_select(mut self, min_features_to_select: usize) -> Self {
+        self.min_features_to_select = min_features_to_select;
+        self
+    }
+    /// Number of features to eliminate at every step.
+    pub fn with_step(mut self, step: usize) -> Self {
+        self.step = step;
+        self
+    }
+    /// Parameters of the base estimator.
+    pub fn with_base_estimator<C>(self, base_estimator: C) -> RFECVParameters<C> {
+        RFECVParameters {
+            min_features_to_select: self.min_features_to_select,
+            step: self.step,
+            base_estimator,
+        }
+    }
+}
+
+impl<B: Default> Default for RFECVParameters<B> {
+    fn default() -> Self {
+        RFECVParameters {
+            min_features_to_select: 1,
+            step: 1,
+            base_estimator: B::default(),
+        }
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, B: Clone, E>
+    SupervisedEstimator<M, M::RowVector, RFEParameters<B>> for RFE<T, E>
+where
+    E: SupervisedEstimator<M, M::RowVector, B> + FeatureImportance<T>,
+{
+    fn fit(x: &M, y: &M::RowVector, parameters: RFEParameters<B>) -> Result<Self, Failed> {
+        RFE::fit(x, y, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, E> Transformer<M> for RFE<T, E> {
+    fn transform(&self, x: &M) -> Result<M, Failed> {
+        self.transform(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, E> Predictor<M, M::RowVector> for RFE<T, E>
+where
+    E: Predictor<M, M::RowVector>,
+{
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, E> Transformer<M> for RFECV<T, E> {
+    fn transform(&self, x: &M) -> Result<M, Failed> {
+        self.rfe.transform(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, E> Predictor<M, M::RowVector> for RFECV<T, E>
+where
+    E: Predictor<M, M::RowVector>,
+{
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.rfe.predict(x)
+    }
+}
+
+impl<T: RealNumber, E> RFE<T, E> {
+    /// Eliminates features until `n_features_to_select` features are left and fits the base estimator to them.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - RFE parameters, including parameters of the base estimator
+    pub fn fit<M: Matrix<T>, B: Clone>(
+        x: &M,
+        y: &M::RowVector,
+        parameters: RFEParameters<B>,
+    ) -> Result<RFE<T, E>, Failed>
+    where
+        E: SupervisedEstimator<M, M::RowVector, B> + FeatureImportance<T>,
+    {
+        let (_, m) = x.shape();
+        let n_features_to_select = parameters.n_features_to_select.unwrap_or(m / 2).max(1);
+        if n_features_to_select > m {
+            return Err(Failed::fit(&format!(
+                "n_features_to_select should not exceed the number of features: {} > {}",
+                n_features_to_select, m
+            )));
+        }
+
+        eliminate(
+            x,
+            y,
+            &parameters.base_estimator,
+            n_features_to_select,
+            parameters.step,
+            |_, _| Ok(()),
+        )
+    }
+
+    /// Selects the columns of `x` that were kept by the elimination.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn transform<M: Matrix<T>>(&self, x: &M) -> Result<M, Failed> {
+        let (_, m) = x.shape();
+        if m != self.support.len() {
+            return Err(Failed::transform(&format!(
+                "Expected {} columns, got {}",
+                self.support.len(),
+                m
+            )));
+        }
+        Ok(x.take(&self.selected(), 1))
+    }
+
+    /// Predict target values from `x` with the base estimator fitted to the selected features.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features, including the eliminated ones.
+    pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed>
+    where
+        E: Predictor<M, M::RowVector>,
+    {
+        self.estimator.predict(&self.transform(x)?)
+    }
+
+    /// The base estimator fitted to the selected features.
+    pub fn estimator(&self) -> &E {
+        &self.estimator
+    }
+
+    /// `true` for every feature that was selected.
+    pub fn support(&self) -> &Vec<bool> {
+        &self.support
+    }
+
+    /// Rank of every feature. Selected features have rank 1, features eliminated at the last step have rank 2 and so on.
+    pub fn ranking(&self) -> &Vec<usize> {
+        &self.ranking
+    }
+
+    /// Number of selected features.
+    pub fn n_features(&self) -> usize {
+        self.support.iter().filter(|s| **s).count()
+    }
+
+    fn selected(&self) -> Vec<usize> {
+        (0..self.support.len())
+            .filter(|j| self.support[*j])
+            .collect()
+    }
+}
+
+impl<T: RealNumber, E> RFECV<T, E> {
+    /// Chooses the number of features by cross-validation, then eliminates features from the whole training set
+    /// until that number of features is left.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - RFECV parameters, including parameters of the base estimator
+    /// * `cv` - the cross-validation splitting strategy, should be an instance of [`BaseKFold`](../../model_selection/trait.BaseKFold.html)
+    /// * `score` - a metric to use for evaluation, see [metrics](../../metrics/index.html), or a [scorer](../../metrics/scorer/index.html)
+    pub fn fit<M: Matrix<T>, B: Clone, K: BaseKFold, S>(
+        x: &M,
+        y: &M::RowVector,
+        parameters: RFECVParameters<B>,
+        cv: &K,
+        score: S,
+    ) -> Result<RFECV<T, E>, Failed>
+    where
+        E: SupervisedEstimator<M, M::RowVector, B>
+            + Predictor<M, M::RowVector>
+            + FeatureImportance<T>,
+        S: Scorer<T, M, E>,
+    {
+        let (_, m) = x.shape();
+        let min_features_to_select = parameters.min_features_to_select.max(1);
+        if min_features_to_select > m {
+            return Err(Failed::fit(&format!(
+                "min_features_to_select should not exceed the number of features: {} > {}",
+                min_features_to_select, m
+            )));
+        }
+
+        let mut n_features: Vec<usize> = Vec::new();
+        let mut sum_test_scores: Vec<T> = Vec::new();
+        for (train_idx, test_idx) in cv.split(x) {
+            let train_x = x.take(&train_idx, 0);
+            let train_y = y.take(&train_idx);
+            let test_x = x.take(&test_idx, 0);
+            let test_y = y.take(&test_idx);
+
+            let mut i = 0;
+            eliminate::<T, M, B, E, _>(
+                &train_x,
+                &train_y,
+                &parameters.base_estimator,
+                min_features_to_select,
+                parameters.step,
+                |selected, estimator| {
+                    let s = score.score(estimator, &test_x.take(selected, 1), &test_y)?;
+                    if i == sum_test_scores.len() {
+                        n_features.push(selected.len());
+                        sum_test_scores.push(s);
+                    } else {
+                        sum_test_scores[i] += s;
+                    }
+                    i += 1;
+                    Ok(())
+                },
+            )?;
+        }
+
+        let n_splits = T::from_usize(cv.n_splits()).unwrap();
+        let mean_test_scores: Vec<T> = sum_test_scores.iter().map(|s| *s / n_splits).collect();
+
+        // the steps go from all features down to the minimum, so ties are resolved in favour of fewer features
+        let mut best = mean_test_scores.len() - 1;
+        for i in (0..mean_test_scores.len()).rev() {
+            let is_better = if score.greater_is_better() {
+                mean_test_scores[i] > mean_test_scores[best]
+            } else {
+                mean_test_scores[i] < mean_test_scores[best]
+            };
+            if is_better {
+                best = i;
+            }
+        }
+
+        let rfe = eliminate(
+            x,
+            y,
+            &parameters.base_estimator,
+            n_features[best],
+            parameters.step,
+            |_, _| Ok(()),
+        )?;
+
+        Ok(RFECV {
+            rfe,
+            n_features,
+            mean_test_scores,
+        })
+    }
+
+    /// Selects the columns of `x` that were kept by the elimination.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn transform<M: Matrix<T>>(&self, x: &M) -> Result<M, Failed> {
+        self.rfe.transform(x)
+    }
+
+    /// Predict target values from `x` with the base estimator fitted to the selected features.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features, including the eliminated ones.
+    pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed>
+    where
+        E: Predictor<M, M::RowVector>,
+    {
+        self.rfe.predict(x)
+    }
+
+    /// The elimination fitted to the whole training set with the chosen number of features.
+    pub fn rfe(&self) -> &RFE<T, E> {
+        &self.rfe
+    }
+
+    /// `true` for every feature that was selected.
+    pub fn support(&self) -> &Vec<bool> {
+        self.rfe.support()
+    }
+
+    /// Rank of every feature. Selected features have rank 1.
+    pub fn ranking(&self) -> &Vec<usize> {
+        self.rfe.ranking()
+    }
+
+    /// Number of features at every step of the elimination, in the order the steps were taken.
+    pub fn n_features_per_step(&self) -> &Vec<usize> {
+        &self.n_features
+    }
+
+    /// Average test score of every step of the elimination over all cross-validation splits.
+    pub fn mean_test_scores(&self) -> &Vec<T> {
+        &self.mean_test_scores
+    }
+}
+
+/// Eliminates features until `n_features_to_select` are left, calling `on_step` with the selected features
+/// and the estimator fitted to them at every step, including the last one.
+fn eliminate<T, M, B, E, C>(
+    x: &M,
+    y: &M::RowVector,
+    base_estimator: &B,
+    n_features_to_select: usize,
+    step: usize,
+    mut on_step: C,
+) -> Result<RFE<T, E>, Failed>
+where
+    T: RealNumber,
+    M: Matrix<T>,
+    B: Clone,
+    E: SupervisedEstimator<M, M::RowVector, B> + FeatureImportance<T>,
+    C: FnMut(&[usize], &E) -> Result<(), Failed>,
+{
+    let (n, m) = x.shape();
+    if y.len() != n {
+        return Err(Failed::fit(&format!(
+            "Size of x should equal size of y; |x|=[{}], |y|=[{}]",
+            n,
+            y.len()
+        )));
+    }
+    if step == 0 {
+        return Err(Failed::fit("step should be at least 1"));
+    }
+
+    let mut ranking = vec![1; m];
+    let mut selected: Vec<usize> = (0..m).collect();
+
+    loop {
+        let estimator = E::fit(&x.take(&selected, 1), y, base_estimator.clone())?;
+        on_step(&selected, &estimator)?;
+
+        if selected.len() <= n_features_to_select {
+            let mut support = vec![false; m];
+            for j in selected {
+                support[j] = true;
+            }
+            return Ok(RFE {
+                estimator,
+                support,
+                ranking,
+                _phantom: PhantomData,
+            });
+        }
+
+        let importance = estimator.feature_importance();
+        if importance.len() != selected.len() {
+            return Err(Failed::fit(&format!(
+                "Base estimator reported importance of {} features, expected {}",
+                importance.len(),
+                selected.len()
+            )));
+        }
+
+        let mut order: Vec<usize> = (0..selected.len()).collect();
+        order.sort_by(|a, b| {
+            importance[*a]
+                .partial_cmp(&importance[*b])
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let n_eliminated = step.min(selected.len() - n_features_to_select);
+        let mut keep = vec![true; selected.len()];
+        for i in order.into_iter().take(n_eliminated) {
+            keep[i] = false;
+        }
+        selected = selected
+            .into_iter()
+            .zip(keep)
+            .filter(|(_, k)| *k)
+            .map(|(j, _)| j)
+            .collect();
+
+        let mut is_selected = vec![false; m];
+        for j in selected.iter() {
+            is_selected[*j] = true;
+        }
+        for (j, rank) in ranking.iter_mut().enumerate() {
+            if !is_selected[j] {
+                *rank += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::linear::linear_regression::{LinearRegression, LinearRegressionParameters};
+    use crate::metrics::r2;
+    use crate::model_selection::KFold;
+    use crate::tree::decision_tree_classifier::{
+        DecisionTreeClassifier, DecisionTreeClassifierParameters,
+    };
+
+    fn sparse_linear_data() -> (DenseMatrix<f64>, Vec<f64>) {
+        let values: Vec<Vec<f64>> = (0..40)
+            .map(|i| {
+                vec![
+                    ((i * 7) % 11) as f64,
+                    ((i * 3) % 5) as f64 / 10.,
+                    ((i * 5) % 13) as f64,
+                    ((i * 11) % 7) as f64 / 10.,
+                    ((i * 13) % 17) as f64,
+                ]
+            })
+            .collect();
+        let y = values
+            .iter()
+            .map(|v| 3. * v[0] - 2. * v[2] + v[4] + 1.)
+            .collect();
+        (DenseMatrix::from_2d_vec(&values), y)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn rfe_linear_regression() {
+        let (x, y) = sparse_linear_data();
+
+        let rfe: RFE<f64, LinearRegression<f64, DenseMatrix<f64>>> = RFE::fit(
+            &x,
+            &y,
+            RFEParameters::<LinearRegressionParameters>::default().with_n_features_to_select(3),
+        )
+        .unwrap();
+
+        assert_eq!(rfe.support(), &vec![true, false, true, false, true]);
+        assert_eq!(rfe.ranking()[0], 1);
+        assert_eq!(rfe.ranking()[2], 1);
+        assert_eq!(rfe.ranking()[4], 1);
+        let mut eliminated = vec![rfe.ranking()[1], rfe.ranking()[3]];
+        eliminated.sort_unstable();
+        assert_eq!(eliminated, vec![2, 3]);
+
+        assert_eq!(rfe.transform(&x).unwrap().shape(), (40, 3));
+        assert!(r2(&y, &rfe.predict(&x).unwrap()) > 0.999);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn rfe_step() {
+        let (x, y) = sparse_linear_data();
+
+        let rfe: RFE<f64, LinearRegression<f64, DenseMatrix<f64>>> = RFE::fit(
+            &x,
+            &y,
+            RFEParameters::<LinearRegressionParameters>::default()
+                .with_n_features_to_select(1)
+                .with_step(3),
+        )
+        .unwrap();
+
+        assert_eq!(rfe.n_features(), 1);
+        assert_eq!(rfe.ranking().iter().max(), Some(&3));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn rfe_decision_tree() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 0.3, 4.],
+            &[2., 0.1, 3.],
+            &[3., 0.4, 5.],
+            &[4., 0.2, 1.],
+            &[5., 0.5, 2.],
+            &[6., 0.1, 6.],
+            &[7., 0.3, 3.],
+            &[8., 0.2, 4.],
+        ]);
+        let y = vec![0., 0., 0., 0., 1., 1., 1., 1.];
+
+        let rfe: RFE<f64, DecisionTreeClassifier<f64>> = RFE::fit(
+            &x,
+            &y,
+            RFEParameters::<DecisionTreeClassifierParameters>::default()
+                .with_n_features_to_select(1),
+        )
+        .unwrap();
+
+        assert_eq!(rfe.support(), &vec![true, false, false]);
+        assert_eq!(rfe.predict(&x).unwrap(), y);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn rfecv() {
+        let (x, y) = sparse_linear_data();
+
+        let rfecv: RFECV<f64, LinearRegression<f64, DenseMatrix<f64>>> = RFECV::fit(
+            &x,
+            &y,
+            RFECVParameters::<LinearRegressionParameters>::default(),
+            &KFold::default().with_n_splits(4).with_shuffle(false),
+            r2,
+        )
+        .unwrap();
+
+        assert_eq!(rfecv.n_features_per_step(), &vec![5, 4, 3, 2, 1]);
+        assert_eq!(rfecv.mean_test_scores().len(), 5);
+        assert_eq!(rfecv.support(), &vec![true, false, true, false, true]);
+    }
+
+    #[test]
+    fn rfe_invalid_parameters() {
+        let (x, y) = sparse_linear_data();
+
+        let result: Result<RFE<f64, LinearRegression<f64, DenseMatrix<f64>>>, Failed> = RFE::fit(
+            &x,
+            &y,
+            RFEParameters::<LinearRegressionParameters>::default().with_n_features_to_select(6),
+        );
+        assert!(result.is_err());
+
+        let result: Result<RFE<f64, LinearRegression<f64, DenseMatrix<f64>>>, Failed> = RFE::fit(
+            &x,
+            &y,
+            RFEParameters::<LinearRegressionParameters>::default().with_step(0),
+        );
+        assert!(result.is_err());
+    }
+}
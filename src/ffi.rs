@@ -0,0 +1,440 @@
+//! # C interface
+//!
+//! Loads models saved with the [persistence](../persistence/index.html) helpers and makes predictions on raw `double` buffers
+//! through `extern "C"` functions, so that models trained in Rust can be served from C, C++, Go or any other language with a C FFI.
+//!
+//! Build SmartCore as a shared or static library with the `ffi` feature enabled:
+//! ```text
+//! cargo rustc --release --features ffi --crate-type cdylib
+//! ```
+//! and declare the functions in a header:
+//! ```c
+//! #include <stddef.h>
+//! #include <stdint.h>
+//!
+//! typedef struct SmartcoreModel SmartcoreModel;
+//!
+//! SmartcoreModel *smartcore_model_load(const char *path, uint32_t kind);
+//! SmartcoreModel *smartcore_model_load_bytes(const uint8_t *data, size_t len, uint32_t kind);
+//! int32_t smartcore_model_predict(const SmartcoreModel *model, const double *x, size_t n_rows, size_t n_cols, double *y);
+//! void smartcore_model_free(SmartcoreModel *model);
+//! const char *smartcore_last_error(void);
+//! ```
+//!
+//! The file format does not record the type of the model, so the caller passes the kind of model, one of the codes of [`ModelKind`](enum.ModelKind.html).
+//! Models must be fitted with `f64` values, with a [`DenseMatrix`](../linalg/naive/dense_matrix/struct.DenseMatrix.html) where the model is generic over the matrix type.
+//!
+//! Observations are passed as a row-major buffer of `n_rows * n_cols` values, `n_cols` must be the number of features the model was trained with.
+//! Predictions, class labels for classifiers and cluster indices for k-means, are written to a buffer of `n_rows` values.
+//! Functions that load a model return `NULL` and `smartcore_model_predict` returns `-1` on failure,
+//! [`smartcore_last_error`](fn.smartcore_last_error.html) then describes what went wrong.
+//!
+//! Example, in C:
+//! ```c
+//! SmartcoreModel *model = smartcore_model_load("tree.model", 6); // DecisionTreeClassifier
+//! if (model == NULL) {
+//!     fprintf(stderr, "%s\n", smartcore_last_error());
+//!     return 1;
+//! }
+//! double x[4] = {5.1, 3.5, 1.4, 0.2};
+//! double y[1];
+//! if (smartcore_model_predict(model, x, 1, 4, y) != 0) {
+//!     fprintf(stderr, "%s\n", smartcore_last_error());
+//! }
+//! smartcore_model_free(model);
+//! ```
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::io::BufRead;
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+
+use serde::de::DeserializeOwned;
+
+use crate::api::Predictor;
+use crate::cluster::kmeans::KMeans;
+use crate::ensemble::hist_gradient_boosting::{
+    HistGradientBoostingClassifier, HistGradientBoostingRegressor,
+};
+use crate::ensemble::random_forest_classifier::RandomForestClassifier;
+use crate::ensemble::random_forest_regressor::RandomForestRegressor;
+use crate::linalg::naive::dense_matrix::DenseMatrix;
+use crate::linear::elastic_net::ElasticNet;
+use crate::linear::lasso::Lasso;
+use crate::linear::linear_regression::LinearRegression;
+use crate::linear::logistic_regression::LogisticRegression;
+use crate::linear::ridge_regression::RidgeRegression;
+use crate::math::distance::euclidian::Euclidian;
+use crate::naive_bayes::gaussian::GaussianNB;
+use crate::neighbors::knn_classifier::KNNClassifier;
+use crate::neighbors::knn_regressor::KNNRegressor;
+use crate::persistence::{self, PersistenceError};
+use crate::tree::decision_tree_classifier::DecisionTreeClassifier;
+use crate::tree::decision_tree_regressor::DecisionTreeRegressor;
+
+/// Models that can be loaded through the C interface. The discriminant is the code passed as `kind` from C.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelKind {
+    /// `LinearRegression<f64, DenseMatrix<f64>>`
+    LinearRegression = 1,
+    /// `RidgeRegression<f64, DenseMatrix<f64>>`
+    RidgeRegression = 2,
+    /// `Lasso<f64, DenseMatrix<f64>>`
+    Lasso = 3,
+    /// `ElasticNet<f64, DenseMatrix<f64>>`
+    ElasticNet = 4,
+    /// `LogisticRegression<f64, DenseMatrix<f64>>`
+    LogisticRegression = 5,
+    /// `DecisionTreeClassifier<f64>`
+    DecisionTreeClassifier = 6,
+    /// `DecisionTreeRegressor<f64>`
+    DecisionTreeRegressor = 7,
+    /// `RandomForestClassifier<f64>`
+    RandomForestClassifier = 8,
+    /// `RandomForestRegressor<f64>`
+    RandomForestRegressor = 9,
+    /// `HistGradientBoostingClassifier<f64>`
+    HistGradientBoostingClassifier = 10,
+    /// `HistGradientBoostingRegressor<f64>`
+    HistGradientBoostingRegressor = 11,
+    /// `GaussianNB<f64, DenseMatrix<f64>>`
+    GaussianNB = 12,
+    /// `KNNClassifier<f64, Euclidian>`
+    KNNClassifier = 13,
+    /// `KNNRegressor<f64, Euclidian>`
+    KNNRegressor = 14,
+    /// `KMeans<f64>`
+    KMeans = 15,
+}
+
+impl ModelKind {
+    /// Kind of model with the given code.
+    pub fn from_code(code: u32) -> Option<ModelKind> {
+        use ModelKind::*;
+        [
+            LinearRegression,
+            RidgeRegression,
+            Lasso,
+            ElasticNet,
+            LogisticRegression,
+            DecisionTreeClassifier,
+            DecisionTreeRegressor,
+            RandomForestClassifier,
+            RandomForestRegressor,
+            HistGradientBoostingClassifier,
+            HistGradientBoostingRegressor,
+            GaussianNB,
+            KNNClassifier,
+            KNNRegressor,
+            KMeans,
+        ]
+        .iter()
+        .copied()
+        .find(|kind| *kind as u32 == code)
+    }
+}
+
+type DynPredictor = Box<dyn Predictor<DenseMatrix<f64>, Vec<f64>>>;
+
+/// A loaded model, opaque to C callers.
+pub struct SmartcoreModel {
+    model: DynPredictor,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(msg: &str) {
+    // interior NUL bytes can't be represented in a C string
+    let msg = CString::new(msg.replace('\0', " ")).unwrap();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(msg));
+}
+
+fn boxed<M, R>(reader: R) -> Result<DynPredictor, PersistenceError>
+where
+    M: Predictor<DenseMatrix<f64>, Vec<f64>> + DeserializeOwned + 'static,
+    R: BufRead,
+{
+    let model: M = persistence::from_reader(reader)?;
+    Ok(Box::new(model))
+}
+
+fn load<R: BufRead>(reader: R, kind: u32) -> Result<SmartcoreModel, String> {
+    let kind = ModelKind::from_code(kind).ok_or_else(|| format!("Unknown model kind {}", kind))?;
+    let model = match kind {
+        ModelKind::LinearRegression => boxed::<LinearRegression<f64, DenseMatrix<f64>>, _>(reader),
+        ModelKind::RidgeRegression => boxed::<RidgeRegression<f64, DenseMatrix<f64>>, _>(reader),
+        ModelKind::Lasso => boxed::<Lasso<f64, DenseMatrix<f64>>, _>(reader),
+        ModelKind::ElasticNet => boxed::<ElasticNet<f64, DenseMatrix<f64>>, _>(reader),
+        ModelKind::LogisticRegression => {
+            boxed::<LogisticRegression<f64, DenseMatrix<f64>>, _>(reader)
+        }
+        ModelKind::DecisionTreeClassifier => boxed::<DecisionTreeClassifier<f64>, _>(reader),
+        ModelKind::DecisionTreeRegressor => boxed::<DecisionTreeRegressor<f64>, _>(reader),
+        ModelKind::RandomForestClassifier => boxed::<RandomForestClassifier<f64>, _>(reader),
+        ModelKind::RandomForestRegressor => boxed::<RandomForestRegressor<f64>, _>(reader),
+        ModelKind::HistGradientBoostingClassifier => {
+            boxed::<HistGradientBoostingClassifier<f64>, _>(reader)
+        }
+        ModelKind::HistGradientBoostingRegressor => {
+            boxed::<HistGradientBoostingRegressor<f64>, _>(reader)
+        }
+        ModelKind::GaussianNB => boxed::<GaussianNB<f64, DenseMatrix<f64>>, _>(reader),
+        ModelKind::KNNClassifier => boxed::<KNNClassifier<f64, Euclidian>, _>(reader),
+        ModelKind::KNNRegressor => boxed::<KNNRegressor<f64, Euclidian>, _>(reader),
+        ModelKind::KMeans => boxed::<KMeans<f64>, _>(reader),
+    };
+    model
+        .map(|model| SmartcoreModel { model })
+        .map_err(|e| e.to_string())
+}
+
+/// Runs `f`, turning errors and panics into a message for [`smartcore_last_error`](fn.smartcore_last_error.html), panics must not unwind into C.
+fn guard<T>(f: impl FnOnce() -> Result<T, String>) -> Option<T> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => Some(value),
+        Ok(Err(msg)) => {
+            set_last_error(&msg);
+            None
+        }
+        Err(cause) => {
+            let msg = cause
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| cause.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown cause".to_string());
+            set_last_error(&format!("Panic: {}", msg));
+            None
+        }
+    }
+}
+
+/// Loads a model from a file written by [`persistence::save`](../persistence/fn.save.html).
+/// Returns `NULL` on failure, the model must be released with [`smartcore_model_free`](fn.smartcore_model_free.html).
+/// * `path` - NUL terminated UTF-8 path of the file
+/// * `kind` - code of the [kind of model](enum.ModelKind.html)
+///
+/// # Safety
+/// `path` must be `NULL` or point to a NUL terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn smartcore_model_load(
+    path: *const c_char,
+    kind: u32,
+) -> *mut SmartcoreModel {
+    guard(|| {
+        if path.is_null() {
+            return Err("The path is NULL".to_string());
+        }
+        let path = CStr::from_ptr(path)
+            .to_str()
+            .map_err(|_| "The path is not valid UTF-8".to_string())?;
+        let file = std::fs::File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+        load(std::io::BufReader::new(file), kind)
+    })
+    .map_or(ptr::null_mut(), |model| Box::into_raw(Box::new(model)))
+}
+
+/// Loads a model from a buffer written by [`persistence::to_writer`](../persistence/fn.to_writer.html), e.g. a model embedded in a binary.
+/// Returns `NULL` on failure, the model must be released with [`smartcore_model_free`](fn.smartcore_model_free.html).
+/// * `data` - the serialized model
+/// * `len` - length of the buffer in bytes
+/// * `kind` - code of the [kind of model](enum.ModelKind.html)
+///
+/// # Safety
+/// `data` must be `NULL` or point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn smartcore_model_load_bytes(
+    data: *const u8,
+    len: usize,
+    kind: u32,
+) -> *mut SmartcoreModel {
+    guard(|| {
+        if data.is_null() {
+            return Err("The buffer is NULL".to_string());
+        }
+        load(slice::from_raw_parts(data, len), kind)
+    })
+    .map_or(ptr::null_mut(), |model| Box::into_raw(Box::new(model)))
+}
+
+/// Predicts the target of every observation. Returns 0 on success and -1 on failure.
+/// * `model` - a model returned by one of the load functions
+/// * `x` - row-major buffer with `n_rows` observations of `n_cols` features each
+/// * `y` - buffer of `n_rows` values that receives the predictions
+///
+/// # Safety
+/// `model` must be `NULL` or a model that has not been released, `x` must point to `n_rows * n_cols` readable values
+/// and `y` to `n_rows` writable values.
+#[no_mangle]
+pub unsafe extern "C" fn smartcore_model_predict(
+    model: *const SmartcoreModel,
+    x: *const f64,
+    n_rows: usize,
+    n_cols: usize,
+    y: *mut f64,
+) -> i32 {
+    let result = guard(|| {
+        if model.is_null() || x.is_null() || y.is_null() {
+            return Err("The model or one of the buffers is NULL".to_string());
+        }
+        let len = n_rows
+            .checked_mul(n_cols)
+            .ok_or_else(|| "The input is too large".to_string())?;
+        let x = DenseMatrix::from_array(n_rows, n_cols, slice::from_raw_parts(x, len));
+        let y_hat = (*model).model.predict(&x).map_err(|e| e.to_string())?;
+        if y_hat.len() != n_rows {
+            return Err(format!(
+                "Expected {} predictions, the model returned {}",
+                n_rows,
+                y_hat.len()
+            ));
+        }
+        slice::from_raw_parts_mut(y, n_rows).copy_from_slice(&y_hat);
+        Ok(())
+    });
+    match result {
+        Some(()) => 0,
+        None => -1,
+    }
+}
+
+/// Releases a model, passing `NULL` is a no-op.
+///
+/// # Safety
+/// `model` must be `NULL` or a model returned by one of the load functions that has not been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn smartcore_model_free(model: *mut SmartcoreModel) {
+    if !model.is_null() {
+        drop(Box::from_raw(model));
+    }
+}
+
+/// Message of the last error on the calling thread, or `NULL` if no call failed yet.
+/// The string is owned by SmartCore and valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn smartcore_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |msg| msg.as_ptr())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::Format;
+
+    fn error() -> String {
+        unsafe { CStr::from_ptr(smartcore_last_error()) }
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn load_and_predict() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 2.],
+            &[2., 1.],
+            &[5., 6.],
+            &[6., 5.],
+            &[2., 2.],
+            &[6., 6.],
+        ]);
+        let y = vec![0., 0., 1., 1., 0., 1.];
+        let tree = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
+
+        for format in [Format::Bincode, Format::Json] {
+            let mut buf = Vec::new();
+            persistence::to_writer(&tree, &mut buf, format).unwrap();
+
+            let model = unsafe {
+                smartcore_model_load_bytes(
+                    buf.as_ptr(),
+                    buf.len(),
+                    ModelKind::DecisionTreeClassifier as u32,
+                )
+            };
+            assert!(!model.is_null());
+
+            let rows = [1.5, 1.5, 5.5, 5.5, 6., 6.];
+            let mut y_hat = [-1.; 3];
+            let status =
+                unsafe { smartcore_model_predict(model, rows.as_ptr(), 3, 2, y_hat.as_mut_ptr()) };
+            assert_eq!(0, status);
+            assert_eq!([0., 1., 1.], y_hat);
+
+            unsafe { smartcore_model_free(model) };
+        }
+    }
+
+    #[test]
+    fn load_from_file() {
+        let x = DenseMatrix::from_2d_array(&[&[1., 1.], &[1., 2.], &[2., 2.], &[2., 3.]]);
+        let y = vec![6., 8., 9., 11.];
+        let lr = LinearRegression::fit(&x, &y, Default::default()).unwrap();
+
+        let path = std::env::temp_dir().join("smartcore_ffi_linear_regression.model");
+        persistence::save(&lr, &path, Format::Bincode).unwrap();
+        let c_path = CString::new(path.to_str().unwrap()).unwrap();
+
+        let model =
+            unsafe { smartcore_model_load(c_path.as_ptr(), ModelKind::LinearRegression as u32) };
+        std::fs::remove_file(&path).unwrap();
+        assert!(!model.is_null());
+
+        let mut y_hat = [0.; 4];
+        let rows = [1., 1., 1., 2., 2., 2., 2., 3.];
+        assert_eq!(0, unsafe {
+            smartcore_model_predict(model, rows.as_ptr(), 4, 2, y_hat.as_mut_ptr())
+        });
+        for (expected, actual) in lr.predict(&x).unwrap().iter().zip(y_hat.iter()) {
+            assert!((expected - actual).abs() < 1e-9);
+        }
+
+        unsafe { smartcore_model_free(model) };
+    }
+
+    #[test]
+    fn errors() {
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.]]);
+        let tree =
+            DecisionTreeRegressor::fit(&x, &vec![1., 2., 3., 4.], Default::default()).unwrap();
+        let mut buf = Vec::new();
+        persistence::to_writer(&tree, &mut buf, Format::Bincode).unwrap();
+
+        let model = unsafe { smartcore_model_load_bytes(buf.as_ptr(), buf.len(), 99) };
+        assert!(model.is_null());
+        assert_eq!("Unknown model kind 99", error());
+
+        let model = unsafe { smartcore_model_load_bytes(buf.as_ptr(), 3, 7) };
+        assert!(model.is_null());
+        assert!(error().starts_with("Invalid header"));
+
+        let missing = CString::new("/nonexistent/smartcore.model").unwrap();
+        assert!(unsafe { smartcore_model_load(missing.as_ptr(), 7) }.is_null());
+        assert!(unsafe { smartcore_model_load(ptr::null(), 7) }.is_null());
+
+        let model = unsafe { smartcore_model_load_bytes(buf.as_ptr(), buf.len(), 7) };
+        assert!(!model.is_null());
+        let mut y_hat = [0.; 1];
+        assert_eq!(-1, unsafe {
+            smartcore_model_predict(model, ptr::null(), 1, 1, y_hat.as_mut_ptr())
+        });
+        assert_eq!("The model or one of the buffers is NULL", error());
+        unsafe { smartcore_model_free(model) };
+        unsafe { smartcore_model_free(ptr::null_mut()) };
+    }
+
+    #[test]
+    fn model_kinds() {
+        assert_eq!(Some(ModelKind::Lasso), ModelKind::from_code(3));
+        assert_eq!(Some(ModelKind::KMeans), ModelKind::from_code(15));
+        assert_eq!(None, ModelKind::from_code(0));
+    }
+}
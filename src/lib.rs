@@ -7,6 +7,7 @@
 )]
 #![warn(missing_docs)]
 #![warn(rustdoc::missing_doc_code_examples)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 //! # SmartCore
 //!
@@ -28,15 +29,44 @@
 //! smartcore = "0.2.0"
 //! ```
 //!
+//! Enable the `parallel` feature to spread predictions, fitting of random forests and cross-validation over all CPU cores,
+//! see [Parallel execution](parallel/index.html).
+//!
+//! Enable the `log` feature to follow long-running fits, like SVC epochs, boosting rounds, k-means iterations and grid search candidates,
+//! through the [log](https://docs.rs/log) crate. Objective values after every iteration are logged at the debug level and summaries at the info level.
+//!
+//! Enable the `persistence` feature to save fitted models to files and load them back, see [Model persistence](persistence/index.html).
+//!
+//! Enable the `onnx` feature to export linear models, trees, forests, gradient boosting models and scalers to [ONNX](https://onnx.ai)
+//! and serve them with any runtime that supports ONNX-ML operators, see [ONNX export](onnx/index.html).
+//!
+//! Enable the `pmml` feature to exchange trees, forests, linear models and k-means with JVM and enterprise scoring engines
+//! through [PMML](https://dmg.org/pmml/v4-4-1/GeneralStructure.html) documents, see [PMML export and import](pmml/index.html).
+//!
+//! Enable the `ffi` feature to load saved models and make predictions from C, C++, Go or other languages with a C interface,
+//! see [C interface](ffi/index.html).
+//!
+//! SmartCore builds without the standard library when default features are disabled, only `alloc` is required.
+//! This way fitted models, e.g. trees, linear models and support vector machines, can make predictions on embedded targets:
+//! ```ignore
+//! [dependencies]
+//! smartcore = { version = "0.2.0", default-features = false }
+//! ```
+//! Without the `std` feature estimators that use random numbers need an explicit seed, hash maps are replaced with ordered maps
+//! and a few modules that read files or hash arbitrary categories, like [readers](readers/index.html) and categorical encoders, are not available.
+//!
 //! All machine learning algorithms in SmartCore are grouped into these broad categories:
 //! * [Clustering](cluster/index.html), unsupervised clustering of unlabeled data.
 //! * [Matrix Decomposition](decomposition/index.html), various methods for matrix decomposition.
+//! * [Manifold Learning](manifold/index.html), non-linear dimensionality reduction with UMAP
 //! * [Linear Models](linear/index.html), regression and classification methods where output is assumed to have linear relation to explanatory variables
 //! * [Ensemble Models](ensemble/index.html), variety of regression and classification ensemble models
 //! * [Tree-based Models](tree/index.html), classification and regression trees
 //! * [Nearest Neighbors](neighbors/index.html), K Nearest Neighbors for classification and regression
 //! * [Naive Bayes](naive_bayes/index.html), statistical classification technique based on Bayes Theorem
 //! * [SVM](svm/index.html), support vector machines
+//! * [Neural Networks](neural_network/index.html), multi-layer perceptrons
+//! * [Feature Extraction](feature_extraction/index.html), token counts and TF-IDF weights of text documents
 //!
 //!
 //! For example, you can use this code to fit a [K Nearest Neighbors classifier](neighbors/knn_classifier/index.html) to a dataset that is defined as standard Rust vector:
@@ -65,10 +95,50 @@
 //! // Predict classes
 //! let y_hat = knn.predict(&x).unwrap();
 //! ```
+//!
+//! ## WebAssembly
+//!
+//! SmartCore compiles to `wasm32-unknown-unknown`, so models can run directly in a browser. Random numbers are seeded from
+//! `crypto.getRandomValues` and, unless the module is built with the `atomics` target feature, all work runs in a single thread.
+//!
+//! A common way to deploy a model is to fit it natively, serialize it with the `serde` feature and
+//! only make predictions in the browser, e.g. from a function exported with [wasm-bindgen](https://docs.rs/wasm-bindgen):
+//!
+//! ```
+//! # #[cfg(feature = "serde")]
+//! # {
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::tree::decision_tree_classifier::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!    &[1., 2.],
+//!    &[3., 4.],
+//!    &[5., 6.],
+//!    &[7., 8.]]);
+//! let y = vec![0., 0., 1., 1.];
+//!
+//! // natively: fit a model and store it
+//! let tree = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
+//! let model = serde_json::to_string(&tree).unwrap();
+//!
+//! // in the browser: load the model and predict
+//! let tree: DecisionTreeClassifier<f64> = serde_json::from_str(&model).unwrap();
+//! let y_hat = tree.predict(&x).unwrap();
+//! # assert_eq!(y, y_hat);
+//! # }
+//! ```
+
+#[cfg_attr(not(feature = "std"), macro_use)]
+extern crate alloc;
+
+#[macro_use]
+mod logging;
 
 /// Various algorithms and helper methods that are used elsewhere in SmartCore
 pub mod algorithm;
 pub mod api;
+/// Callbacks that monitor the training of iterative estimators and can stop it early
+pub mod callback;
 /// Algorithms for clustering of unlabeled data
 pub mod cluster;
 /// Various datasets
@@ -79,10 +149,19 @@ pub mod decomposition;
 /// Ensemble methods, including Random Forest classifier and regressor
 pub mod ensemble;
 pub mod error;
+/// Extraction of numerical features from raw data, like text documents
+pub mod feature_extraction;
+/// Selection of the features that are most relevant for a model
+pub mod feature_selection;
+/// Loading saved models and making predictions from other languages through a C interface
+#[cfg(feature = "ffi")]
+pub mod ffi;
 /// Diverse collection of linear algebra abstractions and methods that power SmartCore algorithms
 pub mod linalg;
 /// Supervised classification and regression models that assume linear relationship between dependent and explanatory variables.
 pub mod linear;
+/// Non-linear dimensionality reduction that preserves the neighbourhoods of the data
+pub mod manifold;
 /// Helper methods and classes, including definitions of distance metrics
 pub mod math;
 /// Functions for assessing prediction error.
@@ -92,14 +171,33 @@ pub mod model_selection;
 pub mod naive_bayes;
 /// Supervised neighbors-based learning methods
 pub mod neighbors;
-pub(crate) mod optimization;
+/// Multi-layer perceptron classifiers and regressors trained with backpropagation
+pub mod neural_network;
+/// Export of fitted models to ONNX
+#[cfg(feature = "onnx")]
+pub mod onnx;
+/// Numerical optimizers used to fit the models, for minimizing your own objective functions
+pub mod optimization;
+pub mod parallel;
+/// Saving fitted models to files and loading them back
+#[cfg(feature = "persistence")]
+pub mod persistence;
+/// Chains of transformers with a final estimator, fitted and applied as a single model
+pub mod pipeline;
+/// Reading and writing models in PMML
+#[cfg(feature = "pmml")]
+pub mod pmml;
 /// Preprocessing utilities
 pub mod preprocessing;
 /// Reading in Data.
+#[cfg(feature = "std")]
 pub mod readers;
 /// Support Vector Machines
 pub mod svm;
 /// Supervised tree-based learning methods
 pub mod tree;
+/// Checks for missing and infinite values in the input of estimators
+pub mod validation;
 
+pub(crate) mod collections;
 pub(crate) mod rand;
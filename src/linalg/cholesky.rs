@@ -30,8 +30,8 @@
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 #![allow(non_snake_case)]
 
-use std::fmt::Debug;
-use std::marker::PhantomData;
+use core::fmt::Debug;
+use core::marker::PhantomData;
 
 use crate::error::{Failed, FailedError};
 use crate::linalg::BaseMatrix;
@@ -85,8 +85,7 @@ impl<T: RealNumber, M: BaseMatrix<T>> Cholesky<T, M> {
         let (rn, _) = self.R.shape();
 
         if bn != rn {
-            return Err(Failed::because(
-                FailedError::SolutionFailed,
+            return Err(Failed::shape_mismatch(
                 "Can\'t solve Ax = b for x. Number of rows in b != number of rows in R.",
             ));
         }
@@ -50,8 +50,9 @@
 use crate::error::Failed;
 use crate::linalg::BaseMatrix;
 use crate::math::num::RealNumber;
+use alloc::vec::Vec;
+use core::fmt::Debug;
 use num::complex::Complex;
-use std::fmt::Debug;
 
 #[derive(Debug, Clone)]
 /// Results of eigen decomposition
@@ -33,11 +33,12 @@
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 #![allow(non_snake_case)]
 
-use std::cmp::Ordering;
-use std::fmt::Debug;
-use std::marker::PhantomData;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::marker::PhantomData;
 
-use crate::error::Failed;
+use crate::error::{Failed, FailedError};
 use crate::linalg::BaseMatrix;
 use crate::math::num::RealNumber;
 
@@ -142,14 +143,17 @@ impl<T: RealNumber, M: BaseMatrix<T>> LU<T, M> {
         let (b_m, b_n) = b.shape();
 
         if b_m != m {
-            panic!(
+            return Err(Failed::shape_mismatch(&format!(
                 "Row dimensions do not agree: A is {} x {}, but B is {} x {}",
                 m, n, b_m, b_n
-            );
+            )));
         }
 
         if self.singular {
-            panic!("Matrix is singular.");
+            return Err(Failed::because(
+                FailedError::SolutionFailed,
+                "Matrix is singular.",
+            ));
         }
 
         let mut X = M::zeros(b_m, b_n);
@@ -202,6 +206,13 @@ pub trait LUDecomposableMatrix<T: RealNumber>: BaseMatrix<T> {
     fn lu_mut(mut self) -> Result<LU<T, Self>, Failed> {
         let (m, n) = self.shape();
 
+        if m != n {
+            return Err(Failed::because(
+                FailedError::DecompositionFailed,
+                "Can\'t do LU decomposition on a non-square matrix",
+            ));
+        }
+
         let mut piv = (0..m).collect::<Vec<_>>();
 
         let mut pivsign = 1;
@@ -284,4 +295,28 @@ mod tests {
         let a_inv = a.lu().and_then(|lu| lu.inverse()).unwrap();
         assert!(a_inv.approximate_eq(&expected, 1e-4));
     }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn lu_errors() {
+        let a = DenseMatrix::from_2d_array(&[&[1., 2., 3.], &[0., 1., 5.]]);
+        assert_eq!(
+            FailedError::DecompositionFailed,
+            a.lu().unwrap_err().error()
+        );
+
+        let singular = DenseMatrix::from_2d_array(&[&[1., 2.], &[2., 4.]]);
+        let b = DenseMatrix::from_2d_array(&[&[1.], &[2.]]);
+        assert_eq!(
+            FailedError::SolutionFailed,
+            singular.lu_solve_mut(b).unwrap_err().error()
+        );
+
+        let a = DenseMatrix::from_2d_array(&[&[1., 2.], &[3., 4.]]);
+        let b = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.]]);
+        assert_eq!(
+            FailedError::ShapeMismatch,
+            a.lu_solve_mut(b).unwrap_err().error()
+        );
+    }
 }
@@ -50,28 +50,38 @@ pub mod nalgebra_bindings;
 pub mod ndarray_bindings;
 /// QR factorization that factors a matrix into a product of an orthogonal matrix and an upper triangular matrix.
 pub mod qr;
+/// Sparse matrix that stores only the non-zero entries.
+pub mod sparse;
 pub mod stats;
 /// Singular value decomposition.
 pub mod svd;
 
-use std::fmt::{Debug, Display};
-use std::marker::PhantomData;
-use std::ops::Range;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display};
+use core::marker::PhantomData;
+use core::ops::Range;
 
+use rand::Rng;
+
+use crate::error::Failed;
 use crate::math::num::RealNumber;
+use crate::parallel::MaybeSync;
+use crate::rand::get_rng_impl;
 use cholesky::CholeskyDecomposableMatrix;
 use evd::EVDDecomposableMatrix;
 use high_order::HighOrderOperations;
 use lu::LUDecomposableMatrix;
 use qr::QRDecomposableMatrix;
 use stats::{MatrixPreprocessing, MatrixStats};
+#[cfg(feature = "std")]
 use std::fs;
 use svd::SVDDecomposableMatrix;
 
+#[cfg(feature = "std")]
 use crate::readers;
 
 /// Column or row vector
-pub trait BaseVector<T: RealNumber>: Clone + Debug {
+pub trait BaseVector<T: RealNumber>: Clone + Debug + MaybeSync {
     /// Get an element of a vector
     /// * `i` - index of an element
     fn get(&self, i: usize) -> T;
@@ -119,6 +129,12 @@ pub trait BaseVector<T: RealNumber>: Clone + Debug {
     /// Vector dot product
     fn dot(&self, other: &Self) -> T;
 
+    /// Vector dot product, returns an error when vectors have different lengths.
+    fn try_dot(&self, other: &Self) -> Result<T, Failed> {
+        check_same_len(self.len(), other.len())?;
+        Ok(self.dot(other))
+    }
+
     /// Returns True if matrices are element-wise equal within a tolerance `error`.
     fn approximate_eq(&self, other: &Self, error: T) -> bool;
 
@@ -203,15 +219,39 @@ pub trait BaseVector<T: RealNumber>: Clone + Debug {
     /// Add vectors, element-wise, overriding original vector with result.
     fn add_mut(&mut self, other: &Self) -> &Self;
 
+    /// Add vectors, element-wise, overriding original vector with result. Returns an error when vectors have different lengths.
+    fn try_add_mut(&mut self, other: &Self) -> Result<&Self, Failed> {
+        check_same_len(self.len(), other.len())?;
+        Ok(self.add_mut(other))
+    }
+
     /// Subtract vectors, element-wise, overriding original vector with result.
     fn sub_mut(&mut self, other: &Self) -> &Self;
 
+    /// Subtract vectors, element-wise, overriding original vector with result. Returns an error when vectors have different lengths.
+    fn try_sub_mut(&mut self, other: &Self) -> Result<&Self, Failed> {
+        check_same_len(self.len(), other.len())?;
+        Ok(self.sub_mut(other))
+    }
+
     /// Multiply vectors, element-wise, overriding original vector with result.
     fn mul_mut(&mut self, other: &Self) -> &Self;
 
+    /// Multiply vectors, element-wise, overriding original vector with result. Returns an error when vectors have different lengths.
+    fn try_mul_mut(&mut self, other: &Self) -> Result<&Self, Failed> {
+        check_same_len(self.len(), other.len())?;
+        Ok(self.mul_mut(other))
+    }
+
     /// Divide vectors, element-wise, overriding original vector with result.
     fn div_mut(&mut self, other: &Self) -> &Self;
 
+    /// Divide vectors, element-wise, overriding original vector with result. Returns an error when vectors have different lengths.
+    fn try_div_mut(&mut self, other: &Self) -> Result<&Self, Failed> {
+        check_same_len(self.len(), other.len())?;
+        Ok(self.div_mut(other))
+    }
+
     /// Add vectors, element-wise
     fn add(&self, other: &Self) -> Self {
         let mut r = self.clone();
@@ -294,7 +334,7 @@ pub trait BaseVector<T: RealNumber>: Clone + Debug {
 }
 
 /// Generic matrix type.
-pub trait BaseMatrix<T: RealNumber>: Clone + Debug {
+pub trait BaseMatrix<T: RealNumber>: Clone + Debug + MaybeSync {
     /// Row vector that is associated with this matrix type,
     /// e.g. if we have an implementation of sparce matrix
     /// we should have an associated sparce vector type that
@@ -315,6 +355,7 @@ pub trait BaseMatrix<T: RealNumber>: Clone + Debug {
     /// );
     /// fs::remove_file("identity.csv");
     /// ```
+    #[cfg(feature = "std")]
     fn from_csv(
         path: &str,
         definition: readers::csv::CSVDefinition<'_>,
@@ -428,6 +469,18 @@ pub trait BaseMatrix<T: RealNumber>: Clone + Debug {
     /// ```
     fn h_stack(&self, other: &Self) -> Self;
 
+    /// Stack arrays side by side, horizontally (column wise). Returns an error when numbers of rows differ.
+    fn try_h_stack(&self, other: &Self) -> Result<Self, Failed> {
+        let ((nrows, ncols), (other_nrows, other_ncols)) = (self.shape(), other.shape());
+        if nrows != other_nrows {
+            return Err(Failed::shape_mismatch(&format!(
+                "Can not stack {}x{} and {}x{} matrices horizontally",
+                nrows, ncols, other_nrows, other_ncols
+            )));
+        }
+        Ok(self.h_stack(other))
+    }
+
     /// Stack arrays in sequence horizontally (column wise).
     /// ```
     /// use smartcore::linalg::naive::dense_matrix::*;
@@ -443,6 +496,18 @@ pub trait BaseMatrix<T: RealNumber>: Clone + Debug {
     /// ```
     fn v_stack(&self, other: &Self) -> Self;
 
+    /// Stack arrays one on top of the other, vertically (row wise). Returns an error when numbers of columns differ.
+    fn try_v_stack(&self, other: &Self) -> Result<Self, Failed> {
+        let ((nrows, ncols), (other_nrows, other_ncols)) = (self.shape(), other.shape());
+        if ncols != other_ncols {
+            return Err(Failed::shape_mismatch(&format!(
+                "Can not stack {}x{} and {}x{} matrices vertically",
+                nrows, ncols, other_nrows, other_ncols
+            )));
+        }
+        Ok(self.v_stack(other))
+    }
+
     /// Matrix product.
     /// ```
     /// use smartcore::linalg::naive::dense_matrix::*;
@@ -457,6 +522,27 @@ pub trait BaseMatrix<T: RealNumber>: Clone + Debug {
     /// ```
     fn matmul(&self, other: &Self) -> Self;
 
+    /// Matrix product, returns an error when number of columns of A does not equal number of rows of B.
+    /// ```
+    /// use smartcore::linalg::naive::dense_matrix::*;
+    /// use smartcore::error::FailedError;
+    ///
+    /// let a = DenseMatrix::from_2d_array(&[&[1., 2.], &[3., 4.]]);
+    /// let b = DenseMatrix::from_2d_array(&[&[1., 2.]]);
+    ///
+    /// assert_eq!(a.try_matmul(&b).unwrap_err().error(), FailedError::ShapeMismatch);
+    /// ```
+    fn try_matmul(&self, other: &Self) -> Result<Self, Failed> {
+        let ((nrows, ncols), (other_nrows, other_ncols)) = (self.shape(), other.shape());
+        if ncols != other_nrows {
+            return Err(Failed::shape_mismatch(&format!(
+                "Can not multiply {}x{} by {}x{} matrices",
+                nrows, ncols, other_nrows, other_ncols
+            )));
+        }
+        Ok(self.matmul(other))
+    }
+
     /// Vector dot product
     /// Both matrices should be of size _1xM_
     /// ```
@@ -469,6 +555,19 @@ pub trait BaseMatrix<T: RealNumber>: Clone + Debug {
     /// ```
     fn dot(&self, other: &Self) -> T;
 
+    /// Vector dot product, returns an error unless both matrices are row or column vectors of the same length.
+    fn try_dot(&self, other: &Self) -> Result<T, Failed> {
+        let ((nrows, ncols), (other_nrows, other_ncols)) = (self.shape(), other.shape());
+        if (nrows != 1 && ncols != 1) || (other_nrows != 1 && other_ncols != 1) {
+            return Err(Failed::shape_mismatch(&format!(
+                "A and B should both be either a row or a column vector, got {}x{} and {}x{}",
+                nrows, ncols, other_nrows, other_ncols
+            )));
+        }
+        check_same_len(nrows * ncols, other_nrows * other_ncols)?;
+        Ok(self.dot(other))
+    }
+
     /// Return a slice of the matrix.
     /// * `rows` - range of rows to return
     /// * `cols` - range of columns to return
@@ -492,15 +591,39 @@ pub trait BaseMatrix<T: RealNumber>: Clone + Debug {
     /// Add matrices, element-wise, overriding original matrix with result.
     fn add_mut(&mut self, other: &Self) -> &Self;
 
+    /// Add matrices, element-wise, overriding original matrix with result. Returns an error when shapes of matrices differ.
+    fn try_add_mut(&mut self, other: &Self) -> Result<&Self, Failed> {
+        check_same_shape(self.shape(), other.shape())?;
+        Ok(self.add_mut(other))
+    }
+
     /// Subtract matrices, element-wise, overriding original matrix with result.
     fn sub_mut(&mut self, other: &Self) -> &Self;
 
+    /// Subtract matrices, element-wise, overriding original matrix with result. Returns an error when shapes of matrices differ.
+    fn try_sub_mut(&mut self, other: &Self) -> Result<&Self, Failed> {
+        check_same_shape(self.shape(), other.shape())?;
+        Ok(self.sub_mut(other))
+    }
+
     /// Multiply matrices, element-wise, overriding original matrix with result.
     fn mul_mut(&mut self, other: &Self) -> &Self;
 
+    /// Multiply matrices, element-wise, overriding original matrix with result. Returns an error when shapes of matrices differ.
+    fn try_mul_mut(&mut self, other: &Self) -> Result<&Self, Failed> {
+        check_same_shape(self.shape(), other.shape())?;
+        Ok(self.mul_mut(other))
+    }
+
     /// Divide matrices, element-wise, overriding original matrix with result.
     fn div_mut(&mut self, other: &Self) -> &Self;
 
+    /// Divide matrices, element-wise, overriding original matrix with result. Returns an error when shapes of matrices differ.
+    fn try_div_mut(&mut self, other: &Self) -> Result<&Self, Failed> {
+        check_same_shape(self.shape(), other.shape())?;
+        Ok(self.div_mut(other))
+    }
+
     /// Divide single element of the matrix by `x`, write result to original matrix.
     fn div_element_mut(&mut self, row: usize, col: usize, x: T);
 
@@ -587,6 +710,27 @@ pub trait BaseMatrix<T: RealNumber>: Clone + Debug {
     /// Create new `nrows` by `ncols` matrix and populate it with random samples from a uniform distribution over [0, 1).
     fn rand(nrows: usize, ncols: usize) -> Self;
 
+    /// Create new `nrows` by `ncols` matrix and populate it with random samples from a uniform distribution over [0, 1).
+    /// The same `seed` always gives the same matrix.
+    /// ```
+    /// use smartcore::linalg::naive::dense_matrix::*;
+    ///
+    /// let a: DenseMatrix<f64> = DenseMatrix::rand_with_seed(3, 2, 42);
+    /// let b: DenseMatrix<f64> = DenseMatrix::rand_with_seed(3, 2, 42);
+    ///
+    /// assert_eq!(a, b);
+    /// ```
+    fn rand_with_seed(nrows: usize, ncols: usize, seed: u64) -> Self {
+        let mut rng = get_rng_impl(Some(seed));
+        let mut m = Self::zeros(nrows, ncols);
+        for c in 0..ncols {
+            for r in 0..nrows {
+                m.set(r, c, T::from_f64(rng.gen()).unwrap());
+            }
+        }
+        m
+    }
+
     /// Returns [L2 norm](https://en.wikipedia.org/wiki/Matrix_norm).
     fn norm2(&self) -> T;
 
@@ -620,6 +764,19 @@ pub trait BaseMatrix<T: RealNumber>: Clone + Debug {
     /// ```
     fn reshape(&self, nrows: usize, ncols: usize) -> Self;
 
+    /// Returns new matrix of shape `nrows` by `ncols` with data copied from original matrix.
+    /// Returns an error when the new shape has a different number of elements.
+    fn try_reshape(&self, nrows: usize, ncols: usize) -> Result<Self, Failed> {
+        let (self_nrows, self_ncols) = self.shape();
+        if self_nrows * self_ncols != nrows * ncols {
+            return Err(Failed::shape_mismatch(&format!(
+                "Can not reshape {}x{} matrix into {}x{} matrix",
+                self_nrows, self_ncols, nrows, ncols
+            )));
+        }
+        Ok(self.reshape(nrows, ncols))
+    }
+
     /// Copies content of `other` matrix.
     fn copy_from(&mut self, other: &Self);
 
@@ -737,6 +894,26 @@ pub trait Matrix<T: RealNumber>:
 {
 }
 
+fn check_same_len(len: usize, other_len: usize) -> Result<(), Failed> {
+    if len != other_len {
+        return Err(Failed::shape_mismatch(&format!(
+            "A and B should have the same size: {} != {}",
+            len, other_len
+        )));
+    }
+    Ok(())
+}
+
+fn check_same_shape(shape: (usize, usize), other_shape: (usize, usize)) -> Result<(), Failed> {
+    if shape != other_shape {
+        return Err(Failed::shape_mismatch(&format!(
+            "A and B should have the same shape: {}x{} != {}x{}",
+            shape.0, shape.1, other_shape.0, other_shape.1
+        )));
+    }
+    Ok(())
+}
+
 pub(crate) fn row_iter<F: RealNumber, M: BaseMatrix<F>>(m: &M) -> RowIter<'_, F, M> {
     RowIter {
         m,
@@ -769,6 +946,7 @@ impl<'a, T: RealNumber, M: BaseMatrix<T>> Iterator for RowIter<'a, T, M> {
 
 #[cfg(test)]
 mod tests {
+    use crate::error::FailedError;
     use crate::linalg::naive::dense_matrix::DenseMatrix;
     use crate::linalg::BaseMatrix;
     use crate::linalg::BaseVector;
@@ -847,6 +1025,41 @@ mod tests {
         );
     }
 
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn try_shape_mismatch() {
+        let mut a = DenseMatrix::from_2d_array(&[&[1., 2., 3.], &[4., 5., 6.]]);
+        let b = DenseMatrix::from_2d_array(&[&[1., 2.], &[3., 4.]]);
+
+        assert_eq!(
+            FailedError::ShapeMismatch,
+            a.try_add_mut(&b).unwrap_err().error()
+        );
+        assert_eq!(
+            FailedError::ShapeMismatch,
+            a.try_div_mut(&b).unwrap_err().error()
+        );
+        assert!(a.try_matmul(&b).is_err());
+        assert!(b.try_matmul(&a).is_ok());
+        assert!(a.try_reshape(4, 2).is_err());
+        assert_eq!(a.try_reshape(3, 2).unwrap(), a.reshape(3, 2));
+        assert!(a.try_v_stack(&b).is_err());
+        assert!(a.try_h_stack(&b).is_ok());
+        assert!(a.try_dot(&a).is_err());
+
+        let row = DenseMatrix::row_vector_from_array(&[1., 2., 3.]);
+        let column = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.]]);
+        assert_eq!(14., row.try_dot(&column).unwrap());
+        assert!(row
+            .try_dot(&DenseMatrix::row_vector_from_array(&[1., 2.]))
+            .is_err());
+
+        let mut v = vec![1., 2., 3.];
+        assert!(v.try_sub_mut(&vec![1., 2.]).is_err());
+        assert_eq!(&vec![0., 0., 0.], v.try_sub_mut(&vec![1., 2., 3.]).unwrap());
+        assert!(BaseVector::try_dot(&v, &vec![1.]).is_err());
+    }
+
     #[test]
     fn test_from_row_vectors_simple() {
         let eye = DenseMatrix::from_row_vectors(vec![
@@ -1,9 +1,10 @@
 #![allow(clippy::ptr_arg)]
-use std::fmt;
-use std::fmt::Debug;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Debug;
 #[cfg(feature = "serde")]
-use std::marker::PhantomData;
-use std::ops::Range;
+use core::marker::PhantomData;
+use core::ops::Range;
 
 #[cfg(feature = "serde")]
 use serde::de::{Deserializer, MapAccess, SeqAccess, Visitor};
@@ -12,6 +13,9 @@ use serde::ser::{SerializeStruct, Serializer};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
 use crate::linalg::cholesky::CholeskyDecomposableMatrix;
 use crate::linalg::evd::EVDDecomposableMatrix;
 use crate::linalg::high_order::HighOrderOperations;
@@ -37,8 +37,8 @@
 //! let lr = LinearRegression::fit(&x, &y, Default::default()).unwrap();
 //! let y_hat = lr.predict(&x).unwrap();
 //! ```
-use std::iter::Sum;
-use std::ops::{AddAssign, DivAssign, MulAssign, Range, SubAssign};
+use core::iter::Sum;
+use core::ops::{AddAssign, DivAssign, MulAssign, Range, SubAssign};
 
 use nalgebra::{Const, DMatrix, Dynamic, Matrix, OMatrix, RowDVector, Scalar, VecStorage, U1};
 
@@ -1006,6 +1006,7 @@ mod tests {
             &y,
             LinearRegressionParameters {
                 solver: LinearRegressionSolverName::QR,
+                ..Default::default()
             },
         )
         .and_then(|lr| lr.predict(&x))
@@ -39,12 +39,12 @@
 //! let lr = LogisticRegression::fit(&x, &y, Default::default()).unwrap();
 //! let y_hat = lr.predict(&x).unwrap();
 //! ```
-use std::iter::Sum;
-use std::ops::AddAssign;
-use std::ops::DivAssign;
-use std::ops::MulAssign;
-use std::ops::Range;
-use std::ops::SubAssign;
+use core::iter::Sum;
+use core::ops::AddAssign;
+use core::ops::DivAssign;
+use core::ops::MulAssign;
+use core::ops::Range;
+use core::ops::SubAssign;
 
 use ndarray::ScalarOperand;
 use ndarray::{concatenate, s, Array, ArrayBase, Axis, Ix1, Ix2, OwnedRepr};
@@ -1009,6 +1009,7 @@ mod tests {
                 m: Option::None,
                 keep_samples: false,
                 seed: 0,
+                keep_leaf_values: false,
             },
         )
         .unwrap()
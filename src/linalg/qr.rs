@@ -28,10 +28,11 @@
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 #![allow(non_snake_case)]
 
-use crate::error::Failed;
+use crate::error::{Failed, FailedError};
 use crate::linalg::BaseMatrix;
 use crate::math::num::RealNumber;
-use std::fmt::Debug;
+use alloc::vec::Vec;
+use core::fmt::Debug;
 
 #[derive(Debug, Clone)]
 /// Results of QR decomposition.
@@ -43,17 +44,19 @@ pub struct QR<T: RealNumber, M: BaseMatrix<T>> {
 
 impl<T: RealNumber, M: BaseMatrix<T>> QR<T, M> {
     pub(crate) fn new(QR: M, tau: Vec<T>) -> QR<T, M> {
-        let mut singular = false;
-        for tau_elem in tau.iter() {
-            if *tau_elem == T::zero() {
-                singular = true;
-                break;
-            }
-        }
+        let (m, n) = QR.shape();
+        let tau_max = tau.iter().fold(T::zero(), |max, t| max.max(t.abs()));
+        let tol = tau_max * T::from_usize(m.max(n)).unwrap() * T::epsilon();
+        let singular = tau.iter().any(|tau_elem| tau_elem.abs() <= tol);
 
         QR { QR, tau, singular }
     }
 
+    /// Returns `true` when the matrix is rank deficient (up to the numerical precision) and `Ax = b` can not be solved with this decomposition.
+    pub fn is_singular(&self) -> bool {
+        self.singular
+    }
+
     /// Get upper triangular matrix.
     pub fn R(&self) -> M {
         let (_, n) = self.QR.shape();
@@ -95,19 +98,29 @@ impl<T: RealNumber, M: BaseMatrix<T>> QR<T, M> {
         Q
     }
 
-    fn solve(&self, mut b: M) -> Result<M, Failed> {
+    pub(crate) fn solve(&self, mut b: M) -> Result<M, Failed> {
         let (m, n) = self.QR.shape();
         let (b_nrows, b_ncols) = b.shape();
 
         if b_nrows != m {
-            panic!(
+            return Err(Failed::shape_mismatch(&format!(
                 "Row dimensions do not agree: A is {} x {}, but B is {} x {}",
                 m, n, b_nrows, b_ncols
-            );
+            )));
+        }
+
+        if m < n {
+            return Err(Failed::shape_mismatch(&format!(
+                "Can't solve an underdetermined system, A is {} x {}",
+                m, n
+            )));
         }
 
         if self.singular {
-            panic!("Matrix is rank deficient.");
+            return Err(Failed::because(
+                FailedError::SolutionFailed,
+                "Matrix is rank deficient.",
+            ));
         }
 
         for k in 0..n {
@@ -227,4 +240,30 @@ mod tests {
         let w = a.qr_solve_mut(b).unwrap();
         assert!(w.approximate_eq(&expected_w, 1e-2));
     }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn qr_solve_rank_deficient() {
+        let a = DenseMatrix::from_2d_array(&[&[1., 2., 3.], &[2., 4., 1.], &[3., 6., 2.]]);
+        let b = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.]]);
+        assert!(a.qr().unwrap().is_singular());
+        assert!(a.qr_solve_mut(b).is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn qr_solve_shape_mismatch() {
+        let a = DenseMatrix::from_2d_array(&[&[1., 2., 3.], &[0., 1., 5.]]);
+        let b = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.]]);
+        assert_eq!(
+            FailedError::ShapeMismatch,
+            a.clone().qr_solve_mut(b).unwrap_err().error()
+        );
+
+        let b = DenseMatrix::from_2d_array(&[&[1.], &[2.]]);
+        assert_eq!(
+            FailedError::ShapeMismatch,
+            a.qr_solve_mut(b).unwrap_err().error()
+        );
+    }
 }
@@ -0,0 +1,196 @@
+//! # Compressed Sparse Row Matrix
+//!
+//! Matrices produced from text or other high-dimensional categorical data are mostly zeros. `CSRMatrix` stores only the non-zero
+//! entries row by row: the column indices and values of row \\(i\\) are stored at positions `indptr[i]..indptr[i + 1]` of `indices` and `data`.
+//! The memory used by the matrix grows with the number of non-zero entries rather than with the number of rows times the number of columns.
+//!
+//! `CSRMatrix` doesn't implement [`Matrix`](../trait.Matrix.html), convert it with `to_dense` before passing it to an estimator that expects a dense matrix.
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::linalg::sparse::CSRMatrix;
+//!
+//! let sparse = CSRMatrix::from_rows(3, vec![vec![(0, 1.0), (2, 2.0)], vec![], vec![(1, 3.0)]]);
+//! assert_eq!(sparse.nnz(), 3);
+//! assert_eq!(sparse.get(0, 2), 2.0);
+//! assert_eq!(
+//!     sparse.to_dense::<DenseMatrix<f64>>(),
+//!     DenseMatrix::from_2d_array(&[&[1.0, 0.0, 2.0], &[0.0, 0.0, 0.0], &[0.0, 3.0, 0.0]])
+//! );
+//! ```
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+
+/// Sparse matrix in compressed sparse row format.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CSRMatrix<T: RealNumber> {
+    ncols: usize,
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    data: Vec<T>,
+}
+
+impl<T: RealNumber> CSRMatrix<T> {
+    /// Creates a matrix from the non-zero entries of every row.
+    /// * `ncols` - number of columns.
+    /// * `rows` - `(column, value)` pairs of every row, columns of a row should be distinct. Entries with zero values are dropped.
+    ///
+    /// Panics when a column index is out of range or repeated within a row.
+    pub fn from_rows(ncols: usize, rows: Vec<Vec<(usize, T)>>) -> CSRMatrix<T> {
+        let mut indptr = Vec::with_capacity(rows.len() + 1);
+        let mut indices = Vec::new();
+        let mut data = Vec::new();
+        indptr.push(0);
+        for mut row in rows {
+            row.sort_by_key(|&(column, _)| column);
+            for (i, &(column, value)) in row.iter().enumerate() {
+                if column >= ncols {
+                    panic!(
+                        "Column index {} is out of range of {} columns",
+                        column, ncols
+                    );
+                }
+                if i > 0 && row[i - 1].0 == column {
+                    panic!("Column index {} is repeated within a row", column);
+                }
+                if value != T::zero() {
+                    indices.push(column);
+                    data.push(value);
+                }
+            }
+            indptr.push(indices.len());
+        }
+        CSRMatrix {
+            ncols,
+            indptr,
+            indices,
+            data,
+        }
+    }
+
+    /// Creates a sparse copy of a dense matrix.
+    pub fn from_dense<M: Matrix<T>>(m: &M) -> CSRMatrix<T> {
+        let (nrows, ncols) = m.shape();
+        let rows = (0..nrows)
+            .map(|i| {
+                (0..ncols)
+                    .map(|j| (j, m.get(i, j)))
+                    .filter(|&(_, v)| v != T::zero())
+                    .collect()
+            })
+            .collect();
+        CSRMatrix::from_rows(ncols, rows)
+    }
+
+    /// Number of rows and columns.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.indptr.len() - 1, self.ncols)
+    }
+
+    /// Number of stored non-zero entries.
+    pub fn nnz(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Value of the entry in row `row` and column `col`.
+    pub fn get(&self, row: usize, col: usize) -> T {
+        let (indices, data) = self.row(row);
+        match indices.binary_search(&col) {
+            Ok(position) => data[position],
+            Err(_) => T::zero(),
+        }
+    }
+
+    /// Column indices and values of the non-zero entries of row `row`, ordered by column.
+    pub fn row(&self, row: usize) -> (&[usize], &[T]) {
+        let range = self.indptr[row]..self.indptr[row + 1];
+        (&self.indices[range.clone()], &self.data[range])
+    }
+
+    /// Row pointers, the entries of row `i` are stored at positions `indptr[i]..indptr[i + 1]`.
+    pub fn indptr(&self) -> &Vec<usize> {
+        &self.indptr
+    }
+
+    /// Column indices of the non-zero entries.
+    pub fn indices(&self) -> &Vec<usize> {
+        &self.indices
+    }
+
+    /// Values of the non-zero entries.
+    pub fn data(&self) -> &Vec<T> {
+        &self.data
+    }
+
+    /// Applies `f` to every non-zero entry, `f` receives the row, the column and the value of the entry.
+    pub fn map_nonzero<F: Fn(usize, usize, T) -> T>(&self, f: F) -> CSRMatrix<T> {
+        let mut result = self.clone();
+        for row in 0..self.indptr.len() - 1 {
+            for position in self.indptr[row]..self.indptr[row + 1] {
+                result.data[position] = f(row, self.indices[position], self.data[position]);
+            }
+        }
+        result
+    }
+
+    /// Dense copy of the matrix.
+    pub fn to_dense<M: Matrix<T>>(&self) -> M {
+        let (nrows, ncols) = self.shape();
+        let mut result = M::zeros(nrows, ncols);
+        for row in 0..nrows {
+            let (indices, data) = self.row(row);
+            for (&col, &value) in indices.iter().zip(data.iter()) {
+                result.set(row, col, value);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn from_rows() {
+        let m = CSRMatrix::from_rows(
+            4,
+            vec![vec![(3, 1.0), (0, 2.0), (1, 0.0)], vec![], vec![(2, 5.0)]],
+        );
+        assert_eq!(m.shape(), (3, 4));
+        assert_eq!(m.nnz(), 3);
+        assert_eq!(m.indptr(), &vec![0, 2, 2, 3]);
+        assert_eq!(m.indices(), &vec![0, 3, 2]);
+        assert_eq!(m.data(), &vec![2.0, 1.0, 5.0]);
+        assert_eq!(m.get(0, 3), 1.0);
+        assert_eq!(m.get(1, 3), 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn column_out_of_range() {
+        CSRMatrix::from_rows(2, vec![vec![(2, 1.0)]]);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn dense_round_trip() {
+        let dense = DenseMatrix::from_2d_array(&[&[0.0, 1.5, 0.0], &[2.0, 0.0, 3.0]]);
+        let sparse = CSRMatrix::from_dense(&dense);
+        assert_eq!(sparse.nnz(), 3);
+        assert_eq!(sparse.to_dense::<DenseMatrix<f64>>(), dense);
+        assert_eq!(
+            sparse
+                .map_nonzero(|row, _, v| v * (row + 1) as f64)
+                .to_dense::<DenseMatrix<f64>>(),
+            DenseMatrix::from_2d_array(&[&[0.0, 1.5, 0.0], &[4.0, 0.0, 6.0]])
+        );
+    }
+}
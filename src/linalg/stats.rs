@@ -5,6 +5,7 @@
 
 use crate::linalg::BaseMatrix;
 use crate::math::num::RealNumber;
+use alloc::vec::Vec;
 
 /// Defines baseline implementations for various statistical functions
 pub trait MatrixStats<T: RealNumber>: BaseMatrix<T> {
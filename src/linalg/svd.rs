@@ -36,7 +36,8 @@
 use crate::error::Failed;
 use crate::linalg::BaseMatrix;
 use crate::math::num::RealNumber;
-use std::fmt::Debug;
+use alloc::vec::Vec;
+use core::fmt::Debug;
 
 /// Results of SVD decomposition
 #[derive(Debug, Clone)]
@@ -0,0 +1,501 @@
+//! # Bayesian Ridge Regression
+//!
+//! Bayesian ridge regression places Gaussian priors on the regression coefficients and on the error term
+//!
+//! \\[p(\beta \vert \lambda) = N(\beta \vert 0, \lambda^{-1}I), \quad p(y \vert X, \beta, \alpha) = N(y \vert X\beta, \alpha^{-1}I)\\]
+//!
+//! where the precision of the coefficients \\(\lambda\\) and the precision of the noise \\(\alpha\\) are estimated from the data by maximizing the marginal likelihood (evidence)
+//! of the training data, with Gamma hyperpriors on both precisions. The posterior mean of the coefficients equals the [ridge regression](../ridge_regression/index.html) estimate with
+//! the penalty \\(\frac{\lambda}{\alpha}\\), so unlike ridge regression the amount of regularization does not need to be tuned.
+//!
+//! The model also estimates the posterior covariance of the coefficients \\(\Sigma = (\alpha X^TX + \lambda I)^{-1}\\), which gives the standard deviation of every prediction
+//!
+//! \\[\sigma(x) = \sqrt{\frac{1}{\alpha} + x^T \Sigma x}\\]
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::linear::bayesian_ridge::*;
+//!
+//! // Longley dataset (https://www.statsmodels.org/stable/datasets/generated/longley.html)
+//! let x = DenseMatrix::from_2d_array(&[
+//!               &[234.289, 235.6, 159.0, 107.608, 1947., 60.323],
+//!               &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+//!               &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+//!               &[284.599, 335.1, 165.0, 110.929, 1950., 61.187],
+//!               &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+//!               &[346.999, 193.2, 359.4, 113.270, 1952., 63.639],
+//!               &[365.385, 187.0, 354.7, 115.094, 1953., 64.989],
+//!               &[363.112, 357.8, 335.0, 116.219, 1954., 63.761],
+//!               &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+//!               &[419.180, 282.2, 285.7, 118.734, 1956., 67.857],
+//!               &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+//!               &[444.546, 468.1, 263.7, 121.950, 1958., 66.513],
+//!               &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+//!               &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+//!               &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+//!               &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+//!          ]);
+//!
+//! let y: Vec<f64> = vec![83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0,
+//!           100.0, 101.2, 104.6, 108.4, 110.8, 112.6, 114.2, 115.7, 116.9];
+//!
+//! let br = BayesianRidgeRegression::fit(&x, &y, Default::default()).unwrap();
+//!
+//! let (y_hat, y_std) = br.predict_with_std(&x).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Bayesian Interpolation", MacKay D. J. C., 1992](https://doi.org/10.1162/neco.1992.4.3.415)
+//! * ["Sparse Bayesian Learning and the Relevance Vector Machine", Tipping M. E., 2001](https://www.jmlr.org/papers/v1/tipping01a.html)
+//! * ["Pattern Recognition and Machine Learning", C.M. Bishop, 3.5 The Evidence Approximation](https://www.microsoft.com/en-us/research/uploads/prod/2006/01/Bishop-Pattern-Recognition-and-Machine-Learning-2006.pdf)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::validation::{check_fit, check_predict};
+
+/// Bayesian Ridge Regression parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct BayesianRidgeRegressionParameters<T: RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of iterations
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Stop when the coefficients change by less than `tol` in the L1 norm.
+    pub tol: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Shape parameter of the Gamma prior over the noise precision \\(\alpha\\).
+    pub alpha_1: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Rate parameter of the Gamma prior over the noise precision \\(\alpha\\).
+    pub alpha_2: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Shape parameter of the Gamma prior over the weight precision \\(\lambda\\).
+    pub lambda_1: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Rate parameter of the Gamma prior over the weight precision \\(\lambda\\).
+    pub lambda_2: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Initial value of the noise precision. Defaults to the inverse of the variance of the target values.
+    pub alpha_init: Option<T>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Initial value of the weight precision. Defaults to 1.
+    pub lambda_init: Option<T>,
+}
+
+/// Bayesian Ridge Regression
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct BayesianRidgeRegression<T: RealNumber, M: Matrix<T>> {
+    coefficients: M,
+    intercept: T,
+    alpha: T,
+    lambda: T,
+    sigma: M,
+    x_mean: M,
+    n_iter: usize,
+}
+
+impl<T: RealNumber> BayesianRidgeRegressionParameters<T> {
+    /// The maximum number of iterations
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Stop when the coefficients change by less than `tol` in the L1 norm.
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Shape parameter of the Gamma prior over the noise precision.
+    pub fn with_alpha_1(mut self, alpha_1: T) -> Self {
+        self.alpha_1 = alpha_1;
+        self
+    }
+    /// Rate parameter of the Gamma prior over the noise precision.
+    pub fn with_alpha_2(mut self, alpha_2: T) -> Self {
+        self.alpha_2 = alpha_2;
+        self
+    }
+    /// Shape parameter of the Gamma prior over the weight precision.
+    pub fn with_lambda_1(mut self, lambda_1: T) -> Self {
+        self.lambda_1 = lambda_1;
+        self
+    }
+    /// Rate parameter of the Gamma prior over the weight precision.
+    pub fn with_lambda_2(mut self, lambda_2: T) -> Self {
+        self.lambda_2 = lambda_2;
+        self
+    }
+    /// Initial value of the noise precision.
+    pub fn with_alpha_init(mut self, alpha_init: T) -> Self {
+        self.alpha_init = Some(alpha_init);
+        self
+    }
+    /// Initial value of the weight precision.
+    pub fn with_lambda_init(mut self, lambda_init: T) -> Self {
+        self.lambda_init = Some(lambda_init);
+        self
+    }
+}
+
+impl<T: RealNumber> Default for BayesianRidgeRegressionParameters<T> {
+    fn default() -> Self {
+        BayesianRidgeRegressionParameters {
+            max_iter: 300,
+            tol: T::from_f64(1e-3).unwrap(),
+            alpha_1: T::from_f64(1e-6).unwrap(),
+            alpha_2: T::from_f64(1e-6).unwrap(),
+            lambda_1: T::from_f64(1e-6).unwrap(),
+            lambda_2: T::from_f64(1e-6).unwrap(),
+            alpha_init: None,
+            lambda_init: None,
+        }
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> PartialEq for BayesianRidgeRegression<T, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.coefficients == other.coefficients
+            && (self.intercept - other.intercept).abs() <= T::epsilon()
+            && (self.alpha - other.alpha).abs() <= T::epsilon()
+            && (self.lambda - other.lambda).abs() <= T::epsilon()
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>>
+    SupervisedEstimator<M, M::RowVector, BayesianRidgeRegressionParameters<T>>
+    for BayesianRidgeRegression<T, M>
+{
+    fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: BayesianRidgeRegressionParameters<T>,
+    ) -> Result<Self, Failed> {
+        BayesianRidgeRegression::fit(x, y, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for BayesianRidgeRegression<T, M> {
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> BayesianRidgeRegression<T, M> {
+    /// Fits Bayesian Ridge Regression to your data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: BayesianRidgeRegressionParameters<T>,
+    ) -> Result<BayesianRidgeRegression<T, M>, Failed> {
+        check_fit(x, y)?;
+        let (n, p) = x.shape();
+        if y.len() != n {
+            return Err(Failed::fit(
+                "Number of rows of X doesn\'t match number of rows of Y",
+            ));
+        }
+        if n < 2 {
+            return Err(Failed::fit("At least 2 observations are required"));
+        }
+        let n_t = T::from_usize(n).unwrap();
+
+        // the intercept is not regularized, center the data instead
+        let x_mean = M::from_row_vector(M::RowVector::from_array(&x.column_mean()));
+        let mut x_centered = x.clone();
+        for i in 0..n {
+            for j in 0..p {
+                x_centered.sub_element_mut(i, j, x_mean.get(0, j));
+            }
+        }
+        let y_mean = y.mean();
+        let mut y_centered = M::from_row_vector(y.sub_scalar(y_mean)).transpose();
+
+        // X^TX = V diag(eigenvalues) V^T
+        let evd = x_centered.ab(true, &x_centered, false).evd_mut(true)?;
+        let eigenvalues: Vec<T> = evd.d.iter().map(|d| d.max(T::zero())).collect();
+        let v = evd.V;
+        let vt_xty = v.ab(true, &x_centered.ab(true, &y_centered, false), false);
+
+        let y_var = y_centered.ab(true, &y_centered, false).get(0, 0) / n_t;
+        let mut alpha = parameters
+            .alpha_init
+            .unwrap_or_else(|| T::one() / (y_var + T::epsilon()));
+        let mut lambda = parameters.lambda_init.unwrap_or_else(T::one);
+        if alpha <= T::zero() || lambda <= T::zero() {
+            return Err(Failed::fit("alpha_init and lambda_init should be > 0"));
+        }
+
+        let posterior_mean = |alpha: T, lambda: T| -> M {
+            let mut scaled = vt_xty.clone();
+            for (j, eigenvalue) in eigenvalues.iter().enumerate() {
+                scaled.set(j, 0, scaled.get(j, 0) / (*eigenvalue + lambda / alpha));
+            }
+            v.matmul(&scaled)
+        };
+
+        let mut coefficients = posterior_mean(alpha, lambda);
+        let mut n_iter = 0;
+        while n_iter < parameters.max_iter {
+            n_iter += 1;
+
+            let residuals = x_centered.matmul(&coefficients).sub(&y_centered);
+            let sse = residuals.ab(true, &residuals, false).get(0, 0);
+            let coefficients_squared = coefficients.ab(true, &coefficients, false).get(0, 0);
+
+            // effective number of parameters
+            let gamma = eigenvalues.iter().fold(T::zero(), |acc, eigenvalue| {
+                acc + alpha * *eigenvalue / (lambda + alpha * *eigenvalue)
+            });
+            lambda = (gamma + T::two() * parameters.lambda_1)
+                / (coefficients_squared + T::two() * parameters.lambda_2);
+            alpha = (n_t - gamma + T::two() * parameters.alpha_1)
+                / (sse + T::two() * parameters.alpha_2);
+
+            let next = posterior_mean(alpha, lambda);
+            let change = (0..p).fold(T::zero(), |acc, j| {
+                acc + (next.get(j, 0) - coefficients.get(j, 0)).abs()
+            });
+            coefficients = next;
+            if change < parameters.tol {
+                break;
+            }
+        }
+
+        // Sigma = V diag(1 / (alpha * eigenvalues + lambda)) V^T
+        let mut v_scaled = v.clone();
+        for (j, eigenvalue) in eigenvalues.iter().enumerate() {
+            let scale = T::one() / (alpha * *eigenvalue + lambda);
+            for i in 0..p {
+                v_scaled.mul_element_mut(i, j, scale);
+            }
+        }
+        let sigma = v_scaled.ab(false, &v, true);
+
+        y_centered = x_mean.matmul(&coefficients);
+        let intercept = y_mean - y_centered.get(0, 0);
+
+        Ok(BayesianRidgeRegression {
+            coefficients,
+            intercept,
+            alpha,
+            lambda,
+            sigma,
+            x_mean,
+            n_iter,
+        })
+    }
+
+    /// Predict the posterior mean of target values from `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
+        self.check_features(x)?;
+        let (nrows, _) = x.shape();
+        let mut y_hat = x.matmul(&self.coefficients);
+        y_hat.add_mut(&M::fill(nrows, 1, self.intercept));
+        Ok(y_hat.transpose().to_row_vector())
+    }
+
+    /// Predict the posterior mean and the standard deviation of the posterior predictive distribution of target values from `x`.
+    /// The standard deviation includes the noise \\(\frac{1}{\alpha}\\) and the uncertainty of the coefficients, but not of the intercept.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_with_std(&self, x: &M) -> Result<(M::RowVector, M::RowVector), Failed> {
+        let y_hat = self.predict(x)?;
+        let (nrows, ncols) = x.shape();
+        let mut y_std = M::RowVector::zeros(nrows);
+        let mut row = M::zeros(1, ncols);
+        for i in 0..nrows {
+            for j in 0..ncols {
+                row.set(0, j, x.get(i, j) - self.x_mean.get(0, j));
+            }
+            let variance = row.matmul(&self.sigma).ab(false, &row, true).get(0, 0);
+            y_std.set(i, (T::one() / self.alpha + variance).sqrt());
+        }
+        Ok((y_hat, y_std))
+    }
+
+    /// Get estimates regression coefficients, the posterior mean of the coefficients.
+    pub fn coefficients(&self) -> &M {
+        &self.coefficients
+    }
+
+    /// Get estimate of intercept
+    pub fn intercept(&self) -> T {
+        self.intercept
+    }
+
+    /// Estimated precision of the noise \\(\alpha\\).
+    pub fn alpha(&self) -> T {
+        self.alpha
+    }
+
+    /// Estimated precision of the coefficients \\(\lambda\\).
+    pub fn lambda(&self) -> T {
+        self.lambda
+    }
+
+    /// Posterior covariance matrix of the coefficients \\(\Sigma\\).
+    pub fn sigma(&self) -> &M {
+        &self.sigma
+    }
+
+    /// Number of iterations of the evidence maximization.
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+
+    fn check_features(&self, x: &M) -> Result<(), Failed> {
+        let (_, ncols) = x.shape();
+        let (num_attributes, _) = self.coefficients.shape();
+        if ncols != num_attributes {
+            return Err(Failed::predict(&format!(
+                "Number of features of x ({}) does not match the number of coefficients ({})",
+                ncols, num_attributes
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::linear::linear_regression::LinearRegression;
+    use crate::linear::ridge_regression::{RidgeRegression, RidgeRegressionParameters};
+
+    fn longley() -> (DenseMatrix<f64>, Vec<f64>) {
+        let x = DenseMatrix::from_2d_array(&[
+            &[234.289, 235.6, 159.0, 107.608, 1947., 60.323],
+            &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+            &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+            &[284.599, 335.1, 165.0, 110.929, 1950., 61.187],
+            &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+            &[346.999, 193.2, 359.4, 113.270, 1952., 63.639],
+            &[365.385, 187.0, 354.7, 115.094, 1953., 64.989],
+            &[363.112, 357.8, 335.0, 116.219, 1954., 63.761],
+            &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+            &[419.180, 282.2, 285.7, 118.734, 1956., 67.857],
+            &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+            &[444.546, 468.1, 263.7, 121.950, 1958., 66.513],
+            &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+            &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+            &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+            &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+        ]);
+        let y: Vec<f64> = vec![
+            83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0, 100.0, 101.2, 104.6, 108.4, 110.8, 112.6,
+            114.2, 115.7, 116.9,
+        ];
+        (x, y)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn bayesian_ridge_is_ridge_with_estimated_penalty() {
+        let (x, y) = longley();
+
+        let br = BayesianRidgeRegression::fit(&x, &y, Default::default()).unwrap();
+
+        // ridge regression without an intercept on the centered data
+        let x_mean = x.column_mean();
+        let mut x_centered = x.clone();
+        for i in 0..16 {
+            for (j, mean) in x_mean.iter().enumerate() {
+                x_centered.sub_element_mut(i, j, *mean);
+            }
+        }
+        let y_mean = y.iter().sum::<f64>() / 16.;
+        let y_centered: Vec<f64> = y.iter().map(|v| v - y_mean).collect();
+        let ridge = RidgeRegression::fit(
+            &x_centered,
+            &y_centered,
+            RidgeRegressionParameters::default()
+                .with_alpha(br.lambda() / br.alpha())
+                .with_normalize(false),
+        )
+        .unwrap();
+
+        assert!(br.coefficients().approximate_eq(ridge.coefficients(), 1e-6));
+        assert!(br.n_iter() < 300);
+
+        let y_hat = br.predict(&x).unwrap();
+        assert!(y
+            .iter()
+            .zip(y_hat.iter())
+            .all(|(&a, &b)| (a - b).abs() <= 5.0));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn noise_and_std() {
+        let values: Vec<Vec<f64>> = (0..200)
+            .map(|i| vec![(i % 20) as f64 / 10., ((i * 7) % 13) as f64 / 6.5])
+            .collect();
+        let x = DenseMatrix::from_2d_vec(&values);
+        // deterministic noise with standard deviation ~0.29
+        let y: Vec<f64> = values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| 3. * v[0] - v[1] + 2. + ((i * 37) % 101) as f64 / 100. - 0.5)
+            .collect();
+
+        let br = BayesianRidgeRegression::fit(&x, &y, Default::default()).unwrap();
+        let lr = LinearRegression::fit(&x, &y, Default::default()).unwrap();
+
+        assert!(br.coefficients().approximate_eq(lr.coefficients(), 1e-2));
+        assert!(((1. / br.alpha()).sqrt() - 0.29).abs() < 0.03);
+
+        let x_new = DenseMatrix::from_2d_array(&[&[1., 1.], &[20., -20.]]);
+        let (y_hat, y_std) = br.predict_with_std(&x_new).unwrap();
+        assert_eq!(y_hat, br.predict(&x_new).unwrap());
+        assert!(y_std[0] >= (1. / br.alpha()).sqrt());
+        // predictions far from the training data are more uncertain
+        assert!(y_std[1] > y_std[0]);
+    }
+
+    #[test]
+    fn wrong_number_of_features() {
+        let (x, y) = longley();
+        let br = BayesianRidgeRegression::fit(&x, &y, Default::default()).unwrap();
+
+        assert!(br
+            .predict(&DenseMatrix::from_2d_array(&[&[1., 2.]]))
+            .is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = longley();
+
+        let br = BayesianRidgeRegression::fit(&x, &y, Default::default()).unwrap();
+
+        let deserialized_br: BayesianRidgeRegression<f64, DenseMatrix<f64>> =
+            serde_json::from_str(&serde_json::to_string(&br).unwrap()).unwrap();
+
+        assert!(br
+            .predict(&x)
+            .unwrap()
+            .approximate_eq(&deserialized_br.predict(&x).unwrap(), 1e-8));
+    }
+}
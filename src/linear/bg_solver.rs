@@ -5,6 +5,7 @@
 use crate::error::Failed;
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
+use alloc::vec::Vec;
 
 pub trait BiconjugateGradientSolver<T: RealNumber, M: Matrix<T>> {
     fn solve_mut(&self, a: &M, b: &M, x: &mut M, tol: T, max_iter: usize) -> Result<T, Failed> {
@@ -54,18 +54,26 @@
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
-use std::fmt::Debug;
+#[cfg(feature = "pmml")]
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Debug;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::api::{Predictor, SupervisedEstimator};
+use crate::api::{FeatureImportance, Predictor, SupervisedEstimator};
 use crate::error::Failed;
 use crate::linalg::BaseVector;
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
+#[cfg(feature = "onnx")]
+use crate::onnx::{self, ToOnnx};
+#[cfg(feature = "pmml")]
+use crate::pmml::{regression, FromPmml, ToPmml};
 
 use crate::linear::lasso_optimizer::InteriorPointOptimizer;
+use crate::validation::{check_fit, check_predict};
 
 /// Elastic net parameters
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -281,6 +289,13 @@ impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for ElasticNet<T, M
     }
 }
 
+impl<T: RealNumber, M: Matrix<T>> FeatureImportance<T> for ElasticNet<T, M> {
+    fn feature_importance(&self) -> Vec<T> {
+        let (p, _) = self.coefficients.shape();
+        (0..p).map(|j| self.coefficients.get(j, 0).abs()).collect()
+    }
+}
+
 impl<T: RealNumber, M: Matrix<T>> ElasticNet<T, M> {
     /// Fits elastic net regression to your data.
     /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
@@ -291,6 +306,7 @@ impl<T: RealNumber, M: Matrix<T>> ElasticNet<T, M> {
         y: &M::RowVector,
         parameters: ElasticNetParameters<T>,
     ) -> Result<ElasticNet<T, M>, Failed> {
+        check_fit(x, y)?;
         let (n, p) = x.shape();
 
         if y.len() != n {
@@ -351,6 +367,7 @@ impl<T: RealNumber, M: Matrix<T>> ElasticNet<T, M> {
     /// Predict target values from `x`
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
         let (nrows, _) = x.shape();
         let mut y_hat = x.matmul(&self.coefficients);
         y_hat.add_mut(&M::fill(nrows, 1, self.intercept));
@@ -410,6 +427,34 @@ impl<T: RealNumber, M: Matrix<T>> ElasticNet<T, M> {
     }
 }
 
+#[cfg(feature = "onnx")]
+impl<T: RealNumber, M: Matrix<T>> ToOnnx for ElasticNet<T, M> {
+    fn to_onnx(&self) -> Result<Vec<u8>, Failed> {
+        Ok(onnx::linear_regressor(&self.coefficients, self.intercept))
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber, M: Matrix<T>> ToPmml for ElasticNet<T, M> {
+    fn to_pmml(&self) -> Result<String, Failed> {
+        Ok(regression::linear_regression(
+            &self.coefficients,
+            self.intercept,
+        ))
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber, M: Matrix<T>> FromPmml for ElasticNet<T, M> {
+    fn from_pmml(pmml: &str) -> Result<Self, Failed> {
+        let (coefficients, intercept) = regression::read_linear_regression(pmml)?;
+        Ok(ElasticNet {
+            coefficients,
+            intercept,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,773 @@
+//! # Generalized Linear Models
+//!
+//! [Linear regression](../linear_regression/index.html) assumes that the response \\(y\\) is normally distributed with constant variance.
+//! Generalized linear models (GLM) relax this assumption: the response follows a distribution from the exponential family and its expected value \\(\mu\\) is related to
+//! the linear predictor \\(\eta\\) through a link function \\(g\\)
+//!
+//! \\[g(\mu) = \eta = \beta_0 + \sum_{i=1}^n \beta_iX_i\\]
+//!
+//! SmartCore supports the Tweedie family of distributions, where the variance of the response is a power \\(p\\) of its mean, \\(Var(y) \propto \mu^p\\):
+//!
+//! | Family   | Power    | Target              | Typical use                           |
+//! |----------|----------|---------------------|---------------------------------------|
+//! | Normal   | 0        | \\(y \in R\\)         | Ordinary least squares                |
+//! | Poisson  | 1        | \\(y \geq 0\\)        | Counts, frequencies                   |
+//! | Tweedie  | (1, 2)   | \\(y \geq 0\\)        | Non-negative values with exact zeros, e.g. insurance claims |
+//! | Gamma    | 2        | \\(y > 0\\)           | Strictly positive, right-skewed values, e.g. durations |
+//! | Tweedie  | \\(p > 2\\) | \\(y > 0\\)        | Strictly positive values with heavier tails |
+//!
+//! The coefficients minimize the mean deviance of the distribution plus an L2 penalty on the coefficients (the intercept is not penalized)
+//!
+//! \\[\hat{\beta} = \underset{\beta}{argmin} \frac{1}{2n} \sum_{i=1}^n d(y_i, \mu_i) + \frac{\alpha}{2} \vert \beta \vert^2\\]
+//!
+//! SmartCore finds the coefficients either with iteratively reweighted least squares (IRLS), a Newton-type method that usually converges in a few iterations,
+//! or with the [L-BFGS](https://en.wikipedia.org/wiki/Limited-memory_BFGS) optimizer, that is cheaper per iteration when the number of features is large.
+//! Full BFGS is also available for a small number of features.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::linear::glm::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[0.0, 1.0],
+//!     &[0.5, 0.0],
+//!     &[1.0, 1.0],
+//!     &[1.5, 0.0],
+//!     &[2.0, 1.0],
+//!     &[2.5, 0.0],
+//!     &[3.0, 1.0],
+//!     &[3.5, 0.0],
+//! ]);
+//! // number of events
+//! let y: Vec<f64> = vec![1., 1., 3., 2., 7., 3., 20., 11.];
+//!
+//! let poisson = GeneralizedLinearModel::fit(&x, &y,
+//!     GeneralizedLinearModelParameters::default().with_family(GLMFamily::Poisson)).unwrap();
+//!
+//! let y_hat = poisson.predict(&x).unwrap(); // expected number of events
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Generalized Linear Models", Nelder J. A., Wedderburn R. W. M., 1972](https://doi.org/10.2307/2344614)
+//! * ["Generalized Linear Models", McCullagh P., Nelder J. A., 2nd edition, 1989](https://doi.org/10.1201/9780203753736)
+//! * ["Exponential Dispersion Models", Jørgensen B., 1987](https://doi.org/10.1111/j.2517-6161.1987.tb01685.x)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::optimization::first_order::bfgs::BFGS;
+use crate::optimization::first_order::lbfgs::LBFGS;
+use crate::optimization::first_order::FirstOrderOptimizer;
+use crate::optimization::line_search::Backtracking;
+use crate::optimization::FunctionOrder;
+use crate::validation::{check_fit, check_predict};
+
+/// Distribution of the response.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub enum GLMFamily {
+    /// Normal distribution, power 0.
+    #[default]
+    Normal,
+    /// Poisson distribution, power 1.
+    Poisson,
+    /// Gamma distribution, power 2.
+    Gamma,
+    /// Tweedie distribution with the power given by the `power` parameter.
+    Tweedie,
+}
+
+/// Link function between the expected value of the response and the linear predictor.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum GLMLink {
+    /// \\(\mu = \eta\\)
+    Identity,
+    /// \\(\mu = e^\eta\\), keeps the predictions positive.
+    Log,
+}
+
+/// Approach to use for estimation of regression coefficients.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub enum GLMSolverName {
+    /// Iteratively reweighted least squares.
+    #[default]
+    IRLS,
+    /// Limited-memory Broyden–Fletcher–Goldfarb–Shanno method, see [LBFGS paper](http://users.iems.northwestern.edu/~nocedal/lbfgsb.html)
+    LBFGS,
+    /// Broyden–Fletcher–Goldfarb–Shanno method with a dense approximation of the inverse Hessian, for a small number of features.
+    BFGS,
+}
+
+/// Generalized Linear Model parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct GeneralizedLinearModelParameters<T: RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Distribution of the response.
+    pub family: GLMFamily,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Power of the Tweedie distribution, `power <= 0` or `power >= 1`. Used with the `Tweedie` family only.
+    pub power: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Link function. Defaults to `Identity` for the `Normal` family and to `Log` otherwise.
+    pub link: Option<GLMLink>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Regularization parameter.
+    pub alpha: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Solver to use for estimation of regression coefficients.
+    pub solver: GLMSolverName,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of iterations
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The tolerance for the optimization
+    pub tol: T,
+}
+
+/// Generalized Linear Model
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct GeneralizedLinearModel<T: RealNumber, M: Matrix<T>> {
+    coefficients: M,
+    intercept: T,
+    power: T,
+    link: GLMLink,
+    converged: bool,
+}
+
+impl<T: RealNumber> GeneralizedLinearModelParameters<T> {
+    /// Distribution of the response.
+    pub fn with_family(mut self, family: GLMFamily) -> Self {
+        self.family = family;
+        self
+    }
+    /// Power of the Tweedie distribution.
+    pub fn with_power(mut self, power: T) -> Self {
+        self.power = power;
+        self
+    }
+    /// Link function.
+    pub fn with_link(mut self, link: GLMLink) -> Self {
+        self.link = Some(link);
+        self
+    }
+    /// Regularization parameter.
+    pub fn with_alpha(mut self, alpha: T) -> Self {
+        self.alpha = alpha;
+        self
+    }
+    /// Solver to use for estimation of regression coefficients.
+    pub fn with_solver(mut self, solver: GLMSolverName) -> Self {
+        self.solver = solver;
+        self
+    }
+    /// The maximum number of iterations
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// The tolerance for the optimization
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+}
+
+impl<T: RealNumber> Default for GeneralizedLinearModelParameters<T> {
+    fn default() -> Self {
+        GeneralizedLinearModelParameters {
+            family: GLMFamily::default(),
+            power: T::from_f64(1.5).unwrap(),
+            link: None,
+            alpha: T::zero(),
+            solver: GLMSolverName::default(),
+            max_iter: 100,
+            tol: T::from_f64(1e-6).unwrap(),
+        }
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> PartialEq for GeneralizedLinearModel<T, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.coefficients == other.coefficients
+            && (self.intercept - other.intercept).abs() <= T::epsilon()
+            && self.power == other.power
+            && self.link == other.link
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>>
+    SupervisedEstimator<M, M::RowVector, GeneralizedLinearModelParameters<T>>
+    for GeneralizedLinearModel<T, M>
+{
+    fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: GeneralizedLinearModelParameters<T>,
+    ) -> Result<Self, Failed> {
+        GeneralizedLinearModel::fit(x, y, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for GeneralizedLinearModel<T, M> {
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> GeneralizedLinearModel<T, M> {
+    /// Fits a Generalized Linear Model to your data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values, should be in the domain of the distribution, see [the table above](index.html).
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: GeneralizedLinearModelParameters<T>,
+    ) -> Result<GeneralizedLinearModel<T, M>, Failed> {
+        check_fit(x, y)?;
+        let (n, num_attributes) = x.shape();
+        if y.len() != n {
+            return Err(Failed::fit(
+                "Number of rows of X doesn\'t match number of rows of Y",
+            ));
+        }
+        if parameters.alpha < T::zero() {
+            return Err(Failed::fit("alpha should be >= 0"));
+        }
+
+        let power = match parameters.family {
+            GLMFamily::Normal => T::zero(),
+            GLMFamily::Poisson => T::one(),
+            GLMFamily::Gamma => T::two(),
+            GLMFamily::Tweedie => parameters.power,
+        };
+        let link = parameters.link.clone().unwrap_or(match parameters.family {
+            GLMFamily::Normal => GLMLink::Identity,
+            _ => GLMLink::Log,
+        });
+        let y = y.to_vec();
+        validate_target(power, &y)?;
+
+        let mean = y.iter().fold(T::zero(), |acc, y_i| acc + *y_i) / T::from_usize(n).unwrap();
+        let mut w0 = M::zeros(num_attributes + 1, 1);
+        w0.set(num_attributes, 0, link.link(mean));
+
+        let objective = Objective {
+            x,
+            y: &y,
+            power,
+            link: &link,
+            alpha: parameters.alpha,
+        };
+
+        let (w, converged) = match parameters.solver {
+            GLMSolverName::IRLS => objective.irls(w0, parameters.max_iter, parameters.tol)?,
+            GLMSolverName::LBFGS | GLMSolverName::BFGS => {
+                let f = |w: &M| -> T { objective.f(&w.transpose()) };
+                let df = |g: &mut M, w: &M| {
+                    let grad = objective.df(&w.transpose());
+                    for j in 0..=num_attributes {
+                        g.set(0, j, grad.get(j, 0));
+                    }
+                };
+                let ls: Backtracking<T> = Backtracking {
+                    order: FunctionOrder::THIRD,
+                    ..Default::default()
+                };
+                let result = if parameters.solver == GLMSolverName::BFGS {
+                    let optimizer: BFGS<T> = BFGS {
+                        max_iter: parameters.max_iter,
+                        g_atol: parameters.tol,
+                        ..Default::default()
+                    };
+                    optimizer.optimize(&f, &df, &w0.transpose(), &ls)
+                } else {
+                    let optimizer: LBFGS<T> = LBFGS {
+                        max_iter: parameters.max_iter,
+                        g_atol: parameters.tol,
+                        ..Default::default()
+                    };
+                    optimizer.optimize(&f, &df, &w0.transpose(), &ls)
+                };
+                (result.x.transpose(), result.status.converged())
+            }
+        };
+
+        Ok(GeneralizedLinearModel {
+            intercept: w.get(num_attributes, 0),
+            coefficients: w.slice(0..num_attributes, 0..1),
+            power,
+            link,
+            converged,
+        })
+    }
+
+    /// Predict the expected value of the response, \\(\mu = g^{-1}(\eta)\\).
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        let mut eta = self.linear_predictor(x)?;
+        for i in 0..eta.len() {
+            eta.set(i, self.link.inverse(eta.get(i)));
+        }
+        Ok(eta)
+    }
+
+    /// Computes the linear predictor \\(\eta\\), e.g. the logarithm of the expected value with the `Log` link.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn linear_predictor(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
+        let (nrows, ncols) = x.shape();
+        if ncols != self.coefficients.shape().0 {
+            return Err(Failed::predict(&format!(
+                "Number of features of x ({}) does not match the number of coefficients ({})",
+                ncols,
+                self.coefficients.shape().0
+            )));
+        }
+        let mut eta = x.matmul(&self.coefficients);
+        eta.add_mut(&M::fill(nrows, 1, self.intercept));
+        Ok(eta.transpose().to_row_vector())
+    }
+
+    /// Computes the mean deviance of the predictions for `x` from `y`.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    /// * `y` - target values
+    pub fn deviance(&self, x: &M, y: &M::RowVector) -> Result<T, Failed> {
+        let y_hat = self.predict(x)?;
+        if y.len() != y_hat.len() {
+            return Err(Failed::predict(&format!(
+                "Size of x ({}) does not match size of y ({})",
+                y_hat.len(),
+                y.len()
+            )));
+        }
+        let total = (0..y.len()).fold(T::zero(), |acc, i| {
+            acc + unit_deviance(self.power, y.get(i), y_hat.get(i))
+        });
+        Ok(total / T::from_usize(y.len()).unwrap())
+    }
+
+    /// Get estimates regression coefficients
+    pub fn coefficients(&self) -> &M {
+        &self.coefficients
+    }
+
+    /// Get estimate of intercept
+    pub fn intercept(&self) -> T {
+        self.intercept
+    }
+
+    /// Whether the solver met its tolerance before reaching the iteration limit.
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+
+    /// Power of the variance function of the fitted distribution.
+    pub fn power(&self) -> T {
+        self.power
+    }
+
+    /// Link function of the fitted model.
+    pub fn link(&self) -> &GLMLink {
+        &self.link
+    }
+}
+
+impl GLMLink {
+    fn link<T: RealNumber>(&self, mu: T) -> T {
+        match self {
+            GLMLink::Identity => mu,
+            GLMLink::Log => mu.ln(),
+        }
+    }
+
+    fn inverse<T: RealNumber>(&self, eta: T) -> T {
+        match self {
+            GLMLink::Identity => eta,
+            GLMLink::Log => eta.exp(),
+        }
+    }
+
+    /// Derivative of the inverse link, \\(\frac{d\mu}{d\eta}\\)
+    fn inverse_derivative<T: RealNumber>(&self, eta: T) -> T {
+        match self {
+            GLMLink::Identity => T::one(),
+            GLMLink::Log => eta.exp(),
+        }
+    }
+}
+
+fn validate_target<T: RealNumber>(power: T, y: &[T]) -> Result<(), Failed> {
+    if power > T::zero() && power < T::one() {
+        return Err(Failed::fit(&format!(
+            "power should be <= 0 or >= 1, got {}",
+            power
+        )));
+    }
+    if power >= T::two() && y.iter().any(|y_i| *y_i <= T::zero()) {
+        return Err(Failed::fit(&format!(
+            "Target values should be > 0 for power {}",
+            power
+        )));
+    }
+    if power > T::zero() && y.iter().any(|y_i| *y_i < T::zero()) {
+        return Err(Failed::fit(&format!(
+            "Target values should be >= 0 for power {}",
+            power
+        )));
+    }
+    Ok(())
+}
+
+/// Deviance of a single observation for the Tweedie distribution with the given power.
+fn unit_deviance<T: RealNumber>(power: T, y: T, mu: T) -> T {
+    if power == T::zero() {
+        (y - mu).square()
+    } else if power == T::one() {
+        if y > T::zero() {
+            T::two() * (y * (y / mu).ln() - y + mu)
+        } else {
+            T::two() * mu
+        }
+    } else if power == T::two() {
+        T::two() * ((mu / y).ln() + y / mu - T::one())
+    } else {
+        let one_minus_p = T::one() - power;
+        let two_minus_p = T::two() - power;
+        T::two()
+            * (y.max(T::zero()).powf(two_minus_p) / (one_minus_p * two_minus_p)
+                - y * mu.powf(one_minus_p) / one_minus_p
+                + mu.powf(two_minus_p) / two_minus_p)
+    }
+}
+
+struct Objective<'a, T: RealNumber, M: Matrix<T>> {
+    x: &'a M,
+    y: &'a [T],
+    power: T,
+    link: &'a GLMLink,
+    alpha: T,
+}
+
+impl<'a, T: RealNumber, M: Matrix<T>> Objective<'a, T, M> {
+    fn eta(&self, w: &M) -> Vec<T> {
+        let (n, p) = self.x.shape();
+        (0..n)
+            .map(|i| (0..p).fold(w.get(p, 0), |acc, j| acc + self.x.get(i, j) * w.get(j, 0)))
+            .collect()
+    }
+
+    fn is_valid(&self, mu: T) -> bool {
+        mu.is_finite() && (self.power <= T::zero() || mu > T::zero())
+    }
+
+    /// Half of the mean deviance plus the penalty, `w` is a column of coefficients followed by the intercept.
+    fn f(&self, w: &M) -> T {
+        let (n, p) = self.x.shape();
+        let mut deviance = T::zero();
+        for (eta, y) in self.eta(w).into_iter().zip(self.y.iter()) {
+            let mu = self.link.inverse(eta);
+            if !self.is_valid(mu) {
+                return T::infinity();
+            }
+            deviance += unit_deviance(self.power, *y, mu);
+        }
+        let penalty = (0..p).fold(T::zero(), |acc, j| acc + w.get(j, 0).square());
+        deviance / (T::two() * T::from_usize(n).unwrap()) + T::half() * self.alpha * penalty
+    }
+
+    fn df(&self, w: &M) -> M {
+        let (n, p) = self.x.shape();
+        let n_t = T::from_usize(n).unwrap();
+        let mut g = M::zeros(p + 1, 1);
+        for (i, (eta, y)) in self.eta(w).into_iter().zip(self.y.iter()).enumerate() {
+            let mu = self.link.inverse(eta);
+            let d_eta = -(*y - mu) / mu.powf(self.power) * self.link.inverse_derivative(eta) / n_t;
+            for j in 0..p {
+                g.add_element_mut(j, 0, d_eta * self.x.get(i, j));
+            }
+            g.add_element_mut(p, 0, d_eta);
+        }
+        for j in 0..p {
+            g.add_element_mut(j, 0, self.alpha * w.get(j, 0));
+        }
+        g
+    }
+
+    /// Returns the coefficients and whether the objective stopped decreasing or the largest change of a coefficient dropped below `tol`.
+    fn irls(&self, mut w: M, max_iter: usize, tol: T) -> Result<(M, bool), Failed> {
+        let (n, p) = self.x.shape();
+        let n_t = T::from_usize(n).unwrap();
+        let mut f_w = self.f(&w);
+        let mut converged = false;
+
+        for _ in 0..max_iter {
+            // weighted least squares for the working response z with weights d_mu^2 / V(mu)
+            let mut xtwx = M::zeros(p + 1, p + 1);
+            let mut xtwz = M::zeros(p + 1, 1);
+            let mut row = vec![T::one(); p + 1];
+            for (i, eta) in self.eta(&w).into_iter().enumerate() {
+                let mu = self.link.inverse(eta);
+                let d_mu = self.link.inverse_derivative(eta);
+                let weight = d_mu.square() / mu.powf(self.power);
+                let z = eta + (self.y[i] - mu) / d_mu;
+                for (j, row_j) in row.iter_mut().enumerate().take(p) {
+                    *row_j = self.x.get(i, j);
+                }
+                for j in 0..=p {
+                    for k in 0..=p {
+                        xtwx.add_element_mut(j, k, weight * row[j] * row[k]);
+                    }
+                    xtwz.add_element_mut(j, 0, weight * row[j] * z);
+                }
+            }
+            for j in 0..p {
+                xtwx.add_element_mut(j, j, n_t * self.alpha);
+            }
+
+            let w_next = xtwx.svd_solve_mut(xtwz)?;
+            let mut step = w_next.sub(&w);
+
+            // halve the step until the objective decreases
+            let mut w_candidate = w.add(&step);
+            let mut f_candidate = self.f(&w_candidate);
+            let mut halvings = 0;
+            while f_candidate > f_w && halvings < 30 {
+                step.mul_scalar_mut(T::half());
+                w_candidate = w.add(&step);
+                f_candidate = self.f(&w_candidate);
+                halvings += 1;
+            }
+            if f_candidate > f_w {
+                converged = true;
+                break;
+            }
+
+            let max_change = (0..=p).fold(T::zero(), |acc, j| acc.max(step.get(j, 0).abs()));
+            w = w_candidate;
+            f_w = f_candidate;
+            if max_change <= tol {
+                converged = true;
+                break;
+            }
+        }
+
+        if !f_w.is_finite() {
+            return Err(Failed::fit(
+                "The predictions are outside the domain of the distribution, try the Log link",
+            ));
+        }
+        Ok((w, converged))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::linear::linear_regression::LinearRegression;
+
+    fn features() -> DenseMatrix<f64> {
+        DenseMatrix::from_2d_vec(
+            &(0..40)
+                .map(|i| vec![(i % 10) as f64 / 10., (i % 4) as f64 / 4.])
+                .collect(),
+        )
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn normal_is_least_squares() {
+        let x = features();
+        let y: Vec<f64> = (0..40)
+            .map(|i| 2. * x.get(i, 0) - x.get(i, 1) + 0.1 * ((i * 7) % 5) as f64)
+            .collect();
+
+        let glm = GeneralizedLinearModel::fit(&x, &y, Default::default()).unwrap();
+        let lr = LinearRegression::fit(&x, &y, Default::default()).unwrap();
+
+        assert!(glm.coefficients().approximate_eq(lr.coefficients(), 1e-6));
+        assert!((glm.intercept() - lr.intercept()).abs() < 1e-6);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn poisson_and_gamma() {
+        let x = features();
+        // exact expected values of a log-linear model
+        let y: Vec<f64> = (0..40)
+            .map(|i| (0.5 + 1.5 * x.get(i, 0) - x.get(i, 1)).exp())
+            .collect();
+
+        for family in [GLMFamily::Poisson, GLMFamily::Gamma, GLMFamily::Tweedie] {
+            for solver in [
+                GLMSolverName::IRLS,
+                GLMSolverName::LBFGS,
+                GLMSolverName::BFGS,
+            ] {
+                let glm = GeneralizedLinearModel::fit(
+                    &x,
+                    &y,
+                    GeneralizedLinearModelParameters::default()
+                        .with_family(family.clone())
+                        .with_solver(solver.clone()),
+                )
+                .unwrap();
+
+                assert!(
+                    (glm.coefficients().get(0, 0) - 1.5).abs() < 1e-3,
+                    "{:?} {:?}",
+                    family,
+                    solver
+                );
+                assert!((glm.coefficients().get(1, 0) + 1.).abs() < 1e-3);
+                assert!((glm.intercept() - 0.5).abs() < 1e-3);
+                assert!(glm.deviance(&x, &y).unwrap() < 1e-5);
+                assert!(glm.converged());
+            }
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn not_converged() {
+        let x = features();
+        let y: Vec<f64> = (0..40)
+            .map(|i| (0.5 + 1.5 * x.get(i, 0) - x.get(i, 1)).exp())
+            .collect();
+
+        for solver in [
+            GLMSolverName::IRLS,
+            GLMSolverName::LBFGS,
+            GLMSolverName::BFGS,
+        ] {
+            let glm = GeneralizedLinearModel::fit(
+                &x,
+                &y,
+                GeneralizedLinearModelParameters::default()
+                    .with_family(GLMFamily::Poisson)
+                    .with_solver(solver.clone())
+                    .with_max_iter(1),
+            )
+            .unwrap();
+
+            assert!(!glm.converged(), "{:?}", solver);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn poisson_counts() {
+        let x = features();
+        let y: Vec<f64> = (0..40)
+            .map(|i| {
+                ((1. + 2. * x.get(i, 0)).exp() + ((i * 13) % 5) as f64 - 2.)
+                    .round()
+                    .max(0.)
+            })
+            .collect();
+
+        let irls = GeneralizedLinearModel::fit(
+            &x,
+            &y,
+            GeneralizedLinearModelParameters::default().with_family(GLMFamily::Poisson),
+        )
+        .unwrap();
+        let lbfgs = GeneralizedLinearModel::fit(
+            &x,
+            &y,
+            GeneralizedLinearModelParameters::default()
+                .with_family(GLMFamily::Poisson)
+                .with_solver(GLMSolverName::LBFGS),
+        )
+        .unwrap();
+
+        assert!(irls
+            .coefficients()
+            .approximate_eq(lbfgs.coefficients(), 1e-3));
+        assert!(irls.predict(&x).unwrap().iter().all(|mu| *mu > 0.));
+        assert!((irls.coefficients().get(0, 0) - 2.).abs() < 0.3);
+
+        // the score equation of the canonical link: predicted and observed totals are equal
+        let total: f64 = y.iter().sum();
+        let predicted: f64 = irls.predict(&x).unwrap().iter().sum();
+        assert!((total - predicted).abs() < 1e-4);
+
+        let penalized = GeneralizedLinearModel::fit(
+            &x,
+            &y,
+            GeneralizedLinearModelParameters::default()
+                .with_family(GLMFamily::Poisson)
+                .with_alpha(1.),
+        )
+        .unwrap();
+        assert!(penalized.coefficients().get(0, 0) < irls.coefficients().get(0, 0));
+    }
+
+    #[test]
+    fn invalid_target() {
+        let x = features();
+        let mut y: Vec<f64> = vec![1.; 40];
+        y[3] = 0.;
+
+        assert!(GeneralizedLinearModel::fit(
+            &x,
+            &y,
+            GeneralizedLinearModelParameters::default().with_family(GLMFamily::Poisson)
+        )
+        .is_ok());
+        assert!(GeneralizedLinearModel::fit(
+            &x,
+            &y,
+            GeneralizedLinearModelParameters::default().with_family(GLMFamily::Gamma)
+        )
+        .is_err());
+        assert!(GeneralizedLinearModel::fit(
+            &x,
+            &y,
+            GeneralizedLinearModelParameters::default()
+                .with_family(GLMFamily::Tweedie)
+                .with_power(0.5)
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let x = features();
+        let y: Vec<f64> = (0..40).map(|i| 1. + x.get(i, 0)).collect();
+
+        let glm = GeneralizedLinearModel::fit(
+            &x,
+            &y,
+            GeneralizedLinearModelParameters::default().with_family(GLMFamily::Gamma),
+        )
+        .unwrap();
+
+        let deserialized_glm: GeneralizedLinearModel<f64, DenseMatrix<f64>> =
+            serde_json::from_str(&serde_json::to_string(&glm).unwrap()).unwrap();
+
+        assert!(glm
+            .predict(&x)
+            .unwrap()
+            .approximate_eq(&deserialized_glm.predict(&x).unwrap(), 1e-12));
+    }
+}
@@ -22,17 +22,25 @@
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
-use std::fmt::Debug;
+#[cfg(feature = "pmml")]
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Debug;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::api::{Predictor, SupervisedEstimator};
+use crate::api::{FeatureImportance, Predictor, SupervisedEstimator};
 use crate::error::Failed;
 use crate::linalg::BaseVector;
 use crate::linalg::Matrix;
 use crate::linear::lasso_optimizer::InteriorPointOptimizer;
 use crate::math::num::RealNumber;
+#[cfg(feature = "onnx")]
+use crate::onnx::{self, ToOnnx};
+#[cfg(feature = "pmml")]
+use crate::pmml::{regression, FromPmml, ToPmml};
+use crate::validation::{check_fit, check_predict};
 
 /// Lasso regression parameters
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -116,6 +124,13 @@ impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for Lasso<T, M> {
     }
 }
 
+impl<T: RealNumber, M: Matrix<T>> FeatureImportance<T> for Lasso<T, M> {
+    fn feature_importance(&self) -> Vec<T> {
+        let (p, _) = self.coefficients.shape();
+        (0..p).map(|j| self.coefficients.get(j, 0).abs()).collect()
+    }
+}
+
 /// Lasso grid search parameters
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
@@ -226,6 +241,7 @@ impl<T: RealNumber, M: Matrix<T>> Lasso<T, M> {
         y: &M::RowVector,
         parameters: LassoParameters<T>,
     ) -> Result<Lasso<T, M>, Failed> {
+        check_fit(x, y)?;
         let (n, p) = x.shape();
 
         if n <= p {
@@ -289,6 +305,7 @@ impl<T: RealNumber, M: Matrix<T>> Lasso<T, M> {
     /// Predict target values from `x`
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
         let (nrows, _) = x.shape();
         let mut y_hat = x.matmul(&self.coefficients);
         y_hat.add_mut(&M::fill(nrows, 1, self.intercept));
@@ -324,6 +341,34 @@ impl<T: RealNumber, M: Matrix<T>> Lasso<T, M> {
     }
 }
 
+#[cfg(feature = "onnx")]
+impl<T: RealNumber, M: Matrix<T>> ToOnnx for Lasso<T, M> {
+    fn to_onnx(&self) -> Result<Vec<u8>, Failed> {
+        Ok(onnx::linear_regressor(&self.coefficients, self.intercept))
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber, M: Matrix<T>> ToPmml for Lasso<T, M> {
+    fn to_pmml(&self) -> Result<String, Failed> {
+        Ok(regression::linear_regression(
+            &self.coefficients,
+            self.intercept,
+        ))
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber, M: Matrix<T>> FromPmml for Lasso<T, M> {
+    fn from_pmml(pmml: &str) -> Result<Self, Failed> {
+        let (coefficients, intercept) = regression::read_linear_regression(pmml)?;
+        Ok(Lasso {
+            coefficients,
+            intercept,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
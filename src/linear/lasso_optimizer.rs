@@ -16,6 +16,7 @@ use crate::linalg::BaseVector;
 use crate::linalg::Matrix;
 use crate::linear::bg_solver::BiconjugateGradientSolver;
 use crate::math::num::RealNumber;
+use alloc::vec::Vec;
 
 pub struct InteriorPointOptimizer<T: RealNumber, M: Matrix<T>> {
     ata: M,
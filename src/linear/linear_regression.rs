@@ -12,9 +12,19 @@
 //! \\[\hat{\beta} = (X^TX)^{-1}X^Ty \\]
 //!
 //! the \\((X^TX)^{-1}\\) term is both computationally expensive and numerically unstable. An alternative approach is to use a matrix decomposition to avoid this operation.
-//! SmartCore uses [SVD](../../linalg/svd/index.html) and [QR](../../linalg/qr/index.html) matrix decomposition to find estimates of \\(\hat{\beta}\\).
+//! SmartCore uses [SVD](../../linalg/svd/index.html), [QR](../../linalg/qr/index.html) and [Cholesky](../../linalg/cholesky/index.html) matrix decomposition to find estimates of \\(\hat{\beta}\\).
+//! The Cholesky decomposition of the normal equations \\(X^TX\\) is the fastest, but squares the condition number of \\(X\\).
 //! The QR decomposition is more computationally efficient and more numerically stable than calculating the normal equation directly,
-//! but does not work for all data matrices. Unlike the QR decomposition, all matrices have an SVD decomposition.
+//! but neither works when \\(X\\) is rank deficient, e.g. when one feature is a linear combination of others.
+//! Unlike the QR decomposition, all matrices have an SVD decomposition, so both the QR and the Cholesky solvers fall back to SVD for rank deficient \\(X\\)
+//! and the minimum norm least squares solution is returned.
+//!
+//! When the model is fitted with `compute_statistics` enabled it also estimates the covariance of the coefficients
+//!
+//! \\[\widehat{Cov}(\hat{\beta}) = \hat{\sigma}^2(X^TX)^{-1}, \quad \hat{\sigma}^2 = \frac{1}{n - p - 1}\sum_{i=1}^n (y_i - \hat{y}_i)^2\\]
+//!
+//! which gives the standard errors, t-statistics and p-values of the coefficients, reported by [`LinearRegression::summary`],
+//! as well as confidence intervals of the mean response and prediction intervals of new observations.
 //!
 //! Example:
 //!
@@ -50,6 +60,14 @@
 //!             with_solver(LinearRegressionSolverName::QR)).unwrap();
 //!
 //! let y_hat = lr.predict(&x).unwrap();
+//!
+//! let lr = LinearRegression::fit(&x, &y,
+//!             LinearRegressionParameters::default().
+//!             with_compute_statistics(true)).unwrap();
+//!
+//! let summary = lr.summary().unwrap();
+//! let p_values = &summary.p_values;
+//! let (lower, upper) = lr.prediction_interval(&x, 0.95).unwrap();
 //! ```
 //!
 //! ## References:
@@ -60,20 +78,33 @@
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
-use std::fmt::Debug;
+#[cfg(feature = "pmml")]
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Debug;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::api::{Predictor, SupervisedEstimator};
+use crate::api::{FeatureImportance, Predictor, SupervisedEstimator, SupervisedEstimatorWeighted};
 use crate::error::Failed;
-use crate::linalg::Matrix;
+use crate::linalg::{BaseVector, Matrix};
+use crate::linear::sample_weight::{scale_rows, validate_sample_weight, weighted_mean};
 use crate::math::num::RealNumber;
+use crate::math::special::{f_cdf, student_t_cdf, student_t_quantile};
+#[cfg(feature = "onnx")]
+use crate::onnx::{self, ToOnnx};
+#[cfg(feature = "pmml")]
+use crate::pmml::{regression, FromPmml, ToPmml};
+use crate::validation::{check_fit, check_predict};
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
-/// Approach to use for estimation of regression coefficients. QR is more efficient but SVD is more stable.
+/// Approach to use for estimation of regression coefficients. QR and Cholesky are more efficient but SVD is more stable.
+/// Both QR and Cholesky fall back to SVD when X is rank deficient.
 pub enum LinearRegressionSolverName {
+    /// Cholesky decomposition of the normal equations, see [Cholesky](../../linalg/cholesky/index.html)
+    Cholesky,
     /// QR decomposition, see [QR](../../linalg/qr/index.html)
     QR,
     #[default]
@@ -88,6 +119,43 @@ pub struct LinearRegressionParameters {
     #[cfg_attr(feature = "serde", serde(default))]
     /// Solver to use for estimation of regression coefficients.
     pub solver: LinearRegressionSolverName,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Compute inference statistics of the coefficients, see [`LinearRegression::summary`].
+    pub compute_statistics: bool,
+}
+
+/// Inference statistics of a fitted linear regression
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct LinearRegressionSummary<T: RealNumber> {
+    /// Estimated regression coefficients
+    pub coefficients: Vec<T>,
+    /// Estimated intercept
+    pub intercept: T,
+    /// Standard errors of the regression coefficients
+    pub std_errors: Vec<T>,
+    /// Standard error of the intercept
+    pub intercept_std_error: T,
+    /// t-statistics of the regression coefficients
+    pub t_values: Vec<T>,
+    /// t-statistic of the intercept
+    pub intercept_t_value: T,
+    /// Two-sided p-values of the regression coefficients, under the null hypothesis that a coefficient is 0
+    pub p_values: Vec<T>,
+    /// Two-sided p-value of the intercept
+    pub intercept_p_value: T,
+    /// Coefficient of determination \\(R^2\\)
+    pub r_squared: T,
+    /// \\(R^2\\) adjusted for the number of features
+    pub adjusted_r_squared: T,
+    /// Estimate of the standard deviation of the error term \\(\hat{\sigma}\\)
+    pub residual_std_error: T,
+    /// Residual degrees of freedom, \\(n - p - 1\\)
+    pub degrees_of_freedom: usize,
+    /// F-statistic of the hypothesis that all regression coefficients are 0
+    pub f_statistic: T,
+    /// p-value of the F-statistic
+    pub f_p_value: T,
 }
 
 /// Linear Regression
@@ -97,6 +165,8 @@ pub struct LinearRegression<T: RealNumber, M: Matrix<T>> {
     coefficients: M,
     intercept: T,
     _solver: LinearRegressionSolverName,
+    summary: Option<LinearRegressionSummary<T>>,
+    unscaled_covariance: Option<M>,
 }
 
 impl LinearRegressionParameters {
@@ -105,6 +175,48 @@ impl LinearRegressionParameters {
         self.solver = solver;
         self
     }
+    /// Compute inference statistics of the coefficients, see [`LinearRegression::summary`].
+    pub fn with_compute_statistics(mut self, compute_statistics: bool) -> Self {
+        self.compute_statistics = compute_statistics;
+        self
+    }
+}
+
+impl<T: RealNumber> LinearRegressionSummary<T> {
+    /// Confidence intervals of the regression coefficients.
+    /// * `level` - confidence level, e.g. 0.95
+    pub fn confidence_intervals(&self, level: T) -> Result<Vec<(T, T)>, Failed> {
+        let t = self.critical_value(level)?;
+        Ok(self
+            .coefficients
+            .iter()
+            .zip(self.std_errors.iter())
+            .map(|(&c, &se)| (c - t * se, c + t * se))
+            .collect())
+    }
+
+    /// Confidence interval of the intercept.
+    /// * `level` - confidence level, e.g. 0.95
+    pub fn intercept_confidence_interval(&self, level: T) -> Result<(T, T), Failed> {
+        let t = self.critical_value(level)?;
+        Ok((
+            self.intercept - t * self.intercept_std_error,
+            self.intercept + t * self.intercept_std_error,
+        ))
+    }
+
+    fn critical_value(&self, level: T) -> Result<T, Failed> {
+        if level <= T::zero() || level >= T::one() {
+            return Err(Failed::predict(&format!(
+                "Confidence level should be in (0, 1), got {}",
+                level
+            )));
+        }
+        Ok(student_t_quantile(
+            T::half() + level / T::two(),
+            T::from_usize(self.degrees_of_freedom).unwrap(),
+        ))
+    }
 }
 
 /// Linear Regression grid search parameters
@@ -144,6 +256,7 @@ impl Iterator for LinearRegressionSearchParametersIterator {
 
         let next = LinearRegressionParameters {
             solver: self.linear_regression_search_parameters.solver[self.current_solver].clone(),
+            ..Default::default()
         };
 
         self.current_solver += 1;
@@ -181,12 +294,33 @@ impl<T: RealNumber, M: Matrix<T>> SupervisedEstimator<M, M::RowVector, LinearReg
     }
 }
 
+impl<T: RealNumber, M: Matrix<T>>
+    SupervisedEstimatorWeighted<M, M::RowVector, LinearRegressionParameters>
+    for LinearRegression<T, M>
+{
+    fn fit_with_weights(
+        x: &M,
+        y: &M::RowVector,
+        sample_weight: &M::RowVector,
+        parameters: LinearRegressionParameters,
+    ) -> Result<Self, Failed> {
+        LinearRegression::fit_with_weights(x, y, sample_weight, parameters)
+    }
+}
+
 impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for LinearRegression<T, M> {
     fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
         self.predict(x)
     }
 }
 
+impl<T: RealNumber, M: Matrix<T>> FeatureImportance<T> for LinearRegression<T, M> {
+    fn feature_importance(&self) -> Vec<T> {
+        let (p, _) = self.coefficients.shape();
+        (0..p).map(|j| self.coefficients.get(j, 0).abs()).collect()
+    }
+}
+
 impl<T: RealNumber, M: Matrix<T>> LinearRegression<T, M> {
     /// Fits Linear Regression to your data.
     /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
@@ -197,8 +331,35 @@ impl<T: RealNumber, M: Matrix<T>> LinearRegression<T, M> {
         y: &M::RowVector,
         parameters: LinearRegressionParameters,
     ) -> Result<LinearRegression<T, M>, Failed> {
+        LinearRegression::fit_weighted(x, y, None, parameters)
+    }
+
+    /// Fits weighted least squares to your data, where each observation has its own weight.
+    /// Coefficients minimize the weighted sum of squared residuals \\(\sum_i w_i(y_i - \hat{y}_i)^2\\),
+    /// e.g. use weights inversely proportional to the variance of the noise of heteroscedastic data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `sample_weight` - non-negative weight of every observation
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit_with_weights(
+        x: &M,
+        y: &M::RowVector,
+        sample_weight: &M::RowVector,
+        parameters: LinearRegressionParameters,
+    ) -> Result<LinearRegression<T, M>, Failed> {
+        let sample_weight = validate_sample_weight(x, sample_weight)?;
+        LinearRegression::fit_weighted(x, y, Some(&sample_weight), parameters)
+    }
+
+    fn fit_weighted(
+        x: &M,
+        y: &M::RowVector,
+        sample_weight: Option<&[T]>,
+        parameters: LinearRegressionParameters,
+    ) -> Result<LinearRegression<T, M>, Failed> {
+        check_fit(x, y)?;
         let y_m = M::from_row_vector(y.clone());
-        let b = y_m.transpose();
+        let mut b = y_m.transpose();
         let (x_nrows, num_attributes) = x.shape();
         let (y_nrows, _) = b.shape();
 
@@ -208,25 +369,160 @@ impl<T: RealNumber, M: Matrix<T>> LinearRegression<T, M> {
             ));
         }
 
-        let a = x.h_stack(&M::ones(x_nrows, 1));
+        let mut a = x.h_stack(&M::ones(x_nrows, 1));
+        if let Some(sample_weight) = sample_weight {
+            scale_rows(&mut a, sample_weight);
+            scale_rows(&mut b, sample_weight);
+        }
 
         let w = match parameters.solver {
-            LinearRegressionSolverName::QR => a.qr_solve_mut(b)?,
-            LinearRegressionSolverName::SVD => a.svd_solve_mut(b)?,
+            LinearRegressionSolverName::Cholesky => Self::normal_equations_solve(&a, &b),
+            LinearRegressionSolverName::QR => a.qr().ok().and_then(|qr| qr.solve(b.clone()).ok()),
+            LinearRegressionSolverName::SVD => None,
+        };
+        // SVD gives the minimum norm solution for rank deficient X
+        let w = match w {
+            Some(w) => w,
+            None => a.svd_solve_mut(b)?,
         };
 
         let wights = w.slice(0..num_attributes, 0..1);
+        let intercept = w.get(num_attributes, 0);
+
+        let (summary, unscaled_covariance) = if parameters.compute_statistics {
+            let ones = vec![T::one(); x_nrows];
+            let sample_weight = sample_weight.unwrap_or(&ones);
+            let (summary, unscaled_covariance) =
+                Self::statistics(x, y, sample_weight, &wights, intercept)?;
+            (Some(summary), Some(unscaled_covariance))
+        } else {
+            (None, None)
+        };
 
         Ok(LinearRegression {
-            intercept: w.get(num_attributes, 0),
+            intercept,
             coefficients: wights,
             _solver: parameters.solver,
+            summary,
+            unscaled_covariance,
         })
     }
 
+    /// Solves \\(X^TXw = X^Ty\\), returns `None` when \\(X^TX\\) is not (numerically) positive definite.
+    fn normal_equations_solve(a: &M, b: &M) -> Option<M> {
+        let (n, p) = a.shape();
+        let x_t_x = a.ab(true, a, false);
+        let x_t_y = a.ab(true, b, false);
+        let cholesky = x_t_x.cholesky().ok()?;
+        let l = cholesky.L();
+        let diagonal_max = (0..p).fold(T::zero(), |max, i| max.max(x_t_x.get(i, i)));
+        let tol = diagonal_max * T::from_usize(n.max(p)).unwrap() * T::epsilon();
+        if (0..p).any(|i| l.get(i, i).square() <= tol) {
+            return None;
+        }
+        cholesky
+            .solve(x_t_y)
+            .ok()
+            .filter(|w| (0..p).all(|i| w.get(i, 0).is_finite()))
+    }
+
+    fn statistics(
+        x: &M,
+        y: &M::RowVector,
+        sample_weight: &[T],
+        coefficients: &M,
+        intercept: T,
+    ) -> Result<(LinearRegressionSummary<T>, M), Failed> {
+        let (nrows, p) = x.shape();
+        // observations with zero weight do not contribute to the fit
+        let n = sample_weight.iter().filter(|w| **w > T::zero()).count();
+        let p_1 = p + 1;
+        if n <= p_1 {
+            return Err(Failed::fit(
+                "Number of rows in X should be > number of columns in X + 1 to compute statistics",
+            ));
+        }
+        let df = n - p_1;
+        let df_t = T::from_usize(df).unwrap();
+
+        // (A^TWA)^-1 = V diag(1 / s^2) V^T, where W^(1/2)A = U diag(s) V^T
+        let mut a = x.h_stack(&M::ones(nrows, 1));
+        scale_rows(&mut a, sample_weight);
+        let svd = a.svd()?;
+        let s_max = svd.s.iter().fold(T::zero(), |m, &s| m.max(s));
+        let tol = s_max * T::from_usize(n).unwrap() * T::epsilon();
+        if svd.s.iter().any(|&s| s <= tol) {
+            return Err(Failed::fit(
+                "X is rank deficient, statistics can not be computed",
+            ));
+        }
+        let mut v_scaled = svd.V.clone();
+        for (j, s) in svd.s.iter().enumerate() {
+            for i in 0..p_1 {
+                v_scaled.mul_element_mut(i, j, T::one() / (*s * *s));
+            }
+        }
+        let unscaled_covariance = v_scaled.ab(false, &svd.V, true);
+
+        let y_mean = weighted_mean(&y.to_vec(), sample_weight);
+        let mut sse = T::zero();
+        let mut sst = T::zero();
+        for (i, w) in sample_weight.iter().enumerate() {
+            let mut y_hat = intercept;
+            for j in 0..p {
+                y_hat += x.get(i, j) * coefficients.get(j, 0);
+            }
+            sse += *w * (y.get(i) - y_hat).square();
+            sst += *w * (y.get(i) - y_mean).square();
+        }
+        let sigma2 = sse / df_t;
+
+        let t_value = |estimate: T, std_error: T| estimate / std_error;
+        let p_value = |t: T| T::two() * (T::one() - student_t_cdf(t.abs(), df_t));
+
+        let std_errors: Vec<T> = (0..p)
+            .map(|j| (sigma2 * unscaled_covariance.get(j, j)).sqrt())
+            .collect();
+        let t_values: Vec<T> = std_errors
+            .iter()
+            .enumerate()
+            .map(|(j, &se)| t_value(coefficients.get(j, 0), se))
+            .collect();
+        let p_values = t_values.iter().map(|&t| p_value(t)).collect();
+        let intercept_std_error = (sigma2 * unscaled_covariance.get(p, p)).sqrt();
+        let intercept_t_value = t_value(intercept, intercept_std_error);
+
+        let r_squared = T::one() - sse / sst;
+        let adjusted_r_squared =
+            T::one() - (T::one() - r_squared) * T::from_usize(n - 1).unwrap() / df_t;
+        let p_t = T::from_usize(p).unwrap();
+        let f_statistic = ((sst - sse) / p_t) / sigma2;
+
+        Ok((
+            LinearRegressionSummary {
+                coefficients: (0..p).map(|j| coefficients.get(j, 0)).collect(),
+                intercept,
+                std_errors,
+                intercept_std_error,
+                t_values,
+                intercept_t_value,
+                p_values,
+                intercept_p_value: p_value(intercept_t_value),
+                r_squared,
+                adjusted_r_squared,
+                residual_std_error: sigma2.sqrt(),
+                degrees_of_freedom: df,
+                f_statistic,
+                f_p_value: T::one() - f_cdf(f_statistic, p_t, df_t),
+            },
+            unscaled_covariance,
+        ))
+    }
+
     /// Predict target values from `x`
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
         let (nrows, _) = x.shape();
         let mut y_hat = x.matmul(&self.coefficients);
         y_hat.add_mut(&M::fill(nrows, 1, self.intercept));
@@ -242,6 +538,104 @@ impl<T: RealNumber, M: Matrix<T>> LinearRegression<T, M> {
     pub fn intercept(&self) -> T {
         self.intercept
     }
+
+    /// Inference statistics of the coefficients, available when the model is fitted with `compute_statistics` enabled.
+    pub fn summary(&self) -> Option<&LinearRegressionSummary<T>> {
+        self.summary.as_ref()
+    }
+
+    /// Confidence interval of the mean response at `x`.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    /// * `level` - confidence level, e.g. 0.95
+    pub fn confidence_interval(
+        &self,
+        x: &M,
+        level: T,
+    ) -> Result<(M::RowVector, M::RowVector), Failed> {
+        self.interval(x, level, false)
+    }
+
+    /// Prediction interval of new observations at `x`, accounts for both the uncertainty of the coefficients and the noise.
+    /// New observations are assumed to have unit weight when the model is fitted with sample weights.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    /// * `level` - confidence level, e.g. 0.95
+    pub fn prediction_interval(
+        &self,
+        x: &M,
+        level: T,
+    ) -> Result<(M::RowVector, M::RowVector), Failed> {
+        self.interval(x, level, true)
+    }
+
+    fn interval(
+        &self,
+        x: &M,
+        level: T,
+        prediction: bool,
+    ) -> Result<(M::RowVector, M::RowVector), Failed> {
+        let (summary, unscaled_covariance) =
+            match (&self.summary, &self.unscaled_covariance) {
+                (Some(summary), Some(unscaled_covariance)) => (summary, unscaled_covariance),
+                _ => return Err(Failed::predict(
+                    "Statistics were not computed, fit the model with compute_statistics enabled",
+                )),
+            };
+        let t = summary.critical_value(level)?;
+        let sigma2 = summary.residual_std_error.square();
+
+        let (nrows, ncols) = x.shape();
+        let a = x.h_stack(&M::ones(nrows, 1));
+        let a_cov = a.matmul(unscaled_covariance);
+        let y_hat = self.predict(x)?;
+        let mut lower = M::RowVector::zeros(nrows);
+        let mut upper = M::RowVector::zeros(nrows);
+        for i in 0..nrows {
+            let mut leverage = T::zero();
+            for j in 0..=ncols {
+                leverage += a_cov.get(i, j) * a.get(i, j);
+            }
+            let variance = if prediction {
+                sigma2 * (T::one() + leverage)
+            } else {
+                sigma2 * leverage
+            };
+            let half_width = t * variance.sqrt();
+            lower.set(i, y_hat.get(i) - half_width);
+            upper.set(i, y_hat.get(i) + half_width);
+        }
+        Ok((lower, upper))
+    }
+}
+
+#[cfg(feature = "onnx")]
+impl<T: RealNumber, M: Matrix<T>> ToOnnx for LinearRegression<T, M> {
+    fn to_onnx(&self) -> Result<Vec<u8>, Failed> {
+        Ok(onnx::linear_regressor(&self.coefficients, self.intercept))
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber, M: Matrix<T>> ToPmml for LinearRegression<T, M> {
+    fn to_pmml(&self) -> Result<String, Failed> {
+        Ok(regression::linear_regression(
+            &self.coefficients,
+            self.intercept,
+        ))
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber, M: Matrix<T>> FromPmml for LinearRegression<T, M> {
+    fn from_pmml(pmml: &str) -> Result<Self, Failed> {
+        let (coefficients, intercept) = regression::read_linear_regression(pmml)?;
+        Ok(LinearRegression {
+            coefficients,
+            intercept,
+            _solver: LinearRegressionSolverName::default(),
+            summary: None,
+            unscaled_covariance: None,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -295,6 +689,7 @@ mod tests {
             &y,
             LinearRegressionParameters {
                 solver: LinearRegressionSolverName::QR,
+                ..Default::default()
             },
         )
         .and_then(|lr| lr.predict(&x))
@@ -314,6 +709,182 @@ mod tests {
             .all(|(&a, &b)| (a - b).abs() <= 5.0));
     }
 
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn solvers() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 2.],
+            &[2., 1.],
+            &[3., 5.],
+            &[4., 3.],
+            &[5., 4.],
+            &[6., 8.],
+            &[7., 6.],
+        ]);
+        let y: Vec<f64> = vec![3.1, 4.2, 8.3, 7.9, 9.6, 14.8, 13.2];
+
+        let svd = LinearRegression::fit(&x, &y, Default::default()).unwrap();
+        for solver in [
+            LinearRegressionSolverName::Cholesky,
+            LinearRegressionSolverName::QR,
+        ] {
+            let lr = LinearRegression::fit(
+                &x,
+                &y,
+                LinearRegressionParameters::default().with_solver(solver),
+            )
+            .unwrap();
+            assert!(lr.coefficients().approximate_eq(svd.coefficients(), 1e-8));
+            assert!((lr.intercept() - svd.intercept()).abs() < 1e-8);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn rank_deficient() {
+        // the third feature is the sum of the first two
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 2., 3.],
+            &[2., 1., 3.],
+            &[3., 5., 8.],
+            &[4., 3., 7.],
+            &[5., 4., 9.],
+            &[6., 8., 14.],
+        ]);
+        let y: Vec<f64> = vec![6., 5., 14., 11., 14., 23.];
+
+        for solver in [
+            LinearRegressionSolverName::Cholesky,
+            LinearRegressionSolverName::QR,
+            LinearRegressionSolverName::SVD,
+        ] {
+            let lr = LinearRegression::fit(
+                &x,
+                &y,
+                LinearRegressionParameters::default().with_solver(solver),
+            )
+            .unwrap();
+            let y_hat = lr.predict(&x).unwrap();
+            assert!(y
+                .iter()
+                .zip(y_hat.iter())
+                .all(|(&a, &b)| (a - b).abs() <= 1e-6));
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn ols_statistics() {
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.], &[5.]]);
+        let y: Vec<f64> = vec![2., 4., 5., 4., 5.];
+
+        let lr = LinearRegression::fit(&x, &y, Default::default()).unwrap();
+        assert!(lr.summary().is_none());
+        assert!(lr.prediction_interval(&x, 0.95).is_err());
+
+        let lr = LinearRegression::fit(
+            &x,
+            &y,
+            LinearRegressionParameters::default().with_compute_statistics(true),
+        )
+        .unwrap();
+        let summary = lr.summary().unwrap();
+
+        assert!((summary.coefficients[0] - 0.6).abs() < 1e-8);
+        assert!((summary.intercept - 2.2).abs() < 1e-8);
+        assert!((summary.std_errors[0] - 0.08f64.sqrt()).abs() < 1e-8);
+        assert!((summary.intercept_std_error - 0.88f64.sqrt()).abs() < 1e-8);
+        assert!((summary.t_values[0] - 2.1213203435).abs() < 1e-8);
+        assert!((summary.p_values[0] - 0.1240270627).abs() < 1e-8);
+        assert!((summary.intercept_p_value - 0.1007434561).abs() < 1e-8);
+        assert!((summary.r_squared - 0.6).abs() < 1e-8);
+        assert!((summary.adjusted_r_squared - 0.4666666667).abs() < 1e-8);
+        assert!((summary.residual_std_error - 0.8f64.sqrt()).abs() < 1e-8);
+        assert_eq!(summary.degrees_of_freedom, 3);
+        assert!((summary.f_statistic - 4.5).abs() < 1e-8);
+        assert!((summary.f_p_value - summary.p_values[0]).abs() < 1e-8);
+
+        let intervals = summary.confidence_intervals(0.95).unwrap();
+        assert!((intervals[0].0 + 0.3001317).abs() < 1e-6);
+        assert!((intervals[0].1 - 1.5001317).abs() < 1e-6);
+        assert!(summary.confidence_intervals(1.5).is_err());
+
+        let x_new = DenseMatrix::from_2d_array(&[&[3.]]);
+        let (lower, upper) = lr.confidence_interval(&x_new, 0.95).unwrap();
+        assert!((lower[0] - (4. - 1.2729785)).abs() < 1e-6);
+        assert!((upper[0] - (4. + 1.2729785)).abs() < 1e-6);
+        let (lower, upper) = lr.prediction_interval(&x_new, 0.95).unwrap();
+        assert!((lower[0] - (4. - 3.1181478)).abs() < 1e-6);
+        assert!((upper[0] - (4. + 3.1181478)).abs() < 1e-6);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn wls_fit_predict() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 2.],
+            &[2., 1.],
+            &[3., 5.],
+            &[4., 3.],
+            &[5., 4.],
+            &[6., 8.],
+            &[7., 6.],
+        ]);
+        let y: Vec<f64> = vec![3.1, 4.2, 8.3, 7.9, 9.6, 14.8, 13.2];
+        let sample_weight = vec![1., 2., 0., 1., 3., 1., 2.];
+
+        // integer weights are equivalent to repeated observations
+        let x_repeated = DenseMatrix::from_2d_array(&[
+            &[1., 2.],
+            &[2., 1.],
+            &[2., 1.],
+            &[4., 3.],
+            &[5., 4.],
+            &[5., 4.],
+            &[5., 4.],
+            &[6., 8.],
+            &[7., 6.],
+            &[7., 6.],
+        ]);
+        let y_repeated: Vec<f64> = vec![3.1, 4.2, 4.2, 7.9, 9.6, 9.6, 9.6, 14.8, 13.2, 13.2];
+
+        for solver in [
+            LinearRegressionSolverName::QR,
+            LinearRegressionSolverName::SVD,
+        ] {
+            let parameters = LinearRegressionParameters::default().with_solver(solver);
+            let wls =
+                LinearRegression::fit_with_weights(&x, &y, &sample_weight, parameters.clone())
+                    .unwrap();
+            let ols = LinearRegression::fit(&x_repeated, &y_repeated, parameters).unwrap();
+
+            assert!(wls.coefficients().approximate_eq(ols.coefficients(), 1e-8));
+            assert!((wls.intercept() - ols.intercept()).abs() < 1e-8);
+        }
+
+        let wls = LinearRegression::fit_with_weights(
+            &x,
+            &y,
+            &sample_weight,
+            LinearRegressionParameters::default().with_compute_statistics(true),
+        )
+        .unwrap();
+        let summary = wls.summary().unwrap();
+        assert_eq!(summary.degrees_of_freedom, 3);
+        assert!(summary.r_squared > 0.9 && summary.r_squared < 1.);
+
+        assert!(LinearRegression::fit_with_weights(
+            &x,
+            &y,
+            &vec![1., 1., -1., 1., 1., 1., 1.],
+            Default::default()
+        )
+        .is_err());
+        assert!(
+            LinearRegression::fit_with_weights(&x, &y, &vec![1., 1.], Default::default()).is_err()
+        );
+    }
+
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     #[test]
     #[cfg(feature = "serde")]
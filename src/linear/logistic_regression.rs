@@ -5,7 +5,8 @@
 //!
 //! \\[ Pr(y=1) \approx \frac{e^{\beta_0 + \sum_{i=1}^n \beta_iX_i}}{1 + e^{\beta_0 + \sum_{i=1}^n \beta_iX_i}} \\]
 //!
-//! SmartCore uses [limited memory BFGS](https://en.wikipedia.org/wiki/Limited-memory_BFGS) method to find estimates of regression coefficients, \\(\beta\\)
+//! SmartCore uses [limited memory BFGS](https://en.wikipedia.org/wiki/Limited-memory_BFGS) method to find estimates of regression coefficients, \\(\beta\\),
+//! or full BFGS when it is selected with [`LogisticRegressionSolverName`](enum.LogisticRegressionSolverName.html).
 //!
 //! Example:
 //!
@@ -52,27 +53,43 @@
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
-use std::cmp::Ordering;
-use std::fmt::Debug;
+#[cfg(feature = "pmml")]
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Debug;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::api::{Predictor, SupervisedEstimator};
+use crate::api::{
+    FeatureImportance, Predictor, PredictorProba, ProbabilisticClassifier, SupervisedEstimator,
+};
 use crate::error::Failed;
+#[cfg(feature = "pmml")]
+use crate::linalg::BaseVector;
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
+#[cfg(feature = "onnx")]
+use crate::onnx::{self, Attribute, Operator, ToOnnx};
+use crate::optimization::first_order::bfgs::BFGS;
 use crate::optimization::first_order::lbfgs::LBFGS;
 use crate::optimization::first_order::{FirstOrderOptimizer, OptimizerResult};
 use crate::optimization::line_search::Backtracking;
 use crate::optimization::FunctionOrder;
+#[cfg(feature = "pmml")]
+use crate::pmml::{regression, FromPmml, ToPmml};
+use crate::validation::{check_fit, check_predict};
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, Eq, PartialEq)]
-/// Solver options for Logistic regression. Right now only LBFGS solver is supported.
+/// Solver options for Logistic regression.
 pub enum LogisticRegressionSolverName {
     /// Limited-memory Broyden–Fletcher–Goldfarb–Shanno method, see [LBFGS paper](http://users.iems.northwestern.edu/~nocedal/lbfgsb.html)
     LBFGS,
+    /// Broyden–Fletcher–Goldfarb–Shanno method with a dense approximation of the inverse Hessian.
+    /// Often needs fewer iterations than LBFGS but its memory grows with the square of the number of coefficients, use it with few features and classes.
+    BFGS,
 }
 
 impl Default for LogisticRegressionSolverName {
@@ -175,6 +192,7 @@ pub struct LogisticRegression<T: RealNumber, M: Matrix<T>> {
     classes: Vec<T>,
     num_attributes: usize,
     num_classes: usize,
+    converged: bool,
 }
 
 trait ObjectiveFunction<T: RealNumber, M: Matrix<T>> {
@@ -386,6 +404,27 @@ impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for LogisticRegress
     }
 }
 
+impl<T: RealNumber, M: Matrix<T>> PredictorProba<M> for LogisticRegression<T, M> {
+    fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.predict_proba(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> ProbabilisticClassifier<T, M> for LogisticRegression<T, M> {
+    fn classes(&self) -> &Vec<T> {
+        self.classes()
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> FeatureImportance<T> for LogisticRegression<T, M> {
+    fn feature_importance(&self) -> Vec<T> {
+        let (k, p) = self.coefficients.shape();
+        (0..p)
+            .map(|j| (0..k).fold(T::zero(), |s, i| s + self.coefficients.get(i, j).abs()))
+            .collect()
+    }
+}
+
 impl<T: RealNumber, M: Matrix<T>> LogisticRegression<T, M> {
     /// Fits Logistic Regression to your data.
     /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
@@ -396,6 +435,7 @@ impl<T: RealNumber, M: Matrix<T>> LogisticRegression<T, M> {
         y: &M::RowVector,
         parameters: LogisticRegressionParameters<T>,
     ) -> Result<LogisticRegression<T, M>, Failed> {
+        check_fit(x, y)?;
         let y_m = M::from_row_vector(y.clone());
         let (x_nrows, num_attributes) = x.shape();
         let (_, y_nrows) = y_m.shape();
@@ -431,7 +471,7 @@ impl<T: RealNumber, M: Matrix<T>> LogisticRegression<T, M> {
                     alpha: parameters.alpha,
                 };
 
-                let result = LogisticRegression::minimize(x0, objective);
+                let result = LogisticRegression::minimize(x0, objective, &parameters.solver);
 
                 let weights = result.x;
 
@@ -441,6 +481,7 @@ impl<T: RealNumber, M: Matrix<T>> LogisticRegression<T, M> {
                     classes,
                     num_attributes,
                     num_classes: k,
+                    converged: result.status.converged(),
                 })
             }
             Ordering::Greater => {
@@ -453,7 +494,7 @@ impl<T: RealNumber, M: Matrix<T>> LogisticRegression<T, M> {
                     alpha: parameters.alpha,
                 };
 
-                let result = LogisticRegression::minimize(x0, objective);
+                let result = LogisticRegression::minimize(x0, objective, &parameters.solver);
                 let weights = result.x.reshape(k, num_attributes + 1);
 
                 Ok(LogisticRegression {
@@ -462,6 +503,7 @@ impl<T: RealNumber, M: Matrix<T>> LogisticRegression<T, M> {
                     classes,
                     num_attributes,
                     num_classes: k,
+                    converged: result.status.converged(),
                 })
             }
         }
@@ -470,6 +512,7 @@ impl<T: RealNumber, M: Matrix<T>> LogisticRegression<T, M> {
     /// Predict class labels for samples in `x`.
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
         let n = x.shape().0;
         let mut result = M::zeros(1, n);
         if self.num_classes == 2 {
@@ -501,6 +544,43 @@ impl<T: RealNumber, M: Matrix<T>> LogisticRegression<T, M> {
         Ok(result.to_row_vector())
     }
 
+    /// Predict class probabilities for samples in `x`, with the logistic function for two classes and the softmax function otherwise.
+    /// Returns _KxC_ matrix where _C_ is the number of classes, columns follow the order of sorted class labels.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        check_predict(x)?;
+        let n = x.shape().0;
+        let mut result = M::zeros(n, self.num_classes);
+        if self.num_classes == 2 {
+            let y_hat: Vec<T> = x.ab(false, &self.coefficients, true).get_col_as_vec(0);
+            let intercept = self.intercept.get(0, 0);
+            for (i, y_hat_i) in y_hat.iter().enumerate() {
+                let p = (*y_hat_i + intercept).sigmoid();
+                result.set(i, 0, T::one() - p);
+                result.set(i, 1, p);
+            }
+        } else {
+            let y_hat = x.matmul(&self.coefficients.transpose());
+            for r in 0..n {
+                let scores: Vec<T> = (0..self.num_classes)
+                    .map(|c| y_hat.get(r, c) + self.intercept.get(c, 0))
+                    .collect();
+                let max = scores.iter().fold(T::neg_infinity(), |a, b| a.max(*b));
+                let exp: Vec<T> = scores.iter().map(|s| (*s - max).exp()).collect();
+                let z = exp.iter().copied().sum::<T>();
+                for (c, e) in exp.iter().enumerate() {
+                    result.set(r, c, *e / z);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Class labels, sorted.
+    pub fn classes(&self) -> &Vec<T> {
+        &self.classes
+    }
+
     /// Get estimates regression coefficients
     pub fn coefficients(&self) -> &M {
         &self.coefficients
@@ -511,7 +591,16 @@ impl<T: RealNumber, M: Matrix<T>> LogisticRegression<T, M> {
         &self.intercept
     }
 
-    fn minimize(x0: M, objective: impl ObjectiveFunction<T, M>) -> OptimizerResult<T, M> {
+    /// Whether the solver met its tolerance before reaching the iteration limit. Models read from PMML are assumed to have converged.
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+
+    fn minimize(
+        x0: M,
+        objective: impl ObjectiveFunction<T, M>,
+        solver: &LogisticRegressionSolverName,
+    ) -> OptimizerResult<T, M> {
         let f = |w: &M| -> T { objective.f(w) };
 
         let df = |g: &mut M, w: &M| objective.df(g, w);
@@ -520,9 +609,95 @@ impl<T: RealNumber, M: Matrix<T>> LogisticRegression<T, M> {
             order: FunctionOrder::THIRD,
             ..Default::default()
         };
-        let optimizer: LBFGS<T> = Default::default();
+        match solver {
+            LogisticRegressionSolverName::LBFGS => {
+                let optimizer: LBFGS<T> = Default::default();
+                optimizer.optimize(&f, &df, &x0, &ls)
+            }
+            LogisticRegressionSolverName::BFGS => {
+                let optimizer: BFGS<T> = Default::default();
+                optimizer.optimize(&f, &df, &x0, &ls)
+            }
+        }
+    }
+}
+
+/// Binary models are exported with the scores of both classes, the negated score of the second class is the score of the first class.
+#[cfg(feature = "onnx")]
+impl<T: RealNumber, M: Matrix<T>> ToOnnx for LogisticRegression<T, M> {
+    fn to_onnx(&self) -> Result<Vec<u8>, Failed> {
+        let labels = onnx::class_labels(&self.classes)?;
+        let (n_rows, _) = self.coefficients.shape();
+        let mut coefficients = Vec::new();
+        let mut intercepts = Vec::new();
+        if self.num_classes == 2 {
+            let w = self.coefficients.get_row_as_vec(0);
+            coefficients.extend(w.iter().map(|w| -*w));
+            coefficients.extend(w);
+            intercepts.push(-self.intercept.get(0, 0));
+            intercepts.push(self.intercept.get(0, 0));
+        } else {
+            for row in 0..n_rows {
+                coefficients.extend(self.coefficients.get_row_as_vec(row));
+                intercepts.push(self.intercept.get(row, 0));
+            }
+        }
+        let post_transform = if self.num_classes == 2 {
+            "LOGISTIC"
+        } else {
+            "SOFTMAX"
+        };
+
+        Ok(Operator::new("LinearClassifier")
+            .with("classlabels_ints", Attribute::Ints(labels))
+            .with(
+                "coefficients",
+                Attribute::Floats(onnx::floats(coefficients)),
+            )
+            .with("intercepts", Attribute::Floats(onnx::floats(intercepts)))
+            .with("multi_class", Attribute::Int(0))
+            .with("post_transform", onnx::post_transform(post_transform))
+            .classifier_model(self.num_attributes, self.num_classes))
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber, M: Matrix<T>> ToPmml for LogisticRegression<T, M> {
+    fn to_pmml(&self) -> Result<String, Failed> {
+        let (n_rows, _) = self.coefficients.shape();
+        let coefficients: Vec<Vec<T>> = (0..n_rows)
+            .map(|row| self.coefficients.get_row_as_vec(row))
+            .collect();
+        let intercepts: Vec<T> = (0..n_rows).map(|row| self.intercept.get(row, 0)).collect();
+        Ok(regression::logistic_regression(
+            &self.classes,
+            &coefficients,
+            &intercepts,
+        ))
+    }
+}
 
-        optimizer.optimize(&f, &df, &x0, &ls)
+#[cfg(feature = "pmml")]
+impl<T: RealNumber, M: Matrix<T>> FromPmml for LogisticRegression<T, M> {
+    fn from_pmml(pmml: &str) -> Result<Self, Failed> {
+        let (classes, coefficients, intercepts) = regression::read_logistic_regression(pmml)?;
+        let num_attributes = coefficients[0].len();
+        let coefficients = M::from_row_vectors(
+            coefficients
+                .iter()
+                .map(|row| M::RowVector::from_array(row))
+                .collect(),
+        )
+        .unwrap();
+        let intercept = M::from_row_vector(M::RowVector::from_array(&intercepts)).transpose();
+        Ok(LogisticRegression {
+            coefficients,
+            intercept,
+            num_classes: classes.len(),
+            classes,
+            num_attributes,
+            converged: true,
+        })
     }
 }
 
@@ -759,6 +934,70 @@ mod tests {
         assert!(lr_reg.coefficients().abs().sum() < lr.coefficients().abs().sum());
     }
 
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn lr_fit_predict_bfgs() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., -5.],
+            &[2., 5.],
+            &[3., -2.],
+            &[1., 2.],
+            &[2., 0.],
+            &[6., -5.],
+            &[7., 5.],
+            &[6., -2.],
+            &[7., 2.],
+            &[6., 0.],
+            &[8., -5.],
+            &[9., 5.],
+            &[10., -2.],
+            &[8., 2.],
+            &[9., 0.],
+        ]);
+        let multiclass: Vec<f64> = vec![0., 0., 1., 1., 2., 1., 1., 0., 0., 2., 1., 1., 0., 0., 1.];
+        let binary: Vec<f64> = multiclass.iter().map(|y| y.min(1.)).collect();
+
+        for y in [binary, multiclass] {
+            let parameters = LogisticRegressionParameters::default().with_alpha(1.0);
+
+            let lbfgs = LogisticRegression::fit(&x, &y, parameters.clone()).unwrap();
+            let bfgs = LogisticRegression::fit(
+                &x,
+                &y,
+                parameters.with_solver(LogisticRegressionSolverName::BFGS),
+            )
+            .unwrap();
+
+            assert!(lbfgs.converged() && bfgs.converged());
+            assert!(bfgs
+                .coefficients()
+                .approximate_eq(lbfgs.coefficients(), 1e-4));
+            assert!(bfgs.intercept().approximate_eq(lbfgs.intercept(), 1e-4));
+            assert_eq!(lbfgs.predict(&x).unwrap(), bfgs.predict(&x).unwrap());
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn lr_predict_proba() {
+        for k in [2, 3].iter() {
+            let blobs = make_blobs(20, 4, *k);
+            let x = DenseMatrix::from_vec(20, 4, &blobs.data);
+            let y = blobs.target;
+
+            let lr = LogisticRegression::fit(&x, &y, Default::default()).unwrap();
+            let proba = lr.predict_proba(&x).unwrap();
+            let y_hat = lr.predict(&x).unwrap();
+
+            assert_eq!(proba.shape(), (20, *k));
+            for (i, class_index) in proba.argmax().iter().enumerate() {
+                let total: f32 = (0..*k).map(|j| proba.get(i, j)).sum();
+                assert!((total - 1.).abs() < 1e-5);
+                assert_eq!(lr.classes()[*class_index], y_hat[i]);
+            }
+        }
+    }
+
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     #[test]
     #[cfg(feature = "serde")]
@@ -0,0 +1,599 @@
+//! # Passive-Aggressive Classifier
+//!
+//! Passive-Aggressive algorithms are online linear classifiers. Like the [perceptron](../perceptron/index.html) they update the coefficients one observation at a time,
+//! but instead of a fixed learning rate every update makes the smallest change to the coefficients that classifies the observation correctly with a margin of at least 1:
+//! the algorithm is passive when the hinge loss \\(\ell = \max(0, 1 - y_i(w^Tx_i + b))\\) is zero and aggressive otherwise.
+//!
+//! \\[w \leftarrow w + \tau y_i x_i, \quad b \leftarrow b + \tau y_i\\]
+//!
+//! Aggressive updates overfit noisy observations, so the step size \\(\tau\\) is limited by the aggressiveness parameter \\(C\\):
+//!
+//! * PA-I, hinge loss: \\(\tau = \min\left(C, \frac{\ell}{\lVert x_i \rVert^2}\right)\\)
+//! * PA-II, squared hinge loss: \\(\tau = \frac{\ell}{\lVert x_i \rVert^2 + \frac{1}{2C}}\\)
+//!
+//! where the norm includes the constant feature of the intercept when it is fitted. Smaller values of \\(C\\) make the classifier more robust to label noise.
+//! Classification problems with more than two classes are solved one-vs-all: one binary classifier is fitted for every class.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::linear::passive_aggressive::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[-1.0, -1.2],
+//!     &[-0.8, -1.0],
+//!     &[-1.2, -0.7],
+//!     &[-0.9, -0.8],
+//!     &[1.0, 1.1],
+//!     &[0.8, 1.3],
+//!     &[1.2, 0.9],
+//!     &[1.1, 1.0],
+//! ]);
+//! let y: Vec<f64> = vec![0., 0., 0., 0., 1., 1., 1., 1.];
+//!
+//! let mut pa = PassiveAggressiveClassifier::fit(
+//!     &x,
+//!     &y,
+//!     PassiveAggressiveClassifierParameters::default().with_c(0.1),
+//! )
+//! .unwrap();
+//! let y_hat = pa.predict(&x).unwrap();
+//!
+//! // continue training when more data arrives
+//! pa.partial_fit(&x, &y).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Online Passive-Aggressive Algorithms", Crammer K., Dekel O., Keshet J., Shalev-Shwartz S., Singer Y., 2006](https://jmlr.org/papers/v7/crammer06a.html)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use rand::seq::SliceRandom;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{OnlineEstimator, Predictor, SupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::rand::get_rng_impl;
+use crate::validation::{check_fit, check_predict};
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+/// Loss minimized by the updates, determines the step size.
+pub enum PassiveAggressiveLoss {
+    #[default]
+    /// Hinge loss, the PA-I algorithm.
+    Hinge,
+    /// Squared hinge loss, the PA-II algorithm.
+    SquaredHinge,
+}
+
+/// Passive-Aggressive classifier parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct PassiveAggressiveClassifierParameters<T: RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Aggressiveness, the maximum step size (PA-I) or the strength of the penalty of large steps (PA-II).
+    pub c: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Loss function, selects between PA-I and PA-II.
+    pub loss: PassiveAggressiveLoss,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to fit the intercept.
+    pub fit_intercept: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of passes over the training data (epochs).
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Training stops when the mean loss of an epoch has not decreased by at least `tol` for `n_iter_no_change` consecutive epochs.
+    pub tol: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of epochs with no improvement to wait before stopping.
+    pub n_iter_no_change: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to shuffle the training data before every epoch.
+    pub shuffle: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used to shuffle the training data.
+    pub seed: Option<u64>,
+}
+
+/// Passive-Aggressive classifier
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct PassiveAggressiveClassifier<T: RealNumber> {
+    c: T,
+    loss: PassiveAggressiveLoss,
+    fit_intercept: bool,
+    classes: Vec<T>,
+    coefficients: Vec<Vec<T>>,
+    intercepts: Vec<T>,
+    n_iter: usize,
+}
+
+impl<T: RealNumber> PassiveAggressiveClassifierParameters<T> {
+    /// Aggressiveness, the maximum step size (PA-I) or the strength of the penalty of large steps (PA-II).
+    pub fn with_c(mut self, c: T) -> Self {
+        self.c = c;
+        self
+    }
+    /// Loss function, selects between PA-I and PA-II.
+    pub fn with_loss(mut self, loss: PassiveAggressiveLoss) -> Self {
+        self.loss = loss;
+        self
+    }
+    /// Whether to fit the intercept.
+    pub fn with_fit_intercept(mut self, fit_intercept: bool) -> Self {
+        self.fit_intercept = fit_intercept;
+        self
+    }
+    /// The maximum number of passes over the training data (epochs).
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Minimum decrease of the mean loss of an epoch.
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Number of epochs with no improvement to wait before stopping.
+    pub fn with_n_iter_no_change(mut self, n_iter_no_change: usize) -> Self {
+        self.n_iter_no_change = n_iter_no_change;
+        self
+    }
+    /// Whether to shuffle the training data before every epoch.
+    pub fn with_shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+    /// Seed used to shuffle the training data.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl<T: RealNumber> Default for PassiveAggressiveClassifierParameters<T> {
+    fn default() -> Self {
+        PassiveAggressiveClassifierParameters {
+            c: T::one(),
+            loss: PassiveAggressiveLoss::default(),
+            fit_intercept: true,
+            max_iter: 1000,
+            tol: T::from_f64(1e-3).unwrap(),
+            n_iter_no_change: 5,
+            shuffle: true,
+            seed: None,
+        }
+    }
+}
+
+impl<T: RealNumber> PartialEq for PassiveAggressiveClassifier<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.classes == other.classes
+            && self.coefficients == other.coefficients
+            && self.intercepts == other.intercepts
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>>
+    SupervisedEstimator<M, M::RowVector, PassiveAggressiveClassifierParameters<T>>
+    for PassiveAggressiveClassifier<T>
+{
+    fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: PassiveAggressiveClassifierParameters<T>,
+    ) -> Result<Self, Failed> {
+        PassiveAggressiveClassifier::fit(x, y, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for PassiveAggressiveClassifier<T> {
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> OnlineEstimator<M, M::RowVector>
+    for PassiveAggressiveClassifier<T>
+{
+    fn partial_fit(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        self.partial_fit(x, y)
+    }
+}
+
+impl<T: RealNumber> PassiveAggressiveClassifier<T> {
+    /// Creates an untrained classifier with zero coefficients to be trained incrementally with [`partial_fit`](PassiveAggressiveClassifier::partial_fit).
+    /// * `classes` - all class labels the classifier will see during training.
+    /// * `num_features` - number of features of every observation.
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn new(
+        classes: &[T],
+        num_features: usize,
+        parameters: PassiveAggressiveClassifierParameters<T>,
+    ) -> Result<PassiveAggressiveClassifier<T>, Failed> {
+        if parameters.c <= T::zero() {
+            return Err(Failed::fit("c should be > 0"));
+        }
+        let mut classes = classes.to_vec();
+        classes.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        classes.dedup();
+        if classes.len() < 2 {
+            return Err(Failed::fit(&format!(
+                "Incorrect number of classes: {}. Should be >= 2.",
+                classes.len()
+            )));
+        }
+
+        let n_models = if classes.len() == 2 { 1 } else { classes.len() };
+        Ok(PassiveAggressiveClassifier {
+            c: parameters.c,
+            loss: parameters.loss,
+            fit_intercept: parameters.fit_intercept,
+            classes,
+            coefficients: vec![vec![T::zero(); num_features]; n_models],
+            intercepts: vec![T::zero(); n_models],
+            n_iter: 0,
+        })
+    }
+
+    /// Fits the classifier with up to `max_iter` passes over the training data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target class values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit<M: Matrix<T>>(
+        x: &M,
+        y: &M::RowVector,
+        parameters: PassiveAggressiveClassifierParameters<T>,
+    ) -> Result<PassiveAggressiveClassifier<T>, Failed> {
+        check_fit(x, y)?;
+        let mut pa =
+            PassiveAggressiveClassifier::new(&y.unique(), x.shape().1, parameters.clone())?;
+        let (rows, targets) = pa.training_data(x, y)?;
+
+        let mut rng = get_rng_impl(parameters.seed);
+        let mut order: Vec<usize> = (0..rows.len()).collect();
+        let n = T::from_usize(rows.len().max(1)).unwrap();
+        let mut best_loss = T::infinity();
+        let mut no_improvement = 0;
+
+        for _ in 0..parameters.max_iter {
+            if parameters.shuffle {
+                order.shuffle(&mut rng);
+            }
+            let loss = pa.train_epoch(&rows, &targets, &order) / n;
+
+            if loss == T::zero() {
+                break;
+            }
+            if loss > best_loss - parameters.tol {
+                no_improvement += 1;
+                if no_improvement >= parameters.n_iter_no_change {
+                    break;
+                }
+            } else {
+                no_improvement = 0;
+            }
+            best_loss = best_loss.min(loss);
+        }
+
+        Ok(pa)
+    }
+
+    /// Updates the classifier with a single pass over `x`, in the order of the rows.
+    /// Use it to train the classifier incrementally on batches of data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target class values, every value should be one of the classes of the classifier.
+    pub fn partial_fit<M: Matrix<T>>(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        check_fit(x, y)?;
+        let (rows, targets) = self.training_data(x, y)?;
+        let order: Vec<usize> = (0..rows.len()).collect();
+        self.train_epoch(&rows, &targets, &order);
+        Ok(())
+    }
+
+    /// Predict class labels for `x`.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed> {
+        let decision = self.decision_function(x)?;
+        let (n, k) = decision.shape();
+        let mut result = M::RowVector::zeros(n);
+        for i in 0..n {
+            let class = if k == 1 {
+                usize::from(decision.get(i, 0) > T::zero())
+            } else {
+                let mut best = 0;
+                for j in 1..k {
+                    if decision.get(i, j) > decision.get(i, best) {
+                        best = j;
+                    }
+                }
+                best
+            };
+            result.set(i, self.classes[class]);
+        }
+        Ok(result)
+    }
+
+    /// Signed distance of every observation to the hyperplane of every binary classifier.
+    /// Returns _Kx1_ matrix for binary problems, where positive values predict the second class, and _KxC_ matrix for _C_ > 2 classes.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn decision_function<M: Matrix<T>>(&self, x: &M) -> Result<M, Failed> {
+        check_predict(x)?;
+        self.check_features(x)?;
+        let (n, m) = x.shape();
+        let mut row = vec![T::zero(); m];
+        let mut result = M::zeros(n, self.coefficients.len());
+        for i in 0..n {
+            x.copy_row_as_vec(i, &mut row);
+            for (j, (coefficients, intercept)) in self
+                .coefficients
+                .iter()
+                .zip(self.intercepts.iter())
+                .enumerate()
+            {
+                result.set(i, j, decision(coefficients, *intercept, &row));
+            }
+        }
+        Ok(result)
+    }
+
+    /// Class labels, sorted.
+    pub fn classes(&self) -> &Vec<T> {
+        &self.classes
+    }
+
+    /// Coefficients of every binary classifier: one vector for binary problems, one vector per class otherwise.
+    pub fn coefficients(&self) -> &Vec<Vec<T>> {
+        &self.coefficients
+    }
+
+    /// Intercept of every binary classifier.
+    pub fn intercepts(&self) -> &Vec<T> {
+        &self.intercepts
+    }
+
+    /// Number of passes over the training data made so far, including calls to `partial_fit`.
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+
+    fn check_features<M: Matrix<T>>(&self, x: &M) -> Result<(), Failed> {
+        let num_features = self.coefficients[0].len();
+        if x.shape().1 != num_features {
+            return Err(Failed::fit(&format!(
+                "Number of features of x ({}) does not match the number of coefficients ({})",
+                x.shape().1,
+                num_features
+            )));
+        }
+        Ok(())
+    }
+
+    fn training_data<M: Matrix<T>>(
+        &self,
+        x: &M,
+        y: &M::RowVector,
+    ) -> Result<(Vec<Vec<T>>, Vec<Vec<T>>), Failed> {
+        let (n, _) = x.shape();
+        if y.len() != n {
+            return Err(Failed::fit("Number of rows in X should = len(y)"));
+        }
+        self.check_features(x)?;
+
+        let mut targets = vec![vec![-T::one(); n]; self.coefficients.len()];
+        for (i, y_i) in y.to_vec().into_iter().enumerate() {
+            let class = self
+                .classes
+                .iter()
+                .position(|c| *c == y_i)
+                .ok_or_else(|| Failed::fit(&format!("Unknown class: {}", y_i)))?;
+            match self.coefficients.len() {
+                1 if class == 1 => targets[0][i] = T::one(),
+                1 => {}
+                _ => targets[class][i] = T::one(),
+            }
+        }
+        Ok(((0..n).map(|i| x.get_row_as_vec(i)).collect(), targets))
+    }
+
+    /// Single pass over the samples in `order`. Returns the sum of the hinge losses of all binary classifiers before every update.
+    fn train_epoch(&mut self, rows: &[Vec<T>], targets: &[Vec<T>], order: &[usize]) -> T {
+        let mut total_loss = T::zero();
+        for i in order.iter() {
+            let row = &rows[*i];
+            let mut squared_norm = row.iter().fold(T::zero(), |acc, x| acc + *x * *x);
+            if self.fit_intercept {
+                squared_norm += T::one();
+            }
+            for ((coefficients, intercept), y) in self
+                .coefficients
+                .iter_mut()
+                .zip(self.intercepts.iter_mut())
+                .zip(targets.iter())
+            {
+                let loss = T::one() - y[*i] * decision(coefficients, *intercept, row);
+                if loss <= T::zero() {
+                    continue;
+                }
+                total_loss += loss;
+
+                let tau = match self.loss {
+                    PassiveAggressiveLoss::Hinge if squared_norm > T::zero() => {
+                        self.c.min(loss / squared_norm)
+                    }
+                    PassiveAggressiveLoss::Hinge => T::zero(),
+                    PassiveAggressiveLoss::SquaredHinge => {
+                        loss / (squared_norm + T::one() / (T::two() * self.c))
+                    }
+                };
+                let step = tau * y[*i];
+                for (w, x) in coefficients.iter_mut().zip(row.iter()) {
+                    *w += step * *x;
+                }
+                if self.fit_intercept {
+                    *intercept += step;
+                }
+            }
+        }
+        self.n_iter += 1;
+        total_loss
+    }
+}
+
+fn decision<T: RealNumber>(coefficients: &[T], intercept: T, x: &[T]) -> T {
+    coefficients
+        .iter()
+        .zip(x.iter())
+        .fold(intercept, |acc, (w, x)| acc + *w * *x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::metrics::accuracy;
+
+    fn blobs() -> (DenseMatrix<f64>, Vec<f64>) {
+        let mut values = Vec::new();
+        let mut y = Vec::new();
+        for i in 0..60 {
+            let class = i % 3;
+            let jitter = ((i * 37) % 11) as f64 / 20. - 0.25;
+            let (a, b) = match class {
+                0 => (-1., -1.),
+                1 => (1., -1.),
+                _ => (0., 1.),
+            };
+            values.push(vec![a + jitter, b - jitter]);
+            y.push(class as f64);
+        }
+        (DenseMatrix::from_2d_vec(&values), y)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn passive_aggressive() {
+        let (x, y) = blobs();
+
+        for loss in [
+            PassiveAggressiveLoss::Hinge,
+            PassiveAggressiveLoss::SquaredHinge,
+        ] {
+            let pa = PassiveAggressiveClassifier::fit(
+                &x,
+                &y,
+                PassiveAggressiveClassifierParameters::default()
+                    .with_loss(loss)
+                    .with_seed(1),
+            )
+            .unwrap();
+            let y_hat = pa.predict(&x).unwrap();
+
+            assert!(accuracy(&y, &y_hat) > 0.9);
+            assert_eq!(pa.coefficients().len(), 3);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn single_update() {
+        let x = DenseMatrix::from_2d_array(&[&[1., 2.]]);
+        let y: Vec<f64> = vec![1.];
+
+        let mut pa = PassiveAggressiveClassifier::new(
+            &[-1., 1.],
+            2,
+            PassiveAggressiveClassifierParameters::default(),
+        )
+        .unwrap();
+        pa.partial_fit(&x, &y).unwrap();
+        // loss 1, squared norm 1 + 4 + 1, the observation ends up exactly on the margin
+        assert!((pa.coefficients()[0][0] - 1. / 6.).abs() < 1e-12);
+        assert!((pa.coefficients()[0][1] - 2. / 6.).abs() < 1e-12);
+        assert!((pa.intercepts()[0] - 1. / 6.).abs() < 1e-12);
+
+        // PA-I limits the step by c
+        let mut pa = PassiveAggressiveClassifier::new(
+            &[-1., 1.],
+            2,
+            PassiveAggressiveClassifierParameters::default()
+                .with_c(0.1)
+                .with_fit_intercept(false),
+        )
+        .unwrap();
+        pa.partial_fit(&x, &y).unwrap();
+        assert!((pa.coefficients()[0][0] - 0.1).abs() < 1e-12);
+        assert!((pa.coefficients()[0][1] - 0.2).abs() < 1e-12);
+        assert_eq!(pa.intercepts()[0], 0.);
+
+        // PA-II shrinks the step by 1 / 2c
+        let mut pa = PassiveAggressiveClassifier::new(
+            &[-1., 1.],
+            2,
+            PassiveAggressiveClassifierParameters::default()
+                .with_c(0.5)
+                .with_loss(PassiveAggressiveLoss::SquaredHinge)
+                .with_fit_intercept(false),
+        )
+        .unwrap();
+        pa.partial_fit(&x, &y).unwrap();
+        assert!((pa.coefficients()[0][0] - 1. / 6.).abs() < 1e-12);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn partial_fit() {
+        let (x, y) = blobs();
+
+        let mut pa = PassiveAggressiveClassifier::new(
+            &[0., 1., 2.],
+            2,
+            PassiveAggressiveClassifierParameters::default().with_c(0.1),
+        )
+        .unwrap();
+        for _ in 0..10 {
+            pa.partial_fit(&x, &y).unwrap();
+        }
+        let y_hat = pa.predict(&x).unwrap();
+
+        assert_eq!(pa.n_iter(), 10);
+        assert!(accuracy(&y, &y_hat) > 0.9);
+        assert!(pa.partial_fit(&x, &vec![3.; 60]).is_err());
+        assert!(pa
+            .partial_fit(&DenseMatrix::from_2d_array(&[&[1., 2., 3.]]), &vec![0.])
+            .is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = blobs();
+
+        let pa = PassiveAggressiveClassifier::fit(
+            &x,
+            &y,
+            PassiveAggressiveClassifierParameters::default().with_seed(1),
+        )
+        .unwrap();
+
+        let deserialized_pa: PassiveAggressiveClassifier<f64> =
+            serde_json::from_str(&serde_json::to_string(&pa).unwrap()).unwrap();
+
+        assert_eq!(pa.classes(), deserialized_pa.classes());
+        assert_eq!(
+            pa.predict(&x).unwrap(),
+            deserialized_pa.predict(&x).unwrap()
+        );
+    }
+}
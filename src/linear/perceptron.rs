@@ -0,0 +1,593 @@
+//! # Perceptron
+//!
+//! The perceptron is the simplest linear classifier trained online. It predicts the class of an observation from the sign of \\(w^Tx + b\\)
+//! and updates the coefficients only when the prediction is wrong:
+//!
+//! \\[w \leftarrow w + \eta y_i x_i, \quad b \leftarrow b + \eta y_i\\]
+//!
+//! where \\(y_i \in \\{-1, 1\\}\\) and \\(\eta\\) is the learning rate. When the classes are linearly separable the perceptron finds a separating hyperplane
+//! after a finite number of mistakes. When they are not, the coefficients keep oscillating; the averaged perceptron predicts with the average of the coefficients
+//! after every training observation instead, which is much more stable and generalizes better.
+//!
+//! Every update uses a single observation, so the perceptron can be trained incrementally with `partial_fit` on batches of data that arrive over time.
+//! Classification problems with more than two classes are solved one-vs-all: one binary perceptron is fitted for every class.
+//! For a perceptron with regularization or other losses see [SGD](../sgd/index.html).
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::linear::perceptron::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[-1.0, -1.2],
+//!     &[-0.8, -1.0],
+//!     &[-1.2, -0.7],
+//!     &[-0.9, -0.8],
+//!     &[1.0, 1.1],
+//!     &[0.8, 1.3],
+//!     &[1.2, 0.9],
+//!     &[1.1, 1.0],
+//! ]);
+//! let y: Vec<f64> = vec![0., 0., 0., 0., 1., 1., 1., 1.];
+//!
+//! let mut perceptron = Perceptron::fit(&x, &y, PerceptronParameters::default().with_average(true)).unwrap();
+//! let y_hat = perceptron.predict(&x).unwrap();
+//!
+//! // continue training when more data arrives
+//! perceptron.partial_fit(&x, &y).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["The Perceptron: A Probabilistic Model for Information Storage and Organization in the Brain", Rosenblatt F., 1958](https://doi.org/10.1037/h0042519)
+//! * ["Large Margin Classification Using the Perceptron Algorithm", Freund Y., Schapire R. E., 1999](https://doi.org/10.1023/A:1007662407062)
+//! * ["Discriminative Training Methods for Hidden Markov Models: Theory and Experiments with Perceptron Algorithms", Collins M., 2002](https://aclanthology.org/W02-1001/)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use rand::seq::SliceRandom;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{OnlineEstimator, Predictor, SupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::rand::get_rng_impl;
+use crate::validation::{check_fit, check_predict};
+
+/// Perceptron parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct PerceptronParameters<T: RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Learning rate, the step size of every update.
+    pub eta0: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to fit the intercept.
+    pub fit_intercept: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to predict with the coefficients averaged over all updates.
+    pub average: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of passes over the training data (epochs).
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Training stops when the number of mistakes has not decreased for `n_iter_no_change` consecutive epochs, or after an epoch without mistakes.
+    pub n_iter_no_change: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to shuffle the training data before every epoch.
+    pub shuffle: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used to shuffle the training data.
+    pub seed: Option<u64>,
+}
+
+/// Perceptron
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct Perceptron<T: RealNumber> {
+    eta0: T,
+    fit_intercept: bool,
+    average: bool,
+    classes: Vec<T>,
+    models: Vec<PerceptronModel<T>>,
+    n_updates: usize,
+    n_iter: usize,
+}
+
+impl<T: RealNumber> PerceptronParameters<T> {
+    /// Learning rate, the step size of every update.
+    pub fn with_eta0(mut self, eta0: T) -> Self {
+        self.eta0 = eta0;
+        self
+    }
+    /// Whether to fit the intercept.
+    pub fn with_fit_intercept(mut self, fit_intercept: bool) -> Self {
+        self.fit_intercept = fit_intercept;
+        self
+    }
+    /// Whether to predict with the coefficients averaged over all updates.
+    pub fn with_average(mut self, average: bool) -> Self {
+        self.average = average;
+        self
+    }
+    /// The maximum number of passes over the training data (epochs).
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Number of epochs with no decrease of the number of mistakes to wait before stopping.
+    pub fn with_n_iter_no_change(mut self, n_iter_no_change: usize) -> Self {
+        self.n_iter_no_change = n_iter_no_change;
+        self
+    }
+    /// Whether to shuffle the training data before every epoch.
+    pub fn with_shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+    /// Seed used to shuffle the training data.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl<T: RealNumber> Default for PerceptronParameters<T> {
+    fn default() -> Self {
+        PerceptronParameters {
+            eta0: T::one(),
+            fit_intercept: true,
+            average: false,
+            max_iter: 1000,
+            n_iter_no_change: 5,
+            shuffle: true,
+            seed: None,
+        }
+    }
+}
+
+impl<T: RealNumber> PartialEq for Perceptron<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.classes == other.classes
+            && self.average == other.average
+            && self.models == other.models
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> SupervisedEstimator<M, M::RowVector, PerceptronParameters<T>>
+    for Perceptron<T>
+{
+    fn fit(x: &M, y: &M::RowVector, parameters: PerceptronParameters<T>) -> Result<Self, Failed> {
+        Perceptron::fit(x, y, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for Perceptron<T> {
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> OnlineEstimator<M, M::RowVector> for Perceptron<T> {
+    fn partial_fit(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        self.partial_fit(x, y)
+    }
+}
+
+impl<T: RealNumber> Perceptron<T> {
+    /// Creates an untrained perceptron with zero coefficients to be trained incrementally with [`partial_fit`](Perceptron::partial_fit).
+    /// * `classes` - all class labels the perceptron will see during training.
+    /// * `num_features` - number of features of every observation.
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn new(
+        classes: &[T],
+        num_features: usize,
+        parameters: PerceptronParameters<T>,
+    ) -> Result<Perceptron<T>, Failed> {
+        if parameters.eta0 <= T::zero() {
+            return Err(Failed::fit("eta0 should be > 0"));
+        }
+        let mut classes = classes.to_vec();
+        classes.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        classes.dedup();
+        if classes.len() < 2 {
+            return Err(Failed::fit(&format!(
+                "Incorrect number of classes: {}. Should be >= 2.",
+                classes.len()
+            )));
+        }
+
+        let n_models = if classes.len() == 2 { 1 } else { classes.len() };
+        Ok(Perceptron {
+            eta0: parameters.eta0,
+            fit_intercept: parameters.fit_intercept,
+            average: parameters.average,
+            classes,
+            models: vec![PerceptronModel::new(num_features); n_models],
+            n_updates: 0,
+            n_iter: 0,
+        })
+    }
+
+    /// Fits the perceptron with up to `max_iter` passes over the training data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target class values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit<M: Matrix<T>>(
+        x: &M,
+        y: &M::RowVector,
+        parameters: PerceptronParameters<T>,
+    ) -> Result<Perceptron<T>, Failed> {
+        check_fit(x, y)?;
+        let mut perceptron = Perceptron::new(&y.unique(), x.shape().1, parameters.clone())?;
+        let (rows, targets) = perceptron.training_data(x, y)?;
+
+        let mut rng = get_rng_impl(parameters.seed);
+        let mut order: Vec<usize> = (0..rows.len()).collect();
+        let mut fewest_mistakes = usize::MAX;
+        let mut no_improvement = 0;
+
+        for _ in 0..parameters.max_iter {
+            if parameters.shuffle {
+                order.shuffle(&mut rng);
+            }
+            let mistakes = perceptron.train_epoch(&rows, &targets, &order);
+
+            if mistakes == 0 {
+                break;
+            }
+            if mistakes < fewest_mistakes {
+                fewest_mistakes = mistakes;
+                no_improvement = 0;
+            } else {
+                no_improvement += 1;
+                if no_improvement >= parameters.n_iter_no_change {
+                    break;
+                }
+            }
+        }
+
+        Ok(perceptron)
+    }
+
+    /// Updates the perceptron with a single pass over `x`, in the order of the rows.
+    /// Use it to train the perceptron incrementally on batches of data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target class values, every value should be one of the classes of the perceptron.
+    pub fn partial_fit<M: Matrix<T>>(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        check_fit(x, y)?;
+        let (rows, targets) = self.training_data(x, y)?;
+        let order: Vec<usize> = (0..rows.len()).collect();
+        self.train_epoch(&rows, &targets, &order);
+        Ok(())
+    }
+
+    /// Predict class labels for `x`.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed> {
+        let decision = self.decision_function(x)?;
+        let (n, k) = decision.shape();
+        let mut result = M::RowVector::zeros(n);
+        for i in 0..n {
+            let class = if k == 1 {
+                usize::from(decision.get(i, 0) > T::zero())
+            } else {
+                let mut best = 0;
+                for j in 1..k {
+                    if decision.get(i, j) > decision.get(i, best) {
+                        best = j;
+                    }
+                }
+                best
+            };
+            result.set(i, self.classes[class]);
+        }
+        Ok(result)
+    }
+
+    /// Signed distance of every observation to the hyperplane of every binary perceptron.
+    /// Returns _Kx1_ matrix for binary problems, where positive values predict the second class, and _KxC_ matrix for _C_ > 2 classes.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn decision_function<M: Matrix<T>>(&self, x: &M) -> Result<M, Failed> {
+        check_predict(x)?;
+        self.check_features(x)?;
+        let (n, m) = x.shape();
+        let weights: Vec<(Vec<T>, T)> = self
+            .models
+            .iter()
+            .map(|model| model.weights(self.average, self.n_updates))
+            .collect();
+        let mut row = vec![T::zero(); m];
+        let mut result = M::zeros(n, self.models.len());
+        for i in 0..n {
+            x.copy_row_as_vec(i, &mut row);
+            for (j, (coefficients, intercept)) in weights.iter().enumerate() {
+                result.set(i, j, decision(coefficients, *intercept, &row));
+            }
+        }
+        Ok(result)
+    }
+
+    /// Class labels, sorted.
+    pub fn classes(&self) -> &Vec<T> {
+        &self.classes
+    }
+
+    /// Coefficients of every binary perceptron, averaged when `average` is set: one vector for binary problems, one vector per class otherwise.
+    pub fn coefficients(&self) -> Vec<Vec<T>> {
+        self.models
+            .iter()
+            .map(|model| model.weights(self.average, self.n_updates).0)
+            .collect()
+    }
+
+    /// Intercept of every binary perceptron, averaged when `average` is set.
+    pub fn intercepts(&self) -> Vec<T> {
+        self.models
+            .iter()
+            .map(|model| model.weights(self.average, self.n_updates).1)
+            .collect()
+    }
+
+    /// Number of passes over the training data made so far, including calls to `partial_fit`.
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+
+    fn check_features<M: Matrix<T>>(&self, x: &M) -> Result<(), Failed> {
+        let num_features = self.models[0].coefficients.len();
+        if x.shape().1 != num_features {
+            return Err(Failed::fit(&format!(
+                "Number of features of x ({}) does not match the number of coefficients ({})",
+                x.shape().1,
+                num_features
+            )));
+        }
+        Ok(())
+    }
+
+    fn training_data<M: Matrix<T>>(
+        &self,
+        x: &M,
+        y: &M::RowVector,
+    ) -> Result<(Vec<Vec<T>>, Vec<Vec<T>>), Failed> {
+        let (n, _) = x.shape();
+        if y.len() != n {
+            return Err(Failed::fit("Number of rows in X should = len(y)"));
+        }
+        self.check_features(x)?;
+
+        let mut targets = vec![vec![-T::one(); n]; self.models.len()];
+        for (i, y_i) in y.to_vec().into_iter().enumerate() {
+            let class = self
+                .classes
+                .iter()
+                .position(|c| *c == y_i)
+                .ok_or_else(|| Failed::fit(&format!("Unknown class: {}", y_i)))?;
+            match self.models.len() {
+                1 if class == 1 => targets[0][i] = T::one(),
+                1 => {}
+                _ => targets[class][i] = T::one(),
+            }
+        }
+        Ok(((0..n).map(|i| x.get_row_as_vec(i)).collect(), targets))
+    }
+
+    /// Single pass over the samples in `order`. Returns the number of observations misclassified by at least one binary perceptron.
+    fn train_epoch(&mut self, rows: &[Vec<T>], targets: &[Vec<T>], order: &[usize]) -> usize {
+        let mut mistakes = 0;
+        for i in order.iter() {
+            let row = &rows[*i];
+            let mut mistake = false;
+            for (model, y) in self.models.iter_mut().zip(targets.iter()) {
+                if y[*i] * decision(&model.coefficients, model.intercept, row) <= T::zero() {
+                    model.update(
+                        row,
+                        self.eta0 * y[*i],
+                        self.fit_intercept,
+                        T::from_usize(self.n_updates).unwrap(),
+                    );
+                    mistake = true;
+                }
+            }
+            if mistake {
+                mistakes += 1;
+            }
+            self.n_updates += 1;
+        }
+        self.n_iter += 1;
+        mistakes
+    }
+}
+
+fn decision<T: RealNumber>(coefficients: &[T], intercept: T, x: &[T]) -> T {
+    coefficients
+        .iter()
+        .zip(x.iter())
+        .fold(intercept, |acc, (w, x)| acc + *w * *x)
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+struct PerceptronModel<T: RealNumber> {
+    coefficients: Vec<T>,
+    intercept: T,
+    /// Sum of the updates of the coefficients, every update multiplied by the number of observations seen before it.
+    /// The averaged coefficients after `c` observations are `coefficients - coefficients_updates / c`.
+    coefficients_updates: Vec<T>,
+    intercept_updates: T,
+}
+
+impl<T: RealNumber> PerceptronModel<T> {
+    fn new(num_features: usize) -> PerceptronModel<T> {
+        PerceptronModel {
+            coefficients: vec![T::zero(); num_features],
+            intercept: T::zero(),
+            coefficients_updates: vec![T::zero(); num_features],
+            intercept_updates: T::zero(),
+        }
+    }
+
+    fn update(&mut self, x: &[T], step: T, fit_intercept: bool, c: T) {
+        for ((w, u), x) in self
+            .coefficients
+            .iter_mut()
+            .zip(self.coefficients_updates.iter_mut())
+            .zip(x.iter())
+        {
+            *w += step * *x;
+            *u += c * step * *x;
+        }
+        if fit_intercept {
+            self.intercept += step;
+            self.intercept_updates += c * step;
+        }
+    }
+
+    fn weights(&self, average: bool, n_updates: usize) -> (Vec<T>, T) {
+        if !average || n_updates == 0 {
+            return (self.coefficients.clone(), self.intercept);
+        }
+        let c = T::from_usize(n_updates).unwrap();
+        (
+            self.coefficients
+                .iter()
+                .zip(self.coefficients_updates.iter())
+                .map(|(w, u)| *w - *u / c)
+                .collect(),
+            self.intercept - self.intercept_updates / c,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::metrics::accuracy;
+
+    fn blobs() -> (DenseMatrix<f64>, Vec<f64>) {
+        let mut values = Vec::new();
+        let mut y = Vec::new();
+        for i in 0..60 {
+            let class = i % 3;
+            let jitter = ((i * 37) % 11) as f64 / 20. - 0.25;
+            let (a, b) = match class {
+                0 => (-1., -1.),
+                1 => (1., -1.),
+                _ => (0., 1.),
+            };
+            values.push(vec![a + jitter, b - jitter]);
+            y.push(class as f64);
+        }
+        (DenseMatrix::from_2d_vec(&values), y)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn perceptron() {
+        let (x, y) = blobs();
+
+        for average in [false, true] {
+            let perceptron = Perceptron::fit(
+                &x,
+                &y,
+                PerceptronParameters::default()
+                    .with_average(average)
+                    .with_seed(1),
+            )
+            .unwrap();
+            let y_hat = perceptron.predict(&x).unwrap();
+
+            assert!(accuracy(&y, &y_hat) > 0.9);
+            assert_eq!(perceptron.coefficients().len(), 3);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn separable_binary() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 1.],
+            &[2., 1.5],
+            &[1.5, 3.],
+            &[-1., -1.],
+            &[-2., -0.5],
+            &[-1.5, -2.],
+        ]);
+        let y: Vec<f64> = vec![1., 1., 1., -1., -1., -1.];
+
+        let perceptron =
+            Perceptron::fit(&x, &y, PerceptronParameters::default().with_shuffle(false)).unwrap();
+
+        assert_eq!(perceptron.predict(&x).unwrap(), y);
+        assert_eq!(perceptron.classes(), &vec![-1., 1.]);
+        assert_eq!(perceptron.coefficients().len(), 1);
+        // the first epoch makes one mistake, the second epoch none
+        assert_eq!(perceptron.n_iter(), 2);
+        assert_eq!(perceptron.coefficients()[0], vec![1., 1.]);
+        assert_eq!(perceptron.intercepts()[0], 1.);
+    }
+
+    #[test]
+    fn averaging() {
+        let mut model = PerceptronModel::new(1);
+        // updates at the 1st and the 3rd observation
+        model.update(&[1.], 1., true, 0.);
+        model.update(&[2.], -1., true, 2.);
+        // coefficients after every observation: 1, 1, -1, -1
+        let (coefficients, intercept) = model.weights(true, 4);
+        assert_eq!(coefficients, vec![0.]);
+        // intercepts after every observation: 1, 1, 0, 0
+        assert_eq!(intercept, 0.5);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn partial_fit() {
+        let (x, y) = blobs();
+
+        let mut perceptron = Perceptron::new(
+            &[0., 1., 2.],
+            2,
+            PerceptronParameters::default().with_average(true),
+        )
+        .unwrap();
+        for _ in 0..10 {
+            perceptron.partial_fit(&x, &y).unwrap();
+        }
+        let y_hat = perceptron.predict(&x).unwrap();
+
+        assert_eq!(perceptron.n_iter(), 10);
+        assert!(accuracy(&y, &y_hat) > 0.9);
+        assert!(perceptron.partial_fit(&x, &vec![3.; 60]).is_err());
+        assert!(perceptron
+            .partial_fit(&DenseMatrix::from_2d_array(&[&[1., 2., 3.]]), &vec![0.])
+            .is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = blobs();
+
+        let perceptron =
+            Perceptron::fit(&x, &y, PerceptronParameters::default().with_seed(1)).unwrap();
+
+        let deserialized_perceptron: Perceptron<f64> =
+            serde_json::from_str(&serde_json::to_string(&perceptron).unwrap()).unwrap();
+
+        assert_eq!(perceptron.classes(), deserialized_perceptron.classes());
+        assert_eq!(
+            perceptron.predict(&x).unwrap(),
+            deserialized_perceptron.predict(&x).unwrap()
+        );
+    }
+}
@@ -0,0 +1,450 @@
+//! # Quantile Regression
+//!
+//! [Linear regression](../linear_regression/index.html) estimates the conditional mean of the response \\(y\\) given the explanatory variables \\(X\\).
+//! Quantile regression estimates a conditional quantile \\(\tau \in (0, 1)\\) of the response instead, e.g. the conditional median for \\(\tau = 0.5\\).
+//! The regression coefficients minimize the pinball loss
+//!
+//! \\[\hat{\beta} = \underset{\beta}{argmin} \sum_{i=1}^n \rho_\tau(y_i - \beta_0 - \sum_{j=1}^m \beta_jx_{ij})\\]
+//!
+//! where \\(\rho_\tau(r) = \tau r\\) for \\(r \geq 0\\) and \\(\rho_\tau(r) = (\tau - 1) r\\) for \\(r < 0\\).
+//!
+//! Unlike least squares, the estimate of the median is robust to outliers in the response. Models fitted to a lower and an upper quantile,
+//! e.g. \\(\tau = 0.05\\) and \\(\tau = 0.95\\), give a prediction interval that makes no assumption about the distribution of the error term.
+//!
+//! SmartCore finds the coefficients with iteratively reweighted least squares: at every iteration the observations are weighted by
+//! \\(\frac{\tau}{\vert r_i \vert}\\) or \\(\frac{1 - \tau}{\vert r_i \vert}\\), depending on the sign of the residual \\(r_i\\) of the previous iteration,
+//! and the weighted least squares problem is solved with [SVD](../../linalg/svd/index.html).
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::linear::quantile_regression::*;
+//!
+//! // Longley dataset (https://www.statsmodels.org/stable/datasets/generated/longley.html)
+//! let x = DenseMatrix::from_2d_array(&[
+//!               &[234.289, 235.6, 159.0, 107.608, 1947., 60.323],
+//!               &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+//!               &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+//!               &[284.599, 335.1, 165.0, 110.929, 1950., 61.187],
+//!               &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+//!               &[346.999, 193.2, 359.4, 113.270, 1952., 63.639],
+//!               &[365.385, 187.0, 354.7, 115.094, 1953., 64.989],
+//!               &[363.112, 357.8, 335.0, 116.219, 1954., 63.761],
+//!               &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+//!               &[419.180, 282.2, 285.7, 118.734, 1956., 67.857],
+//!               &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+//!               &[444.546, 468.1, 263.7, 121.950, 1958., 66.513],
+//!               &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+//!               &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+//!               &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+//!               &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+//!          ]);
+//!
+//! let y: Vec<f64> = vec![83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0,
+//!           100.0, 101.2, 104.6, 108.4, 110.8, 112.6, 114.2, 115.7, 116.9];
+//!
+//! let lower = QuantileRegression::fit(&x, &y,
+//!             QuantileRegressionParameters::default().with_quantile(0.1)).unwrap();
+//! let upper = QuantileRegression::fit(&x, &y,
+//!             QuantileRegressionParameters::default().with_quantile(0.9)).unwrap();
+//!
+//! // 80% prediction interval
+//! let y_lower = lower.predict(&x).unwrap();
+//! let y_upper = upper.predict(&x).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Regression Quantiles", Koenker R., Bassett G., 1978](https://doi.org/10.2307/1913643)
+//! * ["Quantile Regression", Koenker R., 2005, 6.8 Iteratively reweighted least squares](https://doi.org/10.1017/CBO9780511754098)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::validation::{check_fit, check_predict};
+
+/// Quantile Regression parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct QuantileRegressionParameters<T: RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The quantile to estimate, with 0 < quantile < 1.
+    pub quantile: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The tolerance for the optimization
+    pub tol: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of iterations
+    pub max_iter: usize,
+}
+
+/// Quantile Regression
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct QuantileRegression<T: RealNumber, M: Matrix<T>> {
+    coefficients: M,
+    intercept: T,
+    quantile: T,
+}
+
+impl<T: RealNumber> QuantileRegressionParameters<T> {
+    /// The quantile to estimate, with 0 < quantile < 1.
+    pub fn with_quantile(mut self, quantile: T) -> Self {
+        self.quantile = quantile;
+        self
+    }
+    /// The tolerance for the optimization
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// The maximum number of iterations
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+}
+
+impl<T: RealNumber> Default for QuantileRegressionParameters<T> {
+    fn default() -> Self {
+        QuantileRegressionParameters {
+            quantile: T::half(),
+            tol: T::from_f64(1e-6).unwrap(),
+            max_iter: 1000,
+        }
+    }
+}
+
+/// Quantile Regression grid search parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct QuantileRegressionSearchParameters<T: RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The quantile to estimate, with 0 < quantile < 1.
+    pub quantile: Vec<T>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The tolerance for the optimization
+    pub tol: Vec<T>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of iterations
+    pub max_iter: Vec<usize>,
+}
+
+/// Quantile Regression grid search iterator
+pub struct QuantileRegressionSearchParametersIterator<T: RealNumber> {
+    quantile_regression_search_parameters: QuantileRegressionSearchParameters<T>,
+    current_quantile: usize,
+    current_tol: usize,
+    current_max_iter: usize,
+}
+
+impl<T: RealNumber> IntoIterator for QuantileRegressionSearchParameters<T> {
+    type Item = QuantileRegressionParameters<T>;
+    type IntoIter = QuantileRegressionSearchParametersIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        QuantileRegressionSearchParametersIterator {
+            quantile_regression_search_parameters: self,
+            current_quantile: 0,
+            current_tol: 0,
+            current_max_iter: 0,
+        }
+    }
+}
+
+impl<T: RealNumber> Iterator for QuantileRegressionSearchParametersIterator<T> {
+    type Item = QuantileRegressionParameters<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_quantile == self.quantile_regression_search_parameters.quantile.len()
+            && self.current_tol == self.quantile_regression_search_parameters.tol.len()
+            && self.current_max_iter == self.quantile_regression_search_parameters.max_iter.len()
+        {
+            return None;
+        }
+
+        let next = QuantileRegressionParameters {
+            quantile: self.quantile_regression_search_parameters.quantile[self.current_quantile],
+            tol: self.quantile_regression_search_parameters.tol[self.current_tol],
+            max_iter: self.quantile_regression_search_parameters.max_iter[self.current_max_iter],
+        };
+
+        if self.current_quantile + 1 < self.quantile_regression_search_parameters.quantile.len() {
+            self.current_quantile += 1;
+        } else if self.current_tol + 1 < self.quantile_regression_search_parameters.tol.len() {
+            self.current_quantile = 0;
+            self.current_tol += 1;
+        } else if self.current_max_iter + 1
+            < self.quantile_regression_search_parameters.max_iter.len()
+        {
+            self.current_quantile = 0;
+            self.current_tol = 0;
+            self.current_max_iter += 1;
+        } else {
+            self.current_quantile += 1;
+            self.current_tol += 1;
+            self.current_max_iter += 1;
+        }
+
+        Some(next)
+    }
+}
+
+impl<T: RealNumber> Default for QuantileRegressionSearchParameters<T> {
+    fn default() -> Self {
+        let default_params = QuantileRegressionParameters::default();
+
+        QuantileRegressionSearchParameters {
+            quantile: vec![default_params.quantile],
+            tol: vec![default_params.tol],
+            max_iter: vec![default_params.max_iter],
+        }
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> PartialEq for QuantileRegression<T, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.coefficients == other.coefficients
+            && (self.intercept - other.intercept).abs() <= T::epsilon()
+            && self.quantile == other.quantile
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>>
+    SupervisedEstimator<M, M::RowVector, QuantileRegressionParameters<T>>
+    for QuantileRegression<T, M>
+{
+    fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: QuantileRegressionParameters<T>,
+    ) -> Result<Self, Failed> {
+        QuantileRegression::fit(x, y, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for QuantileRegression<T, M> {
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> QuantileRegression<T, M> {
+    /// Fits Quantile Regression to your data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: QuantileRegressionParameters<T>,
+    ) -> Result<QuantileRegression<T, M>, Failed> {
+        check_fit(x, y)?;
+        let (n, num_attributes) = x.shape();
+        if y.len() != n {
+            return Err(Failed::fit(
+                "Number of rows of X doesn\'t match number of rows of Y",
+            ));
+        }
+        let tau = parameters.quantile;
+        if tau <= T::zero() || tau >= T::one() {
+            return Err(Failed::fit(&format!(
+                "quantile should be between 0 and 1, got {}",
+                tau
+            )));
+        }
+
+        let a = x.h_stack(&M::ones(n, 1));
+        let y = y.to_vec();
+        // residuals smaller than epsilon get the weight of a residual of size epsilon
+        let epsilon =
+            T::from_f64(1e-6).unwrap() * y.iter().fold(T::one(), |acc, y_i| acc.max(y_i.abs()));
+
+        let mut weights = vec![T::one(); n];
+        let mut w = weighted_least_squares(&a, &y, &weights)?;
+
+        for _ in 0..parameters.max_iter {
+            let y_hat = a.matmul(&w);
+            for (i, weight) in weights.iter_mut().enumerate() {
+                let r = y[i] - y_hat.get(i, 0);
+                let scale = if r >= T::zero() { tau } else { T::one() - tau };
+                *weight = scale / r.abs().max(epsilon);
+            }
+
+            let w_next = weighted_least_squares(&a, &y, &weights)?;
+            let mut max_change = T::zero();
+            for j in 0..=num_attributes {
+                max_change = max_change.max((w_next.get(j, 0) - w.get(j, 0)).abs());
+            }
+            w = w_next;
+
+            if max_change <= parameters.tol {
+                break;
+            }
+        }
+
+        Ok(QuantileRegression {
+            intercept: w.get(num_attributes, 0),
+            coefficients: w.slice(0..num_attributes, 0..1),
+            quantile: tau,
+        })
+    }
+
+    /// Predict the conditional quantile of target values from `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
+        let (nrows, _) = x.shape();
+        let mut y_hat = x.matmul(&self.coefficients);
+        y_hat.add_mut(&M::fill(nrows, 1, self.intercept));
+        Ok(y_hat.transpose().to_row_vector())
+    }
+
+    /// Get estimates regression coefficients
+    pub fn coefficients(&self) -> &M {
+        &self.coefficients
+    }
+
+    /// Get estimate of intercept
+    pub fn intercept(&self) -> T {
+        self.intercept
+    }
+
+    /// The estimated quantile
+    pub fn quantile(&self) -> T {
+        self.quantile
+    }
+}
+
+/// Solves \\(\underset{w}{argmin} \sum_i weights_i (y_i - a_i w)^2\\).
+fn weighted_least_squares<T: RealNumber, M: Matrix<T>>(
+    a: &M,
+    y: &[T],
+    weights: &[T],
+) -> Result<M, Failed> {
+    let (n, m) = a.shape();
+    let mut a_w = a.clone();
+    let mut b_w = M::zeros(n, 1);
+    for i in 0..n {
+        let s = weights[i].sqrt();
+        for j in 0..m {
+            a_w.mul_element_mut(i, j, s);
+        }
+        b_w.set(i, 0, y[i] * s);
+    }
+    // the solution is stored in the first m rows of b
+    Ok(a_w.svd_solve_mut(b_w)?.slice(0..m, 0..1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+
+    #[test]
+    fn search_parameters() {
+        let parameters = QuantileRegressionSearchParameters {
+            quantile: vec![0.1, 0.9],
+            tol: vec![1e-4, 1e-6],
+            ..Default::default()
+        };
+        let mut iter = parameters.into_iter();
+        let next = iter.next().unwrap();
+        assert_eq!(next.quantile, 0.1);
+        assert_eq!(next.tol, 1e-4);
+        let next = iter.next().unwrap();
+        assert_eq!(next.quantile, 0.9);
+        assert_eq!(next.tol, 1e-4);
+        let next = iter.next().unwrap();
+        assert_eq!(next.quantile, 0.1);
+        assert_eq!(next.tol, 1e-6);
+        let next = iter.next().unwrap();
+        assert_eq!(next.quantile, 0.9);
+        assert_eq!(next.tol, 1e-6);
+        assert!(iter.next().is_none());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn median_is_robust_to_outliers() {
+        let x = DenseMatrix::from_2d_vec(&(0..21).map(|i| vec![i as f64]).collect());
+        let mut y: Vec<f64> = (0..21)
+            .map(|i| 3. * i as f64 + 2. + if i % 2 == 0 { 0.5 } else { -0.5 })
+            .collect();
+        y[4] += 100.;
+        y[15] += 100.;
+
+        let median = QuantileRegression::fit(&x, &y, Default::default()).unwrap();
+
+        assert!((median.coefficients().get(0, 0) - 3.).abs() < 0.05);
+        assert!((median.intercept() - 2.).abs() < 0.6);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn quantiles() {
+        let values: Vec<Vec<f64>> = (0..200).map(|i| vec![(i % 20) as f64]).collect();
+        let x = DenseMatrix::from_2d_vec(&values);
+        // the noise is uniform on [-5, 5)
+        let y: Vec<f64> = (0..200)
+            .map(|i| 2. * (i % 20) as f64 + ((i * 37) % 100) as f64 / 10. - 5.)
+            .collect();
+
+        for tau in [0.1, 0.5, 0.9] {
+            let qr = QuantileRegression::fit(
+                &x,
+                &y,
+                QuantileRegressionParameters::default().with_quantile(tau),
+            )
+            .unwrap();
+            let y_hat = qr.predict(&x).unwrap();
+            let below = y
+                .iter()
+                .zip(y_hat.iter())
+                .filter(|(y, y_hat)| y <= y_hat)
+                .count();
+
+            assert!((below as f64 / 200. - tau).abs() < 0.06);
+            assert!((qr.coefficients().get(0, 0) - 2.).abs() < 0.2);
+            assert!((qr.intercept() - (10. * tau - 5.)).abs() < 1.);
+        }
+    }
+
+    #[test]
+    fn invalid_quantile() {
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.]]);
+        let y: Vec<f64> = vec![1., 2., 3.];
+
+        assert!(QuantileRegression::fit(
+            &x,
+            &y,
+            QuantileRegressionParameters::default().with_quantile(1.)
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let x = DenseMatrix::from_2d_vec(&(0..10).map(|i| vec![i as f64]).collect());
+        let y: Vec<f64> = (0..10).map(|i| (i * i) as f64).collect();
+
+        let qr = QuantileRegression::fit(&x, &y, Default::default()).unwrap();
+
+        let deserialized_qr: QuantileRegression<f64, DenseMatrix<f64>> =
+            serde_json::from_str(&serde_json::to_string(&qr).unwrap()).unwrap();
+
+        assert_eq!(qr, deserialized_qr);
+    }
+}
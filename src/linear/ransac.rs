@@ -0,0 +1,421 @@
+//! # RANSAC
+//!
+//! RANdom SAmple Consensus (RANSAC) fits a regression model that is robust to outliers in the training data.
+//! Instead of fitting a model to all observations at once, RANSAC repeatedly
+//!
+//! 1. selects a random subset of `min_samples` observations,
+//! 2. fits the base estimator to the subset,
+//! 3. classifies every observation as an inlier when its absolute residual \\(\vert y_i - \hat{y}_i \vert\\) is not larger than `residual_threshold`,
+//!
+//! and keeps the model with the largest consensus set, i.e. the largest number of inliers. Ties are broken by the sum of squared residuals of the inliers.
+//! The final model is fitted to all inliers of the best consensus set.
+//!
+//! Any regressor that implements [`SupervisedEstimator`](../../api/trait.SupervisedEstimator.html) and [`Predictor`](../../api/trait.Predictor.html)
+//! can be used as the base estimator. By default the base estimator is a [linear regression](../linear_regression/index.html).
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::linear::linear_regression::LinearRegression;
+//! use smartcore::linear::ransac::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[1.], &[2.], &[3.], &[4.], &[5.], &[6.], &[7.], &[8.], &[9.], &[10.],
+//! ]);
+//! // y = 2x + 1 with two outliers
+//! let y: Vec<f64> = vec![3., 5., 7., 9., 50., 13., 15., -20., 19., 21.];
+//!
+//! let ransac: RANSACRegressor<f64, LinearRegression<f64, DenseMatrix<f64>>> =
+//!     RANSACRegressor::fit(&x, &y, RANSACRegressorParameters::default().with_seed(42)).unwrap();
+//!
+//! let y_hat = ransac.predict(&x).unwrap();
+//! let inliers = ransac.inlier_mask(); // false for the 5th and 8th observations
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Random Sample Consensus: A Paradigm for Model Fitting with Applications to Image Analysis and Automated Cartography", Fischler M. A., Bolles R. C., 1981](https://doi.org/10.1145/358669.358692)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::marker::PhantomData;
+
+use rand::seq::SliceRandom;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::linear::linear_regression::LinearRegressionParameters;
+use crate::math::num::RealNumber;
+use crate::rand::get_rng_impl;
+
+/// Parameters of the RANSAC algorithm.
+/// `B` is the type of parameters of the base estimator.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RANSACRegressorParameters<B> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of observations in every random subset. Defaults to the number of features + 1, the minimum that determines a linear model with an intercept.
+    pub min_samples: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Maximum absolute residual of an inlier. Defaults to the median absolute deviation of the target values.
+    pub residual_threshold: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Maximum number of random subsets to try.
+    pub max_trials: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Stop trying new subsets once a consensus set with at least `stop_n_inliers` inliers is found.
+    pub stop_n_inliers: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used to draw the random subsets.
+    pub seed: Option<u64>,
+    /// Parameters of the base estimator.
+    pub base_estimator: B,
+}
+
+/// RANSAC Regressor
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct RANSACRegressor<T: RealNumber, E> {
+    estimator: E,
+    inlier_mask: Vec<bool>,
+    n_trials: usize,
+    _phantom: PhantomData<T>,
+}
+
+impl<B> RANSACRegressorParameters<B> {
+    /// Number of observations in every random subset.
+    pub fn with_min_samples(mut self, min_samples: usize) -> Self {
+        self.min_samples = Some(min_samples);
+        self
+    }
+    /// Maximum absolute residual of an inlier.
+    pub fn with_residual_threshold(mut self, residual_threshold: f64) -> Self {
+        self.residual_threshold = Some(residual_threshold);
+        self
+    }
+    /// Maximum number of random subsets to try.
+    pub fn with_max_trials(mut self, max_trials: usize) -> Self {
+        self.max_trials = max_trials;
+        self
+    }
+    /// Stop trying new subsets once a consensus set with at least `stop_n_inliers` inliers is found.
+    pub fn with_stop_n_inliers(mut self, stop_n_inliers: usize) -> Self {
+        self.stop_n_inliers = Some(stop_n_inliers);
+        self
+    }
+    /// Seed used to draw the random subsets.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+    /// Parameters of the base estimator.
+    pub fn with_base_estimator<C>(self, base_estimator: C) -> RANSACRegressorParameters<C> {
+        RANSACRegressorParameters {
+            min_samples: self.min_samples,
+            residual_threshold: self.residual_threshold,
+            max_trials: self.max_trials,
+            stop_n_inliers: self.stop_n_inliers,
+            seed: self.seed,
+            base_estimator,
+        }
+    }
+}
+
+impl Default for RANSACRegressorParameters<LinearRegressionParameters> {
+    fn default() -> Self {
+        RANSACRegressorParameters {
+            min_samples: None,
+            residual_threshold: None,
+            max_trials: 100,
+            stop_n_inliers: None,
+            seed: None,
+            base_estimator: LinearRegressionParameters::default(),
+        }
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, B: Clone, E>
+    SupervisedEstimator<M, M::RowVector, RANSACRegressorParameters<B>> for RANSACRegressor<T, E>
+where
+    E: SupervisedEstimator<M, M::RowVector, B> + Predictor<M, M::RowVector>,
+{
+    fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: RANSACRegressorParameters<B>,
+    ) -> Result<Self, Failed> {
+        RANSACRegressor::fit(x, y, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, E> Predictor<M, M::RowVector> for RANSACRegressor<T, E>
+where
+    E: Predictor<M, M::RowVector>,
+{
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.estimator.predict(x)
+    }
+}
+
+impl<T: RealNumber, E> RANSACRegressor<T, E> {
+    /// Fits the base estimator to the largest consensus set found in the training data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - RANSAC parameters, including parameters of the base estimator
+    pub fn fit<M: Matrix<T>, B: Clone>(
+        x: &M,
+        y: &M::RowVector,
+        parameters: RANSACRegressorParameters<B>,
+    ) -> Result<RANSACRegressor<T, E>, Failed>
+    where
+        E: SupervisedEstimator<M, M::RowVector, B> + Predictor<M, M::RowVector>,
+    {
+        let (n, m) = x.shape();
+        if y.len() != n {
+            return Err(Failed::fit(&format!(
+                "Size of x ({}) does not match size of y ({})",
+                n,
+                y.len()
+            )));
+        }
+        let min_samples = parameters.min_samples.unwrap_or(m + 1);
+        if min_samples < 1 || min_samples > n {
+            return Err(Failed::fit(&format!(
+                "min_samples should be between 1 and the number of samples ({}), got {}",
+                n, min_samples
+            )));
+        }
+        if parameters.max_trials < 1 {
+            return Err(Failed::fit("max_trials should be > 0"));
+        }
+        let y_vec = y.to_vec();
+        let threshold = match parameters.residual_threshold {
+            Some(threshold) if threshold < 0f64 => {
+                return Err(Failed::fit("residual_threshold should be >= 0"));
+            }
+            Some(threshold) => T::from_f64(threshold).unwrap(),
+            None => median_absolute_deviation(&y_vec),
+        };
+
+        let mut rng = get_rng_impl(parameters.seed);
+        let mut indices: Vec<usize> = (0..n).collect();
+        let mut best: Option<(Vec<bool>, usize, T)> = None;
+        let mut n_trials = 0;
+
+        while n_trials < parameters.max_trials {
+            n_trials += 1;
+            let (subset, _) = indices.partial_shuffle(&mut rng, min_samples);
+            let subset = subset.to_vec();
+
+            // a degenerate subset, e.g. with collinear observations, is skipped
+            let estimator = match E::fit(
+                &x.take(&subset, 0),
+                &y.take(&subset),
+                parameters.base_estimator.clone(),
+            ) {
+                Ok(estimator) => estimator,
+                Err(_) => continue,
+            };
+            let y_hat = match estimator.predict(x) {
+                Ok(y_hat) => y_hat,
+                Err(_) => continue,
+            };
+
+            let mut inlier_mask = vec![false; n];
+            let mut n_inliers = 0;
+            let mut sse = T::zero();
+            for (i, y_i) in y_vec.iter().enumerate() {
+                let residual = (*y_i - y_hat.get(i)).abs();
+                if residual <= threshold {
+                    inlier_mask[i] = true;
+                    n_inliers += 1;
+                    sse += residual.square();
+                }
+            }
+
+            let is_better = match &best {
+                None => n_inliers > 0,
+                Some((_, best_n_inliers, best_sse)) => {
+                    n_inliers > *best_n_inliers || (n_inliers == *best_n_inliers && sse < *best_sse)
+                }
+            };
+            if is_better {
+                best = Some((inlier_mask, n_inliers, sse));
+                if matches!(parameters.stop_n_inliers, Some(stop_n_inliers) if n_inliers >= stop_n_inliers)
+                {
+                    break;
+                }
+            }
+        }
+
+        let (inlier_mask, n_inliers, _) = best.ok_or_else(|| {
+            Failed::fit(&format!(
+                "RANSAC could not find a valid consensus set in {} trials",
+                n_trials
+            ))
+        })?;
+        if n_inliers < min_samples {
+            return Err(Failed::fit(&format!(
+                "The best consensus set has {} inliers, less than min_samples ({})",
+                n_inliers, min_samples
+            )));
+        }
+
+        let inliers: Vec<usize> = (0..n).filter(|i| inlier_mask[*i]).collect();
+        let estimator = E::fit(
+            &x.take(&inliers, 0),
+            &y.take(&inliers),
+            parameters.base_estimator,
+        )?;
+
+        Ok(RANSACRegressor {
+            estimator,
+            inlier_mask,
+            n_trials,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Predict target values from `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed>
+    where
+        E: Predictor<M, M::RowVector>,
+    {
+        self.estimator.predict(x)
+    }
+
+    /// The base estimator fitted to the inliers.
+    pub fn estimator(&self) -> &E {
+        &self.estimator
+    }
+
+    /// `true` for every training observation that belongs to the consensus set.
+    pub fn inlier_mask(&self) -> &Vec<bool> {
+        &self.inlier_mask
+    }
+
+    /// Number of random subsets that were tried.
+    pub fn n_trials(&self) -> usize {
+        self.n_trials
+    }
+}
+
+fn median<T: RealNumber>(values: &mut [T]) -> T {
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = values.len();
+    if n % 2 == 1 {
+        values[n / 2]
+    } else {
+        (values[n / 2 - 1] + values[n / 2]) / T::two()
+    }
+}
+
+fn median_absolute_deviation<T: RealNumber>(values: &[T]) -> T {
+    let mut values = values.to_vec();
+    let center = median(&mut values);
+    let mut deviations: Vec<T> = values.iter().map(|v| (*v - center).abs()).collect();
+    median(&mut deviations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::linalg::BaseMatrix;
+    use crate::linear::linear_regression::LinearRegression;
+    use crate::linear::ridge_regression::{RidgeRegression, RidgeRegressionParameters};
+
+    fn line_with_outliers() -> (DenseMatrix<f64>, Vec<f64>, Vec<usize>) {
+        let values: Vec<Vec<f64>> = (0..30)
+            .map(|i| vec![i as f64, ((i * 7) % 5) as f64])
+            .collect();
+        let mut y: Vec<f64> = values
+            .iter()
+            .map(|v| 2. * v[0] - v[1] + 1. + ((v[0] * 3.) % 1.3) / 10.)
+            .collect();
+        let outliers = vec![3, 11, 17, 25];
+        for i in outliers.iter() {
+            y[*i] += 40. * if i % 2 == 0 { 1. } else { -1. };
+        }
+        (DenseMatrix::from_2d_vec(&values), y, outliers)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn ransac() {
+        let (x, y, outliers) = line_with_outliers();
+
+        let ransac: RANSACRegressor<f64, LinearRegression<f64, DenseMatrix<f64>>> =
+            RANSACRegressor::fit(
+                &x,
+                &y,
+                RANSACRegressorParameters::default()
+                    .with_residual_threshold(1.)
+                    .with_seed(1),
+            )
+            .unwrap();
+
+        for (i, inlier) in ransac.inlier_mask().iter().enumerate() {
+            assert_eq!(*inlier, !outliers.contains(&i));
+        }
+        let coefficients = ransac.estimator().coefficients();
+        assert!((coefficients.get(0, 0) - 2.).abs() < 0.05);
+        assert!((coefficients.get(1, 0) + 1.).abs() < 0.05);
+        assert!((ransac.estimator().intercept() - 1.).abs() < 0.2);
+
+        assert_eq!(ransac.predict(&x).unwrap().len(), 30);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn ransac_base_estimator() {
+        let (x, y, outliers) = line_with_outliers();
+
+        let ransac: RANSACRegressor<f64, RidgeRegression<f64, DenseMatrix<f64>>> =
+            RANSACRegressor::fit(
+                &x,
+                &y,
+                RANSACRegressorParameters::default()
+                    .with_min_samples(5)
+                    .with_stop_n_inliers(26)
+                    .with_seed(1)
+                    .with_base_estimator(RidgeRegressionParameters::default().with_alpha(0.01)),
+            )
+            .unwrap();
+
+        let n_inliers = ransac.inlier_mask().iter().filter(|i| **i).count();
+        assert!(n_inliers >= 26);
+        assert!(outliers.iter().all(|i| !ransac.inlier_mask()[*i]));
+        assert!(ransac.n_trials() <= 100);
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let (x, y, _) = line_with_outliers();
+
+        let result: Result<RANSACRegressor<f64, LinearRegression<f64, DenseMatrix<f64>>>, _> =
+            RANSACRegressor::fit(
+                &x,
+                &y,
+                RANSACRegressorParameters::default().with_min_samples(31),
+            );
+        assert!(result.is_err());
+
+        let result: Result<RANSACRegressor<f64, LinearRegression<f64, DenseMatrix<f64>>>, _> =
+            RANSACRegressor::fit(&x, &y[..10].to_vec(), RANSACRegressorParameters::default());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn mad() {
+        assert_eq!(median_absolute_deviation(&[1., 1., 2., 2., 4., 6., 9.]), 1.);
+        assert_eq!(median_absolute_deviation(&[1., 2., 3., 4.]), 1.);
+    }
+}
@@ -56,16 +56,25 @@
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
-use std::fmt::Debug;
+#[cfg(feature = "pmml")]
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Debug;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::api::{Predictor, SupervisedEstimator};
+use crate::api::{FeatureImportance, Predictor, SupervisedEstimator, SupervisedEstimatorWeighted};
 use crate::error::Failed;
 use crate::linalg::BaseVector;
 use crate::linalg::Matrix;
+use crate::linear::sample_weight::{scale_rows, validate_sample_weight, weighted_mean};
 use crate::math::num::RealNumber;
+#[cfg(feature = "onnx")]
+use crate::onnx::{self, ToOnnx};
+#[cfg(feature = "pmml")]
+use crate::pmml::{regression, FromPmml, ToPmml};
+use crate::validation::{check_fit, check_predict};
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -239,12 +248,33 @@ impl<T: RealNumber, M: Matrix<T>> SupervisedEstimator<M, M::RowVector, RidgeRegr
     }
 }
 
+impl<T: RealNumber, M: Matrix<T>>
+    SupervisedEstimatorWeighted<M, M::RowVector, RidgeRegressionParameters<T>>
+    for RidgeRegression<T, M>
+{
+    fn fit_with_weights(
+        x: &M,
+        y: &M::RowVector,
+        sample_weight: &M::RowVector,
+        parameters: RidgeRegressionParameters<T>,
+    ) -> Result<Self, Failed> {
+        RidgeRegression::fit_with_weights(x, y, sample_weight, parameters)
+    }
+}
+
 impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for RidgeRegression<T, M> {
     fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
         self.predict(x)
     }
 }
 
+impl<T: RealNumber, M: Matrix<T>> FeatureImportance<T> for RidgeRegression<T, M> {
+    fn feature_importance(&self) -> Vec<T> {
+        let (p, _) = self.coefficients.shape();
+        (0..p).map(|j| self.coefficients.get(j, 0).abs()).collect()
+    }
+}
+
 impl<T: RealNumber, M: Matrix<T>> RidgeRegression<T, M> {
     /// Fits ridge regression to your data.
     /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
@@ -255,7 +285,34 @@ impl<T: RealNumber, M: Matrix<T>> RidgeRegression<T, M> {
         y: &M::RowVector,
         parameters: RidgeRegressionParameters<T>,
     ) -> Result<RidgeRegression<T, M>, Failed> {
-        //w = inv(X^t X + alpha*Id) * X.T y
+        RidgeRegression::fit_weighted(x, y, None, parameters)
+    }
+
+    /// Fits ridge regression to your data where each observation has its own weight.
+    /// Coefficients minimize the weighted sum of squared residuals plus the penalty, \\(\sum_i w_i(y_i - \hat{y}_i)^2 + \alpha \lVert \beta \rVert^2\\).
+    /// When `normalize` is set, the mean and the standard deviation of the regressors are weighted as well.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `sample_weight` - non-negative weight of every observation
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit_with_weights(
+        x: &M,
+        y: &M::RowVector,
+        sample_weight: &M::RowVector,
+        parameters: RidgeRegressionParameters<T>,
+    ) -> Result<RidgeRegression<T, M>, Failed> {
+        let sample_weight = validate_sample_weight(x, sample_weight)?;
+        RidgeRegression::fit_weighted(x, y, Some(&sample_weight), parameters)
+    }
+
+    fn fit_weighted(
+        x: &M,
+        y: &M::RowVector,
+        sample_weight: Option<&[T]>,
+        parameters: RidgeRegressionParameters<T>,
+    ) -> Result<RidgeRegression<T, M>, Failed> {
+        check_fit(x, y)?;
+        //w = inv(X^t W X + alpha*Id) * X.T W y
 
         let (n, p) = x.shape();
 
@@ -269,10 +326,18 @@ impl<T: RealNumber, M: Matrix<T>> RidgeRegression<T, M> {
             return Err(Failed::fit("Number of rows in X should = len(y)"));
         }
 
-        let y_column = M::from_row_vector(y.clone()).transpose();
+        let mut y_column = M::from_row_vector(y.clone()).transpose();
+        if let Some(sample_weight) = sample_weight {
+            for (i, w) in sample_weight.iter().enumerate() {
+                y_column.mul_element_mut(i, 0, w.sqrt());
+            }
+        }
 
         let (w, b) = if parameters.normalize {
-            let (scaled_x, col_mean, col_std) = Self::rescale_x(x)?;
+            let (mut scaled_x, col_mean, col_std) = Self::rescale_x(x, sample_weight)?;
+            if let Some(sample_weight) = sample_weight {
+                scale_rows(&mut scaled_x, sample_weight);
+            }
             let x_t = scaled_x.transpose();
             let x_t_y = x_t.matmul(&y_column);
             let mut x_t_x = x_t.matmul(&scaled_x);
@@ -296,13 +361,21 @@ impl<T: RealNumber, M: Matrix<T>> RidgeRegression<T, M> {
                 b += w.get(i, 0) * *col_mean_i;
             }
 
-            let b = y.mean() - b;
+            let y_mean = match sample_weight {
+                Some(sample_weight) => weighted_mean(&y.to_vec(), sample_weight),
+                None => y.mean(),
+            };
+            let b = y_mean - b;
 
             (w, b)
         } else {
+            let mut x = x.clone();
+            if let Some(sample_weight) = sample_weight {
+                scale_rows(&mut x, sample_weight);
+            }
             let x_t = x.transpose();
             let x_t_y = x_t.matmul(&y_column);
-            let mut x_t_x = x_t.matmul(x);
+            let mut x_t_x = x_t.matmul(&x);
 
             for i in 0..p {
                 x_t_x.add_element_mut(i, i, parameters.alpha);
@@ -323,9 +396,23 @@ impl<T: RealNumber, M: Matrix<T>> RidgeRegression<T, M> {
         })
     }
 
-    fn rescale_x(x: &M) -> Result<(M, Vec<T>, Vec<T>), Failed> {
-        let col_mean = x.mean(0);
-        let col_std = x.std(0);
+    fn rescale_x(x: &M, sample_weight: Option<&[T]>) -> Result<(M, Vec<T>, Vec<T>), Failed> {
+        let (col_mean, col_std) = match sample_weight {
+            Some(sample_weight) => {
+                let (_, p) = x.shape();
+                let mut col_mean = Vec::with_capacity(p);
+                let mut col_std = Vec::with_capacity(p);
+                for j in 0..p {
+                    let column = x.get_col_as_vec(j);
+                    let mean = weighted_mean(&column, sample_weight);
+                    let squared: Vec<T> = column.iter().map(|v| (*v - mean).square()).collect();
+                    col_mean.push(mean);
+                    col_std.push(weighted_mean(&squared, sample_weight).sqrt());
+                }
+                (col_mean, col_std)
+            }
+            None => (x.mean(0), x.std(0)),
+        };
 
         for (i, col_std_i) in col_std.iter().enumerate() {
             if (*col_std_i - T::zero()).abs() < T::epsilon() {
@@ -344,6 +431,7 @@ impl<T: RealNumber, M: Matrix<T>> RidgeRegression<T, M> {
     /// Predict target values from `x`
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
         let (nrows, _) = x.shape();
         let mut y_hat = x.matmul(&self.coefficients);
         y_hat.add_mut(&M::fill(nrows, 1, self.intercept));
@@ -361,6 +449,35 @@ impl<T: RealNumber, M: Matrix<T>> RidgeRegression<T, M> {
     }
 }
 
+#[cfg(feature = "onnx")]
+impl<T: RealNumber, M: Matrix<T>> ToOnnx for RidgeRegression<T, M> {
+    fn to_onnx(&self) -> Result<Vec<u8>, Failed> {
+        Ok(onnx::linear_regressor(&self.coefficients, self.intercept))
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber, M: Matrix<T>> ToPmml for RidgeRegression<T, M> {
+    fn to_pmml(&self) -> Result<String, Failed> {
+        Ok(regression::linear_regression(
+            &self.coefficients,
+            self.intercept,
+        ))
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber, M: Matrix<T>> FromPmml for RidgeRegression<T, M> {
+    fn from_pmml(pmml: &str) -> Result<Self, Failed> {
+        let (coefficients, intercept) = regression::read_linear_regression(pmml)?;
+        Ok(RidgeRegression {
+            coefficients,
+            intercept,
+            _solver: RidgeRegressionSolverName::default(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -438,6 +555,57 @@ mod tests {
         assert!(mean_absolute_error(&y_hat_svd, &y) < 2.0);
     }
 
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn ridge_fit_with_weights() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 2.],
+            &[2., 1.],
+            &[3., 5.],
+            &[4., 3.],
+            &[5., 4.],
+            &[6., 8.],
+        ]);
+        let y: Vec<f64> = vec![3.1, 4.2, 8.3, 7.9, 9.6, 14.8];
+        let sample_weight = vec![1., 2., 0., 1., 3., 1.];
+
+        // integer weights are equivalent to repeated observations
+        let x_repeated = DenseMatrix::from_2d_array(&[
+            &[1., 2.],
+            &[2., 1.],
+            &[2., 1.],
+            &[4., 3.],
+            &[5., 4.],
+            &[5., 4.],
+            &[5., 4.],
+            &[6., 8.],
+        ]);
+        let y_repeated: Vec<f64> = vec![3.1, 4.2, 4.2, 7.9, 9.6, 9.6, 9.6, 14.8];
+
+        for normalize in [true, false] {
+            let parameters = RidgeRegressionParameters::default()
+                .with_alpha(0.5)
+                .with_normalize(normalize);
+            let weighted =
+                RidgeRegression::fit_with_weights(&x, &y, &sample_weight, parameters.clone())
+                    .unwrap();
+            let repeated = RidgeRegression::fit(&x_repeated, &y_repeated, parameters).unwrap();
+
+            assert!(weighted
+                .coefficients()
+                .approximate_eq(repeated.coefficients(), 1e-8));
+            assert!((weighted.intercept() - repeated.intercept()).abs() < 1e-8);
+        }
+
+        assert!(RidgeRegression::fit_with_weights(
+            &x,
+            &y,
+            &vec![0.; 6],
+            RidgeRegressionParameters::default()
+        )
+        .is_err());
+    }
+
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     #[test]
     #[cfg(feature = "serde")]
@@ -0,0 +1,53 @@
+//! Helpers shared by the linear models that support sample weights.
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use alloc::vec::Vec;
+
+/// Checks that there is a non-negative, finite weight for every row of `x` and that not all weights are zero.
+pub(crate) fn validate_sample_weight<T: RealNumber, M: Matrix<T>>(
+    x: &M,
+    sample_weight: &M::RowVector,
+) -> Result<Vec<T>, Failed> {
+    let (x_nrows, _) = x.shape();
+    let sample_weight = sample_weight.to_vec();
+    if sample_weight.len() != x_nrows {
+        return Err(Failed::fit(&format!(
+            "Size of sample_weight ({}) does not match number of rows of x ({})",
+            sample_weight.len(),
+            x_nrows
+        )));
+    }
+    if sample_weight
+        .iter()
+        .any(|w| !w.is_finite() || *w < T::zero())
+    {
+        return Err(Failed::fit("Sample weights should be non-negative"));
+    }
+    if sample_weight.iter().all(|w| *w == T::zero()) {
+        return Err(Failed::fit("At least one sample weight should be positive"));
+    }
+    Ok(sample_weight)
+}
+
+/// Multiplies every row of `x` by the square root of its weight.
+pub(crate) fn scale_rows<T: RealNumber, M: Matrix<T>>(x: &mut M, sample_weight: &[T]) {
+    let (_, ncols) = x.shape();
+    for (i, w) in sample_weight.iter().enumerate() {
+        let w = w.sqrt();
+        for j in 0..ncols {
+            x.mul_element_mut(i, j, w);
+        }
+    }
+}
+
+/// Weighted mean of `v`.
+pub(crate) fn weighted_mean<T: RealNumber>(v: &[T], sample_weight: &[T]) -> T {
+    let (sum, total) = v
+        .iter()
+        .zip(sample_weight.iter())
+        .fold((T::zero(), T::zero()), |(sum, total), (v, w)| {
+            (sum + *v * *w, total + *w)
+        });
+    sum / total
+}
@@ -0,0 +1,1300 @@
+//! # Stochastic Gradient Descent
+//!
+//! Linear models fitted with stochastic gradient descent (SGD) update the coefficients after every training sample using the gradient of the loss at that sample only.
+//! Every update is cheap, so SGD scales to datasets with a very large number of samples, and the model can be trained incrementally with `partial_fit`
+//! on batches of data that arrive over time or do not fit in memory at once.
+//!
+//! The coefficients \\(w\\) and intercept \\(b\\) minimize the regularized training error
+//!
+//! \\[E(w, b) = \frac{1}{n} \sum_{i=1}^n L(y_i, w^T x_i + b) + \alpha R(w)\\]
+//!
+//! where \\(L\\) is one of the losses in [`SGDLoss`](enum.SGDLoss.html):
+//! * `Hinge`, \\(max(0, 1 - y f(x))\\), gives a linear support vector machine,
+//! * `Log`, \\(\log(1 + e^{-y f(x)})\\), gives logistic regression,
+//! * `SquaredError`, \\(\frac{1}{2}(y - f(x))^2\\), gives ordinary least squares.
+//!
+//! and \\(R\\) is one of the penalties in [`SGDPenalty`](enum.SGDPenalty.html): \\(\frac{1}{2}\vert w \vert^2\\) (L2), \\(\vert w \vert_1\\) (L1) or a combination of both mixed by `l1_ratio` (elastic net).
+//! L1 updates are truncated at zero, so the L1 and elastic net penalties produce sparse coefficients.
+//!
+//! The step size of the \\(t\\)-th update follows one of the [`SGDLearningRate`](enum.SGDLearningRate.html) schedules.
+//! SGD is sensitive to feature scaling, standardize your features, e.g. with [`StandardScaler`](../../preprocessing/numerical/index.html), before fitting.
+//!
+//! Classification problems with more than two classes are solved one-vs-all: one binary classifier is fitted for every class.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::linear::sgd::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[-1.0, -1.2],
+//!     &[-0.8, -1.0],
+//!     &[-1.2, -0.7],
+//!     &[-0.9, -0.8],
+//!     &[1.0, 1.1],
+//!     &[0.8, 1.3],
+//!     &[1.2, 0.9],
+//!     &[1.1, 1.0],
+//! ]);
+//! let y: Vec<f64> = vec![0., 0., 0., 0., 1., 1., 1., 1.];
+//!
+//! let mut classifier = SGDClassifier::fit(&x, &y, SGDClassifierParameters::default().with_seed(42)).unwrap();
+//! let y_hat = classifier.predict(&x).unwrap();
+//!
+//! // continue training when more data arrives
+//! classifier.partial_fit(&x, &y).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Stochastic Gradient Descent Tricks", Bottou L., 2012](https://doi.org/10.1007/978-3-642-35289-8_25)
+//! * ["Solving large scale linear prediction problems using stochastic gradient descent algorithms", Zhang T., 2004](https://doi.org/10.1145/1015330.1015332)
+//! * ["Stochastic Gradient Descent Training for L1-regularized Log-linear Models with Cumulative Penalty", Tsuruoka Y., Tsujii J., Ananiadou S., 2009](https://aclanthology.org/P09-1054/)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use rand::seq::SliceRandom;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{
+    OnlineEstimator, Predictor, PredictorProba, ProbabilisticClassifier, SupervisedEstimator,
+};
+use crate::callback::{FitAction, FitCallback, FitProgress, NoCallback};
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::rand::get_rng_impl;
+use crate::validation::{check_fit, check_predict};
+
+/// Loss function minimized by SGD.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SGDLoss {
+    /// Hinge loss of a linear support vector machine, classification only.
+    Hinge,
+    /// Logistic loss of logistic regression, classification only. Enables probability estimates.
+    Log,
+    /// Squared error of least squares regression.
+    SquaredError,
+}
+
+/// Regularization penalty.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum SGDPenalty {
+    /// No regularization.
+    None,
+    /// L1 penalty, leads to sparse coefficients.
+    L1,
+    /// L2 penalty.
+    #[default]
+    L2,
+    /// Combination of L1 and L2 penalties mixed by `l1_ratio`.
+    ElasticNet,
+}
+
+/// Schedule of the step size of the `t`-th update, `t` starts from 1.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SGDLearningRate {
+    /// Constant step size `eta0`.
+    Constant,
+    /// Step size \\(\frac{1}{\alpha (t_0 + t)}\\) where \\(t_0 = \frac{1}{\alpha}\\), proposed by Bottou.
+    Optimal,
+    /// Step size \\(\frac{\eta_0}{t^{power\\_t}}\\).
+    InvScaling,
+}
+
+/// SGD classifier parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct SGDClassifierParameters<T: RealNumber> {
+    /// Loss function, `Hinge`, `Log` or `SquaredError`.
+    pub loss: SGDLoss,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Regularization penalty.
+    pub penalty: SGDPenalty,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Regularization strength.
+    pub alpha: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The elastic net mixing parameter, with 0 <= l1_ratio <= 1. Used with the `ElasticNet` penalty only.
+    pub l1_ratio: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to fit the intercept.
+    pub fit_intercept: bool,
+    /// Learning rate schedule.
+    pub learning_rate: SGDLearningRate,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Initial step size of the `Constant` and `InvScaling` schedules.
+    pub eta0: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Exponent of the `InvScaling` schedule.
+    pub power_t: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of passes over the training data (epochs).
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Training stops when the training loss has not improved by at least `tol` for `n_iter_no_change` consecutive epochs.
+    pub tol: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of epochs with no improvement to wait before stopping.
+    pub n_iter_no_change: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to shuffle the training data before every epoch.
+    pub shuffle: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used to shuffle the training data.
+    pub seed: Option<u64>,
+}
+
+/// SGD regressor parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct SGDRegressorParameters<T: RealNumber> {
+    /// Loss function, only `SquaredError` applies to regression.
+    pub loss: SGDLoss,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Regularization penalty.
+    pub penalty: SGDPenalty,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Regularization strength.
+    pub alpha: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The elastic net mixing parameter, with 0 <= l1_ratio <= 1. Used with the `ElasticNet` penalty only.
+    pub l1_ratio: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to fit the intercept.
+    pub fit_intercept: bool,
+    /// Learning rate schedule.
+    pub learning_rate: SGDLearningRate,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Initial step size of the `Constant` and `InvScaling` schedules.
+    pub eta0: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Exponent of the `InvScaling` schedule.
+    pub power_t: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of passes over the training data (epochs).
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Training stops when the training loss has not improved by at least `tol` for `n_iter_no_change` consecutive epochs.
+    pub tol: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of epochs with no improvement to wait before stopping.
+    pub n_iter_no_change: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to shuffle the training data before every epoch.
+    pub shuffle: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used to shuffle the training data.
+    pub seed: Option<u64>,
+}
+
+/// Linear classifier fitted with stochastic gradient descent
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct SGDClassifier<T: RealNumber> {
+    settings: Settings<T>,
+    classes: Vec<T>,
+    models: Vec<LinearModel<T>>,
+    t: usize,
+    n_iter: usize,
+}
+
+/// Linear regressor fitted with stochastic gradient descent
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct SGDRegressor<T: RealNumber> {
+    settings: Settings<T>,
+    model: LinearModel<T>,
+    t: usize,
+    n_iter: usize,
+}
+
+impl<T: RealNumber> SGDClassifierParameters<T> {
+    /// Loss function, `Hinge`, `Log` or `SquaredError`.
+    pub fn with_loss(mut self, loss: SGDLoss) -> Self {
+        self.loss = loss;
+        self
+    }
+    /// Regularization penalty.
+    pub fn with_penalty(mut self, penalty: SGDPenalty) -> Self {
+        self.penalty = penalty;
+        self
+    }
+    /// Regularization strength.
+    pub fn with_alpha(mut self, alpha: T) -> Self {
+        self.alpha = alpha;
+        self
+    }
+    /// The elastic net mixing parameter, with 0 <= l1_ratio <= 1.
+    pub fn with_l1_ratio(mut self, l1_ratio: T) -> Self {
+        self.l1_ratio = l1_ratio;
+        self
+    }
+    /// Whether to fit the intercept.
+    pub fn with_fit_intercept(mut self, fit_intercept: bool) -> Self {
+        self.fit_intercept = fit_intercept;
+        self
+    }
+    /// Learning rate schedule.
+    pub fn with_learning_rate(mut self, learning_rate: SGDLearningRate) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+    /// Initial step size of the `Constant` and `InvScaling` schedules.
+    pub fn with_eta0(mut self, eta0: T) -> Self {
+        self.eta0 = eta0;
+        self
+    }
+    /// Exponent of the `InvScaling` schedule.
+    pub fn with_power_t(mut self, power_t: T) -> Self {
+        self.power_t = power_t;
+        self
+    }
+    /// The maximum number of passes over the training data (epochs).
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Minimum improvement of the training loss.
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Number of epochs with no improvement to wait before stopping.
+    pub fn with_n_iter_no_change(mut self, n_iter_no_change: usize) -> Self {
+        self.n_iter_no_change = n_iter_no_change;
+        self
+    }
+    /// Whether to shuffle the training data before every epoch.
+    pub fn with_shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+    /// Seed used to shuffle the training data.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl<T: RealNumber> SGDRegressorParameters<T> {
+    /// Loss function, only `SquaredError` applies to regression.
+    pub fn with_loss(mut self, loss: SGDLoss) -> Self {
+        self.loss = loss;
+        self
+    }
+    /// Regularization penalty.
+    pub fn with_penalty(mut self, penalty: SGDPenalty) -> Self {
+        self.penalty = penalty;
+        self
+    }
+    /// Regularization strength.
+    pub fn with_alpha(mut self, alpha: T) -> Self {
+        self.alpha = alpha;
+        self
+    }
+    /// The elastic net mixing parameter, with 0 <= l1_ratio <= 1.
+    pub fn with_l1_ratio(mut self, l1_ratio: T) -> Self {
+        self.l1_ratio = l1_ratio;
+        self
+    }
+    /// Whether to fit the intercept.
+    pub fn with_fit_intercept(mut self, fit_intercept: bool) -> Self {
+        self.fit_intercept = fit_intercept;
+        self
+    }
+    /// Learning rate schedule.
+    pub fn with_learning_rate(mut self, learning_rate: SGDLearningRate) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+    /// Initial step size of the `Constant` and `InvScaling` schedules.
+    pub fn with_eta0(mut self, eta0: T) -> Self {
+        self.eta0 = eta0;
+        self
+    }
+    /// Exponent of the `InvScaling` schedule.
+    pub fn with_power_t(mut self, power_t: T) -> Self {
+        self.power_t = power_t;
+        self
+    }
+    /// The maximum number of passes over the training data (epochs).
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Minimum improvement of the training loss.
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Number of epochs with no improvement to wait before stopping.
+    pub fn with_n_iter_no_change(mut self, n_iter_no_change: usize) -> Self {
+        self.n_iter_no_change = n_iter_no_change;
+        self
+    }
+    /// Whether to shuffle the training data before every epoch.
+    pub fn with_shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+    /// Seed used to shuffle the training data.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl<T: RealNumber> Default for SGDClassifierParameters<T> {
+    fn default() -> Self {
+        SGDClassifierParameters {
+            loss: SGDLoss::Hinge,
+            penalty: SGDPenalty::L2,
+            alpha: T::from_f64(1e-4).unwrap(),
+            l1_ratio: T::from_f64(0.15).unwrap(),
+            fit_intercept: true,
+            learning_rate: SGDLearningRate::Optimal,
+            eta0: T::from_f64(0.01).unwrap(),
+            power_t: T::from_f64(0.5).unwrap(),
+            max_iter: 1000,
+            tol: T::from_f64(1e-3).unwrap(),
+            n_iter_no_change: 5,
+            shuffle: true,
+            seed: None,
+        }
+    }
+}
+
+impl<T: RealNumber> Default for SGDRegressorParameters<T> {
+    fn default() -> Self {
+        SGDRegressorParameters {
+            loss: SGDLoss::SquaredError,
+            penalty: SGDPenalty::L2,
+            alpha: T::from_f64(1e-4).unwrap(),
+            l1_ratio: T::from_f64(0.15).unwrap(),
+            fit_intercept: true,
+            learning_rate: SGDLearningRate::InvScaling,
+            eta0: T::from_f64(0.01).unwrap(),
+            power_t: T::from_f64(0.25).unwrap(),
+            max_iter: 1000,
+            tol: T::from_f64(1e-3).unwrap(),
+            n_iter_no_change: 5,
+            shuffle: true,
+            seed: None,
+        }
+    }
+}
+
+impl<T: RealNumber> PartialEq for SGDClassifier<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.classes == other.classes && self.models == other.models
+    }
+}
+
+impl<T: RealNumber> PartialEq for SGDRegressor<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.model == other.model
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> SupervisedEstimator<M, M::RowVector, SGDClassifierParameters<T>>
+    for SGDClassifier<T>
+{
+    fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: SGDClassifierParameters<T>,
+    ) -> Result<Self, Failed> {
+        SGDClassifier::fit(x, y, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for SGDClassifier<T> {
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> OnlineEstimator<M, M::RowVector> for SGDClassifier<T> {
+    fn partial_fit(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        self.partial_fit(x, y)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> PredictorProba<M> for SGDClassifier<T> {
+    fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.predict_proba(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> ProbabilisticClassifier<T, M> for SGDClassifier<T> {
+    fn classes(&self) -> &Vec<T> {
+        self.classes()
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> SupervisedEstimator<M, M::RowVector, SGDRegressorParameters<T>>
+    for SGDRegressor<T>
+{
+    fn fit(x: &M, y: &M::RowVector, parameters: SGDRegressorParameters<T>) -> Result<Self, Failed> {
+        SGDRegressor::fit(x, y, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for SGDRegressor<T> {
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> OnlineEstimator<M, M::RowVector> for SGDRegressor<T> {
+    fn partial_fit(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        self.partial_fit(x, y)
+    }
+}
+
+impl<T: RealNumber> SGDClassifier<T> {
+    /// Creates an untrained classifier with zero coefficients to be trained incrementally with [`partial_fit`](SGDClassifier::partial_fit).
+    /// * `classes` - all class labels the classifier will see during training.
+    /// * `num_features` - number of features of every observation.
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn new(
+        classes: &[T],
+        num_features: usize,
+        parameters: SGDClassifierParameters<T>,
+    ) -> Result<SGDClassifier<T>, Failed> {
+        let settings = Settings::from(&parameters);
+        settings.validate()?;
+
+        let mut classes = classes.to_vec();
+        classes.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        classes.dedup();
+        if classes.len() < 2 {
+            return Err(Failed::fit(&format!(
+                "Incorrect number of classes: {}. Should be >= 2.",
+                classes.len()
+            )));
+        }
+
+        let n_models = if classes.len() == 2 { 1 } else { classes.len() };
+        Ok(SGDClassifier {
+            settings,
+            classes,
+            models: vec![LinearModel::new(num_features); n_models],
+            t: 1,
+            n_iter: 0,
+        })
+    }
+
+    /// Fits the classifier with up to `max_iter` passes over the training data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target class values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit<M: Matrix<T>>(
+        x: &M,
+        y: &M::RowVector,
+        parameters: SGDClassifierParameters<T>,
+    ) -> Result<SGDClassifier<T>, Failed> {
+        SGDClassifier::fit_with_callback(x, y, parameters, &mut NoCallback)
+    }
+
+    /// Fits the classifier like `fit` and calls `callback` with the mean training loss after every epoch, see [callbacks](../../callback/index.html).
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target class values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    /// * `callback` - monitors the training and can stop it
+    pub fn fit_with_callback<M: Matrix<T>>(
+        x: &M,
+        y: &M::RowVector,
+        parameters: SGDClassifierParameters<T>,
+        callback: &mut dyn FitCallback<T>,
+    ) -> Result<SGDClassifier<T>, Failed> {
+        check_fit(x, y)?;
+        let mut classifier = SGDClassifier::new(&y.unique(), x.shape().1, parameters)?;
+        let (rows, targets) = classifier.training_data(x, y)?;
+        classifier.n_iter = train(
+            &classifier.settings,
+            &mut classifier.models,
+            &rows,
+            &targets,
+            &mut classifier.t,
+            callback,
+        );
+        Ok(classifier)
+    }
+
+    /// Updates the classifier with a single pass over `x`, in the order of the rows.
+    /// Use it to train the classifier incrementally on batches of data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target class values, every value should be one of the classes of the classifier.
+    pub fn partial_fit<M: Matrix<T>>(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        check_fit(x, y)?;
+        let (rows, targets) = self.training_data(x, y)?;
+        let order: Vec<usize> = (0..rows.len()).collect();
+        train_epoch(
+            &self.settings,
+            &mut self.models,
+            &rows,
+            &targets,
+            &order,
+            &mut self.t,
+        );
+        self.n_iter += 1;
+        Ok(())
+    }
+
+    /// Predict class labels for `x`.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed> {
+        let decision = self.decision_function(x)?;
+        let (n, k) = decision.shape();
+        let mut result = M::RowVector::zeros(n);
+        for i in 0..n {
+            let class = if k == 1 {
+                usize::from(decision.get(i, 0) > T::zero())
+            } else {
+                let mut best = 0;
+                for j in 1..k {
+                    if decision.get(i, j) > decision.get(i, best) {
+                        best = j;
+                    }
+                }
+                best
+            };
+            result.set(i, self.classes[class]);
+        }
+        Ok(result)
+    }
+
+    /// Signed distance of every observation to the hyperplane of every binary classifier.
+    /// Returns _Kx1_ matrix for binary problems, where positive values predict the second class, and _KxC_ matrix for _C_ > 2 classes.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn decision_function<M: Matrix<T>>(&self, x: &M) -> Result<M, Failed> {
+        check_predict(x)?;
+        check_features(x, self.models[0].coefficients.len())?;
+        let (n, m) = x.shape();
+        let mut row = vec![T::zero(); m];
+        let mut result = M::zeros(n, self.models.len());
+        for i in 0..n {
+            x.copy_row_as_vec(i, &mut row);
+            for (j, model) in self.models.iter().enumerate() {
+                result.set(i, j, model.decision(&row));
+            }
+        }
+        Ok(result)
+    }
+
+    /// Predict class probabilities for `x`, available with the `Log` loss only. Returns _KxC_ matrix, columns follow the order of sorted class labels.
+    /// Multiclass probabilities are the normalized probabilities of the one-vs-all classifiers.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_proba<M: Matrix<T>>(&self, x: &M) -> Result<M, Failed> {
+        if self.settings.loss != SGDLoss::Log {
+            return Err(Failed::predict(
+                "Probability estimates are only available for the Log loss",
+            ));
+        }
+        let decision = self.decision_function(x)?;
+        let (n, k) = decision.shape();
+        let mut result = M::zeros(n, self.classes.len());
+        for i in 0..n {
+            if k == 1 {
+                let p = decision.get(i, 0).sigmoid();
+                result.set(i, 0, T::one() - p);
+                result.set(i, 1, p);
+            } else {
+                let p: Vec<T> = (0..k).map(|j| decision.get(i, j).sigmoid()).collect();
+                let total = p.iter().fold(T::zero(), |acc, p_j| acc + *p_j);
+                for (j, p_j) in p.iter().enumerate() {
+                    result.set(i, j, *p_j / total);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Class labels, sorted.
+    pub fn classes(&self) -> &Vec<T> {
+        &self.classes
+    }
+
+    /// Coefficients of every binary classifier: one vector for binary problems, one vector per class otherwise.
+    pub fn coefficients(&self) -> Vec<&Vec<T>> {
+        self.models.iter().map(|m| &m.coefficients).collect()
+    }
+
+    /// Intercept of every binary classifier.
+    pub fn intercepts(&self) -> Vec<T> {
+        self.models.iter().map(|m| m.intercept).collect()
+    }
+
+    /// Number of passes over the training data made so far, including calls to `partial_fit`.
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+
+    fn training_data<M: Matrix<T>>(
+        &self,
+        x: &M,
+        y: &M::RowVector,
+    ) -> Result<(Vec<Vec<T>>, Vec<Vec<T>>), Failed> {
+        let (n, _) = x.shape();
+        if y.len() != n {
+            return Err(Failed::fit("Number of rows in X should = len(y)"));
+        }
+        check_features(x, self.models[0].coefficients.len())?;
+
+        let mut targets = vec![vec![-T::one(); n]; self.models.len()];
+        for (i, y_i) in y.to_vec().into_iter().enumerate() {
+            let class = self
+                .classes
+                .iter()
+                .position(|c| *c == y_i)
+                .ok_or_else(|| Failed::fit(&format!("Unknown class: {}", y_i)))?;
+            match self.models.len() {
+                1 if class == 1 => targets[0][i] = T::one(),
+                1 => {}
+                _ => targets[class][i] = T::one(),
+            }
+        }
+        Ok(((0..n).map(|i| x.get_row_as_vec(i)).collect(), targets))
+    }
+}
+
+impl<T: RealNumber> SGDRegressor<T> {
+    /// Creates an untrained regressor with zero coefficients to be trained incrementally with [`partial_fit`](SGDRegressor::partial_fit).
+    /// * `num_features` - number of features of every observation.
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn new(
+        num_features: usize,
+        parameters: SGDRegressorParameters<T>,
+    ) -> Result<SGDRegressor<T>, Failed> {
+        let settings = Settings::from(&parameters);
+        settings.validate()?;
+        if parameters.loss != SGDLoss::SquaredError {
+            return Err(Failed::fit(&format!(
+                "Loss {:?} is not a regression loss",
+                parameters.loss
+            )));
+        }
+        Ok(SGDRegressor {
+            settings,
+            model: LinearModel::new(num_features),
+            t: 1,
+            n_iter: 0,
+        })
+    }
+
+    /// Fits the regressor with up to `max_iter` passes over the training data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit<M: Matrix<T>>(
+        x: &M,
+        y: &M::RowVector,
+        parameters: SGDRegressorParameters<T>,
+    ) -> Result<SGDRegressor<T>, Failed> {
+        SGDRegressor::fit_with_callback(x, y, parameters, &mut NoCallback)
+    }
+
+    /// Fits the regressor like `fit` and calls `callback` with the mean training loss after every epoch, see [callbacks](../../callback/index.html).
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    /// * `callback` - monitors the training and can stop it
+    pub fn fit_with_callback<M: Matrix<T>>(
+        x: &M,
+        y: &M::RowVector,
+        parameters: SGDRegressorParameters<T>,
+        callback: &mut dyn FitCallback<T>,
+    ) -> Result<SGDRegressor<T>, Failed> {
+        check_fit(x, y)?;
+        let mut regressor = SGDRegressor::new(x.shape().1, parameters)?;
+        let (rows, targets) = regressor.training_data(x, y)?;
+        regressor.n_iter = train(
+            &regressor.settings,
+            core::slice::from_mut(&mut regressor.model),
+            &rows,
+            &targets,
+            &mut regressor.t,
+            callback,
+        );
+        Ok(regressor)
+    }
+
+    /// Updates the regressor with a single pass over `x`, in the order of the rows.
+    /// Use it to train the regressor incrementally on batches of data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    pub fn partial_fit<M: Matrix<T>>(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        check_fit(x, y)?;
+        let (rows, targets) = self.training_data(x, y)?;
+        let order: Vec<usize> = (0..rows.len()).collect();
+        train_epoch(
+            &self.settings,
+            core::slice::from_mut(&mut self.model),
+            &rows,
+            &targets,
+            &order,
+            &mut self.t,
+        );
+        self.n_iter += 1;
+        Ok(())
+    }
+
+    /// Predict target values from `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
+        check_features(x, self.model.coefficients.len())?;
+        let (n, m) = x.shape();
+        let mut row = vec![T::zero(); m];
+        let mut result = M::RowVector::zeros(n);
+        for i in 0..n {
+            x.copy_row_as_vec(i, &mut row);
+            result.set(i, self.model.decision(&row));
+        }
+        Ok(result)
+    }
+
+    /// Get estimates regression coefficients
+    pub fn coefficients(&self) -> &Vec<T> {
+        &self.model.coefficients
+    }
+
+    /// Get estimate of intercept
+    pub fn intercept(&self) -> T {
+        self.model.intercept
+    }
+
+    /// Number of passes over the training data made so far, including calls to `partial_fit`.
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn training_data<M: Matrix<T>>(
+        &self,
+        x: &M,
+        y: &M::RowVector,
+    ) -> Result<(Vec<Vec<T>>, Vec<Vec<T>>), Failed> {
+        let (n, _) = x.shape();
+        if y.len() != n {
+            return Err(Failed::fit("Number of rows in X should = len(y)"));
+        }
+        check_features(x, self.model.coefficients.len())?;
+        Ok((
+            (0..n).map(|i| x.get_row_as_vec(i)).collect(),
+            vec![y.to_vec()],
+        ))
+    }
+}
+
+fn check_features<T: RealNumber, M: Matrix<T>>(x: &M, num_features: usize) -> Result<(), Failed> {
+    if x.shape().1 != num_features {
+        return Err(Failed::fit(&format!(
+            "Number of features of x ({}) does not match the number of coefficients ({})",
+            x.shape().1,
+            num_features
+        )));
+    }
+    Ok(())
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+struct LinearModel<T: RealNumber> {
+    coefficients: Vec<T>,
+    intercept: T,
+    /// Total L1 penalty every coefficient could have received so far.
+    l1_total: T,
+    /// L1 penalty actually applied to every coefficient so far.
+    l1_applied: Vec<T>,
+}
+
+impl<T: RealNumber> LinearModel<T> {
+    fn new(num_features: usize) -> LinearModel<T> {
+        LinearModel {
+            coefficients: vec![T::zero(); num_features],
+            intercept: T::zero(),
+            l1_total: T::zero(),
+            l1_applied: vec![T::zero(); num_features],
+        }
+    }
+
+    fn decision(&self, x: &[T]) -> T {
+        self.coefficients
+            .iter()
+            .zip(x.iter())
+            .fold(self.intercept, |acc, (w, x)| acc + *w * *x)
+    }
+}
+
+/// Training settings shared by the classifier and the regressor.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+struct Settings<T: RealNumber> {
+    loss: SGDLoss,
+    l1: T,
+    l2: T,
+    alpha: T,
+    fit_intercept: bool,
+    learning_rate: SGDLearningRate,
+    eta0: T,
+    power_t: T,
+    max_iter: usize,
+    tol: T,
+    n_iter_no_change: usize,
+    shuffle: bool,
+    seed: Option<u64>,
+}
+
+impl<T: RealNumber> Settings<T> {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        loss: &SGDLoss,
+        penalty: &SGDPenalty,
+        alpha: T,
+        l1_ratio: T,
+        fit_intercept: bool,
+        learning_rate: &SGDLearningRate,
+        eta0: T,
+        power_t: T,
+        max_iter: usize,
+        tol: T,
+        n_iter_no_change: usize,
+        shuffle: bool,
+        seed: Option<u64>,
+    ) -> Settings<T> {
+        let l1_ratio = match penalty {
+            SGDPenalty::None => T::zero(),
+            SGDPenalty::L1 => T::one(),
+            SGDPenalty::L2 => T::zero(),
+            SGDPenalty::ElasticNet => l1_ratio,
+        };
+        let penalty_alpha = if *penalty == SGDPenalty::None {
+            T::zero()
+        } else {
+            alpha
+        };
+        Settings {
+            loss: loss.clone(),
+            l1: penalty_alpha * l1_ratio,
+            l2: penalty_alpha * (T::one() - l1_ratio),
+            alpha,
+            fit_intercept,
+            learning_rate: learning_rate.clone(),
+            eta0,
+            power_t,
+            max_iter,
+            tol,
+            n_iter_no_change,
+            shuffle,
+            seed,
+        }
+    }
+
+    fn validate(&self) -> Result<(), Failed> {
+        if self.l1 < T::zero() || self.l2 < T::zero() {
+            return Err(Failed::fit(
+                "alpha should be >= 0 and l1_ratio should be between 0 and 1",
+            ));
+        }
+        if self.learning_rate == SGDLearningRate::Optimal && self.alpha <= T::zero() {
+            return Err(Failed::fit(
+                "alpha should be > 0 with the Optimal learning rate",
+            ));
+        }
+        if self.learning_rate != SGDLearningRate::Optimal && self.eta0 <= T::zero() {
+            return Err(Failed::fit("eta0 should be > 0"));
+        }
+        Ok(())
+    }
+
+    fn eta(&self, t: usize) -> T {
+        let t = T::from_usize(t).unwrap();
+        match self.learning_rate {
+            SGDLearningRate::Constant => self.eta0,
+            SGDLearningRate::Optimal => T::one() / (self.alpha * (T::one() / self.alpha + t)),
+            SGDLearningRate::InvScaling => self.eta0 / t.powf(self.power_t),
+        }
+    }
+
+    /// Loss of prediction `p` and its derivative with respect to `p`.
+    fn loss(&self, p: T, y: T) -> (T, T) {
+        match self.loss {
+            SGDLoss::Hinge => {
+                let z = y * p;
+                if z < T::one() {
+                    (T::one() - z, -y)
+                } else {
+                    (T::zero(), T::zero())
+                }
+            }
+            SGDLoss::Log => {
+                let z = y * p;
+                ((-z).ln_1pe(), -y * (-z).sigmoid())
+            }
+            SGDLoss::SquaredError => ((p - y).square() * T::half(), p - y),
+        }
+    }
+}
+
+impl<T: RealNumber> From<&SGDClassifierParameters<T>> for Settings<T> {
+    fn from(parameters: &SGDClassifierParameters<T>) -> Self {
+        Settings::new(
+            &parameters.loss,
+            &parameters.penalty,
+            parameters.alpha,
+            parameters.l1_ratio,
+            parameters.fit_intercept,
+            &parameters.learning_rate,
+            parameters.eta0,
+            parameters.power_t,
+            parameters.max_iter,
+            parameters.tol,
+            parameters.n_iter_no_change,
+            parameters.shuffle,
+            parameters.seed,
+        )
+    }
+}
+
+impl<T: RealNumber> From<&SGDRegressorParameters<T>> for Settings<T> {
+    fn from(parameters: &SGDRegressorParameters<T>) -> Self {
+        Settings::new(
+            &parameters.loss,
+            &parameters.penalty,
+            parameters.alpha,
+            parameters.l1_ratio,
+            parameters.fit_intercept,
+            &parameters.learning_rate,
+            parameters.eta0,
+            parameters.power_t,
+            parameters.max_iter,
+            parameters.tol,
+            parameters.n_iter_no_change,
+            parameters.shuffle,
+            parameters.seed,
+        )
+    }
+}
+
+/// Runs up to `max_iter` epochs, stops early when the training loss stops improving or the callback asks to stop. Returns the number of epochs.
+fn train<T: RealNumber>(
+    settings: &Settings<T>,
+    models: &mut [LinearModel<T>],
+    rows: &[Vec<T>],
+    targets: &[Vec<T>],
+    t: &mut usize,
+    callback: &mut dyn FitCallback<T>,
+) -> usize {
+    let mut rng = get_rng_impl(settings.seed);
+    let mut order: Vec<usize> = (0..rows.len()).collect();
+    let mut best_loss = T::max_value();
+    let mut no_improvement = 0;
+
+    for epoch in 1..=settings.max_iter {
+        if settings.shuffle {
+            order.shuffle(&mut rng);
+        }
+        let loss = train_epoch(settings, models, rows, targets, &order, t);
+
+        if loss > best_loss - settings.tol {
+            no_improvement += 1;
+        } else {
+            no_improvement = 0;
+        }
+        best_loss = best_loss.min(loss);
+
+        let progress = FitProgress {
+            iteration: epoch,
+            loss: Some(loss),
+            validation_score: None,
+        };
+        let action = callback.on_iteration(&progress);
+        if no_improvement >= settings.n_iter_no_change || action == FitAction::Stop {
+            return epoch;
+        }
+    }
+
+    settings.max_iter
+}
+
+/// Single pass over the samples in `order`. Returns the mean loss of the samples before their updates.
+fn train_epoch<T: RealNumber>(
+    settings: &Settings<T>,
+    models: &mut [LinearModel<T>],
+    rows: &[Vec<T>],
+    targets: &[Vec<T>],
+    order: &[usize],
+    t: &mut usize,
+) -> T {
+    let mut total_loss = T::zero();
+
+    for i in order.iter() {
+        let eta = settings.eta(*t);
+        let row = &rows[*i];
+        for (model, y) in models.iter_mut().zip(targets.iter()) {
+            let (loss, dloss) = settings.loss(model.decision(row), y[*i]);
+            total_loss += loss;
+
+            let decay = T::one() - eta * settings.l2;
+            for (w, x) in model.coefficients.iter_mut().zip(row.iter()) {
+                *w = *w * decay - eta * dloss * *x;
+            }
+            if settings.fit_intercept {
+                model.intercept -= eta * dloss;
+            }
+
+            // cumulative L1 penalty, coefficients are clipped at zero instead of crossing it
+            if settings.l1 > T::zero() {
+                model.l1_total += eta * settings.l1;
+                let l1_total = model.l1_total;
+                for (w, q) in model
+                    .coefficients
+                    .iter_mut()
+                    .zip(model.l1_applied.iter_mut())
+                {
+                    let z = *w;
+                    if z > T::zero() {
+                        *w = (z - (l1_total + *q)).max(T::zero());
+                    } else if z < T::zero() {
+                        *w = (z + (l1_total - *q)).min(T::zero());
+                    }
+                    *q += *w - z;
+                }
+            }
+        }
+        *t += 1;
+    }
+
+    total_loss / T::from_usize(order.len().max(1)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::metrics::*;
+
+    fn blobs() -> (DenseMatrix<f64>, Vec<f64>) {
+        let mut values = Vec::new();
+        let mut y = Vec::new();
+        for i in 0..60 {
+            let class = i % 3;
+            let jitter = ((i * 37) % 11) as f64 / 20. - 0.25;
+            let (a, b) = match class {
+                0 => (-1., -1.),
+                1 => (1., -1.),
+                _ => (0., 1.),
+            };
+            values.push(vec![a + jitter, b - jitter]);
+            y.push(class as f64);
+        }
+        (DenseMatrix::from_2d_vec(&values), y)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn classifier_losses() {
+        let (x, y) = blobs();
+
+        for loss in [SGDLoss::Hinge, SGDLoss::Log, SGDLoss::SquaredError] {
+            let classifier = SGDClassifier::fit(
+                &x,
+                &y,
+                SGDClassifierParameters::default()
+                    .with_loss(loss.clone())
+                    .with_learning_rate(SGDLearningRate::InvScaling)
+                    .with_eta0(0.1)
+                    .with_seed(1),
+            )
+            .unwrap();
+            let y_hat = classifier.predict(&x).unwrap();
+
+            assert!(accuracy(&y, &y_hat) > 0.9, "{:?}", loss);
+            assert_eq!(classifier.coefficients().len(), 3);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn binary_predict_proba() {
+        let (x, y) = blobs();
+        let y: Vec<f64> = y
+            .iter()
+            .map(|y_i| if *y_i == 2. { 1. } else { 0. })
+            .collect();
+
+        let classifier = SGDClassifier::fit(
+            &x,
+            &y,
+            SGDClassifierParameters::default()
+                .with_loss(SGDLoss::Log)
+                .with_seed(1),
+        )
+        .unwrap();
+        let probabilities = classifier.predict_proba(&x).unwrap();
+        let y_hat = classifier.predict(&x).unwrap();
+
+        assert_eq!(classifier.coefficients().len(), 1);
+        assert!(accuracy(&y, &y_hat) > 0.95);
+        for (i, &y_hat_i) in y_hat.iter().enumerate() {
+            assert!((probabilities.get(i, 0) + probabilities.get(i, 1) - 1.).abs() < 1e-8);
+            if y_hat_i == 1. {
+                assert!(probabilities.get(i, 1) > 0.5);
+            } else {
+                assert!(probabilities.get(i, 1) <= 0.5);
+            }
+        }
+
+        let hinge =
+            SGDClassifier::fit(&x, &y, SGDClassifierParameters::default().with_seed(1)).unwrap();
+        assert!(hinge.predict_proba(&x).is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn regressor() {
+        let values: Vec<Vec<f64>> = (0..50)
+            .map(|i| vec![(i % 10) as f64 / 5. - 1., (i % 7) as f64 / 3.5 - 1., 0.5])
+            .collect();
+        let x = DenseMatrix::from_2d_vec(&values);
+        let y: Vec<f64> = values.iter().map(|v| 3. * v[0] - 2. * v[1] + 1.).collect();
+
+        let regressor = SGDRegressor::fit(
+            &x,
+            &y,
+            SGDRegressorParameters::default()
+                .with_penalty(SGDPenalty::None)
+                .with_tol(1e-6)
+                .with_seed(1),
+        )
+        .unwrap();
+        let y_hat = regressor.predict(&x).unwrap();
+
+        assert!(mean_squared_error(&y, &y_hat) < 1e-3);
+        assert!((regressor.coefficients()[0] - 3.).abs() < 0.05);
+        assert!((regressor.coefficients()[1] + 2.).abs() < 0.05);
+        assert!(regressor.n_iter() < 1000);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn l1_penalty_is_sparse() {
+        let values: Vec<Vec<f64>> = (0..50)
+            .map(|i| vec![(i % 10) as f64 / 5. - 1., ((i * 7) % 13) as f64 / 6.5 - 1.])
+            .collect();
+        let x = DenseMatrix::from_2d_vec(&values);
+        let y: Vec<f64> = values.iter().map(|v| 2. * v[0]).collect();
+
+        let regressor = SGDRegressor::fit(
+            &x,
+            &y,
+            SGDRegressorParameters::default()
+                .with_penalty(SGDPenalty::L1)
+                .with_alpha(0.05)
+                .with_seed(1),
+        )
+        .unwrap();
+
+        assert_eq!(regressor.coefficients()[1], 0.);
+        assert!(regressor.coefficients()[0] > 1.5);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn partial_fit() {
+        let (x, y) = blobs();
+
+        let mut classifier =
+            SGDClassifier::new(&[0., 1., 2.], 2, SGDClassifierParameters::default()).unwrap();
+        for _ in 0..20 {
+            classifier.partial_fit(&x, &y).unwrap();
+        }
+        let y_hat = classifier.predict(&x).unwrap();
+
+        assert_eq!(classifier.n_iter(), 20);
+        assert!(accuracy(&y, &y_hat) > 0.9);
+        assert!(classifier.partial_fit(&x, &vec![3.; 60]).is_err());
+
+        let mut regressor = SGDRegressor::new(2, SGDRegressorParameters::default()).unwrap();
+        let y: Vec<f64> = (0..60).map(|i| x.get(i, 0) - x.get(i, 1)).collect();
+        let mut previous = f64::MAX;
+        for _ in 0..5 {
+            regressor.partial_fit(&x, &y).unwrap();
+            let mse = mean_squared_error(&y, &regressor.predict(&x).unwrap());
+            assert!(mse < previous);
+            previous = mse;
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_with_callback() {
+        let (x, y) = blobs();
+
+        let mut epochs = Vec::new();
+        let classifier = SGDClassifier::fit_with_callback(
+            &x,
+            &y,
+            SGDClassifierParameters::default().with_seed(1),
+            &mut |progress: &FitProgress<f64>| {
+                epochs.push(progress.iteration);
+                assert!(progress.loss.unwrap() >= 0.);
+                if progress.iteration < 4 {
+                    FitAction::Continue
+                } else {
+                    FitAction::Stop
+                }
+            },
+        )
+        .unwrap();
+
+        assert_eq!(epochs, vec![1, 2, 3, 4]);
+        assert_eq!(classifier.n_iter(), 4);
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let (x, y) = blobs();
+
+        assert!(
+            SGDClassifier::fit(&x, &y, SGDClassifierParameters::default().with_alpha(0.)).is_err()
+        );
+        assert!(SGDRegressor::fit(
+            &x,
+            &y,
+            SGDRegressorParameters::default().with_loss(SGDLoss::Hinge)
+        )
+        .is_err());
+        assert!(SGDClassifier::fit(&x, &vec![1.; 60], Default::default()).is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = blobs();
+
+        let classifier =
+            SGDClassifier::fit(&x, &y, SGDClassifierParameters::default().with_seed(1)).unwrap();
+
+        let deserialized_classifier: SGDClassifier<f64> =
+            serde_json::from_str(&serde_json::to_string(&classifier).unwrap()).unwrap();
+
+        assert_eq!(classifier.classes(), deserialized_classifier.classes());
+        assert_eq!(
+            classifier.predict(&x).unwrap(),
+            deserialized_classifier.predict(&x).unwrap()
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! Progress messages of long-running fits, forwarded to the [log](https://docs.rs/log) crate when the `log` feature is enabled.
+//! Without the feature the messages are compiled out and their arguments are never evaluated.
+
+/// Logs a message at the info level, e.g. a summary of a finished fit.
+macro_rules! info {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "log")]
+        log::info!($($arg)+);
+        #[cfg(not(feature = "log"))]
+        if false {
+            let _ = alloc::format!($($arg)+);
+        }
+    }};
+}
+
+/// Logs a message at the debug level, e.g. the objective value after every iteration of a fit.
+macro_rules! debug {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "log")]
+        log::debug!($($arg)+);
+        #[cfg(not(feature = "log"))]
+        if false {
+            let _ = alloc::format!($($arg)+);
+        }
+    }};
+}
+
+#[cfg(all(test, feature = "log"))]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::cluster::kmeans::{KMeans, KMeansParameters};
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+
+    static MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    struct Recorder;
+
+    impl log::Log for Recorder {
+        fn enabled(&self, _: &log::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record<'_>) {
+            MESSAGES.lock().unwrap().push(record.args().to_string());
+        }
+
+        fn flush(&self) {}
+    }
+
+    static RECORDER: Recorder = Recorder;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn kmeans_reports_progress() {
+        log::set_logger(&RECORDER).unwrap();
+        log::set_max_level(log::LevelFilter::Debug);
+
+        let x = DenseMatrix::from_2d_array(&[&[1., 1.], &[1.5, 2.], &[5., 7.], &[3.5, 5.]]);
+        KMeans::fit(
+            &x,
+            KMeansParameters::default()
+                .with_k(2)
+                .with_n_init(2)
+                .with_seed(1),
+        )
+        .unwrap();
+
+        let messages = MESSAGES.lock().unwrap();
+        assert!(messages
+            .iter()
+            .any(|m| m.starts_with("KMeans iteration 1: distortion")));
+        assert!(messages.iter().any(|m| m.starts_with("KMeans run 2/2")));
+    }
+}
@@ -0,0 +1,12 @@
+//! # Manifold Learning
+//!
+//! Manifold learning is an approach to non-linear dimensionality reduction. It assumes that high-dimensional data lies close to
+//! a low-dimensional manifold and looks for an embedding of the data into a few dimensions that preserves the neighbourhoods of the manifold.
+//! Unlike [linear methods](../decomposition/index.html), such as PCA, manifold learners can unfold curved structures, which makes them popular
+//! for the visualization of high-dimensional data.
+//!
+//! ## References
+//! * ["The Elements of Statistical Learning: Data Mining, Inference, and Prediction" Trevor et al., 2nd edition, 14.9 Nonlinear Dimension Reduction and Local Multidimensional Scaling](https://web.stanford.edu/~hastie/ElemStatLearn/)
+
+/// Uniform Manifold Approximation and Projection.
+pub mod umap;
@@ -0,0 +1,860 @@
+//! # UMAP
+//!
+//! Uniform Manifold Approximation and Projection (UMAP) finds a low-dimensional embedding of the data that preserves the topology
+//! of its k-nearest-neighbour graph. The algorithm has two phases.
+//!
+//! First, it builds a fuzzy simplicial set, a weighted graph of the data. Every point \\(x_i\\) is connected to its `n_neighbors` nearest neighbours,
+//! found with one of the [nearest neighbour search algorithms](../../algorithm/neighbour/index.html), with weights
+//!
+//! \\[w_{ij} = \exp\left(-\frac{\max(0, d(x_i, x_j) - \rho_i)}{\sigma_i}\right)\\]
+//!
+//! where \\(\rho_i\\) is the distance to the nearest neighbour and \\(\sigma_i\\) is chosen so that \\(\sum_j w_{ij} = \log_2 k\\). This adapts the scale of
+//! the graph to the local density of the data. The directed weights are combined into a symmetric graph with the fuzzy union \\(w_{ij} + w_{ji} - w_{ij} w_{ji}\\).
+//!
+//! Second, it lays out the graph in `n_components` dimensions by minimizing the cross entropy between the graph and a similar graph of the embedding,
+//! whose weights are \\(1 / (1 + a \lVert y_i - y_j \rVert^{2b})\\). The constants \\(a\\) and \\(b\\) are fitted to `min_dist` and `spread`, which control
+//! how tightly points are packed together. The layout is optimized with stochastic gradient descent: edges are sampled proportionally to their weights and pull
+//! their endpoints together, while randomly sampled points are pushed apart.
+//!
+//! New points are embedded with `transform`: they are connected to their nearest neighbours in the training data, placed at the weighted average of
+//! the embeddings of the neighbours and then optimized while the training embedding stays fixed.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::manifold::umap::*;
+//!
+//! // Iris data
+//! let iris = DenseMatrix::from_2d_array(&[
+//!                     &[5.1, 3.5, 1.4, 0.2],
+//!                     &[4.9, 3.0, 1.4, 0.2],
+//!                     &[4.7, 3.2, 1.3, 0.2],
+//!                     &[4.6, 3.1, 1.5, 0.2],
+//!                     &[5.0, 3.6, 1.4, 0.2],
+//!                     &[5.4, 3.9, 1.7, 0.4],
+//!                     &[4.6, 3.4, 1.4, 0.3],
+//!                     &[5.0, 3.4, 1.5, 0.2],
+//!                     &[4.4, 2.9, 1.4, 0.2],
+//!                     &[4.9, 3.1, 1.5, 0.1],
+//!                     &[7.0, 3.2, 4.7, 1.4],
+//!                     &[6.4, 3.2, 4.5, 1.5],
+//!                     &[6.9, 3.1, 4.9, 1.5],
+//!                     &[5.5, 2.3, 4.0, 1.3],
+//!                     &[6.5, 2.8, 4.6, 1.5],
+//!                     &[5.7, 2.8, 4.5, 1.3],
+//!                     &[6.3, 3.3, 4.7, 1.6],
+//!                     &[4.9, 2.4, 3.3, 1.0],
+//!                     &[6.6, 2.9, 4.6, 1.3],
+//!                     &[5.2, 2.7, 3.9, 1.4],
+//!                     ]);
+//!
+//! let umap = UMAP::fit(
+//!     &iris,
+//!     UMAPParameters::default().with_n_neighbors(5).with_seed(42),
+//! ).unwrap();
+//!
+//! let embedding = umap.embedding(); // 20 x 2 matrix
+//! let new_points = umap.transform(&DenseMatrix::from_2d_array(&[&[5.0, 3.3, 1.4, 0.2]])).unwrap();
+//! ```
+//!
+//! ## References:
+//! * ["UMAP: Uniform Manifold Approximation and Projection for Dimension Reduction", McInnes L., Healy J., Melville J., 2018](https://arxiv.org/abs/1802.03426)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::marker::PhantomData;
+
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::algorithm::neighbour::{KNNAlgorithm, KNNAlgorithmName};
+use crate::api::{Transformer, UnsupervisedEstimator};
+use crate::collections::HashMap;
+use crate::error::Failed;
+use crate::linalg::Matrix;
+use crate::math::distance::euclidian::Euclidian;
+use crate::math::distance::{Distance, Distances};
+use crate::math::num::RealNumber;
+use crate::optimization::first_order::lbfgs::LBFGS;
+use crate::optimization::first_order::FirstOrderOptimizer;
+use crate::optimization::line_search::Backtracking;
+use crate::optimization::FunctionOrder;
+use crate::rand::get_rng_impl;
+use crate::validation::{check_transform, check_x};
+
+/// Iterations of the binary search for the bandwidth \\(\sigma_i\\) of a point.
+const SMOOTH_K_MAX_ITER: usize = 64;
+/// Smallest bandwidth relative to the mean distance to the neighbours.
+const MIN_K_DIST_SCALE: f64 = 1e-3;
+/// Gradients of the layout are clipped to this absolute value.
+const GRADIENT_CLIP: f64 = 4.0;
+
+/// How the embedding is initialized before the layout is optimized.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum UMAPInit {
+    #[default]
+    /// Eigenvectors of the normalized Laplacian of the graph. Preserves the global structure of the data better,
+    /// but computes a dense eigendecomposition of an \\(n \times n\\) matrix.
+    Spectral,
+    /// Uniformly distributed random coordinates.
+    Random,
+}
+
+/// UMAP parameters. Use `Default::default()` for default values.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct UMAPParameters<T: RealNumber, D: Distance<Vec<T>, T>> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of dimensions of the embedding. Default value is 2.
+    pub n_components: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Size of the neighbourhood of every point, including the point itself. Larger values preserve more of the global structure
+    /// of the data, smaller values more of the local structure. Default value is 15.
+    pub n_neighbors: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Smallest distance between points in the embedding. Default value is 0.1.
+    pub min_dist: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Scale of the embedded points, together with `min_dist` determines how clustered the embedding is. Default value is 1.
+    pub spread: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of epochs of the layout optimization. Defaults to 500 for up to 10000 points and 200 otherwise.
+    pub n_epochs: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Initial learning rate of the layout optimization. Default value is 1.
+    pub learning_rate: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of points pushed away from a point for every sampled edge. Default value is 5.
+    pub negative_sample_rate: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// How the embedding is initialized. Default value is `UMAPInit::Spectral`.
+    pub init: UMAPInit,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// backend search algorithm. See [`knn search algorithms`](../../algorithm/neighbour/index.html). `CoverTree` is default.
+    pub algorithm: KNNAlgorithmName,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// a function that defines a distance between each pair of point in training data.
+    /// This function should extend [`Distance`](../../math/distance/trait.Distance.html) trait.
+    /// See [`Distances`](../../math/distance/struct.Distances.html) for a list of available functions.
+    distance: D,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed of the random number generator used to initialize and optimize the layout.
+    pub seed: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// this parameter is not used
+    t: PhantomData<T>,
+}
+
+impl<T: RealNumber, D: Distance<Vec<T>, T>> UMAPParameters<T, D> {
+    /// Number of dimensions of the embedding.
+    pub fn with_n_components(mut self, n_components: usize) -> Self {
+        self.n_components = n_components;
+        self
+    }
+    /// Size of the neighbourhood of every point, including the point itself.
+    pub fn with_n_neighbors(mut self, n_neighbors: usize) -> Self {
+        self.n_neighbors = n_neighbors;
+        self
+    }
+    /// Smallest distance between points in the embedding.
+    pub fn with_min_dist(mut self, min_dist: T) -> Self {
+        self.min_dist = min_dist;
+        self
+    }
+    /// Scale of the embedded points.
+    pub fn with_spread(mut self, spread: T) -> Self {
+        self.spread = spread;
+        self
+    }
+    /// Number of epochs of the layout optimization.
+    pub fn with_n_epochs(mut self, n_epochs: usize) -> Self {
+        self.n_epochs = Some(n_epochs);
+        self
+    }
+    /// Initial learning rate of the layout optimization.
+    pub fn with_learning_rate(mut self, learning_rate: T) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+    /// Number of points pushed away from a point for every sampled edge.
+    pub fn with_negative_sample_rate(mut self, negative_sample_rate: usize) -> Self {
+        self.negative_sample_rate = negative_sample_rate;
+        self
+    }
+    /// How the embedding is initialized.
+    pub fn with_init(mut self, init: UMAPInit) -> Self {
+        self.init = init;
+        self
+    }
+    /// backend search algorithm. See [`knn search algorithms`](../../algorithm/neighbour/index.html). `CoverTree` is default.
+    pub fn with_algorithm(mut self, algorithm: KNNAlgorithmName) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+    /// a function that defines a distance between each pair of point in training data.
+    /// This function should extend [`Distance`](../../math/distance/trait.Distance.html) trait.
+    /// See [`Distances`](../../math/distance/struct.Distances.html) for a list of available functions.
+    pub fn with_distance<DD: Distance<Vec<T>, T>>(self, distance: DD) -> UMAPParameters<T, DD> {
+        UMAPParameters {
+            n_components: self.n_components,
+            n_neighbors: self.n_neighbors,
+            min_dist: self.min_dist,
+            spread: self.spread,
+            n_epochs: self.n_epochs,
+            learning_rate: self.learning_rate,
+            negative_sample_rate: self.negative_sample_rate,
+            init: self.init,
+            algorithm: self.algorithm,
+            distance,
+            seed: self.seed,
+            t: PhantomData,
+        }
+    }
+    /// Seed of the random number generator used to initialize and optimize the layout.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl<T: RealNumber> Default for UMAPParameters<T, Euclidian> {
+    fn default() -> Self {
+        UMAPParameters {
+            n_components: 2,
+            n_neighbors: 15,
+            min_dist: T::from_f64(0.1).unwrap(),
+            spread: T::one(),
+            n_epochs: None,
+            learning_rate: T::one(),
+            negative_sample_rate: 5,
+            init: UMAPInit::default(),
+            algorithm: KNNAlgorithmName::default(),
+            distance: Distances::euclidian(),
+            seed: None,
+            t: PhantomData,
+        }
+    }
+}
+
+/// Uniform Manifold Approximation and Projection
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct UMAP<T: RealNumber, M: Matrix<T>, D: Distance<Vec<T>, T>> {
+    knn_algorithm: KNNAlgorithm<T, D>,
+    embedding: M,
+    n_neighbors: usize,
+    a: T,
+    b: T,
+    n_epochs: usize,
+    learning_rate: T,
+    negative_sample_rate: usize,
+    seed: Option<u64>,
+}
+
+impl<T: RealNumber, M: Matrix<T>, D: Distance<Vec<T>, T>>
+    UnsupervisedEstimator<M, UMAPParameters<T, D>> for UMAP<T, M, D>
+{
+    fn fit(x: &M, parameters: UMAPParameters<T, D>) -> Result<Self, Failed> {
+        UMAP::fit(x, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, D: Distance<Vec<T>, T>> Transformer<M> for UMAP<T, M, D> {
+    fn transform(&self, x: &M) -> Result<M, Failed> {
+        self.transform(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, D: Distance<Vec<T>, T>> UMAP<T, M, D> {
+    /// Fits UMAP to your data.
+    /// * `data` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(data: &M, parameters: UMAPParameters<T, D>) -> Result<UMAP<T, M, D>, Failed> {
+        check_x(data, false, Failed::fit)?;
+        let (n, _) = data.shape();
+
+        if parameters.n_neighbors < 2 || parameters.n_neighbors > n {
+            return Err(Failed::fit(&format!(
+                "n_neighbors should be in [2, {}], got {}",
+                n, parameters.n_neighbors
+            )));
+        }
+        if parameters.n_components < 1 {
+            return Err(Failed::fit("n_components should be > 0"));
+        }
+        if parameters.min_dist < T::zero() || parameters.min_dist > parameters.spread {
+            return Err(Failed::fit(&format!(
+                "min_dist should be in [0, spread], got {}",
+                parameters.min_dist
+            )));
+        }
+        if parameters.learning_rate <= T::zero() {
+            return Err(Failed::fit(&format!(
+                "learning_rate should be > 0, got {}",
+                parameters.learning_rate
+            )));
+        }
+        let n_epochs = parameters
+            .n_epochs
+            .unwrap_or(if n <= 10000 { 500 } else { 200 });
+        if n_epochs == 0 {
+            return Err(Failed::fit("n_epochs should be > 0"));
+        }
+
+        let n_components = parameters.n_components;
+        let rows: Vec<Vec<T>> = (0..n).map(|i| data.get_row_as_vec(i)).collect();
+        let knn_algorithm = parameters
+            .algorithm
+            .fit(rows.clone(), parameters.distance)?;
+
+        let mut edges = HashMap::new();
+        for (i, row) in rows.iter().enumerate() {
+            let mut neighbours: Vec<(usize, T)> = knn_algorithm
+                .find(row, parameters.n_neighbors)?
+                .into_iter()
+                .filter(|&(j, _, _)| j != i)
+                .map(|(j, d, _)| (j, d))
+                .collect();
+            neighbours.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+            neighbours.truncate(parameters.n_neighbors - 1);
+            let target = T::from_usize(parameters.n_neighbors).unwrap().log2();
+            for (j, w) in membership_strengths(&neighbours, target, true) {
+                edges.insert((i, j), w);
+            }
+        }
+        let graph = fuzzy_union(&edges);
+
+        let mut rng = get_rng_impl(parameters.seed);
+        let mut embedding = match parameters.init {
+            UMAPInit::Spectral => spectral_layout::<T, M>(n, &graph, n_components),
+            UMAPInit::Random => None,
+        }
+        .unwrap_or_else(|| {
+            (0..n)
+                .map(|_| {
+                    (0..n_components)
+                        .map(|_| T::from_f64(rng.gen_range(-10.0..10.0)).unwrap())
+                        .collect()
+                })
+                .collect()
+        });
+
+        let (a, b) = find_ab_params::<T, M>(parameters.spread, parameters.min_dist);
+        let layout = Layout {
+            a,
+            b,
+            n_epochs,
+            learning_rate: parameters.learning_rate,
+            negative_sample_rate: parameters.negative_sample_rate,
+        };
+        layout.optimize(&mut embedding, None, &graph, &mut rng);
+
+        let mut embedding_matrix = M::zeros(n, n_components);
+        for (i, point) in embedding.iter().enumerate() {
+            for (j, v) in point.iter().enumerate() {
+                embedding_matrix.set(i, j, *v);
+            }
+        }
+
+        Ok(UMAP {
+            knn_algorithm,
+            embedding: embedding_matrix,
+            n_neighbors: parameters.n_neighbors,
+            a,
+            b,
+            n_epochs,
+            learning_rate: parameters.learning_rate,
+            negative_sample_rate: parameters.negative_sample_rate,
+            seed: parameters.seed,
+        })
+    }
+
+    /// Embeds new points into the space of the training embedding.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn transform(&self, x: &M) -> Result<M, Failed> {
+        check_transform(x)?;
+        let (n_train, n_components) = self.embedding.shape();
+        let (n, _) = x.shape();
+
+        let training_embedding: Vec<Vec<T>> = (0..n_train)
+            .map(|i| self.embedding.get_row_as_vec(i))
+            .collect();
+
+        let k = self.n_neighbors.min(n_train);
+        let target = T::from_usize(k).unwrap().log2();
+        let mut graph = Vec::new();
+        let mut embedding = Vec::with_capacity(n);
+        for i in 0..n {
+            let mut neighbours: Vec<(usize, T)> = self
+                .knn_algorithm
+                .find(&x.get_row_as_vec(i), k)?
+                .into_iter()
+                .map(|(j, d, _)| (j, d))
+                .collect();
+            neighbours.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+            let weights = membership_strengths(&neighbours, target, false);
+
+            // start from the weighted average of the embeddings of the neighbours
+            let total = weights.iter().map(|(_, w)| *w).sum::<T>();
+            let mut point = vec![T::zero(); n_components];
+            for &(j, w) in weights.iter() {
+                let w = if total > T::zero() {
+                    w / total
+                } else {
+                    T::one() / T::from_usize(weights.len()).unwrap()
+                };
+                for (c, v) in point.iter_mut().enumerate() {
+                    *v += w * training_embedding[j][c];
+                }
+            }
+            embedding.push(point);
+            graph.extend(weights.into_iter().map(|(j, w)| (i, j, w)));
+        }
+
+        let layout = Layout {
+            a: self.a,
+            b: self.b,
+            n_epochs: (self.n_epochs / 3).max(1),
+            learning_rate: self.learning_rate / T::from_usize(4).unwrap(),
+            negative_sample_rate: self.negative_sample_rate,
+        };
+        let mut rng = get_rng_impl(self.seed);
+        layout.optimize(&mut embedding, Some(&training_embedding), &graph, &mut rng);
+
+        let mut result = M::zeros(n, n_components);
+        for (i, point) in embedding.iter().enumerate() {
+            for (j, v) in point.iter().enumerate() {
+                result.set(i, j, *v);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Embedding of the training data, one row per observation.
+    pub fn embedding(&self) -> &M {
+        &self.embedding
+    }
+
+    /// Constants \\(a\\) and \\(b\\) of the low-dimensional similarity \\(1 / (1 + a d^{2b})\\).
+    pub fn ab(&self) -> (T, T) {
+        (self.a, self.b)
+    }
+}
+
+/// Weights of the edges from a point to its `neighbours`, sorted by distance. The bandwidth \\(\sigma\\) is found with a binary search,
+/// so that the weights sum up to `target`. With `local_connectivity` the nearest neighbour gets weight one.
+fn membership_strengths<T: RealNumber>(
+    neighbours: &[(usize, T)],
+    target: T,
+    local_connectivity: bool,
+) -> Vec<(usize, T)> {
+    let rho = if local_connectivity {
+        neighbours
+            .iter()
+            .map(|(_, d)| *d)
+            .find(|d| *d > T::zero())
+            .unwrap_or_else(T::zero)
+    } else {
+        T::zero()
+    };
+
+    let mut lo = T::zero();
+    let mut hi = T::infinity();
+    let mut sigma = T::one();
+    for _ in 0..SMOOTH_K_MAX_ITER {
+        let psum = neighbours
+            .iter()
+            .map(|(_, d)| (-(*d - rho).max(T::zero()) / sigma).exp())
+            .sum::<T>();
+        if (psum - target).abs() < T::from_f64(1e-5).unwrap() {
+            break;
+        }
+        if psum > target {
+            hi = sigma;
+            sigma = (lo + hi) / T::two();
+        } else {
+            lo = sigma;
+            sigma = if hi.is_infinite() {
+                sigma * T::two()
+            } else {
+                (lo + hi) / T::two()
+            };
+        }
+    }
+
+    if !neighbours.is_empty() {
+        let mean = neighbours.iter().map(|(_, d)| *d).sum::<T>()
+            / T::from_usize(neighbours.len()).unwrap();
+        sigma = sigma.max(T::from_f64(MIN_K_DIST_SCALE).unwrap() * mean);
+    }
+
+    neighbours
+        .iter()
+        .map(|&(j, d)| {
+            let w = if d - rho <= T::zero() || sigma <= T::zero() {
+                T::one()
+            } else {
+                (-(d - rho) / sigma).exp()
+            };
+            (j, w)
+        })
+        .collect()
+}
+
+/// Symmetric graph with the weights of the fuzzy union \\(w_{ij} + w_{ji} - w_{ij} w_{ji}\\), every edge is listed in both directions.
+fn fuzzy_union<T: RealNumber>(edges: &HashMap<(usize, usize), T>) -> Vec<(usize, usize, T)> {
+    let mut graph: Vec<(usize, usize, T)> = Vec::with_capacity(2 * edges.len());
+    for (&(i, j), &w) in edges.iter() {
+        match edges.get(&(j, i)) {
+            Some(&w_t) => graph.push((i, j, w + w_t - w * w_t)),
+            None => {
+                graph.push((i, j, w));
+                graph.push((j, i, w));
+            }
+        }
+    }
+    // iteration order of a hash map is random, sort for reproducible layouts
+    graph.sort_by_key(|&(i, j, _)| (i, j));
+    graph
+}
+
+/// Embedding from the eigenvectors of the normalized graph Laplacian with the smallest non-zero eigenvalues, scaled to [-10, 10].
+fn spectral_layout<T: RealNumber, M: Matrix<T>>(
+    n: usize,
+    graph: &[(usize, usize, T)],
+    n_components: usize,
+) -> Option<Vec<Vec<T>>> {
+    if n <= n_components + 1 {
+        return None;
+    }
+    let mut degree = vec![T::zero(); n];
+    for &(i, _, w) in graph {
+        degree[i] += w;
+    }
+    // eigenvectors of D^-1/2 W D^-1/2 with the largest eigenvalues are the eigenvectors of the Laplacian with the smallest eigenvalues
+    let mut adjacency = M::zeros(n, n);
+    for &(i, j, w) in graph {
+        adjacency.set(i, j, w / (degree[i] * degree[j]).sqrt());
+    }
+    let evd = adjacency.evd(true).ok()?;
+
+    let mut embedding = vec![vec![T::zero(); n_components]; n];
+    let mut max_abs = T::zero();
+    for (i, point) in embedding.iter_mut().enumerate() {
+        for (c, v) in point.iter_mut().enumerate() {
+            *v = evd.V.get(i, c + 1);
+            max_abs = max_abs.max(v.abs());
+        }
+    }
+    if !max_abs.is_finite() || max_abs <= T::zero() {
+        return None;
+    }
+    let expansion = T::from_usize(10).unwrap() / max_abs;
+    for point in embedding.iter_mut() {
+        for v in point.iter_mut() {
+            *v *= expansion;
+        }
+    }
+    Some(embedding)
+}
+
+/// Fits \\(a\\) and \\(b\\) of \\(1 / (1 + a x^{2b})\\) by least squares to a curve that is one up to `min_dist` and decays exponentially with scale `spread` after.
+fn find_ab_params<T: RealNumber, M: Matrix<T>>(spread: T, min_dist: T) -> (T, T) {
+    let n_points = 300;
+    let points: Vec<(T, T)> = (0..n_points)
+        .map(|i| {
+            let x = T::from_usize(3 * i).unwrap() * spread / T::from_usize(n_points - 1).unwrap();
+            let y = if x < min_dist {
+                T::one()
+            } else {
+                (-(x - min_dist) / spread).exp()
+            };
+            (x, y)
+        })
+        .collect();
+
+    let f = |w: &M| {
+        let (a, b) = (w.get(0, 0), w.get(0, 1));
+        points
+            .iter()
+            .map(|&(x, y)| {
+                let x2b = if x > T::zero() {
+                    (T::two() * b * x.ln()).exp()
+                } else {
+                    T::zero()
+                };
+                (T::one() / (T::one() + a * x2b) - y).square()
+            })
+            .sum::<T>()
+    };
+    let df = |g: &mut M, w: &M| {
+        let (a, b) = (w.get(0, 0), w.get(0, 1));
+        let (mut ga, mut gb) = (T::zero(), T::zero());
+        for &(x, y) in points.iter() {
+            if x > T::zero() {
+                let x2b = (T::two() * b * x.ln()).exp();
+                let q = T::one() / (T::one() + a * x2b);
+                let r = T::two() * (q - y) * (-q * q);
+                ga += r * x2b;
+                gb += r * a * x2b * T::two() * x.ln();
+            }
+        }
+        g.set(0, 0, ga);
+        g.set(0, 1, gb);
+    };
+
+    let x0 = M::ones(1, 2);
+    let ls: Backtracking<T> = Backtracking {
+        order: FunctionOrder::THIRD,
+        ..Default::default()
+    };
+    let optimizer: LBFGS<T> = Default::default();
+    let result = optimizer.optimize(&f, &df, &x0, &ls);
+    (result.x.get(0, 0), result.x.get(0, 1))
+}
+
+/// Stochastic gradient descent of the cross entropy between the graph and the embedding.
+struct Layout<T: RealNumber> {
+    a: T,
+    b: T,
+    n_epochs: usize,
+    learning_rate: T,
+    negative_sample_rate: usize,
+}
+
+impl<T: RealNumber> Layout<T> {
+    fn clip(v: T) -> T {
+        let limit = T::from_f64(GRADIENT_CLIP).unwrap();
+        v.max(-limit).min(limit)
+    }
+
+    /// Optimizes `head`, the embedding of the first endpoints of the edges. Without `tail` the edges connect points of `head`
+    /// and both endpoints move, otherwise the edges end in the fixed points of `tail`.
+    fn optimize<R: Rng>(
+        &self,
+        head: &mut [Vec<T>],
+        tail: Option<&[Vec<T>]>,
+        graph: &[(usize, usize, T)],
+        rng: &mut R,
+    ) {
+        let n_tail = tail.map_or(head.len(), |tail| tail.len());
+        let n_epochs = T::from_usize(self.n_epochs).unwrap();
+        let max_weight = graph.iter().fold(T::zero(), |max, &(_, _, w)| max.max(w));
+        // edges are sampled proportionally to their weights, edges that wouldn't be sampled even once are dropped
+        let edges: Vec<(usize, usize, T)> = graph
+            .iter()
+            .filter(|&&(_, _, w)| w * n_epochs >= max_weight && w > T::zero())
+            .map(|&(i, j, w)| (i, j, max_weight / w))
+            .collect();
+        if edges.is_empty() {
+            return;
+        }
+        let negative_sample_rate = T::from_usize(self.negative_sample_rate.max(1)).unwrap();
+        let mut next_sample: Vec<T> = edges.iter().map(|e| e.2).collect();
+        let mut next_negative_sample: Vec<T> =
+            edges.iter().map(|e| e.2 / negative_sample_rate).collect();
+
+        let two_ab = T::two() * self.a * self.b;
+        for epoch in 0..self.n_epochs {
+            let epoch_t = T::from_usize(epoch).unwrap();
+            let alpha = self.learning_rate * (T::one() - epoch_t / n_epochs);
+            for (e, &(i, j, epochs_per_sample)) in edges.iter().enumerate() {
+                if next_sample[e] > epoch_t {
+                    continue;
+                }
+
+                let other = match tail {
+                    Some(tail) => tail[j].clone(),
+                    None => head[j].clone(),
+                };
+                let d = Euclidian::squared_distance(&head[i], &other);
+                if d > T::zero() {
+                    let grad_coeff =
+                        -two_ab * d.powf(self.b - T::one()) / (self.a * d.powf(self.b) + T::one());
+                    for c in 0..other.len() {
+                        let grad = Self::clip(grad_coeff * (head[i][c] - other[c])) * alpha;
+                        head[i][c] += grad;
+                        if tail.is_none() {
+                            head[j][c] -= grad;
+                        }
+                    }
+                }
+                next_sample[e] += epochs_per_sample;
+
+                let epochs_per_negative_sample = epochs_per_sample / negative_sample_rate;
+                let n_negative = ((epoch_t - next_negative_sample[e]) / epochs_per_negative_sample)
+                    .max(T::zero())
+                    .to_usize()
+                    .unwrap();
+                for _ in 0..n_negative {
+                    let k = rng.gen_range(0..n_tail);
+                    if tail.is_none() && k == i {
+                        continue;
+                    }
+                    let other = match tail {
+                        Some(tail) => tail[k].clone(),
+                        None => head[k].clone(),
+                    };
+                    let d = Euclidian::squared_distance(&head[i], &other);
+                    let grad_coeff = if d > T::zero() {
+                        T::two() * self.b
+                            / ((T::from_f64(0.001).unwrap() + d)
+                                * (self.a * d.powf(self.b) + T::one()))
+                    } else {
+                        T::zero()
+                    };
+                    for c in 0..other.len() {
+                        let grad = if grad_coeff > T::zero() {
+                            Self::clip(grad_coeff * (head[i][c] - other[c]))
+                        } else {
+                            T::from_f64(GRADIENT_CLIP).unwrap()
+                        };
+                        head[i][c] += grad * alpha;
+                    }
+                }
+                next_negative_sample[e] +=
+                    T::from_usize(n_negative).unwrap() * epochs_per_negative_sample;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+
+    /// Two groups of 20 points around (0, 0, 0, 0) and (10, 10, 10, 10).
+    fn two_clusters() -> DenseMatrix<f64> {
+        let rows: Vec<Vec<f64>> = (0..40)
+            .map(|i| {
+                let center = if i < 20 { 0.0 } else { 10.0 };
+                (0..4)
+                    .map(|j| center + ((i * 7 + j * 13) as f64).sin())
+                    .collect()
+            })
+            .collect();
+        DenseMatrix::from_2d_vec(&rows)
+    }
+
+    fn centroid(embedding: &DenseMatrix<f64>, rows: std::ops::Range<usize>) -> Vec<f64> {
+        let n = rows.len() as f64;
+        let mut c = vec![0.0; embedding.shape().1];
+        for i in rows {
+            for (j, c_j) in c.iter_mut().enumerate() {
+                *c_j += embedding.get(i, j) / n;
+            }
+        }
+        c
+    }
+
+    fn nearest(point: &[f64], centroids: &[Vec<f64>]) -> usize {
+        let d = |c: &Vec<f64>| Euclidian::squared_distance(point, c);
+        if d(&centroids[0]) < d(&centroids[1]) {
+            0
+        } else {
+            1
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn ab_params() {
+        let (a, b) = find_ab_params::<f64, DenseMatrix<f64>>(1.0, 0.1);
+        assert!((a - 1.577).abs() < 0.01);
+        assert!((b - 0.895).abs() < 0.01);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn membership() {
+        let neighbours = vec![(1, 0.5), (2, 1.0), (3, 2.0), (4, 4.0)];
+        let target = 5f64.log2();
+        let weights = membership_strengths(&neighbours, target, true);
+        assert_eq!(weights[0], (1, 1.0));
+        let total: f64 = weights.iter().map(|(_, w)| w).sum();
+        assert!((total - target).abs() < 1e-4);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn separates_clusters() {
+        let x = two_clusters();
+        for init in [UMAPInit::Spectral, UMAPInit::Random] {
+            let umap = UMAP::fit(
+                &x,
+                UMAPParameters::default()
+                    .with_n_neighbors(8)
+                    .with_init(init)
+                    .with_seed(1),
+            )
+            .unwrap();
+            let embedding = umap.embedding();
+            assert_eq!(embedding.shape(), (40, 2));
+
+            let centroids = vec![centroid(embedding, 0..20), centroid(embedding, 20..40)];
+            for i in 0..40 {
+                assert_eq!(
+                    nearest(&embedding.get_row_as_vec(i), &centroids),
+                    i / 20,
+                    "{:?}",
+                    init
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn transform_new_points() {
+        let x = two_clusters();
+        let umap = UMAP::fit(
+            &x,
+            UMAPParameters::default()
+                .with_n_neighbors(8)
+                .with_algorithm(KNNAlgorithmName::LinearSearch)
+                .with_seed(7),
+        )
+        .unwrap();
+        let embedding = umap.embedding();
+        let centroids = vec![centroid(embedding, 0..20), centroid(embedding, 20..40)];
+
+        let new_points =
+            DenseMatrix::from_2d_array(&[&[0.1, -0.2, 0.3, 0.0], &[9.8, 10.1, 10.2, 9.9]]);
+        let transformed = umap.transform(&new_points).unwrap();
+        assert_eq!(transformed.shape(), (2, 2));
+        assert_eq!(nearest(&transformed.get_row_as_vec(0), &centroids), 0);
+        assert_eq!(nearest(&transformed.get_row_as_vec(1), &centroids), 1);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn reproducible() {
+        let x = two_clusters();
+        let parameters = UMAPParameters::default()
+            .with_n_neighbors(5)
+            .with_n_epochs(50)
+            .with_seed(3);
+        let a = UMAP::fit(&x, parameters.clone()).unwrap();
+        let b = UMAP::fit(&x, parameters).unwrap();
+        assert_eq!(a.embedding(), b.embedding());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn invalid_parameters() {
+        let x = two_clusters();
+        let fit = |parameters| UMAP::fit(&x, parameters).is_err();
+        assert!(fit(UMAPParameters::default().with_n_neighbors(1)));
+        assert!(fit(UMAPParameters::default().with_n_neighbors(41)));
+        assert!(fit(UMAPParameters::default().with_n_components(0)));
+        assert!(fit(UMAPParameters::default().with_min_dist(2.0)));
+        assert!(fit(UMAPParameters::default().with_learning_rate(0.0)));
+        assert!(fit(UMAPParameters::default().with_n_epochs(0)));
+    }
+}
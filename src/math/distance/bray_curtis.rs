@@ -0,0 +1,82 @@
+//! # Bray–Curtis Distance
+//!
+//! Bray–Curtis dissimilarity compares two vectors of non-negative abundances, e.g. counts of species at two sites in ecology.
+//! It is the total absolute difference divided by the total abundance of both vectors.
+//!
+//! \\[ d(x, y) = \frac{\sum_{i=1}^n \lvert x_i - y_i \rvert}{\sum_{i=1}^n \lvert x_i + y_i \rvert} \\]
+//!
+//! For non-negative vectors the distance ranges from 0 for identical vectors to 1 for vectors with no common non-zero dimension.
+//! The distance between two zero vectors is 0. Bray–Curtis dissimilarity does not satisfy the triangle inequality.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::math::distance::Distance;
+//! use smartcore::math::distance::bray_curtis::BrayCurtis;
+//!
+//! let x = vec![6., 7., 4.];
+//! let y = vec![10., 0., 6.];
+//!
+//! let d: f64 = BrayCurtis {}.distance(&x, &y);
+//! ```
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::math::num::RealNumber;
+
+use super::Distance;
+
+/// Bray–Curtis dissimilarity
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct BrayCurtis {}
+
+impl<T: RealNumber> Distance<[T], T> for BrayCurtis {
+    fn distance(&self, x: &[T], y: &[T]) -> T {
+        if x.len() != y.len() {
+            panic!("Input vector sizes are different");
+        }
+
+        let mut difference = T::zero();
+        let mut total = T::zero();
+        for (&x_i, &y_i) in x.iter().zip(y.iter()) {
+            difference += (x_i - y_i).abs();
+            total += (x_i + y_i).abs();
+        }
+
+        if total == T::zero() {
+            T::zero()
+        } else {
+            difference / total
+        }
+    }
+}
+
+impl<T: RealNumber> Distance<Vec<T>, T> for BrayCurtis {
+    fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> T {
+        self.distance(x.as_slice(), y.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn bray_curtis_distance() {
+        let a = vec![6., 7., 4.];
+        let b = vec![10., 0., 6.];
+
+        let d: f64 = BrayCurtis {}.distance(&a, &b);
+        let disjoint: f64 = BrayCurtis {}.distance(&vec![1., 0.], &vec![0., 2.]);
+        let zero: f64 = BrayCurtis {}.distance(&vec![0., 0.], &vec![0., 0.]);
+
+        assert!((d - 13. / 33.).abs() < 1e-8);
+        assert!((disjoint - 1.).abs() < 1e-8);
+        assert_eq!(0., zero);
+    }
+}
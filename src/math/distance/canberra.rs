@@ -0,0 +1,74 @@
+//! # Canberra Distance
+//!
+//! Canberra distance is a weighted version of [Manhattan](../manhattan/index.html) distance, where the difference in every dimension
+//! is divided by the magnitudes of the coordinates. It is sensitive to small changes near zero and is often used for compositional and count data.
+//!
+//! \\[ d(x, y) = \sum_{i=1}^n \frac{\lvert x_i - y_i \rvert}{\lvert x_i \rvert + \lvert y_i \rvert} \\]
+//!
+//! Dimensions where both coordinates are zero contribute nothing.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::math::distance::Distance;
+//! use smartcore::math::distance::canberra::Canberra;
+//!
+//! let x = vec![1., 2.];
+//! let y = vec![3., 2.];
+//!
+//! let d: f64 = Canberra {}.distance(&x, &y);
+//! ```
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::math::num::RealNumber;
+
+use super::Distance;
+
+/// Canberra distance
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Canberra {}
+
+impl<T: RealNumber> Distance<[T], T> for Canberra {
+    fn distance(&self, x: &[T], y: &[T]) -> T {
+        if x.len() != y.len() {
+            panic!("Input vector sizes are different");
+        }
+
+        let mut dist = T::zero();
+        for (&x_i, &y_i) in x.iter().zip(y.iter()) {
+            let denominator = x_i.abs() + y_i.abs();
+            if denominator > T::zero() {
+                dist += (x_i - y_i).abs() / denominator;
+            }
+        }
+
+        dist
+    }
+}
+
+impl<T: RealNumber> Distance<Vec<T>, T> for Canberra {
+    fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> T {
+        self.distance(x.as_slice(), y.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn canberra_distance() {
+        let a = vec![1., 2., 0., -1.];
+        let b = vec![3., 2., 0., 1.];
+
+        let d: f64 = Canberra {}.distance(&a, &b);
+
+        assert!((d - 1.5).abs() < 1e-8);
+    }
+}
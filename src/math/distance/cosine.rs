@@ -0,0 +1,122 @@
+//! # Cosine Distance
+//!
+//! Cosine distance between two points \\(x \in ℝ^n \\) and \\( y \in ℝ^n \\) is one minus the cosine of the angle between them.
+//! It depends only on the directions of the vectors and not on their lengths, which makes it a common choice for text and embedding vectors.
+//!
+//! \\[ d(x, y) = 1 - \frac{\sum_{i=1}^n x_i y_i}{\sqrt{\sum_{i=1}^n x_i^2}\sqrt{\sum_{i=1}^n y_i^2}} \\]
+//!
+//! The distance ranges from 0 for vectors pointing in the same direction to 2 for opposite vectors.
+//! A zero vector has no direction, its cosine similarity with any vector is taken to be 0.
+//!
+//! Cosine distance is not a metric, it does not satisfy the triangle inequality.
+//!
+//! When vectors are already normalized to unit length the norms can be skipped, see [`Cosine::normalized`](struct.Cosine.html#method.normalized).
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::math::distance::Distance;
+//! use smartcore::math::distance::cosine::Cosine;
+//!
+//! let x = vec![1., 0.];
+//! let y = vec![1., 1.];
+//!
+//! let d: f64 = Cosine::new().distance(&x, &y);
+//! ```
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::math::num::RealNumber;
+
+use super::Distance;
+
+/// Cosine distance
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct Cosine {
+    /// Assume that all vectors have unit length and compute the distance from the dot product alone.
+    pub normalized: bool,
+}
+
+impl Cosine {
+    /// Cosine distance of arbitrary vectors.
+    pub fn new() -> Self {
+        Cosine { normalized: false }
+    }
+
+    /// Cosine distance of vectors with unit length, faster than [`new`](#method.new).
+    pub fn normalized() -> Self {
+        Cosine { normalized: true }
+    }
+}
+
+impl<T: RealNumber> Distance<[T], T> for Cosine {
+    fn distance(&self, x: &[T], y: &[T]) -> T {
+        if x.len() != y.len() {
+            panic!("Input vector sizes are different");
+        }
+
+        let mut dot = T::zero();
+        let mut x_norm = T::zero();
+        let mut y_norm = T::zero();
+        for (&x_i, &y_i) in x.iter().zip(y.iter()) {
+            dot += x_i * y_i;
+            if !self.normalized {
+                x_norm += x_i * x_i;
+                y_norm += y_i * y_i;
+            }
+        }
+
+        if self.normalized {
+            return T::one() - dot;
+        }
+
+        let norm = (x_norm * y_norm).sqrt();
+        if norm == T::zero() {
+            T::one()
+        } else {
+            T::one() - dot / norm
+        }
+    }
+}
+
+impl<T: RealNumber> Distance<Vec<T>, T> for Cosine {
+    fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> T {
+        self.distance(x.as_slice(), y.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn cosine_distance() {
+        let a = vec![1., 2., 3.];
+        let b = vec![4., 5., 6.];
+
+        let d: f64 = Cosine::new().distance(&a, &b);
+
+        assert!((d - 0.02536815).abs() < 1e-8);
+        assert!(Cosine::new().distance(&a, &vec![2., 4., 6.]).abs() < 1e-8);
+        assert!((Cosine::new().distance(&a, &vec![-1., -2., -3.]) - 2.).abs() < 1e-8);
+        assert_eq!(1., Cosine::new().distance(&a, &vec![0., 0., 0.]));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn cosine_distance_normalized() {
+        let a = vec![0.6, 0.8];
+        let b = vec![1., 0.];
+
+        let d: f64 = Cosine::normalized().distance(&a, &b);
+
+        assert!((d - 0.4).abs() < 1e-8);
+        assert!((d - Cosine::new().distance(&a, &b)).abs() < 1e-8);
+    }
+}
@@ -16,13 +16,29 @@
 //! let l2: f64 = Euclidian{}.distance(&x, &y);
 //! ```
 //!
+//! Weighted Euclidean distance multiplies the squared difference in every dimension by a non-negative weight \\( w_i \\):
+//!
+//! \\[ d(x, y) = \sqrt{\sum_{i=1}^n w_i (x_i-y_i)^2} \\]
+//!
+//! ```
+//! use smartcore::math::distance::Distance;
+//! use smartcore::math::distance::euclidian::WeightedEuclidian;
+//!
+//! let x = vec![1., 1.];
+//! let y = vec![2., 2.];
+//!
+//! let l2: f64 = WeightedEuclidian::new(vec![1., 0.25]).distance(&x, &y);
+//! ```
+//!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::math::num::RealNumber;
 
+use super::minkowski::check_weights;
 use super::Distance;
 
 /// Euclidean distance is a measure of the true straight line distance between two points in Euclidean n-space.
@@ -47,9 +63,62 @@ impl Euclidian {
     }
 }
 
+impl<T: RealNumber> Distance<[T], T> for Euclidian {
+    fn distance(&self, x: &[T], y: &[T]) -> T {
+        Euclidian::squared_distance(x, y).sqrt()
+    }
+
+    fn is_euclidian(&self) -> bool {
+        true
+    }
+}
+
 impl<T: RealNumber> Distance<Vec<T>, T> for Euclidian {
     fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> T {
-        Euclidian::squared_distance(x, y).sqrt()
+        self.distance(x.as_slice(), y.as_slice())
+    }
+
+    fn is_euclidian(&self) -> bool {
+        true
+    }
+}
+
+/// Euclidean distance with per-dimension weights
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct WeightedEuclidian<T: RealNumber> {
+    /// non-negative weight of every dimension
+    pub weights: Vec<T>,
+}
+
+impl<T: RealNumber> WeightedEuclidian<T> {
+    /// Constructs weighted Euclidean distance
+    /// * `weights` - non-negative weight of every dimension
+    pub fn new(weights: Vec<T>) -> Self {
+        check_weights(&weights);
+        WeightedEuclidian { weights }
+    }
+}
+
+impl<T: RealNumber> Distance<[T], T> for WeightedEuclidian<T> {
+    fn distance(&self, x: &[T], y: &[T]) -> T {
+        if x.len() != y.len() || x.len() != self.weights.len() {
+            panic!("Input vector sizes are different.");
+        }
+
+        let mut sum = T::zero();
+        for ((&x_i, &y_i), &w_i) in x.iter().zip(y.iter()).zip(self.weights.iter()) {
+            let d = x_i - y_i;
+            sum += w_i * d * d;
+        }
+
+        sum.sqrt()
+    }
+}
+
+impl<T: RealNumber> Distance<Vec<T>, T> for WeightedEuclidian<T> {
+    fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> T {
+        self.distance(x.as_slice(), y.as_slice())
     }
 }
 
@@ -66,5 +135,22 @@ mod tests {
         let l2: f64 = Euclidian {}.distance(&a, &b);
 
         assert!((l2 - 5.19615242).abs() < 1e-8);
+        assert!(Distance::<Vec<f64>, f64>::is_euclidian(&Euclidian {}));
+        assert!(!Distance::<Vec<f64>, f64>::is_euclidian(
+            &WeightedEuclidian::new(vec![1., 1., 1.])
+        ));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn weighted_distance() {
+        let a = vec![1., 2., 3.];
+        let b = vec![4., 5., 6.];
+
+        let uniform: f64 = WeightedEuclidian::new(vec![1., 1., 1.]).distance(&a, &b);
+        let l2: f64 = WeightedEuclidian::new(vec![1., 0., 1. / 9.]).distance(&a, &b);
+
+        assert!((uniform - Euclidian {}.distance(&a, &b)).abs() < 1e-8);
+        assert!((l2 - 10f64.sqrt()).abs() < 1e-8);
     }
 }
@@ -0,0 +1,105 @@
+//! # Distance from a Closure
+//!
+//! [`FnDistance`](struct.FnDistance.html) turns any function or closure `Fn(&T, &T) -> F` into a [`Distance`](../trait.Distance.html),
+//! which is handy for one-off metrics that do not deserve a struct of their own.
+//! The closure must be `Clone`, which is the case for closures that capture only `Clone` values.
+//! With the `parallel` feature enabled it must also be `Send + Sync`.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::algorithm::neighbour::cover_tree::CoverTree;
+//! use smartcore::math::distance::fn_distance::FnDistance;
+//!
+//! let data = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
+//!
+//! let distance = FnDistance::new(|a: &i32, b: &i32| (a - b).abs() as f64);
+//! let tree = CoverTree::new(data, distance).unwrap();
+//!
+//! let neighbours = tree.find(&5, 3).unwrap();
+//! ```
+//!
+//! Distances built from closures can not be serialized.
+use core::fmt;
+
+use crate::math::num::RealNumber;
+use crate::parallel::MaybeSync;
+
+use super::Distance;
+
+/// Distance that calls a closure
+#[derive(Clone)]
+pub struct FnDistance<C> {
+    f: C,
+}
+
+impl<C> FnDistance<C> {
+    /// Wraps function `f` that calculates distance between two points.
+    pub fn new(f: C) -> Self {
+        FnDistance { f }
+    }
+}
+
+impl<C> fmt::Debug for FnDistance<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnDistance").finish()
+    }
+}
+
+impl<T: ?Sized, F: RealNumber, C: Fn(&T, &T) -> F + Clone + MaybeSync> Distance<T, F>
+    for FnDistance<C>
+{
+    fn distance(&self, a: &T, b: &T) -> F {
+        (self.f)(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::neighbors::knn_classifier::{KNNClassifier, KNNClassifierParameters};
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fn_distance() {
+        let scale = 2.;
+        let distance =
+            FnDistance::new(move |a: &Vec<f64>, b: &Vec<f64>| scale * (a[0] - b[0]).abs());
+
+        let d: f64 = distance.distance(&vec![1., 5.], &vec![4., -5.]);
+
+        assert_eq!(6., d);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fn_distance_knn() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 100.],
+            &[2., -100.],
+            &[3., 40.],
+            &[9., 50.],
+            &[10., 0.],
+            &[11., -60.],
+        ]);
+        let y = vec![0., 0., 0., 1., 1., 1.];
+
+        // ignores the second, noisy feature
+        let distance = FnDistance::new(|a: &[f64], b: &[f64]| (a[0] - b[0]).abs());
+        let knn = KNNClassifier::fit(
+            &x,
+            &y,
+            KNNClassifierParameters::default()
+                .with_k(3)
+                .with_distance(distance),
+        )
+        .unwrap();
+
+        let y_hat = knn
+            .predict(&DenseMatrix::from_2d_array(&[&[2., 0.], &[10., 100.]]))
+            .unwrap();
+
+        assert_eq!(vec![0., 1.], y_hat);
+    }
+}
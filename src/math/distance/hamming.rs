@@ -2,6 +2,9 @@
 //!
 //! Hamming Distance measures the similarity between two integer-valued vectors of the same length.
 //! Given two vectors \\( x \in ℝ^n \\), \\( y \in ℝ^n \\) the hamming distance between \\( x \\) and \\( y \\), \\( d(x, y) \\), is the number of places where \\( x \\) and \\( y \\) differ.
+//! SmartCore divides this number by \\( n \\), so the distance is the fraction of differing places.
+//!
+//! Elements can be of any type that can be compared for equality, e.g. binary indicators or integer codes of categories.
 //!
 //! Example:
 //!
@@ -19,6 +22,7 @@
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -31,8 +35,8 @@ use super::Distance;
 #[derive(Debug, Clone)]
 pub struct Hamming {}
 
-impl<T: PartialEq, F: RealNumber> Distance<Vec<T>, F> for Hamming {
-    fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> F {
+impl<T: PartialEq, F: RealNumber> Distance<[T], F> for Hamming {
+    fn distance(&self, x: &[T], y: &[T]) -> F {
         if x.len() != y.len() {
             panic!("Input vector sizes are different");
         }
@@ -48,6 +52,12 @@ impl<T: PartialEq, F: RealNumber> Distance<Vec<T>, F> for Hamming {
     }
 }
 
+impl<T: PartialEq, F: RealNumber> Distance<Vec<T>, F> for Hamming {
+    fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> F {
+        self.distance(x.as_slice(), y.as_slice())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,5 +71,7 @@ mod tests {
         let h: f64 = Hamming {}.distance(&a, &b);
 
         assert!((h - 0.42857142).abs() < 1e-8);
+        let h_slices: f64 = Hamming {}.distance(&a[..], &b[..]);
+        assert_eq!(h, h_slices);
     }
 }
@@ -0,0 +1,121 @@
+//! # Haversine Distance
+//!
+//! Haversine distance is the great-circle distance between two points on a sphere given by their latitudes and longitudes.
+//! Unlike Euclidean distance on raw coordinates it accounts for meridians converging towards the poles and for longitudes wrapping around at ±180°.
+//!
+//! For points \\(x = (\varphi_1, \lambda_1) \\) and \\( y = (\varphi_2, \lambda_2) \\), where \\( \varphi \\) is latitude and \\( \lambda \\) is longitude,
+//!
+//! \\[ d(x, y) = 2r \arcsin\sqrt{\sin^2\left(\frac{\varphi_2 - \varphi_1}{2}\right) + \cos\varphi_1\cos\varphi_2\sin^2\left(\frac{\lambda_2 - \lambda_1}{2}\right)} \\]
+//!
+//! where \\( r \\) is the radius of the sphere. Points are vectors of two elements, latitude and longitude in degrees.
+//! The distance is measured in units of the radius, use [`Haversine::earth`](struct.Haversine.html#method.earth) for kilometers on the Earth.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::math::distance::Distance;
+//! use smartcore::math::distance::haversine::Haversine;
+//!
+//! let paris = vec![48.8566, 2.3522];
+//! let london = vec![51.5074, -0.1278];
+//!
+//! let km: f64 = Haversine::earth().distance(&paris, &london);
+//! ```
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::math::num::RealNumber;
+
+use super::Distance;
+
+/// Mean radius of the Earth in kilometers.
+pub const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// Great-circle distance between (latitude, longitude) pairs given in degrees
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Haversine {
+    /// Radius of the sphere, 1 measures distances as central angles in radians.
+    pub radius: f64,
+}
+
+impl Default for Haversine {
+    fn default() -> Self {
+        Haversine { radius: 1. }
+    }
+}
+
+impl Haversine {
+    /// Distance on a sphere with the given radius.
+    pub fn new(radius: f64) -> Self {
+        Haversine { radius }
+    }
+
+    /// Distance on the Earth in kilometers.
+    pub fn earth() -> Self {
+        Haversine::new(EARTH_RADIUS_KM)
+    }
+}
+
+impl<T: RealNumber> Distance<[T], T> for Haversine {
+    fn distance(&self, x: &[T], y: &[T]) -> T {
+        if x.len() != 2 || y.len() != 2 {
+            panic!("Haversine distance is defined for (latitude, longitude) pairs only");
+        }
+
+        let lat1 = x[0].to_radians();
+        let lat2 = y[0].to_radians();
+        let dlat = (lat2 - lat1) / T::two();
+        let dlon = (y[1] - x[1]).to_radians() / T::two();
+
+        let a = dlat.sin().powi(2) + lat1.cos() * lat2.cos() * dlon.sin().powi(2);
+
+        T::two() * T::from_f64(self.radius).unwrap() * a.sqrt().min(T::one()).asin()
+    }
+}
+
+impl<T: RealNumber> Distance<Vec<T>, T> for Haversine {
+    fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> T {
+        self.distance(x.as_slice(), y.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn haversine_distance() {
+        let paris = vec![48.8566, 2.3522];
+        let london = vec![51.5074, -0.1278];
+
+        let km: f64 = Haversine::earth().distance(&paris, &london);
+        assert!((km - 343.56).abs() < 0.1);
+
+        let angle: f64 = Haversine::default().distance(&vec![0., 0.], &vec![0., 90.]);
+        assert!((angle - std::f64::consts::FRAC_PI_2).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn haversine_distance_wraps_longitude() {
+        let d: f64 = Haversine::default().distance(&vec![10., 179.5], &vec![10., -179.5]);
+        let expected = Haversine::default().distance(&vec![10., -0.5], &vec![10., 0.5]);
+
+        assert!((d - expected).abs() < 1e-8);
+        assert!(
+            (Haversine::default().distance(&vec![90., 0.], &vec![90., 120.]) as f64).abs() < 1e-8
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Haversine distance is defined for (latitude, longitude) pairs only")]
+    fn haversine_distance_wrong_dimension() {
+        let _: f64 = Haversine::default().distance(&vec![1., 2., 3.], &vec![1., 2., 3.]);
+    }
+}
@@ -0,0 +1,99 @@
+//! # Jaccard Distance
+//!
+//! Jaccard distance measures dissimilarity of two sets. Binary or integer vectors \\( x \\) and \\( y \\) represent sets of positions
+//! where their elements are non-zero, for example indicators of categories or bits of a molecular fingerprint.
+//!
+//! \\[ d(x, y) = 1 - \frac{\lvert \\{i : x_i \neq 0 \wedge y_i \neq 0\\} \rvert}{\lvert \\{i : x_i \neq 0 \vee y_i \neq 0\\} \rvert} \\]
+//!
+//! Positions where both vectors are zero do not count, which makes Jaccard distance better suited to sparse indicators than [Hamming](../hamming/index.html) distance.
+//! The distance between two all-zero vectors is 0.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::math::distance::Distance;
+//! use smartcore::math::distance::jaccard::Jaccard;
+//!
+//! let a = vec![1, 0, 0, 1, 0, 0, 1];
+//! let b = vec![1, 1, 0, 0, 1, 0, 1];
+//!
+//! let j: f64 = Jaccard {}.distance(&a, &b);
+//! ```
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+
+use alloc::vec::Vec;
+use num_traits::Zero;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::math::num::RealNumber;
+
+use super::Distance;
+
+/// Jaccard distance between sets of non-zero positions of two vectors
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Jaccard {}
+
+impl<T: Zero + PartialEq, F: RealNumber> Distance<[T], F> for Jaccard {
+    fn distance(&self, x: &[T], y: &[T]) -> F {
+        if x.len() != y.len() {
+            panic!("Input vector sizes are different");
+        }
+
+        let mut intersection = 0;
+        let mut union = 0;
+        for (x_i, y_i) in x.iter().zip(y.iter()) {
+            let (in_x, in_y) = (!x_i.is_zero(), !y_i.is_zero());
+            if in_x && in_y {
+                intersection += 1;
+            }
+            if in_x || in_y {
+                union += 1;
+            }
+        }
+
+        if union == 0 {
+            F::zero()
+        } else {
+            F::one() - F::from_usize(intersection).unwrap() / F::from_usize(union).unwrap()
+        }
+    }
+}
+
+impl<T: Zero + PartialEq, F: RealNumber> Distance<Vec<T>, F> for Jaccard {
+    fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> F {
+        self.distance(x.as_slice(), y.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn jaccard_distance() {
+        let a = vec![1, 0, 0, 1, 0, 0, 1];
+        let b = vec![1, 1, 0, 0, 1, 0, 1];
+
+        let j: f64 = Jaccard {}.distance(&a, &b);
+
+        assert!((j - 0.6).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn jaccard_distance_real_indicators() {
+        let a = vec![1., 0., 2.];
+        let b = vec![0., 0., 0.];
+
+        let disjoint: f64 = Jaccard {}.distance(&a, &b);
+        let empty: f64 = Jaccard {}.distance(&b, &b);
+
+        assert_eq!(1., disjoint);
+        assert_eq!(0., empty);
+    }
+}
@@ -42,7 +42,8 @@
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 #![allow(non_snake_case)]
 
-use std::marker::PhantomData;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -89,8 +90,8 @@ impl<T: RealNumber, M: Matrix<T>> Mahalanobis<T, M> {
     }
 }
 
-impl<T: RealNumber, M: Matrix<T>> Distance<Vec<T>, T> for Mahalanobis<T, M> {
-    fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> T {
+impl<T: RealNumber, M: Matrix<T>> Distance<[T], T> for Mahalanobis<T, M> {
+    fn distance(&self, x: &[T], y: &[T]) -> T {
         let (nrows, ncols) = self.sigma.shape();
         if x.len() != nrows {
             panic!(
@@ -128,6 +129,12 @@ impl<T: RealNumber, M: Matrix<T>> Distance<Vec<T>, T> for Mahalanobis<T, M> {
     }
 }
 
+impl<T: RealNumber, M: Matrix<T>> Distance<Vec<T>, T> for Mahalanobis<T, M> {
+    fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> T {
+        self.distance(x.as_slice(), y.as_slice())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
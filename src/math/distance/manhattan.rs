@@ -17,6 +17,7 @@
 //! ```
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -29,8 +30,8 @@ use super::Distance;
 #[derive(Debug, Clone)]
 pub struct Manhattan {}
 
-impl<T: RealNumber> Distance<Vec<T>, T> for Manhattan {
-    fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> T {
+impl<T: RealNumber> Distance<[T], T> for Manhattan {
+    fn distance(&self, x: &[T], y: &[T]) -> T {
         if x.len() != y.len() {
             panic!("Input vector sizes are different");
         }
@@ -44,6 +45,12 @@ impl<T: RealNumber> Distance<Vec<T>, T> for Manhattan {
     }
 }
 
+impl<T: RealNumber> Distance<Vec<T>, T> for Manhattan {
+    fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> T {
+        self.distance(x.as_slice(), y.as_slice())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -57,5 +64,6 @@ mod tests {
         let l1: f64 = Manhattan {}.distance(&a, &b);
 
         assert!((l1 - 9.0).abs() < 1e-8);
+        assert_eq!(l1, Manhattan {}.distance(&a[..], &b[..]));
     }
 }
@@ -18,9 +18,25 @@
 //! let l2: f64 = Minkowski { p: 2 }.distance(&x, &y);
 //!
 //! ```
+//!
+//! Weighted Minkowski distance multiplies the term of every dimension by a non-negative weight \\( w_i \\),
+//! so less reliable features can be de-emphasized without rescaling the data:
+//!
+//! \\[ d(x, y) = \left(\sum_{i=0}^n w_i \lvert x_i - y_i \rvert^p\right)^{1/p} \\]
+//!
+//! ```
+//! use smartcore::math::distance::Distance;
+//! use smartcore::math::distance::minkowski::WeightedMinkowski;
+//!
+//! let x = vec![1., 1.];
+//! let y = vec![2., 2.];
+//!
+//! let l1: f64 = WeightedMinkowski::new(1, vec![1., 0.5]).distance(&x, &y);
+//! ```
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -36,8 +52,8 @@ pub struct Minkowski {
     pub p: u16,
 }
 
-impl<T: RealNumber> Distance<Vec<T>, T> for Minkowski {
-    fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> T {
+impl<T: RealNumber> Distance<[T], T> for Minkowski {
+    fn distance(&self, x: &[T], y: &[T]) -> T {
         if x.len() != y.len() {
             panic!("Input vector sizes are different");
         }
@@ -57,6 +73,63 @@ impl<T: RealNumber> Distance<Vec<T>, T> for Minkowski {
     }
 }
 
+impl<T: RealNumber> Distance<Vec<T>, T> for Minkowski {
+    fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> T {
+        self.distance(x.as_slice(), y.as_slice())
+    }
+}
+
+/// Minkowski distance of order `p` with per-dimension weights
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct WeightedMinkowski<T: RealNumber> {
+    /// order, integer
+    pub p: u16,
+    /// non-negative weight of every dimension
+    pub weights: Vec<T>,
+}
+
+impl<T: RealNumber> WeightedMinkowski<T> {
+    /// Constructs weighted Minkowski distance
+    /// * `p` - function order. Should be >= 1
+    /// * `weights` - non-negative weight of every dimension
+    pub fn new(p: u16, weights: Vec<T>) -> Self {
+        if p < 1 {
+            panic!("p must be at least 1");
+        }
+        check_weights(&weights);
+        WeightedMinkowski { p, weights }
+    }
+}
+
+impl<T: RealNumber> Distance<[T], T> for WeightedMinkowski<T> {
+    fn distance(&self, x: &[T], y: &[T]) -> T {
+        if x.len() != y.len() || x.len() != self.weights.len() {
+            panic!("Input vector sizes are different");
+        }
+
+        let p_t = T::from_u16(self.p).unwrap();
+        let mut dist = T::zero();
+        for ((&x_i, &y_i), &w_i) in x.iter().zip(y.iter()).zip(self.weights.iter()) {
+            dist += w_i * (x_i - y_i).abs().powf(p_t);
+        }
+
+        dist.powf(T::one() / p_t)
+    }
+}
+
+impl<T: RealNumber> Distance<Vec<T>, T> for WeightedMinkowski<T> {
+    fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> T {
+        self.distance(x.as_slice(), y.as_slice())
+    }
+}
+
+pub(crate) fn check_weights<T: RealNumber>(weights: &[T]) {
+    if weights.iter().any(|&w| w < T::zero() || !w.is_finite()) {
+        panic!("Weights must be non-negative and finite");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,6 +149,25 @@ mod tests {
         assert!((l3 - 4.32674871).abs() < 1e-8);
     }
 
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn weighted_minkowski_distance() {
+        let a = vec![1., 2., 3.];
+        let b = vec![4., 5., 6.];
+
+        let uniform: f64 = WeightedMinkowski::new(3, vec![1., 1., 1.]).distance(&a, &b);
+        let l1: f64 = WeightedMinkowski::new(1, vec![1., 0.5, 0.]).distance(&a, &b);
+
+        assert!((uniform - Minkowski { p: 3 }.distance(&a, &b)).abs() < 1e-8);
+        assert!((l1 - 4.5).abs() < 1e-8);
+    }
+
+    #[test]
+    #[should_panic(expected = "Weights must be non-negative and finite")]
+    fn weighted_minkowski_negative_weight() {
+        let _ = WeightedMinkowski::new(2, vec![1., -1.]);
+    }
+
     #[test]
     #[should_panic(expected = "p must be at least 1")]
     fn minkowski_distance_negative_p() {
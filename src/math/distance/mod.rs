@@ -10,27 +10,69 @@
 //!
 //! A good distance metric helps to improve the performance of classification, clustering and information retrieval algorithms significantly.
 //!
+//! Distances between vectors of numbers are implemented for both `Vec<T>` and slices `[T]`, so points stored in a reusable buffer
+//! or in a part of a larger array can be compared without allocating a new vector for every pair:
+//!
+//! ```
+//! use smartcore::math::distance::Distance;
+//! use smartcore::math::distance::euclidian::Euclidian;
+//!
+//! let points = [0., 0., 3., 4.];
+//!
+//! let d: f64 = Euclidian {}.distance(&points[0..2], &points[2..4]);
+//! assert_eq!(d, 5.);
+//! ```
+//!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 
+/// Bray–Curtis dissimilarity between two vectors of non-negative abundances.
+pub mod bray_curtis;
+/// Canberra Distance is a weighted version of Manhattan distance.
+pub mod canberra;
+/// Cosine Distance is one minus the cosine of the angle between two vectors.
+pub mod cosine;
 /// Euclidean Distance is the straight-line distance between two points in Euclidean spacere that presents the shortest distance between these points.
 pub mod euclidian;
+/// Distance computed by a closure.
+pub mod fn_distance;
 /// Hamming Distance between two strings is the number of positions at which the corresponding symbols are different.
 pub mod hamming;
+/// Great-circle distance between two points on a sphere given by latitude and longitude.
+pub mod haversine;
+/// Jaccard Distance between sets of non-zero positions of binary or integer vectors.
+pub mod jaccard;
 /// The Mahalanobis distance is the distance between two points in multivariate space.
 pub mod mahalanobis;
 /// Also known as rectilinear distance, city block distance, taxicab metric.
 pub mod manhattan;
 /// A generalization of both the Euclidean distance and the Manhattan distance.
 pub mod minkowski;
+/// Euclidean Distance that ignores missing values.
+pub mod nan_euclidian;
 
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
+use crate::parallel::MaybeSync;
+use alloc::vec::Vec;
 
-/// Distance metric, a function that calculates distance between two points
-pub trait Distance<T, F: RealNumber>: Clone {
+/// Distance metric, a function that calculates distance between two points.
+/// Points can be of unsized types, like slices.
+pub trait Distance<T: ?Sized, F: RealNumber>: Clone + MaybeSync {
     /// Calculates distance between _a_ and _b_
     fn distance(&self, a: &T, b: &T) -> F;
+
+    /// Whether the distance is defined for points with missing values, marked as `NaN`.
+    /// Estimators that measure distances accept missing values only with such a distance, see [input validation](../../validation/index.html).
+    fn supports_missing_values(&self) -> bool {
+        false
+    }
+
+    /// Whether the distance is the [Euclidian](euclidian/index.html) distance.
+    /// Algorithms that rely on its geometry, like BBD tree filtering in [K-Means](../../cluster/kmeans/index.html), fall back to a generic method for other distances.
+    fn is_euclidian(&self) -> bool {
+        false
+    }
 }
 
 /// Multitude of distance metric functions
@@ -42,12 +84,28 @@ impl Distances {
         euclidian::Euclidian {}
     }
 
+    /// Weighted Euclidian distance, see [`WeightedEuclidian`](euclidian/struct.WeightedEuclidian.html)
+    /// * `weights` - non-negative weight of every dimension
+    pub fn weighted_euclidian<T: RealNumber>(weights: Vec<T>) -> euclidian::WeightedEuclidian<T> {
+        euclidian::WeightedEuclidian::new(weights)
+    }
+
     /// Minkowski distance, see [`Minkowski`](minkowski/index.html)
     /// * `p` - function order. Should be >= 1
     pub fn minkowski(p: u16) -> minkowski::Minkowski {
         minkowski::Minkowski { p }
     }
 
+    /// Weighted Minkowski distance, see [`WeightedMinkowski`](minkowski/struct.WeightedMinkowski.html)
+    /// * `p` - function order. Should be >= 1
+    /// * `weights` - non-negative weight of every dimension
+    pub fn weighted_minkowski<T: RealNumber>(
+        p: u16,
+        weights: Vec<T>,
+    ) -> minkowski::WeightedMinkowski<T> {
+        minkowski::WeightedMinkowski::new(p, weights)
+    }
+
     /// Manhattan distance, see [`Manhattan`](manhattan/index.html)
     pub fn manhattan() -> manhattan::Manhattan {
         manhattan::Manhattan {}
@@ -58,6 +116,44 @@ impl Distances {
         hamming::Hamming {}
     }
 
+    /// Haversine distance in kilometers on the Earth, see [`Haversine`](haversine/index.html)
+    pub fn haversine() -> haversine::Haversine {
+        haversine::Haversine::earth()
+    }
+
+    /// Jaccard distance, see [`Jaccard`](jaccard/index.html)
+    pub fn jaccard() -> jaccard::Jaccard {
+        jaccard::Jaccard {}
+    }
+
+    /// Euclidian distance that ignores missing values, see [`NanEuclidian`](nan_euclidian/index.html)
+    pub fn nan_euclidian() -> nan_euclidian::NanEuclidian {
+        nan_euclidian::NanEuclidian {}
+    }
+
+    /// Canberra distance, see [`Canberra`](canberra/index.html)
+    pub fn canberra() -> canberra::Canberra {
+        canberra::Canberra {}
+    }
+
+    /// Bray–Curtis dissimilarity, see [`BrayCurtis`](bray_curtis/index.html)
+    pub fn bray_curtis() -> bray_curtis::BrayCurtis {
+        bray_curtis::BrayCurtis {}
+    }
+
+    /// Cosine distance, see [`Cosine`](cosine/index.html)
+    pub fn cosine() -> cosine::Cosine {
+        cosine::Cosine::new()
+    }
+
+    /// Distance computed by a closure, see [`FnDistance`](fn_distance/index.html)
+    /// * `f` - function that calculates distance between two points
+    pub fn from_fn<T: ?Sized, F: RealNumber, C: Fn(&T, &T) -> F + Clone + MaybeSync>(
+        f: C,
+    ) -> fn_distance::FnDistance<C> {
+        fn_distance::FnDistance::new(f)
+    }
+
     /// Mahalanobis distance, see [`Mahalanobis`](mahalanobis/index.html)
     pub fn mahalanobis<T: RealNumber, M: Matrix<T>>(data: &M) -> mahalanobis::Mahalanobis<T, M> {
         mahalanobis::Mahalanobis::new(data)
@@ -0,0 +1,99 @@
+//! # Euclidian Distance With Missing Values
+//!
+//! Euclidean distance between two points \\( x \\) and \\( y \\) in n-space that ignores coordinates where either point has a missing value (`NaN`).
+//! The sum of squared differences over the \\( m \\) coordinates present in both points is scaled up to all \\( n \\) coordinates
+//!
+//! \\[ d(x, y) = \sqrt{\frac{n}{m} \sum_{i \in present} (x_i-y_i)^2} \\]
+//!
+//! When the points have no coordinates in common the distance is infinite. Without missing values the distance is equal to the [Euclidian](../euclidian/index.html) distance.
+//! Note that the distance does not satisfy the triangle inequality when values are missing.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::math::distance::Distance;
+//! use smartcore::math::distance::nan_euclidian::NanEuclidian;
+//!
+//! let x = vec![3., f64::NAN, 1.];
+//! let y = vec![1., 2., 1.];
+//!
+//! let l2: f64 = NanEuclidian{}.distance(&x, &y);
+//! assert!((l2 - 6f64.sqrt()).abs() < 1e-8);
+//! ```
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::math::num::RealNumber;
+
+use super::Distance;
+
+/// Euclidean distance that skips missing values and rescales the result to the full number of coordinates.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct NanEuclidian {}
+
+impl<T: RealNumber> Distance<[T], T> for NanEuclidian {
+    fn distance(&self, x: &[T], y: &[T]) -> T {
+        if x.len() != y.len() {
+            panic!("Input vector sizes are different.");
+        }
+
+        let mut sum = T::zero();
+        let mut present = 0;
+        for i in 0..x.len() {
+            if !x[i].is_nan() && !y[i].is_nan() {
+                let d = x[i] - y[i];
+                sum += d * d;
+                present += 1;
+            }
+        }
+
+        if present == 0 {
+            T::infinity()
+        } else {
+            (sum * T::from_usize(x.len()).unwrap() / T::from_usize(present).unwrap()).sqrt()
+        }
+    }
+
+    fn supports_missing_values(&self) -> bool {
+        true
+    }
+}
+
+impl<T: RealNumber> Distance<Vec<T>, T> for NanEuclidian {
+    fn distance(&self, x: &Vec<T>, y: &Vec<T>) -> T {
+        self.distance(x.as_slice(), y.as_slice())
+    }
+
+    fn supports_missing_values(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn nan_euclidian_distance() {
+        let a = vec![1., 2., 3.];
+        let b = vec![4., 5., 6.];
+        let l2: f64 = NanEuclidian {}.distance(&a, &b);
+        assert!((l2 - 5.19615242).abs() < 1e-8);
+
+        let a = vec![1., f64::NAN, 3.];
+        let b = vec![4., 5., f64::NAN];
+        let l2: f64 = NanEuclidian {}.distance(&a, &b);
+        assert!((l2 - 27f64.sqrt()).abs() < 1e-8);
+
+        let a = vec![f64::NAN, 2.];
+        let b = vec![1., f64::NAN];
+        let l2: f64 = NanEuclidian {}.distance(&a, &b);
+        assert!(l2.is_infinite());
+    }
+}
@@ -1,4 +1,5 @@
 /// Multitude of distance metrics are defined here
 pub mod distance;
 pub mod num;
+pub(crate) mod special;
 pub(crate) mod vector;
@@ -2,13 +2,14 @@
 //! Most algorithms in SmartCore rely on basic linear algebra operations like dot product, matrix decomposition and other subroutines that are defined for a set of real numbers, ℝ.
 //! This module defines real number and some useful functions that are used in [Linear Algebra](../../linalg/index.html) module.
 
+use core::fmt::{Debug, Display};
+use core::iter::{Product, Sum};
+use core::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
+use core::str::FromStr;
 use num_traits::{Float, FromPrimitive};
 use rand::prelude::*;
-use std::fmt::{Debug, Display};
-use std::iter::{Product, Sum};
-use std::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
-use std::str::FromStr;
 
+use crate::parallel::MaybeSync;
 use crate::rand::get_rng_impl;
 
 /// Defines real number
@@ -26,6 +27,7 @@ pub trait RealNumber:
     + MulAssign
     + DivAssign
     + FromStr
+    + MaybeSync
 {
     /// Copy sign from `sign` - another real number
     fn copysign(self, sign: Self) -> Self;
@@ -1,7 +1,9 @@
-use crate::math::num::RealNumber;
-use std::collections::HashMap;
+use alloc::vec::Vec;
 
+use crate::collections::HashMap;
 use crate::linalg::BaseVector;
+use crate::math::num::RealNumber;
+
 pub trait RealNumberVector<T: RealNumber> {
     fn unique_with_indices(&self) -> (Vec<T>, Vec<usize>);
 }
@@ -12,7 +14,7 @@ impl<T: RealNumber, V: BaseVector<T>> RealNumberVector<T> for V {
         unique.sort_by(|a, b| a.partial_cmp(b).unwrap());
         unique.dedup();
 
-        let mut index = HashMap::with_capacity(unique.len());
+        let mut index = HashMap::new();
         for (i, u) in unique.iter().enumerate() {
             index.insert(u.to_i64().unwrap(), i);
         }
@@ -19,8 +19,10 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::error::Failed;
 use crate::linalg::BaseVector;
 use crate::math::num::RealNumber;
+use crate::metrics::check_sample_weight;
 
 /// Accuracy metric.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -32,6 +34,20 @@ impl Accuracy {
     /// * `y_true` - cround truth (correct) labels
     /// * `y_pred` - predicted labels, as returned by a classifier.
     pub fn get_score<T: RealNumber, V: BaseVector<T>>(&self, y_true: &V, y_pred: &V) -> T {
+        self.get_score_weighted(y_true, y_pred, &vec![T::one(); y_true.len()])
+            .unwrap()
+    }
+
+    /// Weighted fraction of correctly predicted labels.
+    /// * `y_true` - cround truth (correct) labels
+    /// * `y_pred` - predicted labels, as returned by a classifier.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<T: RealNumber, V: BaseVector<T>>(
+        &self,
+        y_true: &V,
+        y_pred: &V,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
         if y_true.len() != y_pred.len() {
             panic!(
                 "The vector sizes don't match: {} != {}",
@@ -39,23 +55,23 @@ impl Accuracy {
                 y_pred.len()
             );
         }
+        let total_weight = check_sample_weight(y_true.len(), sample_weight)?;
 
-        let n = y_true.len();
-
-        let mut positive = 0;
-        for i in 0..n {
+        let mut positive = T::zero();
+        for (i, &w) in sample_weight.iter().enumerate() {
             if y_true.get(i) == y_pred.get(i) {
-                positive += 1;
+                positive += w;
             }
         }
 
-        T::from_i64(positive).unwrap() / T::from_usize(n).unwrap()
+        Ok(positive / total_weight)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::FailedError;
 
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     #[test]
@@ -69,4 +85,31 @@ mod tests {
         assert!((score1 - 0.5).abs() < 1e-8);
         assert!((score2 - 1.0).abs() < 1e-8);
     }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn accuracy_weighted() {
+        let y_pred: Vec<f64> = vec![0., 2., 1., 3.];
+        let y_true: Vec<f64> = vec![0., 1., 2., 3.];
+
+        let score1: f64 = Accuracy {}
+            .get_score_weighted(&y_true, &y_pred, &[1., 1., 1., 1.])
+            .unwrap();
+        let score2: f64 = Accuracy {}
+            .get_score_weighted(&y_true, &y_pred, &[3., 1., 1., 0.])
+            .unwrap();
+
+        assert!((score1 - 0.5).abs() < 1e-8);
+        assert!((score2 - 0.6).abs() < 1e-8);
+
+        let mismatch = Accuracy {}.get_score_weighted(&y_true, &y_pred, &[1., 1.]);
+        assert_eq!(mismatch.unwrap_err().error(), FailedError::ShapeMismatch);
+        let negative = Accuracy {}.get_score_weighted(&y_true, &y_pred, &[1., -1., 1., 1.]);
+        assert_eq!(
+            negative.unwrap_err().error(),
+            FailedError::InvalidParameters
+        );
+        let zero = Accuracy {}.get_score_weighted(&y_true, &y_pred, &[0., 0., 0., 0.]);
+        assert_eq!(zero.unwrap_err().error(), FailedError::InvalidParameters);
+    }
 }
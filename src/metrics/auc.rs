@@ -14,47 +14,63 @@
 //! let score1: f64 = AUC {}.get_score(&y_true, &y_pred);
 //! ```
 //!
+//! The ROC curve itself plots true positive rate against false positive rate for every threshold on the scores.
+//! Scores can be probabilities of the positive class or any decision values where larger means more likely positive:
+//! ```
+//! use smartcore::metrics::auc::AUC;
+//!
+//! let y_true: Vec<f64> = vec![0., 0., 1., 1.];
+//! let y_score: Vec<f64> = vec![-2.3, 0.4, -0.1, 1.7];
+//!
+//! let curve = AUC {}.roc_curve(&y_true, &y_score);
+//!
+//! assert_eq!(curve.fpr, vec![0., 0., 0.5, 0.5, 1.]);
+//! assert_eq!(curve.tpr, vec![0., 0.5, 0.5, 1., 1.]);
+//! ```
+//!
 //! ## References:
 //! * ["Areas beneath the relative operating characteristics (ROC) and relative operating levels (ROL) curves: Statistical significance and interpretation", Mason S. J., Graham N. E.](http://citeseerx.ist.psu.edu/viewdoc/summary?doi=10.1.1.458.8392)
 //! * [Wikipedia article on ROC AUC](https://en.wikipedia.org/wiki/Receiver_operating_characteristic#Area_under_the_curve)
 //! * ["The ROC-AUC and the Mann-Whitney U-test", Haupt, J.](https://johaupt.github.io/roc-auc/model%20evaluation/Area_under_ROC_curve.html)
 #![allow(non_snake_case)]
 
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::algorithm::sort::quick_sort::QuickArgSort;
+use crate::error::Failed;
 use crate::linalg::BaseVector;
 use crate::math::num::RealNumber;
+use crate::metrics::check_sample_weight;
 
 /// Area Under the Receiver Operating Characteristic Curve (ROC AUC)
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug)]
 pub struct AUC {}
 
+/// Receiver Operating Characteristic (ROC) curve
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct ROCCurve<T: RealNumber> {
+    /// False positive rates, in increasing order.
+    pub fpr: Vec<T>,
+    /// True positive rates, in increasing order.
+    pub tpr: Vec<T>,
+    /// Decreasing score thresholds. A sample is predicted positive when its score is greater or equal to the threshold.
+    /// The first threshold is infinite, no sample is predicted positive.
+    pub thresholds: Vec<T>,
+}
+
 impl AUC {
     /// AUC score.
     /// * `y_true` - cround truth (correct) labels.
-    /// * `y_pred_probabilities` - probability estimates, as returned by a classifier.
+    /// * `y_pred_probabilities` - probability estimates or decision scores of the positive class, as returned by a classifier.
     pub fn get_score<T: RealNumber, V: BaseVector<T>>(&self, y_true: &V, y_pred_prob: &V) -> T {
-        let mut pos = T::zero();
-        let mut neg = T::zero();
+        let (pos, neg) = count_labels(y_true);
 
         let n = y_true.len();
 
-        for i in 0..n {
-            if y_true.get(i) == T::zero() {
-                neg += T::one();
-            } else if y_true.get(i) == T::one() {
-                pos += T::one();
-            } else {
-                panic!(
-                    "AUC is only for binary classification. Invalid label: {}",
-                    y_true.get(i)
-                );
-            }
-        }
-
         let mut y_pred = y_pred_prob.to_vec();
 
         let label_idx = y_pred.quick_argsort_mut();
@@ -87,6 +103,119 @@ impl AUC {
 
         (auc - (pos * (pos + T::one()) / T::two())) / (pos * neg)
     }
+
+    /// Weighted AUC score, area under the ROC curve where every sample counts with its weight.
+    /// * `y_true` - cround truth (correct) labels.
+    /// * `y_pred_probabilities` - probability estimates or decision scores of the positive class, as returned by a classifier.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<T: RealNumber, V: BaseVector<T>>(
+        &self,
+        y_true: &V,
+        y_pred_prob: &V,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
+        if y_true.len() != y_pred_prob.len() {
+            panic!(
+                "The vector sizes don't match: {} != {}",
+                y_true.len(),
+                y_pred_prob.len()
+            );
+        }
+        check_sample_weight(y_true.len(), sample_weight)?;
+        count_labels(y_true);
+
+        let mut y_score = y_pred_prob.to_vec();
+        let label_idx = y_score.quick_argsort_mut();
+
+        let mut tp = T::zero();
+        let mut fp = T::zero();
+        let mut prev_tp = T::zero();
+        let mut prev_fp = T::zero();
+        let mut area = T::zero();
+        for i in (0..y_score.len()).rev() {
+            let w = sample_weight[label_idx[i]];
+            if y_true.get(label_idx[i]) == T::one() {
+                tp += w;
+            } else {
+                fp += w;
+            }
+            if i == 0 || y_score[i - 1] != y_score[i] {
+                area += (fp - prev_fp) * (tp + prev_tp) / T::two();
+                prev_tp = tp;
+                prev_fp = fp;
+            }
+        }
+
+        Ok(area / (tp * fp))
+    }
+
+    /// ROC curve, false and true positive rates for every distinct score threshold.
+    /// * `y_true` - cround truth (correct) labels.
+    /// * `y_score` - probability estimates or decision scores of the positive class, as returned by a classifier.
+    pub fn roc_curve<T: RealNumber, V: BaseVector<T>>(
+        &self,
+        y_true: &V,
+        y_score: &V,
+    ) -> ROCCurve<T> {
+        if y_true.len() != y_score.len() {
+            panic!(
+                "The vector sizes don't match: {} != {}",
+                y_true.len(),
+                y_score.len()
+            );
+        }
+
+        let (pos, neg) = count_labels(y_true);
+
+        let mut y_score = y_score.to_vec();
+        let label_idx = y_score.quick_argsort_mut();
+
+        let mut fpr = vec![T::zero()];
+        let mut tpr = vec![T::zero()];
+        let mut thresholds = vec![T::infinity()];
+
+        let mut tp = T::zero();
+        let mut fp = T::zero();
+        for i in (0..y_score.len()).rev() {
+            if y_true.get(label_idx[i]) == T::one() {
+                tp += T::one();
+            } else {
+                fp += T::one();
+            }
+            if i == 0 || y_score[i - 1] != y_score[i] {
+                fpr.push(fp / neg);
+                tpr.push(tp / pos);
+                thresholds.push(y_score[i]);
+            }
+        }
+
+        ROCCurve {
+            fpr,
+            tpr,
+            thresholds,
+        }
+    }
+}
+
+/// Number of positive and negative labels.
+fn count_labels<T: RealNumber, V: BaseVector<T>>(y_true: &V) -> (T, T) {
+    let mut pos = T::zero();
+    let mut neg = T::zero();
+
+    for i in 0..y_true.len() {
+        if y_true.get(i) == T::zero() {
+            neg += T::one();
+        } else if y_true.get(i) == T::one() {
+            pos += T::one();
+        } else {
+            panic!(
+                "AUC is only for binary classification. Invalid label: {}",
+                y_true.get(i)
+            );
+        }
+    }
+
+    (pos, neg)
 }
 
 #[cfg(test)]
@@ -105,4 +234,47 @@ mod tests {
         assert!((score1 - 0.75).abs() < 1e-8);
         assert!((score2 - 1.0).abs() < 1e-8);
     }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn auc_weighted() {
+        let y_true: Vec<f64> = vec![0., 0., 1., 1., 0., 1.];
+        let y_pred: Vec<f64> = vec![0.1, 0.4, 0.35, 0.8, 0.8, 0.9];
+
+        let uniform: f64 = AUC {}
+            .get_score_weighted(&y_true, &y_pred, &[1.; 6])
+            .unwrap();
+        assert!((uniform - AUC {}.get_score(&y_true, &y_pred)).abs() < 1e-8);
+
+        let weighted: f64 = AUC {}
+            .get_score_weighted(&y_true, &y_pred, &[1., 2., 1., 0., 1., 3.])
+            .unwrap();
+        let repeated: f64 = AUC {}.get_score(
+            &vec![0., 0., 0., 1., 0., 1., 1., 1.],
+            &vec![0.1, 0.4, 0.4, 0.35, 0.8, 0.9, 0.9, 0.9],
+        );
+        assert!((weighted - repeated).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn roc_curve() {
+        let y_true: Vec<f64> = vec![0., 0., 1., 1., 0., 1.];
+        let y_score: Vec<f64> = vec![0.1, 0.4, 0.35, 0.8, 0.8, 0.9];
+
+        let curve = AUC {}.roc_curve(&y_true, &y_score);
+
+        assert_eq!(vec![0., 0., 1. / 3., 2. / 3., 2. / 3., 1.], curve.fpr);
+        assert_eq!(vec![0., 1. / 3., 2. / 3., 2. / 3., 1., 1.], curve.tpr);
+        assert_eq!(
+            vec![f64::INFINITY, 0.9, 0.8, 0.4, 0.35, 0.1],
+            curve.thresholds
+        );
+
+        let mut area = 0.;
+        for i in 1..curve.fpr.len() {
+            area += (curve.fpr[i] - curve.fpr[i - 1]) * (curve.tpr[i] + curve.tpr[i - 1]) / 2.;
+        }
+        assert!((area - AUC {}.get_score(&y_true, &y_score)).abs() < 1e-8);
+    }
 }
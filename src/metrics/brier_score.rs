@@ -0,0 +1,210 @@
+//! # Brier Score
+//!
+//! The Brier score is the mean squared difference between predicted probabilities and actual outcomes.
+//! It measures both how well a classifier separates classes and how well its probabilities are calibrated, lower values are better.
+//!
+//! \\[BS = \frac{1}{n}\sum_{i=1}^n\sum_{k=1}^K (p_{ik} - y_{ik})^2\\]
+//!
+//! where \\(y_{ik}\\) is 1 when sample \\(i\\) has label \\(k\\) and 0 otherwise and \\(p_{ik}\\) is the predicted probability of label \\(k\\).
+//! The multiclass score is between 0 and 2.
+//!
+//! A matrix with a single column holds probabilities of label 1 in binary classification, the score is then
+//! \\(\frac{1}{n}\sum_{i=1}^n (p_i - y_i)^2\\), between 0 and 1. This is half of the multiclass score computed from both columns.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::metrics::brier_score::BrierScore;
+//!
+//! let y_true: Vec<f64> = vec![0., 1., 1., 0.];
+//! let y_proba = DenseMatrix::from_2d_array(&[&[0.1], &[0.9], &[0.8], &[0.3]]);
+//!
+//! let score: f64 = BrierScore::default().get_score(&y_true, &y_proba);
+//! assert!((score - 0.0375).abs() < 1e-8);
+//! ```
+//!
+//! ## References:
+//! * ["Verification of forecasts expressed in terms of probability", Brier G. W., 1950](https://doi.org/10.1175/1520-0493(1950)078%3C0001:VOFEIT%3E2.0.CO;2)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::metrics::check_sample_weight;
+
+/// Brier score
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct BrierScore<T: RealNumber> {
+    /// Labels that correspond to columns of the probability matrix. When `None` sorted unique values of `y_true` are used,
+    /// set labels explicitly when some of them may be missing in `y_true`.
+    pub labels: Option<Vec<T>>,
+}
+
+impl<T: RealNumber> Default for BrierScore<T> {
+    fn default() -> Self {
+        BrierScore { labels: None }
+    }
+}
+
+impl<T: RealNumber> BrierScore<T> {
+    /// Labels that correspond to columns of the probability matrix.
+    pub fn with_labels(mut self, labels: Vec<T>) -> Self {
+        self.labels = Some(labels);
+        self
+    }
+
+    /// Computes Brier score. Note that for binary labels an _Nx2_ matrix gives the multiclass score, which is twice the score of the _Nx1_ matrix of probabilities of label 1.
+    /// * `y_true` - ground truth (correct) labels.
+    /// * `y_proba` - _NxK_ matrix of predicted probabilities, as returned by a classifier, or _Nx1_ matrix of probabilities of label 1.
+    pub fn get_score<M: Matrix<T>>(&self, y_true: &M::RowVector, y_proba: &M) -> T {
+        self.get_score_weighted(y_true, y_proba, &vec![T::one(); y_true.len()])
+            .unwrap()
+    }
+
+    /// Computes weighted Brier score, the squared differences of every sample count with its weight.
+    /// * `y_true` - ground truth (correct) labels.
+    /// * `y_proba` - _NxK_ matrix of predicted probabilities, as returned by a classifier, or _Nx1_ matrix of probabilities of label 1.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<M: Matrix<T>>(
+        &self,
+        y_true: &M::RowVector,
+        y_proba: &M,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
+        let (n, k) = y_proba.shape();
+        if y_true.len() != n {
+            panic!("The vector sizes don't match: {} != {}", y_true.len(), n);
+        }
+        let total_weight = check_sample_weight(n, sample_weight)?;
+
+        let mut score = T::zero();
+
+        if k == 1 {
+            for (i, &w) in sample_weight.iter().enumerate() {
+                let label = y_true.get(i);
+                if label != T::zero() && label != T::one() {
+                    panic!(
+                        "Probabilities of a single class are only for labels 0 and 1. Invalid label: {}",
+                        label
+                    );
+                }
+                score += w * (y_proba.get(i, 0) - label).square();
+            }
+        } else {
+            let labels = match &self.labels {
+                Some(labels) => {
+                    let mut labels = labels.clone();
+                    labels.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                    labels
+                }
+                None => {
+                    let mut labels = y_true.to_vec();
+                    labels.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                    labels.dedup();
+                    labels
+                }
+            };
+            if labels.len() != k {
+                panic!(
+                    "Number of labels {} does not match number of columns {}",
+                    labels.len(),
+                    k
+                );
+            }
+
+            for (i, &w) in sample_weight.iter().enumerate() {
+                let label = y_true.get(i);
+                let c = labels
+                    .binary_search_by(|l| l.partial_cmp(&label).unwrap())
+                    .unwrap_or_else(|_| panic!("Unknown label: {}", label));
+                for j in 0..k {
+                    let outcome = if j == c { T::one() } else { T::zero() };
+                    score += w * (y_proba.get(i, j) - outcome).square();
+                }
+            }
+        }
+
+        Ok(score / total_weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn brier_score_binary() {
+        let y_true: Vec<f64> = vec![0., 1., 1., 0.];
+        let y_proba = DenseMatrix::from_2d_array(&[&[0.1], &[0.9], &[0.8], &[0.3]]);
+        let two_columns =
+            DenseMatrix::from_2d_array(&[&[0.9, 0.1], &[0.1, 0.9], &[0.2, 0.8], &[0.7, 0.3]]);
+
+        let score1: f64 = BrierScore::default().get_score(&y_true, &y_proba);
+        let score2: f64 = BrierScore::default().get_score(&y_true, &two_columns);
+        let score3: f64 = BrierScore::default().get_score(
+            &y_true,
+            &DenseMatrix::from_2d_array(&[&[0.], &[1.], &[1.], &[0.]]),
+        );
+
+        assert!((score1 - 0.0375).abs() < 1e-8);
+        assert!((score2 - 2. * score1).abs() < 1e-8);
+        assert!(score3.abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn brier_score_binary_two_columns_is_multiclass_sum() {
+        let y_true: Vec<f64> = vec![0., 1., 1., 0.];
+        let y_proba = DenseMatrix::from_2d_array(&[&[0.01], &[0.9], &[0.7], &[0.2]]);
+        let two_columns =
+            DenseMatrix::from_2d_array(&[&[0.99, 0.01], &[0.1, 0.9], &[0.3, 0.7], &[0.8, 0.2]]);
+
+        let binary: f64 = BrierScore::default().get_score(&y_true, &y_proba);
+        let multiclass: f64 = BrierScore::default().get_score(&y_true, &two_columns);
+
+        assert!((binary - 0.035025).abs() < 1e-8);
+        assert!((multiclass - 0.07005).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn brier_score_weighted() {
+        let y_true: Vec<f64> = vec![0., 1., 1., 0.];
+        let y_proba = DenseMatrix::from_2d_array(&[&[0.1], &[0.9], &[0.8], &[0.3]]);
+
+        let uniform: f64 = BrierScore::default()
+            .get_score_weighted(&y_true, &y_proba, &[1., 1., 1., 1.])
+            .unwrap();
+        let weighted: f64 = BrierScore::default()
+            .get_score_weighted(&y_true, &y_proba, &[1., 0., 2., 1.])
+            .unwrap();
+
+        assert!((uniform - 0.0375).abs() < 1e-8);
+        assert!((weighted - (0.01 + 2. * 0.04 + 0.09) / 4.).abs() < 1e-8);
+        assert!(BrierScore::default()
+            .get_score_weighted(&y_true, &y_proba, &[1., -1., 1., 1.])
+            .is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn brier_score_multiclass() {
+        let y_true: Vec<f64> = vec![0., 2.];
+        let y_proba = DenseMatrix::from_2d_array(&[&[0.6, 0.3, 0.1], &[0.2, 0.2, 0.6]]);
+
+        let score: f64 = BrierScore::default()
+            .with_labels(vec![0., 1., 2.])
+            .get_score(&y_true, &y_proba);
+
+        assert!((score - (0.16 + 0.09 + 0.01 + 0.04 + 0.04 + 0.16) / 2.).abs() < 1e-8);
+    }
+}
@@ -0,0 +1,253 @@
+//! # Classification Report
+//!
+//! Summary of classifier quality: precision, recall, F1 score and support (number of true samples) of every class,
+//! overall accuracy and macro and weighted averages of per-class scores. Macro average treats all classes equally,
+//! weighted average weights every class by its support.
+//!
+//! The report implements `Display` and prints as a table.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::metrics::classification_report::ClassificationReport;
+//!
+//! let y_true: Vec<f64> = vec![0., 1., 2., 2., 2.];
+//! let y_pred: Vec<f64> = vec![0., 0., 2., 2., 1.];
+//!
+//! let report = ClassificationReport::new(&y_true, &y_pred);
+//!
+//! assert_eq!(report.classes[2].support, 3);
+//! println!("{}", report);
+//! ```
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Failed;
+use crate::linalg::{BaseMatrix, BaseVector};
+use crate::math::num::RealNumber;
+use crate::metrics::check_sample_weight;
+use crate::metrics::confusion_matrix::ConfusionMatrix;
+
+/// Scores of a single class, or their average
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassScores<T: RealNumber> {
+    /// How many samples predicted as the class belong to it, 0 when no sample is predicted as the class.
+    pub precision: T,
+    /// How many samples of the class are predicted as the class, 0 when there are no such samples.
+    pub recall: T,
+    /// Harmonic mean of precision and recall.
+    pub f1: T,
+    /// Number of samples with the class as a true label.
+    pub support: usize,
+}
+
+/// Classification report
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct ClassificationReport<T: RealNumber> {
+    /// Sorted labels found in true or predicted values.
+    pub labels: Vec<T>,
+    /// Scores of every class, in the order of `labels`.
+    pub classes: Vec<ClassScores<T>>,
+    /// Fraction of correctly predicted samples, or of their total weight when the report is built with sample weights.
+    pub accuracy: T,
+    /// Unweighted mean of per-class scores.
+    pub macro_avg: ClassScores<T>,
+    /// Mean of per-class scores weighted by support, or by total weight of samples in the class when the report is built with sample weights.
+    pub weighted_avg: ClassScores<T>,
+}
+
+impl<T: RealNumber> ClassificationReport<T> {
+    /// Builds classification report
+    /// * `y_true` - cround truth (correct) labels.
+    /// * `y_pred` - predicted labels, as returned by a classifier.
+    pub fn new<V: BaseVector<T>>(y_true: &V, y_pred: &V) -> Self {
+        ClassificationReport::new_weighted(y_true, y_pred, &vec![T::one(); y_true.len()]).unwrap()
+    }
+
+    /// Builds classification report where every sample counts with its weight. Support is still the number of samples of every class.
+    /// * `y_true` - cround truth (correct) labels.
+    /// * `y_pred` - predicted labels, as returned by a classifier.
+    /// * `sample_weight` - weight of every sample.
+    pub fn new_weighted<V: BaseVector<T>>(
+        y_true: &V,
+        y_pred: &V,
+        sample_weight: &[T],
+    ) -> Result<Self, Failed> {
+        let (labels, cm) =
+            ConfusionMatrix::default().get_score_weighted(y_true, y_pred, sample_weight)?;
+        let total_weight = check_sample_weight(y_true.len(), sample_weight)?;
+        let k = labels.len();
+
+        let mut support = vec![0; k];
+        for i in 0..y_true.len() {
+            let label = y_true.get(i);
+            support[labels
+                .binary_search_by(|l| l.partial_cmp(&label).unwrap())
+                .unwrap()] += 1;
+        }
+
+        let ratio = |a: T, b: T| if b == T::zero() { T::zero() } else { a / b };
+
+        let mut correct = T::zero();
+        let mut class_weight = Vec::with_capacity(k);
+        let classes: Vec<ClassScores<T>> = (0..k)
+            .map(|c| {
+                let tp = cm.get(c, c);
+                let actual = (0..k).fold(T::zero(), |s, j| s + cm.get(c, j));
+                let predicted = (0..k).fold(T::zero(), |s, i| s + cm.get(i, c));
+                correct += tp;
+                class_weight.push(actual);
+
+                let precision = ratio(tp, predicted);
+                let recall = ratio(tp, actual);
+                ClassScores {
+                    precision,
+                    recall,
+                    f1: ratio(T::two() * precision * recall, precision + recall),
+                    support: support[c],
+                }
+            })
+            .collect();
+
+        let n = y_true.len();
+        let average = |weight: &dyn Fn(usize) -> T| {
+            let total = (0..k).fold(T::zero(), |s, c| s + weight(c));
+            let mean = |score: &dyn Fn(&ClassScores<T>) -> T| {
+                ratio(
+                    classes
+                        .iter()
+                        .enumerate()
+                        .fold(T::zero(), |s, (c, scores)| s + weight(c) * score(scores)),
+                    total,
+                )
+            };
+            ClassScores {
+                precision: mean(&|c| c.precision),
+                recall: mean(&|c| c.recall),
+                f1: mean(&|c| c.f1),
+                support: n,
+            }
+        };
+
+        let macro_avg = average(&|_| T::one());
+        let weighted_avg = average(&|c| class_weight[c]);
+
+        Ok(ClassificationReport {
+            labels,
+            accuracy: ratio(correct, total_weight),
+            classes,
+            macro_avg,
+            weighted_avg,
+        })
+    }
+}
+
+impl<T: RealNumber> fmt::Display for ClassificationReport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let row = |f: &mut fmt::Formatter<'_>, name: &str, s: &ClassScores<T>| {
+            writeln!(
+                f,
+                "{:>12} {:>9.2} {:>9.2} {:>9.2} {:>9}",
+                name,
+                s.precision.to_f64().unwrap(),
+                s.recall.to_f64().unwrap(),
+                s.f1.to_f64().unwrap(),
+                s.support
+            )
+        };
+
+        writeln!(
+            f,
+            "{:>12} {:>9} {:>9} {:>9} {:>9}",
+            "", "precision", "recall", "f1-score", "support"
+        )?;
+        writeln!(f)?;
+        for (label, scores) in self.labels.iter().zip(self.classes.iter()) {
+            row(f, &label.to_string(), scores)?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:>12} {:>9} {:>9} {:>9.2} {:>9}",
+            "accuracy",
+            "",
+            "",
+            self.accuracy.to_f64().unwrap(),
+            self.macro_avg.support
+        )?;
+        row(f, "macro avg", &self.macro_avg)?;
+        row(f, "weighted avg", &self.weighted_avg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn classification_report() {
+        let y_true: Vec<f64> = vec![0., 1., 2., 2., 2.];
+        let y_pred: Vec<f64> = vec![0., 0., 2., 2., 1.];
+
+        let report = ClassificationReport::new(&y_true, &y_pred);
+
+        assert_eq!(vec![0., 1., 2.], report.labels);
+        assert_eq!(
+            ClassScores {
+                precision: 0.5,
+                recall: 1.,
+                f1: 2. / 3.,
+                support: 1
+            },
+            report.classes[0]
+        );
+        assert_eq!(0., report.classes[1].f1);
+        assert_eq!(1., report.classes[2].precision);
+        assert!((report.classes[2].recall - 2. / 3.).abs() < 1e-8);
+        assert!((report.accuracy - 0.6).abs() < 1e-8);
+
+        assert!((report.macro_avg.precision - 0.5).abs() < 1e-8);
+        assert!((report.macro_avg.recall - 5. / 9.).abs() < 1e-8);
+        assert!((report.weighted_avg.precision - 0.7).abs() < 1e-8);
+        assert!((report.weighted_avg.recall - 0.6).abs() < 1e-8);
+        assert_eq!(5, report.weighted_avg.support);
+
+        let table = format!("{}", report);
+        assert_eq!(9, table.lines().count());
+        assert!(table.contains("weighted avg"));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn classification_report_weighted() {
+        let y_true: Vec<f64> = vec![0., 1., 2., 2., 2.];
+        let y_pred: Vec<f64> = vec![0., 0., 2., 2., 1.];
+
+        let uniform =
+            ClassificationReport::new_weighted(&y_true, &y_pred, &[1., 1., 1., 1., 1.]).unwrap();
+        let report = ClassificationReport::new(&y_true, &y_pred);
+        assert_eq!(report.classes, uniform.classes);
+        assert_eq!(report.weighted_avg, uniform.weighted_avg);
+
+        let weighted =
+            ClassificationReport::new_weighted(&y_true, &y_pred, &[1., 3., 1., 1., 2.]).unwrap();
+        let repeated = ClassificationReport::new(
+            &vec![0., 1., 1., 1., 2., 2., 2., 2.],
+            &vec![0., 0., 0., 0., 2., 2., 1., 1.],
+        );
+        assert_eq!(3, weighted.classes[2].support);
+        assert_eq!(4, repeated.classes[2].support);
+        assert_eq!(repeated.classes[0], weighted.classes[0]);
+        assert!((weighted.accuracy - repeated.accuracy).abs() < 1e-8);
+        assert!((weighted.weighted_avg.f1 - repeated.weighted_avg.f1).abs() < 1e-8);
+
+        assert!(ClassificationReport::new_weighted(&y_true, &y_pred, &[1., 1.]).is_err());
+    }
+}
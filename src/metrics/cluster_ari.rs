@@ -0,0 +1,100 @@
+//! # Adjusted Rand Index
+//!
+//! The Rand index is the fraction of pairs of samples on which two labelings agree: both put the pair in the same cluster or both in different ones.
+//! The adjusted Rand index (ARI) corrects it for chance, so that random labelings score close to 0 regardless of the number of clusters
+//! while identical labelings, up to a permutation of label values, score 1.
+//!
+//! \\[ARI = \frac{\sum_{ij} \binom{n_{ij}}{2} - \left[\sum_i \binom{a_i}{2} \sum_j \binom{b_j}{2}\right] / \binom{n}{2}}{\frac{1}{2}\left[\sum_i \binom{a_i}{2} + \sum_j \binom{b_j}{2}\right] - \left[\sum_i \binom{a_i}{2} \sum_j \binom{b_j}{2}\right] / \binom{n}{2}}\\]
+//!
+//! where \\(n_{ij}\\) is the number of samples in class \\(i\\) and cluster \\(j\\), \\(a_i\\) and \\(b_j\\) are sizes of classes and clusters.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::metrics::cluster_ari::AdjustedRandIndex;
+//! let labels_true: Vec<f64> = vec![0., 0., 1., 1., 2., 2.];
+//! let labels_pred: Vec<f64> = vec![1., 1., 0., 0., 0., 2.];
+//!
+//! let ari: f64 = AdjustedRandIndex {}.get_score(&labels_true, &labels_pred);
+//! ```
+//!
+//! ## References:
+//! * ["Comparing partitions", Hubert L., Arabie P., 1985](https://doi.org/10.1007/BF01908075)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::linalg::BaseVector;
+use crate::math::num::RealNumber;
+use crate::metrics::cluster_helpers::*;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+/// Adjusted Rand Index.
+pub struct AdjustedRandIndex {}
+
+impl AdjustedRandIndex {
+    /// Computes adjusted Rand index, between -1.0 and 1.0.
+    /// * `labels_true` - ground truth class labels to be used as a reference.
+    /// * `labels_pred` - cluster labels to evaluate.
+    pub fn get_score<T: RealNumber, V: BaseVector<T>>(
+        &self,
+        labels_true: &V,
+        labels_pred: &V,
+    ) -> T {
+        if labels_true.len() != labels_pred.len() {
+            panic!(
+                "The vector sizes don't match: {} != {}",
+                labels_true.len(),
+                labels_pred.len()
+            );
+        }
+
+        let contingency = contingency_matrix(&labels_true.to_vec(), &labels_pred.to_vec());
+        let pairs = |n: usize| T::from_usize(n * n.saturating_sub(1) / 2).unwrap();
+
+        let mut index = T::zero();
+        let mut sum_classes = T::zero();
+        let mut col_sums = vec![0; contingency.first().map_or(0, |row| row.len())];
+        for row in contingency.iter() {
+            sum_classes += pairs(row.iter().sum());
+            for (j, &n_ij) in row.iter().enumerate() {
+                index += pairs(n_ij);
+                col_sums[j] += n_ij;
+            }
+        }
+        let sum_clusters = col_sums.iter().fold(T::zero(), |s, &b| s + pairs(b));
+
+        let expected = sum_classes * sum_clusters / pairs(labels_true.len());
+        let max_index = (sum_classes + sum_clusters) / T::two();
+
+        if max_index == expected {
+            T::one()
+        } else {
+            (index - expected) / (max_index - expected)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn adjusted_rand_index() {
+        let labels_true: Vec<f64> = vec![0., 0., 1., 1.];
+
+        let score1: f64 = AdjustedRandIndex {}.get_score(&labels_true, &vec![1., 1., 0., 0.]);
+        let score2: f64 = AdjustedRandIndex {}.get_score(&labels_true, &vec![0., 0., 1., 2.]);
+        let score3: f64 = AdjustedRandIndex {}.get_score(&labels_true, &vec![0., 1., 0., 1.]);
+        let score4: f64 = AdjustedRandIndex {}.get_score(&vec![0., 0., 0.], &vec![1., 1., 1.]);
+
+        assert!((score1 - 1.0).abs() < 1e-8);
+        assert!((score2 - 0.571428571).abs() < 1e-8);
+        assert!((score3 + 0.5).abs() < 1e-8);
+        assert!((score4 - 1.0).abs() < 1e-8);
+    }
+}
@@ -1,3 +1,32 @@
+//! # Homogeneity, Completeness and V-Measure
+//!
+//! Entropy-based scores that compare a cluster labeling with ground truth classes, all of them are between 0.0 and 1.0:
+//!
+//! * Homogeneity is 1 when every cluster contains only members of a single class.
+//! * Completeness is 1 when all members of a class are assigned to the same cluster.
+//! * V-Measure is the harmonic mean of homogeneity and completeness.
+//!
+//! \[h = 1 - rac{H(C|K)}{H(C)}, \quad c = 1 - rac{H(K|C)}{H(K)}, \quad v = rac{2hc}{h + c}\]
+//!
+//! where \(H(C)\) and \(H(K)\) are entropies of classes and clusters and \(H(C|K)\), \(H(K|C)\) are conditional entropies.
+//! Homogeneity (completeness) is 1 when all samples belong to a single class (cluster).
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::metrics::cluster_hcv::HCVScore;
+//! let labels_true: Vec<f64> = vec![0., 0., 1., 1.];
+//! let labels_pred: Vec<f64> = vec![0., 0., 1., 2.];
+//!
+//! let (homogeneity, completeness, v_measure): (f64, f64, f64) =
+//!     HCVScore {}.get_score(&labels_true, &labels_pred);
+//! ```
+//!
+//! ## References:
+//! * ["V-Measure: A conditional entropy-based external cluster evaluation measure", Rosenberg A., Hirschberg J., 2007](https://aclanthology.org/D07-1043/)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -26,8 +55,14 @@ impl HCVScore {
         let contingency = contingency_matrix(&labels_true, &labels_pred);
         let mi: T = mutual_info_score(&contingency);
 
-        let homogeneity = entropy_c.map(|e| mi / e).unwrap_or_else(T::one);
-        let completeness = entropy_k.map(|e| mi / e).unwrap_or_else(T::one);
+        let homogeneity = entropy_c
+            .filter(|e| *e > T::zero())
+            .map(|e| mi / e)
+            .unwrap_or_else(T::one);
+        let completeness = entropy_k
+            .filter(|e| *e > T::zero())
+            .map(|e| mi / e)
+            .unwrap_or_else(T::one);
 
         let v_measure_score = if homogeneity + completeness == T::zero() {
             T::zero()
@@ -54,4 +89,23 @@ mod tests {
         assert!((0.5440f32 - scores.1).abs() < 1e-4);
         assert!((0.3471f32 - scores.2).abs() < 1e-4);
     }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn single_class_or_cluster() {
+        let single = vec![0.0, 0.0, 0.0, 0.0];
+        let split = vec![0.0, 0.0, 1.0, 2.0];
+
+        let scores: (f64, f64, f64) = HCVScore {}.get_score(&single, &split);
+        assert!((1.0 - scores.0).abs() < 1e-8);
+        assert!(scores.1.abs() < 1e-8);
+        assert!(scores.2.abs() < 1e-8);
+
+        let scores: (f64, f64, f64) = HCVScore {}.get_score(&split, &single);
+        assert!(scores.0.abs() < 1e-8);
+        assert!((1.0 - scores.1).abs() < 1e-8);
+
+        let scores: (f64, f64, f64) = HCVScore {}.get_score(&single, &single);
+        assert_eq!((1.0, 1.0, 1.0), scores);
+    }
 }
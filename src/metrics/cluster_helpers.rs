@@ -1,6 +1,7 @@
 #![allow(clippy::ptr_arg)]
-use std::collections::HashMap;
+use alloc::vec::Vec;
 
+use crate::collections::HashMap;
 use crate::math::num::RealNumber;
 use crate::math::vector::RealNumberVector;
 
@@ -25,7 +26,7 @@ pub fn contingency_matrix<T: RealNumber>(
 }
 
 pub fn entropy<T: RealNumber>(data: &[T]) -> Option<T> {
-    let mut bincounts = HashMap::with_capacity(data.len());
+    let mut bincounts = HashMap::new();
 
     for e in data.iter() {
         let k = e.to_i64().unwrap();
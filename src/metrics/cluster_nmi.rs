@@ -0,0 +1,89 @@
+//! # Normalized Mutual Information
+//!
+//! Mutual information measures how much knowing the cluster of a sample tells about its class. Normalized mutual information (NMI)
+//! divides it by the arithmetic mean of entropies of both labelings, so that the score is between 0 (independent labelings)
+//! and 1 (identical labelings, up to a permutation of label values).
+//!
+//! \\[NMI(U, V) = \frac{I(U, V)}{\frac{1}{2}\left[H(U) + H(V)\right]}\\]
+//!
+//! where \\(I(U, V)\\) is mutual information and \\(H(U)\\), \\(H(V)\\) are entropies of true and predicted labels.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::metrics::cluster_nmi::NormalizedMutualInfo;
+//! let labels_true: Vec<f64> = vec![0., 0., 1., 1., 2., 2.];
+//! let labels_pred: Vec<f64> = vec![1., 1., 0., 0., 0., 2.];
+//!
+//! let nmi: f64 = NormalizedMutualInfo {}.get_score(&labels_true, &labels_pred);
+//! ```
+//!
+//! ## References:
+//! * ["Information Theoretic Measures for Clusterings Comparison", Vinh N. X., Epps J., Bailey J., 2010](https://jmlr.csail.mit.edu/papers/v11/vinh10a.html)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::linalg::BaseVector;
+use crate::math::num::RealNumber;
+use crate::metrics::cluster_helpers::*;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+/// Normalized Mutual Information.
+pub struct NormalizedMutualInfo {}
+
+impl NormalizedMutualInfo {
+    /// Computes normalized mutual information, between 0.0 and 1.0.
+    /// * `labels_true` - ground truth class labels to be used as a reference.
+    /// * `labels_pred` - cluster labels to evaluate.
+    pub fn get_score<T: RealNumber, V: BaseVector<T>>(
+        &self,
+        labels_true: &V,
+        labels_pred: &V,
+    ) -> T {
+        if labels_true.len() != labels_pred.len() {
+            panic!(
+                "The vector sizes don't match: {} != {}",
+                labels_true.len(),
+                labels_pred.len()
+            );
+        }
+
+        let labels_true = labels_true.to_vec();
+        let labels_pred = labels_pred.to_vec();
+        let entropy_c: T = entropy(&labels_true).unwrap_or_else(T::zero);
+        let entropy_k: T = entropy(&labels_pred).unwrap_or_else(T::zero);
+
+        if entropy_c == T::zero() && entropy_k == T::zero() {
+            return T::one();
+        }
+
+        let mi: T = mutual_info_score(&contingency_matrix(&labels_true, &labels_pred));
+
+        mi / ((entropy_c + entropy_k) / T::two())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn normalized_mutual_info() {
+        let labels_true: Vec<f64> = vec![0., 0., 1., 1.];
+
+        let score1: f64 = NormalizedMutualInfo {}.get_score(&labels_true, &vec![1., 1., 0., 0.]);
+        let score2: f64 = NormalizedMutualInfo {}.get_score(&labels_true, &vec![0., 1., 0., 1.]);
+        let score3: f64 = NormalizedMutualInfo {}.get_score(&labels_true, &vec![0., 0., 1., 2.]);
+        let score4: f64 = NormalizedMutualInfo {}.get_score(&vec![0., 0.], &vec![3., 3.]);
+
+        assert!((score1 - 1.0).abs() < 1e-8);
+        assert!(score2.abs() < 1e-8);
+        assert!((score3 - 0.8).abs() < 1e-8);
+        assert!((score4 - 1.0).abs() < 1e-8);
+    }
+}
@@ -0,0 +1,165 @@
+//! # Cohen's Kappa
+//!
+//! Cohen's kappa measures agreement between two sets of labels, e.g. between a classifier and annotators or between two annotators,
+//! corrected for agreement expected by chance.
+//!
+//! \\[\kappa = 1 - \frac{\sum_{i,j} w_{ij} o_{ij}}{\sum_{i,j} w_{ij} e_{ij}}\\]
+//!
+//! where \\(o_{ij}\\) is the [confusion matrix](../confusion_matrix/index.html), \\(e_{ij}\\) is the confusion matrix expected when both sets
+//! of labels are independent and \\(w_{ij}\\) is the cost of disagreement between labels \\(i\\) and \\(j\\).
+//! Without weighting every disagreement costs 1. For ordinal labels linear, \\(w_{ij} = \lvert i - j \rvert\\),
+//! or quadratic, \\(w_{ij} = (i - j)^2\\), weighting penalizes distant labels more than neighbouring ones.
+//!
+//! Kappa is 1 for complete agreement, 0 for agreement expected by chance and negative when agreement is worse than chance.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::metrics::cohen_kappa::{CohenKappa, KappaWeights};
+//! let y_pred: Vec<f64> = vec![0., 1., 1., 2., 2., 2.];
+//! let y_true: Vec<f64> = vec![0., 1., 2., 2., 2., 1.];
+//!
+//! let kappa: f64 = CohenKappa::default().get_score(&y_true, &y_pred);
+//! let weighted: f64 = CohenKappa::default()
+//!     .with_weights(KappaWeights::Quadratic)
+//!     .get_score(&y_true, &y_pred);
+//! ```
+//!
+//! ## References:
+//! * ["A Coefficient of Agreement for Nominal Scales", Cohen J., 1960](https://doi.org/10.1177/001316446002000104)
+//! * ["Weighted kappa: Nominal scale agreement with provision for scaled disagreement or partial credit", Cohen J., 1968](https://doi.org/10.1037/h0026256)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Failed;
+use crate::linalg::{BaseMatrix, BaseVector};
+use crate::math::num::RealNumber;
+use crate::metrics::confusion_matrix::ConfusionMatrix;
+
+/// Weighting of disagreements between labels
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KappaWeights {
+    /// Cost of disagreement grows linearly with the distance between sorted labels.
+    Linear,
+    /// Cost of disagreement grows with the squared distance between sorted labels.
+    Quadratic,
+}
+
+/// Cohen's kappa
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct CohenKappa {
+    /// Weighting of disagreements, `None` for unweighted kappa.
+    pub weights: Option<KappaWeights>,
+}
+
+impl CohenKappa {
+    /// Weighting of disagreements between labels.
+    pub fn with_weights(mut self, weights: KappaWeights) -> Self {
+        self.weights = Some(weights);
+        self
+    }
+
+    /// Computes Cohen's kappa
+    /// * `y_true` - cround truth (correct) labels.
+    /// * `y_pred` - predicted labels, as returned by a classifier.
+    pub fn get_score<T: RealNumber, V: BaseVector<T>>(&self, y_true: &V, y_pred: &V) -> T {
+        self.get_score_weighted(y_true, y_pred, &vec![T::one(); y_true.len()])
+            .unwrap()
+    }
+
+    /// Computes Cohen's kappa of weighted samples
+    /// * `y_true` - cround truth (correct) labels.
+    /// * `y_pred` - predicted labels, as returned by a classifier.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<T: RealNumber, V: BaseVector<T>>(
+        &self,
+        y_true: &V,
+        y_pred: &V,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
+        let (labels, cm) =
+            ConfusionMatrix::default().get_score_weighted(y_true, y_pred, sample_weight)?;
+        let k = labels.len();
+        let n = sample_weight.iter().fold(T::zero(), |s, &w| s + w);
+
+        let row_sums: Vec<T> = (0..k)
+            .map(|i| (0..k).fold(T::zero(), |s, j| s + cm.get(i, j)))
+            .collect();
+        let col_sums: Vec<T> = (0..k)
+            .map(|j| (0..k).fold(T::zero(), |s, i| s + cm.get(i, j)))
+            .collect();
+
+        let mut observed = T::zero();
+        let mut expected = T::zero();
+        for (i, row_sum) in row_sums.iter().enumerate() {
+            for (j, col_sum) in col_sums.iter().enumerate() {
+                let distance = T::from_usize(i.abs_diff(j)).unwrap();
+                let w = match self.weights {
+                    None => {
+                        if i == j {
+                            T::zero()
+                        } else {
+                            T::one()
+                        }
+                    }
+                    Some(KappaWeights::Linear) => distance,
+                    Some(KappaWeights::Quadratic) => distance * distance,
+                };
+                observed += w * cm.get(i, j);
+                expected += w * *row_sum * *col_sum / n;
+            }
+        }
+
+        if expected == T::zero() {
+            Ok(T::one())
+        } else {
+            Ok(T::one() - observed / expected)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn cohen_kappa() {
+        let y_true: Vec<f64> = vec![0., 1., 2., 2., 2., 1.];
+        let y_pred: Vec<f64> = vec![0., 1., 1., 2., 2., 2.];
+
+        let score1: f64 = CohenKappa::default().get_score(&y_true, &y_pred);
+        let score2: f64 = CohenKappa::default().get_score(&y_true, &y_true);
+
+        assert!((score1 - 0.454545454).abs() < 1e-8);
+        assert!((score2 - 1.0).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn cohen_kappa_weighted() {
+        let y_true: Vec<f64> = vec![0., 1., 2., 2., 2., 1.];
+        let y_pred: Vec<f64> = vec![0., 1., 1., 2., 2., 2.];
+
+        let linear: f64 = CohenKappa::default()
+            .with_weights(KappaWeights::Linear)
+            .get_score(&y_true, &y_pred);
+        let quadratic: f64 = CohenKappa::default()
+            .with_weights(KappaWeights::Quadratic)
+            .get_score(&y_true, &y_pred);
+
+        assert!((linear - 0.571428571).abs() < 1e-8);
+        assert!((quadratic - 0.7).abs() < 1e-8);
+
+        let opposite: f64 = CohenKappa::default()
+            .with_weights(KappaWeights::Quadratic)
+            .get_score(&vec![0., 0., 2., 2.], &vec![2., 2., 0., 0.]);
+        assert!((opposite + 1.0).abs() < 1e-8);
+    }
+}
@@ -0,0 +1,233 @@
+//! # Confusion Matrix
+//!
+//! A confusion matrix shows how predicted labels of a classifier relate to true ones. Element _(i, j)_ is the number of samples
+//! with true label _i_ that were predicted as label _j_, so correct predictions lie on the diagonal.
+//!
+//! Rows and columns follow the sorted union of labels found in `y_true` and `y_pred`, this ordering is returned together with the matrix.
+//! A normalized matrix divides every count by the number of samples with the same true label, the same predicted label or by all samples,
+//! see [`Normalize`](enum.Normalize.html).
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::BaseMatrix;
+//! use smartcore::metrics::confusion_matrix::{ConfusionMatrix, Normalize};
+//!
+//! let y_true: Vec<f64> = vec![2., 0., 2., 2., 0., 1.];
+//! let y_pred: Vec<f64> = vec![0., 0., 2., 2., 0., 2.];
+//!
+//! let (labels, cm) = ConfusionMatrix::default().get_score(&y_true, &y_pred);
+//!
+//! assert_eq!(labels, vec![0., 1., 2.]);
+//! assert_eq!(cm.get(0, 0), 2.);
+//! assert_eq!(cm.get(2, 0), 1.);
+//!
+//! let (_, recall) = ConfusionMatrix::default()
+//!     .with_normalize(Normalize::True)
+//!     .get_score(&y_true, &y_pred);
+//!
+//! assert!((recall.get(2, 2) - 2. / 3.).abs() < 1e-8);
+//! ```
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Failed;
+use crate::linalg::naive::dense_matrix::DenseMatrix;
+use crate::linalg::{BaseMatrix, BaseVector};
+use crate::math::num::RealNumber;
+use crate::metrics::check_sample_weight;
+
+/// Normalization of a confusion matrix
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Normalize {
+    /// Every row sums up to 1, diagonal elements are recall of each class.
+    True,
+    /// Every column sums up to 1, diagonal elements are precision of each class.
+    Pred,
+    /// All elements sum up to 1.
+    All,
+}
+
+/// Confusion matrix.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct ConfusionMatrix {
+    /// Normalization of counts, `None` keeps raw counts.
+    pub normalize: Option<Normalize>,
+}
+
+impl ConfusionMatrix {
+    /// Normalization of counts.
+    pub fn with_normalize(mut self, normalize: Normalize) -> Self {
+        self.normalize = Some(normalize);
+        self
+    }
+
+    /// Computes confusion matrix. Returns sorted labels and a _KxK_ matrix, where _K_ is the number of labels.
+    /// Rows correspond to true labels, columns to predicted labels.
+    /// * `y_true` - cround truth (correct) labels.
+    /// * `y_pred` - predicted labels, as returned by a classifier.
+    pub fn get_score<T: RealNumber, V: BaseVector<T>>(
+        &self,
+        y_true: &V,
+        y_pred: &V,
+    ) -> (Vec<T>, DenseMatrix<T>) {
+        self.get_score_weighted(y_true, y_pred, &vec![T::one(); y_true.len()])
+            .unwrap()
+    }
+
+    /// Computes confusion matrix of sample weights. Element _(i, j)_ is the total weight of samples
+    /// with true label _i_ that were predicted as label _j_.
+    /// * `y_true` - cround truth (correct) labels.
+    /// * `y_pred` - predicted labels, as returned by a classifier.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<T: RealNumber, V: BaseVector<T>>(
+        &self,
+        y_true: &V,
+        y_pred: &V,
+        sample_weight: &[T],
+    ) -> Result<(Vec<T>, DenseMatrix<T>), Failed> {
+        if y_true.len() != y_pred.len() {
+            panic!(
+                "The vector sizes don't match: {} != {}",
+                y_true.len(),
+                y_pred.len()
+            );
+        }
+        let total_weight = check_sample_weight(y_true.len(), sample_weight)?;
+
+        let mut labels = y_true.to_vec();
+        labels.extend(y_pred.to_vec());
+        labels.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        labels.dedup();
+
+        let index_of = |label: T| {
+            labels
+                .binary_search_by(|l| l.partial_cmp(&label).unwrap())
+                .unwrap()
+        };
+
+        let k = labels.len();
+        let mut cm = DenseMatrix::zeros(k, k);
+        for (i, &w) in sample_weight.iter().enumerate() {
+            cm.add_element_mut(index_of(y_true.get(i)), index_of(y_pred.get(i)), w);
+        }
+
+        match self.normalize {
+            Some(Normalize::True) => {
+                for r in 0..k {
+                    let total = (0..k).fold(T::zero(), |s, c| s + cm.get(r, c));
+                    if total > T::zero() {
+                        for c in 0..k {
+                            cm.div_element_mut(r, c, total);
+                        }
+                    }
+                }
+            }
+            Some(Normalize::Pred) => {
+                for c in 0..k {
+                    let total = (0..k).fold(T::zero(), |s, r| s + cm.get(r, c));
+                    if total > T::zero() {
+                        for r in 0..k {
+                            cm.div_element_mut(r, c, total);
+                        }
+                    }
+                }
+            }
+            Some(Normalize::All) if total_weight > T::zero() => {
+                cm.div_scalar_mut(total_weight);
+            }
+            _ => {}
+        }
+
+        Ok((labels, cm))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn confusion_matrix() {
+        let y_true: Vec<f64> = vec![2., 0., 2., 2., 0., 1.];
+        let y_pred: Vec<f64> = vec![0., 0., 2., 2., 0., 2.];
+
+        let (labels, cm) = ConfusionMatrix::default().get_score(&y_true, &y_pred);
+
+        assert_eq!(vec![0., 1., 2.], labels);
+        assert_eq!(
+            DenseMatrix::from_2d_array(&[&[2., 0., 0.], &[0., 0., 1.], &[1., 0., 2.]]),
+            cm
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn confusion_matrix_label_only_predicted() {
+        let y_true: Vec<f64> = vec![1., 1., 3.];
+        let y_pred: Vec<f64> = vec![1., 2., 3.];
+
+        let (labels, cm) = ConfusionMatrix::default().get_score(&y_true, &y_pred);
+
+        assert_eq!(vec![1., 2., 3.], labels);
+        assert_eq!(
+            DenseMatrix::from_2d_array(&[&[1., 1., 0.], &[0., 0., 0.], &[0., 0., 1.]]),
+            cm
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn confusion_matrix_weighted() {
+        let y_true: Vec<f64> = vec![0., 0., 1., 1.];
+        let y_pred: Vec<f64> = vec![0., 1., 1., 1.];
+
+        let (_, cm) = ConfusionMatrix::default()
+            .get_score_weighted(&y_true, &y_pred, &[0.5, 2., 1., 1.5])
+            .unwrap();
+        assert_eq!(DenseMatrix::from_2d_array(&[&[0.5, 2.], &[0., 2.5]]), cm);
+
+        let (_, by_all) = ConfusionMatrix::default()
+            .with_normalize(Normalize::All)
+            .get_score_weighted(&y_true, &y_pred, &[0.5, 2., 1., 1.5])
+            .unwrap();
+        assert!((by_all.get(0, 1) - 0.4).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn confusion_matrix_normalized() {
+        let y_true: Vec<f64> = vec![2., 0., 2., 2., 0., 1.];
+        let y_pred: Vec<f64> = vec![0., 0., 2., 2., 0., 2.];
+
+        let (_, by_true) = ConfusionMatrix::default()
+            .with_normalize(Normalize::True)
+            .get_score(&y_true, &y_pred);
+        assert!(by_true.approximate_eq(
+            &DenseMatrix::from_2d_array(&[&[1., 0., 0.], &[0., 0., 1.], &[1. / 3., 0., 2. / 3.]]),
+            1e-8
+        ));
+
+        let (_, by_pred) = ConfusionMatrix::default()
+            .with_normalize(Normalize::Pred)
+            .get_score(&y_true, &y_pred);
+        assert!(by_pred.approximate_eq(
+            &DenseMatrix::from_2d_array(&[
+                &[2. / 3., 0., 0.],
+                &[0., 0., 1. / 3.],
+                &[1. / 3., 0., 2. / 3.]
+            ]),
+            1e-8
+        ));
+
+        let (_, by_all) = ConfusionMatrix::default()
+            .with_normalize(Normalize::All)
+            .get_score(&y_true, &y_pred);
+        assert!((by_all.sum() - 1.).abs() < 1e-8);
+        assert!((by_all.get(0, 0) - 1. / 3.).abs() < 1e-8);
+    }
+}
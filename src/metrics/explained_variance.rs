@@ -0,0 +1,175 @@
+//! # Explained Variance Score
+//!
+//! Explained variance score is the proportion of the variance of the target that is explained by predictions.
+//! Unlike [R2](../r2/index.html) it ignores a systematic offset of predictions, so both scores are equal only when the mean error is zero.
+//!
+//! \\[ev(y, \hat{y}) = 1 - \frac{Var(y - \hat{y})}{Var(y)} \\]
+//!
+//! where \\(\hat{y}\\) are predictions and \\(y\\) are true target values.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::metrics::explained_variance::ExplainedVariance;
+//! let y_pred: Vec<f64> = vec![3., -0.5, 2., 7.];
+//! let y_true: Vec<f64> = vec![2.5, 0.0, 2., 8.];
+//!
+//! let ev: f64 = ExplainedVariance {}.get_score(&y_true, &y_pred);
+//! ```
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::metrics::check_sample_weight;
+
+/// Explained Variance Score
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct ExplainedVariance {}
+
+impl ExplainedVariance {
+    /// Computes explained variance score. When the true values are constant the score is 1 for perfect predictions and 0 otherwise.
+    /// * `y_true` - Ground truth (correct) target values.
+    /// * `y_pred` - Estimated target values.
+    pub fn get_score<T: RealNumber, V: BaseVector<T>>(&self, y_true: &V, y_pred: &V) -> T {
+        self.get_score_weighted(y_true, y_pred, &vec![T::one(); y_true.len()])
+            .unwrap()
+    }
+
+    /// Computes weighted explained variance score
+    /// * `y_true` - Ground truth (correct) target values.
+    /// * `y_pred` - Estimated target values.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<T: RealNumber, V: BaseVector<T>>(
+        &self,
+        y_true: &V,
+        y_pred: &V,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
+        if y_true.len() != y_pred.len() {
+            panic!(
+                "The vector sizes don't match: {} != {}",
+                y_true.len(),
+                y_pred.len()
+            );
+        }
+        check_sample_weight(y_true.len(), sample_weight)?;
+
+        let n = y_true.len();
+        let residuals: Vec<T> = (0..n).map(|i| y_true.get(i) - y_pred.get(i)).collect();
+
+        let var_res = weighted_var(&residuals, sample_weight);
+        let var_true = weighted_var(&y_true.to_vec(), sample_weight);
+
+        if var_true == T::zero() {
+            if var_res == T::zero() {
+                Ok(T::one())
+            } else {
+                Ok(T::zero())
+            }
+        } else {
+            Ok(T::one() - var_res / var_true)
+        }
+    }
+
+    /// Computes explained variance score of every output.
+    /// * `y_true` - Ground truth (correct) target values, _NxK_ matrix where _K_ is the number of outputs.
+    /// * `y_pred` - Estimated target values, _NxK_ matrix.
+    pub fn get_score_multioutput<T: RealNumber, M: Matrix<T>>(
+        &self,
+        y_true: &M,
+        y_pred: &M,
+    ) -> Vec<T> {
+        if y_true.shape() != y_pred.shape() {
+            panic!(
+                "The matrix shapes don't match: {:?} != {:?}",
+                y_true.shape(),
+                y_pred.shape()
+            );
+        }
+
+        (0..y_true.shape().1)
+            .map(|j| self.get_score(&y_true.get_col_as_vec(j), &y_pred.get_col_as_vec(j)))
+            .collect()
+    }
+}
+
+fn weighted_var<T: RealNumber>(x: &[T], sample_weight: &[T]) -> T {
+    let total = sample_weight.iter().fold(T::zero(), |s, &w| s + w);
+    let mean = x
+        .iter()
+        .zip(sample_weight)
+        .fold(T::zero(), |s, (&x, &w)| s + w * x)
+        / total;
+    x.iter()
+        .zip(sample_weight)
+        .fold(T::zero(), |s, (&x, &w)| s + w * (x - mean).square())
+        / total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::metrics::r2::R2;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn explained_variance() {
+        let y_true: Vec<f64> = vec![3., -0.5, 2., 7.];
+        let y_pred: Vec<f64> = vec![2.5, 0.0, 2., 8.];
+
+        let score1: f64 = ExplainedVariance {}.get_score(&y_true, &y_pred);
+        let score2: f64 = ExplainedVariance {}.get_score(&y_true, &y_true);
+
+        assert!((score1 - 0.957173447).abs() < 1e-8);
+        assert!((score2 - 1.0).abs() < 1e-8);
+
+        let shifted: Vec<f64> = y_true.iter().map(|y| y + 1.).collect();
+        assert!((ExplainedVariance {}.get_score(&y_true, &shifted) - 1.0).abs() < 1e-8);
+        assert!(R2 {}.get_score(&y_true, &shifted) < 1.0);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn explained_variance_weighted() {
+        let y_true: Vec<f64> = vec![3., -0.5, 2., 7., 5.];
+        let y_pred: Vec<f64> = vec![2.5, 0.0, 2., 8., 1.];
+
+        let score: f64 = ExplainedVariance {}
+            .get_score_weighted(&y_true, &y_pred, &[1., 1., 1., 1., 0.])
+            .unwrap();
+
+        assert!((score - 0.957173447).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn explained_variance_multioutput() {
+        let y_true = DenseMatrix::from_2d_array(&[&[0.5, 1.], &[-1., 1.], &[7., -6.]]);
+        let y_pred = DenseMatrix::from_2d_array(&[&[0., 2.], &[-1., 2.], &[8., -5.]]);
+
+        let scores: Vec<f64> = ExplainedVariance {}.get_score_multioutput(&y_true, &y_pred);
+
+        assert_eq!(2, scores.len());
+        assert!((scores[0] - 30. / 31.).abs() < 1e-8);
+        assert!((scores[1] - 1.0).abs() < 1e-8);
+
+        let constant = DenseMatrix::from_2d_array(&[&[1.], &[1.]]);
+        let off = DenseMatrix::from_2d_array(&[&[1.], &[2.]]);
+        assert_eq!(
+            vec![1.],
+            ExplainedVariance {}.get_score_multioutput(&constant, &constant)
+        );
+        assert_eq!(
+            vec![0.],
+            ExplainedVariance {}.get_score_multioutput(&constant, &off)
+        );
+    }
+}
@@ -21,6 +21,7 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::error::Failed;
 use crate::linalg::BaseVector;
 use crate::math::num::RealNumber;
 use crate::metrics::precision::Precision;
@@ -39,6 +40,20 @@ impl<T: RealNumber> F1<T> {
     /// * `y_true` - cround truth (correct) labels.
     /// * `y_pred` - predicted labels, as returned by a classifier.
     pub fn get_score<V: BaseVector<T>>(&self, y_true: &V, y_pred: &V) -> T {
+        self.get_score_weighted(y_true, y_pred, &vec![T::one(); y_true.len()])
+            .unwrap()
+    }
+
+    /// Computes weighted F1 score
+    /// * `y_true` - cround truth (correct) labels.
+    /// * `y_pred` - predicted labels, as returned by a classifier.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<V: BaseVector<T>>(
+        &self,
+        y_true: &V,
+        y_pred: &V,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
         if y_true.len() != y_pred.len() {
             panic!(
                 "The vector sizes don't match: {} != {}",
@@ -48,10 +63,10 @@ impl<T: RealNumber> F1<T> {
         }
         let beta2 = self.beta * self.beta;
 
-        let p = Precision {}.get_score(y_true, y_pred);
-        let r = Recall {}.get_score(y_true, y_pred);
+        let p = Precision {}.get_score_weighted(y_true, y_pred, sample_weight)?;
+        let r = Recall {}.get_score_weighted(y_true, y_pred, sample_weight)?;
 
-        (T::one() + beta2) * (p * r) / (beta2 * p + r)
+        Ok((T::one() + beta2) * (p * r) / (beta2 * p + r))
     }
 }
 
@@ -0,0 +1,221 @@
+//! # Log Loss
+//!
+//! Logarithmic loss, also known as cross-entropy loss, is the negative log-likelihood of true labels given probabilities predicted by a classifier.
+//! Unlike accuracy it penalizes confident wrong predictions much more than uncertain ones.
+//!
+//! \\[L = -\frac{1}{n}\sum_{i=1}^n\sum_{k=1}^K y_{ik}\log p_{ik}\\]
+//!
+//! where \\(y_{ik}\\) is 1 when sample \\(i\\) has label \\(k\\) and 0 otherwise and \\(p_{ik}\\) is the predicted probability of label \\(k\\).
+//! Probabilities are clipped to \\([\epsilon, 1 - \epsilon]\\) to keep the loss finite.
+//!
+//! Columns of the probability matrix follow sorted labels, as returned by [`PredictorProba::predict_proba`](../../api/trait.PredictorProba.html).
+//! A matrix with a single column holds probabilities of label 1 in binary classification.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::metrics::log_loss::LogLoss;
+//!
+//! let y_true: Vec<f64> = vec![0., 1., 2., 1.];
+//! let y_proba = DenseMatrix::from_2d_array(&[
+//!     &[0.8, 0.1, 0.1],
+//!     &[0.2, 0.7, 0.1],
+//!     &[0.1, 0.2, 0.7],
+//!     &[0.3, 0.4, 0.3],
+//! ]);
+//!
+//! let loss: f64 = LogLoss::default().get_score(&y_true, &y_proba);
+//! ```
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::metrics::check_sample_weight;
+
+/// Log loss
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct LogLoss<T: RealNumber> {
+    /// Probabilities are clipped to `[eps, 1 - eps]`.
+    pub eps: T,
+    /// Labels that correspond to columns of the probability matrix. When `None` sorted unique values of `y_true` are used,
+    /// set labels explicitly when some of them may be missing in `y_true`.
+    pub labels: Option<Vec<T>>,
+}
+
+impl<T: RealNumber> Default for LogLoss<T> {
+    fn default() -> Self {
+        LogLoss {
+            eps: T::from_f64(1e-15).unwrap(),
+            labels: None,
+        }
+    }
+}
+
+impl<T: RealNumber> LogLoss<T> {
+    /// Probabilities are clipped to `[eps, 1 - eps]`.
+    pub fn with_eps(mut self, eps: T) -> Self {
+        self.eps = eps;
+        self
+    }
+
+    /// Labels that correspond to columns of the probability matrix.
+    pub fn with_labels(mut self, labels: Vec<T>) -> Self {
+        self.labels = Some(labels);
+        self
+    }
+
+    /// Computes log loss.
+    /// * `y_true` - cround truth (correct) labels.
+    /// * `y_proba` - _NxK_ matrix of predicted probabilities, as returned by a classifier, or _Nx1_ matrix of probabilities of label 1.
+    pub fn get_score<M: Matrix<T>>(&self, y_true: &M::RowVector, y_proba: &M) -> T {
+        self.get_score_weighted(y_true, y_proba, &vec![T::one(); y_true.len()])
+            .unwrap()
+    }
+
+    /// Computes weighted log loss.
+    /// * `y_true` - cround truth (correct) labels.
+    /// * `y_proba` - _NxK_ matrix of predicted probabilities, as returned by a classifier, or _Nx1_ matrix of probabilities of label 1.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<M: Matrix<T>>(
+        &self,
+        y_true: &M::RowVector,
+        y_proba: &M,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
+        let (n, k) = y_proba.shape();
+        if y_true.len() != n {
+            panic!("The vector sizes don't match: {} != {}", y_true.len(), n);
+        }
+        let total_weight = check_sample_weight(n, sample_weight)?;
+
+        let labels = match &self.labels {
+            Some(labels) => {
+                let mut labels = labels.clone();
+                labels.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                labels
+            }
+            None => {
+                let mut labels = y_true.to_vec();
+                labels.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                labels.dedup();
+                labels
+            }
+        };
+
+        let binary = k == 1;
+        if binary && labels.iter().any(|&l| l != T::zero() && l != T::one()) {
+            panic!("Probabilities of a single class are only for labels 0 and 1");
+        }
+        if !binary && labels.len() != k {
+            panic!(
+                "Number of labels {} does not match number of columns {}",
+                labels.len(),
+                k
+            );
+        }
+
+        let mut loss = T::zero();
+        for (i, &w) in sample_weight.iter().enumerate() {
+            let label = y_true.get(i);
+            let p = if binary {
+                let p = y_proba.get(i, 0).max(self.eps).min(T::one() - self.eps);
+                if label == T::one() {
+                    p
+                } else {
+                    T::one() - p
+                }
+            } else {
+                let c = labels
+                    .binary_search_by(|l| l.partial_cmp(&label).unwrap())
+                    .unwrap_or_else(|_| panic!("Unknown label: {}", label));
+                let clip = |p: T| p.max(self.eps).min(T::one() - self.eps);
+                let total = (0..k).fold(T::zero(), |s, j| s + clip(y_proba.get(i, j)));
+                clip(y_proba.get(i, c)) / total
+            };
+            loss -= w * p.ln();
+        }
+
+        Ok(loss / total_weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn log_loss_multiclass() {
+        let y_true: Vec<f64> = vec![0., 1., 2., 1.];
+        let y_proba = DenseMatrix::from_2d_array(&[
+            &[0.8, 0.1, 0.1],
+            &[0.2, 0.7, 0.1],
+            &[0.1, 0.2, 0.7],
+            &[0.3, 0.4, 0.3],
+        ]);
+
+        let loss: f64 = LogLoss::default().get_score(&y_true, &y_proba);
+        let expected = -(0.8f64.ln() + 0.7f64.ln() + 0.7f64.ln() + 0.4f64.ln()) / 4.;
+
+        assert!((loss - expected).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn log_loss_binary_and_clipping() {
+        let y_true: Vec<f64> = vec![0., 1., 1., 0.];
+        let y_proba = DenseMatrix::from_2d_array(&[&[0.1], &[0.9], &[0.8], &[0.35]]);
+
+        let loss: f64 = LogLoss::default().get_score(&y_true, &y_proba);
+        let expected = -(0.9f64.ln() + 0.9f64.ln() + 0.8f64.ln() + 0.65f64.ln()) / 4.;
+        assert!((loss - expected).abs() < 1e-8);
+
+        let two_columns =
+            DenseMatrix::from_2d_array(&[&[0.9, 0.1], &[0.1, 0.9], &[0.2, 0.8], &[0.65, 0.35]]);
+        let loss2: f64 = LogLoss::default().get_score(&y_true, &two_columns);
+        assert!((loss - loss2).abs() < 1e-8);
+
+        let certain = DenseMatrix::from_2d_array(&[&[1.], &[1.], &[1.], &[0.]]);
+        let clipped: f64 = LogLoss::default()
+            .with_eps(1e-3)
+            .get_score(&y_true, &certain);
+        let expected = -(1e-3f64.ln() + 3. * 0.999f64.ln()) / 4.;
+        assert!((clipped - expected).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn log_loss_weighted() {
+        let y_true: Vec<f64> = vec![0., 1., 1.];
+        let y_proba = DenseMatrix::from_2d_array(&[&[0.2], &[0.6], &[0.01]]);
+
+        let loss: f64 = LogLoss::default()
+            .get_score_weighted(&y_true, &y_proba, &[1., 3., 0.])
+            .unwrap();
+        let expected = -(0.8f64.ln() + 3. * 0.6f64.ln()) / 4.;
+
+        assert!((loss - expected).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn log_loss_with_missing_label() {
+        let y_true: Vec<f64> = vec![0., 2.];
+        let y_proba = DenseMatrix::from_2d_array(&[&[0.5, 0.25, 0.25], &[0.25, 0.25, 0.5]]);
+
+        let loss: f64 = LogLoss::default()
+            .with_labels(vec![0., 1., 2.])
+            .get_score(&y_true, &y_proba);
+
+        assert!((loss - 2f64.ln()).abs() < 1e-8);
+    }
+}
@@ -0,0 +1,90 @@
+//! # Mean Absolute Percentage Error
+//!
+//! MAPE measures the average magnitude of the errors relative to true target values. It is scale independent,
+//! so errors of models trained on targets of different magnitude can be compared.
+//!
+//! \\[mape(y, \hat{y}) = \frac{1}{n_{samples}} \sum_{i=1}^{n_{samples}} \frac{\lvert y_i - \hat{y_i} \rvert}{max(\epsilon, \lvert y_i \rvert)} \\]
+//!
+//! where \\(\hat{y}\\) are predictions, \\(y\\) are true target values and \\(\epsilon\\) is the machine epsilon that prevents division by zero.
+//! The error is returned as a fraction, not in percents, and it is very large when some true values are close to zero.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::metrics::mean_absolute_percentage_error::MeanAbsolutePercentageError;
+//! let y_pred: Vec<f64> = vec![3., -0.5, 2., 7.];
+//! let y_true: Vec<f64> = vec![2.5, 0.5, 2., 8.];
+//!
+//! let mape: f64 = MeanAbsolutePercentageError {}.get_score(&y_true, &y_pred);
+//! ```
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Failed;
+use crate::linalg::BaseVector;
+use crate::math::num::RealNumber;
+use crate::metrics::check_sample_weight;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+/// Mean Absolute Percentage Error
+pub struct MeanAbsolutePercentageError {}
+
+impl MeanAbsolutePercentageError {
+    /// Computes mean absolute percentage error
+    /// * `y_true` - Ground truth (correct) target values.
+    /// * `y_pred` - Estimated target values.
+    pub fn get_score<T: RealNumber, V: BaseVector<T>>(&self, y_true: &V, y_pred: &V) -> T {
+        self.get_score_weighted(y_true, y_pred, &vec![T::one(); y_true.len()])
+            .unwrap()
+    }
+
+    /// Computes weighted mean absolute percentage error
+    /// * `y_true` - Ground truth (correct) target values.
+    /// * `y_pred` - Estimated target values.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<T: RealNumber, V: BaseVector<T>>(
+        &self,
+        y_true: &V,
+        y_pred: &V,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
+        if y_true.len() != y_pred.len() {
+            panic!(
+                "The vector sizes don't match: {} != {}",
+                y_true.len(),
+                y_pred.len()
+            );
+        }
+        let total_weight = check_sample_weight(y_true.len(), sample_weight)?;
+
+        let mut ras = T::zero();
+        for (i, &w) in sample_weight.iter().enumerate() {
+            let y_i = y_true.get(i);
+            ras += w * (y_i - y_pred.get(i)).abs() / y_i.abs().max(T::epsilon());
+        }
+
+        Ok(ras / total_weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn mean_absolute_percentage_error() {
+        let y_true: Vec<f64> = vec![2.5, 0.5, 2., 8.];
+        let y_pred: Vec<f64> = vec![3., -0.5, 2., 7.];
+
+        let score1: f64 = MeanAbsolutePercentageError {}.get_score(&y_true, &y_pred);
+        let score2: f64 = MeanAbsolutePercentageError {}.get_score(&y_true, &y_true);
+
+        assert!((score1 - (0.2 + 2. + 0. + 0.125) / 4.).abs() < 1e-8);
+        assert!((score2 - 0.0).abs() < 1e-8);
+    }
+}
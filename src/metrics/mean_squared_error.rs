@@ -21,8 +21,10 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::error::Failed;
 use crate::linalg::BaseVector;
 use crate::math::num::RealNumber;
+use crate::metrics::check_sample_weight;
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug)]
@@ -34,6 +36,20 @@ impl MeanSquareError {
     /// * `y_true` - Ground truth (correct) target values.
     /// * `y_pred` - Estimated target values.
     pub fn get_score<T: RealNumber, V: BaseVector<T>>(&self, y_true: &V, y_pred: &V) -> T {
+        self.get_score_weighted(y_true, y_pred, &vec![T::one(); y_true.len()])
+            .unwrap()
+    }
+
+    /// Computes weighted mean squared error
+    /// * `y_true` - Ground truth (correct) target values.
+    /// * `y_pred` - Estimated target values.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<T: RealNumber, V: BaseVector<T>>(
+        &self,
+        y_true: &V,
+        y_pred: &V,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
         if y_true.len() != y_pred.len() {
             panic!(
                 "The vector sizes don't match: {} != {}",
@@ -41,14 +57,14 @@ impl MeanSquareError {
                 y_pred.len()
             );
         }
+        let total_weight = check_sample_weight(y_true.len(), sample_weight)?;
 
-        let n = y_true.len();
         let mut rss = T::zero();
-        for i in 0..n {
-            rss += (y_true.get(i) - y_pred.get(i)).square();
+        for (i, &w) in sample_weight.iter().enumerate() {
+            rss += w * (y_true.get(i) - y_pred.get(i)).square();
         }
 
-        rss / T::from_usize(n).unwrap()
+        Ok(rss / total_weight)
     }
 }
 
@@ -68,4 +84,17 @@ mod tests {
         assert!((score1 - 0.375).abs() < 1e-8);
         assert!((score2 - 0.0).abs() < 1e-8);
     }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn mean_squared_error_weighted() {
+        let y_true: Vec<f64> = vec![3., -0.5, 2., 7.];
+        let y_pred: Vec<f64> = vec![2.5, 0.0, 2., 8.];
+
+        let score: f64 = MeanSquareError {}
+            .get_score_weighted(&y_true, &y_pred, &[2., 0., 1., 1.])
+            .unwrap();
+
+        assert!((score - 0.375).abs() < 1e-8);
+    }
 }
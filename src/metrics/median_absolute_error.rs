@@ -0,0 +1,144 @@
+//! # Median Absolute Error
+//!
+//! Median of absolute differences between true and predicted values. Unlike the mean absolute error it is robust to outliers,
+//! a few very large errors do not change the score.
+//!
+//! \\[medae(y, \hat{y}) = median(\lvert y_1 - \hat{y_1} \rvert, ..., \lvert y_n - \hat{y_n} \rvert) \\]
+//!
+//! where \\(\hat{y}\\) are predictions and \\(y\\) are true target values.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::metrics::median_absolute_error::MedianAbsoluteError;
+//! let y_pred: Vec<f64> = vec![3., -0.5, 2., 7.];
+//! let y_true: Vec<f64> = vec![2.5, 0.0, 2., 8.];
+//!
+//! let medae: f64 = MedianAbsoluteError {}.get_score(&y_true, &y_pred);
+//! ```
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Failed;
+use crate::linalg::BaseVector;
+use crate::math::num::RealNumber;
+use crate::metrics::check_sample_weight;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+/// Median Absolute Error
+pub struct MedianAbsoluteError {}
+
+impl MedianAbsoluteError {
+    /// Computes median absolute error
+    /// * `y_true` - Ground truth (correct) target values.
+    /// * `y_pred` - Estimated target values.
+    pub fn get_score<T: RealNumber, V: BaseVector<T>>(&self, y_true: &V, y_pred: &V) -> T {
+        if y_true.len() != y_pred.len() {
+            panic!(
+                "The vector sizes don't match: {} != {}",
+                y_true.len(),
+                y_pred.len()
+            );
+        }
+
+        let n = y_true.len();
+        let mut errors: Vec<T> = (0..n)
+            .map(|i| (y_true.get(i) - y_pred.get(i)).abs())
+            .collect();
+        errors.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        if n % 2 == 1 {
+            errors[n / 2]
+        } else {
+            (errors[n / 2 - 1] + errors[n / 2]) / T::two()
+        }
+    }
+
+    /// Computes weighted median absolute error, the error at which the cumulative weight of sorted errors reaches half of the total weight.
+    /// When it reaches exactly half the error is averaged with the next one, so that equal weights give the same result as `get_score`.
+    /// * `y_true` - Ground truth (correct) target values.
+    /// * `y_pred` - Estimated target values.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<T: RealNumber, V: BaseVector<T>>(
+        &self,
+        y_true: &V,
+        y_pred: &V,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
+        if y_true.len() != y_pred.len() {
+            panic!(
+                "The vector sizes don't match: {} != {}",
+                y_true.len(),
+                y_pred.len()
+            );
+        }
+        let half = check_sample_weight(y_true.len(), sample_weight)? / T::two();
+
+        let mut errors: Vec<(T, T)> = sample_weight
+            .iter()
+            .enumerate()
+            .filter(|(_, &w)| w > T::zero())
+            .map(|(i, &w)| ((y_true.get(i) - y_pred.get(i)).abs(), w))
+            .collect();
+        errors.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let mut cumulative = T::zero();
+        for (k, &(error, w)) in errors.iter().enumerate() {
+            cumulative += w;
+            if cumulative == half && k + 1 < errors.len() {
+                return Ok((error + errors[k + 1].0) / T::two());
+            }
+            if cumulative >= half {
+                return Ok(error);
+            }
+        }
+
+        Ok(T::nan())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn median_absolute_error() {
+        let y_true: Vec<f64> = vec![3., -0.5, 2., 7.];
+        let y_pred: Vec<f64> = vec![2.5, 0.0, 2., 8.];
+
+        let score1: f64 = MedianAbsoluteError {}.get_score(&y_true, &y_pred);
+        let score2: f64 = MedianAbsoluteError {}.get_score(&y_true, &y_true);
+        let score3: f64 = MedianAbsoluteError {}.get_score(&vec![1., 2., 3.], &vec![1., 2.5, 103.]);
+
+        assert!((score1 - 0.5).abs() < 1e-8);
+        assert!((score2 - 0.0).abs() < 1e-8);
+        assert!((score3 - 0.5).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn median_absolute_error_weighted() {
+        let y_true: Vec<f64> = vec![3., -0.5, 2., 7.];
+        let y_pred: Vec<f64> = vec![2.5, 0.0, 2., 8.];
+
+        let uniform: f64 = MedianAbsoluteError {}
+            .get_score_weighted(&y_true, &y_pred, &[1., 1., 1., 1.])
+            .unwrap();
+        let weighted: f64 = MedianAbsoluteError {}
+            .get_score_weighted(&y_true, &y_pred, &[1., 1., 0., 3.])
+            .unwrap();
+        let without_last: f64 = MedianAbsoluteError {}
+            .get_score_weighted(&y_true, &y_pred, &[1., 1., 1., 0.])
+            .unwrap();
+
+        assert!((uniform - 0.5).abs() < 1e-8);
+        assert!((weighted - 1.).abs() < 1e-8);
+        assert!((without_last - 0.5).abs() < 1e-8);
+    }
+}
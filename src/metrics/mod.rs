@@ -50,29 +50,79 @@
 //! // or
 //! let acc = accuracy(&y, &y_hat);
 //! ```
+//!
+//! When samples have different importance, e.g. an estimator was trained with sample weights, classification and regression metrics
+//! compute a weighted score with `get_score_weighted`. It fails when a weight is negative, when the weights sum to zero or when
+//! there is not exactly one weight for every sample. [Scorers](scorer/index.html) pass sample weights to these metrics during cross-validation.
+//!
+//! ```
+//! use smartcore::metrics::*;
+//!
+//! let y_true: Vec<f64> = vec![0., 1., 1., 0.];
+//! let y_pred: Vec<f64> = vec![0., 1., 0., 0.];
+//!
+//! let acc = ClassificationMetrics::accuracy().get_score_weighted(&y_true, &y_pred, &[1., 1., 2., 1.]).unwrap();
+//! assert!((acc - 0.6).abs() < 1e-8);
+//! ```
 
 /// Accuracy score.
 pub mod accuracy;
 /// Computes Area Under the Receiver Operating Characteristic Curve (ROC AUC) from prediction scores.
 pub mod auc;
+/// Brier score of predicted probabilities.
+pub mod brier_score;
+/// Per-class precision, recall and F1 score of a classifier.
+pub mod classification_report;
+/// Adjusted Rand index of a clustering.
+pub mod cluster_ari;
 /// Compute the homogeneity, completeness and V-Measure scores.
 pub mod cluster_hcv;
 pub(crate) mod cluster_helpers;
+/// Normalized mutual information of a clustering.
+pub mod cluster_nmi;
+/// Cohen's kappa, agreement between two sets of labels corrected for chance.
+pub mod cohen_kappa;
+/// Confusion matrix of a classifier.
+pub mod confusion_matrix;
+/// Explained variance regression score.
+pub mod explained_variance;
 /// F1 score, also known as balanced F-score or F-measure.
 pub mod f1;
+/// Logarithmic loss of predicted probabilities.
+pub mod log_loss;
 /// Mean absolute error regression loss.
 pub mod mean_absolute_error;
+/// Mean absolute percentage error regression loss.
+pub mod mean_absolute_percentage_error;
 /// Mean squared error regression loss.
 pub mod mean_squared_error;
+/// Median absolute error regression loss.
+pub mod median_absolute_error;
+/// Metrics of multilabel classification on binary indicator matrices.
+pub mod multilabel;
+/// Mean pinball loss of quantile regression.
+pub mod pinball_loss;
 /// Computes the precision.
 pub mod precision;
 /// Coefficient of determination (R2).
 pub mod r2;
 /// Computes the recall.
 pub mod recall;
+/// Bootstrap confidence intervals and permutation tests for metrics.
+pub mod resampling;
+/// Scorers that evaluate fitted estimators for model selection.
+pub mod scorer;
+/// Silhouette coefficient of a clustering.
+pub mod silhouette;
+/// Fraction of samples with the true label among the top-k predicted labels.
+pub mod top_k_accuracy;
 
-use crate::linalg::BaseVector;
+use crate::error::Failed;
+use crate::linalg::naive::dense_matrix::DenseMatrix;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::distance::Distance;
 use crate::math::num::RealNumber;
+use alloc::vec::Vec;
 
 /// Use these metrics to compare classification models.
 pub struct ClassificationMetrics {}
@@ -99,6 +149,26 @@ impl ClassificationMetrics {
         precision::Precision {}
     }
 
+    /// Cohen's kappa, see [Cohen's kappa](cohen_kappa/index.html).
+    pub fn cohen_kappa() -> cohen_kappa::CohenKappa {
+        cohen_kappa::CohenKappa::default()
+    }
+
+    /// Hamming loss of multilabel classification, see [multilabel metrics](multilabel/index.html).
+    pub fn hamming_loss() -> multilabel::HammingLoss {
+        multilabel::HammingLoss {}
+    }
+
+    /// Jaccard score of multilabel classification, see [multilabel metrics](multilabel/index.html).
+    pub fn jaccard_score() -> multilabel::JaccardScore {
+        multilabel::JaccardScore::default()
+    }
+
+    /// Confusion matrix, see [confusion matrix](confusion_matrix/index.html).
+    pub fn confusion_matrix() -> confusion_matrix::ConfusionMatrix {
+        confusion_matrix::ConfusionMatrix::default()
+    }
+
     /// F1 score, also known as balanced F-score or F-measure, see [F1](f1/index.html).
     pub fn f1<T: RealNumber>(beta: T) -> f1::F1<T> {
         f1::F1 { beta }
@@ -108,6 +178,22 @@ impl ClassificationMetrics {
     pub fn roc_auc_score() -> auc::AUC {
         auc::AUC {}
     }
+
+    /// Brier score, see [Brier score](brier_score/index.html).
+    pub fn brier_score<T: RealNumber>() -> brier_score::BrierScore<T> {
+        brier_score::BrierScore::default()
+    }
+
+    /// Top-k accuracy, see [top-k accuracy](top_k_accuracy/index.html).
+    /// * `k` - number of labels with the largest scores that are considered correct predictions.
+    pub fn top_k_accuracy<T: RealNumber>(k: usize) -> top_k_accuracy::TopKAccuracy<T> {
+        top_k_accuracy::TopKAccuracy::new(k)
+    }
+
+    /// Log loss, also known as cross-entropy loss, see [log loss](log_loss/index.html).
+    pub fn log_loss<T: RealNumber>() -> log_loss::LogLoss<T> {
+        log_loss::LogLoss::default()
+    }
 }
 
 impl RegressionMetrics {
@@ -121,17 +207,88 @@ impl RegressionMetrics {
         mean_absolute_error::MeanAbsoluteError {}
     }
 
+    /// Mean absolute percentage error, see [mean absolute percentage error](mean_absolute_percentage_error/index.html).
+    pub fn mean_absolute_percentage_error(
+    ) -> mean_absolute_percentage_error::MeanAbsolutePercentageError {
+        mean_absolute_percentage_error::MeanAbsolutePercentageError {}
+    }
+
+    /// Median absolute error, see [median absolute error](median_absolute_error/index.html).
+    pub fn median_absolute_error() -> median_absolute_error::MedianAbsoluteError {
+        median_absolute_error::MedianAbsoluteError {}
+    }
+
+    /// Mean pinball loss, see [pinball loss](pinball_loss/index.html).
+    /// * `alpha` - quantile, between 0 and 1.
+    pub fn mean_pinball_loss<T: RealNumber>(alpha: T) -> pinball_loss::PinballLoss<T> {
+        pinball_loss::PinballLoss { alpha }
+    }
+
     /// Coefficient of determination (R2), see [R2](r2/index.html).
     pub fn r2() -> r2::R2 {
         r2::R2 {}
     }
+
+    /// Explained variance score, see [explained variance](explained_variance/index.html).
+    pub fn explained_variance_score() -> explained_variance::ExplainedVariance {
+        explained_variance::ExplainedVariance {}
+    }
 }
 
 impl ClusterMetrics {
-    /// Homogeneity and completeness and V-Measure scores at once.
+    /// Homogeneity and completeness and V-Measure scores at once, see [HCV](cluster_hcv/index.html).
     pub fn hcv_score() -> cluster_hcv::HCVScore {
         cluster_hcv::HCVScore {}
     }
+
+    /// Adjusted Rand index, see [ARI](cluster_ari/index.html).
+    pub fn adjusted_rand_score() -> cluster_ari::AdjustedRandIndex {
+        cluster_ari::AdjustedRandIndex {}
+    }
+
+    /// Normalized mutual information, see [NMI](cluster_nmi/index.html).
+    pub fn normalized_mutual_info_score() -> cluster_nmi::NormalizedMutualInfo {
+        cluster_nmi::NormalizedMutualInfo {}
+    }
+
+    /// Silhouette coefficient, see [silhouette](silhouette/index.html).
+    /// * `distance` - distance between samples, see [`Distances`](../math/distance/struct.Distances.html).
+    pub fn silhouette<D>(distance: D) -> silhouette::Silhouette<D> {
+        silhouette::Silhouette {
+            distance,
+            sample_size: None,
+            seed: None,
+        }
+    }
+}
+
+/// Checks that there is a non-negative weight for every sample and that the weights do not sum to zero, returns total weight.
+pub(crate) fn check_sample_weight<T: RealNumber>(
+    n_samples: usize,
+    sample_weight: &[T],
+) -> Result<T, Failed> {
+    if sample_weight.len() != n_samples {
+        return Err(Failed::shape_mismatch(&format!(
+            "Number of sample weights {} does not match number of samples {}",
+            sample_weight.len(),
+            n_samples
+        )));
+    }
+    if sample_weight
+        .iter()
+        .any(|w| !w.is_finite() || *w < T::zero())
+    {
+        return Err(Failed::invalid_parameters(
+            "Sample weights should be non-negative and finite",
+        ));
+    }
+    let total_weight = sample_weight.iter().fold(T::zero(), |s, &w| s + w);
+    if total_weight == T::zero() && n_samples > 0 {
+        return Err(Failed::invalid_parameters(
+            "Sum of sample weights should be positive",
+        ));
+    }
+    Ok(total_weight)
 }
 
 /// Function that calculated accuracy score, see [accuracy](accuracy/index.html).
@@ -155,6 +312,83 @@ pub fn precision<T: RealNumber, V: BaseVector<T>>(y_true: &V, y_pred: &V) -> T {
     ClassificationMetrics::precision().get_score(y_true, y_pred)
 }
 
+/// Builds classification report with per-class precision, recall, F1 score and support, see [classification report](classification_report/index.html).
+/// * `y_true` - cround truth (correct) labels.
+/// * `y_pred` - predicted labels, as returned by a classifier.
+pub fn classification_report<T: RealNumber, V: BaseVector<T>>(
+    y_true: &V,
+    y_pred: &V,
+) -> classification_report::ClassificationReport<T> {
+    classification_report::ClassificationReport::new(y_true, y_pred)
+}
+
+/// Computes Cohen's kappa, see [Cohen's kappa](cohen_kappa/index.html).
+/// * `y_true` - cround truth (correct) labels.
+/// * `y_pred` - predicted labels, as returned by a classifier.
+pub fn cohen_kappa_score<T: RealNumber, V: BaseVector<T>>(y_true: &V, y_pred: &V) -> T {
+    ClassificationMetrics::cohen_kappa().get_score(y_true, y_pred)
+}
+
+/// Computes Cohen's kappa with linear or quadratic weighting of disagreements, see [Cohen's kappa](cohen_kappa/index.html).
+/// * `y_true` - cround truth (correct) labels.
+/// * `y_pred` - predicted labels, as returned by a classifier.
+/// * `weights` - weighting of disagreements between labels.
+pub fn weighted_cohen_kappa_score<T: RealNumber, V: BaseVector<T>>(
+    y_true: &V,
+    y_pred: &V,
+    weights: cohen_kappa::KappaWeights,
+) -> T {
+    ClassificationMetrics::cohen_kappa()
+        .with_weights(weights)
+        .get_score(y_true, y_pred)
+}
+
+/// Computes Hamming loss of multilabel classification, see [multilabel metrics](multilabel/index.html).
+/// * `y_true` - ground truth (correct) binary indicator matrix.
+/// * `y_pred` - predicted binary indicator matrix, as returned by a classifier.
+pub fn hamming_loss<T: RealNumber, M: Matrix<T>>(y_true: &M, y_pred: &M) -> T {
+    ClassificationMetrics::hamming_loss().get_score(y_true, y_pred)
+}
+
+/// Computes Jaccard score of multilabel classification, see [multilabel metrics](multilabel/index.html).
+/// * `y_true` - ground truth (correct) binary indicator matrix.
+/// * `y_pred` - predicted binary indicator matrix, as returned by a classifier.
+/// * `average` - whether scores are averaged over samples, labels or computed from total counts.
+pub fn jaccard_score<T: RealNumber, M: Matrix<T>>(
+    y_true: &M,
+    y_pred: &M,
+    average: multilabel::JaccardAverage,
+) -> T {
+    ClassificationMetrics::jaccard_score()
+        .with_average(average)
+        .get_score(y_true, y_pred)
+}
+
+/// Computes confusion matrix, see [confusion matrix](confusion_matrix/index.html).
+/// Returns sorted labels and a matrix where rows correspond to true labels and columns to predicted labels.
+/// * `y_true` - cround truth (correct) labels.
+/// * `y_pred` - predicted labels, as returned by a classifier.
+pub fn confusion_matrix<T: RealNumber, V: BaseVector<T>>(
+    y_true: &V,
+    y_pred: &V,
+) -> (Vec<T>, DenseMatrix<T>) {
+    ClassificationMetrics::confusion_matrix().get_score(y_true, y_pred)
+}
+
+/// Computes normalized confusion matrix, see [confusion matrix](confusion_matrix/index.html).
+/// * `y_true` - cround truth (correct) labels.
+/// * `y_pred` - predicted labels, as returned by a classifier.
+/// * `normalize` - whether counts are divided by row, column or total sums.
+pub fn confusion_matrix_normalized<T: RealNumber, V: BaseVector<T>>(
+    y_true: &V,
+    y_pred: &V,
+    normalize: confusion_matrix::Normalize,
+) -> (Vec<T>, DenseMatrix<T>) {
+    ClassificationMetrics::confusion_matrix()
+        .with_normalize(normalize)
+        .get_score(y_true, y_pred)
+}
+
 /// Computes F1 score, see [F1](f1/index.html).
 /// * `y_true` - cround truth (correct) labels.
 /// * `y_pred` - predicted labels, as returned by a classifier.
@@ -164,11 +398,44 @@ pub fn f1<T: RealNumber, V: BaseVector<T>>(y_true: &V, y_pred: &V, beta: T) -> T
 
 /// AUC score, see [AUC](auc/index.html).
 /// * `y_true` - cround truth (correct) labels.
-/// * `y_pred_probabilities` - probability estimates, as returned by a classifier.
+/// * `y_pred_probabilities` - probability estimates or decision scores of the positive class, as returned by a classifier.
 pub fn roc_auc_score<T: RealNumber, V: BaseVector<T>>(y_true: &V, y_pred_probabilities: &V) -> T {
     ClassificationMetrics::roc_auc_score().get_score(y_true, y_pred_probabilities)
 }
 
+/// Computes ROC curve, see [AUC](auc/index.html).
+/// * `y_true` - cround truth (correct) labels.
+/// * `y_score` - probability estimates or decision scores of the positive class, as returned by a classifier.
+pub fn roc_curve<T: RealNumber, V: BaseVector<T>>(y_true: &V, y_score: &V) -> auc::ROCCurve<T> {
+    ClassificationMetrics::roc_auc_score().roc_curve(y_true, y_score)
+}
+
+/// Computes Brier score, see [Brier score](brier_score/index.html).
+/// * `y_true` - cround truth (correct) labels.
+/// * `y_proba` - matrix of predicted probabilities, as returned by a classifier.
+pub fn brier_score<T: RealNumber, M: Matrix<T>>(y_true: &M::RowVector, y_proba: &M) -> T {
+    ClassificationMetrics::brier_score().get_score(y_true, y_proba)
+}
+
+/// Computes top-k accuracy, see [top-k accuracy](top_k_accuracy/index.html).
+/// * `y_true` - cround truth (correct) labels.
+/// * `y_score` - matrix of predicted probabilities or scores, as returned by a classifier.
+/// * `k` - number of labels with the largest scores that are considered correct predictions.
+pub fn top_k_accuracy_score<T: RealNumber, M: Matrix<T>>(
+    y_true: &M::RowVector,
+    y_score: &M,
+    k: usize,
+) -> T {
+    ClassificationMetrics::top_k_accuracy(k).get_score(y_true, y_score)
+}
+
+/// Computes log loss, see [log loss](log_loss/index.html).
+/// * `y_true` - cround truth (correct) labels.
+/// * `y_proba` - matrix of predicted probabilities, as returned by a classifier.
+pub fn log_loss<T: RealNumber, M: Matrix<T>>(y_true: &M::RowVector, y_proba: &M) -> T {
+    ClassificationMetrics::log_loss().get_score(y_true, y_proba)
+}
+
 /// Computes mean squared error, see [mean squared error](mean_squared_error/index.html).
 /// * `y_true` - Ground truth (correct) target values.
 /// * `y_pred` - Estimated target values.
@@ -183,6 +450,31 @@ pub fn mean_absolute_error<T: RealNumber, V: BaseVector<T>>(y_true: &V, y_pred:
     RegressionMetrics::mean_absolute_error().get_score(y_true, y_pred)
 }
 
+/// Computes mean absolute percentage error, see [mean absolute percentage error](mean_absolute_percentage_error/index.html).
+/// * `y_true` - Ground truth (correct) target values.
+/// * `y_pred` - Estimated target values.
+pub fn mean_absolute_percentage_error<T: RealNumber, V: BaseVector<T>>(
+    y_true: &V,
+    y_pred: &V,
+) -> T {
+    RegressionMetrics::mean_absolute_percentage_error().get_score(y_true, y_pred)
+}
+
+/// Computes median absolute error, see [median absolute error](median_absolute_error/index.html).
+/// * `y_true` - Ground truth (correct) target values.
+/// * `y_pred` - Estimated target values.
+pub fn median_absolute_error<T: RealNumber, V: BaseVector<T>>(y_true: &V, y_pred: &V) -> T {
+    RegressionMetrics::median_absolute_error().get_score(y_true, y_pred)
+}
+
+/// Computes mean pinball loss, see [pinball loss](pinball_loss/index.html).
+/// * `y_true` - Ground truth (correct) target values.
+/// * `y_pred` - Estimated target values.
+/// * `alpha` - quantile, between 0 and 1.
+pub fn mean_pinball_loss<T: RealNumber, V: BaseVector<T>>(y_true: &V, y_pred: &V, alpha: T) -> T {
+    RegressionMetrics::mean_pinball_loss(alpha).get_score(y_true, y_pred)
+}
+
 /// Computes R2 score, see [R2](r2/index.html).
 /// * `y_true` - Ground truth (correct) target values.
 /// * `y_pred` - Estimated target values.
@@ -190,6 +482,36 @@ pub fn r2<T: RealNumber, V: BaseVector<T>>(y_true: &V, y_pred: &V) -> T {
     RegressionMetrics::r2().get_score(y_true, y_pred)
 }
 
+/// Computes R2 score of a multi-output regression, averaged uniformly over outputs, see [R2](r2/index.html).
+/// Use [`RegressionMetrics::r2`](struct.RegressionMetrics.html#method.r2) to get a score of every output.
+/// * `y_true` - Ground truth (correct) target values, _NxK_ matrix where _K_ is the number of outputs.
+/// * `y_pred` - Estimated target values, _NxK_ matrix.
+pub fn r2_score<T: RealNumber, M: Matrix<T>>(y_true: &M, y_pred: &M) -> T {
+    RegressionMetrics::r2()
+        .get_score_multioutput(y_true, y_pred)
+        .mean()
+}
+
+/// Computes explained variance score, see [explained variance](explained_variance/index.html).
+/// * `y_true` - Ground truth (correct) target values.
+/// * `y_pred` - Estimated target values.
+pub fn explained_variance_score<T: RealNumber, V: BaseVector<T>>(y_true: &V, y_pred: &V) -> T {
+    RegressionMetrics::explained_variance_score().get_score(y_true, y_pred)
+}
+
+/// Computes explained variance score of a multi-output regression, averaged uniformly over outputs,
+/// see [explained variance](explained_variance/index.html).
+/// * `y_true` - Ground truth (correct) target values, _NxK_ matrix where _K_ is the number of outputs.
+/// * `y_pred` - Estimated target values, _NxK_ matrix.
+pub fn explained_variance_score_multioutput<T: RealNumber, M: Matrix<T>>(
+    y_true: &M,
+    y_pred: &M,
+) -> T {
+    RegressionMetrics::explained_variance_score()
+        .get_score_multioutput(y_true, y_pred)
+        .mean()
+}
+
 /// Homogeneity metric of a cluster labeling given a ground truth (range is between 0.0 and 1.0).
 /// A cluster result satisfies homogeneity if all of its clusters contain only data points which are members of a single class.
 /// * `labels_true` - ground truth class labels to be used as a reference.
@@ -210,6 +532,16 @@ pub fn completeness_score<T: RealNumber, V: BaseVector<T>>(labels_true: &V, labe
         .1
 }
 
+/// Homogeneity, completeness and V-Measure of a cluster labeling at once, see [HCV](cluster_hcv/index.html).
+/// * `labels_true` - ground truth class labels to be used as a reference.
+/// * `labels_pred` - cluster labels to evaluate.
+pub fn homogeneity_completeness_v_measure<T: RealNumber, V: BaseVector<T>>(
+    labels_true: &V,
+    labels_pred: &V,
+) -> (T, T, T) {
+    ClusterMetrics::hcv_score().get_score(labels_true, labels_pred)
+}
+
 /// The harmonic mean between homogeneity and completeness.
 /// * `labels_true` - ground truth class labels to be used as a reference.
 /// * `labels_pred` - cluster labels to evaluate.
@@ -218,3 +550,33 @@ pub fn v_measure_score<T: RealNumber, V: BaseVector<T>>(labels_true: &V, labels_
         .get_score(labels_true, labels_pred)
         .2
 }
+
+/// Adjusted Rand index of a cluster labeling given a ground truth (range is between -1.0 and 1.0), see [ARI](cluster_ari/index.html).
+/// * `labels_true` - ground truth class labels to be used as a reference.
+/// * `labels_pred` - cluster labels to evaluate.
+pub fn adjusted_rand_score<T: RealNumber, V: BaseVector<T>>(labels_true: &V, labels_pred: &V) -> T {
+    ClusterMetrics::adjusted_rand_score().get_score(labels_true, labels_pred)
+}
+
+/// Normalized mutual information of a cluster labeling given a ground truth (range is between 0.0 and 1.0), see [NMI](cluster_nmi/index.html).
+/// * `labels_true` - ground truth class labels to be used as a reference.
+/// * `labels_pred` - cluster labels to evaluate.
+pub fn normalized_mutual_info_score<T: RealNumber, V: BaseVector<T>>(
+    labels_true: &V,
+    labels_pred: &V,
+) -> T {
+    ClusterMetrics::normalized_mutual_info_score().get_score(labels_true, labels_pred)
+}
+
+/// Mean silhouette coefficient of all samples, see [silhouette](silhouette/index.html).
+/// Use [`ClusterMetrics::silhouette`](struct.ClusterMetrics.html#method.silhouette) to compute the score on a random subset of a large dataset.
+/// * `x` - _NxM_ matrix with _N_ samples and _M_ features in each sample.
+/// * `labels` - cluster label of every sample.
+/// * `distance` - distance between samples, see [`Distances`](../math/distance/struct.Distances.html).
+pub fn silhouette_score<T: RealNumber, M: Matrix<T>, D: Distance<Vec<T>, T>>(
+    x: &M,
+    labels: &M::RowVector,
+    distance: D,
+) -> T {
+    ClusterMetrics::silhouette(distance).get_score(x, labels)
+}
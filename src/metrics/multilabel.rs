@@ -0,0 +1,258 @@
+//! # Multilabel Metrics
+//!
+//! In multilabel classification every sample can have several labels at once. True and predicted labels are given as binary
+//! indicator matrices of size _NxL_, where element _(i, j)_ is 1 when sample _i_ has label _j_ and 0 otherwise.
+//!
+//! * [Hamming loss](struct.HammingLoss.html) is the fraction of wrongly predicted elements of the indicator matrix.
+//! * [Jaccard score](struct.JaccardScore.html) is the size of the intersection divided by the size of the union of true and predicted label sets,
+//!   averaged over samples, labels or computed from total counts, see [`JaccardAverage`](enum.JaccardAverage.html).
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::metrics::multilabel::{HammingLoss, JaccardAverage, JaccardScore};
+//!
+//! let y_true = DenseMatrix::from_2d_array(&[&[0., 1., 1.], &[1., 1., 0.]]);
+//! let y_pred = DenseMatrix::from_2d_array(&[&[1., 1., 1.], &[1., 0., 0.]]);
+//!
+//! let loss: f64 = HammingLoss {}.get_score(&y_true, &y_pred);
+//! let jaccard: f64 = JaccardScore::default()
+//!     .with_average(JaccardAverage::Samples)
+//!     .get_score(&y_true, &y_pred);
+//! ```
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Failed;
+use crate::linalg::BaseMatrix;
+use crate::math::num::RealNumber;
+use crate::metrics::check_sample_weight;
+
+/// Hamming loss
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct HammingLoss {}
+
+impl HammingLoss {
+    /// Computes Hamming loss, fraction of labels that are wrongly predicted.
+    /// * `y_true` - ground truth (correct) binary indicator matrix.
+    /// * `y_pred` - predicted binary indicator matrix, as returned by a classifier.
+    pub fn get_score<T: RealNumber, M: BaseMatrix<T>>(&self, y_true: &M, y_pred: &M) -> T {
+        self.get_score_weighted(y_true, y_pred, &vec![T::one(); y_true.shape().0])
+            .unwrap()
+    }
+
+    /// Computes weighted Hamming loss, every wrongly predicted label of a sample counts with the weight of the sample.
+    /// * `y_true` - ground truth (correct) binary indicator matrix.
+    /// * `y_pred` - predicted binary indicator matrix, as returned by a classifier.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<T: RealNumber, M: BaseMatrix<T>>(
+        &self,
+        y_true: &M,
+        y_pred: &M,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
+        check_shapes(y_true, y_pred);
+
+        let (n, l) = y_true.shape();
+        let total_weight = check_sample_weight(n, sample_weight)?;
+        let mut errors = T::zero();
+        for (i, &w) in sample_weight.iter().enumerate() {
+            for j in 0..l {
+                if is_positive(y_true.get(i, j)) != is_positive(y_pred.get(i, j)) {
+                    errors += w;
+                }
+            }
+        }
+
+        Ok(errors / (total_weight * T::from_usize(l).unwrap()))
+    }
+}
+
+/// Averaging of the Jaccard score
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JaccardAverage {
+    /// Score of every sample, averaged over samples.
+    Samples,
+    /// Score of every label, averaged over labels.
+    Macro,
+    /// Score of total counts of true positives, false positives and false negatives.
+    Micro,
+}
+
+/// Jaccard similarity coefficient score
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct JaccardScore {
+    /// Averaging of the score.
+    pub average: JaccardAverage,
+}
+
+impl Default for JaccardScore {
+    fn default() -> Self {
+        JaccardScore {
+            average: JaccardAverage::Samples,
+        }
+    }
+}
+
+impl JaccardScore {
+    /// Averaging of the score.
+    pub fn with_average(mut self, average: JaccardAverage) -> Self {
+        self.average = average;
+        self
+    }
+
+    /// Computes Jaccard score. Empty sets, where neither true nor predicted labels are present, score 0.
+    /// * `y_true` - ground truth (correct) binary indicator matrix.
+    /// * `y_pred` - predicted binary indicator matrix, as returned by a classifier.
+    pub fn get_score<T: RealNumber, M: BaseMatrix<T>>(&self, y_true: &M, y_pred: &M) -> T {
+        self.get_score_weighted(y_true, y_pred, &vec![T::one(); y_true.shape().0])
+            .unwrap()
+    }
+
+    /// Computes weighted Jaccard score. Samples contribute to intersections and unions with their weight,
+    /// with `JaccardAverage::Samples` the scores of samples are averaged with these weights.
+    /// * `y_true` - ground truth (correct) binary indicator matrix.
+    /// * `y_pred` - predicted binary indicator matrix, as returned by a classifier.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<T: RealNumber, M: BaseMatrix<T>>(
+        &self,
+        y_true: &M,
+        y_pred: &M,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
+        check_shapes(y_true, y_pred);
+
+        let (n, l) = y_true.shape();
+        let total_weight = check_sample_weight(n, sample_weight)?;
+        let count = |i: usize, j: usize| {
+            let (t, p) = (is_positive(y_true.get(i, j)), is_positive(y_pred.get(i, j)));
+            let w = sample_weight[i];
+            (
+                if t && p { w } else { T::zero() },
+                if t || p { w } else { T::zero() },
+            )
+        };
+        let ratio = |(intersection, union): (T, T)| {
+            if union == T::zero() {
+                T::zero()
+            } else {
+                intersection / union
+            }
+        };
+        let add = |a: (T, T), b: (T, T)| (a.0 + b.0, a.1 + b.1);
+        let zero = (T::zero(), T::zero());
+
+        Ok(match self.average {
+            JaccardAverage::Samples => {
+                (0..n)
+                    .map(|i| sample_weight[i] * ratio((0..l).map(|j| count(i, j)).fold(zero, add)))
+                    .fold(T::zero(), |s, v| s + v)
+                    / total_weight
+            }
+            JaccardAverage::Macro => {
+                (0..l)
+                    .map(|j| ratio((0..n).map(|i| count(i, j)).fold(zero, add)))
+                    .fold(T::zero(), |s, v| s + v)
+                    / T::from_usize(l).unwrap()
+            }
+            JaccardAverage::Micro => ratio(
+                (0..n)
+                    .flat_map(|i| (0..l).map(move |j| (i, j)))
+                    .map(|(i, j)| count(i, j))
+                    .fold(zero, add),
+            ),
+        })
+    }
+}
+
+fn is_positive<T: RealNumber>(value: T) -> bool {
+    value != T::zero()
+}
+
+fn check_shapes<T: RealNumber, M: BaseMatrix<T>>(y_true: &M, y_pred: &M) {
+    if y_true.shape() != y_pred.shape() {
+        panic!(
+            "The matrix shapes don't match: {:?} != {:?}",
+            y_true.shape(),
+            y_pred.shape()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn hamming_loss() {
+        let y_true = DenseMatrix::from_2d_array(&[&[0., 1., 1.], &[1., 1., 0.]]);
+        let y_pred = DenseMatrix::from_2d_array(&[&[1., 1., 1.], &[1., 0., 0.]]);
+
+        let score1: f64 = HammingLoss {}.get_score(&y_true, &y_pred);
+        let score2: f64 = HammingLoss {}.get_score(&y_true, &y_true);
+
+        assert!((score1 - 1. / 3.).abs() < 1e-8);
+        assert!((score2 - 0.0).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn jaccard_score() {
+        let y_true = DenseMatrix::from_2d_array(&[&[0., 1., 1.], &[1., 1., 0.]]);
+        let y_pred = DenseMatrix::from_2d_array(&[&[1., 1., 1.], &[1., 0., 0.]]);
+
+        let samples: f64 = JaccardScore::default().get_score(&y_true, &y_pred);
+        let macro_avg: f64 = JaccardScore::default()
+            .with_average(JaccardAverage::Macro)
+            .get_score(&y_true, &y_pred);
+        let micro_avg: f64 = JaccardScore::default()
+            .with_average(JaccardAverage::Micro)
+            .get_score(&y_true, &y_pred);
+
+        assert!((samples - 7. / 12.).abs() < 1e-8);
+        assert!((macro_avg - 2. / 3.).abs() < 1e-8);
+        assert!((micro_avg - 0.6).abs() < 1e-8);
+
+        let perfect: f64 = JaccardScore::default().get_score(&y_true, &y_true);
+        assert!((perfect - 1.0).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn weighted_multilabel_scores() {
+        let y_true = DenseMatrix::from_2d_array(&[&[0., 1., 1.], &[1., 1., 0.]]);
+        let y_pred = DenseMatrix::from_2d_array(&[&[1., 1., 1.], &[1., 0., 0.]]);
+        let y_true_repeated =
+            DenseMatrix::from_2d_array(&[&[0., 1., 1.], &[1., 1., 0.], &[1., 1., 0.]]);
+        let y_pred_repeated =
+            DenseMatrix::from_2d_array(&[&[1., 1., 1.], &[1., 0., 0.], &[1., 0., 0.]]);
+
+        let loss: f64 = HammingLoss {}
+            .get_score_weighted(&y_true, &y_pred, &[1., 2.])
+            .unwrap();
+        assert!((loss - HammingLoss {}.get_score(&y_true_repeated, &y_pred_repeated)).abs() < 1e-8);
+
+        for average in &[
+            JaccardAverage::Samples,
+            JaccardAverage::Macro,
+            JaccardAverage::Micro,
+        ] {
+            let jaccard = JaccardScore::default().with_average(*average);
+            let weighted: f64 = jaccard
+                .get_score_weighted(&y_true, &y_pred, &[1., 2.])
+                .unwrap();
+            let repeated: f64 = jaccard.get_score(&y_true_repeated, &y_pred_repeated);
+            assert!((weighted - repeated).abs() < 1e-8);
+        }
+
+        assert!(HammingLoss {}
+            .get_score_weighted(&y_true, &y_pred, &[1.])
+            .is_err());
+    }
+}
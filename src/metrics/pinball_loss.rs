@@ -0,0 +1,125 @@
+//! # Mean Pinball Loss
+//!
+//! Pinball loss, also known as quantile loss, evaluates predictions of a quantile of the target distribution.
+//! Underestimates are weighted by \\(\alpha\\) and overestimates by \\(1 - \alpha\\), so the loss is minimized by the \\(\alpha\\)-quantile.
+//! With \\(\alpha = 0.5\\) it is half of the [mean absolute error](../mean_absolute_error/index.html).
+//!
+//! \\[L_\alpha(y, \hat{y}) = \frac{1}{n}\sum_{i=1}^n \max\left(\alpha (y_i - \hat{y_i}), (\alpha - 1)(y_i - \hat{y_i})\right)\\]
+//!
+//! where \\(\hat{y}\\) are predictions, \\(y\\) are true target values and \\(\alpha\\) is the quantile.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::metrics::pinball_loss::PinballLoss;
+//! let y_pred: Vec<f64> = vec![3., -0.5, 2., 7.];
+//! let y_true: Vec<f64> = vec![2.5, 0.0, 2., 8.];
+//!
+//! let loss: f64 = PinballLoss { alpha: 0.9 }.get_score(&y_true, &y_pred);
+//! ```
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Failed;
+use crate::linalg::BaseVector;
+use crate::math::num::RealNumber;
+use crate::metrics::check_sample_weight;
+
+/// Mean Pinball Loss
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct PinballLoss<T: RealNumber> {
+    /// Quantile, between 0 and 1.
+    pub alpha: T,
+}
+
+impl<T: RealNumber> PinballLoss<T> {
+    /// Computes mean pinball loss
+    /// * `y_true` - Ground truth (correct) target values.
+    /// * `y_pred` - Estimated target values.
+    pub fn get_score<V: BaseVector<T>>(&self, y_true: &V, y_pred: &V) -> T {
+        self.get_score_weighted(y_true, y_pred, &vec![T::one(); y_true.len()])
+            .unwrap()
+    }
+
+    /// Computes weighted mean pinball loss
+    /// * `y_true` - Ground truth (correct) target values.
+    /// * `y_pred` - Estimated target values.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<V: BaseVector<T>>(
+        &self,
+        y_true: &V,
+        y_pred: &V,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
+        if y_true.len() != y_pred.len() {
+            panic!(
+                "The vector sizes don't match: {} != {}",
+                y_true.len(),
+                y_pred.len()
+            );
+        }
+        if self.alpha < T::zero() || self.alpha > T::one() {
+            panic!("alpha should be between 0 and 1, got {}", self.alpha);
+        }
+
+        let total_weight = check_sample_weight(y_true.len(), sample_weight)?;
+
+        let mut loss = T::zero();
+        for (i, &w) in sample_weight.iter().enumerate() {
+            let diff = y_true.get(i) - y_pred.get(i);
+            loss += w * (self.alpha * diff).max((self.alpha - T::one()) * diff);
+        }
+
+        Ok(loss / total_weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::mean_absolute_error::MeanAbsoluteError;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn pinball_loss() {
+        let y_true: Vec<f64> = vec![1., 2., 3.];
+        let y_pred: Vec<f64> = vec![0., 2., 3.];
+
+        let under: f64 = PinballLoss { alpha: 0.1 }.get_score(&y_true, &y_pred);
+        let over: f64 = PinballLoss { alpha: 0.1 }.get_score(&y_pred, &y_true);
+
+        assert!((under - 0.1 / 3.).abs() < 1e-8);
+        assert!((over - 0.9 / 3.).abs() < 1e-8);
+
+        let y_true: Vec<f64> = vec![3., -0.5, 2., 7.];
+        let y_pred: Vec<f64> = vec![2.5, 0.0, 2., 8.];
+        let median: f64 = PinballLoss { alpha: 0.5 }.get_score(&y_true, &y_pred);
+        let mae: f64 = MeanAbsoluteError {}.get_score(&y_true, &y_pred);
+
+        assert!((median - mae / 2.).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn pinball_loss_weighted() {
+        let y_true: Vec<f64> = vec![1., 2., 3.];
+        let y_pred: Vec<f64> = vec![0., 2., 4.];
+
+        let uniform: f64 = PinballLoss { alpha: 0.1 }
+            .get_score_weighted(&y_true, &y_pred, &[1., 1., 1.])
+            .unwrap();
+        let weighted: f64 = PinballLoss { alpha: 0.1 }
+            .get_score_weighted(&y_true, &y_pred, &[3., 1., 0.])
+            .unwrap();
+
+        assert!((uniform - PinballLoss { alpha: 0.1 }.get_score(&y_true, &y_pred)).abs() < 1e-8);
+        assert!((weighted - 0.3 / 4.).abs() < 1e-8);
+        assert!(PinballLoss { alpha: 0.1 }
+            .get_score_weighted(&y_true, &y_pred, &[0., 0., 0.])
+            .is_err());
+    }
+}
@@ -18,13 +18,15 @@
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
-use std::collections::HashSet;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::collections::HashSet;
+use crate::error::Failed;
 use crate::linalg::BaseVector;
 use crate::math::num::RealNumber;
+use crate::metrics::check_sample_weight;
 
 /// Precision metric.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -36,6 +38,20 @@ impl Precision {
     /// * `y_true` - cround truth (correct) labels.
     /// * `y_pred` - predicted labels, as returned by a classifier.
     pub fn get_score<T: RealNumber, V: BaseVector<T>>(&self, y_true: &V, y_pred: &V) -> T {
+        self.get_score_weighted(y_true, y_pred, &vec![T::one(); y_true.len()])
+            .unwrap()
+    }
+
+    /// Calculated weighted precision score
+    /// * `y_true` - cround truth (correct) labels.
+    /// * `y_pred` - predicted labels, as returned by a classifier.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<T: RealNumber, V: BaseVector<T>>(
+        &self,
+        y_true: &V,
+        y_pred: &V,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
         if y_true.len() != y_pred.len() {
             panic!(
                 "The vector sizes don't match: {} != {}",
@@ -43,6 +59,7 @@ impl Precision {
                 y_pred.len()
             );
         }
+        check_sample_weight(y_true.len(), sample_weight)?;
 
         let mut classes = HashSet::new();
         for i in 0..y_true.len() {
@@ -50,27 +67,27 @@ impl Precision {
         }
         let classes = classes.len();
 
-        let mut tp = 0;
-        let mut fp = 0;
-        for i in 0..y_true.len() {
+        let mut tp = T::zero();
+        let mut fp = T::zero();
+        for (i, &w) in sample_weight.iter().enumerate() {
             if y_pred.get(i) == y_true.get(i) {
                 if classes == 2 {
                     if y_true.get(i) == T::one() {
-                        tp += 1;
+                        tp += w;
                     }
                 } else {
-                    tp += 1;
+                    tp += w;
                 }
             } else if classes == 2 {
                 if y_true.get(i) == T::one() {
-                    fp += 1;
+                    fp += w;
                 }
             } else {
-                fp += 1;
+                fp += w;
             }
         }
 
-        T::from_i64(tp).unwrap() / (T::from_i64(tp).unwrap() + T::from_i64(fp).unwrap())
+        Ok(tp / (tp + fp))
     }
 }
 
@@ -18,11 +18,14 @@
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::linalg::BaseVector;
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
 use crate::math::num::RealNumber;
+use crate::metrics::check_sample_weight;
 
 /// Coefficient of Determination (R2)
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -34,6 +37,20 @@ impl R2 {
     /// * `y_true` - Ground truth (correct) target values.
     /// * `y_pred` - Estimated target values.
     pub fn get_score<T: RealNumber, V: BaseVector<T>>(&self, y_true: &V, y_pred: &V) -> T {
+        self.get_score_weighted(y_true, y_pred, &vec![T::one(); y_true.len()])
+            .unwrap()
+    }
+
+    /// Computes weighted R2 score
+    /// * `y_true` - Ground truth (correct) target values.
+    /// * `y_pred` - Estimated target values.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<T: RealNumber, V: BaseVector<T>>(
+        &self,
+        y_true: &V,
+        y_pred: &V,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
         if y_true.len() != y_pred.len() {
             panic!(
                 "The vector sizes don't match: {} != {}",
@@ -41,34 +58,55 @@ impl R2 {
                 y_pred.len()
             );
         }
-
-        let n = y_true.len();
+        let total_weight = check_sample_weight(y_true.len(), sample_weight)?;
 
         let mut mean = T::zero();
 
-        for i in 0..n {
-            mean += y_true.get(i);
+        for (i, &w) in sample_weight.iter().enumerate() {
+            mean += w * y_true.get(i);
         }
 
-        mean /= T::from_usize(n).unwrap();
+        mean /= total_weight;
 
         let mut ss_tot = T::zero();
         let mut ss_res = T::zero();
 
-        for i in 0..n {
+        for (i, &w) in sample_weight.iter().enumerate() {
             let y_i = y_true.get(i);
             let f_i = y_pred.get(i);
-            ss_tot += (y_i - mean).square();
-            ss_res += (y_i - f_i).square();
+            ss_tot += w * (y_i - mean).square();
+            ss_res += w * (y_i - f_i).square();
+        }
+
+        Ok(T::one() - (ss_res / ss_tot))
+    }
+
+    /// Computes R2 score of every output.
+    /// * `y_true` - Ground truth (correct) target values, _NxK_ matrix where _K_ is the number of outputs.
+    /// * `y_pred` - Estimated target values, _NxK_ matrix.
+    pub fn get_score_multioutput<T: RealNumber, M: Matrix<T>>(
+        &self,
+        y_true: &M,
+        y_pred: &M,
+    ) -> Vec<T> {
+        if y_true.shape() != y_pred.shape() {
+            panic!(
+                "The matrix shapes don't match: {:?} != {:?}",
+                y_true.shape(),
+                y_pred.shape()
+            );
         }
 
-        T::one() - (ss_res / ss_tot)
+        (0..y_true.shape().1)
+            .map(|j| self.get_score(&y_true.get_col_as_vec(j), &y_pred.get_col_as_vec(j)))
+            .collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
 
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     #[test]
@@ -82,4 +120,32 @@ mod tests {
         assert!((score1 - 0.948608137).abs() < 1e-8);
         assert!((score2 - 1.0).abs() < 1e-8);
     }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn r2_weighted() {
+        let y_true: Vec<f64> = vec![3., -0.5, 2., 7., 7.];
+        let y_pred: Vec<f64> = vec![2.5, 0.0, 2., 8., 8.];
+
+        let score1: f64 = R2 {}
+            .get_score_weighted(&y_true, &y_pred, &[1., 1., 1., 1., 0.])
+            .unwrap();
+        let score2: f64 = R2 {}.get_score(&y_true, &y_pred);
+
+        assert!((score1 - 0.948608137).abs() < 1e-8);
+        assert!((score1 - score2).abs() > 1e-4);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn r2_multioutput() {
+        let y_true = DenseMatrix::from_2d_array(&[&[0.5, 1.], &[-1., 1.], &[7., -6.]]);
+        let y_pred = DenseMatrix::from_2d_array(&[&[0., 2.], &[-1., 2.], &[8., -5.]]);
+
+        let scores: Vec<f64> = R2 {}.get_score_multioutput(&y_true, &y_pred);
+
+        assert_eq!(2, scores.len());
+        assert!((scores[0] - (1. - 1.25 / (50.25 - 6.5 * 6.5 / 3.))).abs() < 1e-8);
+        assert!((scores[1] - (1. - 3. / (294. / 9.))).abs() < 1e-8);
+    }
 }
@@ -18,14 +18,16 @@
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
-use std::collections::HashSet;
-use std::convert::TryInto;
+use core::convert::TryInto;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::collections::HashSet;
+use crate::error::Failed;
 use crate::linalg::BaseVector;
 use crate::math::num::RealNumber;
+use crate::metrics::check_sample_weight;
 
 /// Recall metric.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -37,6 +39,20 @@ impl Recall {
     /// * `y_true` - cround truth (correct) labels.
     /// * `y_pred` - predicted labels, as returned by a classifier.
     pub fn get_score<T: RealNumber, V: BaseVector<T>>(&self, y_true: &V, y_pred: &V) -> T {
+        self.get_score_weighted(y_true, y_pred, &vec![T::one(); y_true.len()])
+            .unwrap()
+    }
+
+    /// Calculated weighted recall score
+    /// * `y_true` - cround truth (correct) labels.
+    /// * `y_pred` - predicted labels, as returned by a classifier.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<T: RealNumber, V: BaseVector<T>>(
+        &self,
+        y_true: &V,
+        y_pred: &V,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
         if y_true.len() != y_pred.len() {
             panic!(
                 "The vector sizes don't match: {} != {}",
@@ -44,6 +60,7 @@ impl Recall {
                 y_pred.len()
             );
         }
+        check_sample_weight(y_true.len(), sample_weight)?;
 
         let mut classes = HashSet::new();
         for i in 0..y_true.len() {
@@ -51,26 +68,26 @@ impl Recall {
         }
         let classes: i64 = classes.len().try_into().unwrap();
 
-        let mut tp = 0;
-        let mut fne = 0;
-        for i in 0..y_true.len() {
+        let mut tp = T::zero();
+        let mut fne = T::zero();
+        for (i, &w) in sample_weight.iter().enumerate() {
             if y_pred.get(i) == y_true.get(i) {
                 if classes == 2 {
                     if y_true.get(i) == T::one() {
-                        tp += 1;
+                        tp += w;
                     }
                 } else {
-                    tp += 1;
+                    tp += w;
                 }
             } else if classes == 2 {
                 if y_true.get(i) != T::one() {
-                    fne += 1;
+                    fne += w;
                 }
             } else {
-                fne += 1;
+                fne += w;
             }
         }
-        T::from_i64(tp).unwrap() / (T::from_i64(tp).unwrap() + T::from_i64(fne).unwrap())
+        Ok(tp / (tp + fne))
     }
 }
 
@@ -0,0 +1,331 @@
+//! # Resampling Statistics
+//!
+//! A metric computed on a single test set is only an estimate of how well a model performs. Resampling shows how much this estimate can be trusted.
+//!
+//! * [Bootstrap](struct.Bootstrap.html) draws samples with replacement from the test set, computes the metric on every resample
+//!   and reports the percentile confidence interval of the metric.
+//! * [PermutationTest](struct.PermutationTest.html) compares predictions of two models on the same test set. Under the null hypothesis that both models
+//!   perform equally well, predictions of the two models for any sample are interchangeable. The test swaps them at random
+//!   and reports how often the difference between the models is at least as large as the observed one.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::metrics::accuracy;
+//! use smartcore::metrics::resampling::{Bootstrap, PermutationTest};
+//!
+//! let y_true: Vec<f64> = vec![0., 1., 1., 0., 1., 0., 1., 1., 0., 0.];
+//! let y_pred_a: Vec<f64> = vec![0., 1., 1., 0., 1., 0., 1., 0., 0., 0.];
+//! let y_pred_b: Vec<f64> = vec![1., 1., 0., 0., 1., 1., 1., 0., 0., 1.];
+//!
+//! let interval = Bootstrap::default()
+//!     .with_seed(Some(42))
+//!     .confidence_interval(&y_true, &y_pred_a, accuracy);
+//! println!("accuracy: {} [{}, {}]", interval.score, interval.lower, interval.upper);
+//!
+//! let test = PermutationTest::default()
+//!     .with_seed(Some(42))
+//!     .compare(&y_true, &y_pred_a, &y_pred_b, accuracy);
+//! println!("difference: {}, p-value: {}", test.score_difference, test.p_value);
+//! ```
+//!
+//! ## References:
+//!
+//! * ["An Introduction to the Bootstrap", Efron B., Tibshirani R. J., 1993](https://doi.org/10.1201/9780429246593)
+//! * ["Approximate Statistical Tests for Comparing Supervised Classification Learning Algorithms", Dietterich T. G., 1998](https://doi.org/10.1162/089976698300017197)
+use alloc::vec::Vec;
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::linalg::BaseVector;
+use crate::math::num::RealNumber;
+use crate::rand::get_rng_impl;
+
+/// Bootstrap confidence intervals of a metric
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Bootstrap {
+    /// Number of resamples drawn from the test set.
+    pub n_resamples: usize,
+    /// Confidence level of the interval, in (0, 1).
+    pub confidence_level: f64,
+    /// Seed used to draw resamples.
+    pub seed: Option<u64>,
+}
+
+/// Score of a metric with its confidence interval
+#[derive(Debug, Clone)]
+pub struct ConfidenceInterval<T: RealNumber> {
+    /// Metric computed on the whole test set.
+    pub score: T,
+    /// Lower bound of the interval.
+    pub lower: T,
+    /// Upper bound of the interval.
+    pub upper: T,
+}
+
+impl Default for Bootstrap {
+    fn default() -> Self {
+        Bootstrap {
+            n_resamples: 1000,
+            confidence_level: 0.95,
+            seed: None,
+        }
+    }
+}
+
+impl Bootstrap {
+    /// Number of resamples drawn from the test set.
+    pub fn with_n_resamples(mut self, n_resamples: usize) -> Self {
+        self.n_resamples = n_resamples;
+        self
+    }
+    /// Confidence level of the interval, in (0, 1).
+    pub fn with_confidence_level(mut self, confidence_level: f64) -> Self {
+        self.confidence_level = confidence_level;
+        self
+    }
+    /// Seed used to draw resamples.
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Computes the metric and its percentile confidence interval.
+    /// * `y_true` - ground truth (correct) target values.
+    /// * `y_pred` - estimated target values.
+    /// * `metric` - a metric to compute, e.g. [accuracy](../fn.accuracy.html)
+    pub fn confidence_interval<T, V, F>(
+        &self,
+        y_true: &V,
+        y_pred: &V,
+        metric: F,
+    ) -> ConfidenceInterval<T>
+    where
+        T: RealNumber,
+        V: BaseVector<T>,
+        F: Fn(&V, &V) -> T,
+    {
+        if y_true.len() != y_pred.len() {
+            panic!(
+                "The vector sizes don't match: {} != {}",
+                y_true.len(),
+                y_pred.len()
+            );
+        }
+        if self.n_resamples < 1 {
+            panic!("Number of resamples should be at least 1");
+        }
+        if self.confidence_level <= 0. || self.confidence_level >= 1. {
+            panic!("confidence_level should be between 0 and 1");
+        }
+
+        let n = y_true.len();
+        let mut rng = get_rng_impl(self.seed);
+
+        let mut scores: Vec<T> = (0..self.n_resamples)
+            .map(|_| {
+                let index: Vec<usize> = (0..n).map(|_| rng.gen_range(0..n)).collect();
+                metric(&y_true.take(&index), &y_pred.take(&index))
+            })
+            .collect();
+        scores.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let alpha = (1. - self.confidence_level) / 2.;
+
+        ConfidenceInterval {
+            score: metric(y_true, y_pred),
+            lower: percentile(&scores, alpha),
+            upper: percentile(&scores, 1. - alpha),
+        }
+    }
+}
+
+/// Paired permutation test of two models
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct PermutationTest {
+    /// Number of random permutations.
+    pub n_permutations: usize,
+    /// Seed used to draw permutations.
+    pub seed: Option<u64>,
+}
+
+/// Outcome of a permutation test
+#[derive(Debug, Clone)]
+pub struct PermutationTestResult<T: RealNumber> {
+    /// Metric of the first model minus metric of the second model.
+    pub score_difference: T,
+    /// Probability to observe a difference at least as large, in absolute value, when both models perform equally well.
+    pub p_value: T,
+}
+
+impl Default for PermutationTest {
+    fn default() -> Self {
+        PermutationTest {
+            n_permutations: 1000,
+            seed: None,
+        }
+    }
+}
+
+impl PermutationTest {
+    /// Number of random permutations.
+    pub fn with_n_permutations(mut self, n_permutations: usize) -> Self {
+        self.n_permutations = n_permutations;
+        self
+    }
+    /// Seed used to draw permutations.
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Two-sided test of the difference between metrics of two models evaluated on the same samples.
+    /// * `y_true` - ground truth (correct) target values.
+    /// * `y_pred_a` - target values estimated by the first model.
+    /// * `y_pred_b` - target values estimated by the second model.
+    /// * `metric` - a metric to compare, e.g. [accuracy](../fn.accuracy.html)
+    pub fn compare<T, V, F>(
+        &self,
+        y_true: &V,
+        y_pred_a: &V,
+        y_pred_b: &V,
+        metric: F,
+    ) -> PermutationTestResult<T>
+    where
+        T: RealNumber,
+        V: BaseVector<T>,
+        F: Fn(&V, &V) -> T,
+    {
+        if y_true.len() != y_pred_a.len() || y_true.len() != y_pred_b.len() {
+            panic!(
+                "The vector sizes don't match: {} != {} != {}",
+                y_true.len(),
+                y_pred_a.len(),
+                y_pred_b.len()
+            );
+        }
+
+        let n = y_true.len();
+        let mut rng = get_rng_impl(self.seed);
+
+        let score_difference = metric(y_true, y_pred_a) - metric(y_true, y_pred_b);
+
+        let mut permuted_a = y_pred_a.clone();
+        let mut permuted_b = y_pred_b.clone();
+        let mut n_extreme = 0;
+        for _ in 0..self.n_permutations {
+            for i in 0..n {
+                let (a, b) = (y_pred_a.get(i), y_pred_b.get(i));
+                if rng.gen::<bool>() {
+                    permuted_a.set(i, b);
+                    permuted_b.set(i, a);
+                } else {
+                    permuted_a.set(i, a);
+                    permuted_b.set(i, b);
+                }
+            }
+            let difference = metric(y_true, &permuted_a) - metric(y_true, &permuted_b);
+            if difference.abs() >= score_difference.abs() {
+                n_extreme += 1;
+            }
+        }
+
+        PermutationTestResult {
+            score_difference,
+            p_value: T::from_usize(n_extreme + 1).unwrap()
+                / T::from_usize(self.n_permutations + 1).unwrap(),
+        }
+    }
+}
+
+/// Linear interpolation between the closest ranks of sorted values.
+fn percentile<T: RealNumber>(sorted: &[T], q: f64) -> T {
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    let fraction = T::from_f64(position - lower as f64).unwrap();
+    sorted[lower] + (sorted[upper] - sorted[lower]) * fraction
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::{accuracy, mean_squared_error};
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn bootstrap_confidence_interval() {
+        let y_true: Vec<f64> = (0..100).map(|i| (i % 2) as f64).collect();
+        let y_pred: Vec<f64> = (0..100)
+            .map(|i| {
+                if i % 5 == 0 {
+                    1. - y_true[i]
+                } else {
+                    y_true[i]
+                }
+            })
+            .collect();
+
+        let interval = Bootstrap::default()
+            .with_seed(Some(1))
+            .confidence_interval(&y_true, &y_pred, accuracy);
+
+        assert!((interval.score - 0.8).abs() < 1e-8);
+        assert!(interval.lower < interval.score && interval.score < interval.upper);
+        assert!(interval.lower > 0.65 && interval.upper < 0.95);
+
+        let narrow = Bootstrap::default()
+            .with_seed(Some(1))
+            .with_confidence_level(0.5)
+            .confidence_interval(&y_true, &y_pred, accuracy);
+        assert!(narrow.upper - narrow.lower < interval.upper - interval.lower);
+
+        let exact = Bootstrap::default()
+            .with_n_resamples(20)
+            .confidence_interval(&y_true, &y_true, mean_squared_error);
+        assert_eq!(0., exact.lower);
+        assert_eq!(0., exact.upper);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn permutation_test() {
+        let y_true: Vec<f64> = (0..60).map(|i| (i % 2) as f64).collect();
+        let good: Vec<f64> = (0..60)
+            .map(|i| {
+                if i % 10 == 0 {
+                    1. - y_true[i]
+                } else {
+                    y_true[i]
+                }
+            })
+            .collect();
+        let bad: Vec<f64> = (0..60)
+            .map(|i| {
+                if i % 2 == 0 {
+                    1. - y_true[i]
+                } else {
+                    y_true[i]
+                }
+            })
+            .collect();
+
+        let test = PermutationTest::default()
+            .with_seed(Some(1))
+            .compare(&y_true, &good, &bad, accuracy);
+        assert!((test.score_difference - 0.4).abs() < 1e-8);
+        assert!(test.p_value < 0.01);
+
+        let same = PermutationTest::default()
+            .with_seed(Some(1))
+            .compare(&y_true, &good, &good, accuracy);
+        assert_eq!(0., same.score_difference);
+        assert_eq!(1., same.p_value);
+    }
+}
@@ -0,0 +1,467 @@
+//! # Scorers
+//!
+//! A scorer evaluates a fitted estimator on a dataset. Unlike a plain metric, that compares true and predicted values,
+//! a scorer decides how to get predictions from the estimator and whether a larger score means a better model.
+//! This lets [model selection](../../model_selection/index.html) utilities treat accuracy, error measures and
+//! probability-based metrics like ROC AUC and log loss in the same way.
+//!
+//! * [PredictionScorer](struct.PredictionScorer.html) compares true values with the output of [`Predictor::predict`](../../api/trait.Predictor.html).
+//! * [ProbaScorer](struct.ProbaScorer.html) compares true values with class probabilities of [`PredictorProba::predict_proba`](../../api/trait.PredictorProba.html).
+//! * Any function `Fn(&y_true, &y_pred) -> T`, e.g. [accuracy](../fn.accuracy.html), is a scorer where larger values are better.
+//!
+//! Scorers returned by the functions of this module also compute weighted scores with [`Scorer::score_weighted`](trait.Scorer.html#method.score_weighted),
+//! which is used by [cross_validate_weighted](../../model_selection/fn.cross_validate_weighted.html).
+//! To add sample weights to your own metric, pass its weighted version to `with_weighted_metric`.
+//!
+//! Example:
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::metrics::scorer::{self, Scorer};
+//! use smartcore::tree::decision_tree_classifier::DecisionTreeClassifier;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!             &[5.1, 3.5, 1.4, 0.2],
+//!             &[4.9, 3.0, 1.4, 0.2],
+//!             &[4.7, 3.2, 1.3, 0.2],
+//!             &[4.6, 3.1, 1.5, 0.2],
+//!             &[5.0, 3.6, 1.4, 0.2],
+//!             &[7.0, 3.2, 4.7, 1.4],
+//!             &[6.4, 3.2, 4.5, 1.5],
+//!             &[6.9, 3.1, 4.9, 1.5],
+//!             &[5.5, 2.3, 4.0, 1.3],
+//!             &[6.5, 2.8, 4.6, 1.5],
+//!   ]);
+//! let y: Vec<f64> = vec![0., 0., 0., 0., 0., 1., 1., 1., 1., 1.];
+//!
+//! let tree = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
+//!
+//! let acc = scorer::accuracy().score(&tree, &x, &y).unwrap();
+//! let auc = scorer::roc_auc().score(&tree, &x, &y).unwrap();
+//! ```
+
+use crate::api::{Predictor, PredictorProba};
+use crate::error::Failed;
+use crate::linalg::{BaseMatrix, BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::metrics::auc::AUC;
+use crate::metrics::explained_variance::ExplainedVariance;
+use crate::metrics::log_loss::LogLoss;
+use crate::metrics::mean_absolute_error::MeanAbsoluteError;
+use crate::metrics::mean_absolute_percentage_error::MeanAbsolutePercentageError;
+use crate::metrics::mean_squared_error::MeanSquareError;
+use crate::metrics::median_absolute_error::MedianAbsoluteError;
+use crate::metrics::r2::R2;
+use crate::metrics::ClassificationMetrics;
+
+/// Evaluates a fitted estimator on a dataset
+pub trait Scorer<T: RealNumber, M: Matrix<T>, E> {
+    /// Score of a fitted `estimator` on features `x` with true target values `y`.
+    fn score(&self, estimator: &E, x: &M, y: &M::RowVector) -> Result<T, Failed>;
+
+    /// Score of a fitted `estimator` on features `x` with true target values `y`, where every sample counts with its weight.
+    /// Fails when the weights are invalid or when the scorer does not support sample weights.
+    fn score_weighted(
+        &self,
+        _estimator: &E,
+        _x: &M,
+        _y: &M::RowVector,
+        _sample_weight: &[T],
+    ) -> Result<T, Failed> {
+        Err(Failed::invalid_parameters(
+            "The scorer does not support sample weights",
+        ))
+    }
+
+    /// Whether larger scores denote better models. True for metrics like accuracy, false for error measures.
+    fn greater_is_better(&self) -> bool {
+        true
+    }
+}
+
+/// Metric that compares true values `Y` with predictions `P` of samples with different weights.
+pub trait WeightedMetric<T, Y, P> {
+    /// Weighted score, `metric(y_true, y_pred, sample_weight)`.
+    fn weighted_score(&self, y_true: &Y, y_pred: &P, sample_weight: &[T]) -> Result<T, Failed>;
+}
+
+impl<T, Y, P, F> WeightedMetric<T, Y, P> for F
+where
+    F: Fn(&Y, &P, &[T]) -> Result<T, Failed>,
+{
+    fn weighted_score(&self, y_true: &Y, y_pred: &P, sample_weight: &[T]) -> Result<T, Failed> {
+        self(y_true, y_pred, sample_weight)
+    }
+}
+
+/// Weighted metric of a scorer that does not support sample weights, every weighted score fails.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoWeightedMetric;
+
+impl<T, Y, P> WeightedMetric<T, Y, P> for NoWeightedMetric {
+    fn weighted_score(&self, _: &Y, _: &P, _: &[T]) -> Result<T, Failed> {
+        Err(Failed::invalid_parameters(
+            "The scorer does not support sample weights",
+        ))
+    }
+}
+
+impl<T, M, E, F> Scorer<T, M, E> for F
+where
+    T: RealNumber,
+    M: Matrix<T>,
+    E: Predictor<M, M::RowVector>,
+    F: Fn(&M::RowVector, &M::RowVector) -> T,
+{
+    fn score(&self, estimator: &E, x: &M, y: &M::RowVector) -> Result<T, Failed> {
+        Ok(self(y, &estimator.predict(x)?))
+    }
+}
+
+/// Scorer that compares true values with predicted ones.
+#[derive(Clone, Debug)]
+pub struct PredictionScorer<F, W = NoWeightedMetric> {
+    metric: F,
+    weighted_metric: W,
+    greater_is_better: bool,
+}
+
+/// Scorer of a built-in metric that compares true values with predictions, supports sample weights.
+pub type WeightedPredictionScorer<T, V> =
+    PredictionScorer<fn(&V, &V) -> T, fn(&V, &V, &[T]) -> Result<T, Failed>>;
+
+impl<F> PredictionScorer<F> {
+    /// Wraps a metric that takes true values and predictions, `metric(y_true, y_pred)`.
+    pub fn new(metric: F) -> Self {
+        PredictionScorer {
+            metric,
+            weighted_metric: NoWeightedMetric,
+            greater_is_better: true,
+        }
+    }
+}
+
+impl<F, W> PredictionScorer<F, W> {
+    /// Whether larger values of the metric denote better models.
+    pub fn with_greater_is_better(mut self, greater_is_better: bool) -> Self {
+        self.greater_is_better = greater_is_better;
+        self
+    }
+
+    /// Metric used when samples have weights, `weighted_metric(y_true, y_pred, sample_weight)`.
+    pub fn with_weighted_metric<W2>(self, weighted_metric: W2) -> PredictionScorer<F, W2> {
+        PredictionScorer {
+            metric: self.metric,
+            weighted_metric,
+            greater_is_better: self.greater_is_better,
+        }
+    }
+}
+
+impl<T, M, E, F, W> Scorer<T, M, E> for PredictionScorer<F, W>
+where
+    T: RealNumber,
+    M: Matrix<T>,
+    E: Predictor<M, M::RowVector>,
+    F: Fn(&M::RowVector, &M::RowVector) -> T,
+    W: WeightedMetric<T, M::RowVector, M::RowVector>,
+{
+    fn score(&self, estimator: &E, x: &M, y: &M::RowVector) -> Result<T, Failed> {
+        Ok((self.metric)(y, &estimator.predict(x)?))
+    }
+
+    fn score_weighted(
+        &self,
+        estimator: &E,
+        x: &M,
+        y: &M::RowVector,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
+        self.weighted_metric
+            .weighted_score(y, &estimator.predict(x)?, sample_weight)
+    }
+
+    fn greater_is_better(&self) -> bool {
+        self.greater_is_better
+    }
+}
+
+/// Scorer that compares true class labels with predicted class probabilities.
+#[derive(Clone, Debug)]
+pub struct ProbaScorer<F, W = NoWeightedMetric> {
+    metric: F,
+    weighted_metric: W,
+    greater_is_better: bool,
+}
+
+/// Scorer of a built-in metric that compares true class labels with predicted class probabilities, supports sample weights.
+pub type WeightedProbaScorer<T, M> = ProbaScorer<
+    fn(&<M as BaseMatrix<T>>::RowVector, &M) -> T,
+    fn(&<M as BaseMatrix<T>>::RowVector, &M, &[T]) -> Result<T, Failed>,
+>;
+
+impl<F> ProbaScorer<F> {
+    /// Wraps a metric that takes true labels and a _NxK_ matrix of class probabilities, `metric(y_true, y_proba)`.
+    pub fn new(metric: F) -> Self {
+        ProbaScorer {
+            metric,
+            weighted_metric: NoWeightedMetric,
+            greater_is_better: true,
+        }
+    }
+}
+
+impl<F, W> ProbaScorer<F, W> {
+    /// Whether larger values of the metric denote better models.
+    pub fn with_greater_is_better(mut self, greater_is_better: bool) -> Self {
+        self.greater_is_better = greater_is_better;
+        self
+    }
+
+    /// Metric used when samples have weights, `weighted_metric(y_true, y_proba, sample_weight)`.
+    pub fn with_weighted_metric<W2>(self, weighted_metric: W2) -> ProbaScorer<F, W2> {
+        ProbaScorer {
+            metric: self.metric,
+            weighted_metric,
+            greater_is_better: self.greater_is_better,
+        }
+    }
+}
+
+impl<T, M, E, F, W> Scorer<T, M, E> for ProbaScorer<F, W>
+where
+    T: RealNumber,
+    M: Matrix<T>,
+    E: PredictorProba<M>,
+    F: Fn(&M::RowVector, &M) -> T,
+    W: WeightedMetric<T, M::RowVector, M>,
+{
+    fn score(&self, estimator: &E, x: &M, y: &M::RowVector) -> Result<T, Failed> {
+        Ok((self.metric)(y, &estimator.predict_proba(x)?))
+    }
+
+    fn score_weighted(
+        &self,
+        estimator: &E,
+        x: &M,
+        y: &M::RowVector,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
+        self.weighted_metric
+            .weighted_score(y, &estimator.predict_proba(x)?, sample_weight)
+    }
+
+    fn greater_is_better(&self) -> bool {
+        self.greater_is_better
+    }
+}
+
+fn prediction_scorer<T: RealNumber, V: BaseVector<T>>(
+    metric: fn(&V, &V) -> T,
+    weighted_metric: fn(&V, &V, &[T]) -> Result<T, Failed>,
+    greater_is_better: bool,
+) -> WeightedPredictionScorer<T, V> {
+    PredictionScorer::new(metric)
+        .with_weighted_metric(weighted_metric)
+        .with_greater_is_better(greater_is_better)
+}
+
+/// Accuracy scorer, see [accuracy](../fn.accuracy.html).
+pub fn accuracy<T: RealNumber, V: BaseVector<T>>() -> WeightedPredictionScorer<T, V> {
+    prediction_scorer(
+        super::accuracy,
+        |y_true, y_pred, sample_weight| {
+            ClassificationMetrics::accuracy().get_score_weighted(y_true, y_pred, sample_weight)
+        },
+        true,
+    )
+}
+
+/// Mean squared error scorer, smaller values are better. See [mean squared error](../fn.mean_squared_error.html).
+pub fn mean_squared_error<T: RealNumber, V: BaseVector<T>>() -> WeightedPredictionScorer<T, V> {
+    prediction_scorer(
+        super::mean_squared_error,
+        |y_true, y_pred, sample_weight| {
+            MeanSquareError {}.get_score_weighted(y_true, y_pred, sample_weight)
+        },
+        false,
+    )
+}
+
+/// Mean absolute error scorer, smaller values are better. See [mean absolute error](../fn.mean_absolute_error.html).
+pub fn mean_absolute_error<T: RealNumber, V: BaseVector<T>>() -> WeightedPredictionScorer<T, V> {
+    prediction_scorer(
+        super::mean_absolute_error,
+        |y_true, y_pred, sample_weight| {
+            MeanAbsoluteError {}.get_score_weighted(y_true, y_pred, sample_weight)
+        },
+        false,
+    )
+}
+
+/// Mean absolute percentage error scorer, smaller values are better. See [mean absolute percentage error](../fn.mean_absolute_percentage_error.html).
+pub fn mean_absolute_percentage_error<T: RealNumber, V: BaseVector<T>>(
+) -> WeightedPredictionScorer<T, V> {
+    prediction_scorer(
+        super::mean_absolute_percentage_error,
+        |y_true, y_pred, sample_weight| {
+            MeanAbsolutePercentageError {}.get_score_weighted(y_true, y_pred, sample_weight)
+        },
+        false,
+    )
+}
+
+/// Median absolute error scorer, smaller values are better. See [median absolute error](../fn.median_absolute_error.html).
+pub fn median_absolute_error<T: RealNumber, V: BaseVector<T>>() -> WeightedPredictionScorer<T, V> {
+    prediction_scorer(
+        super::median_absolute_error,
+        |y_true, y_pred, sample_weight| {
+            MedianAbsoluteError {}.get_score_weighted(y_true, y_pred, sample_weight)
+        },
+        false,
+    )
+}
+
+/// Coefficient of determination scorer, see [R2](../fn.r2.html).
+pub fn r2<T: RealNumber, V: BaseVector<T>>() -> WeightedPredictionScorer<T, V> {
+    prediction_scorer(
+        super::r2,
+        |y_true, y_pred, sample_weight| R2 {}.get_score_weighted(y_true, y_pred, sample_weight),
+        true,
+    )
+}
+
+/// Explained variance scorer, see [explained variance](../fn.explained_variance_score.html).
+pub fn explained_variance<T: RealNumber, V: BaseVector<T>>() -> WeightedPredictionScorer<T, V> {
+    prediction_scorer(
+        super::explained_variance_score,
+        |y_true, y_pred, sample_weight| {
+            ExplainedVariance {}.get_score_weighted(y_true, y_pred, sample_weight)
+        },
+        true,
+    )
+}
+
+/// ROC AUC scorer for binary classification, computed from the probability of the class with the larger label.
+/// See [ROC AUC](../fn.roc_auc_score.html).
+pub fn roc_auc<T: RealNumber, M: Matrix<T>>() -> WeightedProbaScorer<T, M> {
+    ProbaScorer::new(positive_class_roc_auc::<T, M> as fn(&M::RowVector, &M) -> T)
+        .with_weighted_metric(
+            positive_class_roc_auc_weighted::<T, M> as fn(&M::RowVector, &M, &[T]) -> _,
+        )
+}
+
+/// Log loss scorer, smaller values are better. See [log loss](../fn.log_loss.html).
+pub fn log_loss<T: RealNumber, M: Matrix<T>>() -> WeightedProbaScorer<T, M> {
+    ProbaScorer::new(super::log_loss::<T, M> as fn(&M::RowVector, &M) -> T)
+        .with_weighted_metric(log_loss_weighted::<T, M> as fn(&M::RowVector, &M, &[T]) -> _)
+        .with_greater_is_better(false)
+}
+
+fn positive_class_roc_auc<T: RealNumber, M: Matrix<T>>(y_true: &M::RowVector, y_proba: &M) -> T {
+    let (_, k) = y_proba.shape();
+    let positive = y_proba.get_col_as_vec(k - 1);
+    super::roc_auc_score(&y_true.to_vec(), &positive)
+}
+
+fn positive_class_roc_auc_weighted<T: RealNumber, M: Matrix<T>>(
+    y_true: &M::RowVector,
+    y_proba: &M,
+    sample_weight: &[T],
+) -> Result<T, Failed> {
+    let (_, k) = y_proba.shape();
+    let positive = y_proba.get_col_as_vec(k - 1);
+    AUC {}.get_score_weighted(&y_true.to_vec(), &positive, sample_weight)
+}
+
+fn log_loss_weighted<T: RealNumber, M: Matrix<T>>(
+    y_true: &M::RowVector,
+    y_proba: &M,
+    sample_weight: &[T],
+) -> Result<T, Failed> {
+    LogLoss::default().get_score_weighted(y_true, y_proba, sample_weight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::tree::decision_tree_classifier::{
+        DecisionTreeClassifier, DecisionTreeClassifierParameters,
+    };
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn prediction_and_proba_scorers() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 1.],
+            &[2., 1.],
+            &[1., 2.],
+            &[2., 2.],
+            &[8., 8.],
+            &[9., 8.],
+            &[8., 9.],
+            &[9., 9.],
+        ]);
+        let y: Vec<f64> = vec![0., 0., 0., 1., 1., 1., 1., 0.];
+
+        let tree = DecisionTreeClassifier::fit(
+            &x,
+            &y,
+            DecisionTreeClassifierParameters::default().with_max_depth(1),
+        )
+        .unwrap();
+        let y_hat = tree.predict(&x).unwrap();
+
+        let acc = accuracy().score(&tree, &x, &y).unwrap();
+        assert!((acc - crate::metrics::accuracy(&y, &y_hat)).abs() < 1e-8);
+        assert!((acc - 0.75).abs() < 1e-8);
+        assert!(
+            Scorer::<f64, DenseMatrix<f64>, DecisionTreeClassifier<f64>>::greater_is_better(
+                &accuracy()
+            )
+        );
+
+        let mse = mean_squared_error().score(&tree, &x, &y).unwrap();
+        assert!((mse - 0.25).abs() < 1e-8);
+        assert!(
+            !Scorer::<f64, DenseMatrix<f64>, DecisionTreeClassifier<f64>>::greater_is_better(
+                &mean_squared_error()
+            )
+        );
+
+        let closure = |y: &Vec<f64>, y_hat: &Vec<f64>| crate::metrics::accuracy(y, y_hat);
+        assert!((closure.score(&tree, &x, &y).unwrap() - acc).abs() < 1e-8);
+
+        let auc = roc_auc().score(&tree, &x, &y).unwrap();
+        assert!((auc - 0.75).abs() < 1e-8);
+
+        let loss = log_loss().score(&tree, &x, &y).unwrap();
+        let y_proba = tree.predict_proba(&x).unwrap();
+        assert!((loss - crate::metrics::log_loss(&y, &y_proba)).abs() < 1e-8);
+        assert!(
+            !Scorer::<f64, DenseMatrix<f64>, DecisionTreeClassifier<f64>>::greater_is_better(
+                &log_loss()
+            )
+        );
+
+        let sample_weight = [1., 2., 1., 3., 1., 0.5, 1., 2.];
+        let weighted_acc = accuracy()
+            .score_weighted(&tree, &x, &y, &sample_weight)
+            .unwrap();
+        let expected = crate::metrics::accuracy::Accuracy {}
+            .get_score_weighted(&y, &y_hat, &sample_weight)
+            .unwrap();
+        assert!((weighted_acc - expected).abs() < 1e-8);
+
+        let uniform = [1.; 8];
+        assert!((roc_auc().score_weighted(&tree, &x, &y, &uniform).unwrap() - auc).abs() < 1e-8);
+        assert!((log_loss().score_weighted(&tree, &x, &y, &uniform).unwrap() - loss).abs() < 1e-8);
+        assert!(accuracy()
+            .score_weighted(&tree, &x, &y, &sample_weight[..4])
+            .is_err());
+        assert!(closure.score_weighted(&tree, &x, &y, &uniform).is_err());
+        assert!(PredictionScorer::new(closure)
+            .score_weighted(&tree, &x, &y, &uniform)
+            .is_err());
+    }
+}
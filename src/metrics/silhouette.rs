@@ -0,0 +1,262 @@
+//! # Silhouette Coefficient
+//!
+//! The silhouette coefficient measures how similar every sample is to its own cluster compared to the other clusters and does not require ground truth labels.
+//! For a sample \\(i\\) let \\(a_i\\) be the mean distance to the other samples of its cluster and \\(b_i\\) the mean distance to the samples of the nearest other cluster, then
+//!
+//! \\[s_i = \frac{b_i - a_i}{max(a_i, b_i)}\\]
+//!
+//! The coefficient of a sample that is alone in its cluster is 0. The silhouette score is the mean coefficient over all samples and ranges from -1 to 1,
+//! higher values indicate dense and well separated clusters. Comparing the score of clusterings with different number of clusters is a common way to choose _k_ for [K-Means](../../cluster/kmeans/index.html).
+//!
+//! Computing the score requires distances between all pairs of samples. For large datasets set `sample_size` to compute the score on a random subset of the samples.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::math::distance::Distances;
+//! use smartcore::metrics::silhouette_score;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[1., 1.],
+//!     &[1.5, 2.],
+//!     &[1., 1.5],
+//!     &[8., 8.],
+//!     &[9., 8.5],
+//!     &[8.5, 9.],
+//! ]);
+//! let labels = vec![0., 0., 0., 1., 1., 1.];
+//!
+//! let score: f64 = silhouette_score(&x, &labels, Distances::euclidian());
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Silhouettes: a Graphical Aid to the Interpretation and Validation of Cluster Analysis", Rousseeuw P. J., 1987](https://doi.org/10.1016/0377-0427(87)90125-7)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use rand::seq::SliceRandom;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::distance::Distance;
+use crate::math::num::RealNumber;
+use crate::rand::get_rng_impl;
+
+/// Silhouette coefficient
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Silhouette<D> {
+    /// Distance between samples.
+    pub distance: D,
+    /// Number of randomly chosen samples to compute the score on, all samples when `None`.
+    pub sample_size: Option<usize>,
+    /// Seed used to choose the samples.
+    pub seed: Option<u64>,
+}
+
+impl<D> Silhouette<D> {
+    /// Computes the score on `sample_size` randomly chosen samples.
+    pub fn with_sample_size(mut self, sample_size: usize) -> Self {
+        self.sample_size = Some(sample_size);
+        self
+    }
+
+    /// Seed used to choose the samples.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Computes the mean silhouette coefficient of all samples.
+    /// * `x` - _NxM_ matrix with _N_ samples and _M_ features in each sample.
+    /// * `labels` - cluster label of every sample.
+    pub fn get_score<T: RealNumber, M: Matrix<T>>(&self, x: &M, labels: &M::RowVector) -> T
+    where
+        D: Distance<Vec<T>, T>,
+    {
+        let (n, _) = x.shape();
+        let indices: Vec<usize> = match self.sample_size {
+            Some(sample_size) if sample_size < n => {
+                let mut indices: Vec<usize> = (0..n).collect();
+                indices.shuffle(&mut get_rng_impl(self.seed));
+                indices.truncate(sample_size);
+                indices
+            }
+            _ => (0..n).collect(),
+        };
+
+        let coefficients = self.coefficients(x, labels, &indices);
+        coefficients.iter().fold(T::zero(), |acc, s| acc + *s)
+            / T::from_usize(coefficients.len()).unwrap()
+    }
+
+    /// Computes the silhouette coefficient of every sample.
+    /// * `x` - _NxM_ matrix with _N_ samples and _M_ features in each sample.
+    /// * `labels` - cluster label of every sample.
+    pub fn get_samples<T: RealNumber, M: Matrix<T>>(&self, x: &M, labels: &M::RowVector) -> Vec<T>
+    where
+        D: Distance<Vec<T>, T>,
+    {
+        let (n, _) = x.shape();
+        self.coefficients(x, labels, &(0..n).collect::<Vec<usize>>())
+    }
+
+    fn coefficients<T: RealNumber, M: Matrix<T>>(
+        &self,
+        x: &M,
+        labels: &M::RowVector,
+        indices: &[usize],
+    ) -> Vec<T>
+    where
+        D: Distance<Vec<T>, T>,
+    {
+        let (n, _) = x.shape();
+        if n != labels.len() {
+            panic!(
+                "Number of samples ({}) does not match the number of labels ({})",
+                n,
+                labels.len()
+            );
+        }
+
+        let clusters: Vec<T> = indices.iter().map(|i| labels.get(*i)).collect::<Vec<T>>();
+        let mut unique = clusters.clone();
+        unique.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        unique.dedup();
+        let k = unique.len();
+        if k < 2 || k >= indices.len() {
+            panic!(
+                "Number of clusters ({}) should be between 2 and the number of samples - 1 ({})",
+                k,
+                indices.len() - 1
+            );
+        }
+
+        let cluster: Vec<usize> = clusters
+            .iter()
+            .map(|c| unique.iter().position(|u| u == c).unwrap())
+            .collect();
+        let mut size = vec![0; k];
+        for c in cluster.iter() {
+            size[*c] += 1;
+        }
+
+        let rows: Vec<Vec<T>> = indices.iter().map(|i| x.get_row_as_vec(*i)).collect();
+        // in parallel every row sums up its own distances, so each pair is computed twice
+        #[cfg(feature = "parallel")]
+        let distance_sums: Vec<Vec<T>> = crate::parallel::map_indices(rows.len(), |i| {
+            let mut sums = vec![T::zero(); k];
+            for j in (0..rows.len()).filter(|j| *j != i) {
+                sums[cluster[j]] += self.distance.distance(&rows[i], &rows[j]);
+            }
+            sums
+        });
+        #[cfg(not(feature = "parallel"))]
+        let distance_sums = {
+            let mut distance_sums = vec![vec![T::zero(); k]; rows.len()];
+            for i in 0..rows.len() {
+                for j in (i + 1)..rows.len() {
+                    let d = self.distance.distance(&rows[i], &rows[j]);
+                    distance_sums[i][cluster[j]] += d;
+                    distance_sums[j][cluster[i]] += d;
+                }
+            }
+            distance_sums
+        };
+
+        distance_sums
+            .iter()
+            .zip(cluster.iter())
+            .map(|(sums, c)| {
+                if size[*c] == 1 {
+                    return T::zero();
+                }
+                let a = sums[*c] / T::from_usize(size[*c] - 1).unwrap();
+                let b = (0..k)
+                    .filter(|l| l != c)
+                    .map(|l| sums[l] / T::from_usize(size[l]).unwrap())
+                    .fold(T::max_value(), |acc, mean| acc.min(mean));
+                let max = a.max(b);
+                if max == T::zero() {
+                    T::zero()
+                } else {
+                    (b - a) / max
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::math::distance::Distances;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn silhouette() {
+        let x = DenseMatrix::from_2d_array(&[&[0.], &[1.], &[4.], &[6.], &[10.]]);
+        let labels: Vec<f64> = vec![0., 0., 1., 1., 2.];
+        let silhouette = Silhouette {
+            distance: Distances::euclidian(),
+            sample_size: None,
+            seed: None,
+        };
+
+        let samples = silhouette.get_samples(&x, &labels);
+        // a = 1, b = mean(4, 6) = 5
+        assert!((samples[0] - 0.8).abs() < 1e-8);
+        // a = 1, b = mean(3, 5) = 4
+        assert!((samples[1] - 0.75).abs() < 1e-8);
+        // a = 2, b = mean(4, 3) = 3.5
+        assert!((samples[2] - 1.5 / 3.5).abs() < 1e-8);
+        // a = 2, b = 4
+        assert!((samples[3] - 0.5).abs() < 1e-8);
+        // a singleton cluster
+        assert_eq!(samples[4], 0.);
+
+        let score: f64 = silhouette.get_score(&x, &labels);
+        assert!((score - samples.iter().sum::<f64>() / 5.).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn silhouette_sampled() {
+        let values: Vec<Vec<f64>> = (0..100)
+            .map(|i| vec![(i % 2) as f64 * 10. + (i % 7) as f64 / 7.])
+            .collect();
+        let x = DenseMatrix::from_2d_vec(&values);
+        let labels: Vec<f64> = (0..100).map(|i| (i % 2) as f64).collect();
+
+        let silhouette = Silhouette {
+            distance: Distances::euclidian(),
+            sample_size: None,
+            seed: None,
+        };
+        let score: f64 = silhouette.get_score(&x, &labels);
+        let sampled = silhouette.with_sample_size(30).with_seed(1);
+        let sampled_score: f64 = sampled.get_score(&x, &labels);
+
+        assert!(score > 0.9);
+        assert!((score - sampled_score).abs() < 0.02);
+        assert_eq!(sampled_score, sampled.get_score(&x, &labels));
+    }
+
+    #[test]
+    #[should_panic]
+    fn single_cluster() {
+        let x = DenseMatrix::from_2d_array(&[&[0.], &[1.], &[4.]]);
+        let labels: Vec<f64> = vec![0., 0., 0.];
+        Silhouette {
+            distance: Distances::euclidian(),
+            sample_size: None,
+            seed: None,
+        }
+        .get_score(&x, &labels);
+    }
+}
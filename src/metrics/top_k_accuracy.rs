@@ -0,0 +1,186 @@
+//! # Top-k Accuracy
+//!
+//! Fraction of samples whose true label is among the _k_ labels with the largest predicted scores. With _k = 1_ it is the usual accuracy.
+//! It is useful for problems with many labels, where a model that puts the right label among its first guesses is still valuable.
+//!
+//! Columns of the score matrix follow sorted labels, as returned by [`PredictorProba::predict_proba`](../../api/trait.PredictorProba.html).
+//! Scores can be probabilities or any decision values where larger means more likely. A label tied with the _k_-th largest score counts as a hit.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::metrics::top_k_accuracy::TopKAccuracy;
+//!
+//! let y_true: Vec<f64> = vec![0., 1., 2., 2.];
+//! let y_score = DenseMatrix::from_2d_array(&[
+//!     &[0.5, 0.2, 0.2],
+//!     &[0.3, 0.4, 0.2],
+//!     &[0.2, 0.4, 0.3],
+//!     &[0.7, 0.2, 0.1],
+//! ]);
+//!
+//! let score: f64 = TopKAccuracy::new(2).get_score(&y_true, &y_score);
+//! assert!((score - 0.75).abs() < 1e-8);
+//! ```
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::metrics::check_sample_weight;
+
+/// Top-k accuracy
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct TopKAccuracy<T: RealNumber> {
+    /// Number of labels with the largest scores that are considered correct predictions.
+    pub k: usize,
+    /// Labels that correspond to columns of the score matrix. When `None` sorted unique values of `y_true` are used,
+    /// set labels explicitly when some of them may be missing in `y_true`.
+    pub labels: Option<Vec<T>>,
+}
+
+impl<T: RealNumber> TopKAccuracy<T> {
+    /// New top-k accuracy metric.
+    /// * `k` - number of labels with the largest scores that are considered correct predictions.
+    pub fn new(k: usize) -> Self {
+        TopKAccuracy { k, labels: None }
+    }
+
+    /// Labels that correspond to columns of the score matrix.
+    pub fn with_labels(mut self, labels: Vec<T>) -> Self {
+        self.labels = Some(labels);
+        self
+    }
+
+    /// Computes top-k accuracy.
+    /// * `y_true` - cround truth (correct) labels.
+    /// * `y_score` - _NxK_ matrix of predicted probabilities or scores, as returned by a classifier.
+    pub fn get_score<M: Matrix<T>>(&self, y_true: &M::RowVector, y_score: &M) -> T {
+        self.get_score_weighted(y_true, y_score, &vec![T::one(); y_true.len()])
+            .unwrap()
+    }
+
+    /// Computes weighted top-k accuracy, total weight of samples with the true label among the top _k_ labels divided by total weight of all samples.
+    /// * `y_true` - cround truth (correct) labels.
+    /// * `y_score` - _NxK_ matrix of predicted probabilities or scores, as returned by a classifier.
+    /// * `sample_weight` - weight of every sample.
+    pub fn get_score_weighted<M: Matrix<T>>(
+        &self,
+        y_true: &M::RowVector,
+        y_score: &M,
+        sample_weight: &[T],
+    ) -> Result<T, Failed> {
+        let (n, n_labels) = y_score.shape();
+        if y_true.len() != n {
+            panic!("The vector sizes don't match: {} != {}", y_true.len(), n);
+        }
+        if self.k < 1 {
+            panic!("k should be at least 1");
+        }
+        let total_weight = check_sample_weight(n, sample_weight)?;
+
+        let labels = match &self.labels {
+            Some(labels) => {
+                let mut labels = labels.clone();
+                labels.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                labels
+            }
+            None => {
+                let mut labels = y_true.to_vec();
+                labels.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                labels.dedup();
+                labels
+            }
+        };
+        if labels.len() != n_labels {
+            panic!(
+                "Number of labels {} does not match number of columns {}",
+                labels.len(),
+                n_labels
+            );
+        }
+
+        let mut hits = T::zero();
+        for (i, &w) in sample_weight.iter().enumerate() {
+            let label = y_true.get(i);
+            let c = labels
+                .binary_search_by(|l| l.partial_cmp(&label).unwrap())
+                .unwrap_or_else(|_| panic!("Unknown label: {}", label));
+            let score = y_score.get(i, c);
+            let n_better = (0..n_labels).filter(|&j| y_score.get(i, j) > score).count();
+            if n_better < self.k {
+                hits += w;
+            }
+        }
+
+        Ok(hits / total_weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn top_k_accuracy() {
+        let y_true: Vec<f64> = vec![0., 1., 2., 2.];
+        let y_score = DenseMatrix::from_2d_array(&[
+            &[0.5, 0.2, 0.2],
+            &[0.3, 0.4, 0.2],
+            &[0.2, 0.4, 0.3],
+            &[0.7, 0.2, 0.1],
+        ]);
+
+        let top1: f64 = TopKAccuracy::new(1).get_score(&y_true, &y_score);
+        let top2: f64 = TopKAccuracy::new(2).get_score(&y_true, &y_score);
+        let top3: f64 = TopKAccuracy::new(3).get_score(&y_true, &y_score);
+
+        assert!((top1 - 0.5).abs() < 1e-8);
+        assert!((top2 - 0.75).abs() < 1e-8);
+        assert!((top3 - 1.0).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn top_k_accuracy_with_labels() {
+        let y_true: Vec<f64> = vec![1., 3.];
+        let y_score = DenseMatrix::from_2d_array(&[&[0.1, 0.6, 0.2, 0.1], &[0.5, 0.1, 0.1, 0.3]]);
+
+        let score: f64 = TopKAccuracy::new(2)
+            .with_labels(vec![0., 1., 2., 3.])
+            .get_score(&y_true, &y_score);
+
+        assert!((score - 1.0).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn top_k_accuracy_weighted() {
+        let y_true: Vec<f64> = vec![0., 1., 2., 2.];
+        let y_score = DenseMatrix::from_2d_array(&[
+            &[0.5, 0.2, 0.2],
+            &[0.3, 0.4, 0.2],
+            &[0.2, 0.4, 0.3],
+            &[0.7, 0.2, 0.1],
+        ]);
+
+        let uniform: f64 = TopKAccuracy::new(2)
+            .get_score_weighted(&y_true, &y_score, &[1., 1., 1., 1.])
+            .unwrap();
+        let weighted: f64 = TopKAccuracy::new(2)
+            .get_score_weighted(&y_true, &y_score, &[1., 2., 1., 4.])
+            .unwrap();
+
+        assert!((uniform - 0.75).abs() < 1e-8);
+        assert!((weighted - 0.5).abs() < 1e-8);
+        assert!(TopKAccuracy::new(2)
+            .get_score_weighted(&y_true, &y_score, &[1., 1.])
+            .is_err());
+    }
+}
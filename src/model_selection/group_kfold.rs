@@ -0,0 +1,155 @@
+//! # Group KFold
+//!
+//! Defines k-fold cross validator for grouped data. Samples that share a group, e.g. several measurements of the same patient,
+//! are never split between training and test sets, so the test score tells how well a model generalizes to groups it has never seen.
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::model_selection::{BaseKFold, GroupKFold};
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[1., 2.], &[3., 4.], &[5., 6.], &[7., 8.], &[9., 10.], &[11., 12.],
+//! ]);
+//! let groups = vec![1., 1., 2., 2., 2., 3.];
+//!
+//! let cv = GroupKFold::new(&groups).with_n_splits(3);
+//!
+//! for (train, test) in cv.split(&x) {
+//!     println!("train: {:?}, test: {:?}", train, test);
+//! }
+//! ```
+
+use crate::linalg::BaseVector;
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::math::vector::RealNumberVector;
+use crate::model_selection::kfold::KFoldIter;
+use crate::model_selection::BaseKFold;
+use alloc::vec::Vec;
+
+/// K-Folds cross-validator with non-overlapping groups
+#[derive(Clone, Debug)]
+pub struct GroupKFold {
+    /// Number of folds. Must be at least 2 and at most the number of distinct groups.
+    pub n_splits: usize,
+    groups: Vec<usize>,
+}
+
+impl GroupKFold {
+    /// Creates a new splitter that keeps samples of each group found in `groups` within a single fold.
+    /// * `groups` - group labels of the samples that will be split, should be of size _N_
+    pub fn new<T: RealNumber, V: BaseVector<T>>(groups: &V) -> GroupKFold {
+        let (_, groups) = groups.unique_with_indices();
+        GroupKFold {
+            n_splits: 3,
+            groups,
+        }
+    }
+
+    /// Number of folds. Must be at least 2 and at most the number of distinct groups.
+    pub fn with_n_splits(mut self, n_splits: usize) -> Self {
+        self.n_splits = n_splits;
+        self
+    }
+
+    fn test_indices(&self, n_groups: usize) -> Vec<Vec<usize>> {
+        let mut group_sizes = vec![0usize; n_groups];
+        for &group in self.groups.iter() {
+            group_sizes[group] += 1;
+        }
+
+        // largest groups go first, each one to the fold that has the fewest samples so far
+        let mut order: Vec<usize> = (0..n_groups).collect();
+        order.sort_by(|&a, &b| group_sizes[b].cmp(&group_sizes[a]).then(a.cmp(&b)));
+
+        let mut fold_sizes = vec![0usize; self.n_splits];
+        let mut group_fold = vec![0usize; n_groups];
+        for group in order {
+            let mut fold = 0;
+            for f in 1..self.n_splits {
+                if fold_sizes[f] < fold_sizes[fold] {
+                    fold = f;
+                }
+            }
+            fold_sizes[fold] += group_sizes[group];
+            group_fold[group] = fold;
+        }
+
+        let mut test_indices: Vec<Vec<usize>> = vec![Vec::new(); self.n_splits];
+        for (i, &group) in self.groups.iter().enumerate() {
+            test_indices[group_fold[group]].push(i);
+        }
+
+        test_indices
+    }
+}
+
+impl BaseKFold for GroupKFold {
+    type Output = KFoldIter;
+
+    fn n_splits(&self) -> usize {
+        self.n_splits
+    }
+
+    fn split<T: RealNumber, M: Matrix<T>>(&self, x: &M) -> Self::Output {
+        if self.n_splits < 2 {
+            panic!("Number of splits is too small: {}", self.n_splits);
+        }
+        let n_samples = x.shape().0;
+        if n_samples != self.groups.len() {
+            panic!(
+                "x and groups should have the same number of samples. |x|: {}, |groups|: {}",
+                n_samples,
+                self.groups.len()
+            );
+        }
+        let n_groups = self.groups.iter().max().map_or(0, |&max| max + 1);
+        if self.n_splits > n_groups {
+            panic!(
+                "Number of splits {} is greater than the number of groups {}",
+                self.n_splits, n_groups
+            );
+        }
+
+        KFoldIter::from_test_indices(n_samples, self.test_indices(n_groups))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn group_kfold_keeps_groups_together() {
+        let groups = vec![
+            0., 0., 1., 1., 1., 2., 3., 3., 4., 4., 4., 4., 5., 6., 6., 7., 7., 7., 8., 9.,
+        ];
+        let x: DenseMatrix<f64> = DenseMatrix::rand(20, 2);
+
+        let cv = GroupKFold::new(&groups).with_n_splits(4);
+
+        let mut n_tested = [0; 20];
+        for (train, test) in cv.split(&x) {
+            assert_eq!(20, train.len() + test.len());
+            assert_eq!(5, test.len());
+            for &i in test.iter() {
+                assert!(train.iter().all(|&j| groups[j] != groups[i]));
+                n_tested[i] += 1;
+            }
+        }
+        assert!(n_tested.iter().all(|&n| n == 1));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    #[should_panic]
+    fn group_kfold_too_many_splits() {
+        let groups = vec![0., 0., 1., 1.];
+        let x: DenseMatrix<f64> = DenseMatrix::rand(4, 2);
+
+        let _ = GroupKFold::new(&groups).with_n_splits(3).split(&x);
+    }
+}
@@ -1,9 +1,132 @@
+//! # Hyperparameter Tuning
+//!
+//! Most estimators have hyperparameters that are not learned from data but have a large effect on how well a model generalizes,
+//! e.g. the number of neighbors of [KNN](../../neighbors/index.html) or the depth of a [decision tree](../../tree/index.html).
+//! Grid search fits an estimator with every combination of candidate values, scores each combination with cross-validation
+//! and keeps the one with the highest mean test score.
+//!
+//! A grid of candidates can be defined either with the `*SearchParameters` structs of the estimators, e.g.
+//! [LogisticRegressionSearchParameters](../../linear/logistic_regression/struct.LogisticRegressionSearchParameters.html),
+//! or with a [ParameterGrid](struct.ParameterGrid.html) that applies the `with_*` builders of any `*Parameters` struct:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::model_selection::{GridSearchCV, KFold, ParameterGrid};
+//! use smartcore::neighbors::knn_classifier::{KNNClassifier, KNNClassifierParameters};
+//! use smartcore::metrics::accuracy;
+//!
+//! //Iris data
+//! let x = DenseMatrix::from_2d_array(&[
+//!           &[5.1, 3.5, 1.4, 0.2],
+//!           &[4.9, 3.0, 1.4, 0.2],
+//!           &[4.7, 3.2, 1.3, 0.2],
+//!           &[4.6, 3.1, 1.5, 0.2],
+//!           &[5.0, 3.6, 1.4, 0.2],
+//!           &[5.4, 3.9, 1.7, 0.4],
+//!           &[4.6, 3.4, 1.4, 0.3],
+//!           &[5.0, 3.4, 1.5, 0.2],
+//!           &[4.4, 2.9, 1.4, 0.2],
+//!           &[4.9, 3.1, 1.5, 0.1],
+//!           &[7.0, 3.2, 4.7, 1.4],
+//!           &[6.4, 3.2, 4.5, 1.5],
+//!           &[6.9, 3.1, 4.9, 1.5],
+//!           &[5.5, 2.3, 4.0, 1.3],
+//!           &[6.5, 2.8, 4.6, 1.5],
+//!           &[5.7, 2.8, 4.5, 1.3],
+//!           &[6.3, 3.3, 4.7, 1.6],
+//!           &[4.9, 2.4, 3.3, 1.0],
+//!           &[6.6, 2.9, 4.6, 1.3],
+//!           &[5.2, 2.7, 3.9, 1.4],
+//!           ]);
+//! let y: Vec<f64> = vec![
+//!           0., 0., 0., 0., 0., 0., 0., 0., 0., 0., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1.,
+//! ];
+//!
+//! let grid = ParameterGrid::new(KNNClassifierParameters::default())
+//!     .with_values(&[3, 5, 7], |p, k| p.with_k(k));
+//!
+//! let search = GridSearchCV::fit(
+//!     KNNClassifier::fit,
+//!     &x, &y,
+//!     grid,
+//!     &KFold::default().with_n_splits(3),
+//!     accuracy,
+//! ).unwrap();
+//!
+//! println!("Best k: {}, mean test accuracy: {}", search.best_parameters().k, search.best_score());
+//!
+//! for result in search.results() {
+//!     println!("k: {}, accuracy: {} +/- {}",
+//!         result.parameters.k,
+//!         result.cross_validation_result.mean_test_score(),
+//!         result.cross_validation_result.std_test_score());
+//! }
+//!
+//! let y_hat = search.predict(&x).unwrap();
+//! ```
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::{Failed, FailedError};
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::metrics::scorer::Scorer;
+use crate::model_selection::{cross_validate_with_scorer, BaseKFold, CrossValidationResult};
+use crate::parallel::MaybeSync;
+use alloc::vec::Vec;
+
+/// A grid of hyperparameters, every combination of candidate values set with the `with_*` builders of a `*Parameters` struct.
+#[derive(Clone, Debug)]
+pub struct ParameterGrid<H: Clone> {
+    candidates: Vec<H>,
+}
+
+impl<H: Clone> ParameterGrid<H> {
+    /// Creates a grid with a single combination, `base`. Hyperparameters that are not varied keep values of `base`.
+    pub fn new(base: H) -> Self {
+        ParameterGrid {
+            candidates: vec![base],
+        }
+    }
+
+    /// Combines every candidate of the grid with every value from `values`.
+    /// * `values` - candidate values of a single hyperparameter
+    /// * `set` - sets a value of the hyperparameter, e.g. `|p, k| p.with_k(k)`
+    pub fn with_values<V: Clone, F: Fn(H, V) -> H>(self, values: &[V], set: F) -> Self {
+        let mut candidates = Vec::with_capacity(self.candidates.len() * values.len());
+        for candidate in self.candidates.iter() {
+            for value in values.iter() {
+                candidates.push(set(candidate.clone(), value.clone()));
+            }
+        }
+        ParameterGrid { candidates }
+    }
+
+    /// Number of combinations in the grid
+    pub fn len(&self) -> usize {
+        self.candidates.len()
+    }
+
+    /// Whether the grid has no combinations
+    pub fn is_empty(&self) -> bool {
+        self.candidates.is_empty()
+    }
+}
+
+impl<H: Clone> IntoIterator for ParameterGrid<H> {
+    type Item = H;
+    type IntoIter = alloc::vec::IntoIter<H>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.candidates.into_iter()
+    }
+}
+
 /// grid search results.
 #[derive(Clone, Debug)]
 pub struct GridSearchResult<T: RealNumber, I: Clone> {
-    /// Vector with test scores on each cv split
+    /// Training and test scores on each cv split
     pub cross_validation_result: CrossValidationResult<T>,
-    /// Vector with training scores on each cv split
+    /// Hyperparameters that were evaluated
     pub parameters: I,
 }
 
@@ -13,7 +136,7 @@ pub struct GridSearchResult<T: RealNumber, I: Clone> {
 /// * `y` - target values, should be of size _N_
 /// * `parameter_search` - an iterator for parameters that will be tested.
 /// * `cv` - the cross-validation splitting strategy, should be an instance of [`BaseKFold`](./trait.BaseKFold.html)
-/// * `score` - a metric to use for evaluation, see [metrics](../metrics/index.html)
+/// * `score` - a metric to use for evaluation, see [metrics](../metrics/index.html), or a [scorer](../metrics/scorer/index.html)
 pub fn grid_search<T, M, I, E, K, F, S>(
     fit_estimator: F,
     x: &M,
@@ -26,19 +149,28 @@ where
     T: RealNumber,
     M: Matrix<T>,
     I: Iterator,
-    I::Item: Clone,
-    E: Predictor<M, M::RowVector>,
+    I::Item: Clone + MaybeSync,
     K: BaseKFold,
-    F: Fn(&M, &M::RowVector, I::Item) -> Result<E, Failed>,
-    S: Fn(&M::RowVector, &M::RowVector) -> T,
+    F: Fn(&M, &M::RowVector, I::Item) -> Result<E, Failed> + MaybeSync,
+    S: Scorer<T, M, E> + MaybeSync,
 {
     let mut best_result: Option<CrossValidationResult<T>> = None;
     let mut best_parameters = None;
 
-    for parameters in parameter_search {
-        let result = cross_validate(&fit_estimator, x, y, &parameters, &cv, &score)?;
+    for (candidate, parameters) in parameter_search.enumerate() {
+        let result = cross_validate_with_scorer(&fit_estimator, x, y, &parameters, &cv, &score)?;
+        info!(
+            "Grid search candidate {}: mean test score {}, std {}",
+            candidate + 1,
+            result.mean_test_score(),
+            result.std_test_score()
+        );
         if best_result.is_none()
-            || result.mean_test_score() > best_result.as_ref().unwrap().mean_test_score()
+            || is_better(
+                &score,
+                result.mean_test_score(),
+                best_result.as_ref().unwrap().mean_test_score(),
+            )
         {
             best_parameters = Some(parameters);
             best_result = Some(result);
@@ -58,60 +190,393 @@ where
     }
 }
 
+fn is_better<T: RealNumber, M: Matrix<T>, E, S: Scorer<T, M, E>>(score: &S, a: T, b: T) -> bool {
+    if score.greater_is_better() {
+        a > b
+    } else {
+        a < b
+    }
+}
+
+/// Grid search parameters, used when [GridSearchCV](struct.GridSearchCV.html) is fitted as a [`SupervisedEstimator`](../api/trait.SupervisedEstimator.html),
+/// e.g. inside another cross-validation loop.
+#[derive(Clone)]
+pub struct GridSearchCVParameters<H: Clone, K: BaseKFold + Clone, S: Clone> {
+    /// Combinations of hyperparameters to evaluate
+    pub parameter_grid: Vec<H>,
+    /// The cross-validation splitting strategy
+    pub cv: K,
+    /// A metric or a [scorer](../metrics/scorer/index.html) to use for evaluation
+    pub score: S,
+}
+
+impl<H: Clone, K: BaseKFold + Clone, S: Clone> GridSearchCVParameters<H, K, S> {
+    /// Creates parameters of a grid search.
+    /// * `parameter_grid` - combinations of hyperparameters to evaluate, e.g. a [ParameterGrid](struct.ParameterGrid.html)
+    /// * `cv` - the cross-validation splitting strategy, should be an instance of [`BaseKFold`](./trait.BaseKFold.html)
+    /// * `score` - a metric to use for evaluation, higher values are better, see [metrics](../metrics/index.html), or a [scorer](../metrics/scorer/index.html)
+    pub fn new<P: IntoIterator<Item = H>>(parameter_grid: P, cv: K, score: S) -> Self {
+        GridSearchCVParameters {
+            parameter_grid: parameter_grid.into_iter().collect(),
+            cv,
+            score,
+        }
+    }
+}
+
+/// Exhaustive search over a grid of hyperparameters with cross-validation.
+/// The best combination is refitted on the whole dataset and used for predictions.
+#[derive(Debug)]
+pub struct GridSearchCV<T: RealNumber, H: Clone, E> {
+    results: Vec<GridSearchResult<T, H>>,
+    best_index: usize,
+    best_estimator: E,
+}
+
+impl<T: RealNumber, H: Clone, E> GridSearchCV<T, H, E> {
+    /// Evaluates every combination of hyperparameters with cross-validation and refits the best one on the whole dataset.
+    /// * `fit_estimator` - a `fit` function of an estimator
+    /// * `x` - features, matrix of size _NxM_ where _N_ is number of samples and _M_ is number of attributes.
+    /// * `y` - target values, should be of size _N_
+    /// * `parameter_grid` - combinations of hyperparameters to evaluate, e.g. a [ParameterGrid](struct.ParameterGrid.html)
+    /// * `cv` - the cross-validation splitting strategy, should be an instance of [`BaseKFold`](./trait.BaseKFold.html)
+    /// * `score` - a metric to use for evaluation, higher values are better, see [metrics](../metrics/index.html), or a [scorer](../metrics/scorer/index.html)
+    pub fn fit<M, P, K, F, S>(
+        fit_estimator: F,
+        x: &M,
+        y: &M::RowVector,
+        parameter_grid: P,
+        cv: &K,
+        score: S,
+    ) -> Result<GridSearchCV<T, H, E>, Failed>
+    where
+        M: Matrix<T>,
+        H: MaybeSync,
+        P: IntoIterator<Item = H>,
+        K: BaseKFold,
+        F: Fn(&M, &M::RowVector, H) -> Result<E, Failed> + MaybeSync,
+        S: Scorer<T, M, E> + MaybeSync,
+    {
+        let mut results: Vec<GridSearchResult<T, H>> = Vec::new();
+        let mut best_index = 0;
+
+        for parameters in parameter_grid {
+            let cross_validation_result =
+                cross_validate_with_scorer(&fit_estimator, x, y, &parameters, cv, &score)?;
+            info!(
+                "Grid search candidate {}: mean test score {}, std {}",
+                results.len() + 1,
+                cross_validation_result.mean_test_score(),
+                cross_validation_result.std_test_score()
+            );
+            if !results.is_empty()
+                && is_better(
+                    &score,
+                    cross_validation_result.mean_test_score(),
+                    results[best_index]
+                        .cross_validation_result
+                        .mean_test_score(),
+                )
+            {
+                best_index = results.len();
+            }
+            results.push(GridSearchResult {
+                cross_validation_result,
+                parameters,
+            });
+        }
+
+        if results.is_empty() {
+            return Err(Failed::because(
+                FailedError::FindFailed,
+                "there were no parameter sets found",
+            ));
+        }
+
+        let best_estimator = fit_estimator(x, y, results[best_index].parameters.clone())?;
+
+        Ok(GridSearchCV {
+            results,
+            best_index,
+            best_estimator,
+        })
+    }
+
+    /// Cross-validation results of every combination of hyperparameters, in the order they were evaluated.
+    pub fn results(&self) -> &[GridSearchResult<T, H>] {
+        &self.results
+    }
+
+    /// Position of the best combination in [results](#method.results)
+    pub fn best_index(&self) -> usize {
+        self.best_index
+    }
+
+    /// Combination of hyperparameters with the highest mean test score
+    pub fn best_parameters(&self) -> &H {
+        &self.results[self.best_index].parameters
+    }
+
+    /// Mean test score of the best combination of hyperparameters
+    pub fn best_score(&self) -> T {
+        self.results[self.best_index]
+            .cross_validation_result
+            .mean_test_score()
+    }
+
+    /// Estimator fitted on the whole dataset with the best combination of hyperparameters
+    pub fn best_estimator(&self) -> &E {
+        &self.best_estimator
+    }
+
+    /// Predict target values with the best estimator.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed>
+    where
+        E: Predictor<M, M::RowVector>,
+    {
+        self.best_estimator.predict(x)
+    }
+}
+
+impl<T, M, H, E, K, S> SupervisedEstimator<M, M::RowVector, GridSearchCVParameters<H, K, S>>
+    for GridSearchCV<T, H, E>
+where
+    T: RealNumber,
+    M: Matrix<T>,
+    H: Clone + MaybeSync,
+    E: SupervisedEstimator<M, M::RowVector, H> + Predictor<M, M::RowVector>,
+    K: BaseKFold + Clone,
+    S: Scorer<T, M, E> + Clone + MaybeSync,
+{
+    fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: GridSearchCVParameters<H, K, S>,
+    ) -> Result<Self, Failed> {
+        GridSearchCV::fit(
+            E::fit,
+            x,
+            y,
+            parameters.parameter_grid,
+            &parameters.cv,
+            parameters.score,
+        )
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, H: Clone, E: Predictor<M, M::RowVector>>
+    Predictor<M, M::RowVector> for GridSearchCV<T, H, E>
+{
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-  use crate::linear::logistic_regression::{
-    LogisticRegression, LogisticRegressionSearchParameters,
-};
-
-  #[test]
-  fn test_grid_search() {
-      let x = DenseMatrix::from_2d_array(&[
-          &[5.1, 3.5, 1.4, 0.2],
-          &[4.9, 3.0, 1.4, 0.2],
-          &[4.7, 3.2, 1.3, 0.2],
-          &[4.6, 3.1, 1.5, 0.2],
-          &[5.0, 3.6, 1.4, 0.2],
-          &[5.4, 3.9, 1.7, 0.4],
-          &[4.6, 3.4, 1.4, 0.3],
-          &[5.0, 3.4, 1.5, 0.2],
-          &[4.4, 2.9, 1.4, 0.2],
-          &[4.9, 3.1, 1.5, 0.1],
-          &[7.0, 3.2, 4.7, 1.4],
-          &[6.4, 3.2, 4.5, 1.5],
-          &[6.9, 3.1, 4.9, 1.5],
-          &[5.5, 2.3, 4.0, 1.3],
-          &[6.5, 2.8, 4.6, 1.5],
-          &[5.7, 2.8, 4.5, 1.3],
-          &[6.3, 3.3, 4.7, 1.6],
-          &[4.9, 2.4, 3.3, 1.0],
-          &[6.6, 2.9, 4.6, 1.3],
-          &[5.2, 2.7, 3.9, 1.4],
-      ]);
-      let y = vec![
-          0., 0., 0., 0., 0., 0., 0., 0., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1.,
-      ];
-
-      let cv = KFold {
-          n_splits: 5,
-          ..KFold::default()
-      };
-
-      let parameters = LogisticRegressionSearchParameters {
-          alpha: vec![0., 1.],
-          ..Default::default()
-      };
-
-      let results = grid_search(
-          LogisticRegression::fit,
-          &x,
-          &y,
-          parameters.into_iter(),
-          cv,
-          &accuracy,
-      )
-      .unwrap();
-
-      assert!([0., 1.].contains(&results.parameters.alpha));
-  }
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::linear::logistic_regression::{
+        LogisticRegression, LogisticRegressionSearchParameters,
+    };
+    use crate::metrics::{accuracy, scorer};
+    use crate::model_selection::{cross_validate, KFold};
+    use crate::tree::decision_tree_classifier::{
+        DecisionTreeClassifier, DecisionTreeClassifierParameters,
+    };
+
+    fn iris() -> (DenseMatrix<f64>, Vec<f64>) {
+        let x = DenseMatrix::from_2d_array(&[
+            &[5.1, 3.5, 1.4, 0.2],
+            &[4.9, 3.0, 1.4, 0.2],
+            &[4.7, 3.2, 1.3, 0.2],
+            &[4.6, 3.1, 1.5, 0.2],
+            &[5.0, 3.6, 1.4, 0.2],
+            &[5.4, 3.9, 1.7, 0.4],
+            &[4.6, 3.4, 1.4, 0.3],
+            &[5.0, 3.4, 1.5, 0.2],
+            &[4.4, 2.9, 1.4, 0.2],
+            &[4.9, 3.1, 1.5, 0.1],
+            &[7.0, 3.2, 4.7, 1.4],
+            &[6.4, 3.2, 4.5, 1.5],
+            &[6.9, 3.1, 4.9, 1.5],
+            &[5.5, 2.3, 4.0, 1.3],
+            &[6.5, 2.8, 4.6, 1.5],
+            &[5.7, 2.8, 4.5, 1.3],
+            &[6.3, 3.3, 4.7, 1.6],
+            &[4.9, 2.4, 3.3, 1.0],
+            &[6.6, 2.9, 4.6, 1.3],
+            &[5.2, 2.7, 3.9, 1.4],
+        ]);
+        let y = vec![
+            0., 0., 0., 0., 0., 0., 0., 0., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1.,
+        ];
+        (x, y)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn test_grid_search() {
+        let (x, y) = iris();
+
+        let cv = KFold {
+            n_splits: 5,
+            ..KFold::default()
+        };
+
+        let parameters = LogisticRegressionSearchParameters {
+            alpha: vec![0., 1.],
+            ..Default::default()
+        };
+
+        let results = grid_search(
+            LogisticRegression::fit,
+            &x,
+            &y,
+            parameters.into_iter(),
+            cv,
+            accuracy,
+        )
+        .unwrap();
+
+        assert!([0., 1.].contains(&results.parameters.alpha));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn parameter_grid_combinations() {
+        let grid = ParameterGrid::new(DecisionTreeClassifierParameters::default())
+            .with_values(&[1, 2, 3], |p, depth| p.with_max_depth(depth))
+            .with_values(&[1, 2], |p, leaf| p.with_min_samples_leaf(leaf));
+
+        assert_eq!(6, grid.len());
+
+        let combinations: Vec<(Option<u16>, usize)> = grid
+            .into_iter()
+            .map(|p| (p.max_depth, p.min_samples_leaf))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (Some(1), 1),
+                (Some(1), 2),
+                (Some(2), 1),
+                (Some(2), 2),
+                (Some(3), 1),
+                (Some(3), 2)
+            ],
+            combinations
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn grid_search_cv_refits_best_model() {
+        let (x, y) = iris();
+
+        let grid = ParameterGrid::new(DecisionTreeClassifierParameters::default())
+            .with_values(&[1, 3], |p, depth| p.with_max_depth(depth));
+
+        let search = GridSearchCV::fit(
+            DecisionTreeClassifier::fit,
+            &x,
+            &y,
+            grid,
+            &KFold::default().with_n_splits(4).with_seed(Some(0)),
+            accuracy,
+        )
+        .unwrap();
+
+        assert_eq!(2, search.results().len());
+        for result in search.results() {
+            assert_eq!(4, result.cross_validation_result.test_score.len());
+            assert!(result.cross_validation_result.mean_test_score() <= search.best_score());
+        }
+        assert_eq!(
+            search.best_parameters().max_depth,
+            search.results()[search.best_index()].parameters.max_depth
+        );
+
+        let y_hat = search.predict(&x).unwrap();
+        let expected = DecisionTreeClassifier::fit(&x, &y, search.best_parameters().clone())
+            .unwrap()
+            .predict(&x)
+            .unwrap();
+        assert_eq!(expected, y_hat);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn grid_search_cv_nested_cross_validation() {
+        let (x, y) = iris();
+
+        let grid = ParameterGrid::new(DecisionTreeClassifierParameters::default())
+            .with_values(&[1, 3], |p, depth| p.with_max_depth(depth));
+        let parameters = GridSearchCVParameters::new(
+            grid,
+            KFold::default().with_n_splits(3).with_seed(Some(0)),
+            accuracy,
+        );
+
+        let search: GridSearchCV<f64, _, DecisionTreeClassifier<f64>> =
+            SupervisedEstimator::fit(&x, &y, parameters.clone()).unwrap();
+        assert_eq!(2, search.results().len());
+
+        let results = cross_validate(
+            |x: &DenseMatrix<f64>, y: &Vec<f64>, p| {
+                let search: GridSearchCV<f64, _, DecisionTreeClassifier<f64>> =
+                    SupervisedEstimator::fit(x, y, p)?;
+                Ok(search)
+            },
+            &x,
+            &y,
+            &parameters,
+            &KFold::default().with_n_splits(4).with_seed(Some(1)),
+            accuracy,
+        )
+        .unwrap();
+
+        assert!(results.mean_test_score() > 0.7);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn grid_search_cv_minimizes_error_scorer() {
+        let (x, y) = iris();
+
+        let grid = ParameterGrid::new(DecisionTreeClassifierParameters::default())
+            .with_values(&[1, 2, 3], |p, depth| p.with_max_depth(depth));
+
+        let search = GridSearchCV::fit(
+            DecisionTreeClassifier::fit,
+            &x,
+            &y,
+            grid,
+            &KFold::default().with_n_splits(4).with_seed(Some(0)),
+            scorer::mean_squared_error(),
+        )
+        .unwrap();
+
+        for result in search.results() {
+            assert!(result.cross_validation_result.mean_test_score() >= search.best_score());
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn grid_search_cv_empty_grid() {
+        let (x, y) = iris();
+
+        let search = GridSearchCV::fit(
+            DecisionTreeClassifier::fit,
+            &x,
+            &y,
+            Vec::<DecisionTreeClassifierParameters>::new(),
+            &KFold::default(),
+            accuracy,
+        );
+
+        assert!(search.is_err());
+    }
 }
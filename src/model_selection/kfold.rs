@@ -6,12 +6,14 @@ use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
 use crate::model_selection::BaseKFold;
 use crate::rand::get_rng_impl;
+use alloc::vec::Vec;
 use rand::seq::SliceRandom;
 
 /// K-Folds cross-validator
+#[derive(Clone, Debug)]
 pub struct KFold {
     /// Number of folds. Must be at least 2.
-    pub n_splits: usize, // cannot exceed std::usize::MAX
+    pub n_splits: usize, // cannot exceed core::usize::MAX
     /// Whether to shuffle the data before splitting into batches
     pub shuffle: bool,
     /// When shuffle is True, seed affects the ordering of the indices.
@@ -101,6 +103,28 @@ pub struct KFoldIter {
     test_indices: Vec<Vec<bool>>,
 }
 
+impl KFoldIter {
+    /// Builds an iterator from the test indices of every fold, training indices are the rest of the samples.
+    pub(crate) fn from_test_indices(n_samples: usize, test_indices: Vec<Vec<usize>>) -> KFoldIter {
+        let mut test_masks: Vec<Vec<bool>> = test_indices
+            .into_iter()
+            .map(|test_index| {
+                let mut test_mask = vec![false; n_samples];
+                for i in test_index {
+                    test_mask[i] = true;
+                }
+                test_mask
+            })
+            .collect();
+        test_masks.reverse();
+
+        KFoldIter {
+            indices: (0..n_samples).collect(),
+            test_indices: test_masks,
+        }
+    }
+}
+
 impl Iterator for KFoldIter {
     type Item = (Vec<usize>, Vec<usize>);
 
@@ -47,12 +47,62 @@
 //!             x_train.shape(), y_train.len(), x_test.shape(), y_test.len());
 //! ```
 //!
+//! With imbalanced classes [stratified_train_test_split](./fn.stratified_train_test_split.html) and [StratifiedKFold](./struct.StratifiedKFold.html)
+//! keep the proportion of every class the same in all subsets.
+//!
 //! When we partition the available data into two disjoint sets, we drastically reduce the number of samples that can be used for training.
 //!
 //! One way to solve this problem is to use k-fold cross-validation. With k-fold validation, the dataset is split into k disjoint sets.
 //! A model is trained using k - 1 of the folds, and the resulting model is validated on the remaining portion of the data.
 //!
-//! The simplest way to run cross-validation is to use the [cross_val_score](./fn.cross_validate.html) helper function on your estimator and the dataset.
+//! The simplest way to run cross-validation is to use the [cross_val_score](./fn.cross_val_score.html) helper function on your estimator and the dataset.
+//! It returns the test score of every fold:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::model_selection::{KFold, cross_val_score};
+//! use smartcore::metrics::accuracy;
+//! use smartcore::linear::logistic_regression::LogisticRegression;
+//!
+//! //Iris data
+//! let x = DenseMatrix::from_2d_array(&[
+//!           &[5.1, 3.5, 1.4, 0.2],
+//!           &[4.9, 3.0, 1.4, 0.2],
+//!           &[4.7, 3.2, 1.3, 0.2],
+//!           &[4.6, 3.1, 1.5, 0.2],
+//!           &[5.0, 3.6, 1.4, 0.2],
+//!           &[5.4, 3.9, 1.7, 0.4],
+//!           &[4.6, 3.4, 1.4, 0.3],
+//!           &[5.0, 3.4, 1.5, 0.2],
+//!           &[4.4, 2.9, 1.4, 0.2],
+//!           &[4.9, 3.1, 1.5, 0.1],
+//!           &[7.0, 3.2, 4.7, 1.4],
+//!           &[6.4, 3.2, 4.5, 1.5],
+//!           &[6.9, 3.1, 4.9, 1.5],
+//!           &[5.5, 2.3, 4.0, 1.3],
+//!           &[6.5, 2.8, 4.6, 1.5],
+//!           &[5.7, 2.8, 4.5, 1.3],
+//!           &[6.3, 3.3, 4.7, 1.6],
+//!           &[4.9, 2.4, 3.3, 1.0],
+//!           &[6.6, 2.9, 4.6, 1.3],
+//!           &[5.2, 2.7, 3.9, 1.4],
+//!           ]);
+//! let y: Vec<f64> = vec![
+//!           0., 0., 0., 0., 0., 0., 0., 0., 0., 0., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1.,
+//! ];
+//!
+//! let cv = KFold::default().with_n_splits(3);
+//!
+//! let scores = cross_val_score(
+//!     |x, y| LogisticRegression::fit(x, y, Default::default()), //estimator
+//!     &x, &y,                                                   //data
+//!     &cv,                                                      //cross validation split
+//!     &accuracy).unwrap();                                      //metric
+//!
+//! println!("Test accuracy on each fold: {:?}", scores);
+//! ```
+//!
+//! When you also need training scores use [cross_validate](./fn.cross_validate.html), which takes estimator's hyperparameters separately:
 //!
 //! ```
 //! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
@@ -99,6 +149,18 @@
 //!     results.mean_test_score(), results.mean_train_score());
 //! ```
 //!
+//! Shuffled k folds assume that samples are independent of each other. When they are not, use a splitter that respects the structure of the data:
+//! [GroupKFold](./struct.GroupKFold.html) never puts samples of the same group into both training and test sets,
+//! and [TimeSeriesSplit](./struct.TimeSeriesSplit.html) always tests a model on samples that come after the ones it was trained on.
+//!
+//! To choose hyperparameters of an estimator, [GridSearchCV](./struct.GridSearchCV.html) cross-validates every combination from a grid
+//! and refits the best one. [validation_curve](./fn.validation_curve.html) shows how training and test scores change with a single hyperparameter.
+//!
+//! To evaluate several metrics at once and to measure how long fitting takes, use [cross_validate_multimetric](./fn.cross_validate_multimetric.html).
+//! When samples have different weights, [cross_validate_weighted](./fn.cross_validate_weighted.html) fits estimators and computes scores with these weights.
+//! Every function that takes a metric also accepts a [scorer](../metrics/scorer/index.html), which can score class probabilities, e.g. ROC AUC,
+//! and tells whether smaller values are better, e.g. for mean squared error.
+//!
 //! The function [cross_val_predict](./fn.cross_val_predict.html) has a similar interface to `cross_val_score`,
 //! but instead of test error it calculates predictions for all samples in the test set.
 
@@ -107,12 +169,29 @@ use crate::error::Failed;
 use crate::linalg::BaseVector;
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
+use crate::math::vector::RealNumberVector;
+use crate::metrics::scorer::Scorer;
+use crate::parallel::{map_slice, MaybeSync};
 use crate::rand::get_rng_impl;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use rand::seq::SliceRandom;
 
+pub(crate) mod group_kfold;
+pub(crate) mod hyper_tuning;
 pub(crate) mod kfold;
+pub(crate) mod stratified_kfold;
+pub(crate) mod time_series_split;
+pub(crate) mod validation_curve;
 
+pub use group_kfold::GroupKFold;
+pub use hyper_tuning::{
+    grid_search, GridSearchCV, GridSearchCVParameters, GridSearchResult, ParameterGrid,
+};
 pub use kfold::{KFold, KFoldIter};
+pub use stratified_kfold::StratifiedKFold;
+pub use time_series_split::{TimeSeriesSplit, TimeSeriesSplitIter};
+pub use validation_curve::{validation_curve, ValidationCurveResult};
 
 /// An interface for the K-Folds cross-validator
 pub trait BaseKFold {
@@ -138,17 +217,31 @@ pub fn train_test_split<T: RealNumber, M: Matrix<T>>(
     shuffle: bool,
     seed: Option<u64>,
 ) -> (M, M, M::RowVector, M::RowVector) {
+    try_train_test_split(x, y, test_size, shuffle, seed).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Same as [train_test_split](./fn.train_test_split.html), but returns an error instead of panicking
+/// when sizes of `x` and `y` don't match or `test_size` leaves no samples for the test set.
+pub fn try_train_test_split<T: RealNumber, M: Matrix<T>>(
+    x: &M,
+    y: &M::RowVector,
+    test_size: f32,
+    shuffle: bool,
+    seed: Option<u64>,
+) -> Result<(M, M, M::RowVector, M::RowVector), Failed> {
     if x.shape().0 != y.len() {
-        panic!(
+        return Err(Failed::shape_mismatch(&format!(
             "x and y should have the same number of samples. |x|: {}, |y|: {}",
             x.shape().0,
             y.len()
-        );
+        )));
     }
     let mut rng = get_rng_impl(seed);
 
     if test_size <= 0. || test_size > 1.0 {
-        panic!("test_size should be between 0 and 1");
+        return Err(Failed::invalid_parameters(
+            "test_size should be between 0 and 1",
+        ));
     }
 
     let n = y.len();
@@ -156,7 +249,10 @@ pub fn train_test_split<T: RealNumber, M: Matrix<T>>(
     let n_test = ((n as f32) * test_size) as usize;
 
     if n_test < 1 {
-        panic!("number of sample is too small {}", n);
+        return Err(Failed::invalid_parameters(&format!(
+            "number of sample is too small {}",
+            n
+        )));
     }
 
     let mut indices: Vec<usize> = (0..n).collect();
@@ -170,7 +266,109 @@ pub fn train_test_split<T: RealNumber, M: Matrix<T>>(
     let y_train = y.take(&indices[n_test..n]);
     let y_test = y.take(&indices[0..n_test]);
 
-    (x_train, x_test, y_train, y_test)
+    Ok((x_train, x_test, y_train, y_test))
+}
+
+/// Splits data into 2 disjoint datasets, keeping the proportion of every class of `y` in both of them.
+/// Use it instead of [train_test_split](./fn.train_test_split.html) with imbalanced classes, where a random split can leave rare classes out of the test set.
+/// * `x` - features, matrix of size _NxM_ where _N_ is number of samples and _M_ is number of attributes.
+/// * `y` - class labels, should be of size _N_
+/// * `test_size`, (0, 1] - the proportion of the dataset to include in the test split.
+/// * `shuffle`, - whether or not to shuffle samples of each class before splitting
+/// * `seed` - Controls the shuffling applied to the data before applying the split. Pass an int for reproducible output across multiple function calls
+pub fn stratified_train_test_split<T: RealNumber, M: Matrix<T>>(
+    x: &M,
+    y: &M::RowVector,
+    test_size: f32,
+    shuffle: bool,
+    seed: Option<u64>,
+) -> (M, M, M::RowVector, M::RowVector) {
+    try_stratified_train_test_split(x, y, test_size, shuffle, seed)
+        .unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Same as [stratified_train_test_split](./fn.stratified_train_test_split.html), but returns an error instead of panicking
+/// when sizes of `x` and `y` don't match or `test_size` leaves no samples for the test set.
+pub fn try_stratified_train_test_split<T: RealNumber, M: Matrix<T>>(
+    x: &M,
+    y: &M::RowVector,
+    test_size: f32,
+    shuffle: bool,
+    seed: Option<u64>,
+) -> Result<(M, M, M::RowVector, M::RowVector), Failed> {
+    if x.shape().0 != y.len() {
+        return Err(Failed::shape_mismatch(&format!(
+            "x and y should have the same number of samples. |x|: {}, |y|: {}",
+            x.shape().0,
+            y.len()
+        )));
+    }
+    let mut rng = get_rng_impl(seed);
+
+    if test_size <= 0. || test_size > 1.0 {
+        return Err(Failed::invalid_parameters(
+            "test_size should be between 0 and 1",
+        ));
+    }
+
+    let n = y.len();
+
+    let n_test = ((n as f32) * test_size) as usize;
+
+    if n_test < 1 {
+        return Err(Failed::invalid_parameters(&format!(
+            "number of sample is too small {}",
+            n
+        )));
+    }
+
+    let (classes, labels) = y.unique_with_indices();
+    let mut class_indices: Vec<Vec<usize>> = vec![Vec::new(); classes.len()];
+    for (i, &label) in labels.iter().enumerate() {
+        class_indices[label].push(i);
+    }
+
+    // every class gets the floor of its share of the test set, the samples that are left
+    // go to classes with the largest remainders
+    let mut class_n_test: Vec<usize> = class_indices
+        .iter()
+        .map(|indices| indices.len() * n_test / n)
+        .collect();
+    let mut remainders: Vec<(usize, usize)> = class_indices
+        .iter()
+        .enumerate()
+        .map(|(c, indices)| (indices.len() * n_test % n, c))
+        .collect();
+    remainders.sort_by(|a, b| b.cmp(a));
+    let n_left = n_test - class_n_test.iter().sum::<usize>();
+    for &(_, c) in remainders.iter().take(n_left) {
+        class_n_test[c] += 1;
+    }
+
+    let mut train_indices: Vec<usize> = Vec::with_capacity(n - n_test);
+    let mut test_indices: Vec<usize> = Vec::with_capacity(n_test);
+    for (mut indices, c_n_test) in class_indices.into_iter().zip(class_n_test) {
+        if shuffle {
+            indices.shuffle(&mut rng);
+        }
+        test_indices.extend_from_slice(&indices[0..c_n_test]);
+        train_indices.extend_from_slice(&indices[c_n_test..]);
+    }
+
+    if shuffle {
+        train_indices.shuffle(&mut rng);
+        test_indices.shuffle(&mut rng);
+    } else {
+        train_indices.sort_unstable();
+        test_indices.sort_unstable();
+    }
+
+    let x_train = x.take(&train_indices, 0);
+    let x_test = x.take(&test_indices, 0);
+    let y_train = y.take(&train_indices);
+    let y_test = y.take(&test_indices);
+
+    Ok((x_train, x_test, y_train, y_test))
 }
 
 /// Cross validation results.
@@ -191,6 +389,14 @@ impl<T: RealNumber> CrossValidationResult<T> {
     pub fn mean_train_score(&self) -> T {
         self.train_score.sum() / T::from_usize(self.train_score.len()).unwrap()
     }
+    /// Standard deviation of test scores
+    pub fn std_test_score(&self) -> T {
+        self.test_score.std()
+    }
+    /// Standard deviation of training scores
+    pub fn std_train_score(&self) -> T {
+        self.train_score.std()
+    }
 }
 
 /// Evaluate an estimator by cross-validation using given metric.
@@ -199,7 +405,7 @@ impl<T: RealNumber> CrossValidationResult<T> {
 /// * `y` - target values, should be of size _N_
 /// * `parameters` - parameters of selected estimator. Use `Default::default()` for default parameters.
 /// * `cv` - the cross-validation splitting strategy, should be an instance of [`BaseKFold`](./trait.BaseKFold.html)
-/// * `score` - a metric to use for evaluation, see [metrics](../metrics/index.html)
+/// * `score` - a metric to use for evaluation, see [metrics](../metrics/index.html), or a [scorer](../metrics/scorer/index.html)
 pub fn cross_validate<T, M, H, E, K, F, S>(
     fit_estimator: F,
     x: &M,
@@ -211,34 +417,290 @@ pub fn cross_validate<T, M, H, E, K, F, S>(
 where
     T: RealNumber,
     M: Matrix<T>,
-    H: Clone,
-    E: Predictor<M, M::RowVector>,
+    H: Clone + MaybeSync,
     K: BaseKFold,
-    F: Fn(&M, &M::RowVector, H) -> Result<E, Failed>,
-    S: Fn(&M::RowVector, &M::RowVector) -> T,
+    F: Fn(&M, &M::RowVector, H) -> Result<E, Failed> + MaybeSync,
+    S: Scorer<T, M, E> + MaybeSync,
+{
+    cross_validate_with_scorer(&fit_estimator, x, y, parameters, cv, &score)
+}
+
+pub(crate) fn cross_validate_with_scorer<T, M, H, E, K, F, S>(
+    fit_estimator: &F,
+    x: &M,
+    y: &M::RowVector,
+    parameters: &H,
+    cv: &K,
+    score: &S,
+) -> Result<CrossValidationResult<T>, Failed>
+where
+    T: RealNumber,
+    M: Matrix<T>,
+    H: Clone + MaybeSync,
+    K: BaseKFold,
+    F: Fn(&M, &M::RowVector, H) -> Result<E, Failed> + MaybeSync,
+    S: Scorer<T, M, E> + MaybeSync,
 {
     let k = cv.n_splits();
     let mut test_score = Vec::with_capacity(k);
     let mut train_score = Vec::with_capacity(k);
 
+    let splits: Vec<(Vec<usize>, Vec<usize>)> = cv.split(x).collect();
+    let fold_scores = map_slice(&splits, |(train_idx, test_idx)| {
+        let train_x = x.take(train_idx, 0);
+        let train_y = y.take(train_idx);
+        let test_x = x.take(test_idx, 0);
+        let test_y = y.take(test_idx);
+
+        let estimator = fit_estimator(&train_x, &train_y, parameters.clone())?;
+
+        Ok((
+            score.score(&estimator, &train_x, &train_y)?,
+            score.score(&estimator, &test_x, &test_y)?,
+        ))
+    });
+
+    for fold_score in fold_scores {
+        let (train, test): (T, T) = fold_score?;
+        train_score.push(train);
+        test_score.push(test);
+    }
+
+    Ok(CrossValidationResult {
+        test_score,
+        train_score,
+    })
+}
+
+/// Evaluate an estimator by cross-validation on samples with different weights. Estimators are fitted with the weights of training samples
+/// and scored with [`Scorer::score_weighted`](../metrics/scorer/trait.Scorer.html#method.score_weighted).
+/// * `fit_estimator` - a `fit_with_weights` function of an estimator, see [`SupervisedEstimatorWeighted`](../api/trait.SupervisedEstimatorWeighted.html)
+/// * `x` - features, matrix of size _NxM_ where _N_ is number of samples and _M_ is number of attributes.
+/// * `y` - target values, should be of size _N_
+/// * `sample_weight` - non-negative weight of every sample, should be of size _N_
+/// * `parameters` - parameters of selected estimator. Use `Default::default()` for default parameters.
+/// * `cv` - the cross-validation splitting strategy, should be an instance of [`BaseKFold`](./trait.BaseKFold.html)
+/// * `score` - a [scorer](../metrics/scorer/index.html) that supports sample weights
+pub fn cross_validate_weighted<T, M, H, E, K, F, S>(
+    fit_estimator: F,
+    x: &M,
+    y: &M::RowVector,
+    sample_weight: &M::RowVector,
+    parameters: &H,
+    cv: &K,
+    score: S,
+) -> Result<CrossValidationResult<T>, Failed>
+where
+    T: RealNumber,
+    M: Matrix<T>,
+    H: Clone + MaybeSync,
+    K: BaseKFold,
+    F: Fn(&M, &M::RowVector, &M::RowVector, H) -> Result<E, Failed> + MaybeSync,
+    S: Scorer<T, M, E> + MaybeSync,
+{
+    if sample_weight.len() != y.len() {
+        return Err(Failed::fit(&format!(
+            "sample_weight and y should have the same number of samples. |sample_weight|: {}, |y|: {}",
+            sample_weight.len(),
+            y.len()
+        )));
+    }
+
+    let k = cv.n_splits();
+    let mut test_score = Vec::with_capacity(k);
+    let mut train_score = Vec::with_capacity(k);
+
+    let splits: Vec<(Vec<usize>, Vec<usize>)> = cv.split(x).collect();
+    let fold_scores = map_slice(&splits, |(train_idx, test_idx)| {
+        let train_x = x.take(train_idx, 0);
+        let train_y = y.take(train_idx);
+        let train_weight = sample_weight.take(train_idx);
+        let test_x = x.take(test_idx, 0);
+        let test_y = y.take(test_idx);
+        let test_weight = sample_weight.take(test_idx);
+
+        let estimator = fit_estimator(&train_x, &train_y, &train_weight, parameters.clone())?;
+
+        Ok((
+            score.score_weighted(&estimator, &train_x, &train_y, &train_weight.to_vec())?,
+            score.score_weighted(&estimator, &test_x, &test_y, &test_weight.to_vec())?,
+        ))
+    });
+
+    for fold_score in fold_scores {
+        let (train, test): (T, T) = fold_score?;
+        train_score.push(train);
+        test_score.push(test);
+    }
+
+    Ok(CrossValidationResult {
+        test_score,
+        train_score,
+    })
+}
+
+/// Evaluate an estimator by cross-validation and return the test score of every split.
+/// * `fit_estimator` - a function that fits an estimator to the training set, e.g. `|x, y| KNNRegressor::fit(x, y, Default::default())`
+/// * `x` - features, matrix of size _NxM_ where _N_ is number of samples and _M_ is number of attributes.
+/// * `y` - target values, should be of size _N_
+/// * `cv` - the cross-validation splitting strategy, should be an instance of [`BaseKFold`](./trait.BaseKFold.html)
+/// * `score` - a metric to use for evaluation, see [metrics](../metrics/index.html), or a [scorer](../metrics/scorer/index.html)
+pub fn cross_val_score<T, M, E, K, F, S>(
+    fit_estimator: F,
+    x: &M,
+    y: &M::RowVector,
+    cv: &K,
+    score: S,
+) -> Result<Vec<T>, Failed>
+where
+    T: RealNumber,
+    M: Matrix<T>,
+    K: BaseKFold,
+    F: Fn(&M, &M::RowVector) -> Result<E, Failed> + MaybeSync,
+    S: Scorer<T, M, E> + MaybeSync,
+{
+    if x.shape().0 != y.len() {
+        return Err(Failed::fit(&format!(
+            "x and y should have the same number of samples. |x|: {}, |y|: {}",
+            x.shape().0,
+            y.len()
+        )));
+    }
+
+    let splits: Vec<(Vec<usize>, Vec<usize>)> = cv.split(x).collect();
+
+    map_slice(&splits, |(train_idx, test_idx)| {
+        let train_x = x.take(train_idx, 0);
+        let train_y = y.take(train_idx);
+        let test_x = x.take(test_idx, 0);
+        let test_y = y.take(test_idx);
+
+        let estimator = fit_estimator(&train_x, &train_y)?;
+
+        score.score(&estimator, &test_x, &test_y)
+    })
+    .into_iter()
+    .collect()
+}
+
+/// Multi-metric cross validation results.
+#[derive(Clone, Debug)]
+pub struct CrossValidationReport<T: RealNumber, E> {
+    /// Time in seconds spent fitting the estimator on each cv split
+    pub fit_time: Vec<f64>,
+    /// Time in seconds spent scoring the estimator on each cv split, with all metrics
+    pub score_time: Vec<f64>,
+    /// Training and test scores on each cv split for every metric, in the order the metrics were given
+    pub scores: Vec<(String, CrossValidationResult<T>)>,
+    /// Estimators fitted on each cv split, if requested
+    pub estimators: Option<Vec<E>>,
+}
+
+impl<T: RealNumber, E> CrossValidationReport<T, E> {
+    /// Training and test scores of a metric with the given name
+    pub fn score(&self, name: &str) -> Option<&CrossValidationResult<T>> {
+        self.scores
+            .iter()
+            .find(|(metric, _)| metric == name)
+            .map(|(_, result)| result)
+    }
+}
+
+/// Evaluate an estimator by cross-validation using several metrics at once, measuring how long fitting and scoring take.
+/// * `fit_estimator` - a `fit` function of an estimator
+/// * `x` - features, matrix of size _NxM_ where _N_ is number of samples and _M_ is number of attributes.
+/// * `y` - target values, should be of size _N_
+/// * `parameters` - parameters of selected estimator. Use `Default::default()` for default parameters.
+/// * `cv` - the cross-validation splitting strategy, should be an instance of [`BaseKFold`](./trait.BaseKFold.html)
+/// * `scorers` - named [scorers](../metrics/scorer/index.html) to use for evaluation
+/// * `return_estimators` - whether to keep estimators fitted on each cv split
+pub fn cross_validate_multimetric<T, M, H, E, K, F>(
+    fit_estimator: F,
+    x: &M,
+    y: &M::RowVector,
+    parameters: &H,
+    cv: &K,
+    scorers: &[(&str, &dyn Scorer<T, M, E>)],
+    return_estimators: bool,
+) -> Result<CrossValidationReport<T, E>, Failed>
+where
+    T: RealNumber,
+    M: Matrix<T>,
+    H: Clone,
+    K: BaseKFold,
+    F: Fn(&M, &M::RowVector, H) -> Result<E, Failed>,
+{
+    let k = cv.n_splits();
+    let mut fit_time = Vec::with_capacity(k);
+    let mut score_time = Vec::with_capacity(k);
+    let mut scores: Vec<(String, CrossValidationResult<T>)> = scorers
+        .iter()
+        .map(|(name, _)| {
+            (
+                name.to_string(),
+                CrossValidationResult {
+                    test_score: Vec::with_capacity(k),
+                    train_score: Vec::with_capacity(k),
+                },
+            )
+        })
+        .collect();
+    let mut estimators = Vec::new();
+
     for (train_idx, test_idx) in cv.split(x) {
         let train_x = x.take(&train_idx, 0);
         let train_y = y.take(&train_idx);
         let test_x = x.take(&test_idx, 0);
         let test_y = y.take(&test_idx);
 
-        let estimator = fit_estimator(&train_x, &train_y, parameters.clone())?;
+        let (estimator, elapsed) = timed(|| fit_estimator(&train_x, &train_y, parameters.clone()));
+        let estimator = estimator?;
+        fit_time.push(elapsed);
+
+        let (result, elapsed) = timed(|| -> Result<(), Failed> {
+            for ((_, scorer), (_, result)) in scorers.iter().zip(scores.iter_mut()) {
+                result
+                    .train_score
+                    .push(scorer.score(&estimator, &train_x, &train_y)?);
+                result
+                    .test_score
+                    .push(scorer.score(&estimator, &test_x, &test_y)?);
+            }
+            Ok(())
+        });
+        result?;
+        score_time.push(elapsed);
 
-        train_score.push(score(&train_y, &estimator.predict(&train_x)?));
-        test_score.push(score(&test_y, &estimator.predict(&test_x)?));
+        if return_estimators {
+            estimators.push(estimator);
+        }
     }
 
-    Ok(CrossValidationResult {
-        test_score,
-        train_score,
+    Ok(CrossValidationReport {
+        fit_time,
+        score_time,
+        scores,
+        estimators: if return_estimators {
+            Some(estimators)
+        } else {
+            None
+        },
     })
 }
 
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+fn timed<R, F: FnOnce() -> R>(f: F) -> (R, f64) {
+    let start = std::time::Instant::now();
+    let result = f();
+    (result, start.elapsed().as_secs_f64())
+}
+
+// std::time::Instant is not available in the browser and without std
+#[cfg(any(not(feature = "std"), target_arch = "wasm32"))]
+fn timed<R, F: FnOnce() -> R>(f: F) -> (R, f64) {
+    (f(), 0.)
+}
+
 /// Generate cross-validated estimates for each input data point.
 /// The data is split according to the cv parameter. Each sample belongs to exactly one test set, and its prediction is computed with an estimator fitted on the corresponding training set.
 /// * `fit_estimator` - a `fit` function of an estimator
@@ -283,10 +745,13 @@ where
 mod tests {
 
     use super::*;
+    use crate::error::FailedError;
     use crate::linalg::naive::dense_matrix::*;
-    use crate::metrics::{accuracy, mean_absolute_error};
+    use crate::linear::linear_regression::LinearRegression;
+    use crate::metrics::{accuracy, mean_absolute_error, scorer};
     use crate::model_selection::kfold::KFold;
     use crate::neighbors::knn_regressor::KNNRegressor;
+    use crate::tree::decision_tree_classifier::DecisionTreeClassifier;
 
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     #[test]
@@ -309,6 +774,51 @@ mod tests {
         assert_eq!(x_test.shape().0, y_test.len());
     }
 
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn run_stratified_train_test_split() {
+        let n = 100;
+        let x: DenseMatrix<f64> = DenseMatrix::rand(n, 3);
+        let y: Vec<f64> = (0..n).map(|i| if i % 10 == 0 { 1. } else { 0. }).collect();
+
+        let (x_train, x_test, y_train, y_test) =
+            stratified_train_test_split(&x, &y, 0.25, true, Some(7));
+
+        assert_eq!(75, x_train.shape().0);
+        assert_eq!(25, x_test.shape().0);
+        assert_eq!(x_train.shape().0, y_train.len());
+        assert_eq!(x_test.shape().0, y_test.len());
+        assert_eq!(3, y_test.iter().filter(|&&v| v == 1.).count());
+        assert_eq!(7, y_train.iter().filter(|&&v| v == 1.).count());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn try_train_test_split_invalid_input() {
+        let x: DenseMatrix<f64> = DenseMatrix::rand(10, 3);
+        let y: Vec<f64> = vec![0.; 10];
+
+        assert_eq!(
+            FailedError::ShapeMismatch,
+            try_train_test_split(&x, &vec![0.; 9], 0.2, true, None)
+                .unwrap_err()
+                .error()
+        );
+        assert_eq!(
+            FailedError::InvalidParameters,
+            try_train_test_split(&x, &y, 1.5, true, None)
+                .unwrap_err()
+                .error()
+        );
+        assert_eq!(
+            FailedError::InvalidParameters,
+            try_stratified_train_test_split(&x, &y, 0.05, true, None)
+                .unwrap_err()
+                .error()
+        );
+        assert!(try_train_test_split(&x, &y, 0.2, true, None).is_ok());
+    }
+
     #[derive(Clone)]
     struct NoParameters {}
 
@@ -424,6 +934,208 @@ mod tests {
         assert!(results.mean_train_score() < results.mean_test_score());
     }
 
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn test_cross_validate_weighted() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 1.],
+            &[2., 1.],
+            &[3., 2.],
+            &[4., 3.],
+            &[5., 5.],
+            &[6., 8.],
+            &[7., 13.],
+            &[8., 21.],
+        ]);
+        let y: Vec<f64> = vec![3., 4., 7., 10., 15., 22., 33., 50.];
+        let cv = KFold {
+            n_splits: 2,
+            shuffle: false,
+            ..KFold::default()
+        };
+
+        let uniform = cross_validate_weighted(
+            LinearRegression::fit_with_weights,
+            &x,
+            &y,
+            &vec![1.; 8],
+            &Default::default(),
+            &cv,
+            scorer::mean_squared_error(),
+        )
+        .unwrap();
+        let unweighted = cross_validate(
+            LinearRegression::fit,
+            &x,
+            &y,
+            &Default::default(),
+            &cv,
+            scorer::mean_squared_error(),
+        )
+        .unwrap();
+        for (a, b) in uniform.test_score.iter().zip(unweighted.test_score.iter()) {
+            assert!((a - b).abs() < 1e-8);
+        }
+
+        let sample_weight = vec![1., 2., 1., 0., 3., 1., 1., 2.];
+        let weighted = cross_validate_weighted(
+            LinearRegression::fit_with_weights,
+            &x,
+            &y,
+            &sample_weight,
+            &Default::default(),
+            &cv,
+            scorer::mean_squared_error(),
+        )
+        .unwrap();
+        let (train_idx, test_idx) = cv.split(&x).next().unwrap();
+        let estimator = LinearRegression::fit_with_weights(
+            &x.take(&train_idx, 0),
+            &y.take(&train_idx),
+            &sample_weight.take(&train_idx),
+            Default::default(),
+        )
+        .unwrap();
+        let expected = crate::metrics::mean_squared_error::MeanSquareError {}
+            .get_score_weighted(
+                &y.take(&test_idx),
+                &estimator.predict(&x.take(&test_idx, 0)).unwrap(),
+                &sample_weight.take(&test_idx),
+            )
+            .unwrap();
+        assert!((weighted.test_score[0] - expected).abs() < 1e-8);
+
+        let error = cross_validate_weighted(
+            LinearRegression::fit_with_weights,
+            &x,
+            &y,
+            &sample_weight,
+            &Default::default(),
+            &cv,
+            |y: &Vec<f64>, y_hat: &Vec<f64>| mean_absolute_error(y, y_hat),
+        )
+        .unwrap_err();
+        assert_eq!(error.error(), FailedError::InvalidParameters);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn test_cross_val_score_knn() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[234.289, 235.6, 159., 107.608, 1947., 60.323],
+            &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+            &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+            &[284.599, 335.1, 165., 110.929, 1950., 61.187],
+            &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+            &[346.999, 193.2, 359.4, 113.27, 1952., 63.639],
+            &[365.385, 187., 354.7, 115.094, 1953., 64.989],
+            &[363.112, 357.8, 335., 116.219, 1954., 63.761],
+            &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+            &[419.18, 282.2, 285.7, 118.734, 1956., 67.857],
+            &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+            &[444.546, 468.1, 263.7, 121.95, 1958., 66.513],
+            &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+            &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+            &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+            &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+        ]);
+        let y = vec![
+            83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0, 100.0, 101.2, 104.6, 108.4, 110.8, 112.6,
+            114.2, 115.7, 116.9,
+        ];
+
+        let cv = KFold::default().with_n_splits(4).with_seed(Some(0));
+
+        let scores = cross_val_score(
+            |x, y| KNNRegressor::fit(x, y, Default::default()),
+            &x,
+            &y,
+            &cv,
+            mean_absolute_error,
+        )
+        .unwrap();
+
+        let results = cross_validate(
+            KNNRegressor::fit,
+            &x,
+            &y,
+            &Default::default(),
+            &cv,
+            mean_absolute_error,
+        )
+        .unwrap();
+
+        assert_eq!(4, scores.len());
+        assert_eq!(results.test_score, scores);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn test_cross_validate_multimetric() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[5.1, 3.5, 1.4, 0.2],
+            &[4.9, 3.0, 1.4, 0.2],
+            &[4.7, 3.2, 1.3, 0.2],
+            &[4.6, 3.1, 1.5, 0.2],
+            &[5.0, 3.6, 1.4, 0.2],
+            &[5.4, 3.9, 1.7, 0.4],
+            &[4.6, 3.4, 1.4, 0.3],
+            &[5.0, 3.4, 1.5, 0.2],
+            &[4.4, 2.9, 1.4, 0.2],
+            &[4.9, 3.1, 1.5, 0.1],
+            &[7.0, 3.2, 4.7, 1.4],
+            &[6.4, 3.2, 4.5, 1.5],
+            &[6.9, 3.1, 4.9, 1.5],
+            &[5.5, 2.3, 4.0, 1.3],
+            &[6.5, 2.8, 4.6, 1.5],
+            &[5.7, 2.8, 4.5, 1.3],
+            &[6.3, 3.3, 4.7, 1.6],
+            &[4.9, 2.4, 3.3, 1.0],
+            &[6.6, 2.9, 4.6, 1.3],
+            &[5.2, 2.7, 3.9, 1.4],
+        ]);
+        let y: Vec<f64> = vec![
+            0., 0., 0., 0., 0., 0., 0., 0., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1.,
+        ];
+
+        let cv = KFold::default().with_n_splits(4).with_seed(Some(0));
+
+        let report = cross_validate_multimetric(
+            DecisionTreeClassifier::fit,
+            &x,
+            &y,
+            &Default::default(),
+            &cv,
+            &[
+                ("accuracy", &scorer::accuracy()),
+                ("roc_auc", &scorer::roc_auc()),
+            ],
+            true,
+        )
+        .unwrap();
+
+        let results = cross_validate(
+            DecisionTreeClassifier::fit,
+            &x,
+            &y,
+            &Default::default(),
+            &cv,
+            accuracy,
+        )
+        .unwrap();
+
+        assert_eq!(4, report.fit_time.len());
+        assert_eq!(4, report.score_time.len());
+        assert!(report.fit_time.iter().all(|&t| t >= 0.));
+        assert_eq!(
+            results.test_score,
+            report.score("accuracy").unwrap().test_score
+        );
+        assert_eq!(4, report.score("roc_auc").unwrap().test_score.len());
+        assert!(report.score("f1").is_none());
+        assert_eq!(4, report.estimators.unwrap().len());
+    }
+
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     #[test]
     fn test_cross_val_predict_knn() {
@@ -0,0 +1,189 @@
+//! # Stratified KFold
+//!
+//! Defines stratified k-fold cross validator. Every fold keeps approximately the same proportion of samples of each class as the complete set,
+//! which makes the test score of each fold representative of the whole dataset even when some classes are rare.
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::model_selection::{BaseKFold, StratifiedKFold};
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[1., 2.], &[3., 4.], &[5., 6.], &[7., 8.], &[9., 10.], &[11., 12.],
+//! ]);
+//! let y = vec![0., 0., 0., 0., 1., 1.];
+//!
+//! let cv = StratifiedKFold::new(&y).with_n_splits(2);
+//!
+//! for (train, test) in cv.split(&x) {
+//!     // every test fold has two samples of class 0 and one sample of class 1
+//!     println!("train: {:?}, test: {:?}", train, test);
+//! }
+//! ```
+
+use crate::linalg::BaseVector;
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::math::vector::RealNumberVector;
+use crate::model_selection::kfold::KFoldIter;
+use crate::model_selection::BaseKFold;
+use crate::rand::get_rng_impl;
+use alloc::vec::Vec;
+use rand::seq::SliceRandom;
+
+/// Stratified K-Folds cross-validator
+#[derive(Clone, Debug)]
+pub struct StratifiedKFold {
+    /// Number of folds. Must be at least 2.
+    pub n_splits: usize,
+    /// Whether to shuffle samples of each class before splitting into batches
+    pub shuffle: bool,
+    /// When shuffle is True, seed affects the ordering of the indices.
+    pub seed: Option<u64>,
+    labels: Vec<usize>,
+}
+
+impl StratifiedKFold {
+    /// Creates a new splitter that preserves proportions of classes found in `y`.
+    /// * `y` - class labels of the samples that will be split, should be of size _N_
+    pub fn new<T: RealNumber, V: BaseVector<T>>(y: &V) -> StratifiedKFold {
+        let (_, labels) = y.unique_with_indices();
+        StratifiedKFold {
+            n_splits: 3,
+            shuffle: true,
+            seed: None,
+            labels,
+        }
+    }
+
+    /// Number of folds. Must be at least 2.
+    pub fn with_n_splits(mut self, n_splits: usize) -> Self {
+        self.n_splits = n_splits;
+        self
+    }
+
+    /// Whether to shuffle samples of each class before splitting into batches
+    pub fn with_shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+
+    /// When shuffle is True, seed affects the ordering of the indices.
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    fn test_indices(&self) -> Vec<Vec<usize>> {
+        let n_classes = self.labels.iter().max().map_or(0, |&max| max + 1);
+
+        let mut class_indices: Vec<Vec<usize>> = vec![Vec::new(); n_classes];
+        for (i, &label) in self.labels.iter().enumerate() {
+            class_indices[label].push(i);
+        }
+
+        if self.shuffle {
+            let mut rng = get_rng_impl(self.seed);
+            for indices in class_indices.iter_mut() {
+                indices.shuffle(&mut rng);
+            }
+        }
+
+        // samples sorted by class are dealt to folds one by one, so that every fold
+        // gets either floor(n_c / k) or ceil(n_c / k) samples of class c
+        let mut test_indices: Vec<Vec<usize>> = vec![Vec::new(); self.n_splits];
+        for (position, i) in class_indices.into_iter().flatten().enumerate() {
+            test_indices[position % self.n_splits].push(i);
+        }
+
+        for test_index in test_indices.iter_mut() {
+            test_index.sort_unstable();
+        }
+
+        test_indices
+    }
+}
+
+impl BaseKFold for StratifiedKFold {
+    type Output = KFoldIter;
+
+    fn n_splits(&self) -> usize {
+        self.n_splits
+    }
+
+    fn split<T: RealNumber, M: Matrix<T>>(&self, x: &M) -> Self::Output {
+        if self.n_splits < 2 {
+            panic!("Number of splits is too small: {}", self.n_splits);
+        }
+        let n_samples = x.shape().0;
+        if n_samples != self.labels.len() {
+            panic!(
+                "x and y should have the same number of samples. |x|: {}, |y|: {}",
+                n_samples,
+                self.labels.len()
+            );
+        }
+        if self.n_splits > n_samples {
+            panic!(
+                "Number of splits {} is greater than the number of samples {}",
+                self.n_splits, n_samples
+            );
+        }
+
+        KFoldIter::from_test_indices(n_samples, self.test_indices())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn stratified_kfold_preserves_class_proportions() {
+        let y = vec![
+            0., 1., 0., 0., 1., 0., 0., 2., 0., 0., 1., 0., 0., 2., 0., 0., 1., 0., 0., 2.,
+        ];
+        let x: DenseMatrix<f64> = DenseMatrix::rand(20, 2);
+
+        for shuffle in &[false, true] {
+            let cv = StratifiedKFold::new(&y)
+                .with_n_splits(3)
+                .with_shuffle(*shuffle)
+                .with_seed(Some(1));
+
+            let mut n_tested = [0; 20];
+            for (train, test) in cv.split(&x) {
+                assert_eq!(20, train.len() + test.len());
+                assert!(test.len() == 6 || test.len() == 7);
+
+                let count = |c: f64| test.iter().filter(|&&i| y[i] == c).count();
+                assert!(count(0.) == 4 || count(0.) == 5);
+                assert!(count(1.) == 1 || count(1.) == 2);
+                assert_eq!(1, count(2.));
+
+                for i in test {
+                    n_tested[i] += 1;
+                }
+            }
+            assert!(n_tested.iter().all(|&n| n == 1));
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn stratified_kfold_without_shuffle() {
+        let y = vec![0., 0., 0., 0., 1., 1.];
+        let x: DenseMatrix<f64> = DenseMatrix::rand(6, 2);
+
+        let cv = StratifiedKFold::new(&y)
+            .with_n_splits(2)
+            .with_shuffle(false);
+
+        let splits: Vec<(Vec<usize>, Vec<usize>)> = cv.split(&x).collect();
+
+        assert_eq!((vec![1, 3, 5], vec![0, 2, 4]), splits[0]);
+        assert_eq!((vec![0, 2, 4], vec![1, 3, 5]), splits[1]);
+    }
+}
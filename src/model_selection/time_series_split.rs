@@ -0,0 +1,188 @@
+//! # Time Series Split
+//!
+//! Defines cross validator for samples ordered in time. Each test set directly follows its training set,
+//! so a model is never trained on observations made after the ones it is tested on.
+//! The training set either grows with every split (expanding window) or, when `max_train_size` is set, slides forward (rolling window).
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::model_selection::{BaseKFold, TimeSeriesSplit};
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[1., 2.], &[3., 4.], &[5., 6.], &[7., 8.], &[9., 10.], &[11., 12.],
+//! ]);
+//!
+//! let cv = TimeSeriesSplit::default().with_n_splits(3);
+//!
+//! let splits: Vec<(Vec<usize>, Vec<usize>)> = cv.split(&x).collect();
+//!
+//! assert_eq!(splits[0], (vec![0, 1, 2], vec![3]));
+//! assert_eq!(splits[1], (vec![0, 1, 2, 3], vec![4]));
+//! assert_eq!(splits[2], (vec![0, 1, 2, 3, 4], vec![5]));
+//! ```
+
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::model_selection::BaseKFold;
+use alloc::vec::Vec;
+
+/// Time Series cross-validator
+#[derive(Clone, Debug)]
+pub struct TimeSeriesSplit {
+    /// Number of splits. Must be at least 2.
+    pub n_splits: usize,
+    /// Maximum size of a training set, older samples are dropped. When `None` the training set grows with every split.
+    pub max_train_size: Option<usize>,
+    /// Size of every test set. When `None` it is `n_samples / (n_splits + 1)`.
+    pub test_size: Option<usize>,
+    /// Number of samples left out between the end of a training set and the start of a test set.
+    pub gap: usize,
+}
+
+impl Default for TimeSeriesSplit {
+    fn default() -> TimeSeriesSplit {
+        TimeSeriesSplit {
+            n_splits: 5,
+            max_train_size: None,
+            test_size: None,
+            gap: 0,
+        }
+    }
+}
+
+impl TimeSeriesSplit {
+    /// Number of splits. Must be at least 2.
+    pub fn with_n_splits(mut self, n_splits: usize) -> Self {
+        self.n_splits = n_splits;
+        self
+    }
+
+    /// Maximum size of a training set, older samples are dropped.
+    pub fn with_max_train_size(mut self, max_train_size: usize) -> Self {
+        self.max_train_size = Some(max_train_size);
+        self
+    }
+
+    /// Size of every test set.
+    pub fn with_test_size(mut self, test_size: usize) -> Self {
+        self.test_size = Some(test_size);
+        self
+    }
+
+    /// Number of samples left out between the end of a training set and the start of a test set.
+    pub fn with_gap(mut self, gap: usize) -> Self {
+        self.gap = gap;
+        self
+    }
+}
+
+/// An iterator over indices that split ordered data into training and test set.
+pub struct TimeSeriesSplitIter {
+    test_starts: alloc::vec::IntoIter<usize>,
+    test_size: usize,
+    max_train_size: Option<usize>,
+    gap: usize,
+}
+
+impl Iterator for TimeSeriesSplitIter {
+    type Item = (Vec<usize>, Vec<usize>);
+
+    fn next(&mut self) -> Option<(Vec<usize>, Vec<usize>)> {
+        self.test_starts.next().map(|test_start| {
+            let train_end = test_start - self.gap;
+            let train_start = match self.max_train_size {
+                Some(max_train_size) if max_train_size < train_end => train_end - max_train_size,
+                _ => 0,
+            };
+
+            (
+                (train_start..train_end).collect(),
+                (test_start..test_start + self.test_size).collect(),
+            )
+        })
+    }
+}
+
+impl BaseKFold for TimeSeriesSplit {
+    type Output = TimeSeriesSplitIter;
+
+    fn n_splits(&self) -> usize {
+        self.n_splits
+    }
+
+    fn split<T: RealNumber, M: Matrix<T>>(&self, x: &M) -> Self::Output {
+        if self.n_splits < 2 {
+            panic!("Number of splits is too small: {}", self.n_splits);
+        }
+        let n_samples = x.shape().0;
+        let test_size = self.test_size.unwrap_or(n_samples / (self.n_splits + 1));
+        if test_size == 0 || n_samples < self.gap + self.n_splits * test_size + 1 {
+            panic!(
+                "Too few samples {} for {} splits with test size {} and gap {}",
+                n_samples, self.n_splits, test_size, self.gap
+            );
+        }
+
+        let first_test_start = n_samples - self.n_splits * test_size;
+        let test_starts: Vec<usize> = (0..self.n_splits)
+            .map(|i| first_test_start + i * test_size)
+            .collect();
+
+        TimeSeriesSplitIter {
+            test_starts: test_starts.into_iter(),
+            test_size,
+            max_train_size: self.max_train_size,
+            gap: self.gap,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn time_series_split_expanding_window() {
+        let x: DenseMatrix<f64> = DenseMatrix::rand(10, 2);
+
+        let splits: Vec<(Vec<usize>, Vec<usize>)> = TimeSeriesSplit::default()
+            .with_n_splits(3)
+            .split(&x)
+            .collect();
+
+        assert_eq!(
+            vec![
+                (vec![0, 1, 2, 3], vec![4, 5]),
+                (vec![0, 1, 2, 3, 4, 5], vec![6, 7]),
+                (vec![0, 1, 2, 3, 4, 5, 6, 7], vec![8, 9]),
+            ],
+            splits
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn time_series_split_rolling_window_with_gap() {
+        let x: DenseMatrix<f64> = DenseMatrix::rand(10, 2);
+
+        let splits: Vec<(Vec<usize>, Vec<usize>)> = TimeSeriesSplit::default()
+            .with_n_splits(3)
+            .with_max_train_size(3)
+            .with_test_size(2)
+            .with_gap(1)
+            .split(&x)
+            .collect();
+
+        assert_eq!(
+            vec![
+                (vec![0, 1, 2], vec![4, 5]),
+                (vec![2, 3, 4], vec![6, 7]),
+                (vec![4, 5, 6], vec![8, 9]),
+            ],
+            splits
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! # Validation Curve
+//!
+//! A validation curve shows training and test scores of an estimator for a range of values of a single hyperparameter.
+//! Low scores on both sets point to an underfitted model, a high training score together with a low test score points to an overfitted one.
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::model_selection::{validation_curve, KFold};
+//! use smartcore::tree::decision_tree_regressor::{DecisionTreeRegressor, DecisionTreeRegressorParameters};
+//! use smartcore::metrics::r2;
+//!
+//! // Longley dataset
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[234.289, 235.6, 159., 107.608, 1947., 60.323],
+//!     &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+//!     &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+//!     &[284.599, 335.1, 165., 110.929, 1950., 61.187],
+//!     &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+//!     &[346.999, 193.2, 359.4, 113.27, 1952., 63.639],
+//!     &[365.385, 187., 354.7, 115.094, 1953., 64.989],
+//!     &[363.112, 357.8, 335., 116.219, 1954., 63.761],
+//!     &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+//!     &[419.18, 282.2, 285.7, 118.734, 1956., 67.857],
+//!     &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+//!     &[444.546, 468.1, 263.7, 121.95, 1958., 66.513],
+//!     &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+//!     &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+//!     &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+//!     &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+//! ]);
+//! let y: Vec<f64> = vec![
+//!     83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0, 100.0, 101.2, 104.6, 108.4, 110.8, 112.6,
+//!     114.2, 115.7, 116.9,
+//! ];
+//!
+//! let depths = [1, 2, 3, 4];
+//!
+//! let curve = validation_curve(
+//!     DecisionTreeRegressor::fit,
+//!     &x, &y,
+//!     &DecisionTreeRegressorParameters::default(),
+//!     |p, depth| p.with_max_depth(depth),
+//!     &depths,
+//!     &KFold::default().with_n_splits(4),
+//!     r2,
+//! ).unwrap();
+//!
+//! for (i, depth) in depths.iter().enumerate() {
+//!     println!("max depth: {}, train r2: {}, test r2: {} +/- {}",
+//!         depth,
+//!         curve.mean_train_scores()[i],
+//!         curve.mean_test_scores()[i],
+//!         curve.std_test_scores()[i]);
+//! }
+//! ```
+
+use crate::error::Failed;
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::metrics::scorer::Scorer;
+use crate::model_selection::{cross_validate_with_scorer, BaseKFold, CrossValidationResult};
+use crate::parallel::MaybeSync;
+use alloc::vec::Vec;
+
+/// Validation curve results, one cross-validation result for every value of the hyperparameter.
+#[derive(Clone, Debug)]
+pub struct ValidationCurveResult<T: RealNumber> {
+    /// Training and test scores on each cv split, in the order of the hyperparameter values
+    pub cross_validation_results: Vec<CrossValidationResult<T>>,
+}
+
+impl<T: RealNumber> ValidationCurveResult<T> {
+    /// Average training score for every value of the hyperparameter
+    pub fn mean_train_scores(&self) -> Vec<T> {
+        self.cross_validation_results
+            .iter()
+            .map(|r| r.mean_train_score())
+            .collect()
+    }
+    /// Standard deviation of training scores for every value of the hyperparameter
+    pub fn std_train_scores(&self) -> Vec<T> {
+        self.cross_validation_results
+            .iter()
+            .map(|r| r.std_train_score())
+            .collect()
+    }
+    /// Average test score for every value of the hyperparameter
+    pub fn mean_test_scores(&self) -> Vec<T> {
+        self.cross_validation_results
+            .iter()
+            .map(|r| r.mean_test_score())
+            .collect()
+    }
+    /// Standard deviation of test scores for every value of the hyperparameter
+    pub fn std_test_scores(&self) -> Vec<T> {
+        self.cross_validation_results
+            .iter()
+            .map(|r| r.std_test_score())
+            .collect()
+    }
+}
+
+/// Evaluate an estimator by cross-validation for every value of a single hyperparameter.
+/// * `fit_estimator` - a `fit` function of an estimator
+/// * `x` - features, matrix of size _NxM_ where _N_ is number of samples and _M_ is number of attributes.
+/// * `y` - target values, should be of size _N_
+/// * `parameters` - parameters of selected estimator, all hyperparameters except the one that is varied keep these values.
+/// * `set_parameter` - sets a value of the hyperparameter, e.g. `|p, depth| p.with_max_depth(depth)`
+/// * `values` - values of the hyperparameter to evaluate
+/// * `cv` - the cross-validation splitting strategy, should be an instance of [`BaseKFold`](./trait.BaseKFold.html)
+/// * `score` - a metric to use for evaluation, see [metrics](../metrics/index.html), or a [scorer](../metrics/scorer/index.html)
+pub fn validation_curve<T, M, H, V, E, K, F, P, S>(
+    fit_estimator: F,
+    x: &M,
+    y: &M::RowVector,
+    parameters: &H,
+    set_parameter: P,
+    values: &[V],
+    cv: &K,
+    score: S,
+) -> Result<ValidationCurveResult<T>, Failed>
+where
+    T: RealNumber,
+    M: Matrix<T>,
+    H: Clone + MaybeSync,
+    V: Clone,
+    K: BaseKFold,
+    F: Fn(&M, &M::RowVector, H) -> Result<E, Failed> + MaybeSync,
+    P: Fn(H, V) -> H,
+    S: Scorer<T, M, E> + MaybeSync,
+{
+    let mut cross_validation_results = Vec::with_capacity(values.len());
+
+    for value in values.iter() {
+        let parameters = set_parameter(parameters.clone(), value.clone());
+        cross_validation_results.push(cross_validate_with_scorer(
+            &fit_estimator,
+            x,
+            y,
+            &parameters,
+            cv,
+            &score,
+        )?);
+    }
+
+    Ok(ValidationCurveResult {
+        cross_validation_results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::metrics::accuracy;
+    use crate::model_selection::KFold;
+    use crate::tree::decision_tree_classifier::{
+        DecisionTreeClassifier, DecisionTreeClassifierParameters,
+    };
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn validation_curve_tree_depth() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[5.1, 3.5, 1.4, 0.2],
+            &[4.9, 3.0, 1.4, 0.2],
+            &[4.7, 3.2, 1.3, 0.2],
+            &[4.6, 3.1, 1.5, 0.2],
+            &[5.0, 3.6, 1.4, 0.2],
+            &[5.4, 3.9, 1.7, 0.4],
+            &[4.6, 3.4, 1.4, 0.3],
+            &[5.0, 3.4, 1.5, 0.2],
+            &[4.4, 2.9, 1.4, 0.2],
+            &[4.9, 3.1, 1.5, 0.1],
+            &[7.0, 3.2, 4.7, 1.4],
+            &[6.4, 3.2, 4.5, 1.5],
+            &[6.9, 3.1, 4.9, 1.5],
+            &[5.5, 2.3, 4.0, 1.3],
+            &[6.5, 2.8, 4.6, 1.5],
+            &[5.7, 2.8, 4.5, 1.3],
+            &[6.3, 3.3, 4.7, 1.6],
+            &[4.9, 2.4, 3.3, 1.0],
+            &[6.6, 2.9, 4.6, 1.3],
+            &[5.2, 2.7, 3.9, 1.4],
+        ]);
+        let y: Vec<f64> = vec![
+            0., 0., 0., 0., 0., 0., 0., 0., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1.,
+        ];
+
+        let curve = validation_curve(
+            DecisionTreeClassifier::fit,
+            &x,
+            &y,
+            &DecisionTreeClassifierParameters::default(),
+            |p, depth| p.with_max_depth(depth),
+            &[1, 2, 5],
+            &KFold::default().with_n_splits(4).with_seed(Some(0)),
+            accuracy,
+        )
+        .unwrap();
+
+        assert_eq!(3, curve.cross_validation_results.len());
+
+        let mean_train = curve.mean_train_scores();
+        assert_eq!(3, mean_train.len());
+        assert!(mean_train[0] <= mean_train[2]);
+        assert!((mean_train[2] - 1.).abs() < f64::EPSILON);
+        assert!(curve.std_train_scores()[2].abs() < f64::EPSILON);
+
+        let mean_test = curve.mean_test_scores();
+        let std_test = curve.std_test_scores();
+        for i in 0..3 {
+            assert!(mean_test[i] >= 0. && mean_test[i] <= 1.);
+            assert!(std_test[i] >= 0.);
+        }
+    }
+}
@@ -33,14 +33,18 @@
 //! ## References:
 //!
 //! * ["Introduction to Information Retrieval", Manning C. D., Raghavan P., Schutze H., 2009, Chapter 13 ](https://nlp.stanford.edu/IR-book/information-retrieval-book.html)
-use crate::api::{Predictor, SupervisedEstimator};
+use crate::api::{
+    OnlineEstimator, Predictor, PredictorProba, ProbabilisticClassifier, SupervisedEstimator,
+};
 use crate::error::Failed;
 use crate::linalg::row_iter;
 use crate::linalg::BaseVector;
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
 use crate::math::vector::RealNumberVector;
-use crate::naive_bayes::{BaseNaiveBayes, NBDistribution};
+use crate::naive_bayes::{batch_class_indices, class_frequencies, BaseNaiveBayes, NBDistribution};
+use crate::validation::{check_fit, check_predict};
+use alloc::vec::Vec;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -61,6 +65,10 @@ struct BernoulliNBDistribution<T: RealNumber> {
     feature_log_prob: Vec<Vec<T>>,
     /// Number of features of each sample
     n_features: usize,
+    /// Additive (Laplace/Lidstone) smoothing parameter
+    alpha: T,
+    /// Whether the priors were provided, otherwise they follow the number of samples in each class
+    fixed_priors: bool,
 }
 
 impl<T: RealNumber> PartialEq for BernoulliNBDistribution<T> {
@@ -252,6 +260,7 @@ impl<T: RealNumber> BernoulliNBDistribution<T> {
         alpha: T,
         priors: Option<Vec<T>>,
     ) -> Result<Self, Failed> {
+        check_fit(x, y)?;
         let (n_samples, n_features) = x.shape();
         let y_samples = y.len();
         if y_samples != n_samples {
@@ -277,63 +286,86 @@ impl<T: RealNumber> BernoulliNBDistribution<T> {
         let y = y.to_vec();
 
         let (class_labels, indices) = <Vec<T> as RealNumberVector<T>>::unique_with_indices(&y);
-        let mut class_count = vec![0_usize; class_labels.len()];
-
-        for class_index in indices.iter() {
-            class_count[*class_index] += 1;
-        }
+        let n_classes = class_labels.len();
 
+        let fixed_priors = priors.is_some();
         let class_priors = if let Some(class_priors) = priors {
-            if class_priors.len() != class_labels.len() {
+            if class_priors.len() != n_classes {
                 return Err(Failed::fit(
                     "Size of priors provided does not match the number of classes of the data.",
                 ));
             }
             class_priors
         } else {
-            class_count
-                .iter()
-                .map(|&c| T::from(c).unwrap() / T::from(n_samples).unwrap())
-                .collect()
+            Vec::new()
         };
 
-        let mut feature_in_class_counter = vec![vec![0_usize; n_features]; class_labels.len()];
+        let mut distribution = Self {
+            class_labels,
+            class_priors,
+            class_count: vec![0; n_classes],
+            feature_count: vec![vec![0; n_features]; n_classes],
+            feature_log_prob: Vec::new(),
+            n_features,
+            alpha,
+            fixed_priors,
+        };
+        distribution.update(x, &indices)?;
+        Ok(distribution)
+    }
 
-        for (row, class_index) in row_iter(x).zip(indices) {
-            for (idx, row_i) in row.iter().enumerate().take(n_features) {
-                feature_in_class_counter[class_index][idx] +=
+    /// Updates the distribution with a batch of training data with classes known to the distribution.
+    pub fn partial_fit<M: Matrix<T>>(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        let indices = batch_class_indices(&self.class_labels, self.n_features, x, y)?;
+        self.update(x, &indices)
+    }
+
+    /// Adds the samples of `x` to the counts of their classes and estimates the probabilities again.
+    fn update<M: Matrix<T>>(&mut self, x: &M, indices: &[usize]) -> Result<(), Failed> {
+        let mut rows = Vec::with_capacity(indices.len());
+        for row in row_iter(x) {
+            let row = row
+                .iter()
+                .map(|row_i| {
                     row_i.to_usize().ok_or_else(|| {
                         Failed::fit(&format!(
                             "Elements of the matrix should be 1.0 or 0.0 |found|=[{}]",
                             row_i
                         ))
-                    })?;
+                    })
+                })
+                .collect::<Result<Vec<usize>, Failed>>()?;
+            rows.push(row);
+        }
+
+        for (row, &class_index) in rows.iter().zip(indices) {
+            self.class_count[class_index] += 1;
+            for (count, row_i) in self.feature_count[class_index].iter_mut().zip(row) {
+                *count += row_i;
             }
         }
 
-        let feature_log_prob = feature_in_class_counter
+        if !self.fixed_priors {
+            self.class_priors = class_frequencies(&self.class_count);
+        }
+
+        let alpha = self.alpha;
+        self.feature_log_prob = self
+            .feature_count
             .iter()
-            .enumerate()
-            .map(|(class_index, feature_count)| {
+            .zip(self.class_count.iter())
+            .map(|(feature_count, &class_count)| {
                 feature_count
                     .iter()
                     .map(|&count| {
                         ((T::from(count).unwrap() + alpha)
-                            / (T::from(class_count[class_index]).unwrap() + alpha * T::two()))
+                            / (T::from(class_count).unwrap() + alpha * T::two()))
                         .ln()
                     })
                     .collect()
             })
             .collect();
-
-        Ok(Self {
-            class_labels,
-            class_priors,
-            class_count,
-            feature_count: feature_in_class_counter,
-            feature_log_prob,
-            n_features,
-        })
+        Ok(())
     }
 }
 
@@ -360,6 +392,24 @@ impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for BernoulliNB<T,
     }
 }
 
+impl<T: RealNumber, M: Matrix<T>> PredictorProba<M> for BernoulliNB<T, M> {
+    fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.predict_proba(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> ProbabilisticClassifier<T, M> for BernoulliNB<T, M> {
+    fn classes(&self) -> &Vec<T> {
+        self.classes()
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> OnlineEstimator<M, M::RowVector> for BernoulliNB<T, M> {
+    fn partial_fit(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        self.partial_fit(x, y)
+    }
+}
+
 impl<T: RealNumber, M: Matrix<T>> BernoulliNB<T, M> {
     /// Fits BernoulliNB with given data
     /// * `x` - training data of size NxM where N is the number of samples and M is the number of
@@ -372,6 +422,7 @@ impl<T: RealNumber, M: Matrix<T>> BernoulliNB<T, M> {
         y: &M::RowVector,
         parameters: BernoulliNBParameters<T>,
     ) -> Result<Self, Failed> {
+        check_fit(x, y)?;
         let distribution = if let Some(threshold) = parameters.binarize {
             BernoulliNBDistribution::fit(
                 &(x.binarize(threshold)),
@@ -390,10 +441,25 @@ impl<T: RealNumber, M: Matrix<T>> BernoulliNB<T, M> {
         })
     }
 
+    /// Updates the classifier with a batch of training data, so it can be trained incrementally on data that arrives over time.
+    /// * `x` - training data of size NxM where N is the number of samples and M is the number of features.
+    /// * `y` - vector with target values (classes) of length N, every value should be one of the classes seen by `fit`.
+    pub fn partial_fit(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        check_fit(x, y)?;
+        if let Some(threshold) = self.binarize {
+            self.inner
+                .distribution
+                .partial_fit(&(x.binarize(threshold)), y)
+        } else {
+            self.inner.distribution.partial_fit(x, y)
+        }
+    }
+
     /// Estimates the class labels for the provided data.
     /// * `x` - data of shape NxM where N is number of data points to estimate and M is number of features.
     /// Returns a vector of size N with class estimates.
     pub fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
         if let Some(threshold) = self.binarize {
             self.inner.predict(&(x.binarize(threshold)))
         } else {
@@ -401,6 +467,18 @@ impl<T: RealNumber, M: Matrix<T>> BernoulliNB<T, M> {
         }
     }
 
+    /// Estimates the probability of every class for the provided data.
+    /// Returns a matrix of size NxK where K is the number of classes, columns follow the order of `classes`.
+    /// * `x` - data of shape NxM where N is number of data points to estimate and M is number of features.
+    pub fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        check_predict(x)?;
+        if let Some(threshold) = self.binarize {
+            self.inner.predict_proba(&(x.binarize(threshold)))
+        } else {
+            self.inner.predict_proba(x)
+        }
+    }
+
     /// Class labels known to the classifier.
     /// Returns a vector of size n_classes.
     pub fn classes(&self) -> &Vec<T> {
@@ -434,6 +512,7 @@ impl<T: RealNumber, M: Matrix<T>> BernoulliNB<T, M> {
 mod tests {
     use super::*;
     use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::linalg::BaseMatrix;
 
     #[test]
     fn search_parameters() {
@@ -500,6 +579,11 @@ mod tests {
         let y_hat = bnb.predict(&x_test).unwrap();
 
         assert_eq!(y_hat, &[1.]);
+
+        // P(China | d) is about 0.19 in the book
+        let proba = bnb.predict_proba(&x_test).unwrap();
+        assert!((proba.get(0, 0) - 0.1910668).abs() < 1e-6);
+        assert!((proba.get(0, 1) - 0.8089332).abs() < 1e-6);
     }
 
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
@@ -583,4 +667,27 @@ mod tests {
 
         assert_eq!(bnb, deserialized_bnb);
     }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn partial_fit() {
+        let x = DenseMatrix::<f64>::from_2d_array(&[
+            &[1., 1., 0., 0., 0., 0.],
+            &[0., 1., 0., 0., 1., 0.],
+            &[0., 1., 0., 1., 0., 0.],
+            &[0., 1., 1., 0., 0., 1.],
+        ]);
+        let y = vec![0., 0., 0., 1.];
+        let parameters = BernoulliNBParameters::default().with_priors(vec![0.5, 0.5]);
+        let bnb = BernoulliNB::fit(&x, &y, parameters.clone()).unwrap();
+
+        let mut online = BernoulliNB::fit(&x.slice(2..4, 0..6), &vec![0., 1.], parameters).unwrap();
+        online
+            .partial_fit(&x.slice(0..2, 0..6), &vec![0., 0.])
+            .unwrap();
+
+        assert_eq!(online, bnb);
+        assert_eq!(online.inner.distribution.class_priors, &[0.5, 0.5]);
+        assert!(online.partial_fit(&x.slice(0..1, 0..5), &vec![0.]).is_err());
+    }
 }
@@ -30,12 +30,14 @@
 //! let nb = CategoricalNB::fit(&x, &y, Default::default()).unwrap();
 //! let y_hat = nb.predict(&x).unwrap();
 //! ```
-use crate::api::{Predictor, SupervisedEstimator};
+use crate::api::{Predictor, PredictorProba, ProbabilisticClassifier, SupervisedEstimator};
 use crate::error::Failed;
 use crate::linalg::BaseVector;
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
 use crate::naive_bayes::{BaseNaiveBayes, NBDistribution};
+use crate::validation::check_fit;
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -130,6 +132,7 @@ impl<T: RealNumber> CategoricalNBDistribution<T> {
     /// * `y` - vector with target values (classes) of length N.
     /// * `alpha` - Additive (Laplace/Lidstone) smoothing parameter (0 for no smoothing).
     pub fn fit<M: Matrix<T>>(x: &M, y: &M::RowVector, alpha: T) -> Result<Self, Failed> {
+        check_fit(x, y)?;
         if alpha < T::zero() {
             return Err(Failed::fit(&format!(
                 "alpha should be >= 0, alpha=[{}]",
@@ -342,6 +345,18 @@ impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for CategoricalNB<T
     }
 }
 
+impl<T: RealNumber, M: Matrix<T>> PredictorProba<M> for CategoricalNB<T, M> {
+    fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.predict_proba(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> ProbabilisticClassifier<T, M> for CategoricalNB<T, M> {
+    fn classes(&self) -> &Vec<T> {
+        self.classes()
+    }
+}
+
 impl<T: RealNumber, M: Matrix<T>> CategoricalNB<T, M> {
     /// Fits CategoricalNB with given data
     /// * `x` - training data of size NxM where N is the number of samples and M is the number of
@@ -366,6 +381,13 @@ impl<T: RealNumber, M: Matrix<T>> CategoricalNB<T, M> {
         self.inner.predict(x)
     }
 
+    /// Estimates the probability of every class for the provided data.
+    /// Returns a matrix of size NxK where K is the number of classes, columns follow the order of `classes`.
+    /// * `x` - data of shape NxM where N is number of data points to estimate and M is number of features.
+    pub fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.inner.predict_proba(x)
+    }
+
     /// Class labels known to the classifier.
     /// Returns a vector of size n_classes.
     pub fn classes(&self) -> &Vec<T> {
@@ -0,0 +1,590 @@
+//! # Complement Naive Bayes
+//!
+//! Complement Naive Bayes classifier is an adaptation of [Multinomial Naive Bayes](../multinomial/index.html) that is better suited for imbalanced data.
+//! Instead of estimating the distribution of the features in every class it estimates the distribution of the features in the complement of every class,
+//! i.e. in all the other classes, which is estimated from many more samples when the class is small:
+//!
+//! \\[\hat{\theta}_{\tilde{c}i} = \frac{\alpha + \sum_{j:y_j \neq c} x_{ji}}{\alpha n + \sum_{j:y_j \neq c} \sum_k x_{jk}}\\]
+//!
+//! where \\(n\\) is the number of features and \\(\alpha\\) is the smoothing parameter. The weight of feature \\(i\\) for class \\(c\\) is \\(w_{ci} = -\log \hat{\theta}_{\tilde{c}i}\\),
+//! optionally normalized by the sum of the weights of the class, and a sample \\(x\\) is assigned to the class with the largest \\(\sum_i x_i w_{ci}\\),
+//! the class whose complement matches the sample the least. The class priors are not used.
+//!
+//! Like the multinomial classifier it works with counts as well as with fractional non-negative features, such as TF-IDF weights.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::naive_bayes::complement::ComplementNB;
+//!
+//! // Training data points are:
+//! // Chinese Beijing Chinese (class: China)
+//! // Chinese Chinese Shanghai (class: China)
+//! // Chinese Macao (class: China)
+//! // Tokyo Japan Chinese (class: Japan)
+//! let x = DenseMatrix::<f64>::from_2d_array(&[
+//!                   &[1., 2., 0., 0., 0., 0.],
+//!                   &[0., 2., 0., 0., 1., 0.],
+//!                   &[0., 1., 0., 1., 0., 0.],
+//!                   &[0., 1., 1., 0., 0., 1.],
+//!         ]);
+//! let y = vec![0., 0., 0., 1.];
+//! let nb = ComplementNB::fit(&x, &y, Default::default()).unwrap();
+//!
+//! // Testing data point is:
+//! //  Chinese Chinese Chinese Tokyo Japan
+//! let x_test = DenseMatrix::<f64>::from_2d_array(&[&[0., 3., 1., 0., 0., 1.]]);
+//! let y_hat = nb.predict(&x_test).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Tackling the Poor Assumptions of Naive Bayes Text Classifiers", Rennie J. D. M., Shih L., Teevan J., Karger D. R., 2003](https://people.csail.mit.edu/jrennie/papers/icml03-nb.pdf)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use crate::api::{
+    OnlineEstimator, Predictor, PredictorProba, ProbabilisticClassifier, SupervisedEstimator,
+};
+use crate::error::Failed;
+use crate::linalg::row_iter;
+use crate::linalg::BaseVector;
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::math::vector::RealNumberVector;
+use crate::naive_bayes::{batch_class_indices, BaseNaiveBayes, NBDistribution};
+use crate::validation::check_fit;
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Naive Bayes classifier for the complements of the classes
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq)]
+struct ComplementNBDistribution<T: RealNumber> {
+    /// class labels known to the classifier
+    class_labels: Vec<T>,
+    /// number of training samples observed in each class
+    class_count: Vec<usize>,
+    /// Weight of every feature for every class
+    feature_log_prob: Vec<Vec<T>>,
+    /// Sum of each feature over the samples of each class
+    feature_count: Vec<Vec<T>>,
+    /// Sum of each feature over all samples
+    feature_all: Vec<T>,
+    /// Number of features of each sample
+    n_features: usize,
+    /// Additive (Laplace/Lidstone) smoothing parameter
+    alpha: T,
+    /// Whether the weights of every class are normalized
+    norm: bool,
+}
+
+impl<T: RealNumber, M: Matrix<T>> NBDistribution<T, M> for ComplementNBDistribution<T> {
+    /// Complement Naive Bayes does not use the class priors, 1 for every class does not change the decision.
+    fn prior(&self, _class_index: usize) -> T {
+        T::one()
+    }
+
+    fn log_likelihood(&self, class_index: usize, j: &M::RowVector) -> T {
+        let mut likelihood = T::zero();
+        for feature in 0..j.len() {
+            let value = j.get(feature);
+            likelihood += value * self.feature_log_prob[class_index][feature];
+        }
+        likelihood
+    }
+
+    fn classes(&self) -> &Vec<T> {
+        &self.class_labels
+    }
+}
+
+/// `ComplementNB` parameters. Use `Default::default()` for default values.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct ComplementNBParameters<T: RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Additive (Laplace/Lidstone) smoothing parameter (0 for no smoothing).
+    pub alpha: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to normalize the weights of every class by their sum.
+    pub norm: bool,
+}
+
+impl<T: RealNumber> ComplementNBParameters<T> {
+    /// Additive (Laplace/Lidstone) smoothing parameter (0 for no smoothing).
+    pub fn with_alpha(mut self, alpha: T) -> Self {
+        self.alpha = alpha;
+        self
+    }
+    /// Whether to normalize the weights of every class by their sum.
+    pub fn with_norm(mut self, norm: bool) -> Self {
+        self.norm = norm;
+        self
+    }
+}
+
+impl<T: RealNumber> Default for ComplementNBParameters<T> {
+    fn default() -> Self {
+        Self {
+            alpha: T::one(),
+            norm: false,
+        }
+    }
+}
+
+/// ComplementNB grid search parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct ComplementNBSearchParameters<T: RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Additive (Laplace/Lidstone) smoothing parameter (0 for no smoothing).
+    pub alpha: Vec<T>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to normalize the weights of every class by their sum.
+    pub norm: Vec<bool>,
+}
+
+/// ComplementNB grid search iterator
+pub struct ComplementNBSearchParametersIterator<T: RealNumber> {
+    complement_nb_search_parameters: ComplementNBSearchParameters<T>,
+    current_alpha: usize,
+    current_norm: usize,
+}
+
+impl<T: RealNumber> IntoIterator for ComplementNBSearchParameters<T> {
+    type Item = ComplementNBParameters<T>;
+    type IntoIter = ComplementNBSearchParametersIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ComplementNBSearchParametersIterator {
+            complement_nb_search_parameters: self,
+            current_alpha: 0,
+            current_norm: 0,
+        }
+    }
+}
+
+impl<T: RealNumber> Iterator for ComplementNBSearchParametersIterator<T> {
+    type Item = ComplementNBParameters<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_alpha == self.complement_nb_search_parameters.alpha.len()
+            && self.current_norm == self.complement_nb_search_parameters.norm.len()
+        {
+            return None;
+        }
+
+        let next = ComplementNBParameters {
+            alpha: self.complement_nb_search_parameters.alpha[self.current_alpha],
+            norm: self.complement_nb_search_parameters.norm[self.current_norm],
+        };
+
+        if self.current_alpha + 1 < self.complement_nb_search_parameters.alpha.len() {
+            self.current_alpha += 1;
+        } else if self.current_norm + 1 < self.complement_nb_search_parameters.norm.len() {
+            self.current_alpha = 0;
+            self.current_norm += 1;
+        } else {
+            self.current_alpha += 1;
+            self.current_norm += 1;
+        }
+
+        Some(next)
+    }
+}
+
+impl<T: RealNumber> Default for ComplementNBSearchParameters<T> {
+    fn default() -> Self {
+        let default_params = ComplementNBParameters::default();
+
+        ComplementNBSearchParameters {
+            alpha: vec![default_params.alpha],
+            norm: vec![default_params.norm],
+        }
+    }
+}
+
+impl<T: RealNumber> ComplementNBDistribution<T> {
+    /// Fits the distribution to a NxM matrix where N is number of samples and M is number of features.
+    /// * `x` - training data.
+    /// * `y` - vector with target values (classes) of length N.
+    /// * `alpha` - Additive (Laplace/Lidstone) smoothing parameter.
+    /// * `norm` - Whether to normalize the weights of every class.
+    pub fn fit<M: Matrix<T>>(
+        x: &M,
+        y: &M::RowVector,
+        alpha: T,
+        norm: bool,
+    ) -> Result<Self, Failed> {
+        check_fit(x, y)?;
+        let (n_samples, n_features) = x.shape();
+        let y_samples = y.len();
+        if y_samples != n_samples {
+            return Err(Failed::fit(&format!(
+                "Size of x should equal size of y; |x|=[{}], |y|=[{}]",
+                n_samples, y_samples
+            )));
+        }
+
+        if n_samples == 0 {
+            return Err(Failed::fit(&format!(
+                "Size of x and y should greater than 0; |x|=[{}]",
+                n_samples
+            )));
+        }
+        if alpha < T::zero() {
+            return Err(Failed::fit(&format!(
+                "Alpha should be greater than 0; |alpha|=[{}]",
+                alpha
+            )));
+        }
+
+        let y = y.to_vec();
+
+        let (class_labels, indices) = <Vec<T> as RealNumberVector<T>>::unique_with_indices(&y);
+        let n_classes = class_labels.len();
+
+        let mut distribution = Self {
+            class_count: vec![0; n_classes],
+            class_labels,
+            feature_log_prob: Vec::new(),
+            feature_count: vec![vec![T::zero(); n_features]; n_classes],
+            feature_all: vec![T::zero(); n_features],
+            n_features,
+            alpha,
+            norm,
+        };
+        distribution.update(x, &indices)?;
+        Ok(distribution)
+    }
+
+    /// Updates the distribution with a batch of training data with classes known to the distribution.
+    pub fn partial_fit<M: Matrix<T>>(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        let indices = batch_class_indices(&self.class_labels, self.n_features, x, y)?;
+        self.update(x, &indices)
+    }
+
+    /// Adds the samples of `x` to the counts of their classes and estimates the weights again.
+    fn update<M: Matrix<T>>(&mut self, x: &M, indices: &[usize]) -> Result<(), Failed> {
+        for row in row_iter(x) {
+            if let Some(row_i) = row.iter().find(|v| !v.is_finite() || **v < T::zero()) {
+                return Err(Failed::fit(&format!(
+                    "Elements of the matrix should be non-negative |found|=[{}]",
+                    row_i
+                )));
+            }
+        }
+
+        for (row, &class_index) in row_iter(x).zip(indices) {
+            self.class_count[class_index] += 1;
+            for (idx, row_i) in row.into_iter().enumerate() {
+                self.feature_count[class_index][idx] += row_i;
+                self.feature_all[idx] += row_i;
+            }
+        }
+
+        let (alpha, norm) = (self.alpha, self.norm);
+        self.feature_log_prob = self
+            .feature_count
+            .iter()
+            .map(|feature_count| {
+                let complement_count: Vec<T> = self
+                    .feature_all
+                    .iter()
+                    .zip(feature_count.iter())
+                    .map(|(&all, &count)| all - count + alpha)
+                    .collect();
+                let total: T = complement_count.iter().copied().sum();
+                let logged: Vec<T> = complement_count
+                    .iter()
+                    .map(|&count| (count / total).ln())
+                    .collect();
+                if norm {
+                    let summed: T = logged.iter().copied().sum();
+                    logged.iter().map(|&l| l / summed).collect()
+                } else {
+                    logged.iter().map(|&l| -l).collect()
+                }
+            })
+            .collect();
+        Ok(())
+    }
+}
+
+/// ComplementNB implements the complement naive Bayes algorithm for multinomially distributed data.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct ComplementNB<T: RealNumber, M: Matrix<T>> {
+    inner: BaseNaiveBayes<T, M, ComplementNBDistribution<T>>,
+}
+
+impl<T: RealNumber, M: Matrix<T>> SupervisedEstimator<M, M::RowVector, ComplementNBParameters<T>>
+    for ComplementNB<T, M>
+{
+    fn fit(x: &M, y: &M::RowVector, parameters: ComplementNBParameters<T>) -> Result<Self, Failed> {
+        ComplementNB::fit(x, y, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for ComplementNB<T, M> {
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> PredictorProba<M> for ComplementNB<T, M> {
+    fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.predict_proba(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> ProbabilisticClassifier<T, M> for ComplementNB<T, M> {
+    fn classes(&self) -> &Vec<T> {
+        self.classes()
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> OnlineEstimator<M, M::RowVector> for ComplementNB<T, M> {
+    fn partial_fit(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        self.partial_fit(x, y)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> ComplementNB<T, M> {
+    /// Fits ComplementNB with given data
+    /// * `x` - training data of size NxM where N is the number of samples and M is the number of
+    ///   features.
+    /// * `y` - vector with target values (classes) of length N.
+    /// * `parameters` - additional parameters like alpha for smoothing and normalization of the weights.
+    pub fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: ComplementNBParameters<T>,
+    ) -> Result<Self, Failed> {
+        let distribution = ComplementNBDistribution::fit(x, y, parameters.alpha, parameters.norm)?;
+        let inner = BaseNaiveBayes::fit(distribution)?;
+        Ok(Self { inner })
+    }
+
+    /// Updates the classifier with a batch of training data, so it can be trained incrementally on data that arrives over time.
+    /// * `x` - training data of size NxM where N is the number of samples and M is the number of features.
+    /// * `y` - vector with target values (classes) of length N, every value should be one of the classes seen by `fit`.
+    pub fn partial_fit(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        self.inner.distribution.partial_fit(x, y)
+    }
+
+    /// Estimates the class labels for the provided data.
+    /// Returns a vector of size N with class estimates.
+    /// * `x` - data of shape NxM where N is number of data points to estimate and M is number of features.
+    pub fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.inner.predict(x)
+    }
+
+    /// Estimates the probability of every class for the provided data.
+    /// Returns a matrix of size NxK where K is the number of classes, columns follow the order of `classes`.
+    /// * `x` - data of shape NxM where N is number of data points to estimate and M is number of features.
+    pub fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.inner.predict_proba(x)
+    }
+
+    /// Class labels known to the classifier.
+    /// Returns a vector of size n_classes.
+    pub fn classes(&self) -> &Vec<T> {
+        &self.inner.distribution.class_labels
+    }
+
+    /// Number of training samples observed in each class.
+    /// Returns a vector of size n_classes.
+    pub fn class_count(&self) -> &Vec<usize> {
+        &self.inner.distribution.class_count
+    }
+
+    /// Weight of every feature for every class, the negative logarithm of the probability of the feature in the complement of the class
+    /// or its normalized logarithm when `norm` is set.
+    /// Returns a 2d vector of shape (n_classes, n_features)
+    pub fn feature_log_prob(&self) -> &Vec<Vec<T>> {
+        &self.inner.distribution.feature_log_prob
+    }
+
+    /// Number of features of each sample
+    pub fn n_features(&self) -> usize {
+        self.inner.distribution.n_features
+    }
+
+    /// Sum of each feature over the training samples of each class.
+    /// Returns a 2d vector of shape (n_classes, n_features)
+    pub fn feature_count(&self) -> &Vec<Vec<T>> {
+        &self.inner.distribution.feature_count
+    }
+
+    /// Sum of each feature over all training samples.
+    /// Returns a vector of size n_features
+    pub fn feature_all(&self) -> &Vec<T> {
+        &self.inner.distribution.feature_all
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::linalg::BaseMatrix;
+
+    #[test]
+    fn search_parameters() {
+        let parameters = ComplementNBSearchParameters {
+            alpha: vec![1., 2.],
+            norm: vec![false, true],
+        };
+        let mut iter = parameters.into_iter();
+        let next = iter.next().unwrap();
+        assert_eq!((next.alpha, next.norm), (1., false));
+        let next = iter.next().unwrap();
+        assert_eq!((next.alpha, next.norm), (2., false));
+        let next = iter.next().unwrap();
+        assert_eq!((next.alpha, next.norm), (1., true));
+        let next = iter.next().unwrap();
+        assert_eq!((next.alpha, next.norm), (2., true));
+        assert!(iter.next().is_none());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn run_complement_naive_bayes() {
+        // Training data points are:
+        // Chinese Beijing Chinese (class: China)
+        // Chinese Chinese Shanghai (class: China)
+        // Chinese Macao (class: China)
+        // Tokyo Japan Chinese (class: Japan)
+        let x = DenseMatrix::<f64>::from_2d_array(&[
+            &[1., 2., 0., 0., 0., 0.],
+            &[0., 2., 0., 0., 1., 0.],
+            &[0., 1., 0., 1., 0., 0.],
+            &[0., 1., 1., 0., 0., 1.],
+        ]);
+        let y = vec![0., 0., 0., 1.];
+        let cnb = ComplementNB::fit(&x, &y, Default::default()).unwrap();
+
+        assert_eq!(cnb.classes(), &[0., 1.]);
+        assert_eq!(cnb.class_count(), &[3, 1]);
+        assert_eq!(cnb.n_features(), 6);
+        assert_eq!(cnb.feature_all(), &[1., 6., 1., 1., 1., 1.]);
+        assert_eq!(
+            cnb.feature_count(),
+            &[&[1., 5., 0., 1., 1., 0.], &[0., 1., 1., 0., 0., 1.]]
+        );
+        // the complement of China is Japan: (counts + 1) / (3 + 6)
+        assert!(cnb.feature_log_prob()[0].approximate_eq(
+            &vec![
+                -(1_f64 / 9_f64).ln(),
+                -(2_f64 / 9_f64).ln(),
+                -(2_f64 / 9_f64).ln(),
+                -(1_f64 / 9_f64).ln(),
+                -(1_f64 / 9_f64).ln(),
+                -(2_f64 / 9_f64).ln()
+            ],
+            1e-12
+        ));
+        // the complement of Japan is China: (counts + 1) / (8 + 6)
+        assert!(cnb.feature_log_prob()[1].approximate_eq(
+            &vec![
+                -(2_f64 / 14_f64).ln(),
+                -(6_f64 / 14_f64).ln(),
+                -(1_f64 / 14_f64).ln(),
+                -(2_f64 / 14_f64).ln(),
+                -(2_f64 / 14_f64).ln(),
+                -(1_f64 / 14_f64).ln()
+            ],
+            1e-12
+        ));
+
+        // Testing data point is:
+        //  Chinese Chinese Chinese Tokyo Japan
+        // Tokyo and Japan are rare outside of Japan, unlike multinomial NB the class prior does not outweigh them
+        let x_test = DenseMatrix::<f64>::from_2d_array(&[&[0., 3., 1., 0., 0., 1.]]);
+        let y_hat = cnb.predict(&x_test).unwrap();
+
+        assert_eq!(y_hat, &[1.]);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn complement_nb_norm() {
+        let x = DenseMatrix::<f64>::from_2d_array(&[
+            &[3., 0., 1.],
+            &[2., 1., 0.],
+            &[4., 0., 1.],
+            &[0., 3., 1.],
+            &[1., 2., 2.],
+        ]);
+        let y = vec![0., 0., 0., 1., 1.];
+        let cnb =
+            ComplementNB::fit(&x, &y, ComplementNBParameters::default().with_norm(true)).unwrap();
+
+        for weights in cnb.feature_log_prob().iter() {
+            assert!((weights.iter().sum::<f64>() - 1.).abs() < 1e-12);
+        }
+        // complement of class 0 is class 1: counts [1, 5, 3] + 1
+        let logged = [(2f64 / 12.).ln(), (6f64 / 12.).ln(), (4f64 / 12.).ln()];
+        let summed: f64 = logged.iter().sum();
+        assert!(cnb.feature_log_prob()[0]
+            .approximate_eq(&logged.iter().map(|l| l / summed).collect(), 1e-12));
+
+        assert_eq!(cnb.predict(&x).unwrap(), y);
+
+        let x_negative = DenseMatrix::<f64>::from_2d_array(&[&[-1., 1.], &[1., 1.]]);
+        assert!(ComplementNB::fit(&x_negative, &vec![0., 1.], Default::default()).is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let x = DenseMatrix::<f64>::from_2d_array(&[
+            &[1., 1., 0., 0., 0., 0.],
+            &[0., 1., 0., 0., 1., 0.],
+            &[0., 1., 0., 1., 0., 0.],
+            &[0., 1., 1., 0., 0., 1.],
+        ]);
+        let y = vec![0., 0., 0., 1.];
+
+        let cnb = ComplementNB::fit(&x, &y, Default::default()).unwrap();
+        let deserialized_cnb: ComplementNB<f64, DenseMatrix<f64>> =
+            serde_json::from_str(&serde_json::to_string(&cnb).unwrap()).unwrap();
+
+        assert_eq!(
+            cnb.predict(&x).unwrap(),
+            deserialized_cnb.predict(&x).unwrap()
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn partial_fit() {
+        let x = DenseMatrix::<f64>::from_2d_array(&[
+            &[1., 2., 0., 0., 0., 0.],
+            &[0., 2., 0., 0., 1., 0.],
+            &[0., 1., 0., 1., 0., 0.],
+            &[0., 1., 1., 0., 0., 1.],
+        ]);
+        let y = vec![0., 0., 0., 1.];
+        let parameters = ComplementNBParameters::default().with_norm(true);
+        let cnb = ComplementNB::fit(&x, &y, parameters.clone()).unwrap();
+
+        let mut online =
+            ComplementNB::fit(&x.slice(2..4, 0..6), &vec![0., 1.], parameters).unwrap();
+        online
+            .partial_fit(&x.slice(0..2, 0..6), &vec![0., 0.])
+            .unwrap();
+
+        assert_eq!(online.class_count(), cnb.class_count());
+        assert_eq!(online.feature_count(), cnb.feature_count());
+        assert_eq!(online.feature_all(), cnb.feature_all());
+        assert_eq!(online.feature_log_prob(), cnb.feature_log_prob());
+        assert!(online.partial_fit(&x.slice(0..1, 0..6), &vec![2.]).is_err());
+    }
+}
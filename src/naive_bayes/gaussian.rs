@@ -22,14 +22,18 @@
 //! let nb = GaussianNB::fit(&x, &y, Default::default()).unwrap();
 //! let y_hat = nb.predict(&x).unwrap();
 //! ```
-use crate::api::{Predictor, SupervisedEstimator};
+use crate::api::{
+    OnlineEstimator, Predictor, PredictorProba, ProbabilisticClassifier, SupervisedEstimator,
+};
 use crate::error::Failed;
 use crate::linalg::row_iter;
 use crate::linalg::BaseVector;
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
 use crate::math::vector::RealNumberVector;
-use crate::naive_bayes::{BaseNaiveBayes, NBDistribution};
+use crate::naive_bayes::{batch_class_indices, class_frequencies, BaseNaiveBayes, NBDistribution};
+use crate::validation::check_fit;
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -47,6 +51,8 @@ struct GaussianNBDistribution<T: RealNumber> {
     var: Vec<Vec<T>>,
     /// mean of each feature per class
     theta: Vec<Vec<T>>,
+    /// Whether the priors were provided, otherwise they follow the number of samples in each class
+    fixed_priors: bool,
 }
 
 impl<T: RealNumber, M: Matrix<T>> NBDistribution<T, M> for GaussianNBDistribution<T> {
@@ -163,6 +169,7 @@ impl<T: RealNumber> GaussianNBDistribution<T> {
         y: &M::RowVector,
         priors: Option<Vec<T>>,
     ) -> Result<Self, Failed> {
+        check_fit(x, y)?;
         let (n_samples, n_features) = x.shape();
         let y_samples = y.len();
         if y_samples != n_samples {
@@ -190,6 +197,7 @@ impl<T: RealNumber> GaussianNBDistribution<T> {
             subdataset[*class_index].push(row);
         }
 
+        let fixed_priors = priors.is_some();
         let class_priors = if let Some(class_priors) = priors {
             if class_priors.len() != class_labels.len() {
                 return Err(Failed::fit(
@@ -228,13 +236,55 @@ impl<T: RealNumber> GaussianNBDistribution<T> {
             class_priors,
             var,
             theta,
+            fixed_priors,
         })
     }
 
+    /// Updates the distribution with a batch of training data with classes known to the distribution.
+    /// The mean and variance of every class are merged with the ones of the samples of the class in the batch.
+    pub fn partial_fit<M: Matrix<T>>(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        let n_features = self.theta[0].len();
+        let indices = batch_class_indices(&self.class_labels, n_features, x, y)?;
+
+        let mut subdataset: Vec<Vec<Vec<T>>> = vec![vec![]; self.class_labels.len()];
+        for (row, class_index) in row_iter(x).zip(indices) {
+            subdataset[class_index].push(row);
+        }
+
+        for (class_index, rows) in subdataset.iter().enumerate() {
+            if rows.is_empty() {
+                continue;
+            }
+            let n_seen = T::from(self.class_count[class_index]).unwrap();
+            let n_batch = T::from(rows.len()).unwrap();
+            let n_total = n_seen + n_batch;
+            for feature in 0..n_features {
+                let mean: T = rows.iter().map(|row| row[feature]).sum::<T>() / n_batch;
+                let var: T = rows
+                    .iter()
+                    .map(|row| (row[feature] - mean).square())
+                    .sum::<T>()
+                    / n_batch;
+                let delta = mean - self.theta[class_index][feature];
+                self.var[class_index][feature] = (self.var[class_index][feature] * n_seen
+                    + var * n_batch
+                    + delta * delta * n_seen * n_batch / n_total)
+                    / n_total;
+                self.theta[class_index][feature] += delta * n_batch / n_total;
+            }
+            self.class_count[class_index] += rows.len();
+        }
+
+        if !self.fixed_priors {
+            self.class_priors = class_frequencies(&self.class_count);
+        }
+        Ok(())
+    }
+
     /// Calculate probability of x equals to a value of a Gaussian distribution given its mean and its
     /// variance.
     fn calculate_log_probability(&self, value: T, mean: T, variance: T) -> T {
-        let pi = T::from(std::f64::consts::PI).unwrap();
+        let pi = T::from(core::f64::consts::PI).unwrap();
         -((value - mean).powf(T::two()) / (T::two() * variance))
             - (T::two() * pi).ln() / T::two()
             - (variance).ln() / T::two()
@@ -263,6 +313,24 @@ impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for GaussianNB<T, M
     }
 }
 
+impl<T: RealNumber, M: Matrix<T>> PredictorProba<M> for GaussianNB<T, M> {
+    fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.predict_proba(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> ProbabilisticClassifier<T, M> for GaussianNB<T, M> {
+    fn classes(&self) -> &Vec<T> {
+        self.classes()
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> OnlineEstimator<M, M::RowVector> for GaussianNB<T, M> {
+    fn partial_fit(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        self.partial_fit(x, y)
+    }
+}
+
 impl<T: RealNumber, M: Matrix<T>> GaussianNB<T, M> {
     /// Fits GaussianNB with given data
     /// * `x` - training data of size NxM where N is the number of samples and M is the number of
@@ -279,6 +347,13 @@ impl<T: RealNumber, M: Matrix<T>> GaussianNB<T, M> {
         Ok(Self { inner })
     }
 
+    /// Updates the classifier with a batch of training data, so it can be trained incrementally on data that arrives over time.
+    /// * `x` - training data of size NxM where N is the number of samples and M is the number of features.
+    /// * `y` - vector with target values (classes) of length N, every value should be one of the classes seen by `fit`.
+    pub fn partial_fit(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        self.inner.distribution.partial_fit(x, y)
+    }
+
     /// Estimates the class labels for the provided data.
     /// * `x` - data of shape NxM where N is number of data points to estimate and M is number of features.
     /// Returns a vector of size N with class estimates.
@@ -286,6 +361,13 @@ impl<T: RealNumber, M: Matrix<T>> GaussianNB<T, M> {
         self.inner.predict(x)
     }
 
+    /// Estimates the probability of every class for the provided data.
+    /// Returns a matrix of size NxK where K is the number of classes, columns follow the order of `classes`.
+    /// * `x` - data of shape NxM where N is number of data points to estimate and M is number of features.
+    pub fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.inner.predict_proba(x)
+    }
+
     /// Class labels known to the classifier.
     /// Returns a vector of size n_classes.
     pub fn classes(&self) -> &Vec<T> {
@@ -321,6 +403,7 @@ impl<T: RealNumber, M: Matrix<T>> GaussianNB<T, M> {
 mod tests {
     use super::*;
     use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::linalg::BaseMatrix;
 
     #[test]
     fn search_parameters() {
@@ -355,6 +438,12 @@ mod tests {
 
         assert_eq!(gnb.classes(), &[1., 2.]);
 
+        let proba: DenseMatrix<f64> = gnb.predict_proba(&x).unwrap();
+        for (i, y_i) in y.iter().enumerate() {
+            assert!((proba.get(i, 0) + proba.get(i, 1) - 1.).abs() < 1e-8);
+            assert_eq!(proba.get(i, 0) > 0.5, *y_i == 1.);
+        }
+
         assert_eq!(gnb.class_count(), &[3, 3]);
 
         assert_eq!(
@@ -413,4 +502,48 @@ mod tests {
 
         assert_eq!(gnb, deserialized_gnb);
     }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn partial_fit() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[-1., -1.],
+            &[-2., -1.],
+            &[-3., -2.],
+            &[1., 1.],
+            &[2., 1.],
+            &[3., 2.],
+        ]);
+        let y = vec![1., 1., 1., 2., 2., 2.];
+        let gnb = GaussianNB::fit(&x, &y, Default::default()).unwrap();
+
+        let mut online = GaussianNB::fit(
+            &DenseMatrix::from_2d_array(&[&[-1., -1.], &[1., 1.]]),
+            &vec![1., 2.],
+            Default::default(),
+        )
+        .unwrap();
+        online
+            .partial_fit(
+                &DenseMatrix::from_2d_array(&[&[-2., -1.], &[-3., -2.], &[2., 1.], &[3., 2.]]),
+                &vec![1., 1., 2., 2.],
+            )
+            .unwrap();
+
+        assert_eq!(online.class_count(), gnb.class_count());
+        assert_eq!(online.class_priors(), gnb.class_priors());
+        for (a, b) in online.theta().iter().zip(gnb.theta()) {
+            assert!(a.approximate_eq(b, 1e-12));
+        }
+        for (a, b) in online.var().iter().zip(gnb.var()) {
+            assert!(a.approximate_eq(b, 1e-12));
+        }
+
+        assert!(online
+            .partial_fit(&DenseMatrix::from_2d_array(&[&[0., 0.]]), &vec![3.])
+            .is_err());
+        assert!(online
+            .partial_fit(&DenseMatrix::from_2d_array(&[&[0.]]), &vec![1.])
+            .is_err());
+    }
 }
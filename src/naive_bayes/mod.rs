@@ -39,9 +39,11 @@ use crate::error::Failed;
 use crate::linalg::BaseVector;
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
+use crate::validation::{check_fit, check_predict};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::marker::PhantomData;
 
 /// Distribution used in the Naive Bayes classifier.
 pub(crate) trait NBDistribution<T: RealNumber, M: Matrix<T>> {
@@ -79,6 +81,7 @@ impl<T: RealNumber, M: Matrix<T>, D: NBDistribution<T, M>> BaseNaiveBayes<T, M,
     /// * `x` - data of shape NxM where N is number of data points to estimate and M is number of features.
     /// Returns a vector of size N with class estimates.
     pub fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
         let y_classes = self.distribution.classes();
         let (rows, _) = x.shape();
         let predictions = (0..rows)
@@ -102,8 +105,85 @@ impl<T: RealNumber, M: Matrix<T>, D: NBDistribution<T, M>> BaseNaiveBayes<T, M,
         let y_hat = M::RowVector::from_array(&predictions);
         Ok(y_hat)
     }
+
+    /// Estimates the probability of every class for the provided data, normalizing \\(P(y)\prod_{i=1}^nP(x_i|y)\\) over the classes.
+    /// Returns a matrix of size NxK where K is the number of classes, columns follow the order of `classes`.
+    /// * `x` - data of shape NxM where N is number of data points to estimate and M is number of features.
+    pub fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        check_predict(x)?;
+        let n_classes = self.distribution.classes().len();
+        let (rows, _) = x.shape();
+        let mut result = M::zeros(rows, n_classes);
+        for row_index in 0..rows {
+            let row = x.get_row(row_index);
+            let log_joint: Vec<T> = (0..n_classes)
+                .map(|class_index| {
+                    self.distribution.log_likelihood(class_index, &row)
+                        + self.distribution.prior(class_index).ln()
+                })
+                .collect();
+            // subtracting the largest log probability keeps the exponents from underflowing
+            let max = log_joint.iter().fold(T::neg_infinity(), |a, b| a.max(*b));
+            let joint: Vec<T> = log_joint.iter().map(|l| (*l - max).exp()).collect();
+            let total = joint.iter().copied().sum::<T>();
+            for (class_index, p) in joint.iter().enumerate() {
+                result.set(row_index, class_index, *p / total);
+            }
+        }
+        Ok(result)
+    }
 }
+/// Checks a batch of training data passed to `partial_fit` and returns the index of the class of every observation.
+/// * `class_labels` - class labels known to the classifier, labels of the batch must be one of them
+/// * `n_features` - number of features the classifier was fitted with
+pub(crate) fn batch_class_indices<T: RealNumber, M: Matrix<T>>(
+    class_labels: &[T],
+    n_features: usize,
+    x: &M,
+    y: &M::RowVector,
+) -> Result<Vec<usize>, Failed> {
+    check_fit(x, y)?;
+    let (n_samples, x_features) = x.shape();
+    if y.len() != n_samples {
+        return Err(Failed::fit(&format!(
+            "Size of x should equal size of y; |x|=[{}], |y|=[{}]",
+            n_samples,
+            y.len()
+        )));
+    }
+    if x_features != n_features {
+        return Err(Failed::fit(&format!(
+            "Number of features of x ({}) does not match the number of features of the classifier ({})",
+            x_features, n_features
+        )));
+    }
+    y.to_vec()
+        .iter()
+        .map(|label| {
+            class_labels
+                .iter()
+                .position(|class| class == label)
+                .ok_or_else(|| {
+                    Failed::fit(&format!(
+                        "Class {} is not one of the classes seen by fit: {:?}",
+                        label, class_labels
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// Probability of each class estimated from the number of training samples observed in each class.
+pub(crate) fn class_frequencies<T: RealNumber>(class_count: &[usize]) -> Vec<T> {
+    let n_samples = T::from_usize(class_count.iter().sum()).unwrap();
+    class_count
+        .iter()
+        .map(|&c| T::from_usize(c).unwrap() / n_samples)
+        .collect()
+}
+
 pub mod bernoulli;
 pub mod categorical;
+pub mod complement;
 pub mod gaussian;
 pub mod multinomial;
@@ -4,6 +4,14 @@
 //! It is often used for discrete data with predictors representing the number of times an event was observed in a particular instance,
 //! for example frequency of the words present in the document.
 //!
+//! The probability of feature \\(i\\) given class \\(y\\) is estimated with additive smoothing
+//!
+//! \\[\hat{\theta}_{yi} = \frac{N_{yi} + \alpha}{N_y + \alpha n}\\]
+//!
+//! where \\(N_{yi}\\) is the sum of feature \\(i\\) over the training samples of class \\(y\\), \\(N_y = \sum_i N_{yi}\\) and \\(n\\) is the number of features.
+//! \\(\alpha = 1\\) is Laplace smoothing and \\(0 < \alpha < 1\\) Lidstone smoothing.
+//! Features do not have to be integer counts: fractional non-negative values, such as TF-IDF weights, work as well.
+//!
 //! Example:
 //!
 //! ```
@@ -33,14 +41,18 @@
 //! ## References:
 //!
 //! * ["Introduction to Information Retrieval", Manning C. D., Raghavan P., Schutze H., 2009, Chapter 13 ](https://nlp.stanford.edu/IR-book/information-retrieval-book.html)
-use crate::api::{Predictor, SupervisedEstimator};
+use crate::api::{
+    OnlineEstimator, Predictor, PredictorProba, ProbabilisticClassifier, SupervisedEstimator,
+};
 use crate::error::Failed;
 use crate::linalg::row_iter;
 use crate::linalg::BaseVector;
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
 use crate::math::vector::RealNumberVector;
-use crate::naive_bayes::{BaseNaiveBayes, NBDistribution};
+use crate::naive_bayes::{batch_class_indices, class_frequencies, BaseNaiveBayes, NBDistribution};
+use crate::validation::check_fit;
+use alloc::vec::Vec;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -57,10 +69,14 @@ struct MultinomialNBDistribution<T: RealNumber> {
     class_priors: Vec<T>,
     /// Empirical log probability of features given a class
     feature_log_prob: Vec<Vec<T>>,
-    /// Number of samples encountered for each (class, feature)
-    feature_count: Vec<Vec<usize>>,
+    /// Sum of each feature over the samples of each class
+    feature_count: Vec<Vec<T>>,
     /// Number of features of each sample
     n_features: usize,
+    /// Additive (Laplace/Lidstone) smoothing parameter
+    alpha: T,
+    /// Whether the priors were provided, otherwise they follow the number of samples in each class
+    fixed_priors: bool,
 }
 
 impl<T: RealNumber, M: Matrix<T>> NBDistribution<T, M> for MultinomialNBDistribution<T> {
@@ -201,6 +217,7 @@ impl<T: RealNumber> MultinomialNBDistribution<T> {
         alpha: T,
         priors: Option<Vec<T>>,
     ) -> Result<Self, Failed> {
+        check_fit(x, y)?;
         let (n_samples, n_features) = x.shape();
         let y_samples = y.len();
         if y_samples != n_samples {
@@ -226,63 +243,76 @@ impl<T: RealNumber> MultinomialNBDistribution<T> {
         let y = y.to_vec();
 
         let (class_labels, indices) = <Vec<T> as RealNumberVector<T>>::unique_with_indices(&y);
-        let mut class_count = vec![0_usize; class_labels.len()];
-
-        for class_index in indices.iter() {
-            class_count[*class_index] += 1;
-        }
+        let n_classes = class_labels.len();
 
+        let fixed_priors = priors.is_some();
         let class_priors = if let Some(class_priors) = priors {
-            if class_priors.len() != class_labels.len() {
+            if class_priors.len() != n_classes {
                 return Err(Failed::fit(
                     "Size of priors provided does not match the number of classes of the data.",
                 ));
             }
             class_priors
         } else {
-            class_count
-                .iter()
-                .map(|&c| T::from(c).unwrap() / T::from(n_samples).unwrap())
-                .collect()
+            Vec::new()
+        };
+
+        let mut distribution = Self {
+            class_count: vec![0; n_classes],
+            class_labels,
+            class_priors,
+            feature_log_prob: Vec::new(),
+            feature_count: vec![vec![T::zero(); n_features]; n_classes],
+            n_features,
+            alpha,
+            fixed_priors,
         };
+        distribution.update(x, &indices)?;
+        Ok(distribution)
+    }
+
+    /// Updates the distribution with a batch of training data with classes known to the distribution.
+    pub fn partial_fit<M: Matrix<T>>(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        let indices = batch_class_indices(&self.class_labels, self.n_features, x, y)?;
+        self.update(x, &indices)
+    }
+
+    /// Adds the samples of `x` to the counts of their classes and estimates the probabilities again.
+    fn update<M: Matrix<T>>(&mut self, x: &M, indices: &[usize]) -> Result<(), Failed> {
+        for row in row_iter(x) {
+            if let Some(row_i) = row.iter().find(|v| !v.is_finite() || **v < T::zero()) {
+                return Err(Failed::fit(&format!(
+                    "Elements of the matrix should be non-negative |found|=[{}]",
+                    row_i
+                )));
+            }
+        }
 
-        let mut feature_in_class_counter = vec![vec![0_usize; n_features]; class_labels.len()];
-
-        for (row, class_index) in row_iter(x).zip(indices) {
-            for (idx, row_i) in row.iter().enumerate().take(n_features) {
-                feature_in_class_counter[class_index][idx] +=
-                    row_i.to_usize().ok_or_else(|| {
-                        Failed::fit(&format!(
-                            "Elements of the matrix should be convertible to usize |found|=[{}]",
-                            row_i
-                        ))
-                    })?;
+        for (row, &class_index) in row_iter(x).zip(indices) {
+            self.class_count[class_index] += 1;
+            for (count, row_i) in self.feature_count[class_index].iter_mut().zip(row) {
+                *count += row_i;
             }
         }
 
-        let feature_log_prob = feature_in_class_counter
+        if !self.fixed_priors {
+            self.class_priors = class_frequencies(&self.class_count);
+        }
+
+        let alpha = self.alpha;
+        let n_features = T::from(self.n_features).unwrap();
+        self.feature_log_prob = self
+            .feature_count
             .iter()
             .map(|feature_count| {
-                let n_c: usize = feature_count.iter().sum();
+                let n_c: T = feature_count.iter().copied().sum();
                 feature_count
                     .iter()
-                    .map(|&count| {
-                        ((T::from(count).unwrap() + alpha)
-                            / (T::from(n_c).unwrap() + alpha * T::from(n_features).unwrap()))
-                        .ln()
-                    })
+                    .map(|&count| ((count + alpha) / (n_c + alpha * n_features)).ln())
                     .collect()
             })
             .collect();
-
-        Ok(Self {
-            class_count,
-            class_labels,
-            class_priors,
-            feature_log_prob,
-            feature_count: feature_in_class_counter,
-            n_features,
-        })
+        Ok(())
     }
 }
 
@@ -311,6 +341,24 @@ impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for MultinomialNB<T
     }
 }
 
+impl<T: RealNumber, M: Matrix<T>> PredictorProba<M> for MultinomialNB<T, M> {
+    fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.predict_proba(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> ProbabilisticClassifier<T, M> for MultinomialNB<T, M> {
+    fn classes(&self) -> &Vec<T> {
+        self.classes()
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> OnlineEstimator<M, M::RowVector> for MultinomialNB<T, M> {
+    fn partial_fit(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        self.partial_fit(x, y)
+    }
+}
+
 impl<T: RealNumber, M: Matrix<T>> MultinomialNB<T, M> {
     /// Fits MultinomialNB with given data
     /// * `x` - training data of size NxM where N is the number of samples and M is the number of
@@ -329,6 +377,13 @@ impl<T: RealNumber, M: Matrix<T>> MultinomialNB<T, M> {
         Ok(Self { inner })
     }
 
+    /// Updates the classifier with a batch of training data, so it can be trained incrementally on data that arrives over time.
+    /// * `x` - training data of size NxM where N is the number of samples and M is the number of features.
+    /// * `y` - vector with target values (classes) of length N, every value should be one of the classes seen by `fit`.
+    pub fn partial_fit(&mut self, x: &M, y: &M::RowVector) -> Result<(), Failed> {
+        self.inner.distribution.partial_fit(x, y)
+    }
+
     /// Estimates the class labels for the provided data.
     /// * `x` - data of shape NxM where N is number of data points to estimate and M is number of features.
     /// Returns a vector of size N with class estimates.
@@ -336,6 +391,13 @@ impl<T: RealNumber, M: Matrix<T>> MultinomialNB<T, M> {
         self.inner.predict(x)
     }
 
+    /// Estimates the probability of every class for the provided data.
+    /// Returns a matrix of size NxK where K is the number of classes, columns follow the order of `classes`.
+    /// * `x` - data of shape NxM where N is number of data points to estimate and M is number of features.
+    pub fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.inner.predict_proba(x)
+    }
+
     /// Class labels known to the classifier.
     /// Returns a vector of size n_classes.
     pub fn classes(&self) -> &Vec<T> {
@@ -359,9 +421,9 @@ impl<T: RealNumber, M: Matrix<T>> MultinomialNB<T, M> {
         self.inner.distribution.n_features
     }
 
-    /// Number of samples encountered for each (class, feature)
+    /// Sum of each feature over the training samples of each class, the number of times a feature was observed when features are counts.
     /// Returns a 2d vector of shape (n_classes, n_features)
-    pub fn feature_count(&self) -> &Vec<Vec<usize>> {
+    pub fn feature_count(&self) -> &Vec<Vec<T>> {
         &self.inner.distribution.feature_count
     }
 }
@@ -370,6 +432,7 @@ impl<T: RealNumber, M: Matrix<T>> MultinomialNB<T, M> {
 mod tests {
     use super::*;
     use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::linalg::BaseMatrix;
 
     #[test]
     fn search_parameters() {
@@ -468,9 +531,9 @@ mod tests {
         assert_eq!(
             nb.feature_count(),
             &[
-                &[12, 20, 11, 24, 12, 14, 13, 17, 13, 18],
-                &[9, 6, 9, 4, 7, 3, 8, 5, 4, 9],
-                &[10, 12, 9, 9, 11, 3, 9, 18, 10, 10]
+                &[12., 20., 11., 24., 12., 14., 13., 17., 13., 18.],
+                &[9., 6., 9., 4., 7., 3., 8., 5., 4., 9.],
+                &[10., 12., 9., 9., 11., 3., 9., 18., 10., 10.]
             ]
         );
 
@@ -501,6 +564,40 @@ mod tests {
             1e-5
         ));
     }
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn multinomial_nb_fractional_features() {
+        // TF-IDF like weights
+        let x = DenseMatrix::<f64>::from_2d_array(&[
+            &[0.5, 1.5, 0., 0.],
+            &[0.2, 0.8, 0., 0.1],
+            &[0., 0.1, 1.2, 0.7],
+            &[0., 0., 0.9, 1.1],
+        ]);
+        let y = vec![0., 0., 1., 1.];
+        let nb =
+            MultinomialNB::fit(&x, &y, MultinomialNBParameters::default().with_alpha(0.5)).unwrap();
+
+        assert!(nb.feature_count()[0].approximate_eq(&vec![0.7, 2.3, 0., 0.1], 1e-12));
+        // (count + alpha) / (3.1 + 4 * alpha)
+        assert!(nb.feature_log_prob()[0].approximate_eq(
+            &vec![
+                (1.2f64 / 5.1).ln(),
+                (2.8f64 / 5.1).ln(),
+                (0.5f64 / 5.1).ln(),
+                (0.6f64 / 5.1).ln()
+            ],
+            1e-12
+        ));
+
+        let x_test =
+            DenseMatrix::<f64>::from_2d_array(&[&[0.3, 0.9, 0.1, 0.], &[0., 0.2, 0.6, 0.9]]);
+        assert_eq!(nb.predict(&x_test).unwrap(), vec![0., 1.]);
+
+        let x_negative = DenseMatrix::<f64>::from_2d_array(&[&[-1., 1.], &[1., 1.]]);
+        assert!(MultinomialNB::fit(&x_negative, &vec![0., 1.], Default::default()).is_err());
+    }
+
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     #[test]
     #[cfg(feature = "serde")]
@@ -519,4 +616,33 @@ mod tests {
 
         assert_eq!(mnb, deserialized_mnb);
     }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn partial_fit() {
+        let x = DenseMatrix::<f64>::from_2d_array(&[
+            &[1., 2., 0., 0., 0., 0.],
+            &[0., 2., 0., 0., 1., 0.],
+            &[0., 1., 0., 1., 0., 0.],
+            &[0., 1., 1., 0., 0., 1.],
+        ]);
+        let y = vec![0., 0., 0., 1.];
+        let mnb = MultinomialNB::fit(&x, &y, Default::default()).unwrap();
+
+        let mut online =
+            MultinomialNB::fit(&x.slice(2..4, 0..6), &vec![0., 1.], Default::default()).unwrap();
+        online
+            .partial_fit(&x.slice(0..2, 0..6), &vec![0., 0.])
+            .unwrap();
+
+        assert_eq!(online.class_count(), mnb.class_count());
+        assert_eq!(online.feature_count(), mnb.feature_count());
+        assert_eq!(online.feature_log_prob(), mnb.feature_log_prob());
+        assert_eq!(mnb.predict(&x).unwrap(), online.predict(&x).unwrap());
+
+        assert!(online.partial_fit(&x.slice(0..1, 0..6), &vec![2.]).is_err());
+        assert!(online
+            .partial_fit(&x.slice(0..1, 0..6).mul_scalar(-1.), &vec![0.])
+            .is_err());
+    }
 }
@@ -31,24 +31,27 @@
 //!
 //! variable `y_hat` will hold a vector with estimates of class labels
 //!
-use std::marker::PhantomData;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::algorithm::neighbour::{KNNAlgorithm, KNNAlgorithmName};
+use crate::algorithm::neighbour::{KNNAlgorithm, KNNAlgorithmName, RowDistance};
 use crate::api::{Predictor, SupervisedEstimator};
 use crate::error::Failed;
-use crate::linalg::{row_iter, Matrix};
+use crate::linalg::{row_iter, BaseVector, Matrix};
 use crate::math::distance::euclidian::Euclidian;
 use crate::math::distance::{Distance, Distances};
 use crate::math::num::RealNumber;
 use crate::neighbors::KNNWeightFunction;
+use crate::parallel::map_indices_with;
+use crate::validation::{check_x, check_y};
 
 /// `KNNClassifier` parameters. Use `Default::default()` for default values.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct KNNClassifierParameters<T: RealNumber, D: Distance<Vec<T>, T>> {
+pub struct KNNClassifierParameters<T: RealNumber, D: Distance<[T], T>> {
     #[cfg_attr(feature = "serde", serde(default))]
     /// a function that defines a distance between each pair of point in training data.
     /// This function should extend [`Distance`](../../math/distance/trait.Distance.html) trait.
@@ -71,15 +74,15 @@ pub struct KNNClassifierParameters<T: RealNumber, D: Distance<Vec<T>, T>> {
 /// K Nearest Neighbors Classifier
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug)]
-pub struct KNNClassifier<T: RealNumber, D: Distance<Vec<T>, T>> {
+pub struct KNNClassifier<T: RealNumber, D: Distance<[T], T>> {
     classes: Vec<T>,
     y: Vec<usize>,
-    knn_algorithm: KNNAlgorithm<T, D>,
+    knn_algorithm: KNNAlgorithm<T, RowDistance<D>>,
     weight: KNNWeightFunction,
     k: usize,
 }
 
-impl<T: RealNumber, D: Distance<Vec<T>, T>> KNNClassifierParameters<T, D> {
+impl<T: RealNumber, D: Distance<[T], T>> KNNClassifierParameters<T, D> {
     /// number of training samples to consider when estimating class for new point. Default value is 3.
     pub fn with_k(mut self, k: usize) -> Self {
         self.k = k;
@@ -88,7 +91,7 @@ impl<T: RealNumber, D: Distance<Vec<T>, T>> KNNClassifierParameters<T, D> {
     /// a function that defines a distance between each pair of point in training data.
     /// This function should extend [`Distance`](../../math/distance/trait.Distance.html) trait.
     /// See [`Distances`](../../math/distance/struct.Distances.html) for a list of available functions.
-    pub fn with_distance<DD: Distance<Vec<T>, T>>(
+    pub fn with_distance<DD: Distance<[T], T>>(
         self,
         distance: DD,
     ) -> KNNClassifierParameters<T, DD> {
@@ -124,7 +127,7 @@ impl<T: RealNumber> Default for KNNClassifierParameters<T, Euclidian> {
     }
 }
 
-impl<T: RealNumber, D: Distance<Vec<T>, T>> PartialEq for KNNClassifier<T, D> {
+impl<T: RealNumber, D: Distance<[T], T>> PartialEq for KNNClassifier<T, D> {
     fn eq(&self, other: &Self) -> bool {
         if self.classes.len() != other.classes.len()
             || self.k != other.k
@@ -147,7 +150,7 @@ impl<T: RealNumber, D: Distance<Vec<T>, T>> PartialEq for KNNClassifier<T, D> {
     }
 }
 
-impl<T: RealNumber, M: Matrix<T>, D: Distance<Vec<T>, T>>
+impl<T: RealNumber, M: Matrix<T>, D: Distance<[T], T>>
     SupervisedEstimator<M, M::RowVector, KNNClassifierParameters<T, D>> for KNNClassifier<T, D>
 {
     fn fit(
@@ -159,7 +162,7 @@ impl<T: RealNumber, M: Matrix<T>, D: Distance<Vec<T>, T>>
     }
 }
 
-impl<T: RealNumber, M: Matrix<T>, D: Distance<Vec<T>, T>> Predictor<M, M::RowVector>
+impl<T: RealNumber, M: Matrix<T>, D: Distance<[T], T>> Predictor<M, M::RowVector>
     for KNNClassifier<T, D>
 {
     fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
@@ -167,7 +170,7 @@ impl<T: RealNumber, M: Matrix<T>, D: Distance<Vec<T>, T>> Predictor<M, M::RowVec
     }
 }
 
-impl<T: RealNumber, D: Distance<Vec<T>, T>> KNNClassifier<T, D> {
+impl<T: RealNumber, D: Distance<[T], T>> KNNClassifier<T, D> {
     /// Fits KNN classifier to a NxM matrix where N is number of samples and M is number of features.
     /// * `x` - training data
     /// * `y` - vector with target values (classes) of length N    
@@ -177,6 +180,12 @@ impl<T: RealNumber, D: Distance<Vec<T>, T>> KNNClassifier<T, D> {
         y: &M::RowVector,
         parameters: KNNClassifierParameters<T, D>,
     ) -> Result<KNNClassifier<T, D>, Failed> {
+        check_x(
+            x,
+            parameters.distance.supports_missing_values(),
+            Failed::fit,
+        )?;
+        check_y(y, Failed::fit)?;
         let y_m = M::from_row_vector(y.clone());
 
         let (_, y_n) = y_m.shape();
@@ -210,7 +219,9 @@ impl<T: RealNumber, D: Distance<Vec<T>, T>> KNNClassifier<T, D> {
             classes,
             y: yi,
             k: parameters.k,
-            knn_algorithm: parameters.algorithm.fit(data, parameters.distance)?,
+            knn_algorithm: parameters
+                .algorithm
+                .fit(data, RowDistance(parameters.distance))?,
             weight: parameters.weight,
         })
     }
@@ -219,17 +230,28 @@ impl<T: RealNumber, D: Distance<Vec<T>, T>> KNNClassifier<T, D> {
     /// * `x` - data of shape NxM where N is number of data points to estimate and M is number of features.
     /// Returns a vector of size N with class estimates.
     pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed> {
-        let mut result = M::zeros(1, x.shape().0);
-
-        for (i, x) in row_iter(x).enumerate() {
-            result.set(0, i, self.classes[self.predict_for_row(x)?]);
-        }
+        check_x(
+            x,
+            self.knn_algorithm.supports_missing_values(),
+            Failed::predict,
+        )?;
+        let (n, m) = x.shape();
+        let y_hat = map_indices_with(
+            n,
+            || vec![T::zero(); m],
+            |row, i| {
+                x.copy_row_as_vec(i, row);
+                self.predict_for_row(row).map(|c| self.classes[c])
+            },
+        )
+        .into_iter()
+        .collect::<Result<Vec<T>, Failed>>()?;
 
-        Ok(result.to_row_vector())
+        Ok(M::RowVector::from_array(&y_hat))
     }
 
-    fn predict_for_row(&self, x: Vec<T>) -> Result<usize, Failed> {
-        let search_result = self.knn_algorithm.find(&x, self.k)?;
+    fn predict_for_row(&self, x: &Vec<T>) -> Result<usize, Failed> {
+        let search_result = self.knn_algorithm.find(x, self.k)?;
 
         let weights = self
             .weight
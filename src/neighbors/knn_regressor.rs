@@ -34,12 +34,13 @@
 //! variable `y_hat` will hold predicted value
 //!
 //!
-use std::marker::PhantomData;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::algorithm::neighbour::{KNNAlgorithm, KNNAlgorithmName};
+use crate::algorithm::neighbour::{KNNAlgorithm, KNNAlgorithmName, RowDistance};
 use crate::api::{Predictor, SupervisedEstimator};
 use crate::error::Failed;
 use crate::linalg::{row_iter, BaseVector, Matrix};
@@ -47,11 +48,13 @@ use crate::math::distance::euclidian::Euclidian;
 use crate::math::distance::{Distance, Distances};
 use crate::math::num::RealNumber;
 use crate::neighbors::KNNWeightFunction;
+use crate::parallel::map_indices_with;
+use crate::validation::{check_x, check_y};
 
 /// `KNNRegressor` parameters. Use `Default::default()` for default values.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct KNNRegressorParameters<T: RealNumber, D: Distance<Vec<T>, T>> {
+pub struct KNNRegressorParameters<T: RealNumber, D: Distance<[T], T>> {
     #[cfg_attr(feature = "serde", serde(default))]
     /// a function that defines a distance between each pair of point in training data.
     /// This function should extend [`Distance`](../../math/distance/trait.Distance.html) trait.
@@ -74,14 +77,14 @@ pub struct KNNRegressorParameters<T: RealNumber, D: Distance<Vec<T>, T>> {
 /// K Nearest Neighbors Regressor
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug)]
-pub struct KNNRegressor<T: RealNumber, D: Distance<Vec<T>, T>> {
+pub struct KNNRegressor<T: RealNumber, D: Distance<[T], T>> {
     y: Vec<T>,
-    knn_algorithm: KNNAlgorithm<T, D>,
+    knn_algorithm: KNNAlgorithm<T, RowDistance<D>>,
     weight: KNNWeightFunction,
     k: usize,
 }
 
-impl<T: RealNumber, D: Distance<Vec<T>, T>> KNNRegressorParameters<T, D> {
+impl<T: RealNumber, D: Distance<[T], T>> KNNRegressorParameters<T, D> {
     /// number of training samples to consider when estimating class for new point. Default value is 3.
     pub fn with_k(mut self, k: usize) -> Self {
         self.k = k;
@@ -90,7 +93,7 @@ impl<T: RealNumber, D: Distance<Vec<T>, T>> KNNRegressorParameters<T, D> {
     /// a function that defines a distance between each pair of point in training data.
     /// This function should extend [`Distance`](../../math/distance/trait.Distance.html) trait.
     /// See [`Distances`](../../math/distance/struct.Distances.html) for a list of available functions.
-    pub fn with_distance<DD: Distance<Vec<T>, T>>(
+    pub fn with_distance<DD: Distance<[T], T>>(
         self,
         distance: DD,
     ) -> KNNRegressorParameters<T, DD> {
@@ -126,7 +129,7 @@ impl<T: RealNumber> Default for KNNRegressorParameters<T, Euclidian> {
     }
 }
 
-impl<T: RealNumber, D: Distance<Vec<T>, T>> PartialEq for KNNRegressor<T, D> {
+impl<T: RealNumber, D: Distance<[T], T>> PartialEq for KNNRegressor<T, D> {
     fn eq(&self, other: &Self) -> bool {
         if self.k != other.k || self.y.len() != other.y.len() {
             false
@@ -141,7 +144,7 @@ impl<T: RealNumber, D: Distance<Vec<T>, T>> PartialEq for KNNRegressor<T, D> {
     }
 }
 
-impl<T: RealNumber, M: Matrix<T>, D: Distance<Vec<T>, T>>
+impl<T: RealNumber, M: Matrix<T>, D: Distance<[T], T>>
     SupervisedEstimator<M, M::RowVector, KNNRegressorParameters<T, D>> for KNNRegressor<T, D>
 {
     fn fit(
@@ -153,7 +156,7 @@ impl<T: RealNumber, M: Matrix<T>, D: Distance<Vec<T>, T>>
     }
 }
 
-impl<T: RealNumber, M: Matrix<T>, D: Distance<Vec<T>, T>> Predictor<M, M::RowVector>
+impl<T: RealNumber, M: Matrix<T>, D: Distance<[T], T>> Predictor<M, M::RowVector>
     for KNNRegressor<T, D>
 {
     fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
@@ -161,7 +164,7 @@ impl<T: RealNumber, M: Matrix<T>, D: Distance<Vec<T>, T>> Predictor<M, M::RowVec
     }
 }
 
-impl<T: RealNumber, D: Distance<Vec<T>, T>> KNNRegressor<T, D> {
+impl<T: RealNumber, D: Distance<[T], T>> KNNRegressor<T, D> {
     /// Fits KNN regressor to a NxM matrix where N is number of samples and M is number of features.
     /// * `x` - training data
     /// * `y` - vector with real values    
@@ -171,6 +174,12 @@ impl<T: RealNumber, D: Distance<Vec<T>, T>> KNNRegressor<T, D> {
         y: &M::RowVector,
         parameters: KNNRegressorParameters<T, D>,
     ) -> Result<KNNRegressor<T, D>, Failed> {
+        check_x(
+            x,
+            parameters.distance.supports_missing_values(),
+            Failed::fit,
+        )?;
+        check_y(y, Failed::fit)?;
         let y_m = M::from_row_vector(y.clone());
 
         let (_, y_n) = y_m.shape();
@@ -195,7 +204,9 @@ impl<T: RealNumber, D: Distance<Vec<T>, T>> KNNRegressor<T, D> {
         Ok(KNNRegressor {
             y: y.to_vec(),
             k: parameters.k,
-            knn_algorithm: parameters.algorithm.fit(data, parameters.distance)?,
+            knn_algorithm: parameters
+                .algorithm
+                .fit(data, RowDistance(parameters.distance))?,
             weight: parameters.weight,
         })
     }
@@ -204,17 +215,28 @@ impl<T: RealNumber, D: Distance<Vec<T>, T>> KNNRegressor<T, D> {
     /// * `x` - data of shape NxM where N is number of data points to estimate and M is number of features.
     /// Returns a vector of size N with estimates.
     pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed> {
-        let mut result = M::zeros(1, x.shape().0);
-
-        for (i, x) in row_iter(x).enumerate() {
-            result.set(0, i, self.predict_for_row(x)?);
-        }
+        check_x(
+            x,
+            self.knn_algorithm.supports_missing_values(),
+            Failed::predict,
+        )?;
+        let (n, m) = x.shape();
+        let y_hat = map_indices_with(
+            n,
+            || vec![T::zero(); m],
+            |row, i| {
+                x.copy_row_as_vec(i, row);
+                self.predict_for_row(row)
+            },
+        )
+        .into_iter()
+        .collect::<Result<Vec<T>, Failed>>()?;
 
-        Ok(result.to_row_vector())
+        Ok(M::RowVector::from_array(&y_hat))
     }
 
-    fn predict_for_row(&self, x: Vec<T>) -> Result<T, Failed> {
-        let search_result = self.knn_algorithm.find(&x, self.k)?;
+    fn predict_for_row(&self, x: &Vec<T>) -> Result<T, Failed> {
+        let search_result = self.knn_algorithm.find(x, self.k)?;
         let mut result = T::zero();
 
         let weights = self
@@ -260,6 +282,37 @@ mod tests {
         }
     }
 
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn knn_missing_values() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 2.],
+            &[3., f64::NAN],
+            &[5., 6.],
+            &[f64::NAN, 8.],
+            &[9., 10.],
+        ]);
+        let y: Vec<f64> = vec![1., 2., 3., 4., 5.];
+
+        assert_eq!(
+            KNNRegressor::fit(&x, &y, Default::default()).unwrap_err(),
+            Failed::fit("x contains NaN at row 1, column 1, impute missing values or use an estimator that supports them")
+        );
+
+        let knn = KNNRegressor::fit(
+            &x,
+            &y,
+            KNNRegressorParameters::default()
+                .with_k(1)
+                .with_distance(Distances::nan_euclidian()),
+        )
+        .unwrap();
+        assert_eq!(knn.predict(&x).unwrap(), y);
+        assert!(knn
+            .predict(&DenseMatrix::from_2d_array(&[&[f64::INFINITY, 1.]]))
+            .is_err());
+    }
+
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     #[test]
     fn knn_fit_predict_uniform() {
@@ -33,6 +33,7 @@
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 
 use crate::math::num::RealNumber;
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -65,7 +66,7 @@ impl Default for KNNWeightFunction {
 }
 
 impl KNNWeightFunction {
-    fn calc_weights<T: RealNumber>(&self, distances: Vec<T>) -> std::vec::Vec<T> {
+    pub(crate) fn calc_weights<T: RealNumber>(&self, distances: Vec<T>) -> alloc::vec::Vec<T> {
         match *self {
             KNNWeightFunction::Distance => {
                 // if there are any points that has zero distance from one or more training points,
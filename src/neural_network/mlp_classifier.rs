@@ -0,0 +1,649 @@
+//! # Multi-layer Perceptron Classifier
+//!
+//! A [multi-layer perceptron](../index.html) with a softmax output layer that has one unit per class. The network estimates the class probabilities
+//!
+//! \\[\hat{p}_{ik} = \frac{e^{z_{ik}}}{\sum_j e^{z_{ij}}}\\]
+//!
+//! where \\(z_i\\) are the outputs of the last layer for observation \\(i\\), by minimizing the cross-entropy of the predicted probabilities and the one-hot encoded class labels
+//!
+//! \\[L = -\frac{1}{n} \sum_{i=1}^n \sum_{k=1}^K y_{ik} \log \hat{p}_{ik}\\]
+//!
+//! The gradients of the loss are calculated with backpropagation. The weights are fitted with L-BFGS, which uses all training data in every iteration,
+//! or, for larger datasets, with mini-batch SGD or Adam, optionally stopped early when the loss on held out data stops decreasing, see [`MLPSolverName`](../enum.MLPSolverName.html).
+//! Neural networks are sensitive to feature scaling, standardize your features, e.g. with [`StandardScaler`](../../preprocessing/numerical/index.html), before fitting.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::neural_network::mlp_classifier::*;
+//! use smartcore::neural_network::Activation;
+//!
+//! // XOR, classes that can't be separated by a linear model
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[0., 0.],
+//!     &[0., 1.],
+//!     &[1., 0.],
+//!     &[1., 1.],
+//! ]);
+//! let y: Vec<f64> = vec![0., 1., 1., 0.];
+//!
+//! let mlp = MLPClassifier::fit(
+//!     &x,
+//!     &y,
+//!     MLPClassifierParameters::default()
+//!         .with_hidden_layer_sizes(vec![8])
+//!         .with_activation(Activation::Tanh)
+//!         .with_alpha(0.)
+//!         .with_seed(3),
+//! )
+//! .unwrap();
+//!
+//! let y_hat = mlp.predict(&x).unwrap();
+//! let probabilities = mlp.predict_proba(&x).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Learning representations by back-propagating errors", Rumelhart D.E., Hinton G.E., Williams R.J., 1986](https://www.nature.com/articles/323533a0)
+//! * ["Pattern Recognition and Machine Learning", C.M. Bishop, 5.2 Network Training, 5.3 Error Backpropagation](https://www.microsoft.com/en-us/research/uploads/prod/2006/01/Bishop-Pattern-Recognition-and-Machine-Learning-2006.pdf)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, PredictorProba, ProbabilisticClassifier, SupervisedEstimator};
+use crate::callback::{FitCallback, NoCallback};
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::neural_network::network::{Loss, Network, TrainingOptions};
+use crate::neural_network::{Activation, MLPSolverName};
+use crate::validation::{check_fit, check_predict};
+
+/// Multi-layer perceptron classifier parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct MLPClassifierParameters<T: RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of units in every hidden layer.
+    pub hidden_layer_sizes: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Activation function of the hidden layers.
+    pub activation: Activation,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Strength of the L2 penalty of the weights.
+    pub alpha: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Optimizer used to fit the weights.
+    pub solver: MLPSolverName,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Maximum number of iterations of L-BFGS or maximum number of epochs of the mini-batch solvers.
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// L-BFGS stops when the largest absolute value of the gradient drops below `tol`,
+    /// the mini-batch solvers stop when the loss has not decreased by at least `tol` for `n_iter_no_change` consecutive epochs.
+    pub tol: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of observations in every mini-batch, `min(200, n)` when not set. Not used by L-BFGS.
+    pub batch_size: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Step size of SGD, initial step size of the adaptive solvers.
+    pub learning_rate_init: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to shuffle the training data before every epoch of the mini-batch solvers.
+    pub shuffle: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to hold out `validation_fraction` of the training data and stop the mini-batch solvers when the loss on the held out data stops decreasing.
+    /// The weights with the lowest validation loss are kept.
+    pub early_stopping: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of the training data held out for early stopping.
+    pub validation_fraction: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of epochs with no improvement to wait before stopping the mini-batch solvers.
+    pub n_iter_no_change: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used to initialize the weights, split and shuffle the training data.
+    pub seed: Option<u64>,
+}
+
+/// Multi-layer perceptron classifier
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct MLPClassifier<T: RealNumber, M: Matrix<T>> {
+    classes: Vec<T>,
+    network: Network<T, M>,
+    n_iter: usize,
+    loss_curve: Vec<T>,
+    validation_loss_curve: Vec<T>,
+}
+
+impl<T: RealNumber> MLPClassifierParameters<T> {
+    /// Number of units in every hidden layer.
+    pub fn with_hidden_layer_sizes(mut self, hidden_layer_sizes: Vec<usize>) -> Self {
+        self.hidden_layer_sizes = hidden_layer_sizes;
+        self
+    }
+    /// Activation function of the hidden layers.
+    pub fn with_activation(mut self, activation: Activation) -> Self {
+        self.activation = activation;
+        self
+    }
+    /// Strength of the L2 penalty of the weights.
+    pub fn with_alpha(mut self, alpha: T) -> Self {
+        self.alpha = alpha;
+        self
+    }
+    /// Optimizer used to fit the weights.
+    pub fn with_solver(mut self, solver: MLPSolverName) -> Self {
+        self.solver = solver;
+        self
+    }
+    /// Maximum number of iterations of L-BFGS or maximum number of epochs of the mini-batch solvers.
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Tolerance of the stopping criterion.
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Number of observations in every mini-batch.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+    /// Step size of SGD, initial step size of the adaptive solvers.
+    pub fn with_learning_rate_init(mut self, learning_rate_init: T) -> Self {
+        self.learning_rate_init = learning_rate_init;
+        self
+    }
+    /// Whether to shuffle the training data before every epoch.
+    pub fn with_shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+    /// Whether to stop training when the loss on held out data stops decreasing.
+    pub fn with_early_stopping(mut self, early_stopping: bool) -> Self {
+        self.early_stopping = early_stopping;
+        self
+    }
+    /// Fraction of the training data held out for early stopping.
+    pub fn with_validation_fraction(mut self, validation_fraction: T) -> Self {
+        self.validation_fraction = validation_fraction;
+        self
+    }
+    /// Number of epochs with no improvement to wait before stopping.
+    pub fn with_n_iter_no_change(mut self, n_iter_no_change: usize) -> Self {
+        self.n_iter_no_change = n_iter_no_change;
+        self
+    }
+    /// Seed used to initialize the weights, split and shuffle the training data.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl<T: RealNumber> Default for MLPClassifierParameters<T> {
+    fn default() -> Self {
+        MLPClassifierParameters {
+            hidden_layer_sizes: vec![100],
+            activation: Activation::default(),
+            alpha: T::from_f64(1e-4).unwrap(),
+            solver: MLPSolverName::default(),
+            max_iter: 200,
+            tol: T::from_f64(1e-4).unwrap(),
+            batch_size: None,
+            learning_rate_init: T::from_f64(1e-3).unwrap(),
+            shuffle: true,
+            early_stopping: false,
+            validation_fraction: T::from_f64(0.1).unwrap(),
+            n_iter_no_change: 10,
+            seed: None,
+        }
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> PartialEq for MLPClassifier<T, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.classes == other.classes && self.network == other.network
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> SupervisedEstimator<M, M::RowVector, MLPClassifierParameters<T>>
+    for MLPClassifier<T, M>
+{
+    fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: MLPClassifierParameters<T>,
+    ) -> Result<Self, Failed> {
+        MLPClassifier::fit(x, y, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for MLPClassifier<T, M> {
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> PredictorProba<M> for MLPClassifier<T, M> {
+    fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.predict_proba(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> ProbabilisticClassifier<T, M> for MLPClassifier<T, M> {
+    fn classes(&self) -> &Vec<T> {
+        self.classes()
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> MLPClassifier<T, M> {
+    /// Fits the classifier to a training dataset.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target class values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: MLPClassifierParameters<T>,
+    ) -> Result<MLPClassifier<T, M>, Failed> {
+        MLPClassifier::fit_with_callback(x, y, parameters, &mut NoCallback)
+    }
+
+    /// Fits the classifier like `fit` and calls `callback` after every epoch of the mini-batch solvers or iteration of L-BFGS, see [callbacks](../../callback/index.html).
+    /// The callback receives the training loss and, with `early_stopping`, the negative loss on the held out data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target class values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    /// * `callback` - monitors the training and can stop it
+    pub fn fit_with_callback(
+        x: &M,
+        y: &M::RowVector,
+        parameters: MLPClassifierParameters<T>,
+        callback: &mut dyn FitCallback<T>,
+    ) -> Result<MLPClassifier<T, M>, Failed> {
+        check_fit(x, y)?;
+        let (n, num_features) = x.shape();
+        if n != y.len() {
+            return Err(Failed::fit(
+                "Number of rows of X doesn't match number of rows of Y",
+            ));
+        }
+        if parameters.hidden_layer_sizes.contains(&0) {
+            return Err(Failed::fit("hidden layers should have at least one unit"));
+        }
+        if parameters.alpha < T::zero() {
+            return Err(Failed::fit("alpha should be >= 0"));
+        }
+        if parameters.max_iter == 0 {
+            return Err(Failed::fit("max_iter should be > 0"));
+        }
+
+        let classes = y.unique();
+        if classes.len() < 2 {
+            return Err(Failed::fit(&format!(
+                "Incorrect number of classes: {}. Should be >= 2.",
+                classes.len()
+            )));
+        }
+
+        let mut targets = M::zeros(n, classes.len());
+        for i in 0..n {
+            let k = classes
+                .iter()
+                .position(|&c| c == y.get(i))
+                .ok_or_else(|| Failed::fit("target values should be finite"))?;
+            targets.set(i, k, T::one());
+        }
+
+        let mut layer_sizes = Vec::with_capacity(parameters.hidden_layer_sizes.len() + 2);
+        layer_sizes.push(num_features);
+        layer_sizes.extend_from_slice(&parameters.hidden_layer_sizes);
+        layer_sizes.push(classes.len());
+
+        let mut network = Network::new(
+            &layer_sizes,
+            parameters.activation,
+            Loss::CrossEntropy,
+            parameters.seed,
+        );
+        let history = network.fit(
+            x,
+            &targets,
+            &TrainingOptions {
+                solver: parameters.solver,
+                alpha: parameters.alpha,
+                max_iter: parameters.max_iter,
+                tol: parameters.tol,
+                batch_size: parameters.batch_size,
+                learning_rate: parameters.learning_rate_init,
+                shuffle: parameters.shuffle,
+                early_stopping: parameters.early_stopping,
+                validation_fraction: parameters.validation_fraction,
+                n_iter_no_change: parameters.n_iter_no_change,
+                seed: parameters.seed,
+            },
+            callback,
+        )?;
+
+        Ok(MLPClassifier {
+            classes,
+            network,
+            n_iter: history.n_iter,
+            loss_curve: history.loss_curve,
+            validation_loss_curve: history.validation_loss_curve,
+        })
+    }
+
+    /// Predicts class labels, the class with the highest estimated probability.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        let probabilities = self.predict_proba(x)?;
+        let predictions: Vec<T> = probabilities
+            .argmax()
+            .into_iter()
+            .map(|k| self.classes[k])
+            .collect();
+        Ok(M::RowVector::from_array(&predictions))
+    }
+
+    /// Estimates class probabilities.
+    /// Returns _KxC_ matrix, where _C_ is the number of classes, columns are ordered by class label.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        check_predict(x)?;
+        if x.shape().1 != self.network.n_inputs() {
+            return Err(Failed::predict(&format!(
+                "Expected {} features, got {}",
+                self.network.n_inputs(),
+                x.shape().1
+            )));
+        }
+        Ok(self.network.predict(x))
+    }
+
+    /// Get class labels, in the order of the columns of [`predict_proba`](MLPClassifier::predict_proba).
+    pub fn classes(&self) -> &Vec<T> {
+        &self.classes
+    }
+
+    /// Weight matrices of all layers. The weights of layer _l_ form an _IxO_ matrix, where _I_ is the number of inputs and _O_ the number of units of the layer.
+    pub fn weights(&self) -> &[M] {
+        self.network.weights()
+    }
+
+    /// Bias vectors of all layers.
+    pub fn biases(&self) -> &[Vec<T>] {
+        self.network.biases()
+    }
+
+    /// Number of iterations of L-BFGS or number of epochs of the mini-batch solvers.
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+
+    /// Mean training loss after every epoch of the mini-batch solvers. L-BFGS records the final loss only.
+    pub fn loss_curve(&self) -> &Vec<T> {
+        &self.loss_curve
+    }
+
+    /// Loss on the held out data after every epoch, empty unless the network was trained with early stopping.
+    pub fn validation_loss_curve(&self) -> &Vec<T> {
+        &self.validation_loss_curve
+    }
+
+    /// Penalized cross-entropy loss of the fitted network on the training data.
+    pub fn loss(&self) -> T {
+        *self.loss_curve.last().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::callback::{FitAction, FitProgress};
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::metrics::accuracy;
+
+    fn blobs() -> (DenseMatrix<f64>, Vec<f64>) {
+        let mut values = Vec::new();
+        let mut y = Vec::new();
+        for i in 0..90 {
+            let class = i % 3;
+            let jitter = ((i * 37) % 11) as f64 / 20. - 0.25;
+            let (a, b) = match class {
+                0 => (-1., -1.),
+                1 => (1., -1.),
+                _ => (0., 1.),
+            };
+            values.push(vec![a + jitter, b - jitter]);
+            y.push(class as f64);
+        }
+        (DenseMatrix::from_2d_vec(&values), y)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn xor() {
+        let x = DenseMatrix::from_2d_array(&[&[0., 0.], &[0., 1.], &[1., 0.], &[1., 1.]]);
+        let y: Vec<f64> = vec![0., 1., 1., 0.];
+
+        for activation in [Activation::ReLU, Activation::Tanh, Activation::Logistic] {
+            let mlp = MLPClassifier::fit(
+                &x,
+                &y,
+                MLPClassifierParameters::default()
+                    .with_hidden_layer_sizes(vec![8])
+                    .with_activation(activation)
+                    .with_alpha(0.)
+                    .with_seed(3),
+            )
+            .unwrap();
+
+            assert_eq!(mlp.predict(&x).unwrap(), y);
+            assert_eq!(mlp.weights().len(), 2);
+            assert_eq!(mlp.weights()[0].shape(), (2, 8));
+            assert_eq!(mlp.biases()[1].len(), 2);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn multiclass() {
+        let (x, y) = blobs();
+
+        let mlp = MLPClassifier::fit(
+            &x,
+            &y,
+            MLPClassifierParameters::default()
+                .with_hidden_layer_sizes(vec![10, 5])
+                .with_seed(1),
+        )
+        .unwrap();
+
+        assert!(accuracy(&y, &mlp.predict(&x).unwrap()) > 0.95);
+        assert_eq!(mlp.classes(), &vec![0., 1., 2.]);
+
+        let probabilities = mlp.predict_proba(&x).unwrap();
+        assert_eq!(probabilities.shape(), (90, 3));
+        for i in 0..90 {
+            let row_sum: f64 = (0..3).map(|j| probabilities.get(i, j)).sum();
+            assert!((row_sum - 1.).abs() < 1e-9);
+        }
+
+        assert!(mlp.predict(&DenseMatrix::zeros(1, 3)).is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn mini_batch() {
+        let (x, y) = blobs();
+
+        for (solver, learning_rate) in [
+            (MLPSolverName::Adam, 0.01),
+            (MLPSolverName::RMSProp, 0.01),
+            (MLPSolverName::AdaGrad, 0.1),
+            (MLPSolverName::SGD, 0.1),
+        ] {
+            let mlp = MLPClassifier::fit(
+                &x,
+                &y,
+                MLPClassifierParameters::default()
+                    .with_hidden_layer_sizes(vec![10])
+                    .with_solver(solver)
+                    .with_batch_size(16)
+                    .with_learning_rate_init(learning_rate)
+                    .with_seed(1),
+            )
+            .unwrap();
+
+            assert!(accuracy(&y, &mlp.predict(&x).unwrap()) > 0.95);
+            assert_eq!(mlp.loss_curve().len(), mlp.n_iter());
+            assert!(mlp.loss_curve()[mlp.n_iter() - 1] < mlp.loss_curve()[0]);
+            assert!(mlp.validation_loss_curve().is_empty());
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn early_stopping() {
+        let (x, y) = blobs();
+
+        let mlp = MLPClassifier::fit(
+            &x,
+            &y,
+            MLPClassifierParameters::default()
+                .with_hidden_layer_sizes(vec![10])
+                .with_solver(MLPSolverName::Adam)
+                .with_learning_rate_init(0.01)
+                .with_early_stopping(true)
+                .with_validation_fraction(0.2)
+                .with_n_iter_no_change(5)
+                .with_max_iter(1000)
+                .with_seed(1),
+        )
+        .unwrap();
+
+        assert!(mlp.n_iter() < 1000);
+        assert_eq!(mlp.validation_loss_curve().len(), mlp.n_iter());
+        assert!(accuracy(&y, &mlp.predict(&x).unwrap()) > 0.95);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_with_callback() {
+        let (x, y) = blobs();
+
+        let mut scores = Vec::new();
+        let mlp = MLPClassifier::fit_with_callback(
+            &x,
+            &y,
+            MLPClassifierParameters::default()
+                .with_hidden_layer_sizes(vec![10])
+                .with_solver(MLPSolverName::Adam)
+                .with_early_stopping(true)
+                .with_seed(1),
+            &mut |progress: &FitProgress<f64>| {
+                scores.push(progress.validation_score.unwrap());
+                if progress.iteration < 2 {
+                    FitAction::Continue
+                } else {
+                    FitAction::Stop
+                }
+            },
+        )
+        .unwrap();
+
+        assert_eq!(mlp.n_iter(), 2);
+        assert_eq!(scores.len(), 2);
+        assert_eq!(-scores[1], mlp.validation_loss_curve()[1]);
+
+        let mut iterations = 0;
+        MLPClassifier::fit_with_callback(
+            &x,
+            &y,
+            MLPClassifierParameters::default()
+                .with_hidden_layer_sizes(vec![10])
+                .with_solver(MLPSolverName::LBFGS)
+                .with_seed(1),
+            &mut |progress: &FitProgress<f64>| {
+                iterations = progress.iteration;
+                assert!(progress.validation_score.is_none());
+                if progress.iteration < 3 {
+                    FitAction::Continue
+                } else {
+                    FitAction::Stop
+                }
+            },
+        )
+        .unwrap();
+        assert_eq!(iterations, 3);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn invalid_parameters() {
+        let x = DenseMatrix::from_2d_array(&[&[0., 0.], &[1., 1.]]);
+
+        assert!(MLPClassifier::fit(&x, &vec![1., 1.], Default::default()).is_err());
+        assert!(MLPClassifier::fit(
+            &x,
+            &vec![0., 1.],
+            MLPClassifierParameters::default().with_hidden_layer_sizes(vec![0])
+        )
+        .is_err());
+        assert!(MLPClassifier::fit(&x, &vec![0., 1., 1.], Default::default()).is_err());
+        assert!(MLPClassifier::fit(
+            &x,
+            &vec![0., 1.],
+            MLPClassifierParameters::default()
+                .with_solver(MLPSolverName::Adam)
+                .with_early_stopping(true)
+                .with_validation_fraction(0.9)
+        )
+        .is_err());
+        assert!(MLPClassifier::fit(
+            &x,
+            &vec![0., 1.],
+            MLPClassifierParameters::default()
+                .with_solver(MLPSolverName::SGD)
+                .with_batch_size(0)
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let x = DenseMatrix::from_2d_array(&[&[0., 0.], &[0., 1.], &[1., 0.], &[1., 1.]]);
+        let y: Vec<f64> = vec![0., 1., 1., 0.];
+
+        let mlp = MLPClassifier::fit(
+            &x,
+            &y,
+            MLPClassifierParameters::default()
+                .with_hidden_layer_sizes(vec![4])
+                .with_seed(1),
+        )
+        .unwrap();
+
+        let deserialized_mlp: MLPClassifier<f64, DenseMatrix<f64>> =
+            serde_json::from_str(&serde_json::to_string(&mlp).unwrap()).unwrap();
+
+        assert_eq!(
+            mlp.predict(&x).unwrap(),
+            deserialized_mlp.predict(&x).unwrap()
+        );
+    }
+}
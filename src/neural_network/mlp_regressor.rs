@@ -0,0 +1,547 @@
+//! # Multi-layer Perceptron Regressor
+//!
+//! A [multi-layer perceptron](../index.html) with a single linear output unit, \\(\hat{y}_i = a_i^{(L-1)} W^{(L-1)} + b^{(L-1)}\\), fitted by minimizing one of two losses of the residuals \\(r_i = \hat{y}_i - y_i\\):
+//!
+//! * `SquaredError`, \\(\frac{1}{2} r_i^2\\), estimates the conditional mean of the target.
+//! * `Huber`, \\(\frac{1}{2} r_i^2\\) if \\(\vert r_i \vert \le \epsilon\\) and \\(\epsilon (\vert r_i \vert - \frac{1}{2}\epsilon)\\) otherwise.
+//!   Large residuals are penalized linearly, so outliers in the target have less influence on the fit.
+//!
+//! Both losses are averaged over the training set and the weights are penalized with the L2 penalty \\(\frac{\alpha}{2n} \sum_l \lVert W^{(l)} \rVert_F^2\\).
+//! The gradients of the loss are calculated with backpropagation. The weights are fitted with L-BFGS, which uses all training data in every iteration,
+//! or, for larger datasets, with mini-batch SGD or Adam, optionally stopped early when the loss on held out data stops decreasing, see [`MLPSolverName`](../enum.MLPSolverName.html).
+//! Neural networks are sensitive to feature scaling, standardize your features, e.g. with [`StandardScaler`](../../preprocessing/numerical/index.html), before fitting.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::neural_network::mlp_regressor::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[-1.0],
+//!     &[-0.5],
+//!     &[0.0],
+//!     &[0.5],
+//!     &[1.0],
+//! ]);
+//! let y: Vec<f64> = vec![1.0, 0.25, 0.0, 0.25, 1.0];
+//!
+//! let mlp = MLPRegressor::fit(
+//!     &x,
+//!     &y,
+//!     MLPRegressorParameters::default()
+//!         .with_hidden_layer_sizes(vec![10])
+//!         .with_seed(1),
+//! )
+//! .unwrap();
+//!
+//! let y_hat = mlp.predict(&x).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Learning representations by back-propagating errors", Rumelhart D.E., Hinton G.E., Williams R.J., 1986](https://www.nature.com/articles/323533a0)
+//! * ["Robust Estimation of a Location Parameter", Huber P.J., 1964](https://projecteuclid.org/euclid.aoms/1177703732)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::callback::{FitCallback, NoCallback};
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::neural_network::network::{Loss, Network, TrainingOptions};
+use crate::neural_network::{Activation, MLPSolverName};
+use crate::validation::{check_fit, check_predict};
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+/// Loss minimized by the regressor.
+pub enum MLPRegressorLoss {
+    #[default]
+    /// Half of the squared error.
+    SquaredError,
+    /// Huber loss, quadratic for residuals up to `epsilon` and linear beyond.
+    Huber,
+}
+
+/// Multi-layer perceptron regressor parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct MLPRegressorParameters<T: RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of units in every hidden layer.
+    pub hidden_layer_sizes: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Activation function of the hidden layers.
+    pub activation: Activation,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Loss function.
+    pub loss: MLPRegressorLoss,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Threshold of the Huber loss, residuals larger than `epsilon` are penalized linearly.
+    pub epsilon: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Strength of the L2 penalty of the weights.
+    pub alpha: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Optimizer used to fit the weights.
+    pub solver: MLPSolverName,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Maximum number of iterations of L-BFGS or maximum number of epochs of the mini-batch solvers.
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// L-BFGS stops when the largest absolute value of the gradient drops below `tol`,
+    /// the mini-batch solvers stop when the loss has not decreased by at least `tol` for `n_iter_no_change` consecutive epochs.
+    pub tol: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of observations in every mini-batch, `min(200, n)` when not set. Not used by L-BFGS.
+    pub batch_size: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Step size of SGD, initial step size of the adaptive solvers.
+    pub learning_rate_init: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to shuffle the training data before every epoch of the mini-batch solvers.
+    pub shuffle: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to hold out `validation_fraction` of the training data and stop the mini-batch solvers when the loss on the held out data stops decreasing.
+    /// The weights with the lowest validation loss are kept.
+    pub early_stopping: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of the training data held out for early stopping.
+    pub validation_fraction: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of epochs with no improvement to wait before stopping the mini-batch solvers.
+    pub n_iter_no_change: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used to initialize the weights, split and shuffle the training data.
+    pub seed: Option<u64>,
+}
+
+/// Multi-layer perceptron regressor
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct MLPRegressor<T: RealNumber, M: Matrix<T>> {
+    network: Network<T, M>,
+    n_iter: usize,
+    loss_curve: Vec<T>,
+    validation_loss_curve: Vec<T>,
+}
+
+impl<T: RealNumber> MLPRegressorParameters<T> {
+    /// Number of units in every hidden layer.
+    pub fn with_hidden_layer_sizes(mut self, hidden_layer_sizes: Vec<usize>) -> Self {
+        self.hidden_layer_sizes = hidden_layer_sizes;
+        self
+    }
+    /// Activation function of the hidden layers.
+    pub fn with_activation(mut self, activation: Activation) -> Self {
+        self.activation = activation;
+        self
+    }
+    /// Loss function.
+    pub fn with_loss(mut self, loss: MLPRegressorLoss) -> Self {
+        self.loss = loss;
+        self
+    }
+    /// Threshold of the Huber loss.
+    pub fn with_epsilon(mut self, epsilon: T) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+    /// Strength of the L2 penalty of the weights.
+    pub fn with_alpha(mut self, alpha: T) -> Self {
+        self.alpha = alpha;
+        self
+    }
+    /// Optimizer used to fit the weights.
+    pub fn with_solver(mut self, solver: MLPSolverName) -> Self {
+        self.solver = solver;
+        self
+    }
+    /// Maximum number of iterations of L-BFGS or maximum number of epochs of the mini-batch solvers.
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Tolerance of the stopping criterion.
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Number of observations in every mini-batch.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+    /// Step size of SGD, initial step size of the adaptive solvers.
+    pub fn with_learning_rate_init(mut self, learning_rate_init: T) -> Self {
+        self.learning_rate_init = learning_rate_init;
+        self
+    }
+    /// Whether to shuffle the training data before every epoch.
+    pub fn with_shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+    /// Whether to stop training when the loss on held out data stops decreasing.
+    pub fn with_early_stopping(mut self, early_stopping: bool) -> Self {
+        self.early_stopping = early_stopping;
+        self
+    }
+    /// Fraction of the training data held out for early stopping.
+    pub fn with_validation_fraction(mut self, validation_fraction: T) -> Self {
+        self.validation_fraction = validation_fraction;
+        self
+    }
+    /// Number of epochs with no improvement to wait before stopping.
+    pub fn with_n_iter_no_change(mut self, n_iter_no_change: usize) -> Self {
+        self.n_iter_no_change = n_iter_no_change;
+        self
+    }
+    /// Seed used to initialize the weights, split and shuffle the training data.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl<T: RealNumber> Default for MLPRegressorParameters<T> {
+    fn default() -> Self {
+        MLPRegressorParameters {
+            hidden_layer_sizes: vec![100],
+            activation: Activation::default(),
+            loss: MLPRegressorLoss::default(),
+            epsilon: T::from_f64(1.35).unwrap(),
+            alpha: T::from_f64(1e-4).unwrap(),
+            solver: MLPSolverName::default(),
+            max_iter: 200,
+            tol: T::from_f64(1e-4).unwrap(),
+            batch_size: None,
+            learning_rate_init: T::from_f64(1e-3).unwrap(),
+            shuffle: true,
+            early_stopping: false,
+            validation_fraction: T::from_f64(0.1).unwrap(),
+            n_iter_no_change: 10,
+            seed: None,
+        }
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> PartialEq for MLPRegressor<T, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.network == other.network
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> SupervisedEstimator<M, M::RowVector, MLPRegressorParameters<T>>
+    for MLPRegressor<T, M>
+{
+    fn fit(x: &M, y: &M::RowVector, parameters: MLPRegressorParameters<T>) -> Result<Self, Failed> {
+        MLPRegressor::fit(x, y, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for MLPRegressor<T, M> {
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> MLPRegressor<T, M> {
+    /// Fits the regressor to a training dataset.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(
+        x: &M,
+        y: &M::RowVector,
+        parameters: MLPRegressorParameters<T>,
+    ) -> Result<MLPRegressor<T, M>, Failed> {
+        MLPRegressor::fit_with_callback(x, y, parameters, &mut NoCallback)
+    }
+
+    /// Fits the regressor like `fit` and calls `callback` after every epoch of the mini-batch solvers or iteration of L-BFGS, see [callbacks](../../callback/index.html).
+    /// The callback receives the training loss and, with `early_stopping`, the negative loss on the held out data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    /// * `callback` - monitors the training and can stop it
+    pub fn fit_with_callback(
+        x: &M,
+        y: &M::RowVector,
+        parameters: MLPRegressorParameters<T>,
+        callback: &mut dyn FitCallback<T>,
+    ) -> Result<MLPRegressor<T, M>, Failed> {
+        check_fit(x, y)?;
+        let (n, num_features) = x.shape();
+        if n != y.len() {
+            return Err(Failed::fit(
+                "Number of rows of X doesn't match number of rows of Y",
+            ));
+        }
+        if n == 0 {
+            return Err(Failed::fit("training data should not be empty"));
+        }
+        if parameters.hidden_layer_sizes.contains(&0) {
+            return Err(Failed::fit("hidden layers should have at least one unit"));
+        }
+        if parameters.alpha < T::zero() {
+            return Err(Failed::fit("alpha should be >= 0"));
+        }
+        if parameters.epsilon <= T::zero() {
+            return Err(Failed::fit("epsilon should be > 0"));
+        }
+        if parameters.max_iter == 0 {
+            return Err(Failed::fit("max_iter should be > 0"));
+        }
+
+        let mut targets = M::zeros(n, 1);
+        for i in 0..n {
+            targets.set(i, 0, y.get(i));
+        }
+
+        let mut layer_sizes = Vec::with_capacity(parameters.hidden_layer_sizes.len() + 2);
+        layer_sizes.push(num_features);
+        layer_sizes.extend_from_slice(&parameters.hidden_layer_sizes);
+        layer_sizes.push(1);
+
+        let loss = match parameters.loss {
+            MLPRegressorLoss::SquaredError => Loss::SquaredError,
+            MLPRegressorLoss::Huber => Loss::Huber(parameters.epsilon),
+        };
+        let mut network = Network::new(&layer_sizes, parameters.activation, loss, parameters.seed);
+        let history = network.fit(
+            x,
+            &targets,
+            &TrainingOptions {
+                solver: parameters.solver,
+                alpha: parameters.alpha,
+                max_iter: parameters.max_iter,
+                tol: parameters.tol,
+                batch_size: parameters.batch_size,
+                learning_rate: parameters.learning_rate_init,
+                shuffle: parameters.shuffle,
+                early_stopping: parameters.early_stopping,
+                validation_fraction: parameters.validation_fraction,
+                n_iter_no_change: parameters.n_iter_no_change,
+                seed: parameters.seed,
+            },
+            callback,
+        )?;
+
+        Ok(MLPRegressor {
+            network,
+            n_iter: history.n_iter,
+            loss_curve: history.loss_curve,
+            validation_loss_curve: history.validation_loss_curve,
+        })
+    }
+
+    /// Predicts target values.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
+        if x.shape().1 != self.network.n_inputs() {
+            return Err(Failed::predict(&format!(
+                "Expected {} features, got {}",
+                self.network.n_inputs(),
+                x.shape().1
+            )));
+        }
+        Ok(M::RowVector::from_array(
+            &self.network.predict(x).get_col_as_vec(0),
+        ))
+    }
+
+    /// Weight matrices of all layers. The weights of layer _l_ form an _IxO_ matrix, where _I_ is the number of inputs and _O_ the number of units of the layer.
+    pub fn weights(&self) -> &[M] {
+        self.network.weights()
+    }
+
+    /// Bias vectors of all layers.
+    pub fn biases(&self) -> &[Vec<T>] {
+        self.network.biases()
+    }
+
+    /// Number of iterations of L-BFGS or number of epochs of the mini-batch solvers.
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+
+    /// Mean training loss after every epoch of the mini-batch solvers. L-BFGS records the final loss only.
+    pub fn loss_curve(&self) -> &Vec<T> {
+        &self.loss_curve
+    }
+
+    /// Loss on the held out data after every epoch, empty unless the network was trained with early stopping.
+    pub fn validation_loss_curve(&self) -> &Vec<T> {
+        &self.validation_loss_curve
+    }
+
+    /// Penalized loss of the fitted network on the training data.
+    pub fn loss(&self) -> T {
+        *self.loss_curve.last().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::metrics::mean_squared_error;
+    use crate::neural_network::MLPSolverName;
+
+    fn sine() -> (DenseMatrix<f64>, Vec<f64>) {
+        let x: Vec<f64> = (0..40).map(|i| -3. + 6. * i as f64 / 39.).collect();
+        let y = x.iter().map(|v| v.sin()).collect();
+        (DenseMatrix::from_vec(40, 1, &x), y)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn mlp_regressor() {
+        let (x, y) = sine();
+
+        for activation in [Activation::Tanh, Activation::ReLU, Activation::Logistic] {
+            let mlp = MLPRegressor::fit(
+                &x,
+                &y,
+                MLPRegressorParameters::default()
+                    .with_hidden_layer_sizes(vec![20])
+                    .with_activation(activation)
+                    .with_max_iter(1000)
+                    .with_seed(1),
+            )
+            .unwrap();
+
+            assert!(mean_squared_error(&y, &mlp.predict(&x).unwrap()) < 0.01);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn mini_batch() {
+        let (x, y) = sine();
+
+        let mlp = MLPRegressor::fit(
+            &x,
+            &y,
+            MLPRegressorParameters::default()
+                .with_hidden_layer_sizes(vec![20])
+                .with_activation(Activation::Tanh)
+                .with_solver(MLPSolverName::Adam)
+                .with_batch_size(8)
+                .with_learning_rate_init(0.01)
+                .with_max_iter(2000)
+                .with_tol(1e-6)
+                .with_seed(1),
+        )
+        .unwrap();
+
+        assert!(mean_squared_error(&y, &mlp.predict(&x).unwrap()) < 0.01);
+        assert_eq!(mlp.loss_curve().len(), mlp.n_iter());
+        assert_eq!(mlp.loss(), mlp.loss_curve()[mlp.n_iter() - 1]);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn huber() {
+        let x = DenseMatrix::from_vec(20, 1, &(0..20).map(|i| i as f64 / 10.).collect::<Vec<_>>());
+        let mut y: Vec<f64> = (0..20).map(|i| 2. * i as f64 / 10. + 1.).collect();
+        y[19] = 100.;
+
+        let fit = |loss| {
+            MLPRegressor::fit(
+                &x,
+                &y,
+                MLPRegressorParameters::default()
+                    .with_hidden_layer_sizes(vec![1])
+                    .with_activation(Activation::Identity)
+                    .with_loss(loss)
+                    .with_epsilon(0.5)
+                    .with_alpha(0.)
+                    .with_max_iter(1000)
+                    .with_tol(1e-8)
+                    .with_seed(1),
+            )
+            .unwrap()
+        };
+        let squared = fit(MLPRegressorLoss::SquaredError);
+        let huber = fit(MLPRegressorLoss::Huber);
+
+        // the linear network recovers the line despite the outlier only with the robust loss
+        let y_clean: Vec<f64> = (0..19).map(|i| 2. * i as f64 / 10. + 1.).collect();
+        let x_clean = x.slice(0..19, 0..1);
+        assert!(mean_squared_error(&y_clean, &huber.predict(&x_clean).unwrap()) < 0.01);
+        assert!(mean_squared_error(&y_clean, &squared.predict(&x_clean).unwrap()) > 1.);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn regularization() {
+        let (x, y) = sine();
+
+        let norm = |alpha| {
+            let mlp = MLPRegressor::fit(
+                &x,
+                &y,
+                MLPRegressorParameters::default()
+                    .with_hidden_layer_sizes(vec![10])
+                    .with_alpha(alpha)
+                    .with_seed(1),
+            )
+            .unwrap();
+            mlp.weights().iter().map(|w| w.mul(w).sum()).sum::<f64>()
+        };
+
+        assert!(norm(10.) < norm(0.));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn invalid_parameters() {
+        let (x, y) = sine();
+
+        assert!(
+            MLPRegressor::fit(&x, &y, MLPRegressorParameters::default().with_epsilon(0.)).is_err()
+        );
+        assert!(MLPRegressor::fit(&x, &y[..10].to_vec(), Default::default()).is_err());
+
+        let mlp =
+            MLPRegressor::fit(&x, &y, MLPRegressorParameters::default().with_seed(1)).unwrap();
+        assert!(mlp.predict(&DenseMatrix::zeros(1, 2)).is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = sine();
+
+        let mlp = MLPRegressor::fit(
+            &x,
+            &y,
+            MLPRegressorParameters::default()
+                .with_hidden_layer_sizes(vec![5])
+                .with_loss(MLPRegressorLoss::Huber)
+                .with_seed(1),
+        )
+        .unwrap();
+
+        let deserialized_mlp: MLPRegressor<f64, DenseMatrix<f64>> =
+            serde_json::from_str(&serde_json::to_string(&mlp).unwrap()).unwrap();
+
+        let y_hat = mlp.predict(&x).unwrap();
+        let deserialized_y_hat = deserialized_mlp.predict(&x).unwrap();
+        for (a, b) in y_hat.iter().zip(deserialized_y_hat.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+}
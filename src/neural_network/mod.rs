@@ -0,0 +1,77 @@
+//! # Neural Networks
+//!
+//! A multi-layer perceptron (MLP) is a feedforward neural network that consists of an input layer, one or more hidden layers and an output layer.
+//! Every hidden layer applies an affine transformation followed by a nonlinear activation function \\(\sigma\\) to the outputs of the previous layer
+//!
+//! \\[a^{(l+1)} = \sigma\left(a^{(l)} W^{(l)} + b^{(l)}\right), \quad a^{(0)} = x\\]
+//!
+//! The activation of the output layer depends on the task: the softmax function turns the outputs into class probabilities for classification,
+//! while regression uses the outputs of the last affine transformation directly.
+//! The weights \\(W^{(l)}\\) and biases \\(b^{(l)}\\) are estimated by minimizing a loss function with an L2 penalty
+//!
+//! \\[\frac{1}{n} \sum_{i=1}^n L(y_i, \hat{y}_i) + \frac{\alpha}{2n} \sum_l \lVert W^{(l)} \rVert_F^2\\]
+//!
+//! using gradients calculated with backpropagation. Unlike linear models, the loss function is not convex and different initializations of the weights
+//! can lead to different solutions, set a seed to get reproducible results.
+//!
+//! The loss can be minimized with one of the solvers in [`MLPSolverName`](enum.MLPSolverName.html). L-BFGS evaluates the gradient on the whole training set
+//! in every iteration and converges quickly on small datasets. On larger datasets mini-batch gradient descent is cheaper: every epoch shuffles the training data
+//! and updates the parameters \(\theta\) once per batch of observations, either with a constant step size (SGD) or with Adam
+//!
+//! \[m_t = \beta_1 m_{t-1} + (1 - \beta_1) g_t, \quad v_t = \beta_2 v_{t-1} + (1 - \beta_2) g_t^2, \quad \theta_t = \theta_{t-1} - \eta \frac{\sqrt{1 - \beta_2^t}}{1 - \beta_1^t} \frac{m_t}{\sqrt{v_t} + \epsilon}\]
+//!
+//! where \(g_t\) is the gradient of the loss on the batch, \(\beta_1 = 0.9\), \(\beta_2 = 0.999\) and \(\epsilon = 10^{-8}\).
+//! RMSProp and AdaGrad scale the step of every parameter in the same way, with a moving average or the running sum of \(g_t^2\) in place of \(v_t\) and \(g_t\) in place of \(m_t\).
+//! With early stopping a fraction of the training data is held out and training stops when the loss on the held out data stops decreasing.
+//!
+//! ## References:
+//!
+//! * ["Learning representations by back-propagating errors", Rumelhart D.E., Hinton G.E., Williams R.J., 1986](https://www.nature.com/articles/323533a0)
+//! * ["Understanding the difficulty of training deep feedforward neural networks", Glorot X., Bengio Y., 2010](http://proceedings.mlr.press/v9/glorot10a.html)
+//! * ["Adam: A Method for Stochastic Optimization", Kingma D.P., Ba J., 2014](https://arxiv.org/abs/1412.6980)
+//! * ["Early Stopping - But When?", Prechelt L., 1998](https://doi.org/10.1007/3-540-49430-8_3)
+//! * ["Pattern Recognition and Machine Learning", C.M. Bishop, Chapter 5 Neural Networks](https://www.microsoft.com/en-us/research/uploads/prod/2006/01/Bishop-Pattern-Recognition-and-Machine-Learning-2006.pdf)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Multi-layer perceptron classifier
+pub mod mlp_classifier;
+/// Multi-layer perceptron regressor
+pub mod mlp_regressor;
+pub(crate) mod network;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+/// Optimizer used to fit the weights of the network.
+pub enum MLPSolverName {
+    #[default]
+    /// Limited-memory Broyden–Fletcher–Goldfarb–Shanno method, uses all training data in every iteration. Converges fast on small datasets.
+    LBFGS,
+    /// Mini-batch stochastic gradient descent with a constant learning rate.
+    SGD,
+    /// Mini-batch gradient descent with adaptive per-parameter learning rates estimated from the first and second moments of the gradients.
+    Adam,
+    /// Mini-batch gradient descent that divides the step of every parameter by a moving average of its squared gradients.
+    RMSProp,
+    /// Mini-batch gradient descent that divides the step of every parameter by the square root of the sum of its squared gradients.
+    AdaGrad,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+/// Activation function of the hidden layers.
+pub enum Activation {
+    /// No-op activation, \\(\sigma(z) = z\\), turns the network into a linear model.
+    Identity,
+    /// Logistic sigmoid, \\(\sigma(z) = 1 / (1 + e^{-z})\\).
+    Logistic,
+    /// Hyperbolic tangent, \\(\sigma(z) = \tanh(z)\\).
+    Tanh,
+    #[default]
+    /// Rectified linear unit, \\(\sigma(z) = \max(0, z)\\).
+    ReLU,
+}
@@ -0,0 +1,659 @@
+//! Fully connected feedforward network shared by the multi-layer perceptron estimators.
+//!
+//! Weights of layer \\(l\\) are stored as an _in x out_ matrix so that a batch of observations, one per row, is propagated with a single matrix product.
+//! All parameters can be flattened into a _1 x P_ row vector, the layout expected by the optimizers in [`optimization`](crate::optimization).
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::callback::{FitAction, FitCallback, FitProgress};
+use crate::error::Failed;
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::neural_network::{Activation, MLPSolverName};
+use crate::optimization::first_order::adaptive::{
+    AdaGrad, Adam, AdaptiveOptimizer, AdaptiveState, RMSProp,
+};
+use crate::optimization::first_order::lbfgs::LBFGS;
+use crate::optimization::line_search::Backtracking;
+use crate::optimization::FunctionOrder;
+use crate::rand::get_rng_impl;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Loss minimized by the network, also determines the activation of the output layer.
+pub(crate) enum Loss<T: RealNumber> {
+    /// Cross-entropy of softmax probabilities and one-hot encoded targets.
+    CrossEntropy,
+    /// Half of the squared error of linear outputs.
+    SquaredError,
+    /// Huber loss of linear outputs, squared error for residuals up to the threshold and absolute error beyond it.
+    Huber(T),
+}
+
+impl Activation {
+    fn apply<T: RealNumber>(&self, z: T) -> T {
+        match self {
+            Activation::Identity => z,
+            Activation::Logistic => z.sigmoid(),
+            Activation::Tanh => z.tanh(),
+            Activation::ReLU => z.max(T::zero()),
+        }
+    }
+
+    /// Derivative of the activation function expressed in terms of its output `a`.
+    fn derivative<T: RealNumber>(&self, a: T) -> T {
+        match self {
+            Activation::Identity => T::one(),
+            Activation::Logistic => a * (T::one() - a),
+            Activation::Tanh => T::one() - a * a,
+            Activation::ReLU => {
+                if a > T::zero() {
+                    T::one()
+                } else {
+                    T::zero()
+                }
+            }
+        }
+    }
+}
+
+/// Solver and its settings, shared by the multi-layer perceptron estimators.
+#[derive(Debug, Clone)]
+pub(crate) struct TrainingOptions<T: RealNumber> {
+    pub solver: MLPSolverName,
+    pub alpha: T,
+    pub max_iter: usize,
+    pub tol: T,
+    pub batch_size: Option<usize>,
+    pub learning_rate: T,
+    pub shuffle: bool,
+    pub early_stopping: bool,
+    pub validation_fraction: T,
+    pub n_iter_no_change: usize,
+    pub seed: Option<u64>,
+}
+
+/// Progress of the training.
+#[derive(Debug, Clone)]
+pub(crate) struct TrainingHistory<T: RealNumber> {
+    /// Number of iterations of L-BFGS or number of epochs of the mini-batch solvers.
+    pub n_iter: usize,
+    /// Training loss after every epoch, only the final loss for L-BFGS.
+    pub loss_curve: Vec<T>,
+    /// Loss on the validation set after every epoch, empty without early stopping.
+    pub validation_loss_curve: Vec<T>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Network<T: RealNumber, M: Matrix<T>> {
+    activation: Activation,
+    loss: Loss<T>,
+    weights: Vec<M>,
+    biases: Vec<Vec<T>>,
+}
+
+impl<T: RealNumber, M: Matrix<T>> Network<T, M> {
+    /// Creates a network with Glorot uniform initialization of the weights.
+    /// * `layer_sizes` - number of units in every layer, including the input and the output layer.
+    pub fn new(
+        layer_sizes: &[usize],
+        activation: Activation,
+        loss: Loss<T>,
+        seed: Option<u64>,
+    ) -> Self {
+        let mut rng = get_rng_impl(seed);
+        // sigmoid units saturate quicker, their weights are initialized in a narrower range
+        let factor = match activation {
+            Activation::Logistic => 2f64,
+            _ => 6f64,
+        };
+
+        let mut weights = Vec::with_capacity(layer_sizes.len() - 1);
+        let mut biases = Vec::with_capacity(layer_sizes.len() - 1);
+        for l in 1..layer_sizes.len() {
+            let (fan_in, fan_out) = (layer_sizes[l - 1], layer_sizes[l]);
+            let bound = (factor / (fan_in + fan_out) as f64).sqrt();
+            let mut w = M::zeros(fan_in, fan_out);
+            for i in 0..fan_in {
+                for j in 0..fan_out {
+                    w.set(i, j, T::from_f64(rng.gen_range(-bound..bound)).unwrap());
+                }
+            }
+            weights.push(w);
+            biases.push(
+                (0..fan_out)
+                    .map(|_| T::from_f64(rng.gen_range(-bound..bound)).unwrap())
+                    .collect(),
+            );
+        }
+
+        Network {
+            activation,
+            loss,
+            weights,
+            biases,
+        }
+    }
+
+    /// Weight matrices of all layers.
+    pub fn weights(&self) -> &[M] {
+        &self.weights
+    }
+
+    /// Bias vectors of all layers.
+    pub fn biases(&self) -> &[Vec<T>] {
+        &self.biases
+    }
+
+    /// Number of inputs of the network.
+    pub fn n_inputs(&self) -> usize {
+        self.weights[0].shape().0
+    }
+
+    /// Outputs of the network.
+    pub fn predict(&self, x: &M) -> M {
+        self.forward(x).pop().unwrap()
+    }
+
+    /// Outputs of every layer, starting with the input.
+    fn forward(&self, x: &M) -> Vec<M> {
+        let n_layers = self.weights.len();
+        let mut activations = Vec::with_capacity(n_layers + 1);
+        activations.push(x.clone());
+        for l in 0..n_layers {
+            let mut z = activations[l].matmul(&self.weights[l]);
+            let (n, k) = z.shape();
+            for i in 0..n {
+                for j in 0..k {
+                    z.set(i, j, z.get(i, j) + self.biases[l][j]);
+                }
+            }
+            if l + 1 < n_layers {
+                for i in 0..n {
+                    for j in 0..k {
+                        z.set(i, j, self.activation.apply(z.get(i, j)));
+                    }
+                }
+            } else {
+                self.output_activation(&mut z);
+            }
+            activations.push(z);
+        }
+        activations
+    }
+
+    fn output_activation(&self, z: &mut M) {
+        match self.loss {
+            Loss::CrossEntropy => {
+                let (n, k) = z.shape();
+                for i in 0..n {
+                    let max = (0..k).fold(T::neg_infinity(), |m, j| m.max(z.get(i, j)));
+                    let mut sum = T::zero();
+                    for j in 0..k {
+                        let e = (z.get(i, j) - max).exp();
+                        z.set(i, j, e);
+                        sum += e;
+                    }
+                    for j in 0..k {
+                        z.set(i, j, z.get(i, j) / sum);
+                    }
+                }
+            }
+            Loss::SquaredError | Loss::Huber(_) => {}
+        }
+    }
+
+    fn data_loss(&self, output: &M, y: &M) -> T {
+        let (n, k) = output.shape();
+        let mut loss = T::zero();
+        match self.loss {
+            Loss::CrossEntropy => {
+                let eps = T::epsilon();
+                for i in 0..n {
+                    for j in 0..k {
+                        let y_ij = y.get(i, j);
+                        if y_ij > T::zero() {
+                            loss -= y_ij * output.get(i, j).max(eps).ln();
+                        }
+                    }
+                }
+            }
+            Loss::SquaredError => {
+                for i in 0..n {
+                    for j in 0..k {
+                        loss += (output.get(i, j) - y.get(i, j)).square() / T::two();
+                    }
+                }
+            }
+            Loss::Huber(epsilon) => {
+                for i in 0..n {
+                    for j in 0..k {
+                        let r = (output.get(i, j) - y.get(i, j)).abs();
+                        loss += if r <= epsilon {
+                            r * r / T::two()
+                        } else {
+                            epsilon * (r - epsilon / T::two())
+                        };
+                    }
+                }
+            }
+        }
+        loss / T::from_usize(n).unwrap()
+    }
+
+    fn penalty(&self, alpha: T, n: usize) -> T {
+        let squares = self
+            .weights
+            .iter()
+            .fold(T::zero(), |s, w| s + w.mul(w).sum());
+        alpha * squares / (T::two() * T::from_usize(n).unwrap())
+    }
+
+    /// Penalized mean loss of the network on the training data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - _NxK_ matrix of targets, one column per output of the network.
+    /// * `alpha` - strength of the L2 penalty of the weights.
+    pub fn loss(&self, x: &M, y: &M, alpha: T) -> T {
+        self.data_loss(&self.predict(x), y) + self.penalty(alpha, x.shape().0)
+    }
+
+    /// Penalized mean loss and its gradients with respect to the weights and the biases of every layer, calculated with backpropagation.
+    pub fn loss_and_gradient(&self, x: &M, y: &M, alpha: T) -> (T, Vec<M>, Vec<Vec<T>>) {
+        let n = x.shape().0;
+        let n_t = T::from_usize(n).unwrap();
+        let activations = self.forward(x);
+        let n_layers = self.weights.len();
+        let output = &activations[n_layers];
+        let loss = self.data_loss(output, y) + self.penalty(alpha, n);
+
+        // softmax outputs with cross-entropy and linear outputs with squared error have the same error term at the output layer
+        let mut delta = output.sub(y);
+        if let Loss::Huber(epsilon) = self.loss {
+            let (rows, cols) = delta.shape();
+            for i in 0..rows {
+                for j in 0..cols {
+                    delta.set(i, j, delta.get(i, j).max(-epsilon).min(epsilon));
+                }
+            }
+        }
+        delta.div_scalar_mut(n_t);
+
+        let mut grad_weights = Vec::with_capacity(n_layers);
+        let mut grad_biases = Vec::with_capacity(n_layers);
+        for l in (0..n_layers).rev() {
+            let mut grad_w = activations[l].ab(true, &delta, false);
+            grad_w.add_mut(&self.weights[l].mul_scalar(alpha / n_t));
+            let (rows, cols) = delta.shape();
+            let mut grad_b = vec![T::zero(); cols];
+            for i in 0..rows {
+                for (j, g) in grad_b.iter_mut().enumerate() {
+                    *g += delta.get(i, j);
+                }
+            }
+            grad_weights.push(grad_w);
+            grad_biases.push(grad_b);
+
+            if l > 0 {
+                let mut prev = delta.ab(false, &self.weights[l], true);
+                let (rows, cols) = prev.shape();
+                for i in 0..rows {
+                    for j in 0..cols {
+                        let d = self.activation.derivative(activations[l].get(i, j));
+                        prev.set(i, j, prev.get(i, j) * d);
+                    }
+                }
+                delta = prev;
+            }
+        }
+        grad_weights.reverse();
+        grad_biases.reverse();
+
+        (loss, grad_weights, grad_biases)
+    }
+
+    fn n_params(&self) -> usize {
+        self.weights
+            .iter()
+            .zip(self.biases.iter())
+            .map(|(w, b)| {
+                let (rows, cols) = w.shape();
+                rows * cols + b.len()
+            })
+            .sum()
+    }
+
+    /// Writes weights and biases of all layers into a single row vector.
+    fn pack(weights: &[M], biases: &[Vec<T>], params: &mut M) {
+        let mut k = 0;
+        for (w, b) in weights.iter().zip(biases.iter()) {
+            let (rows, cols) = w.shape();
+            for i in 0..rows {
+                for j in 0..cols {
+                    params.set(0, k, w.get(i, j));
+                    k += 1;
+                }
+            }
+            for &b_j in b.iter() {
+                params.set(0, k, b_j);
+                k += 1;
+            }
+        }
+    }
+
+    /// Reads weights and biases of all layers from a row vector created with `pack`.
+    fn unpack(&mut self, params: &M) {
+        let mut k = 0;
+        for (w, b) in self.weights.iter_mut().zip(self.biases.iter_mut()) {
+            let (rows, cols) = w.shape();
+            for i in 0..rows {
+                for j in 0..cols {
+                    w.set(i, j, params.get(0, k));
+                    k += 1;
+                }
+            }
+            for b_j in b.iter_mut() {
+                *b_j = params.get(0, k);
+                k += 1;
+            }
+        }
+    }
+
+    /// Minimizes the penalized loss starting from the current parameters.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - _NxK_ matrix of targets, one column per output of the network.
+    /// * `options` - solver and its settings.
+    /// * `callback` - called after every iteration of L-BFGS or epoch of the mini-batch solvers, can stop the training.
+    pub fn fit(
+        &mut self,
+        x: &M,
+        y: &M,
+        options: &TrainingOptions<T>,
+        callback: &mut dyn FitCallback<T>,
+    ) -> Result<TrainingHistory<T>, Failed> {
+        if options.solver != MLPSolverName::LBFGS {
+            if options.batch_size == Some(0) {
+                return Err(Failed::fit("batch_size should be > 0"));
+            }
+            if options.learning_rate <= T::zero() {
+                return Err(Failed::fit("learning_rate_init should be > 0"));
+            }
+            if options.n_iter_no_change == 0 {
+                return Err(Failed::fit("n_iter_no_change should be > 0"));
+            }
+            if options.early_stopping
+                && (options.validation_fraction <= T::zero()
+                    || options.validation_fraction >= T::one())
+            {
+                return Err(Failed::fit("validation_fraction should be in (0, 1)"));
+            }
+        }
+
+        match options.solver {
+            MLPSolverName::LBFGS => Ok(self.fit_lbfgs(x, y, options, callback)),
+            MLPSolverName::SGD
+            | MLPSolverName::Adam
+            | MLPSolverName::RMSProp
+            | MLPSolverName::AdaGrad => self.fit_stochastic(x, y, options, callback),
+        }
+    }
+
+    fn fit_lbfgs(
+        &mut self,
+        x: &M,
+        y: &M,
+        options: &TrainingOptions<T>,
+        callback: &mut dyn FitCallback<T>,
+    ) -> TrainingHistory<T> {
+        let alpha = options.alpha;
+        let mut x0 = M::zeros(1, self.n_params());
+        Network::pack(&self.weights, &self.biases, &mut x0);
+
+        let f = |params: &M| -> T {
+            let mut network = self.clone();
+            network.unpack(params);
+            network.loss(x, y, alpha)
+        };
+
+        let df = |g: &mut M, params: &M| {
+            let mut network = self.clone();
+            network.unpack(params);
+            let (_, grad_weights, grad_biases) = network.loss_and_gradient(x, y, alpha);
+            Network::pack(&grad_weights, &grad_biases, g);
+        };
+
+        let ls: Backtracking<T> = Backtracking {
+            order: FunctionOrder::THIRD,
+            ..Default::default()
+        };
+        let optimizer: LBFGS<T> = LBFGS {
+            max_iter: options.max_iter,
+            g_atol: options.tol,
+            ..Default::default()
+        };
+
+        let result = optimizer.optimize_with_callback(&f, &df, &x0, &ls, &mut |iteration, loss| {
+            let progress = FitProgress {
+                iteration,
+                loss: Some(loss),
+                validation_score: None,
+            };
+            callback.on_iteration(&progress) == FitAction::Continue
+        });
+        self.unpack(&result.x);
+
+        TrainingHistory {
+            n_iter: result.iterations,
+            loss_curve: vec![result.f_x],
+            validation_loss_curve: Vec::new(),
+        }
+    }
+
+    /// Mini-batch gradient descent, every epoch is a pass over the training data in batches of `batch_size` observations.
+    fn fit_stochastic(
+        &mut self,
+        x: &M,
+        y: &M,
+        options: &TrainingOptions<T>,
+        callback: &mut dyn FitCallback<T>,
+    ) -> Result<TrainingHistory<T>, Failed> {
+        let mut rng = get_rng_impl(options.seed);
+        let n = x.shape().0;
+
+        let (x_train, y_train, validation) = if options.early_stopping {
+            let n_validation = (T::from_usize(n).unwrap() * options.validation_fraction)
+                .ceil()
+                .to_usize()
+                .unwrap();
+            if n_validation == 0 || n_validation >= n {
+                return Err(Failed::fit(&format!(
+                    "validation_fraction {} leaves no observations for validation or training",
+                    options.validation_fraction
+                )));
+            }
+            let mut index: Vec<usize> = (0..n).collect();
+            index.shuffle(&mut rng);
+            let (validation_index, train_index) = index.split_at(n_validation);
+            (
+                x.take(train_index, 0),
+                y.take(train_index, 0),
+                Some((x.take(validation_index, 0), y.take(validation_index, 0))),
+            )
+        } else {
+            (x.clone(), y.clone(), None)
+        };
+
+        let n_train = x_train.shape().0;
+        let batch_size = options.batch_size.unwrap_or(200).min(n_train);
+        let n_params = self.n_params();
+
+        let mut params = M::zeros(1, n_params);
+        Network::pack(&self.weights, &self.biases, &mut params);
+        let mut grad = M::zeros(1, n_params);
+        let mut state = AdaptiveState::new(&params);
+        let mut best_params = params.clone();
+        let mut best_loss = T::infinity();
+        let mut no_improvement = 0;
+
+        let mut history = TrainingHistory {
+            n_iter: 0,
+            loss_curve: Vec::new(),
+            validation_loss_curve: Vec::new(),
+        };
+        let mut order: Vec<usize> = (0..n_train).collect();
+
+        for _ in 0..options.max_iter {
+            if options.shuffle {
+                order.shuffle(&mut rng);
+            }
+
+            let mut epoch_loss = T::zero();
+            for batch in order.chunks(batch_size) {
+                let (loss, grad_weights, grad_biases) = self.loss_and_gradient(
+                    &x_train.take(batch, 0),
+                    &y_train.take(batch, 0),
+                    options.alpha,
+                );
+                epoch_loss += loss * T::from_usize(batch.len()).unwrap();
+                Network::pack(&grad_weights, &grad_biases, &mut grad);
+
+                match options.solver {
+                    MLPSolverName::Adam => Adam {
+                        learning_rate: options.learning_rate,
+                        ..Default::default()
+                    }
+                    .update(&mut state, &mut params, &grad),
+                    MLPSolverName::RMSProp => RMSProp {
+                        learning_rate: options.learning_rate,
+                        ..Default::default()
+                    }
+                    .update(&mut state, &mut params, &grad),
+                    MLPSolverName::AdaGrad => AdaGrad {
+                        learning_rate: options.learning_rate,
+                        ..Default::default()
+                    }
+                    .update(&mut state, &mut params, &grad),
+                    _ => {
+                        for k in 0..n_params {
+                            params.set(
+                                0,
+                                k,
+                                params.get(0, k) - options.learning_rate * grad.get(0, k),
+                            );
+                        }
+                    }
+                }
+                self.unpack(&params);
+            }
+
+            epoch_loss /= T::from_usize(n_train).unwrap();
+            history.loss_curve.push(epoch_loss);
+            history.n_iter += 1;
+
+            let score = match &validation {
+                Some((x_validation, y_validation)) => {
+                    let loss = self.loss(x_validation, y_validation, T::zero());
+                    history.validation_loss_curve.push(loss);
+                    loss
+                }
+                None => epoch_loss,
+            };
+
+            if score < best_loss - options.tol {
+                no_improvement = 0;
+            } else {
+                no_improvement += 1;
+            }
+            if score < best_loss {
+                best_loss = score;
+                best_params.copy_from(&params);
+            }
+
+            let progress = FitProgress {
+                iteration: history.n_iter,
+                loss: Some(epoch_loss),
+                validation_score: validation.as_ref().map(|_| -score),
+            };
+            let action = callback.on_iteration(&progress);
+            if no_improvement >= options.n_iter_no_change || action == FitAction::Stop {
+                break;
+            }
+        }
+
+        if options.early_stopping {
+            self.unpack(&best_params);
+        }
+
+        Ok(history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn gradient() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[0.5, -1.0, 2.0],
+            &[1.5, 0.3, -0.7],
+            &[-0.2, 0.8, 0.1],
+            &[1.1, -0.4, 0.9],
+        ]);
+        let y = DenseMatrix::from_2d_array(&[&[1., 0.], &[0., 1.], &[0., 1.], &[1., 0.]]);
+        let alpha = 0.1;
+
+        for (activation, loss) in [
+            (Activation::Logistic, Loss::CrossEntropy),
+            (Activation::Tanh, Loss::CrossEntropy),
+            (Activation::Identity, Loss::CrossEntropy),
+            (Activation::Tanh, Loss::SquaredError),
+            (Activation::Logistic, Loss::Huber(0.5)),
+        ] {
+            let network: Network<f64, DenseMatrix<f64>> =
+                Network::new(&[3, 4, 3, 2], activation, loss, Some(7));
+            let (_, grad_weights, grad_biases) = network.loss_and_gradient(&x, &y, alpha);
+            let mut grad = DenseMatrix::zeros(1, network.n_params());
+            Network::pack(&grad_weights, &grad_biases, &mut grad);
+
+            let mut params = DenseMatrix::zeros(1, network.n_params());
+            Network::pack(network.weights(), network.biases(), &mut params);
+            let h = 1e-6;
+            for k in 0..network.n_params() {
+                let mut shifted = network.clone();
+                let mut p = params.clone();
+                p.set(0, k, params.get(0, k) + h);
+                shifted.unpack(&p);
+                let f_plus = shifted.loss(&x, &y, alpha);
+                p.set(0, k, params.get(0, k) - h);
+                shifted.unpack(&p);
+                let f_minus = shifted.loss(&x, &y, alpha);
+
+                assert!(((f_plus - f_minus) / (2. * h) - grad.get(0, k)).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn softmax_output() {
+        let x = DenseMatrix::from_2d_array(&[&[100., -50.], &[0.1, 0.2]]);
+        let network: Network<f64, DenseMatrix<f64>> =
+            Network::new(&[2, 5, 3], Activation::ReLU, Loss::CrossEntropy, Some(1));
+        let p = network.predict(&x);
+        for i in 0..2 {
+            let row_sum: f64 = (0..3).map(|j| p.get(i, j)).sum();
+            assert!((row_sum - 1.).abs() < 1e-12);
+        }
+    }
+}
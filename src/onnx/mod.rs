@@ -0,0 +1,537 @@
+//! # ONNX export
+//!
+//! Exports fitted models to [ONNX](https://onnx.ai), so they can be served by any runtime that implements
+//! the [ONNX-ML operators](https://github.com/onnx/onnx/blob/main/docs/Operators-ml.md), e.g. [ONNX Runtime](https://onnxruntime.ai).
+//!
+//! Models that implement [`ToOnnx`](trait.ToOnnx.html) are converted to a graph with a single ONNX-ML operator:
+//!
+//! | Model | Operator |
+//! |-------|----------|
+//! | [LinearRegression](../linear/linear_regression/index.html), [RidgeRegression](../linear/ridge_regression/index.html), [Lasso](../linear/lasso/index.html), [ElasticNet](../linear/elastic_net/index.html) | `LinearRegressor` |
+//! | [LogisticRegression](../linear/logistic_regression/index.html) | `LinearClassifier` |
+//! | [DecisionTreeRegressor](../tree/decision_tree_regressor/index.html), [RandomForestRegressor](../ensemble/random_forest_regressor/index.html), [HistGradientBoostingRegressor](../ensemble/hist_gradient_boosting/index.html) | `TreeEnsembleRegressor` |
+//! | [DecisionTreeClassifier](../tree/decision_tree_classifier/index.html), [RandomForestClassifier](../ensemble/random_forest_classifier/index.html), [HistGradientBoostingClassifier](../ensemble/hist_gradient_boosting/index.html) | `TreeEnsembleClassifier` |
+//! | [StandardScaler](../preprocessing/numerical/index.html), [MinMaxScaler](../preprocessing/numerical/index.html), [RobustScaler](../preprocessing/numerical/index.html) | `Scaler` |
+//!
+//! Every graph takes a single float tensor `X` of shape _Nx M_ where _M_ is the number of features.
+//! Regressors output a tensor `Y` of shape _Nx1_ and scalers a tensor `Y` of shape _NxM_.
+//! Classifiers output the predicted class labels `label` as a tensor of 64-bit integers and the scores of every class `probabilities`
+//! as a tensor of shape _NxC_, with columns that follow the order of sorted class labels.
+//! Since ONNX-ML operators identify classes by integers, only classifiers fitted with integer class labels can be exported.
+//!
+//! ONNX-ML operators compute in single precision, so predictions of an exported model may differ slightly from the predictions in SmartCore,
+//! in particular for observations that are very close to a split threshold of a tree.
+//! Trees with [oblique splits](../tree/oblique/index.html) can't be expressed with ONNX-ML operators and fail to export.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::onnx::ToOnnx;
+//! use smartcore::tree::decision_tree_classifier::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[5.1, 3.5, 1.4, 0.2],
+//!     &[4.9, 3.0, 1.4, 0.2],
+//!     &[7.0, 3.2, 4.7, 1.4],
+//!     &[6.4, 3.2, 4.5, 1.5],
+//! ]);
+//! let y = vec![0., 0., 1., 1.];
+//!
+//! let tree = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
+//!
+//! let model = tree.to_onnx().unwrap();
+//! std::fs::write(std::env::temp_dir().join("tree.onnx"), &model).unwrap();
+//! ```
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::error::Failed;
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+
+use self::proto::Message;
+
+pub(crate) mod proto;
+pub(crate) mod tree;
+
+/// Version of the ONNX file format, 8 is the version of ONNX 1.10 and later.
+const IR_VERSION: u64 = 8;
+/// Version of the default `ai.onnx` operator set.
+const OPSET_VERSION: u64 = 15;
+/// Domain of the ONNX-ML operators.
+const ML_DOMAIN: &str = "ai.onnx.ml";
+/// Version of the `ai.onnx.ml` operator set.
+const ML_OPSET_VERSION: u64 = 3;
+
+/// Element type of a float tensor.
+const FLOAT: u64 = 1;
+/// Element type of a 64-bit integer tensor.
+const INT64: u64 = 7;
+
+/// Models that can be exported to ONNX.
+pub trait ToOnnx {
+    /// Serializes the model to an ONNX model, the returned bytes can be written to a `.onnx` file.
+    fn to_onnx(&self) -> Result<Vec<u8>, Failed>;
+}
+
+/// Value of an attribute of an operator.
+#[derive(Debug, Clone)]
+pub(crate) enum Attribute {
+    Int(i64),
+    String(String),
+    Floats(Vec<f32>),
+    Ints(Vec<i64>),
+    Strings(Vec<String>),
+}
+
+impl Attribute {
+    fn encode(&self, name: &str) -> Message {
+        let mut attribute = Message::new();
+        attribute.string(1, name);
+        // field numbers and attribute types of `AttributeProto`
+        match self {
+            Attribute::Int(value) => attribute.int64(3, *value).varint(20, 2),
+            Attribute::String(value) => attribute.string(4, value).varint(20, 3),
+            Attribute::Floats(values) => attribute.floats(7, values).varint(20, 6),
+            Attribute::Ints(values) => attribute.int64s(8, values).varint(20, 7),
+            Attribute::Strings(values) => {
+                for value in values {
+                    attribute.string(9, value);
+                }
+                attribute.varint(20, 8)
+            }
+        };
+        attribute
+    }
+}
+
+/// An ONNX-ML operator that reads the input `X`.
+#[derive(Debug, Clone)]
+pub(crate) struct Operator {
+    op_type: &'static str,
+    attributes: Vec<(&'static str, Attribute)>,
+}
+
+impl Operator {
+    pub(crate) fn new(op_type: &'static str) -> Self {
+        Operator {
+            op_type,
+            attributes: Vec::new(),
+        }
+    }
+
+    pub(crate) fn with(mut self, name: &'static str, value: Attribute) -> Self {
+        self.attributes.push((name, value));
+        self
+    }
+
+    /// Builds a model with a regressor that predicts a single target.
+    pub(crate) fn regressor_model(self, n_features: usize) -> Vec<u8> {
+        self.model(n_features, &[("Y", FLOAT, Some(1))])
+    }
+
+    /// Builds a model with a classifier that predicts labels and scores of `n_classes` classes.
+    pub(crate) fn classifier_model(self, n_features: usize, n_classes: usize) -> Vec<u8> {
+        self.model(
+            n_features,
+            &[
+                ("label", INT64, None),
+                ("probabilities", FLOAT, Some(n_classes)),
+            ],
+        )
+    }
+
+    /// Builds a model with a transformer that keeps the shape of the input.
+    pub(crate) fn transformer_model(self, n_features: usize) -> Vec<u8> {
+        self.model(n_features, &[("Y", FLOAT, Some(n_features))])
+    }
+
+    fn model(self, n_features: usize, outputs: &[(&str, u64, Option<usize>)]) -> Vec<u8> {
+        let mut node = Message::new();
+        node.string(1, "X");
+        for (name, _, _) in outputs {
+            node.string(2, name);
+        }
+        node.string(3, self.op_type)
+            .string(4, self.op_type)
+            .string(7, ML_DOMAIN);
+        for (name, value) in self.attributes.iter() {
+            node.message(5, &value.encode(name));
+        }
+
+        let mut graph = Message::new();
+        graph
+            .message(1, &node)
+            .string(2, "smartcore")
+            .message(11, &value_info("X", FLOAT, Some(n_features)));
+        for (name, elem_type, n_columns) in outputs {
+            graph.message(12, &value_info(name, *elem_type, *n_columns));
+        }
+
+        let mut model = Message::new();
+        model
+            .varint(1, IR_VERSION)
+            .string(2, "smartcore")
+            .string(3, env!("CARGO_PKG_VERSION"))
+            .message(7, &graph)
+            .message(8, &opset_import("", OPSET_VERSION))
+            .message(8, &opset_import(ML_DOMAIN, ML_OPSET_VERSION));
+        model.into_bytes()
+    }
+}
+
+/// Describes a tensor with a variable number of rows `N` and `n_columns` columns, or a vector when `n_columns` is `None`.
+fn value_info(name: &str, elem_type: u64, n_columns: Option<usize>) -> Message {
+    let mut rows = Message::new();
+    rows.string(2, "N");
+    let mut shape = Message::new();
+    shape.message(1, &rows);
+    if let Some(n_columns) = n_columns {
+        let mut columns = Message::new();
+        columns.int64(1, n_columns as i64);
+        shape.message(1, &columns);
+    }
+
+    let mut tensor_type = Message::new();
+    tensor_type.varint(1, elem_type).message(2, &shape);
+    let mut value_type = Message::new();
+    value_type.message(1, &tensor_type);
+
+    let mut value_info = Message::new();
+    value_info.string(1, name).message(2, &value_type);
+    value_info
+}
+
+fn opset_import(domain: &str, version: u64) -> Message {
+    let mut opset = Message::new();
+    opset.string(1, domain).varint(2, version);
+    opset
+}
+
+/// Builds a model with a `LinearRegressor`.
+/// * `coefficients` - _Mx1_ coefficients of a linear model
+/// * `intercept` - intercept of the model
+pub(crate) fn linear_regressor<T: RealNumber, M: Matrix<T>>(
+    coefficients: &M,
+    intercept: T,
+) -> Vec<u8> {
+    let (n_features, _) = coefficients.shape();
+    Operator::new("LinearRegressor")
+        .with(
+            "coefficients",
+            Attribute::Floats(floats(coefficients.get_col_as_vec(0))),
+        )
+        .with("intercepts", Attribute::Floats(floats(vec![intercept])))
+        .with("post_transform", post_transform("NONE"))
+        .with("targets", Attribute::Int(1))
+        .regressor_model(n_features)
+}
+
+/// Builds a model with a `Scaler` that computes `(x - offset) * scale` for every feature.
+pub(crate) fn scaler<T: RealNumber>(offset: Vec<T>, scale: Vec<T>) -> Vec<u8> {
+    let n_features = offset.len();
+    Operator::new("Scaler")
+        .with("offset", Attribute::Floats(floats(offset)))
+        .with("scale", Attribute::Floats(floats(scale)))
+        .transformer_model(n_features)
+}
+
+/// Converts values to single precision.
+pub(crate) fn floats<T: RealNumber>(values: impl IntoIterator<Item = T>) -> Vec<f32> {
+    values.into_iter().map(|v| v.to_f32().unwrap()).collect()
+}
+
+/// Converts class labels to the integers that identify classes in ONNX-ML operators.
+pub(crate) fn class_labels<T: RealNumber>(classes: &[T]) -> Result<Vec<i64>, Failed> {
+    classes
+        .iter()
+        .map(|c| match c.to_i64() {
+            Some(label) if c.fract() == T::zero() => Ok(label),
+            _ => Err(Failed::export(&format!(
+                "Class label {} is not an integer, ONNX-ML operators only support integer class labels",
+                c
+            ))),
+        })
+        .collect()
+}
+
+/// `post_transform` attribute of an operator.
+pub(crate) fn post_transform(name: &str) -> Attribute {
+    Attribute::String(name.to_string())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::api::{Transformer, UnsupervisedEstimator};
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::linalg::BaseMatrix;
+    use crate::linear::linear_regression::LinearRegression;
+    use crate::linear::logistic_regression::LogisticRegression;
+    use crate::preprocessing::numerical::*;
+    use std::collections::HashMap;
+
+    /// Value of a field in the protocol buffers wire format.
+    #[derive(Debug, Clone)]
+    pub(crate) enum Value {
+        Varint(u64),
+        Bytes(Vec<u8>),
+    }
+
+    impl Value {
+        pub(crate) fn bytes(&self) -> &[u8] {
+            match self {
+                Value::Bytes(bytes) => bytes,
+                _ => panic!("not a length-delimited field"),
+            }
+        }
+
+        pub(crate) fn string(&self) -> String {
+            String::from_utf8(self.bytes().to_vec()).unwrap()
+        }
+    }
+
+    fn read_varint(bytes: &[u8], pos: &mut usize) -> u64 {
+        let mut value = 0;
+        let mut shift = 0;
+        loop {
+            let byte = bytes[*pos];
+            *pos += 1;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte < 0x80 {
+                return value;
+            }
+            shift += 7;
+        }
+    }
+
+    /// Decodes all fields of a message.
+    pub(crate) fn decode(bytes: &[u8]) -> Vec<(u32, Value)> {
+        let mut fields = Vec::new();
+        let mut pos = 0;
+        while pos < bytes.len() {
+            let key = read_varint(bytes, &mut pos);
+            let value = match key & 7 {
+                0 => Value::Varint(read_varint(bytes, &mut pos)),
+                2 => {
+                    let len = read_varint(bytes, &mut pos) as usize;
+                    pos += len;
+                    Value::Bytes(bytes[pos - len..pos].to_vec())
+                }
+                wire_type => panic!("unexpected wire type {}", wire_type),
+            };
+            fields.push(((key >> 3) as u32, value));
+        }
+        fields
+    }
+
+    pub(crate) fn field(fields: &[(u32, Value)], number: u32) -> Vec<Value> {
+        fields
+            .iter()
+            .filter(|(n, _)| *n == number)
+            .map(|(_, v)| v.clone())
+            .collect()
+    }
+
+    /// Decoded operator of an exported model.
+    #[derive(Debug)]
+    pub(crate) struct Decoded {
+        pub(crate) op_type: String,
+        pub(crate) outputs: Vec<String>,
+        attributes: HashMap<String, Vec<(u32, Value)>>,
+    }
+
+    impl Decoded {
+        pub(crate) fn has(&self, name: &str) -> bool {
+            self.attributes.contains_key(name)
+        }
+
+        pub(crate) fn ints(&self, name: &str) -> Vec<i64> {
+            let bytes = field(&self.attributes[name], 8)[0].bytes().to_vec();
+            let mut pos = 0;
+            let mut values = Vec::new();
+            while pos < bytes.len() {
+                values.push(read_varint(&bytes, &mut pos) as i64);
+            }
+            values
+        }
+
+        pub(crate) fn floats(&self, name: &str) -> Vec<f32> {
+            field(&self.attributes[name], 7)[0]
+                .bytes()
+                .chunks(4)
+                .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                .collect()
+        }
+
+        pub(crate) fn string(&self, name: &str) -> String {
+            field(&self.attributes[name], 4)[0].string()
+        }
+
+        pub(crate) fn strings(&self, name: &str) -> Vec<String> {
+            field(&self.attributes[name], 9)
+                .iter()
+                .map(|v| v.string())
+                .collect()
+        }
+
+        pub(crate) fn int(&self, name: &str) -> i64 {
+            match field(&self.attributes[name], 3)[0] {
+                Value::Varint(v) => v as i64,
+                _ => panic!("not an integer"),
+            }
+        }
+    }
+
+    /// Decodes the only operator of a model exported by `to_onnx`.
+    pub(crate) fn decode_operator(model: &[u8]) -> Decoded {
+        let model = decode(model);
+        let graph = decode(field(&model, 7)[0].bytes());
+        let node = decode(field(&graph, 1)[0].bytes());
+        let attributes = field(&node, 5)
+            .iter()
+            .map(|a| {
+                let attribute = decode(a.bytes());
+                (field(&attribute, 1)[0].string(), attribute)
+            })
+            .collect();
+        Decoded {
+            op_type: field(&node, 4)[0].string(),
+            outputs: field(&node, 2).iter().map(|o| o.string()).collect(),
+            attributes,
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn model_structure() {
+        let x = DenseMatrix::from_2d_array(&[&[1., 1.], &[1., 2.], &[2., 2.], &[2., 3.]]);
+        let y = vec![6., 8., 9., 11.];
+        let lr = LinearRegression::fit(&x, &y, Default::default()).unwrap();
+        let bytes = lr.to_onnx().unwrap();
+
+        let model = decode(&bytes);
+        assert!(matches!(field(&model, 1)[0], Value::Varint(IR_VERSION)));
+        assert_eq!("smartcore", field(&model, 2)[0].string());
+        let opsets: Vec<(String, u64)> = field(&model, 8)
+            .iter()
+            .map(|o| {
+                let opset = decode(o.bytes());
+                let version = match field(&opset, 2)[0] {
+                    Value::Varint(v) => v,
+                    _ => panic!(),
+                };
+                (field(&opset, 1)[0].string(), version)
+            })
+            .collect();
+        assert_eq!(
+            vec![("".to_string(), 15), ("ai.onnx.ml".to_string(), 3)],
+            opsets
+        );
+
+        let graph = decode(field(&model, 7)[0].bytes());
+        let input = decode(field(&graph, 11)[0].bytes());
+        assert_eq!("X", field(&input, 1)[0].string());
+        let tensor_type = decode(field(&decode(field(&input, 2)[0].bytes()), 1)[0].bytes());
+        assert!(matches!(field(&tensor_type, 1)[0], Value::Varint(FLOAT)));
+        let dims = field(&decode(field(&tensor_type, 2)[0].bytes()), 1);
+        assert_eq!("N", field(&decode(dims[0].bytes()), 2)[0].string());
+        assert!(matches!(
+            field(&decode(dims[1].bytes()), 1)[0],
+            Value::Varint(2)
+        ));
+
+        let operator = decode_operator(&bytes);
+        assert_eq!("LinearRegressor", operator.op_type);
+        assert_eq!(vec!["Y"], operator.outputs);
+        let coefficients = operator.floats("coefficients");
+        assert!((coefficients[0] - 1.).abs() < 1e-5);
+        assert!((coefficients[1] - 2.).abs() < 1e-5);
+        assert!((operator.floats("intercepts")[0] - 3.).abs() < 1e-5);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn logistic_regression() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., -5.],
+            &[2., 5.],
+            &[3., -2.],
+            &[1., 2.],
+            &[2., 0.],
+            &[6., -5.],
+            &[7., 5.],
+            &[6., -2.],
+            &[7., 2.],
+            &[6., 0.],
+            &[8., -5.],
+            &[9., 5.],
+            &[10., -2.],
+            &[8., 2.],
+            &[9., 0.],
+        ]);
+
+        let binary: Vec<f64> = vec![0., 0., 1., 1., 0., 0., 1., 1., 0., 0., 0., 0., 1., 1., 0.];
+        let lr = LogisticRegression::fit(&x, &binary, Default::default()).unwrap();
+        let operator = decode_operator(&lr.to_onnx().unwrap());
+        assert_eq!("LinearClassifier", operator.op_type);
+        assert_eq!(vec!["label", "probabilities"], operator.outputs);
+        assert_eq!(vec![0, 1], operator.ints("classlabels_ints"));
+        assert_eq!("LOGISTIC", operator.string("post_transform"));
+        let coefficients = operator.floats("coefficients");
+        assert_eq!(4, coefficients.len());
+        assert_eq!(coefficients[0], -coefficients[2]);
+        let y_hat = lr.predict(&x).unwrap();
+        for (i, y_hat_i) in y_hat.iter().enumerate() {
+            let score = (0..2).fold(operator.floats("intercepts")[1], |acc, j| {
+                acc + coefficients[2 + j] * x.get(i, j) as f32
+            });
+            assert_eq!(*y_hat_i == 1., score > 0.);
+        }
+
+        let y: Vec<f64> = vec![0., 0., 1., 1., 2., 1., 1., 0., 0., 2., 1., 1., 0., 0., 1.];
+        let lr = LogisticRegression::fit(&x, &y, Default::default()).unwrap();
+        let operator = decode_operator(&lr.to_onnx().unwrap());
+        assert_eq!(vec![0, 1, 2], operator.ints("classlabels_ints"));
+        assert_eq!("SOFTMAX", operator.string("post_transform"));
+        assert_eq!(6, operator.floats("coefficients").len());
+        assert_eq!(3, operator.floats("intercepts").len());
+
+        let y: Vec<f64> = binary.iter().map(|y| y + 0.5).collect();
+        let lr = LogisticRegression::fit(&x, &y, Default::default()).unwrap();
+        assert!(lr.to_onnx().is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn scalers() {
+        let x = DenseMatrix::from_2d_array(&[&[0., 10.], &[1., 20.], &[3., 30.], &[4., 60.]]);
+        let check = |model: Vec<u8>, transformed: DenseMatrix<f64>| {
+            let operator = decode_operator(&model);
+            assert_eq!("Scaler", operator.op_type);
+            let (offset, scale) = (operator.floats("offset"), operator.floats("scale"));
+            for i in 0..4 {
+                for j in 0..2 {
+                    let y = (x.get(i, j) as f32 - offset[j]) * scale[j];
+                    assert!((y - transformed.get(i, j) as f32).abs() < 1e-5);
+                }
+            }
+        };
+
+        let standard: StandardScaler<f64> =
+            UnsupervisedEstimator::fit(&x, StandardScalerParameters::default()).unwrap();
+        check(standard.to_onnx().unwrap(), standard.transform(&x).unwrap());
+
+        let min_max: MinMaxScaler<f64> = UnsupervisedEstimator::fit(
+            &x,
+            MinMaxScalerParameters::default().with_feature_range(-1., 1.),
+        )
+        .unwrap();
+        check(min_max.to_onnx().unwrap(), min_max.transform(&x).unwrap());
+
+        let robust: RobustScaler<f64> =
+            UnsupervisedEstimator::fit(&x, RobustScalerParameters::default()).unwrap();
+        check(robust.to_onnx().unwrap(), robust.transform(&x).unwrap());
+    }
+}
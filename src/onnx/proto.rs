@@ -0,0 +1,107 @@
+//! Minimal encoder of the protocol buffers wire format, enough to write ONNX models.
+use alloc::vec::Vec;
+
+const VARINT: u32 = 0;
+const LENGTH_DELIMITED: u32 = 2;
+
+/// A protocol buffers message under construction, fields are appended in the order they are written.
+#[derive(Debug, Default)]
+pub(crate) struct Message {
+    buf: Vec<u8>,
+}
+
+impl Message {
+    pub(crate) fn new() -> Self {
+        Message::default()
+    }
+
+    pub(crate) fn varint(&mut self, field: u32, value: u64) -> &mut Self {
+        self.key(field, VARINT);
+        write_varint(&mut self.buf, value);
+        self
+    }
+
+    /// Negative values are written as 10 bytes two's complement, like protobuf does for `int64` fields.
+    pub(crate) fn int64(&mut self, field: u32, value: i64) -> &mut Self {
+        self.varint(field, value as u64)
+    }
+
+    pub(crate) fn bytes(&mut self, field: u32, value: &[u8]) -> &mut Self {
+        self.key(field, LENGTH_DELIMITED);
+        write_varint(&mut self.buf, value.len() as u64);
+        self.buf.extend_from_slice(value);
+        self
+    }
+
+    pub(crate) fn string(&mut self, field: u32, value: &str) -> &mut Self {
+        self.bytes(field, value.as_bytes())
+    }
+
+    pub(crate) fn message(&mut self, field: u32, value: &Message) -> &mut Self {
+        self.bytes(field, &value.buf)
+    }
+
+    /// Writes a repeated `float` field in the packed encoding.
+    pub(crate) fn floats(&mut self, field: u32, values: &[f32]) -> &mut Self {
+        let mut packed = Vec::with_capacity(4 * values.len());
+        for value in values {
+            packed.extend_from_slice(&value.to_le_bytes());
+        }
+        self.bytes(field, &packed)
+    }
+
+    /// Writes a repeated `int64` field in the packed encoding.
+    pub(crate) fn int64s(&mut self, field: u32, values: &[i64]) -> &mut Self {
+        let mut packed = Vec::new();
+        for value in values {
+            write_varint(&mut packed, *value as u64);
+        }
+        self.bytes(field, &packed)
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+
+    fn key(&mut self, field: u32, wire_type: u32) {
+        write_varint(&mut self.buf, u64::from(field << 3 | wire_type));
+    }
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn encode() {
+        let mut inner = Message::new();
+        inner.string(2, "testing");
+
+        let mut message = Message::new();
+        message
+            .varint(1, 150)
+            .message(3, &inner)
+            .int64(4, -1)
+            .int64s(6, &[3, 270]);
+
+        assert_eq!(
+            vec![
+                0x08, 0x96, 0x01, // field 1 = 150
+                0x1a, 0x09, 0x12, 0x07, b't', b'e', b's', b't', b'i', b'n', b'g', // field 3
+                0x20, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                0x01, // field 4 = -1
+                0x32, 0x03, 0x03, 0x8e, 0x02, // packed field 6 = [3, 270]
+            ],
+            message.into_bytes()
+        );
+    }
+}
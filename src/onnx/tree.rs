@@ -0,0 +1,442 @@
+//! Attributes of the `TreeEnsembleRegressor` and `TreeEnsembleClassifier` operators.
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::error::Failed;
+use crate::math::num::RealNumber;
+use crate::onnx::{floats, post_transform, Attribute, Operator};
+use crate::tree::node::TreeNode;
+
+/// Nodes of all trees of an ensemble. Every leaf adds weights to some of the outputs of the ensemble,
+/// the targets of a regressor or the scores of the classes of a classifier.
+#[derive(Debug, Default)]
+pub(crate) struct TreeEnsemble {
+    tree_ids: Vec<i64>,
+    node_ids: Vec<i64>,
+    feature_ids: Vec<i64>,
+    modes: Vec<String>,
+    values: Vec<f32>,
+    true_node_ids: Vec<i64>,
+    false_node_ids: Vec<i64>,
+    missing_tracks_true: Vec<i64>,
+    leaf_tree_ids: Vec<i64>,
+    leaf_node_ids: Vec<i64>,
+    leaf_output_ids: Vec<i64>,
+    leaf_weights: Vec<f32>,
+}
+
+impl TreeEnsemble {
+    /// Adds a split, observations with a value of `feature` less than or equal to `threshold` go to `true_child`.
+    /// Observations with a missing value go to `true_child` when `missing_true` is set and to `false_child` otherwise.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn add_split<T: RealNumber>(
+        &mut self,
+        tree_id: usize,
+        node_id: usize,
+        feature: usize,
+        threshold: T,
+        true_child: usize,
+        false_child: usize,
+        missing_true: bool,
+    ) {
+        self.add_node(
+            tree_id,
+            node_id,
+            "BRANCH_LEQ",
+            feature,
+            threshold.to_f32().unwrap(),
+        );
+        self.true_node_ids.push(true_child as i64);
+        self.false_node_ids.push(false_child as i64);
+        self.missing_tracks_true.push(missing_true as i64);
+    }
+
+    /// Adds a leaf with `(output, weight)` pairs.
+    pub(crate) fn add_leaf(&mut self, tree_id: usize, node_id: usize, weights: &[(usize, f32)]) {
+        self.add_node(tree_id, node_id, "LEAF", 0, 0.);
+        self.true_node_ids.push(0);
+        self.false_node_ids.push(0);
+        self.missing_tracks_true.push(0);
+        for (output, weight) in weights {
+            self.leaf_tree_ids.push(tree_id as i64);
+            self.leaf_node_ids.push(node_id as i64);
+            self.leaf_output_ids.push(*output as i64);
+            self.leaf_weights.push(*weight);
+        }
+    }
+
+    /// Adds all nodes of a decision tree, `leaf_weights` gives the weights of a leaf.
+    pub(crate) fn add_tree<'a, T: RealNumber + 'a>(
+        &mut self,
+        tree_id: usize,
+        nodes: impl Iterator<Item = TreeNode<'a, T>>,
+        leaf_weights: impl Fn(&TreeNode<'a, T>) -> Vec<(usize, f32)>,
+    ) -> Result<(), Failed> {
+        for node in nodes {
+            match (
+                node.split_feature,
+                node.threshold,
+                node.true_child,
+                node.false_child,
+            ) {
+                _ if node.split_weights.is_some() => {
+                    return Err(Failed::export(
+                        "Oblique splits can't be expressed with ONNX-ML tree operators",
+                    ))
+                }
+                (Some(feature), Some(threshold), Some(true_child), Some(false_child)) => self
+                    .add_split(
+                        tree_id,
+                        node.index,
+                        feature,
+                        threshold,
+                        true_child,
+                        false_child,
+                        false,
+                    ),
+                _ => self.add_leaf(tree_id, node.index, &leaf_weights(&node)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds a `TreeEnsembleRegressor` with a single target.
+    /// * `aggregate_function` - how the weights of the trees are combined, `SUM` or `AVERAGE`
+    /// * `base_value` - value added to the aggregated weights
+    pub(crate) fn regressor(self, aggregate_function: &str, base_value: f32) -> Operator {
+        self.into_operator(
+            "TreeEnsembleRegressor",
+            [
+                "target_treeids",
+                "target_nodeids",
+                "target_ids",
+                "target_weights",
+            ],
+        )
+        .with(
+            "aggregate_function",
+            Attribute::String(aggregate_function.to_string()),
+        )
+        .with("base_values", Attribute::Floats(vec![base_value]))
+        .with("n_targets", Attribute::Int(1))
+        .with("post_transform", post_transform("NONE"))
+    }
+
+    /// Builds a `TreeEnsembleClassifier`, the scores of the classes are the sums of the weights of the trees.
+    /// * `labels` - integer labels of the classes
+    /// * `base_values` - values added to the scores of the classes, empty for none
+    /// * `transform` - function applied to the scores, e.g. `SOFTMAX`
+    pub(crate) fn classifier<T: RealNumber>(
+        self,
+        labels: Vec<i64>,
+        base_values: &[T],
+        transform: &str,
+    ) -> Operator {
+        let operator = self
+            .into_operator(
+                "TreeEnsembleClassifier",
+                [
+                    "class_treeids",
+                    "class_nodeids",
+                    "class_ids",
+                    "class_weights",
+                ],
+            )
+            .with("classlabels_int64s", Attribute::Ints(labels))
+            .with("post_transform", post_transform(transform));
+        if base_values.is_empty() {
+            operator
+        } else {
+            operator.with(
+                "base_values",
+                Attribute::Floats(floats(base_values.iter().copied())),
+            )
+        }
+    }
+
+    /// Adds the nodes and the weights of the leaves to a new operator, `leaf_attributes` are the names of
+    /// the attributes with the tree ids, node ids, output ids and weights of the leaves.
+    fn into_operator(self, op_type: &'static str, leaf_attributes: [&'static str; 4]) -> Operator {
+        let operator = if self.missing_tracks_true.iter().any(|m| *m != 0) {
+            Operator::new(op_type).with(
+                "nodes_missing_value_tracks_true",
+                Attribute::Ints(self.missing_tracks_true),
+            )
+        } else {
+            Operator::new(op_type)
+        };
+        operator
+            .with("nodes_treeids", Attribute::Ints(self.tree_ids))
+            .with("nodes_nodeids", Attribute::Ints(self.node_ids))
+            .with("nodes_featureids", Attribute::Ints(self.feature_ids))
+            .with("nodes_modes", Attribute::Strings(self.modes))
+            .with("nodes_values", Attribute::Floats(self.values))
+            .with("nodes_truenodeids", Attribute::Ints(self.true_node_ids))
+            .with("nodes_falsenodeids", Attribute::Ints(self.false_node_ids))
+            .with(leaf_attributes[0], Attribute::Ints(self.leaf_tree_ids))
+            .with(leaf_attributes[1], Attribute::Ints(self.leaf_node_ids))
+            .with(leaf_attributes[2], Attribute::Ints(self.leaf_output_ids))
+            .with(leaf_attributes[3], Attribute::Floats(self.leaf_weights))
+    }
+
+    fn add_node(&mut self, tree_id: usize, node_id: usize, mode: &str, feature: usize, value: f32) {
+        self.tree_ids.push(tree_id as i64);
+        self.node_ids.push(node_id as i64);
+        self.feature_ids.push(feature as i64);
+        self.modes.push(mode.to_string());
+        self.values.push(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ensemble::hist_gradient_boosting::*;
+    use crate::ensemble::random_forest_classifier::*;
+    use crate::ensemble::random_forest_regressor::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::linalg::BaseMatrix;
+    use crate::onnx::tests::{decode_operator, Decoded};
+    use crate::onnx::ToOnnx;
+    use crate::tree::decision_tree_classifier::*;
+    use crate::tree::decision_tree_regressor::*;
+    use crate::tree::oblique::SplitType;
+
+    /// Evaluates a decoded tree ensemble the way ONNX-ML runtimes do, returns the aggregated weights of every output before `post_transform`.
+    fn evaluate(operator: &Decoded, prefix: &str, n_outputs: usize, x: &[f32]) -> Vec<f32> {
+        let tree_ids = operator.ints("nodes_treeids");
+        let node_ids = operator.ints("nodes_nodeids");
+        let features = operator.ints("nodes_featureids");
+        let modes = operator.strings("nodes_modes");
+        let values = operator.floats("nodes_values");
+        let true_ids = operator.ints("nodes_truenodeids");
+        let false_ids = operator.ints("nodes_falsenodeids");
+        let missing_true = if operator.has("nodes_missing_value_tracks_true") {
+            operator.ints("nodes_missing_value_tracks_true")
+        } else {
+            vec![0; tree_ids.len()]
+        };
+        let position = |tree: i64, node: i64| {
+            (0..tree_ids.len())
+                .find(|i| tree_ids[*i] == tree && node_ids[*i] == node)
+                .unwrap()
+        };
+
+        let leaf_trees = operator.ints(&format!("{}_treeids", prefix));
+        let leaf_nodes = operator.ints(&format!("{}_nodeids", prefix));
+        let leaf_outputs = operator.ints(&format!("{}_ids", prefix));
+        let leaf_weights = operator.floats(&format!("{}_weights", prefix));
+
+        let mut trees = tree_ids.clone();
+        trees.dedup();
+        let mut result = vec![0f32; n_outputs];
+        for tree in trees {
+            let mut i = position(tree, 0);
+            while modes[i] == "BRANCH_LEQ" {
+                let x_i = x[features[i] as usize];
+                let next = if x_i <= values[i] || (x_i.is_nan() && missing_true[i] == 1) {
+                    true_ids[i]
+                } else {
+                    false_ids[i]
+                };
+                i = position(tree, next);
+            }
+            for l in 0..leaf_trees.len() {
+                if leaf_trees[l] == tree && leaf_nodes[l] == node_ids[i] {
+                    result[leaf_outputs[l] as usize] += leaf_weights[l];
+                }
+            }
+        }
+        result
+    }
+
+    fn iris() -> (DenseMatrix<f64>, Vec<f64>) {
+        let x = DenseMatrix::from_2d_array(&[
+            &[5.1, 3.5, 1.4, 0.2],
+            &[4.9, 3.0, 1.4, 0.2],
+            &[4.7, 3.2, 1.3, 0.2],
+            &[4.6, 3.1, 1.5, 0.2],
+            &[5.0, 3.6, 1.4, 0.2],
+            &[5.4, 3.9, 1.7, 0.4],
+            &[4.6, 3.4, 1.4, 0.3],
+            &[5.0, 3.4, 1.5, 0.2],
+            &[4.4, 2.9, 1.4, 0.2],
+            &[4.9, 3.1, 1.5, 0.1],
+            &[7.0, 3.2, 4.7, 1.4],
+            &[6.4, 3.2, 4.5, 1.5],
+            &[6.9, 3.1, 4.9, 1.5],
+            &[5.5, 2.3, 4.0, 1.3],
+            &[6.5, 2.8, 4.6, 1.5],
+            &[5.7, 2.8, 4.5, 1.3],
+            &[6.3, 3.3, 4.7, 1.6],
+            &[4.9, 2.4, 3.3, 1.0],
+            &[6.6, 2.9, 4.6, 1.3],
+            &[5.2, 2.7, 3.9, 1.4],
+        ]);
+        let y = vec![
+            0., 0., 0., 0., 0., 0., 0., 0., 0., 0., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1.,
+        ];
+        (x, y)
+    }
+
+    fn row(x: &DenseMatrix<f64>, i: usize) -> Vec<f32> {
+        x.get_row_as_vec(i).iter().map(|v| *v as f32).collect()
+    }
+
+    fn argmax(scores: &[f32]) -> usize {
+        (0..scores.len()).fold(0, |best, k| if scores[k] > scores[best] { k } else { best })
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn decision_tree_classifier() {
+        let (x, y) = iris();
+        let tree = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
+        let operator = decode_operator(&tree.to_onnx().unwrap());
+        assert_eq!("TreeEnsembleClassifier", operator.op_type);
+        assert_eq!(vec![0, 1], operator.ints("classlabels_int64s"));
+        assert_eq!("NONE", operator.string("post_transform"));
+
+        let probabilities = tree.predict_proba(&x).unwrap();
+        for i in 0..y.len() {
+            let scores = evaluate(&operator, "class", 2, &row(&x, i));
+            for (k, score) in scores.iter().enumerate() {
+                assert!((*score as f64 - probabilities.get(i, k)).abs() < 1e-6);
+            }
+        }
+
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 0.],
+            &[2., 1.],
+            &[3., 2.],
+            &[4., 3.],
+            &[0., 1.],
+            &[1., 2.],
+            &[2., 3.],
+            &[3., 4.],
+        ]);
+        let y = vec![0., 0., 0., 0., 1., 1., 1., 1.];
+        let oblique = DecisionTreeClassifier::fit(
+            &x,
+            &y,
+            DecisionTreeClassifierParameters::default().with_split_type(SplitType::Oblique {
+                max_features: 2,
+                alpha: 1e-3,
+            }),
+        )
+        .unwrap();
+        assert!(oblique.to_onnx().is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn decision_tree_regressor() {
+        let (x, _) = iris();
+        let y: Vec<f64> = (0..20).map(|i| x.get(i, 2) * 2. + x.get(i, 3)).collect();
+        let tree = DecisionTreeRegressor::fit(&x, &y, Default::default()).unwrap();
+        let operator = decode_operator(&tree.to_onnx().unwrap());
+        assert_eq!("TreeEnsembleRegressor", operator.op_type);
+        assert_eq!(1, operator.int("n_targets"));
+
+        let y_hat = tree.predict(&x).unwrap();
+        for (i, y_hat_i) in y_hat.iter().enumerate() {
+            let y_onnx = evaluate(&operator, "target", 1, &row(&x, i))[0];
+            assert!((y_onnx as f64 - y_hat_i).abs() < 1e-4);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn random_forests() {
+        let (x, y) = iris();
+        let forest = RandomForestClassifier::fit(
+            &x,
+            &y,
+            RandomForestClassifierParameters::default()
+                .with_n_trees(10)
+                .with_seed(7),
+        )
+        .unwrap();
+        let operator = decode_operator(&forest.to_onnx().unwrap());
+        let y_hat = forest.predict(&x).unwrap();
+        for (i, y_hat_i) in y_hat.iter().enumerate() {
+            let scores = evaluate(&operator, "class", 2, &row(&x, i));
+            assert!((scores.iter().sum::<f32>() - 1.).abs() < 1e-5);
+            assert_eq!(*y_hat_i, argmax(&scores) as f64);
+        }
+
+        let y: Vec<f64> = (0..20).map(|i| x.get(i, 2) * 2. + x.get(i, 3)).collect();
+        let forest = RandomForestRegressor::fit(
+            &x,
+            &y,
+            RandomForestRegressorParameters::default()
+                .with_n_trees(10)
+                .with_seed(7),
+        )
+        .unwrap();
+        let operator = decode_operator(&forest.to_onnx().unwrap());
+        assert_eq!("AVERAGE", operator.string("aggregate_function"));
+        let y_hat = forest.predict(&x).unwrap();
+        for (i, y_hat_i) in y_hat.iter().enumerate() {
+            let y_onnx = evaluate(&operator, "target", 1, &row(&x, i))[0] / 10.;
+            assert!((y_onnx as f64 - y_hat_i).abs() < 1e-4);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn hist_gradient_boosting() {
+        let (x, y) = iris();
+        let parameters = HistGradientBoostingClassifierParameters::default().with_max_iter(10);
+
+        let classifier = HistGradientBoostingClassifier::fit(&x, &y, parameters.clone()).unwrap();
+        let operator = decode_operator(&classifier.to_onnx().unwrap());
+        assert_eq!("LOGISTIC", operator.string("post_transform"));
+        let base_values = operator.floats("base_values");
+        let probabilities = classifier.predict_proba(&x).unwrap();
+        for i in 0..y.len() {
+            let scores = evaluate(&operator, "class", 2, &row(&x, i));
+            let p = 1. / (1. + (-(scores[1] + base_values[1])).exp());
+            let q = 1. / (1. + (-(scores[0] + base_values[0])).exp());
+            assert!((p as f64 - probabilities.get(i, 1)).abs() < 1e-4);
+            assert!((q as f64 - probabilities.get(i, 0)).abs() < 1e-4);
+        }
+
+        let y3: Vec<f64> = (0..20).map(|i| (i % 3) as f64).collect();
+        let classifier = HistGradientBoostingClassifier::fit(&x, &y3, parameters).unwrap();
+        let operator = decode_operator(&classifier.to_onnx().unwrap());
+        assert_eq!("SOFTMAX", operator.string("post_transform"));
+        let base_values = operator.floats("base_values");
+        let probabilities = classifier.predict_proba(&x).unwrap();
+        for i in 0..y3.len() {
+            let scores = evaluate(&operator, "class", 3, &row(&x, i));
+            let exp: Vec<f32> = (0..3).map(|k| (scores[k] + base_values[k]).exp()).collect();
+            let total: f32 = exp.iter().sum();
+            for (k, exp_k) in exp.iter().enumerate() {
+                assert!((exp_k / total - probabilities.get(i, k) as f32).abs() < 1e-4);
+            }
+        }
+
+        let mut x = x;
+        let y: Vec<f64> = (0..20).map(|i| x.get(i, 2) * 2. + x.get(i, 3)).collect();
+        for i in [1, 4, 7] {
+            x.set(i, 2, f64::NAN);
+        }
+        let regressor = HistGradientBoostingRegressor::fit(
+            &x,
+            &y,
+            HistGradientBoostingRegressorParameters::default()
+                .with_max_iter(10)
+                .with_min_samples_leaf(2),
+        )
+        .unwrap();
+        let operator = decode_operator(&regressor.to_onnx().unwrap());
+        assert_eq!("SUM", operator.string("aggregate_function"));
+        assert!(operator.has("nodes_missing_value_tracks_true"));
+        let base_value = operator.floats("base_values")[0];
+        let y_hat = regressor.predict(&x).unwrap();
+        for (i, y_hat_i) in y_hat.iter().enumerate() {
+            let y_onnx = evaluate(&operator, "target", 1, &row(&x, i))[0] + base_value;
+            assert!((y_onnx as f64 - y_hat_i).abs() < 1e-4);
+        }
+    }
+}
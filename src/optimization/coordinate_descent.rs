@@ -0,0 +1,234 @@
+use alloc::vec::Vec;
+use rand::Rng;
+
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::optimization::prox::ProxOperator;
+use crate::rand::get_rng_impl;
+
+/// Order in which coordinates are updated within an epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoordinateSelection {
+    /// Coordinates are updated one after another.
+    Cyclic,
+    /// Every step updates a coordinate drawn uniformly at random.
+    Random,
+}
+
+/// Smooth part of an objective minimized one coordinate at a time.
+/// Implementations usually cache quantities like residuals and refresh them in `coordinate_updated`.
+pub trait CoordinateObjective<T: RealNumber> {
+    /// Number of coordinates of `x`.
+    fn n_coordinates(&self) -> usize;
+
+    /// Partial derivative of the objective with respect to coordinate `j` at `x`.
+    fn partial_derivative(&self, x: &[T], j: usize) -> T;
+
+    /// Lipschitz constant of the partial derivative along coordinate `j`, the inverse of the step size.
+    fn lipschitz(&self, j: usize) -> T;
+
+    /// Called after coordinate `j` has moved by `delta`.
+    fn coordinate_updated(&mut self, _j: usize, _delta: T) {}
+}
+
+/// Least squares loss \\(\frac{1}{2n}\lVert y - Ax \rVert^2\\) with cached residuals.
+/// Coordinate steps are exact minimizations, which together with [`SoftThreshold`](../prox/struct.SoftThreshold.html)
+/// or [`ElasticNetPenalty`](../prox/struct.ElasticNetPenalty.html) solve Lasso and Elastic Net.
+pub struct LeastSquares<'a, T: RealNumber, M: Matrix<T>> {
+    a: &'a M,
+    residual: Vec<T>,
+    col_sq_norm: Vec<T>,
+    n: T,
+}
+
+impl<'a, T: RealNumber, M: Matrix<T>> LeastSquares<'a, T, M> {
+    /// Loss of design matrix `a` and targets `y`, with residuals computed at `x0`.
+    pub fn new(a: &'a M, y: &M::RowVector, x0: &[T]) -> Self {
+        let (n, p) = a.shape();
+        let mut residual = y.to_vec();
+        let mut col_sq_norm = vec![T::zero(); p];
+        for (i, r) in residual.iter_mut().enumerate() {
+            for (j, &x_j) in x0.iter().enumerate() {
+                let a_ij = a.get(i, j);
+                *r -= a_ij * x_j;
+                col_sq_norm[j] += a_ij * a_ij;
+            }
+        }
+
+        LeastSquares {
+            a,
+            residual,
+            col_sq_norm,
+            n: T::from_usize(n).unwrap(),
+        }
+    }
+}
+
+impl<'a, T: RealNumber, M: Matrix<T>> CoordinateObjective<T> for LeastSquares<'a, T, M> {
+    fn n_coordinates(&self) -> usize {
+        self.col_sq_norm.len()
+    }
+
+    fn partial_derivative(&self, _x: &[T], j: usize) -> T {
+        -self
+            .residual
+            .iter()
+            .enumerate()
+            .fold(T::zero(), |s, (i, &r)| s + self.a.get(i, j) * r)
+            / self.n
+    }
+
+    fn lipschitz(&self, j: usize) -> T {
+        self.col_sq_norm[j] / self.n
+    }
+
+    fn coordinate_updated(&mut self, j: usize, delta: T) {
+        for (i, r) in self.residual.iter_mut().enumerate() {
+            *r -= self.a.get(i, j) * delta;
+        }
+    }
+}
+
+/// Proximal coordinate descent. Every step moves a single coordinate to
+/// `prox(x_j - partial_derivative / L_j, 1 / L_j)`.
+pub struct CoordinateDescent {
+    /// Order in which coordinates are updated.
+    pub selection: CoordinateSelection,
+    /// Maximum number of epochs.
+    pub max_iter: usize,
+    /// Stops when the largest coordinate change in an epoch is below `tol` times the largest coordinate.
+    pub tol: f64,
+    /// Seed of the random generator for `CoordinateSelection::Random`.
+    pub seed: Option<u64>,
+}
+
+impl Default for CoordinateDescent {
+    fn default() -> Self {
+        CoordinateDescent {
+            selection: CoordinateSelection::Cyclic,
+            max_iter: 1000,
+            tol: 1e-4,
+            seed: None,
+        }
+    }
+}
+
+/// Result of coordinate descent.
+#[derive(Debug, Clone)]
+pub struct CoordinateDescentResult<T: RealNumber> {
+    /// Minimizer found.
+    pub x: Vec<T>,
+    /// Number of epochs, each makes as many steps as there are coordinates.
+    pub iterations: usize,
+}
+
+impl CoordinateDescent {
+    /// Minimizes `objective` plus the penalty of `prox` starting from `x0`.
+    pub fn optimize<T: RealNumber, O: CoordinateObjective<T>, P: ProxOperator<T>>(
+        &self,
+        objective: &mut O,
+        prox: &P,
+        x0: &[T],
+    ) -> CoordinateDescentResult<T> {
+        let p = objective.n_coordinates();
+        let tol = T::from_f64(self.tol).unwrap();
+        let mut rng = get_rng_impl(self.seed);
+        let mut x = x0.to_vec();
+
+        let mut iter = 0;
+        while iter < self.max_iter {
+            iter += 1;
+
+            let mut max_delta = T::zero();
+            for step in 0..p {
+                let j = match self.selection {
+                    CoordinateSelection::Cyclic => step,
+                    CoordinateSelection::Random => rng.gen_range(0..p),
+                };
+
+                let l = objective.lipschitz(j);
+                if l <= T::zero() {
+                    continue;
+                }
+
+                let g = objective.partial_derivative(&x, j);
+                let x_j = prox.prox(x[j] - g / l, T::one() / l, j);
+                let delta = x_j - x[j];
+                if delta != T::zero() {
+                    x[j] = x_j;
+                    objective.coordinate_updated(j, delta);
+                    max_delta = max_delta.max(delta.abs());
+                }
+            }
+
+            let max_x = x.iter().fold(T::zero(), |m, x_j| m.max(x_j.abs()));
+            if max_delta == T::zero() || max_delta <= tol * max_x {
+                break;
+            }
+        }
+
+        CoordinateDescentResult {
+            x,
+            iterations: iter,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::optimization::prox::{ElasticNetPenalty, Identity, SoftThreshold};
+
+    fn orthogonal_design() -> (DenseMatrix<f64>, Vec<f64>) {
+        // A'A / n = I and A'y / n = [2, 1], so every penalized solution is the prox of [2, 1]
+        let a = DenseMatrix::from_2d_array(&[&[1., 1.], &[1., -1.], &[1., 1.], &[1., -1.]]);
+        (a, vec![3., 1., 3., 1.])
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn coordinate_descent_lasso() {
+        let (a, y) = orthogonal_design();
+        let optimizer: CoordinateDescent = Default::default();
+
+        let mut objective = LeastSquares::new(&a, &y, &[0., 0.]);
+        let result = optimizer.optimize(&mut objective, &SoftThreshold { lambda: 0.5 }, &[0., 0.]);
+        assert_eq!(vec![1.5, 0.5], result.x);
+
+        let mut objective = LeastSquares::new(&a, &y, &[0., 0.]);
+        let result = optimizer.optimize(
+            &mut objective,
+            &ElasticNetPenalty { l1: 0.5, l2: 1. },
+            &[0., 0.],
+        );
+        assert_eq!(vec![0.75, 0.25], result.x);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn coordinate_descent_random_selection() {
+        let a = DenseMatrix::from_2d_array(&[
+            &[1., 2., 0.5],
+            &[-1., 0.5, 1.],
+            &[2., 1., -1.],
+            &[0., 1., 2.],
+            &[1., -1., 1.],
+        ]);
+        let y: Vec<f64> = vec![3.5, 0.5, 2., 3., 1.];
+        let optimizer = CoordinateDescent {
+            selection: CoordinateSelection::Random,
+            tol: 1e-10,
+            seed: Some(42),
+            ..Default::default()
+        };
+
+        let mut objective = LeastSquares::new(&a, &y, &[0., 0., 0.]);
+        let result = optimizer.optimize(&mut objective, &Identity {}, &[0., 0., 0.]);
+
+        // y = A * [1, 1, 1] exactly
+        for x_j in result.x {
+            assert!((x_j - 1.).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use core::default::Default;
+
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::optimization::first_order::stochastic::{MiniBatches, StochasticOptimizer};
+use crate::optimization::first_order::OptimizerResult;
+use crate::optimization::{BatchDF, BatchF};
+
+/// Optimizer that scales the step of every parameter by statistics of its past gradients.
+/// Call `update` with the gradient of every mini-batch.
+pub trait AdaptiveOptimizer<T: RealNumber> {
+    /// Makes a single step from `x` along gradient `g` and updates the gradient statistics in `state`.
+    fn update<X: Matrix<T>>(&self, state: &mut AdaptiveState<T, X>, x: &mut X, g: &X);
+}
+
+/// Running gradient statistics of an adaptive optimizer, one element per parameter.
+#[derive(Debug, Clone)]
+pub struct AdaptiveState<T: RealNumber, X: Matrix<T>> {
+    /// First moment, moving average of gradients.
+    pub m: X,
+    /// Second moment, moving average or sum of squared gradients.
+    pub v: X,
+    /// Number of updates done so far.
+    pub t: usize,
+    _phantom: core::marker::PhantomData<T>,
+}
+
+impl<T: RealNumber, X: Matrix<T>> AdaptiveState<T, X> {
+    /// Empty statistics for parameters of the same shape as `x`.
+    pub fn new(x: &X) -> Self {
+        let (nrows, ncols) = x.shape();
+        AdaptiveState {
+            m: X::zeros(nrows, ncols),
+            v: X::zeros(nrows, ncols),
+            t: 0,
+            _phantom: core::marker::PhantomData,
+        }
+    }
+}
+
+/// Adam, steps along the moving average of gradients divided by the square root of the moving average of squared gradients,
+/// both corrected for their bias towards zero in the first steps.
+pub struct Adam<T: RealNumber> {
+    /// Step size.
+    pub learning_rate: T,
+    /// Decay rate of the moving average of gradients.
+    pub beta1: T,
+    /// Decay rate of the moving average of squared gradients.
+    pub beta2: T,
+    /// Small constant that keeps the division finite.
+    pub epsilon: T,
+}
+
+impl<T: RealNumber> Default for Adam<T> {
+    fn default() -> Self {
+        Adam {
+            learning_rate: T::from(1e-3).unwrap(),
+            beta1: T::from(0.9).unwrap(),
+            beta2: T::from(0.999).unwrap(),
+            epsilon: T::from(1e-8).unwrap(),
+        }
+    }
+}
+
+impl<T: RealNumber> AdaptiveOptimizer<T> for Adam<T> {
+    fn update<X: Matrix<T>>(&self, state: &mut AdaptiveState<T, X>, x: &mut X, g: &X) {
+        state.t += 1;
+        // bias correction of both moments folded into the step size
+        let t = state.t as i32;
+        let learning_rate = self.learning_rate * (T::one() - self.beta2.powi(t)).sqrt()
+            / (T::one() - self.beta1.powi(t));
+
+        let (nrows, ncols) = x.shape();
+        for r in 0..nrows {
+            for c in 0..ncols {
+                let g_rc = g.get(r, c);
+                let m = self.beta1 * state.m.get(r, c) + (T::one() - self.beta1) * g_rc;
+                let v = self.beta2 * state.v.get(r, c) + (T::one() - self.beta2) * g_rc * g_rc;
+                state.m.set(r, c, m);
+                state.v.set(r, c, v);
+                x.sub_element_mut(r, c, learning_rate * m / (v.sqrt() + self.epsilon));
+            }
+        }
+    }
+}
+
+/// RMSProp, divides the gradient by the square root of the moving average of squared gradients.
+pub struct RMSProp<T: RealNumber> {
+    /// Step size.
+    pub learning_rate: T,
+    /// Decay rate of the moving average of squared gradients.
+    pub rho: T,
+    /// Small constant that keeps the division finite.
+    pub epsilon: T,
+}
+
+impl<T: RealNumber> Default for RMSProp<T> {
+    fn default() -> Self {
+        RMSProp {
+            learning_rate: T::from(1e-3).unwrap(),
+            rho: T::from(0.9).unwrap(),
+            epsilon: T::from(1e-8).unwrap(),
+        }
+    }
+}
+
+impl<T: RealNumber> AdaptiveOptimizer<T> for RMSProp<T> {
+    fn update<X: Matrix<T>>(&self, state: &mut AdaptiveState<T, X>, x: &mut X, g: &X) {
+        state.t += 1;
+
+        let (nrows, ncols) = x.shape();
+        for r in 0..nrows {
+            for c in 0..ncols {
+                let g_rc = g.get(r, c);
+                let v = self.rho * state.v.get(r, c) + (T::one() - self.rho) * g_rc * g_rc;
+                state.v.set(r, c, v);
+                x.sub_element_mut(r, c, self.learning_rate * g_rc / (v.sqrt() + self.epsilon));
+            }
+        }
+    }
+}
+
+/// AdaGrad, divides the gradient by the square root of the sum of all past squared gradients.
+pub struct AdaGrad<T: RealNumber> {
+    /// Step size.
+    pub learning_rate: T,
+    /// Small constant that keeps the division finite.
+    pub epsilon: T,
+}
+
+impl<T: RealNumber> Default for AdaGrad<T> {
+    fn default() -> Self {
+        AdaGrad {
+            learning_rate: T::from(1e-2).unwrap(),
+            epsilon: T::from(1e-8).unwrap(),
+        }
+    }
+}
+
+impl<T: RealNumber> AdaptiveOptimizer<T> for AdaGrad<T> {
+    fn update<X: Matrix<T>>(&self, state: &mut AdaptiveState<T, X>, x: &mut X, g: &X) {
+        state.t += 1;
+
+        let (nrows, ncols) = x.shape();
+        for r in 0..nrows {
+            for c in 0..ncols {
+                let g_rc = g.get(r, c);
+                let v = state.v.get(r, c) + g_rc * g_rc;
+                state.v.set(r, c, v);
+                x.sub_element_mut(r, c, self.learning_rate * g_rc / (v.sqrt() + self.epsilon));
+            }
+        }
+    }
+}
+
+macro_rules! impl_stochastic_optimizer {
+    ($optimizer:ident) => {
+        impl<T: RealNumber> StochasticOptimizer<T> for $optimizer<T> {
+            fn optimize_batched<X: Matrix<T>>(
+                &self,
+                f: &BatchF<'_, T, X>,
+                df: &BatchDF<'_, X>,
+                x0: &X,
+                n_samples: usize,
+                batches: &MiniBatches,
+            ) -> OptimizerResult<T, X> {
+                let mut state = AdaptiveState::new(x0);
+                batches.run(f, df, x0, n_samples, |x, g, _| {
+                    self.update(&mut state, x, g)
+                })
+            }
+        }
+    };
+}
+
+impl_stochastic_optimizer!(Adam);
+impl_stochastic_optimizer!(RMSProp);
+impl_stochastic_optimizer!(AdaGrad);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::linalg::BaseMatrix;
+
+    fn df(g: &mut DenseMatrix<f64>, x: &DenseMatrix<f64>) {
+        g.set(0, 0, 2. * (x.get(0, 0) - 3.));
+        g.set(0, 1, 20. * (x.get(0, 1) + 1.));
+    }
+
+    fn assert_minimum<O: AdaptiveOptimizer<f64>>(optimizer: &O) {
+        // minimizes (x0 - 3)^2 + 10 * (x1 + 1)^2 with full gradients
+        let mut x = DenseMatrix::row_vector_from_array(&[0., 0.]);
+        let mut g = DenseMatrix::zeros(1, 2);
+        let mut state = AdaptiveState::new(&x);
+        for _ in 0..10000 {
+            df(&mut g, &x);
+            optimizer.update(&mut state, &mut x, &g);
+        }
+
+        assert!((x.get(0, 0) - 3.0).abs() < 1e-2);
+        assert!((x.get(0, 1) + 1.0).abs() < 1e-2);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn adam() {
+        assert_minimum(&Adam {
+            learning_rate: 0.1,
+            ..Default::default()
+        });
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn rmsprop() {
+        assert_minimum(&RMSProp {
+            learning_rate: 0.01,
+            ..Default::default()
+        });
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn adagrad() {
+        assert_minimum(&AdaGrad {
+            learning_rate: 1.,
+            ..Default::default()
+        });
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn adam_mini_batches() {
+        // least squares fit of y = 2x - 1 from mini-batches of two samples
+        let x = [0., 1., 2., 3., 4., 5.];
+        let y: Vec<f64> = x.iter().map(|x| 2. * x - 1.).collect();
+
+        let optimizer = Adam {
+            learning_rate: 0.05,
+            ..Default::default()
+        };
+        let mut w = DenseMatrix::row_vector_from_array(&[0., 0.]);
+        let mut state = AdaptiveState::new(&w);
+        let mut g = DenseMatrix::zeros(1, 2);
+
+        for _ in 0..2000 {
+            for batch in [[0, 3], [1, 4], [2, 5]] {
+                g.copy_from(&DenseMatrix::zeros(1, 2));
+                for i in batch {
+                    let residual = w.get(0, 0) * x[i] + w.get(0, 1) - y[i];
+                    g.add_element_mut(0, 0, residual * x[i]);
+                    g.add_element_mut(0, 1, residual);
+                }
+                optimizer.update(&mut state, &mut w, &g);
+            }
+        }
+
+        assert_eq!(6000, state.t);
+        assert!((w.get(0, 0) - 2.).abs() < 1e-2);
+        assert!((w.get(0, 1) + 1.).abs() < 1e-2);
+    }
+}
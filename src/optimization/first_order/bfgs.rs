@@ -0,0 +1,219 @@
+use alloc::vec::Vec;
+use core::default::Default;
+
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::optimization::first_order::{FirstOrderOptimizer, OptimizerResult, OptimizerStatus};
+use crate::optimization::line_search::LineSearchMethod;
+use crate::optimization::{DF, F};
+
+/// BFGS keeps a dense _NxN_ approximation of the inverse Hessian, where _N_ is the length of the parameter row vector.
+/// Prefer [`LBFGS`](../lbfgs/struct.LBFGS.html) when _N_ is large.
+#[allow(clippy::upper_case_acronyms)]
+pub struct BFGS<T: RealNumber> {
+    /// Maximum number of iterations.
+    pub max_iter: usize,
+    /// Stops when the infinity norm of the gradient drops below `g_atol`.
+    pub g_atol: T,
+    /// Stops when no element of `x` changes by more than `x_atol` in an iteration.
+    pub x_atol: T,
+}
+
+impl<T: RealNumber> Default for BFGS<T> {
+    fn default() -> Self {
+        BFGS {
+            max_iter: 1000,
+            g_atol: T::from(1e-8).unwrap(),
+            x_atol: T::zero(),
+        }
+    }
+}
+
+impl<T: RealNumber> BFGS<T> {
+    /// Search direction `-H * g`.
+    fn direction<X: Matrix<T>>(&self, h: &X, g: &X, s: &mut X) {
+        let n = h.shape().0;
+        for i in 0..n {
+            let si = (0..n).fold(T::zero(), |s, j| s - h.get(i, j) * g.get(0, j));
+            s.set(0, i, si);
+        }
+    }
+
+    /// Rank two update of the inverse Hessian approximation with step `dx` and gradient change `dg`.
+    fn update_hessian<X: Matrix<T>>(&self, h: &mut X, dx: &X, dg: &X, first: bool) {
+        let n = h.shape().0;
+
+        let sy = dx.dot(dg);
+        // pairs without positive curvature would make the approximation indefinite
+        if !(sy.is_finite() && sy > T::zero()) {
+            return;
+        }
+
+        if first {
+            let yy = dg.dot(dg);
+            h.mul_scalar_mut(sy / yy);
+        }
+
+        let hy: Vec<T> = (0..n)
+            .map(|i| (0..n).fold(T::zero(), |s, j| s + h.get(i, j) * dg.get(0, j)))
+            .collect();
+        let yhy = (0..n).fold(T::zero(), |s, i| s + dg.get(0, i) * hy[i]);
+        let a = (sy + yhy) / (sy * sy);
+
+        for i in 0..n {
+            let dx_i = dx.get(0, i);
+            for (j, hy_j) in hy.iter().enumerate() {
+                let dx_j = dx.get(0, j);
+                h.add_element_mut(i, j, a * dx_i * dx_j - (hy[i] * dx_j + dx_i * *hy_j) / sy);
+            }
+        }
+    }
+}
+
+impl<T: RealNumber> FirstOrderOptimizer<T> for BFGS<T> {
+    fn optimize<'a, X: Matrix<T>, LS: LineSearchMethod<T>>(
+        &self,
+        f: &F<'_, T, X>,
+        df: &'a DF<'_, X>,
+        x0: &X,
+        ls: &'a LS,
+    ) -> OptimizerResult<T, X> {
+        let n = x0.shape().1;
+
+        let mut h = X::eye(n);
+        let mut x = x0.clone();
+        let mut fx = f(&x);
+        let mut gvec = x0.clone();
+        df(&mut gvec, &x);
+        let mut step = x0.clone();
+
+        let mut iter = 0;
+        let mut f_history = Vec::new();
+        let mut x_converged = false;
+        while iter < self.max_iter && gvec.norm(T::infinity()) > self.g_atol {
+            self.direction(&h, &gvec, &mut step);
+            let mut df0 = step.dot(&gvec);
+            if df0 >= T::zero() {
+                // not a descent direction, restart from steepest descent
+                h = X::eye(n);
+                step = gvec.negative();
+                df0 = step.dot(&gvec);
+            }
+
+            let f_alpha = |alpha: T| -> T {
+                let mut dx = step.clone();
+                dx.mul_scalar_mut(alpha);
+                f(dx.add_mut(&x)) // f(x) = f(x .+ step .* alpha)
+            };
+
+            let df_alpha = |alpha: T| -> T {
+                let mut dx = step.clone();
+                let mut dg = gvec.clone();
+                dx.mul_scalar_mut(alpha);
+                df(&mut dg, dx.add_mut(&x)); //df(x) = df(x .+ step .* alpha)
+                step.dot(&dg)
+            };
+
+            let ls_r = ls.search(&f_alpha, &df_alpha, T::one(), fx, df0);
+            fx = ls_r.f_x;
+
+            let x_prev = x.clone();
+            x.add_mut(&step.mul_scalar(ls_r.alpha));
+            // actual change of x, which is zero when the step is lost in rounding
+            let dx = x.sub(&x_prev);
+            let gprev = gvec.clone();
+            df(&mut gvec, &x);
+            self.update_hessian(&mut h, &dx, &gvec.sub(&gprev), iter == 0);
+
+            iter += 1;
+            f_history.push(fx);
+
+            if dx.norm(T::infinity()) <= self.x_atol {
+                x_converged = true;
+                break;
+            }
+        }
+
+        let f_x = f(&x);
+        let g_norm = gvec.norm(T::infinity());
+        let status = if g_norm <= self.g_atol {
+            OptimizerStatus::GradientTolerance
+        } else if x_converged {
+            OptimizerStatus::XTolerance
+        } else {
+            OptimizerStatus::MaxIterations
+        };
+
+        OptimizerResult {
+            x,
+            f_x,
+            iterations: iter,
+            status,
+            g_norm,
+            f_history,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::optimization::first_order::lbfgs::LBFGS;
+    use crate::optimization::line_search::Backtracking;
+    use crate::optimization::FunctionOrder;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn bfgs() {
+        let x0 = DenseMatrix::row_vector_from_array(&[0., 0.]);
+        let f = |x: &DenseMatrix<f64>| {
+            (1.0 - x.get(0, 0)).powf(2.) + 100.0 * (x.get(0, 1) - x.get(0, 0).powf(2.)).powf(2.)
+        };
+
+        let df = |g: &mut DenseMatrix<f64>, x: &DenseMatrix<f64>| {
+            g.set(
+                0,
+                0,
+                -2. * (1. - x.get(0, 0))
+                    - 400. * (x.get(0, 1) - x.get(0, 0).powf(2.)) * x.get(0, 0),
+            );
+            g.set(0, 1, 200. * (x.get(0, 1) - x.get(0, 0).powf(2.)));
+        };
+        let ls = Backtracking::<f64> {
+            order: FunctionOrder::THIRD,
+            ..Default::default()
+        };
+        let optimizer: BFGS<f64> = Default::default();
+
+        let result = optimizer.optimize(&f, &df, &x0, &ls);
+
+        assert!(result.f_x.abs() < 1e-12);
+        assert!((result.x.get(0, 0) - 1.0).abs() < 1e-6);
+        assert!((result.x.get(0, 1) - 1.0).abs() < 1e-6);
+
+        let lbfgs: LBFGS<f64> = Default::default();
+        assert!(result.iterations <= lbfgs.optimize(&f, &df, &x0, &ls).iterations);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn bfgs_quadratic() {
+        // f(x) = x'Ax/2 - b'x has its minimum at A^-1 b = [1, -2, 3]
+        let a = DenseMatrix::from_2d_array(&[&[4., 1., 0.], &[1., 3., 1.], &[0., 1., 2.]]);
+        let b = DenseMatrix::row_vector_from_array(&[2., -2., 4.]);
+        let f = |x: &DenseMatrix<f64>| x.matmul(&a).dot(x) / 2. - b.dot(x);
+        let df = |g: &mut DenseMatrix<f64>, x: &DenseMatrix<f64>| {
+            g.copy_from(&x.matmul(&a).sub(&b));
+        };
+        let ls: Backtracking<f64> = Default::default();
+        let optimizer: BFGS<f64> = Default::default();
+
+        let result = optimizer.optimize(&f, &df, &DenseMatrix::zeros(1, 3), &ls);
+
+        assert!(result
+            .x
+            .approximate_eq(&DenseMatrix::row_vector_from_array(&[1., -2., 3.]), 1e-6));
+        assert!(result.iterations <= 10);
+    }
+}
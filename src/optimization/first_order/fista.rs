@@ -0,0 +1,182 @@
+use alloc::vec::Vec;
+use core::default::Default;
+
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::optimization::first_order::{OptimizerResult, OptimizerStatus};
+use crate::optimization::prox::ProxOperator;
+use crate::optimization::{DF, F};
+
+/// Accelerated proximal gradient method (FISTA) for objectives `f(x) + g(x)`,
+/// where `f` is smooth and `g` is a penalty with a cheap proximal operator.
+/// The step size is found by backtracking, starting from `1 / lipschitz`.
+#[allow(clippy::upper_case_acronyms)]
+pub struct FISTA<T: RealNumber> {
+    /// Maximum number of iterations.
+    pub max_iter: usize,
+    /// Stops when no element of `x` changes by more than `x_atol` in an iteration.
+    pub x_atol: T,
+    /// Initial estimate of the Lipschitz constant of the gradient of `f`.
+    pub lipschitz: T,
+    /// Factor the Lipschitz estimate grows by when a step is too long.
+    pub eta: T,
+}
+
+impl<T: RealNumber> Default for FISTA<T> {
+    fn default() -> Self {
+        FISTA {
+            max_iter: 1000,
+            x_atol: T::from(1e-8).unwrap(),
+            lipschitz: T::one(),
+            eta: T::two(),
+        }
+    }
+}
+
+impl<T: RealNumber> FISTA<T> {
+    /// Minimizes `f(x) + g(x)`, where `prox` is the proximal operator of `g`.
+    /// The returned `f_x` and `f_history` include the penalty, `g_norm` is the infinity norm of the gradient mapping
+    /// `L * (x - prox(x - df(x) / L))`, which vanishes at a minimum.
+    pub fn optimize<X: Matrix<T>, P: ProxOperator<T>>(
+        &self,
+        f: &F<'_, T, X>,
+        df: &DF<'_, X>,
+        prox: &P,
+        x0: &X,
+    ) -> OptimizerResult<T, X> {
+        let mut x = x0.clone();
+        prox.prox_mut(&mut x, T::zero());
+        let mut y = x.clone();
+        let mut gvec = x0.clone();
+        let mut t = T::one();
+        let mut l = self.lipschitz;
+
+        let mut iter = 0;
+        let mut f_history = Vec::new();
+        let mut converged = false;
+        while iter < self.max_iter {
+            iter += 1;
+
+            let f_y = f(&y);
+            df(&mut gvec, &y);
+
+            let mut z;
+            let mut f_z;
+            loop {
+                z = gvec.mul_scalar(-T::one() / l);
+                z.add_mut(&y);
+                prox.prox_mut(&mut z, T::one() / l);
+
+                let dz = z.sub(&y);
+                let upper_bound = f_y + gvec.dot(&dz) + l * dz.dot(&dz) / T::two();
+                f_z = f(&z);
+                if f_z <= upper_bound || !upper_bound.is_finite() {
+                    break;
+                }
+                l *= self.eta;
+            }
+
+            let t_next = (T::one() + (T::one() + T::from(4.).unwrap() * t * t).sqrt()) / T::two();
+            let dx = z.sub(&x);
+            y = dx.mul_scalar((t - T::one()) / t_next);
+            y.add_mut(&z);
+
+            f_history.push(f_z + prox.penalty_sum(&z));
+            converged = dx.norm(T::infinity()) <= self.x_atol;
+            x = z;
+            t = t_next;
+
+            if converged {
+                break;
+            }
+        }
+
+        let f_x = f(&x) + prox.penalty_sum(&x);
+
+        df(&mut gvec, &x);
+        let mut mapping = gvec.mul_scalar(-T::one() / l);
+        mapping.add_mut(&x);
+        prox.prox_mut(&mut mapping, T::one() / l);
+        let g_norm = x.sub(&mapping).norm(T::infinity()) * l;
+
+        OptimizerResult {
+            x,
+            f_x,
+            iterations: iter,
+            status: if converged {
+                OptimizerStatus::XTolerance
+            } else {
+                OptimizerStatus::MaxIterations
+            },
+            g_norm,
+            f_history,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::optimization::prox::{BoxProjection, Identity, SoftThreshold};
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fista_lasso() {
+        // 1/(2n)|y - Ax|^2 + 0.5|x|_1 with A'A / n = I and A'y / n = [2, 1, -0.25]
+        let a = DenseMatrix::from_2d_array(&[
+            &[1., 1., 1.],
+            &[1., -1., -1.],
+            &[1., 1., -1.],
+            &[1., -1., 1.],
+        ]);
+        let y = DenseMatrix::row_vector_from_array(&[2.75, 1.25, 3.25, 0.75]);
+        let f = |x: &DenseMatrix<f64>| {
+            let r = x.matmul(&a.transpose()).sub(&y);
+            r.dot(&r) / 8.
+        };
+        let df = |g: &mut DenseMatrix<f64>, x: &DenseMatrix<f64>| {
+            let r = x.matmul(&a.transpose()).sub(&y);
+            g.copy_from(&r.matmul(&a).div_scalar(4.));
+        };
+        let optimizer: FISTA<f64> = Default::default();
+
+        let result = optimizer.optimize(
+            &f,
+            &df,
+            &SoftThreshold { lambda: 0.5 },
+            &DenseMatrix::zeros(1, 3),
+        );
+
+        assert!(result
+            .x
+            .approximate_eq(&DenseMatrix::row_vector_from_array(&[1.5, 0.5, 0.]), 1e-6));
+        assert!((result.f_x - (f(&result.x) + 1.)).abs() < 1e-8);
+        assert_eq!(OptimizerStatus::XTolerance, result.status);
+        assert!(result.g_norm < 1e-6);
+        assert_eq!(result.iterations, result.f_history.len());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fista_box_constraints() {
+        let x0 = DenseMatrix::row_vector_from_array(&[0., 0.]);
+        let f =
+            |x: &DenseMatrix<f64>| (x.get(0, 0) - 3.).powf(2.) + 10. * (x.get(0, 1) + 1.).powf(2.);
+        let df = |g: &mut DenseMatrix<f64>, x: &DenseMatrix<f64>| {
+            g.set(0, 0, 2. * (x.get(0, 0) - 3.));
+            g.set(0, 1, 20. * (x.get(0, 1) + 1.));
+        };
+        let optimizer: FISTA<f64> = Default::default();
+
+        let bounded = optimizer.optimize(&f, &df, &BoxProjection::uniform(0., 2., 2), &x0);
+        assert!(bounded
+            .x
+            .approximate_eq(&DenseMatrix::row_vector_from_array(&[2., 0.]), 1e-6));
+
+        let unbounded = optimizer.optimize(&f, &df, &Identity {}, &x0);
+        assert!(unbounded
+            .x
+            .approximate_eq(&DenseMatrix::row_vector_from_array(&[3., -1.]), 1e-6));
+    }
+}
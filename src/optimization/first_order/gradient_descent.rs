@@ -1,14 +1,19 @@
-use std::default::Default;
+use alloc::vec::Vec;
+use core::default::Default;
 
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
-use crate::optimization::first_order::{FirstOrderOptimizer, OptimizerResult};
+use crate::optimization::first_order::{FirstOrderOptimizer, OptimizerResult, OptimizerStatus};
 use crate::optimization::line_search::LineSearchMethod;
 use crate::optimization::{DF, F};
 
+/// Steepest descent, steps along the negative gradient with step lengths found by a line search.
 pub struct GradientDescent<T: RealNumber> {
+    /// Maximum number of iterations.
     pub max_iter: usize,
+    /// Stops when the norm of the gradient drops below `g_rtol` times its initial norm.
     pub g_rtol: T,
+    /// Stops when the norm of the gradient drops below `g_atol`.
     pub g_atol: T,
 }
 
@@ -40,6 +45,7 @@ impl<T: RealNumber> FirstOrderOptimizer<T> for GradientDescent<T> {
 
         let mut iter = 0;
         let mut alpha = T::one();
+        let mut f_history = Vec::new();
         df(&mut gvec, &x);
 
         while iter < self.max_iter && (iter == 0 || gnorm > gtol) {
@@ -69,14 +75,23 @@ impl<T: RealNumber> FirstOrderOptimizer<T> for GradientDescent<T> {
             x.add_mut(step.mul_scalar_mut(alpha));
             df(&mut gvec, &x);
             gnorm = gvec.norm2();
+            f_history.push(fx);
         }
 
         let f_x = f(&x);
+        let status = if iter > 0 && gnorm <= gtol {
+            OptimizerStatus::GradientTolerance
+        } else {
+            OptimizerStatus::MaxIterations
+        };
 
         OptimizerResult {
             x,
             f_x,
             iterations: iter,
+            status,
+            g_norm: gnorm,
+            f_history,
         }
     }
 }
@@ -115,5 +130,6 @@ mod tests {
         assert!((result.f_x - 0.0).abs() < 1e-5);
         assert!((result.x.get(0, 0) - 1.0).abs() < 1e-2);
         assert!((result.x.get(0, 1) - 1.0).abs() < 1e-2);
+        assert_eq!(result.iterations, result.f_history.len());
     }
 }
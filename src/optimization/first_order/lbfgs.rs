@@ -1,23 +1,34 @@
 #![allow(clippy::suspicious_operation_groupings)]
-use std::default::Default;
-use std::fmt::Debug;
+use alloc::vec::Vec;
+use core::default::Default;
+use core::fmt::Debug;
 
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
-use crate::optimization::first_order::{FirstOrderOptimizer, OptimizerResult};
+use crate::optimization::first_order::{FirstOrderOptimizer, OptimizerResult, OptimizerStatus};
 use crate::optimization::line_search::LineSearchMethod;
 use crate::optimization::{DF, F};
 
+/// Limited-memory BFGS, approximates the inverse Hessian from the last `m` steps and gradient changes.
 #[allow(clippy::upper_case_acronyms)]
 pub struct LBFGS<T: RealNumber> {
+    /// Maximum number of iterations.
     pub max_iter: usize,
+    /// Relative gradient tolerance, currently not used.
     pub g_rtol: T,
+    /// Stops when the infinity norm of the gradient drops below `g_atol`.
     pub g_atol: T,
+    /// Stops when no element of `x` changes by more than `x_atol`.
     pub x_atol: T,
+    /// Stops when no element of `x` changes by more than `x_rtol` times the infinity norm of `x`.
     pub x_rtol: T,
+    /// Absolute tolerance of the change of the objective.
     pub f_abstol: T,
+    /// Relative tolerance of the change of the objective.
     pub f_reltol: T,
+    /// Stops when the change of the objective has been within the tolerances more than `successive_f_tol` times.
     pub successive_f_tol: usize,
+    /// Number of past steps that make up the inverse Hessian approximation.
     pub m: usize,
 }
 
@@ -138,7 +149,10 @@ impl<T: RealNumber> LBFGS<T> {
         df(&mut state.x_df, &state.x);
     }
 
-    fn assess_convergence<X: Matrix<T>>(&self, state: &mut LBFGSState<T, X>) -> bool {
+    fn assess_convergence<X: Matrix<T>>(
+        &self,
+        state: &mut LBFGSState<T, X>,
+    ) -> Option<OptimizerStatus> {
         let (mut x_converged, mut g_converged) = (false, false);
 
         if state.x.max_diff(&state.x_prev) <= self.x_atol {
@@ -161,13 +175,23 @@ impl<T: RealNumber> LBFGS<T> {
             g_converged = true;
         }
 
-        g_converged || x_converged || state.counter_f_tol > self.successive_f_tol
+        if g_converged {
+            Some(OptimizerStatus::GradientTolerance)
+        } else if x_converged {
+            Some(OptimizerStatus::XTolerance)
+        } else if state.counter_f_tol > self.successive_f_tol {
+            Some(OptimizerStatus::FTolerance)
+        } else {
+            None
+        }
     }
 
     fn update_hessian<'a, X: Matrix<T>>(&self, _: &'a DF<'_, X>, state: &mut LBFGSState<T, X>) {
         state.dg = state.x_df.sub(&state.x_df_prev);
         let rho_iteration = T::one() / state.dx.dot(&state.dg);
-        if !rho_iteration.is_infinite() {
+        // pairs without positive curvature would make the inverse Hessian approximation indefinite,
+        // which happens when the objective is not convex
+        if rho_iteration.is_finite() && rho_iteration > T::zero() {
             let idx = state.iteration.rem_euclid(self.m);
             state.dx_history[idx].copy_from(&state.dx);
             state.dg_history[idx].copy_from(&state.dg);
@@ -204,31 +228,60 @@ impl<T: RealNumber> FirstOrderOptimizer<T> for LBFGS<T> {
         df: &'a DF<'_, X>,
         x0: &X,
         ls: &'a LS,
+    ) -> OptimizerResult<T, X> {
+        self.optimize_with_callback(f, df, x0, ls, &mut |_, _| true)
+    }
+}
+
+impl<T: RealNumber> LBFGS<T> {
+    /// Minimizes `f` like `optimize` and calls `callback` with the number of completed iterations and the objective after every iteration.
+    /// The optimizer stops with status `Interrupted` when the callback returns `false`.
+    pub fn optimize_with_callback<'a, X: Matrix<T>, LS: LineSearchMethod<T>>(
+        &self,
+        f: &F<'_, T, X>,
+        df: &'a DF<'_, X>,
+        x0: &X,
+        ls: &'a LS,
+        callback: &mut dyn FnMut(usize, T) -> bool,
     ) -> OptimizerResult<T, X> {
         let mut state = self.init_state(x0);
 
         df(&mut state.x_df, x0);
 
-        let g_converged = state.x_df.norm(T::infinity()) < self.g_atol;
-        let mut converged = g_converged;
-        let stopped = false;
+        let mut status = if state.x_df.norm(T::infinity()) < self.g_atol {
+            Some(OptimizerStatus::GradientTolerance)
+        } else {
+            None
+        };
+        let mut f_history = Vec::new();
 
-        while !converged && !stopped && state.iteration < self.max_iter {
+        while status.is_none() && state.iteration < self.max_iter {
             self.update_state(f, df, ls, &mut state);
+            f_history.push(state.x_f);
 
-            converged = self.assess_convergence(&mut state);
+            status = self.assess_convergence(&mut state);
 
-            if !converged {
+            if status.is_none() {
                 self.update_hessian(df, &mut state);
             }
 
             state.iteration += 1;
+
+            let proceed = callback(state.iteration, state.x_f);
+            if status.is_none() && !proceed {
+                status = Some(OptimizerStatus::Interrupted);
+            }
         }
 
+        let g_norm = state.x_df.norm(T::infinity());
+
         OptimizerResult {
             x: state.x,
             f_x: state.x_f,
             iterations: state.iteration,
+            status: status.unwrap_or(OptimizerStatus::MaxIterations),
+            g_norm,
+            f_history,
         }
     }
 }
@@ -267,5 +320,27 @@ mod tests {
         assert!((result.x.get(0, 0) - 1.0).abs() < 1e-8);
         assert!((result.x.get(0, 1) - 1.0).abs() < 1e-8);
         assert!(result.iterations <= 24);
+        assert!(result.status.converged());
+        assert!(result.g_norm <= 1e-8);
+        assert_eq!(result.iterations, result.f_history.len());
+        assert_eq!(result.f_x, *result.f_history.last().unwrap());
+
+        let limited = LBFGS {
+            max_iter: 3,
+            ..Default::default()
+        };
+        let result = limited.optimize(&f, &df, &x0, &ls);
+        assert_eq!(OptimizerStatus::MaxIterations, result.status);
+        assert!(!result.status.converged());
+
+        let mut objective = Vec::new();
+        let result = optimizer.optimize_with_callback(&f, &df, &x0, &ls, &mut |iteration, f_x| {
+            objective.push(f_x);
+            iteration < 2
+        });
+        assert_eq!(OptimizerStatus::Interrupted, result.status);
+        assert!(!result.status.converged());
+        assert_eq!(2, result.iterations);
+        assert_eq!(objective, result.f_history);
     }
 }
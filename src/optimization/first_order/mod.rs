@@ -1,15 +1,32 @@
+/// Adam, RMSProp and AdaGrad, steps scaled by statistics of past gradients
+pub mod adaptive;
+/// Broyden–Fletcher–Goldfarb–Shanno method with a dense inverse Hessian approximation
+pub mod bfgs;
+/// Accelerated proximal gradient method for a smooth objective plus a penalty
+pub mod fista;
+/// Gradient descent with a line search
 pub mod gradient_descent;
+/// Limited-memory Broyden–Fletcher–Goldfarb–Shanno method
 pub mod lbfgs;
+/// Gradient descent with momentum and Nesterov acceleration, with learning rate schedules
+pub mod momentum;
+/// Gradient descent with box constraints, keeps every iterate inside the box
+pub mod projected_gradient;
+/// Optimizers that update parameters from gradients over mini-batches of samples
+pub mod stochastic;
 
-use std::clone::Clone;
-use std::fmt::Debug;
+use alloc::vec::Vec;
+use core::clone::Clone;
+use core::fmt::Debug;
 
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
 use crate::optimization::line_search::LineSearchMethod;
 use crate::optimization::{DF, F};
 
+/// Optimizer that minimizes a function using its value and gradient.
 pub trait FirstOrderOptimizer<T: RealNumber> {
+    /// Minimizes `f` with gradient `df` starting from `x0`, `ls` finds the step length along the search direction when the optimizer needs one.
     fn optimize<'a, X: Matrix<T>, LS: LineSearchMethod<T>>(
         &self,
         f: &F<'_, T, X>,
@@ -19,9 +36,88 @@ pub trait FirstOrderOptimizer<T: RealNumber> {
     ) -> OptimizerResult<T, X>;
 }
 
+/// Reason why an optimizer stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptimizerStatus {
+    /// Norm of the gradient dropped below the gradient tolerance.
+    GradientTolerance,
+    /// Change of `x` between iterations dropped below the `x` tolerance.
+    XTolerance,
+    /// Change of the objective between iterations dropped below the function tolerance.
+    FTolerance,
+    /// Iteration limit was reached before any tolerance was met.
+    MaxIterations,
+    /// A callback asked the optimizer to stop before any tolerance was met.
+    Interrupted,
+}
+
+impl OptimizerStatus {
+    /// Whether any of the tolerances was met.
+    pub fn converged(&self) -> bool {
+        !matches!(
+            self,
+            OptimizerStatus::MaxIterations | OptimizerStatus::Interrupted
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
+/// Result of an optimization.
 pub struct OptimizerResult<T: RealNumber, X: Matrix<T>> {
+    /// Minimizer found.
     pub x: X,
+    /// Objective value at `x`.
     pub f_x: T,
+    /// Number of iterations done.
     pub iterations: usize,
+    /// Reason why the optimizer stopped.
+    pub status: OptimizerStatus,
+    /// Norm of the gradient at `x`, the same norm the optimizer compares with its gradient tolerance.
+    pub g_norm: T,
+    /// Objective value after every iteration.
+    pub f_history: Vec<T>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::linalg::BaseMatrix;
+    use crate::optimization::first_order::bfgs::BFGS;
+    use crate::optimization::first_order::gradient_descent::GradientDescent;
+    use crate::optimization::first_order::lbfgs::LBFGS;
+    use crate::optimization::line_search::Backtracking;
+
+    fn assert_non_increasing<O: FirstOrderOptimizer<f64>>(optimizer: &O) {
+        // convex quadratic with minimum 0 at [1, -2]
+        let f = |x: &DenseMatrix<f64>| {
+            let (a, b) = (x.get(0, 0) - 1., x.get(0, 1) + 2.);
+            2. * a * a + a * b + b * b
+        };
+        let df = |g: &mut DenseMatrix<f64>, x: &DenseMatrix<f64>| {
+            let (a, b) = (x.get(0, 0) - 1., x.get(0, 1) + 2.);
+            g.set(0, 0, 4. * a + b);
+            g.set(0, 1, a + 2. * b);
+        };
+        let x0 = DenseMatrix::row_vector_from_array(&[-3., 4.]);
+        let ls: Backtracking<f64> = Default::default();
+
+        let result = optimizer.optimize(&f, &df, &x0, &ls);
+
+        assert!(result.status.converged());
+        assert!(result.f_x < 1e-8);
+        assert_eq!(result.iterations, result.f_history.len());
+        assert!(result.f_history[0] <= f(&x0));
+        for w in result.f_history.windows(2) {
+            assert!(w[1] <= w[0]);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn f_history_non_increasing() {
+        assert_non_increasing(&GradientDescent::default());
+        assert_non_increasing(&BFGS::default());
+        assert_non_increasing(&LBFGS::default());
+    }
 }
@@ -0,0 +1,253 @@
+use alloc::vec::Vec;
+use core::default::Default;
+
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::optimization::first_order::stochastic::{MiniBatches, StochasticOptimizer};
+use crate::optimization::first_order::{FirstOrderOptimizer, OptimizerResult, OptimizerStatus};
+use crate::optimization::line_search::LineSearchMethod;
+use crate::optimization::{BatchDF, BatchF, DF, F};
+
+/// How the learning rate changes with iteration number `t`, starting from 0.
+#[derive(Debug, Clone)]
+pub enum LearningRateSchedule<T: RealNumber> {
+    /// Learning rate stays the same.
+    Constant,
+    /// Learning rate is multiplied by `gamma` every `step_size` iterations.
+    Step {
+        /// Number of iterations between two decays.
+        step_size: usize,
+        /// Factor the learning rate is multiplied by.
+        gamma: T,
+    },
+    /// Learning rate decays as `learning_rate / (t + 1)^power`.
+    InverseScaling {
+        /// Exponent of the decay.
+        power: T,
+    },
+    /// Learning rate follows a half cosine from `learning_rate` down to `min_learning_rate` in `t_max` iterations and stays there.
+    Cosine {
+        /// Number of iterations to reach `min_learning_rate`.
+        t_max: usize,
+        /// Final learning rate.
+        min_learning_rate: T,
+    },
+}
+
+impl<T: RealNumber> LearningRateSchedule<T> {
+    /// Learning rate at iteration `t` for initial learning rate `learning_rate`.
+    pub fn learning_rate(&self, learning_rate: T, t: usize) -> T {
+        match self {
+            LearningRateSchedule::Constant => learning_rate,
+            LearningRateSchedule::Step { step_size, gamma } => {
+                learning_rate * gamma.powi((t / step_size) as i32)
+            }
+            LearningRateSchedule::InverseScaling { power } => {
+                learning_rate / T::from_usize(t + 1).unwrap().powf(*power)
+            }
+            LearningRateSchedule::Cosine {
+                t_max,
+                min_learning_rate,
+            } => {
+                let progress =
+                    T::from_usize(t.min(*t_max)).unwrap() / T::from_usize(*t_max).unwrap();
+                let pi = T::from_f64(core::f64::consts::PI).unwrap();
+                *min_learning_rate
+                    + (learning_rate - *min_learning_rate) * (T::one() + (pi * progress).cos())
+                        / T::two()
+            }
+        }
+    }
+}
+
+/// Gradient descent with momentum, with optional Nesterov acceleration.
+pub struct Momentum<T: RealNumber> {
+    /// Initial step size.
+    pub learning_rate: T,
+    /// Fraction of the previous direction kept in the next one.
+    pub momentum: T,
+    /// Steps along the gradient at the look-ahead point when `true`.
+    pub nesterov: bool,
+    /// How the step size changes with iterations.
+    pub schedule: LearningRateSchedule<T>,
+    /// Maximum number of iterations.
+    pub max_iter: usize,
+    /// Stops when the norm of the gradient drops below `g_atol`.
+    pub g_atol: T,
+}
+
+impl<T: RealNumber> Default for Momentum<T> {
+    fn default() -> Self {
+        Momentum {
+            learning_rate: T::from(1e-2).unwrap(),
+            momentum: T::from(0.9).unwrap(),
+            nesterov: false,
+            schedule: LearningRateSchedule::Constant,
+            max_iter: 10000,
+            g_atol: T::epsilon().sqrt(),
+        }
+    }
+}
+
+impl<T: RealNumber> Momentum<T> {
+    /// Makes a single step from `x` along gradient `g`, `velocity` keeps the running direction between steps and starts with zeros.
+    /// `t` is the number of steps done so far.
+    pub fn update<X: Matrix<T>>(&self, velocity: &mut X, x: &mut X, g: &X, t: usize) {
+        let learning_rate = self.schedule.learning_rate(self.learning_rate, t);
+
+        velocity.mul_scalar_mut(self.momentum);
+        velocity.add_mut(g);
+
+        let mut step = if self.nesterov {
+            let mut step = velocity.clone();
+            step.mul_scalar_mut(self.momentum);
+            step.add_mut(g);
+            step
+        } else {
+            velocity.clone()
+        };
+
+        x.sub_mut(step.mul_scalar_mut(learning_rate));
+    }
+}
+
+impl<T: RealNumber> FirstOrderOptimizer<T> for Momentum<T> {
+    fn optimize<'a, X: Matrix<T>, LS: LineSearchMethod<T>>(
+        &self,
+        f: &F<'_, T, X>,
+        df: &'a DF<'_, X>,
+        x0: &X,
+        _ls: &'a LS,
+    ) -> OptimizerResult<T, X> {
+        let (nrows, ncols) = x0.shape();
+        let mut x = x0.clone();
+        let mut gvec = x0.clone();
+        let mut velocity = X::zeros(nrows, ncols);
+        df(&mut gvec, &x);
+
+        let mut iter = 0;
+        let mut f_history = Vec::new();
+        let mut gnorm = gvec.norm2();
+        while iter < self.max_iter && gnorm > self.g_atol {
+            self.update(&mut velocity, &mut x, &gvec, iter);
+            iter += 1;
+            df(&mut gvec, &x);
+            gnorm = gvec.norm2();
+            f_history.push(f(&x));
+        }
+
+        let f_x = f(&x);
+        let status = if gnorm <= self.g_atol {
+            OptimizerStatus::GradientTolerance
+        } else {
+            OptimizerStatus::MaxIterations
+        };
+
+        OptimizerResult {
+            x,
+            f_x,
+            iterations: iter,
+            status,
+            g_norm: gnorm,
+            f_history,
+        }
+    }
+}
+
+impl<T: RealNumber> StochasticOptimizer<T> for Momentum<T> {
+    fn optimize_batched<X: Matrix<T>>(
+        &self,
+        f: &BatchF<'_, T, X>,
+        df: &BatchDF<'_, X>,
+        x0: &X,
+        n_samples: usize,
+        batches: &MiniBatches,
+    ) -> OptimizerResult<T, X> {
+        let (nrows, ncols) = x0.shape();
+        let mut velocity = X::zeros(nrows, ncols);
+        batches.run(f, df, x0, n_samples, |x, g, t| {
+            self.update(&mut velocity, x, g, t)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::linalg::BaseMatrix;
+    use crate::optimization::line_search::Backtracking;
+
+    fn f(x: &DenseMatrix<f64>) -> f64 {
+        (x.get(0, 0) - 3.).powf(2.) + 10. * (x.get(0, 1) + 1.).powf(2.)
+    }
+
+    fn df(g: &mut DenseMatrix<f64>, x: &DenseMatrix<f64>) {
+        g.set(0, 0, 2. * (x.get(0, 0) - 3.));
+        g.set(0, 1, 20. * (x.get(0, 1) + 1.));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn momentum_and_nesterov() {
+        let x0 = DenseMatrix::row_vector_from_array(&[0., 0.]);
+        let ls: Backtracking<f64> = Default::default();
+
+        let momentum: Momentum<f64> = Default::default();
+        let result = momentum.optimize(&f, &df, &x0, &ls);
+        assert!(result.f_x.abs() < 1e-10);
+        assert!((result.x.get(0, 0) - 3.0).abs() < 1e-5);
+        assert!((result.x.get(0, 1) + 1.0).abs() < 1e-5);
+
+        let nesterov = Momentum {
+            nesterov: true,
+            ..Default::default()
+        };
+        let accelerated = nesterov.optimize(&f, &df, &x0, &ls);
+        assert!(accelerated.f_x.abs() < 1e-10);
+        assert!(accelerated.iterations < result.iterations);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn momentum_with_schedule() {
+        let x0 = DenseMatrix::row_vector_from_array(&[0., 0.]);
+        let ls: Backtracking<f64> = Default::default();
+
+        let optimizer = Momentum {
+            learning_rate: 0.05,
+            schedule: LearningRateSchedule::Cosine {
+                t_max: 500,
+                min_learning_rate: 0.005,
+            },
+            ..Default::default()
+        };
+        let result = optimizer.optimize(&f, &df, &x0, &ls);
+
+        assert!(result.f_x.abs() < 1e-10);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn learning_rate_schedules() {
+        let step = LearningRateSchedule::Step {
+            step_size: 10,
+            gamma: 0.5,
+        };
+        assert_eq!(1., step.learning_rate(1., 9));
+        assert_eq!(0.25, step.learning_rate(1., 25));
+
+        let inverse = LearningRateSchedule::InverseScaling { power: 0.5f64 };
+        assert!((inverse.learning_rate(1., 3) - 0.5).abs() < 1e-8);
+
+        let cosine = LearningRateSchedule::Cosine {
+            t_max: 100,
+            min_learning_rate: 0.1f64,
+        };
+        assert!((cosine.learning_rate(1., 0) - 1.).abs() < 1e-8);
+        assert!((cosine.learning_rate(1., 50) - 0.55).abs() < 1e-8);
+        assert!((cosine.learning_rate(1., 200) - 0.1).abs() < 1e-8);
+
+        assert_eq!(1., LearningRateSchedule::Constant.learning_rate(1., 1000));
+    }
+}
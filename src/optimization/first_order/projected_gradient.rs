@@ -0,0 +1,180 @@
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::optimization::first_order::{FirstOrderOptimizer, OptimizerResult, OptimizerStatus};
+use crate::optimization::line_search::LineSearchMethod;
+use crate::optimization::prox::{BoxProjection, ProxOperator};
+use crate::optimization::{DF, F};
+use alloc::vec::Vec;
+
+/// Gradient descent restricted to box constraints `lower[j] <= x_j <= upper[j]`.
+/// Every iteration searches along the feasible direction `P(x - s * g) - x`, where `P` projects onto the box
+/// and `s` is the Barzilai-Borwein step size, so the line search keeps all iterates inside the box.
+pub struct ProjectedGradient<T: RealNumber> {
+    /// Maximum number of iterations.
+    pub max_iter: usize,
+    /// Stops when no element of the projected gradient `P(x - g) - x` exceeds `g_atol`.
+    pub g_atol: T,
+    /// Box the iterates are kept in.
+    pub bounds: BoxProjection<T>,
+}
+
+impl<T: RealNumber> ProjectedGradient<T> {
+    /// Optimizer for the box `bounds` with default tolerances.
+    pub fn new(bounds: BoxProjection<T>) -> Self {
+        ProjectedGradient {
+            max_iter: 10000,
+            g_atol: T::from(1e-8).unwrap(),
+            bounds,
+        }
+    }
+
+    fn projected_step<X: Matrix<T>>(&self, x: &X, gvec: &X, step_size: T) -> X {
+        let mut step = gvec.mul_scalar(-step_size);
+        step.add_mut(x);
+        self.bounds.prox_mut(&mut step, step_size);
+        step.sub_mut(x);
+        step
+    }
+}
+
+impl<T: RealNumber> FirstOrderOptimizer<T> for ProjectedGradient<T> {
+    fn optimize<'a, X: Matrix<T>, LS: LineSearchMethod<T>>(
+        &self,
+        f: &F<'_, T, X>,
+        df: &'a DF<'_, X>,
+        x0: &X,
+        ls: &'a LS,
+    ) -> OptimizerResult<T, X> {
+        let mut x = x0.clone();
+        self.bounds.prox_mut(&mut x, T::one());
+        let mut fx = f(&x);
+
+        let mut gvec = x0.clone();
+        df(&mut gvec, &x);
+
+        let mut step_size = T::one();
+        let mut iter = 0;
+        let mut f_history = Vec::new();
+        // the projected gradient vanishes at a constrained minimum
+        let mut g_norm = self.projected_step(&x, &gvec, T::one()).norm(T::infinity());
+        while iter < self.max_iter && g_norm > self.g_atol {
+            iter += 1;
+
+            let step = self.projected_step(&x, &gvec, step_size);
+
+            let f_alpha = |alpha: T| -> T {
+                let mut dx = step.clone();
+                dx.mul_scalar_mut(alpha);
+                f(dx.add_mut(&x)) // f(x) = f(x .+ step .* alpha)
+            };
+
+            let df_alpha = |alpha: T| -> T {
+                let mut dx = step.clone();
+                let mut dg = gvec.clone();
+                dx.mul_scalar_mut(alpha);
+                df(&mut dg, dx.add_mut(&x)); //df(x) = df(x .+ step .* alpha)
+                step.dot(&dg)
+            };
+
+            let df0 = step.dot(&gvec);
+
+            // points beyond alpha = 1 may leave the box
+            let ls_r = ls.search(&f_alpha, &df_alpha, T::one(), fx, df0);
+            let alpha = ls_r.alpha.min(T::one());
+
+            let dx = step.mul_scalar(alpha);
+            x.add_mut(&dx);
+            fx = f(&x);
+            f_history.push(fx);
+
+            let gprev = gvec.clone();
+            df(&mut gvec, &x);
+            let dg = gvec.sub(&gprev);
+
+            let curvature = dx.dot(&dg);
+            step_size = if curvature > T::zero() && curvature.is_finite() {
+                dx.dot(&dx) / curvature
+            } else {
+                T::one()
+            };
+
+            g_norm = self.projected_step(&x, &gvec, T::one()).norm(T::infinity());
+        }
+
+        let status = if g_norm <= self.g_atol {
+            OptimizerStatus::GradientTolerance
+        } else {
+            OptimizerStatus::MaxIterations
+        };
+
+        OptimizerResult {
+            x,
+            f_x: fx,
+            iterations: iter,
+            status,
+            g_norm,
+            f_history,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::optimization::line_search::Backtracking;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn projected_gradient_rosenbrock() {
+        let x0 = DenseMatrix::row_vector_from_array(&[-1., 1.]);
+        let f = |x: &DenseMatrix<f64>| {
+            (1.0 - x.get(0, 0)).powf(2.) + 100.0 * (x.get(0, 1) - x.get(0, 0).powf(2.)).powf(2.)
+        };
+
+        let df = |g: &mut DenseMatrix<f64>, x: &DenseMatrix<f64>| {
+            g.set(
+                0,
+                0,
+                -2. * (1. - x.get(0, 0))
+                    - 400. * (x.get(0, 1) - x.get(0, 0).powf(2.)) * x.get(0, 0),
+            );
+            g.set(0, 1, 200. * (x.get(0, 1) - x.get(0, 0).powf(2.)));
+        };
+        let ls: Backtracking<f64> = Default::default();
+        let optimizer = ProjectedGradient::new(BoxProjection::new(
+            vec![f64::NEG_INFINITY, f64::NEG_INFINITY],
+            vec![0.5, f64::INFINITY],
+        ));
+
+        let result = optimizer.optimize(&f, &df, &x0, &ls);
+
+        assert!((result.f_x - 0.25).abs() < 1e-6);
+        assert!((result.x.get(0, 0) - 0.5).abs() < 1e-6);
+        assert!((result.x.get(0, 1) - 0.25).abs() < 1e-4);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn non_negative_least_squares() {
+        // unconstrained solution is [13/6, -3/2], with x >= 0 it is [2/3, 0]
+        let a = DenseMatrix::from_2d_array(&[&[1., 0.], &[1., 1.], &[1., 2.]]);
+        let b = DenseMatrix::row_vector_from_array(&[2., 1., -1.]);
+        let f = |x: &DenseMatrix<f64>| {
+            let r = x.matmul(&a.transpose()).sub(&b);
+            r.dot(&r) / 2.
+        };
+        let df = |g: &mut DenseMatrix<f64>, x: &DenseMatrix<f64>| {
+            let r = x.matmul(&a.transpose()).sub(&b);
+            g.copy_from(&r.matmul(&a));
+        };
+        let ls: Backtracking<f64> = Default::default();
+        let optimizer = ProjectedGradient::new(BoxProjection::non_negative(2));
+
+        let result = optimizer.optimize(&f, &df, &DenseMatrix::zeros(1, 2), &ls);
+
+        assert!(result
+            .x
+            .approximate_eq(&DenseMatrix::row_vector_from_array(&[2. / 3., 0.]), 1e-6));
+    }
+}
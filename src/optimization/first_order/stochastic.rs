@@ -0,0 +1,162 @@
+use alloc::vec::Vec;
+use rand::seq::SliceRandom;
+
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::optimization::first_order::{OptimizerResult, OptimizerStatus};
+use crate::optimization::{BatchDF, BatchF};
+use crate::rand::get_rng_impl;
+
+/// Optimizer that updates parameters from gradients over mini-batches of samples.
+pub trait StochasticOptimizer<T: RealNumber> {
+    /// Minimizes `f` over `n_samples` samples. `f` and `df` receive indices of the samples in the current batch.
+    fn optimize_batched<X: Matrix<T>>(
+        &self,
+        f: &BatchF<'_, T, X>,
+        df: &BatchDF<'_, X>,
+        x0: &X,
+        n_samples: usize,
+        batches: &MiniBatches,
+    ) -> OptimizerResult<T, X>;
+}
+
+/// How samples are split into mini-batches.
+#[derive(Debug, Clone)]
+pub struct MiniBatches {
+    /// Number of samples in a mini-batch, the last one of an epoch may be smaller.
+    pub batch_size: usize,
+    /// Number of passes over all samples.
+    pub epochs: usize,
+    /// Shuffle samples before every epoch.
+    pub shuffle: bool,
+    /// Seed of the random generator that shuffles samples.
+    pub seed: Option<u64>,
+}
+
+impl Default for MiniBatches {
+    fn default() -> Self {
+        MiniBatches {
+            batch_size: 32,
+            epochs: 100,
+            shuffle: true,
+            seed: None,
+        }
+    }
+}
+
+impl MiniBatches {
+    /// Runs `update(x, g, t)` with the gradient `g` of every mini-batch, `t` counts updates done so far.
+    /// The history holds the objective over the batch after every update, the status is always `MaxIterations`.
+    pub(crate) fn run<T: RealNumber, X: Matrix<T>>(
+        &self,
+        f: &BatchF<'_, T, X>,
+        df: &BatchDF<'_, X>,
+        x0: &X,
+        n_samples: usize,
+        mut update: impl FnMut(&mut X, &X, usize),
+    ) -> OptimizerResult<T, X> {
+        if self.batch_size == 0 {
+            panic!("batch_size must be positive");
+        }
+
+        let mut rng = get_rng_impl(self.seed);
+        let mut indices: Vec<usize> = (0..n_samples).collect();
+        let mut x = x0.clone();
+        let mut gvec = x0.clone();
+
+        let mut iter = 0;
+        let mut f_history = Vec::new();
+        for _ in 0..self.epochs {
+            if self.shuffle {
+                indices.shuffle(&mut rng);
+            }
+            for batch in indices.chunks(self.batch_size) {
+                df(&mut gvec, &x, batch);
+                update(&mut x, &gvec, iter);
+                iter += 1;
+                f_history.push(f(&x, batch));
+            }
+        }
+
+        indices.sort_unstable();
+        let f_x = f(&x, &indices);
+        df(&mut gvec, &x, &indices);
+
+        OptimizerResult {
+            x,
+            f_x,
+            iterations: iter,
+            status: OptimizerStatus::MaxIterations,
+            g_norm: gvec.norm2(),
+            f_history,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::optimization::first_order::adaptive::{AdaGrad, Adam, RMSProp};
+    use crate::optimization::first_order::momentum::Momentum;
+
+    fn fit<O: StochasticOptimizer<f64>>(optimizer: &O) -> OptimizerResult<f64, DenseMatrix<f64>> {
+        // least squares fit of y = 2x - 1, the objective and its gradient average over a batch
+        let x: Vec<f64> = (0..20).map(|i| i as f64 / 4.).collect();
+        let y: Vec<f64> = x.iter().map(|x| 2. * x - 1.).collect();
+
+        let f = |w: &DenseMatrix<f64>, batch: &[usize]| {
+            batch
+                .iter()
+                .map(|&i| (w.get(0, 0) * x[i] + w.get(0, 1) - y[i]).powi(2))
+                .sum::<f64>()
+                / (2. * batch.len() as f64)
+        };
+        let df = |g: &mut DenseMatrix<f64>, w: &DenseMatrix<f64>, batch: &[usize]| {
+            g.copy_from(&DenseMatrix::zeros(1, 2));
+            for &i in batch {
+                let residual = (w.get(0, 0) * x[i] + w.get(0, 1) - y[i]) / batch.len() as f64;
+                g.add_element_mut(0, 0, residual * x[i]);
+                g.add_element_mut(0, 1, residual);
+            }
+        };
+        let batches = MiniBatches {
+            batch_size: 4,
+            epochs: 500,
+            seed: Some(1),
+            ..Default::default()
+        };
+
+        optimizer.optimize_batched(&f, &df, &DenseMatrix::zeros(1, 2), 20, &batches)
+    }
+
+    fn assert_fit(result: OptimizerResult<f64, DenseMatrix<f64>>) {
+        assert_eq!(2500, result.iterations);
+        assert_eq!(2500, result.f_history.len());
+        assert!(result.g_norm < 1e-2);
+        assert!(result.f_x < 1e-4);
+        assert!((result.x.get(0, 0) - 2.).abs() < 1e-2);
+        assert!((result.x.get(0, 1) + 1.).abs() < 1e-2);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn stochastic_optimizers() {
+        assert_fit(fit(&Adam {
+            learning_rate: 0.05,
+            ..Default::default()
+        }));
+        assert_fit(fit(&RMSProp {
+            learning_rate: 0.01,
+            ..Default::default()
+        }));
+        assert_fit(fit(&AdaGrad {
+            learning_rate: 0.5,
+            ..Default::default()
+        }));
+        assert_fit(fit(&Momentum {
+            nesterov: true,
+            ..Default::default()
+        }));
+    }
+}
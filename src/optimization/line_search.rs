@@ -1,7 +1,10 @@
 use crate::optimization::FunctionOrder;
 use num_traits::Float;
 
+/// Method that finds a step length along a descent direction.
 pub trait LineSearchMethod<T: Float> {
+    /// Finds a step length, where `f` and `df` are the objective and its directional derivative as functions of the step length,
+    /// `alpha` is the initial step length and `f0`, `df0` are the values of `f` and `df` at step length 0.
     fn search(
         &self,
         f: &(dyn Fn(T) -> T),
@@ -13,17 +16,27 @@ pub trait LineSearchMethod<T: Float> {
 }
 
 #[derive(Debug, Clone)]
+/// Step length found by a line search.
 pub struct LineSearchResult<T: Float> {
+    /// Step length.
     pub alpha: T,
+    /// Objective value after the step.
     pub f_x: T,
 }
 
+/// Backtracking line search, shrinks the step length until the sufficient decrease (Armijo) condition holds.
 pub struct Backtracking<T: Float> {
+    /// Constant of the sufficient decrease condition.
     pub c1: T,
+    /// Maximum number of shrinking steps, the search panics when it is exceeded.
     pub max_iterations: usize,
+    /// Maximum number of halvings of the step length while the objective is not finite.
     pub max_infinity_iterations: usize,
+    /// Upper bound of the factor the step length shrinks by in one iteration.
     pub phi: T,
+    /// Lower bound of the factor the step length shrinks by in one iteration.
     pub plo: T,
+    /// Order of the interpolating polynomial that proposes the next step length.
     pub order: FunctionOrder,
 }
 
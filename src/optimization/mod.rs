@@ -1,12 +1,56 @@
+//! # Optimization
+//!
+//! Numerical optimizers SmartCore uses to fit its models. They minimize an objective \\(f(x)\\) given as a closure `f` and its gradient as a closure `df`
+//! that writes the gradient at `x` into its first argument. Parameters \\(x\\) are a matrix, usually a row vector.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::linalg::BaseMatrix;
+//! use smartcore::optimization::first_order::lbfgs::LBFGS;
+//! use smartcore::optimization::first_order::FirstOrderOptimizer;
+//! use smartcore::optimization::line_search::Backtracking;
+//!
+//! // minimum of (x0 - 1)^2 + (x1 + 2)^2
+//! let f = |x: &DenseMatrix<f64>| (x.get(0, 0) - 1.).powi(2) + (x.get(0, 1) + 2.).powi(2);
+//! let df = |g: &mut DenseMatrix<f64>, x: &DenseMatrix<f64>| {
+//!     g.set(0, 0, 2. * (x.get(0, 0) - 1.));
+//!     g.set(0, 1, 2. * (x.get(0, 1) + 2.));
+//! };
+//!
+//! let x0 = DenseMatrix::row_vector_from_array(&[0., 0.]);
+//! let ls: Backtracking<f64> = Default::default();
+//! let result = LBFGS::default().optimize(&f, &df, &x0, &ls);
+//!
+//! assert!((result.x.get(0, 0) - 1.).abs() < 1e-6);
+//! assert!((result.x.get(0, 1) + 2.).abs() < 1e-6);
+//! ```
+
+/// Proximal coordinate descent, minimizes one coordinate at a time
+pub mod coordinate_descent;
+/// Optimizers that use the objective and its gradient
 pub mod first_order;
+/// Search for a step length along a descent direction
 pub mod line_search;
+/// Proximal operators of penalties added to a smooth objective
+pub mod prox;
 
+/// Objective function, returns the value at `x`.
 pub type F<'a, T, X> = dyn for<'b> Fn(&'b X) -> T + 'a;
+/// Gradient of the objective, writes the gradient at `x` (second argument) into the first argument.
 pub type DF<'a, X> = dyn for<'b> Fn(&'b mut X, &'b X) + 'a;
+/// Objective over a mini-batch, returns the value at `x` over the samples with the given indices.
+pub type BatchF<'a, T, X> = dyn for<'b> Fn(&'b X, &'b [usize]) -> T + 'a;
+/// Gradient of the objective over a mini-batch, writes the gradient at `x` over the samples with the given indices into the first argument.
+pub type BatchDF<'a, X> = dyn for<'b> Fn(&'b mut X, &'b X, &'b [usize]) + 'a;
 
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, PartialEq, Eq)]
+/// Order of the polynomial that interpolates the objective along the search direction in the line search.
 pub enum FunctionOrder {
+    /// Quadratic interpolation.
     SECOND,
+    /// Cubic interpolation.
     THIRD,
 }
@@ -0,0 +1,177 @@
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use alloc::vec::Vec;
+
+/// Proximal operator of a separable penalty \\(\sum_j g_j(x_j)\\), applied one coordinate at a time.
+pub trait ProxOperator<T: RealNumber> {
+    /// Returns \\(\arg\min_z g_j(z) + \frac{1}{2 step}(z - v)^2\\) for coordinate `j`.
+    fn prox(&self, v: T, step: T, j: usize) -> T;
+
+    /// Value of the penalty \\(g_j(v)\\).
+    fn penalty(&self, v: T, j: usize) -> T;
+
+    /// Applies the operator to every element of `x`, elements are numbered in row-major order.
+    fn prox_mut<X: Matrix<T>>(&self, x: &mut X, step: T) {
+        let (nrows, ncols) = x.shape();
+        for r in 0..nrows {
+            for c in 0..ncols {
+                x.set(r, c, self.prox(x.get(r, c), step, r * ncols + c));
+            }
+        }
+    }
+
+    /// Total penalty of all elements of `x`.
+    fn penalty_sum<X: Matrix<T>>(&self, x: &X) -> T {
+        let (nrows, ncols) = x.shape();
+        let mut sum = T::zero();
+        for r in 0..nrows {
+            for c in 0..ncols {
+                sum += self.penalty(x.get(r, c), r * ncols + c);
+            }
+        }
+        sum
+    }
+}
+
+/// No penalty, the operator returns its argument.
+#[derive(Debug, Clone, Default)]
+pub struct Identity {}
+
+impl<T: RealNumber> ProxOperator<T> for Identity {
+    fn prox(&self, v: T, _step: T, _j: usize) -> T {
+        v
+    }
+
+    fn penalty(&self, _v: T, _j: usize) -> T {
+        T::zero()
+    }
+}
+
+/// Soft-thresholding, the proximal operator of L1 penalty `lambda * |x|`.
+#[derive(Debug, Clone)]
+pub struct SoftThreshold<T: RealNumber> {
+    /// Strength of the penalty.
+    pub lambda: T,
+}
+
+impl<T: RealNumber> ProxOperator<T> for SoftThreshold<T> {
+    fn prox(&self, v: T, step: T, _j: usize) -> T {
+        soft_threshold(v, step * self.lambda)
+    }
+
+    fn penalty(&self, v: T, _j: usize) -> T {
+        self.lambda * v.abs()
+    }
+}
+
+/// Proximal operator of elastic net penalty `l1 * |x| + l2 / 2 * x^2`.
+#[derive(Debug, Clone)]
+pub struct ElasticNetPenalty<T: RealNumber> {
+    /// Strength of the L1 penalty.
+    pub l1: T,
+    /// Strength of the L2 penalty.
+    pub l2: T,
+}
+
+impl<T: RealNumber> ProxOperator<T> for ElasticNetPenalty<T> {
+    fn prox(&self, v: T, step: T, _j: usize) -> T {
+        soft_threshold(v, step * self.l1) / (T::one() + step * self.l2)
+    }
+
+    fn penalty(&self, v: T, _j: usize) -> T {
+        self.l1 * v.abs() + self.l2 * v * v / T::two()
+    }
+}
+
+/// Projection onto box constraints `lower[j] <= x_j <= upper[j]`, the proximal operator of their indicator function.
+/// Use infinite bounds for unconstrained sides.
+#[derive(Debug, Clone)]
+pub struct BoxProjection<T: RealNumber> {
+    /// Lower bound of every coordinate.
+    pub lower: Vec<T>,
+    /// Upper bound of every coordinate.
+    pub upper: Vec<T>,
+}
+
+impl<T: RealNumber> BoxProjection<T> {
+    /// Box with per-coordinate bounds.
+    pub fn new(lower: Vec<T>, upper: Vec<T>) -> Self {
+        if lower.len() != upper.len() {
+            panic!(
+                "The vector sizes don't match: {} != {}",
+                lower.len(),
+                upper.len()
+            );
+        }
+        BoxProjection { lower, upper }
+    }
+
+    /// The same bounds for all `n` coordinates.
+    pub fn uniform(lower: T, upper: T, n: usize) -> Self {
+        BoxProjection::new(vec![lower; n], vec![upper; n])
+    }
+
+    /// Constrains all `n` coordinates to be non-negative.
+    pub fn non_negative(n: usize) -> Self {
+        BoxProjection::uniform(T::zero(), T::infinity(), n)
+    }
+}
+
+impl<T: RealNumber> ProxOperator<T> for BoxProjection<T> {
+    fn prox(&self, v: T, _step: T, j: usize) -> T {
+        v.max(self.lower[j]).min(self.upper[j])
+    }
+
+    fn penalty(&self, v: T, j: usize) -> T {
+        if v < self.lower[j] || v > self.upper[j] {
+            T::infinity()
+        } else {
+            T::zero()
+        }
+    }
+}
+
+fn soft_threshold<T: RealNumber>(v: T, threshold: T) -> T {
+    if v > threshold {
+        v - threshold
+    } else if v < -threshold {
+        v + threshold
+    } else {
+        T::zero()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn prox_operators() {
+        assert_eq!(-2., Identity {}.prox(-2., 0.5, 0));
+
+        let l1 = SoftThreshold { lambda: 2. };
+        assert_eq!(2., l1.prox(3., 0.5, 0));
+        assert_eq!(-1., l1.prox(-3., 1., 0));
+        assert_eq!(0., l1.prox(0.9, 0.5, 0));
+
+        let elastic_net = ElasticNetPenalty { l1: 2., l2: 2. };
+        assert_eq!(1., elastic_net.prox(3., 0.5, 0));
+        assert_eq!(8., elastic_net.penalty(-2., 0));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn box_projection() {
+        let bounds = BoxProjection::new(vec![0., -1.], vec![1., f64::INFINITY]);
+        let mut x = DenseMatrix::row_vector_from_array(&[2., -3.]);
+
+        assert_eq!(f64::INFINITY, bounds.penalty_sum(&x));
+        bounds.prox_mut(&mut x, 1.);
+        assert_eq!(DenseMatrix::row_vector_from_array(&[1., -1.]), x);
+        assert_eq!(0., bounds.penalty_sum(&x));
+
+        assert_eq!(0., BoxProjection::non_negative(1).prox(-0.5, 1., 0));
+    }
+}
@@ -0,0 +1,127 @@
+//! # Parallel execution
+//!
+//! With the `parallel` feature enabled SmartCore spreads independent pieces of work, like predictions for different samples,
+//! fitting of trees in a forest or cross-validation folds, over the [rayon](https://docs.rs/rayon) thread pool.
+//! Without the feature the same code runs sequentially and gives the same results.
+//!
+//! Parallel code shares matrices, kernels and distance functions between threads, so these have to be `Send + Sync` when the feature is enabled.
+//! This requirement is expressed by [`MaybeSync`](trait.MaybeSync.html), a supertrait of [`RealNumber`](../math/num/trait.RealNumber.html),
+//! [`BaseVector`](../linalg/trait.BaseVector.html), [`BaseMatrix`](../linalg/trait.BaseMatrix.html), [`Distance`](../math/distance/trait.Distance.html)
+//! and [`Kernel`](../svm/trait.Kernel.html) that every type implements when the feature is disabled.
+//!
+//! Size of the thread pool can be changed with the `RAYON_NUM_THREADS` environment variable or with
+//! [`rayon::ThreadPoolBuilder`](https://docs.rs/rayon/latest/rayon/struct.ThreadPoolBuilder.html).
+//!
+//! WebAssembly modules can only spawn threads when built with the `atomics` target feature, otherwise work runs sequentially
+//! even with the `parallel` feature enabled.
+use alloc::vec::Vec;
+#[cfg(all(
+    feature = "parallel",
+    any(not(target_arch = "wasm32"), target_feature = "atomics")
+))]
+use rayon::prelude::*;
+
+/// Types that can be shared between threads, `Send + Sync` when the `parallel` feature is enabled.
+#[cfg(feature = "parallel")]
+pub trait MaybeSync: Send + Sync {}
+
+#[cfg(feature = "parallel")]
+impl<T: Send + Sync> MaybeSync for T {}
+
+/// Types that can be shared between threads, implemented for every type when the `parallel` feature is disabled.
+#[cfg(not(feature = "parallel"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "parallel"))]
+impl<T> MaybeSync for T {}
+
+/// Applies `f` to every index in `0..n`, in parallel when the `parallel` feature is enabled.
+/// Results are returned in the order of indices.
+pub(crate) fn map_indices<R, F>(n: usize, f: F) -> Vec<R>
+where
+    R: MaybeSync,
+    F: Fn(usize) -> R + MaybeSync,
+{
+    #[cfg(all(
+        feature = "parallel",
+        any(not(target_arch = "wasm32"), target_feature = "atomics")
+    ))]
+    {
+        (0..n).into_par_iter().map(f).collect()
+    }
+    #[cfg(not(all(
+        feature = "parallel",
+        any(not(target_arch = "wasm32"), target_feature = "atomics")
+    )))]
+    {
+        (0..n).map(f).collect()
+    }
+}
+
+/// Applies `f` to every index in `0..n` like [`map_indices`], passing it a scratch value created by `init`.
+/// The value is created once per thread and reused for the indices that thread processes, e.g. as a row buffer.
+pub(crate) fn map_indices_with<S, R, I, F>(n: usize, init: I, f: F) -> Vec<R>
+where
+    R: MaybeSync,
+    I: Fn() -> S + MaybeSync,
+    F: Fn(&mut S, usize) -> R + MaybeSync,
+{
+    #[cfg(all(
+        feature = "parallel",
+        any(not(target_arch = "wasm32"), target_feature = "atomics")
+    ))]
+    {
+        (0..n).into_par_iter().map_init(init, f).collect()
+    }
+    #[cfg(not(all(
+        feature = "parallel",
+        any(not(target_arch = "wasm32"), target_feature = "atomics")
+    )))]
+    {
+        let mut scratch = init();
+        (0..n).map(|i| f(&mut scratch, i)).collect()
+    }
+}
+
+/// Applies `f` to every element of `items`, in parallel when the `parallel` feature is enabled.
+/// Results are returned in the order of elements.
+pub(crate) fn map_slice<I, R, F>(items: &[I], f: F) -> Vec<R>
+where
+    I: MaybeSync,
+    R: MaybeSync,
+    F: Fn(&I) -> R + MaybeSync,
+{
+    #[cfg(all(
+        feature = "parallel",
+        any(not(target_arch = "wasm32"), target_feature = "atomics")
+    ))]
+    {
+        items.par_iter().map(f).collect()
+    }
+    #[cfg(not(all(
+        feature = "parallel",
+        any(not(target_arch = "wasm32"), target_feature = "atomics")
+    )))]
+    {
+        items.iter().map(f).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn map_keeps_order() {
+        assert_eq!(vec![0, 1, 4, 9, 16], map_indices(5, |i| i * i));
+        assert_eq!(vec![2., 4., 6.], map_slice(&[1., 2., 3.], |x: &f64| 2. * x));
+        assert_eq!(
+            vec![0, 2, 4],
+            map_indices_with(3, Vec::new, |buffer: &mut Vec<usize>, i| {
+                buffer.push(i);
+                2 * i
+            })
+        );
+    }
+}
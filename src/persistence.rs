@@ -0,0 +1,317 @@
+//! # Model persistence
+//!
+//! Saves fitted models to files and loads them back. Every model that can be serialized with the `serde` feature can be stored,
+//! either as JSON, which is human readable, or in the compact binary [bincode](https://docs.rs/bincode) format.
+//!
+//! Every file starts with a one-line header that records the version of the file layout, the format of the payload
+//! and the version of SmartCore that wrote it, e.g. `SMARTCORE 1 json 0.2.1`. [`load`](fn.load.html) picks the format from the header
+//! and rejects files that were not written by these helpers or use a layout this version can't read.
+//! Use [`read_header`](fn.read_header.html) to inspect a file without loading the model.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::linear::linear_regression::*;
+//! use smartcore::persistence::{self, Format};
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[1., 1.],
+//!     &[1., 2.],
+//!     &[2., 2.],
+//!     &[2., 3.],
+//! ]);
+//! let y = vec![6., 8., 9., 11.];
+//!
+//! let lr = LinearRegression::fit(&x, &y, Default::default()).unwrap();
+//!
+//! let path = std::env::temp_dir().join("smartcore_linear_regression.model");
+//! persistence::save(&lr, &path, Format::Bincode).unwrap();
+//!
+//! let restored: LinearRegression<f64, DenseMatrix<f64>> = persistence::load(&path).unwrap();
+//! assert_eq!(lr, restored);
+//! # std::fs::remove_file(&path).unwrap();
+//! ```
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Magic word at the start of every model file.
+const MAGIC: &str = "SMARTCORE";
+/// Version of the file layout, incremented whenever files written by older versions can't be read anymore.
+pub const FORMAT_VERSION: u32 = 1;
+/// Headers longer than this are rejected without reading the rest of the file.
+const MAX_HEADER_LENGTH: u64 = 256;
+
+/// Serialization format of a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Compact binary encoding with [bincode](https://docs.rs/bincode).
+    Bincode,
+    /// Human readable JSON.
+    Json,
+}
+
+impl Format {
+    fn name(&self) -> &'static str {
+        match self {
+            Format::Bincode => "bincode",
+            Format::Json => "json",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "bincode" => Some(Format::Bincode),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Header of a model file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    /// Version of the file layout.
+    pub format_version: u32,
+    /// Serialization format of the model.
+    pub format: Format,
+    /// Version of SmartCore that saved the model.
+    pub smartcore_version: String,
+}
+
+/// Error wrapping all failures that can happen while saving or loading a model.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PersistenceError {
+    /// The file could not be read from or written to the file-system.
+    Io {
+        /// More details about the specific file-system error.
+        msg: String,
+    },
+    /// The file does not start with a valid header, it was not written by [`save`](fn.save.html).
+    InvalidHeader {
+        /// More details about what is wrong with the header.
+        msg: String,
+    },
+    /// The file was written with a layout this version of SmartCore can't read.
+    IncompatibleVersion {
+        /// Layout version found in the file.
+        found: u32,
+        /// Layout version supported by this version of SmartCore.
+        supported: u32,
+    },
+    /// The model could not be serialized or deserialized, e.g. the file holds a different type of model.
+    Serialization {
+        /// More details about the serialization error.
+        msg: String,
+    },
+}
+
+impl fmt::Display for PersistenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersistenceError::Io { msg } => write!(f, "I/O error: {}", msg),
+            PersistenceError::InvalidHeader { msg } => write!(f, "Invalid header: {}", msg),
+            PersistenceError::IncompatibleVersion { found, supported } => write!(
+                f,
+                "Incompatible model file version {}, supported version is {}",
+                found, supported
+            ),
+            PersistenceError::Serialization { msg } => write!(f, "Serialization error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for PersistenceError {}
+
+impl From<std::io::Error> for PersistenceError {
+    fn from(io_error: std::io::Error) -> Self {
+        PersistenceError::Io {
+            msg: io_error.to_string(),
+        }
+    }
+}
+
+/// Saves a model to a file, overwriting the file if it exists.
+/// * `model` - a fitted model
+/// * `path` - location of the file
+/// * `format` - serialization format
+pub fn save<M: Serialize, P: AsRef<Path>>(
+    model: &M,
+    path: P,
+    format: Format,
+) -> Result<(), PersistenceError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    to_writer(model, &mut writer, format)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Loads a model from a file written by [`save`](fn.save.html).
+/// * `path` - location of the file
+pub fn load<M: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<M, PersistenceError> {
+    from_reader(BufReader::new(File::open(path)?))
+}
+
+/// Reads the header of a model file without loading the model.
+/// * `path` - location of the file
+pub fn read_header<P: AsRef<Path>>(path: P) -> Result<Header, PersistenceError> {
+    parse_header(&mut BufReader::new(File::open(path)?))
+}
+
+/// Writes a header followed by the serialized model to `writer`.
+/// * `model` - a fitted model
+/// * `writer` - destination of the model
+/// * `format` - serialization format
+pub fn to_writer<M: Serialize, W: Write>(
+    model: &M,
+    mut writer: W,
+    format: Format,
+) -> Result<(), PersistenceError> {
+    writeln!(
+        writer,
+        "{} {} {} {}",
+        MAGIC,
+        FORMAT_VERSION,
+        format.name(),
+        env!("CARGO_PKG_VERSION")
+    )?;
+    match format {
+        Format::Bincode => bincode::serialize_into(writer, model).map_err(serialization_error),
+        Format::Json => serde_json::to_writer(writer, model).map_err(serialization_error),
+    }
+}
+
+/// Reads a model written by [`to_writer`](fn.to_writer.html) from `reader`.
+/// * `reader` - source of the model
+pub fn from_reader<M: DeserializeOwned, R: BufRead>(mut reader: R) -> Result<M, PersistenceError> {
+    let header = parse_header(&mut reader)?;
+    match header.format {
+        Format::Bincode => bincode::deserialize_from(reader).map_err(serialization_error),
+        Format::Json => serde_json::from_reader(reader).map_err(serialization_error),
+    }
+}
+
+fn parse_header<R: BufRead>(reader: &mut R) -> Result<Header, PersistenceError> {
+    let invalid = |msg: &str| PersistenceError::InvalidHeader {
+        msg: msg.to_string(),
+    };
+
+    let mut line = Vec::new();
+    reader
+        .take(MAX_HEADER_LENGTH)
+        .read_until(b'\n', &mut line)?;
+    if line.pop() != Some(b'\n') {
+        return Err(invalid("the file does not start with a SmartCore header"));
+    }
+    let line = String::from_utf8(line).map_err(|_| invalid("the header is not valid UTF-8"))?;
+
+    let fields: Vec<&str> = line.split(' ').collect();
+    if fields.len() != 4 || fields[0] != MAGIC {
+        return Err(invalid("the file does not start with a SmartCore header"));
+    }
+    let format_version: u32 = fields[1]
+        .parse()
+        .map_err(|_| invalid(&format!("unknown format version {}", fields[1])))?;
+    if format_version != FORMAT_VERSION {
+        return Err(PersistenceError::IncompatibleVersion {
+            found: format_version,
+            supported: FORMAT_VERSION,
+        });
+    }
+    let format = Format::from_name(fields[2])
+        .ok_or_else(|| invalid(&format!("unknown serialization format {}", fields[2])))?;
+
+    Ok(Header {
+        format_version,
+        format,
+        smartcore_version: fields[3].to_string(),
+    })
+}
+
+fn serialization_error<E: fmt::Display>(error: E) -> PersistenceError {
+    PersistenceError::Serialization {
+        msg: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::tree::decision_tree_classifier::DecisionTreeClassifier;
+
+    fn fit_tree() -> DecisionTreeClassifier<f64> {
+        let x = DenseMatrix::from_2d_array(&[&[1., 2.], &[3., 4.], &[5., 6.], &[7., 8.]]);
+        let y = vec![0., 0., 1., 1.];
+        DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap()
+    }
+
+    #[test]
+    fn save_and_load() {
+        let tree = fit_tree();
+        for format in [Format::Bincode, Format::Json] {
+            let path = std::env::temp_dir().join(format!(
+                "smartcore_persistence_{}_{}.model",
+                std::process::id(),
+                format.name()
+            ));
+            save(&tree, &path, format).unwrap();
+
+            let header = read_header(&path).unwrap();
+            assert_eq!(FORMAT_VERSION, header.format_version);
+            assert_eq!(format, header.format);
+            assert_eq!(env!("CARGO_PKG_VERSION"), header.smartcore_version);
+
+            let restored: DecisionTreeClassifier<f64> = load(&path).unwrap();
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(tree, restored);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn writer_and_reader() {
+        let tree = fit_tree();
+        let mut bytes = Vec::new();
+        to_writer(&tree, &mut bytes, Format::Json).unwrap();
+
+        assert!(bytes.starts_with(b"SMARTCORE 1 json "));
+        let restored: DecisionTreeClassifier<f64> = from_reader(bytes.as_slice()).unwrap();
+        assert_eq!(tree, restored);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn incompatible_files() {
+        let tree = fit_tree();
+
+        let mut json = Vec::new();
+        serde_json::to_writer(&mut json, &tree).unwrap();
+        assert!(matches!(
+            from_reader::<DecisionTreeClassifier<f64>, _>(json.as_slice()),
+            Err(PersistenceError::InvalidHeader { .. })
+        ));
+
+        let mut newer = b"SMARTCORE 2 json 9.0.0\n".to_vec();
+        newer.extend(json);
+        assert_eq!(
+            Err(PersistenceError::IncompatibleVersion {
+                found: 2,
+                supported: FORMAT_VERSION
+            }),
+            from_reader::<DecisionTreeClassifier<f64>, _>(newer.as_slice())
+        );
+
+        let mut bytes = Vec::new();
+        to_writer(&tree, &mut bytes, Format::Json).unwrap();
+        assert!(matches!(
+            from_reader::<Vec<String>, _>(bytes.as_slice()),
+            Err(PersistenceError::Serialization { .. })
+        ));
+    }
+}
@@ -0,0 +1,341 @@
+//! # Pipeline
+//!
+//! Most estimators expect their inputs to be preprocessed, for example scaled to comparable ranges or with categorical variables encoded as numbers.
+//! The preprocessing has to be learned from the training data only and then applied unchanged to every matrix the estimator is asked about.
+//! A `Pipeline` chains a sequence of [transformers](../api/trait.Transformer.html) with a final estimator behind a single `fit` and `predict`:
+//!
+//! * `fit` fits the first transformer to the training data, transforms the data with it and passes the result to the next transformer,
+//!   the final estimator is fitted to the output of the last transformer,
+//! * `predict` and `predict_proba` transform new data with the fitted transformers before passing it to the final estimator.
+//!
+//! Since a pipeline implements [`SupervisedEstimator`](../api/trait.SupervisedEstimator.html) and [`Predictor`](../api/trait.Predictor.html),
+//! it can be [cross-validated](../model_selection/index.html) as a whole, which refits the preprocessing on the training split of every fold.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::api::UnsupervisedEstimator;
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::neighbors::knn_classifier::{KNNClassifier, KNNClassifierParameters};
+//! use smartcore::pipeline::{Pipeline, PipelineParameters};
+//! use smartcore::preprocessing::numerical::{StandardScaler, StandardScalerParameters};
+//! use smartcore::math::distance::euclidian::Euclidian;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[1., 1000.],
+//!     &[2., 3000.],
+//!     &[3., 2000.],
+//!     &[7., 1000.],
+//!     &[8., 3000.],
+//!     &[9., 2000.],
+//! ]);
+//! let y = vec![0., 0., 0., 1., 1., 1.];
+//!
+//! let parameters = PipelineParameters::new(KNNClassifierParameters::default().with_k(3))
+//!     .with_transformer(|x: &DenseMatrix<f64>| {
+//!         StandardScaler::fit(x, StandardScalerParameters::default())
+//!     });
+//! let pipeline: Pipeline<f64, DenseMatrix<f64>, KNNClassifier<f64, Euclidian>> =
+//!     Pipeline::fit(&x, &y, parameters).unwrap();
+//!
+//! let y_hat = pipeline.predict(&x).unwrap();
+//! assert_eq!(y_hat, y);
+//! ```
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::api::{
+    Predictor, PredictorProba, ProbabilisticClassifier, SupervisedEstimator, Transformer,
+};
+use crate::error::Failed;
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::parallel::MaybeSync;
+use crate::preprocessing::compose::{boxed, FitTransformer};
+
+/// Parameters of a pipeline: the transformers in the order they are applied and the parameters of the final estimator.
+/// `B` is the type of parameters of the final estimator.
+pub struct PipelineParameters<M, B> {
+    transformers: Vec<FitTransformer<M>>,
+    /// Parameters of the final estimator.
+    pub estimator: B,
+}
+
+/// A chain of fitted transformers followed by a fitted estimator.
+pub struct Pipeline<T: RealNumber, M: Matrix<T>, E> {
+    transformers: Vec<Box<dyn Transformer<M>>>,
+    estimator: E,
+    phantom: PhantomData<T>,
+}
+
+impl<M, B> PipelineParameters<M, B> {
+    /// Parameters of a pipeline with the final estimator only, add the transformers with `with_transformer`.
+    pub fn new(estimator: B) -> Self {
+        PipelineParameters {
+            transformers: Vec::new(),
+            estimator,
+        }
+    }
+    /// Appends a transformer to the chain.
+    /// * `fit` - a function that fits the transformer to the output of the previous transformer.
+    pub fn with_transformer<E, F>(mut self, fit: F) -> Self
+    where
+        E: Transformer<M> + 'static,
+        F: Fn(&M) -> Result<E, Failed> + MaybeSync + 'static,
+    {
+        self.transformers.push(boxed(fit));
+        self
+    }
+    /// Parameters of the final estimator.
+    pub fn with_estimator<C>(self, estimator: C) -> PipelineParameters<M, C> {
+        PipelineParameters {
+            transformers: self.transformers,
+            estimator,
+        }
+    }
+}
+
+impl<M, B: Default> Default for PipelineParameters<M, B> {
+    fn default() -> Self {
+        PipelineParameters {
+            transformers: Vec::new(),
+            estimator: B::default(),
+        }
+    }
+}
+
+impl<M, B: Clone> Clone for PipelineParameters<M, B> {
+    fn clone(&self) -> Self {
+        PipelineParameters {
+            transformers: self.transformers.clone(),
+            estimator: self.estimator.clone(),
+        }
+    }
+}
+
+impl<M, B: fmt::Debug> fmt::Debug for PipelineParameters<M, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PipelineParameters")
+            .field("n_transformers", &self.transformers.len())
+            .field("estimator", &self.estimator)
+            .finish()
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, E: fmt::Debug> fmt::Debug for Pipeline<T, M, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("n_transformers", &self.transformers.len())
+            .field("estimator", &self.estimator)
+            .finish()
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, B: Clone, E>
+    SupervisedEstimator<M, M::RowVector, PipelineParameters<M, B>> for Pipeline<T, M, E>
+where
+    E: SupervisedEstimator<M, M::RowVector, B>,
+{
+    fn fit(x: &M, y: &M::RowVector, parameters: PipelineParameters<M, B>) -> Result<Self, Failed> {
+        Pipeline::fit(x, y, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, E> Predictor<M, M::RowVector> for Pipeline<T, M, E>
+where
+    E: Predictor<M, M::RowVector>,
+{
+    fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, E> PredictorProba<M> for Pipeline<T, M, E>
+where
+    E: PredictorProba<M>,
+{
+    fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.predict_proba(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, E> ProbabilisticClassifier<T, M> for Pipeline<T, M, E>
+where
+    E: ProbabilisticClassifier<T, M>,
+{
+    fn classes(&self) -> &Vec<T> {
+        self.estimator.classes()
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, E> Pipeline<T, M, E> {
+    /// Fits the transformers one after another and the final estimator to the output of the last transformer.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - the transformers and the parameters of the final estimator
+    pub fn fit<B: Clone>(
+        x: &M,
+        y: &M::RowVector,
+        parameters: PipelineParameters<M, B>,
+    ) -> Result<Pipeline<T, M, E>, Failed>
+    where
+        E: SupervisedEstimator<M, M::RowVector, B>,
+    {
+        let mut transformers = Vec::with_capacity(parameters.transformers.len());
+        let mut x_t: Option<M> = None;
+        for fit in parameters.transformers.iter() {
+            let input = x_t.as_ref().unwrap_or(x);
+            let transformer = fit(input)?;
+            x_t = Some(transformer.transform(input)?);
+            transformers.push(transformer);
+        }
+
+        let estimator = E::fit(x_t.as_ref().unwrap_or(x), y, parameters.estimator)?;
+
+        Ok(Pipeline {
+            transformers,
+            estimator,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Passes `x` through all fitted transformers.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn transform(&self, x: &M) -> Result<M, Failed> {
+        let mut x_t = x.clone();
+        for transformer in self.transformers.iter() {
+            x_t = transformer.transform(&x_t)?;
+        }
+        Ok(x_t)
+    }
+
+    /// Predict target values from `x` with the final estimator after passing `x` through all fitted transformers.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &M) -> Result<M::RowVector, Failed>
+    where
+        E: Predictor<M, M::RowVector>,
+    {
+        self.estimator.predict(&self.transform(x)?)
+    }
+
+    /// Predict class probabilities from `x` with the final estimator after passing `x` through all fitted transformers.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_proba(&self, x: &M) -> Result<M, Failed>
+    where
+        E: PredictorProba<M>,
+    {
+        self.estimator.predict_proba(&self.transform(x)?)
+    }
+
+    /// The final estimator fitted to the output of the last transformer.
+    pub fn estimator(&self) -> &E {
+        &self.estimator
+    }
+
+    /// Number of transformers in the chain.
+    pub fn n_transformers(&self) -> usize {
+        self.transformers.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::UnsupervisedEstimator;
+    use crate::linalg::naive::dense_matrix::*;
+    use crate::linalg::BaseMatrix;
+    use crate::math::distance::euclidian::Euclidian;
+    use crate::metrics::accuracy;
+    use crate::model_selection::{cross_validate, KFold};
+    use crate::neighbors::knn_classifier::{KNNClassifier, KNNClassifierParameters};
+    use crate::preprocessing::impute::{KNNImputer, KNNImputerParameters};
+    use crate::preprocessing::numerical::{StandardScaler, StandardScalerParameters};
+    use crate::tree::decision_tree_classifier::{
+        DecisionTreeClassifier, DecisionTreeClassifierParameters,
+    };
+
+    type ScaledKNN = Pipeline<f64, DenseMatrix<f64>, KNNClassifier<f64, Euclidian>>;
+
+    fn data() -> (DenseMatrix<f64>, Vec<f64>) {
+        // the second feature is noise on a much larger scale than the informative first feature
+        let values: Vec<Vec<f64>> = (0..20)
+            .map(|i| {
+                let class = (i % 2) as f64;
+                vec![class * 5. + (i % 3) as f64, ((i * 7) % 10) as f64 * 1000.]
+            })
+            .collect();
+        let y = (0..20).map(|i| (i % 2) as f64).collect();
+        (DenseMatrix::from_2d_vec(&values), y)
+    }
+
+    fn scaled_knn() -> PipelineParameters<DenseMatrix<f64>, KNNClassifierParameters<f64, Euclidian>>
+    {
+        PipelineParameters::new(KNNClassifierParameters::default().with_k(3)).with_transformer(
+            |x: &DenseMatrix<f64>| StandardScaler::fit(x, StandardScalerParameters::default()),
+        )
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn pipeline_applies_fitted_transformers() {
+        let (x, y) = data();
+        let pipeline: ScaledKNN = Pipeline::fit(&x, &y, scaled_knn()).unwrap();
+
+        assert_eq!(pipeline.n_transformers(), 1);
+        assert_eq!(pipeline.predict(&x).unwrap(), y);
+
+        // new data is scaled with the statistics of the training data
+        let scaler = StandardScaler::fit(&x, StandardScalerParameters::default()).unwrap();
+        let new = DenseMatrix::from_2d_array(&[&[0., 9000.], &[6., 0.]]);
+        assert_eq!(
+            pipeline.transform(&new).unwrap(),
+            scaler.transform(&new).unwrap()
+        );
+        assert_eq!(pipeline.predict(&new).unwrap(), vec![0., 1.]);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn pipeline_cross_validate() {
+        let (x, y) = data();
+        let cv = KFold::default().with_n_splits(4).with_seed(Some(0));
+
+        let results = cross_validate(ScaledKNN::fit, &x, &y, &scaled_knn(), &cv, accuracy).unwrap();
+
+        assert!(results.mean_test_score() > 0.9);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn pipeline_chain_and_predict_proba() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., f64::NAN],
+            &[2., 1.],
+            &[3., 2.],
+            &[7., f64::NAN],
+            &[8., 8.],
+            &[9., 9.],
+        ]);
+        let y = vec![0., 0., 0., 1., 1., 1.];
+
+        let parameters = PipelineParameters::new(DecisionTreeClassifierParameters::default())
+            .with_transformer(|x: &DenseMatrix<f64>| {
+                KNNImputer::fit(x, KNNImputerParameters::default().with_k(1))
+            })
+            .with_transformer(|x: &DenseMatrix<f64>| {
+                StandardScaler::fit(x, StandardScalerParameters::default())
+            });
+        let pipeline: Pipeline<f64, DenseMatrix<f64>, DecisionTreeClassifier<f64>> =
+            Pipeline::fit(&x, &y, parameters).unwrap();
+
+        assert_eq!(pipeline.n_transformers(), 2);
+        assert_eq!(pipeline.predict(&x).unwrap(), y);
+
+        let proba = pipeline.predict_proba(&x).unwrap();
+        assert_eq!(proba.shape(), (6, 2));
+        assert_eq!(proba.get(0, 0), 1.);
+        assert_eq!(proba.get(5, 1), 1.);
+    }
+}
@@ -0,0 +1,180 @@
+//! Center based clustering as PMML `ClusteringModel` elements.
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::error::Failed;
+use crate::math::num::RealNumber;
+use crate::pmml::xml::Element;
+use crate::pmml::{
+    document, feature_name, model, number, parse_count, parse_number, Document, Target,
+};
+
+/// Writes a clustering model that assigns observations to the cluster with the nearest center in Euclidean distance.
+/// * `centroids` - center of every cluster
+/// * `sizes` - number of training samples in every cluster
+pub(crate) fn clustering_model<T: RealNumber>(centroids: &[Vec<T>], sizes: &[usize]) -> String {
+    let n_features = centroids[0].len();
+    let target = Target::<T>::None;
+    let clusters = centroids
+        .iter()
+        .zip(sizes.iter())
+        .enumerate()
+        .map(|(i, (centroid, size))| {
+            let values: Vec<String> = centroid.iter().map(|v| number(*v)).collect();
+            Element::new("Cluster")
+                .attr("id", i + 1)
+                .attr("size", size)
+                .child(
+                    Element::new("Array")
+                        .attr("n", n_features)
+                        .attr("type", "real")
+                        .text(values.join(" ")),
+                )
+        });
+    let model = model("ClusteringModel", "clustering", n_features, &target)
+        .attr("modelClass", "centerBased")
+        .attr("numberOfClusters", centroids.len())
+        .child(
+            Element::new("ComparisonMeasure")
+                .attr("kind", "distance")
+                .child(Element::new("euclidean")),
+        )
+        .children((0..n_features).map(|i| {
+            Element::new("ClusteringField")
+                .attr("field", feature_name(i))
+                .attr("compareFunction", "absDiff")
+        }))
+        .children(clusters);
+    document(n_features, &target, model)
+}
+
+/// Reads the centers of the clusters and the number of training samples in every cluster, 0 when the size is not known.
+pub(crate) fn read_clustering_model<T: RealNumber>(
+    pmml: &str,
+) -> Result<(Vec<Vec<T>>, Vec<usize>), Failed> {
+    let document = Document::parse(pmml)?;
+    let (model, schema) = document.model("ClusteringModel", "clustering")?;
+    if model.attribute("modelClass")? != "centerBased" {
+        return Err(Failed::import(
+            "Only center based clustering models are supported",
+        ));
+    }
+    let measure = model.find("ComparisonMeasure")?;
+    let metric = measure
+        .children
+        .iter()
+        .find(|c| c.name != "Extension")
+        .map_or("none", |c| c.name.as_str());
+    if measure.attribute("kind")? != "distance"
+        || !(metric == "euclidean" || metric == "squaredEuclidean")
+    {
+        return Err(Failed::import(&format!(
+            "Only the euclidean distance is supported, found {}",
+            metric
+        )));
+    }
+
+    // values of the centers follow the order of the clustering fields, or of the mining schema when there are none
+    let mut columns = Vec::new();
+    for field in model.find_all("ClusteringField") {
+        if field.optional_attribute("isCenterField") == Some("false") {
+            continue;
+        }
+        if field
+            .optional_attribute("compareFunction")
+            .unwrap_or("absDiff")
+            != "absDiff"
+        {
+            return Err(Failed::import(
+                "Only the absDiff compare function is supported",
+            ));
+        }
+        columns.push(schema.feature(field.attribute("field")?)?);
+    }
+    if columns.is_empty() {
+        columns = (0..schema.n_features()).collect();
+    }
+
+    let mut centroids = Vec::new();
+    let mut sizes = Vec::new();
+    for cluster in model.find_all("Cluster") {
+        let values = cluster
+            .find("Array")?
+            .text
+            .split_whitespace()
+            .map(|v| parse_number(v.trim_matches('"')))
+            .collect::<Result<Vec<T>, Failed>>()?;
+        if values.len() != columns.len() {
+            return Err(Failed::import(&format!(
+                "Expected a center with {} values, found {}",
+                columns.len(),
+                values.len()
+            )));
+        }
+        let mut centroid = vec![T::zero(); schema.n_features()];
+        for (column, value) in columns.iter().zip(values) {
+            centroid[*column] = value;
+        }
+        centroids.push(centroid);
+        sizes.push(match cluster.optional_attribute("size") {
+            Some(size) => parse_count(size)?,
+            None => 0,
+        });
+    }
+    if centroids.is_empty() {
+        return Err(Failed::import("The model has no clusters"));
+    }
+    Ok((centroids, sizes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cluster::kmeans::*;
+    use crate::dataset::generator::make_blobs_with_seed;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::pmml::{FromPmml, ToPmml};
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn kmeans() {
+        let blobs = make_blobs_with_seed(30, 2, 3, Some(5));
+        let x = DenseMatrix::from_vec(30, 2, &blobs.data);
+
+        let kmeans = KMeans::fit(&x, KMeansParameters::default().with_k(3)).unwrap();
+        let restored = KMeans::<f32>::from_pmml(&kmeans.to_pmml().unwrap()).unwrap();
+        assert_eq!(kmeans, restored);
+        assert_eq!(kmeans.predict(&x).unwrap(), restored.predict(&x).unwrap());
+        assert!(restored.labels().is_empty());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn foreign_clustering_model() {
+        // clustering fields list the features in a different order than the mining schema
+        let pmml = r#"<PMML version="4.4">
+            <DataDictionary>
+                <DataField name="a" optype="continuous" dataType="double"/>
+                <DataField name="b" optype="continuous" dataType="double"/>
+            </DataDictionary>
+            <ClusteringModel functionName="clustering" modelClass="centerBased" numberOfClusters="2">
+                <MiningSchema>
+                    <MiningField name="a"/>
+                    <MiningField name="b"/>
+                </MiningSchema>
+                <ComparisonMeasure kind="distance"><squaredEuclidean/></ComparisonMeasure>
+                <ClusteringField field="b"/>
+                <ClusteringField field="a"/>
+                <Cluster name="first"><Array n="2" type="real">1 2</Array></Cluster>
+                <Cluster name="second" size="4"><Array n="2" type="real">3 4</Array></Cluster>
+            </ClusteringModel>
+        </PMML>"#;
+
+        let (centroids, sizes) = read_clustering_model::<f64>(pmml).unwrap();
+        assert_eq!(vec![vec![2., 1.], vec![4., 3.]], centroids);
+        assert_eq!(vec![0, 4], sizes);
+
+        let manhattan = pmml.replace("squaredEuclidean", "cityBlock");
+        assert!(read_clustering_model::<f64>(&manhattan).is_err());
+    }
+}
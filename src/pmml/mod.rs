@@ -0,0 +1,332 @@
+//! # PMML export and import
+//!
+//! Reads and writes models in the [Predictive Model Markup Language](https://dmg.org/pmml/v4-4-1/GeneralStructure.html) (PMML) 4.4,
+//! an XML format understood by many Java and enterprise scoring engines, e.g. [JPMML](https://github.com/jpmml).
+//!
+//! Models that implement [`ToPmml`](trait.ToPmml.html) are written as a PMML document with a single model,
+//! models that implement [`FromPmml`](trait.FromPmml.html) can be read back from such a document:
+//!
+//! | Model | PMML model |
+//! |-------|------------|
+//! | [DecisionTreeClassifier](../tree/decision_tree_classifier/index.html), [DecisionTreeRegressor](../tree/decision_tree_regressor/index.html) | `TreeModel` |
+//! | [RandomForestClassifier](../ensemble/random_forest_classifier/index.html), [RandomForestRegressor](../ensemble/random_forest_regressor/index.html) | `MiningModel` with a segment for every tree |
+//! | [LinearRegression](../linear/linear_regression/index.html), [RidgeRegression](../linear/ridge_regression/index.html), [Lasso](../linear/lasso/index.html), [ElasticNet](../linear/elastic_net/index.html) | `RegressionModel` |
+//! | [LogisticRegression](../linear/logistic_regression/index.html) | `RegressionModel` with the `logit` or `softmax` normalization |
+//! | [KMeans](../cluster/kmeans/index.html) | `ClusteringModel` |
+//!
+//! Exported documents name the features `x1`, `x2`, ... and the target `y`. When a document is imported,
+//! features follow the order of the active fields in the mining schema of the model, so documents written by other tools can be read as well,
+//! as long as all features are numeric, class labels are numbers and trees only split on conditions like `x <= t`.
+//! Statistics that are only known while fitting a model, like the impurity decrease of tree splits, the training labels of k-means
+//! or the out-of-bag predictions of random forests, are not part of PMML and are empty in imported models.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::pmml::{FromPmml, ToPmml};
+//! use smartcore::tree::decision_tree_classifier::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[5.1, 3.5, 1.4, 0.2],
+//!     &[4.9, 3.0, 1.4, 0.2],
+//!     &[7.0, 3.2, 4.7, 1.4],
+//!     &[6.4, 3.2, 4.5, 1.5],
+//! ]);
+//! let y = vec![0., 0., 1., 1.];
+//!
+//! let tree = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
+//! let pmml = tree.to_pmml().unwrap();
+//!
+//! let restored = DecisionTreeClassifier::<f64>::from_pmml(&pmml).unwrap();
+//! assert_eq!(y, restored.predict(&x).unwrap());
+//! ```
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::error::Failed;
+use crate::math::num::RealNumber;
+
+use self::xml::Element;
+
+pub(crate) mod cluster;
+pub(crate) mod regression;
+pub(crate) mod tree;
+pub(crate) mod xml;
+
+/// Version of PMML written by SmartCore.
+const VERSION: &str = "4.4";
+const NAMESPACE: &str = "http://www.dmg.org/PMML-4_4";
+/// Name of the target field of exported models.
+const TARGET: &str = "y";
+
+/// Models that can be exported to PMML.
+pub trait ToPmml {
+    /// Writes the model to a PMML document.
+    fn to_pmml(&self) -> Result<String, Failed>;
+}
+
+/// Models that can be imported from PMML.
+pub trait FromPmml: Sized {
+    /// Reads a model from a PMML document.
+    /// * `pmml` - PMML document with a single model
+    fn from_pmml(pmml: &str) -> Result<Self, Failed>;
+}
+
+/// Target field of a model.
+pub(crate) enum Target<'a, T: RealNumber> {
+    /// Continuous response of a regression model.
+    Continuous,
+    /// Class labels of a classification model.
+    Categorical(&'a [T]),
+    /// Models without a target, e.g. clustering models.
+    None,
+}
+
+/// Name of the feature in column `i`.
+pub(crate) fn feature_name(i: usize) -> String {
+    format!("x{}", i + 1)
+}
+
+/// Formats a number so that it is read back without loss of precision.
+pub(crate) fn number<T: RealNumber>(value: T) -> String {
+    value.to_f64().unwrap().to_string()
+}
+
+/// Parses a number written by [`number`](fn.number.html).
+pub(crate) fn parse_number<T: RealNumber>(value: &str) -> Result<T, Failed> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .and_then(T::from_f64)
+        .ok_or_else(|| Failed::import(&format!("{} is not a number", value)))
+}
+
+/// Parses a number of records, counts written as decimals are rounded.
+pub(crate) fn parse_count(value: &str) -> Result<usize, Failed> {
+    Ok(parse_number::<f64>(value)?.round() as usize)
+}
+
+/// Creates a model element with the mining schema of the model.
+/// * `name` - name of the model element, e.g. `TreeModel`
+/// * `function` - `regression`, `classification` or `clustering`
+pub(crate) fn model<T: RealNumber>(
+    name: &str,
+    function: &str,
+    n_features: usize,
+    target: &Target<'_, T>,
+) -> Element {
+    let mut schema = Element::new("MiningSchema").children(
+        (0..n_features).map(|i| Element::new("MiningField").attr("name", feature_name(i))),
+    );
+    if !matches!(target, Target::None) {
+        schema = schema.child(
+            Element::new("MiningField")
+                .attr("name", TARGET)
+                .attr("usageType", "target"),
+        );
+    }
+    Element::new(name)
+        .attr("functionName", function)
+        .child(schema)
+}
+
+/// Writes a PMML document with a single model.
+pub(crate) fn document<T: RealNumber>(
+    n_features: usize,
+    target: &Target<'_, T>,
+    model: Element,
+) -> String {
+    let mut fields: Vec<Element> = (0..n_features)
+        .map(|i| {
+            Element::new("DataField")
+                .attr("name", feature_name(i))
+                .attr("optype", "continuous")
+                .attr("dataType", "double")
+        })
+        .collect();
+    match target {
+        Target::Continuous => fields.push(
+            Element::new("DataField")
+                .attr("name", TARGET)
+                .attr("optype", "continuous")
+                .attr("dataType", "double"),
+        ),
+        Target::Categorical(classes) => fields.push(
+            Element::new("DataField")
+                .attr("name", TARGET)
+                .attr("optype", "categorical")
+                .attr("dataType", "double")
+                .children(
+                    classes
+                        .iter()
+                        .map(|c| Element::new("Value").attr("value", number(*c))),
+                ),
+        ),
+        Target::None => {}
+    }
+
+    let root = Element::new("PMML")
+        .attr("xmlns", NAMESPACE)
+        .attr("version", VERSION)
+        .child(
+            Element::new("Header").child(
+                Element::new("Application")
+                    .attr("name", "SmartCore")
+                    .attr("version", env!("CARGO_PKG_VERSION")),
+            ),
+        )
+        .child(
+            Element::new("DataDictionary")
+                .attr("numberOfFields", fields.len())
+                .children(fields),
+        )
+        .child(model);
+
+    let mut pmml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    root.write(&mut pmml, 0);
+    pmml
+}
+
+/// A parsed PMML document.
+pub(crate) struct Document {
+    root: Element,
+}
+
+/// Features and target of a model, read from its mining schema.
+pub(crate) struct Schema {
+    features: Vec<String>,
+    target: Option<String>,
+}
+
+impl Document {
+    pub(crate) fn parse(pmml: &str) -> Result<Self, Failed> {
+        let root = Element::parse(pmml)?;
+        if root.name != "PMML" {
+            return Err(Failed::import(&format!(
+                "Expected a PMML document, found {}",
+                root.name
+            )));
+        }
+        Ok(Document { root })
+    }
+
+    /// Returns the model of the document and its schema, fails when the document holds a different kind of model.
+    /// * `name` - name of the model element, e.g. `TreeModel`
+    /// * `function` - expected function of the model, e.g. `classification`
+    pub(crate) fn model(&self, name: &str, function: &str) -> Result<(&Element, Schema), Failed> {
+        let model = self
+            .root
+            .children
+            .iter()
+            .find(|c| c.name.ends_with("Model"))
+            .ok_or_else(|| Failed::import("The document has no model"))?;
+        if model.name != name || model.attribute("functionName")? != function {
+            return Err(Failed::import(&format!(
+                "Expected a {} for {}, found a {} for {}",
+                name,
+                function,
+                model.name,
+                model
+                    .optional_attribute("functionName")
+                    .unwrap_or("unknown")
+            )));
+        }
+        let schema = Schema::read(model)?;
+        Ok((model, schema))
+    }
+
+    /// Class labels of the target of a classification model, in ascending order.
+    pub(crate) fn classes<T: RealNumber>(&self, schema: &Schema) -> Result<Vec<T>, Failed> {
+        let target = schema
+            .target
+            .as_ref()
+            .ok_or_else(|| Failed::import("The model has no target field"))?;
+        let field = self
+            .root
+            .find("DataDictionary")?
+            .find_all("DataField")
+            .find(|f| f.optional_attribute("name") == Some(target.as_str()))
+            .ok_or_else(|| Failed::import(&format!("Target field {} is not defined", target)))?;
+        let mut classes = field
+            .find_all("Value")
+            .map(|v| parse_number(v.attribute("value")?))
+            .collect::<Result<Vec<T>, Failed>>()?;
+        if classes.len() < 2 {
+            return Err(Failed::import(&format!(
+                "Target field {} should list at least two class labels",
+                target
+            )));
+        }
+        classes.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        Ok(classes)
+    }
+}
+
+impl Schema {
+    fn read(model: &Element) -> Result<Self, Failed> {
+        let mut features = Vec::new();
+        let mut target = None;
+        for field in model.find("MiningSchema")?.find_all("MiningField") {
+            let name = field.attribute("name")?.to_string();
+            match field.optional_attribute("usageType").unwrap_or("active") {
+                "active" => features.push(name),
+                "target" | "predicted" => target = Some(name),
+                _ => {}
+            }
+        }
+        Ok(Schema { features, target })
+    }
+
+    pub(crate) fn n_features(&self) -> usize {
+        self.features.len()
+    }
+
+    /// Column of a feature.
+    pub(crate) fn feature(&self, name: &str) -> Result<usize, Failed> {
+        self.features
+            .iter()
+            .position(|f| f == name)
+            .ok_or_else(|| Failed::import(&format!("{} is not an active field", name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn document_and_schema() {
+        let classes = [1., 0.5, 3.];
+        let target = Target::Categorical(&classes);
+        let pmml = document(2, &target, model("TreeModel", "classification", 2, &target));
+        assert!(pmml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<PMML xmlns="));
+
+        let document = Document::parse(&pmml).unwrap();
+        let (model, schema) = document.model("TreeModel", "classification").unwrap();
+        assert_eq!("TreeModel", model.name);
+        assert_eq!(2, schema.n_features());
+        assert_eq!(1, schema.feature("x2").unwrap());
+        assert!(schema.feature("y").is_err());
+        assert_eq!(vec![0.5, 1., 3.], document.classes::<f64>(&schema).unwrap());
+
+        assert!(document.model("TreeModel", "regression").is_err());
+        assert!(document.model("RegressionModel", "classification").is_err());
+        assert!(Document::parse("<Model/>").is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn numbers() {
+        for value in [0.1, -1e-300, 12345.678901234567, 2.] {
+            assert_eq!(value, parse_number::<f64>(&number(value)).unwrap());
+        }
+        assert_eq!("2", number(2f32));
+        assert!(parse_number::<f64>("two").is_err());
+    }
+}
@@ -0,0 +1,278 @@
+//! Linear and logistic regression as PMML `RegressionModel` elements.
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::error::Failed;
+use crate::linalg::{BaseVector, Matrix};
+use crate::math::num::RealNumber;
+use crate::pmml::tree::class_index;
+use crate::pmml::xml::Element;
+use crate::pmml::{document, feature_name, model, number, parse_number, Document, Schema, Target};
+
+/// Writes a linear regression model.
+/// * `coefficients` - column vector with the coefficient of every feature
+/// * `intercept` - intercept of the model
+pub(crate) fn linear_regression<T: RealNumber, M: Matrix<T>>(
+    coefficients: &M,
+    intercept: T,
+) -> String {
+    let (n_features, _) = coefficients.shape();
+    let target = Target::<T>::Continuous;
+    let model = model("RegressionModel", "regression", n_features, &target)
+        .child(regression_table(intercept, &coefficients.get_col_as_vec(0)));
+    document(n_features, &target, model)
+}
+
+/// Reads the coefficients, as a column vector, and the intercept of a linear regression model.
+pub(crate) fn read_linear_regression<T: RealNumber, M: Matrix<T>>(
+    pmml: &str,
+) -> Result<(M, T), Failed> {
+    let document = Document::parse(pmml)?;
+    let (model, schema) = document.model("RegressionModel", "regression")?;
+    if let Some(method) = model
+        .optional_attribute("normalizationMethod")
+        .filter(|method| *method != "none")
+    {
+        return Err(Failed::import(&format!(
+            "Normalization method {} is not supported for linear regression",
+            method
+        )));
+    }
+    let (coefficients, intercept) = read_regression_table(model.find("RegressionTable")?, &schema)?;
+    let coefficients = M::from_row_vector(M::RowVector::from_array(&coefficients)).transpose();
+    Ok((coefficients, intercept))
+}
+
+/// Writes a logistic regression model. A binary model has a single row of coefficients for the second class
+/// and is written with the `logit` normalization, models with more classes have a row for every class and use `softmax`.
+/// * `classes` - class labels
+/// * `coefficients` - rows of coefficients
+/// * `intercepts` - intercept of every row
+pub(crate) fn logistic_regression<T: RealNumber>(
+    classes: &[T],
+    coefficients: &[Vec<T>],
+    intercepts: &[T],
+) -> String {
+    let n_features = coefficients[0].len();
+    let target = Target::Categorical(classes);
+    let model = model("RegressionModel", "classification", n_features, &target);
+    let model = if classes.len() == 2 {
+        model
+            .attr("normalizationMethod", "logit")
+            .child(
+                regression_table(intercepts[0], &coefficients[0])
+                    .attr("targetCategory", number(classes[1])),
+            )
+            .child(regression_table(T::zero(), &[]).attr("targetCategory", number(classes[0])))
+    } else {
+        model
+            .attr("normalizationMethod", "softmax")
+            .children(classes.iter().enumerate().map(|(k, class)| {
+                regression_table(intercepts[k], &coefficients[k])
+                    .attr("targetCategory", number(*class))
+            }))
+    };
+    document(n_features, &target, model)
+}
+
+/// Reads the class labels, coefficients and intercepts of a logistic regression model, in the layout written by
+/// [`logistic_regression`](fn.logistic_regression.html).
+pub(crate) fn read_logistic_regression<T: RealNumber>(
+    pmml: &str,
+) -> Result<(Vec<T>, Vec<Vec<T>>, Vec<T>), Failed> {
+    let document = Document::parse(pmml)?;
+    let (model, schema) = document.model("RegressionModel", "classification")?;
+    let classes: Vec<T> = document.classes(&schema)?;
+
+    let mut tables = Vec::new();
+    for table in model.find_all("RegressionTable") {
+        let class = class_index(
+            classes.as_slice(),
+            parse_number(table.attribute("targetCategory")?)?,
+        )?;
+        tables.push((class, read_regression_table(table, &schema)?));
+    }
+    let table = |class: usize| {
+        tables
+            .iter()
+            .find(|(k, _)| *k == class)
+            .map(|(_, table)| table.clone())
+            .unwrap_or_else(|| (vec![T::zero(); schema.n_features()], T::zero()))
+    };
+
+    let method = model
+        .optional_attribute("normalizationMethod")
+        .unwrap_or("none");
+    match (method, classes.len()) {
+        // the probability of the first table is the logistic function of its score, the other class gets the rest
+        ("logit", 2) => {
+            let (first, (coefficients, intercept)) = tables
+                .first()
+                .cloned()
+                .ok_or_else(|| Failed::import("The model has no regression tables"))?;
+            let sign = if first == 1 { T::one() } else { -T::one() };
+            Ok((
+                classes,
+                vec![coefficients.iter().map(|c| sign * *c).collect()],
+                vec![sign * intercept],
+            ))
+        }
+        ("softmax", 2) => {
+            let ((w0, b0), (w1, b1)) = (table(0), table(1));
+            Ok((
+                classes,
+                vec![w1.iter().zip(w0.iter()).map(|(a, b)| *a - *b).collect()],
+                vec![b1 - b0],
+            ))
+        }
+        ("softmax", n_classes) => {
+            let (coefficients, intercepts) = (0..n_classes).map(table).unzip();
+            Ok((classes, coefficients, intercepts))
+        }
+        (method, n_classes) => Err(Failed::import(&format!(
+            "Normalization method {} is not supported for logistic regression with {} classes",
+            method, n_classes
+        ))),
+    }
+}
+
+fn regression_table<T: RealNumber>(intercept: T, coefficients: &[T]) -> Element {
+    Element::new("RegressionTable")
+        .attr("intercept", number(intercept))
+        .children(coefficients.iter().enumerate().map(|(i, coefficient)| {
+            Element::new("NumericPredictor")
+                .attr("name", feature_name(i))
+                .attr("coefficient", number(*coefficient))
+        }))
+}
+
+/// Reads the coefficient of every feature and the intercept of a table, features without a predictor have a zero coefficient.
+fn read_regression_table<T: RealNumber>(
+    table: &Element,
+    schema: &Schema,
+) -> Result<(Vec<T>, T), Failed> {
+    if let Some(term) = table
+        .children
+        .iter()
+        .find(|c| c.name == "CategoricalPredictor" || c.name == "PredictorTerm")
+    {
+        return Err(Failed::import(&format!(
+            "{} is not supported, only numeric predictors are",
+            term.name
+        )));
+    }
+    let intercept = match table.optional_attribute("intercept") {
+        Some(intercept) => parse_number(intercept)?,
+        None => T::zero(),
+    };
+    let mut coefficients = vec![T::zero(); schema.n_features()];
+    for predictor in table.find_all("NumericPredictor") {
+        if predictor.optional_attribute("exponent").unwrap_or("1") != "1" {
+            return Err(Failed::import(
+                "Only predictors with exponent 1 are supported",
+            ));
+        }
+        let coefficient: T = parse_number(predictor.attribute("coefficient")?)?;
+        coefficients[schema.feature(predictor.attribute("name")?)?] += coefficient;
+    }
+    Ok((coefficients, intercept))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataset::generator::make_blobs_with_seed;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::linear::lasso::*;
+    use crate::linear::linear_regression::*;
+    use crate::linear::logistic_regression::*;
+    use crate::pmml::{FromPmml, ToPmml};
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn linear_models() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[234.289, 235.6, 159.0, 107.608, 1947., 60.323],
+            &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+            &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+            &[284.599, 335.1, 165.0, 110.929, 1950., 61.187],
+            &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+            &[346.999, 193.2, 359.4, 113.270, 1952., 63.639],
+            &[365.385, 187.0, 354.7, 115.094, 1953., 64.989],
+            &[363.112, 357.8, 335.0, 116.219, 1954., 63.761],
+        ]);
+        let y = vec![83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0, 100.0];
+
+        let lr = LinearRegression::fit(&x, &y, Default::default()).unwrap();
+        let restored =
+            LinearRegression::<f64, DenseMatrix<f64>>::from_pmml(&lr.to_pmml().unwrap()).unwrap();
+        assert_eq!(lr.coefficients(), restored.coefficients());
+        assert_eq!(lr.intercept(), restored.intercept());
+        assert_eq!(lr.predict(&x).unwrap(), restored.predict(&x).unwrap());
+
+        let lasso = Lasso::fit(&x, &y, Default::default()).unwrap();
+        let restored =
+            Lasso::<f64, DenseMatrix<f64>>::from_pmml(&lasso.to_pmml().unwrap()).unwrap();
+        assert_eq!(lasso.predict(&x).unwrap(), restored.predict(&x).unwrap());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn logistic_regression() {
+        for n_classes in [2, 3] {
+            let blobs = make_blobs_with_seed(30, 4, n_classes, Some(3));
+            let x = DenseMatrix::from_vec(30, 4, &blobs.data);
+            let y = blobs.target;
+
+            let lr = LogisticRegression::fit(&x, &y, Default::default()).unwrap();
+            let pmml = lr.to_pmml().unwrap();
+            let restored = LogisticRegression::<f32, DenseMatrix<f32>>::from_pmml(&pmml).unwrap();
+            assert_eq!(lr.predict(&x).unwrap(), restored.predict(&x).unwrap());
+            assert_eq!(lr.coefficients(), restored.coefficients());
+            assert_eq!(lr.intercept(), restored.intercept());
+            assert!(LinearRegression::<f32, DenseMatrix<f32>>::from_pmml(&pmml).is_err());
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn foreign_logistic_regression() {
+        // the first table is written for the first class, so its coefficients have the opposite sign
+        let pmml = |method: &str| {
+            format!(
+                r#"<PMML version="4.4">
+                <DataDictionary>
+                    <DataField name="a" optype="continuous" dataType="double"/>
+                    <DataField name="label" optype="categorical" dataType="integer">
+                        <Value value="0"/><Value value="1"/>
+                    </DataField>
+                </DataDictionary>
+                <RegressionModel functionName="classification" normalizationMethod="{}">
+                    <MiningSchema>
+                        <MiningField name="a"/>
+                        <MiningField name="label" usageType="target"/>
+                    </MiningSchema>
+                    <RegressionTable targetCategory="0" intercept="1.5">
+                        <NumericPredictor name="a" coefficient="-2"/>
+                    </RegressionTable>
+                    <RegressionTable targetCategory="1" intercept="0"/>
+                </RegressionModel>
+            </PMML>"#,
+                method
+            )
+        };
+
+        let (classes, coefficients, intercepts) =
+            read_logistic_regression::<f64>(&pmml("logit")).unwrap();
+        assert_eq!(vec![0., 1.], classes);
+        assert_eq!(vec![vec![2.]], coefficients);
+        assert_eq!(vec![-1.5], intercepts);
+
+        let (_, coefficients, intercepts) =
+            read_logistic_regression::<f64>(&pmml("softmax")).unwrap();
+        assert_eq!(vec![vec![2.]], coefficients);
+        assert_eq!(vec![-1.5], intercepts);
+
+        assert!(read_logistic_regression::<f64>(&pmml("probit")).is_err());
+    }
+}
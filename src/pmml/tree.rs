@@ -0,0 +1,521 @@
+//! Decision trees as PMML `TreeModel` elements.
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::error::Failed;
+use crate::math::num::RealNumber;
+use crate::pmml::xml::Element;
+use crate::pmml::{feature_name, model, number, parse_count, parse_number, Schema, Target};
+use crate::tree::node::{node_depths, TreeNode};
+
+/// A node of a decision tree. Nodes are stored in a flat list where the root node comes first and parents come before their children.
+#[derive(Debug, Clone)]
+pub(crate) struct PmmlNode<T: RealNumber> {
+    /// Predicted value, the class label for classification trees.
+    pub(crate) score: T,
+    pub(crate) n_samples: usize,
+    /// Probability of every class, empty for regression trees.
+    pub(crate) probabilities: Vec<T>,
+    /// Feature and threshold of the split, observations with a value less than or equal to the threshold go to the first child.
+    pub(crate) split: Option<(usize, T)>,
+    /// Indices of the true and false child.
+    pub(crate) children: Option<(usize, usize)>,
+}
+
+/// Converts the nodes of a fitted tree, fails when the tree has oblique splits.
+/// * `nodes` - nodes of the tree
+/// * `probabilities` - class probabilities of the node with the given index, empty for regression trees
+pub(crate) fn pmml_nodes<'a, T: RealNumber + 'a>(
+    nodes: impl Iterator<Item = TreeNode<'a, T>>,
+    probabilities: impl Fn(usize) -> Vec<T>,
+) -> Result<Vec<PmmlNode<T>>, Failed> {
+    nodes
+        .map(|node| {
+            if node.split_weights.is_some() {
+                return Err(Failed::export("Oblique splits can not be written to PMML"));
+            }
+            Ok(PmmlNode {
+                score: node.output,
+                n_samples: node.n_samples,
+                probabilities: probabilities(node.index),
+                split: node.split_feature.zip(node.threshold),
+                children: node.true_child.zip(node.false_child),
+            })
+        })
+        .collect()
+}
+
+/// Depth of a tree as stored by fitted trees, 0 for a tree with a single node.
+pub(crate) fn depth<T: RealNumber>(nodes: &[PmmlNode<T>]) -> u16 {
+    if nodes.len() < 2 {
+        0
+    } else {
+        node_depths(nodes.iter().map(|node| match node.children {
+            Some((true_child, false_child)) => (Some(true_child), Some(false_child)),
+            None => (None, None),
+        }))
+        .into_iter()
+        .max()
+        .unwrap_or(0)
+    }
+}
+
+/// Builds a `TreeModel` element.
+/// * `nodes` - nodes of the tree
+/// * `n_features` - number of features
+/// * `target` - target of the tree, a classification tree when the target is categorical
+pub(crate) fn tree_model<T: RealNumber>(
+    nodes: &[PmmlNode<T>],
+    n_features: usize,
+    target: &Target<'_, T>,
+) -> Element {
+    let function = match target {
+        Target::Categorical(_) => "classification",
+        _ => "regression",
+    };
+    model("TreeModel", function, n_features, target)
+        .attr("splitCharacteristic", "binarySplit")
+        .child(node_element(nodes, 0, Element::new("True"), target))
+}
+
+/// Builds a `MiningModel` element with a segment for every tree, all segments apply to every observation.
+/// * `trees` - `TreeModel` elements
+/// * `method` - how the predictions of the trees are combined, e.g. `majorityVote`
+pub(crate) fn forest_model<T: RealNumber>(
+    trees: Vec<Element>,
+    n_features: usize,
+    target: &Target<'_, T>,
+    method: &str,
+) -> Element {
+    let function = match target {
+        Target::Categorical(_) => "classification",
+        _ => "regression",
+    };
+    let segments = trees.into_iter().enumerate().map(|(i, tree)| {
+        Element::new("Segment")
+            .attr("id", i + 1)
+            .child(Element::new("True"))
+            .child(tree)
+    });
+    model("MiningModel", function, n_features, target).child(
+        Element::new("Segmentation")
+            .attr("multipleModelMethod", method)
+            .children(segments),
+    )
+}
+
+/// Returns the `TreeModel` elements of a `MiningModel`, fails unless the predictions of the trees are combined with `method`
+/// and every segment applies to all observations.
+pub(crate) fn read_forest<'a>(
+    model: &'a Element,
+    method: &str,
+) -> Result<Vec<&'a Element>, Failed> {
+    let segmentation = model.find("Segmentation")?;
+    let found = segmentation.attribute("multipleModelMethod")?;
+    if found != method {
+        return Err(Failed::import(&format!(
+            "Expected segments combined with {}, found {}",
+            method, found
+        )));
+    }
+    let trees = segmentation
+        .find_all("Segment")
+        .map(|segment| {
+            if predicate(segment)?.name != "True" {
+                return Err(Failed::import(
+                    "Only segments with a True predicate are supported",
+                ));
+            }
+            segment.find("TreeModel")
+        })
+        .collect::<Result<Vec<&Element>, Failed>>()?;
+    if trees.is_empty() {
+        return Err(Failed::import("The model has no trees"));
+    }
+    Ok(trees)
+}
+
+fn node_element<T: RealNumber>(
+    nodes: &[PmmlNode<T>],
+    index: usize,
+    predicate: Element,
+    target: &Target<'_, T>,
+) -> Element {
+    let node = &nodes[index];
+    let mut element = Element::new("Node")
+        .attr("id", index)
+        .attr("score", number(node.score))
+        .attr("recordCount", node.n_samples)
+        .child(predicate);
+    if let Target::Categorical(classes) = target {
+        for (class, probability) in classes.iter().zip(node.probabilities.iter()) {
+            element = element.child(
+                Element::new("ScoreDistribution")
+                    .attr("value", number(*class))
+                    .attr(
+                        "recordCount",
+                        number(*probability * T::from_usize(node.n_samples).unwrap()),
+                    )
+                    .attr("probability", number(*probability)),
+            );
+        }
+    }
+    if let (Some((feature, threshold)), Some((true_child, false_child))) =
+        (node.split, node.children)
+    {
+        let predicate = |operator: &str| {
+            Element::new("SimplePredicate")
+                .attr("field", feature_name(feature))
+                .attr("operator", operator)
+                .attr("value", number(threshold))
+        };
+        element = element
+            .child(node_element(
+                nodes,
+                true_child,
+                predicate("lessOrEqual"),
+                target,
+            ))
+            .child(node_element(
+                nodes,
+                false_child,
+                predicate("greaterThan"),
+                target,
+            ));
+    }
+    element
+}
+
+/// Reads the nodes of a `TreeModel` element.
+/// * `model` - the `TreeModel` element
+/// * `schema` - schema of the document, used to find the columns of the features
+/// * `classes` - class labels of a classification tree, empty for regression trees
+pub(crate) fn read_tree<T: RealNumber>(
+    model: &Element,
+    schema: &Schema,
+    classes: &[T],
+) -> Result<Vec<PmmlNode<T>>, Failed> {
+    let mut nodes = Vec::new();
+    let mut ids = Vec::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(model.find("Node")?);
+
+    while let Some(element) = queue.pop_front() {
+        let mut node = read_node(element, classes)?;
+        let children: Vec<&Element> = element.find_all("Node").collect();
+        match children.len() {
+            0 => {}
+            2 => {
+                let (feature, threshold, first_is_true) =
+                    split(predicate(children[0])?, predicate(children[1])?)?;
+                let first = nodes.len() + queue.len() + 1;
+                node.split = Some((schema.feature(&feature)?, threshold));
+                node.children = Some(if first_is_true {
+                    (first, first + 1)
+                } else {
+                    (first + 1, first)
+                });
+                queue.extend(children);
+            }
+            n => {
+                return Err(Failed::import(&format!(
+                    "Only binary splits are supported, found a node with {} children",
+                    n
+                )))
+            }
+        }
+        ids.push(
+            element
+                .optional_attribute("id")
+                .and_then(|id| id.parse::<usize>().ok()),
+        );
+        nodes.push(node);
+    }
+
+    Ok(renumber(nodes, &ids))
+}
+
+/// Puts the nodes in the order of their ids when the ids are the positions of the nodes in an exported tree.
+fn renumber<T: RealNumber>(nodes: Vec<PmmlNode<T>>, ids: &[Option<usize>]) -> Vec<PmmlNode<T>> {
+    let mut seen = vec![false; nodes.len()];
+    for id in ids.iter() {
+        match id {
+            Some(id) if *id < nodes.len() && !seen[*id] => seen[*id] = true,
+            _ => return nodes,
+        }
+    }
+    let ids: Vec<usize> = ids.iter().map(|id| id.unwrap()).collect();
+    let mut renumbered = nodes.clone();
+    for (i, mut node) in nodes.into_iter().enumerate() {
+        node.children = node
+            .children
+            .map(|(true_child, false_child)| (ids[true_child], ids[false_child]));
+        renumbered[ids[i]] = node;
+    }
+    renumbered
+}
+
+fn read_node<T: RealNumber>(element: &Element, classes: &[T]) -> Result<PmmlNode<T>, Failed> {
+    let score = parse_number(element.attribute("score")?)?;
+    let n_samples = match element.optional_attribute("recordCount") {
+        Some(count) => parse_count(count)?,
+        None => 0,
+    };
+
+    let mut probabilities = vec![T::zero(); classes.len()];
+    if !classes.is_empty() {
+        let distributions: Vec<&Element> = element.find_all("ScoreDistribution").collect();
+        let mut counts = vec![T::zero(); classes.len()];
+        let mut has_probabilities = true;
+        for distribution in distributions.iter() {
+            let class = class_index(classes, parse_number(distribution.attribute("value")?)?)?;
+            counts[class] = parse_number(distribution.attribute("recordCount")?)?;
+            match distribution.optional_attribute("probability") {
+                Some(p) => probabilities[class] = parse_number(p)?,
+                None => has_probabilities = false,
+            }
+        }
+        let total = counts.iter().fold(T::zero(), |acc, c| acc + *c);
+        if distributions.is_empty() || (!has_probabilities && total == T::zero()) {
+            // without a distribution all observations in the node belong to the predicted class
+            probabilities = vec![T::zero(); classes.len()];
+            probabilities[class_index(classes, score)?] = T::one();
+        } else if !has_probabilities {
+            for (p, c) in probabilities.iter_mut().zip(counts.iter()) {
+                *p = *c / total;
+            }
+        }
+        class_index(classes, score)?;
+    }
+
+    Ok(PmmlNode {
+        score,
+        n_samples,
+        probabilities,
+        split: None,
+        children: None,
+    })
+}
+
+/// Position of a class label.
+pub(crate) fn class_index<T: RealNumber>(classes: &[T], label: T) -> Result<usize, Failed> {
+    classes
+        .iter()
+        .position(|c| *c == label)
+        .ok_or_else(|| Failed::import(&format!("{} is not a class label of the target", label)))
+}
+
+/// The predicate of a node, the first element after optional extensions.
+fn predicate(node: &Element) -> Result<&Element, Failed> {
+    node.children
+        .iter()
+        .find(|c| c.name != "Extension")
+        .ok_or_else(|| Failed::import("Node without a predicate"))
+}
+
+/// Reads the split of a node from the predicates of its children.
+/// Returns the feature, the threshold and whether the first child is the child with observations less than or equal to the threshold.
+fn split<T: RealNumber>(first: &Element, second: &Element) -> Result<(String, T, bool), Failed> {
+    let condition = |predicate: &Element| -> Result<Option<(String, String, T)>, Failed> {
+        if predicate.name == "SimplePredicate" {
+            Ok(Some((
+                predicate.attribute("field")?.into(),
+                predicate.attribute("operator")?.into(),
+                parse_number(predicate.attribute("value")?)?,
+            )))
+        } else {
+            Ok(None)
+        }
+    };
+    let unsupported = || {
+        Failed::import(&format!(
+            "Only splits on a single field with predicates lessOrEqual and greaterThan are supported, found {} and {}",
+            first.name, second.name
+        ))
+    };
+
+    let (first_condition, second_condition) = (condition(first)?, condition(second)?);
+    let (feature, operator, threshold) = first_condition
+        .clone()
+        .or_else(|| second_condition.clone())
+        .ok_or_else(unsupported)?;
+    let from_first = first_condition.is_some();
+    let (first_is_true, opposite) = match operator.as_str() {
+        "lessOrEqual" => (from_first, "greaterThan"),
+        "greaterThan" => (!from_first, "lessOrEqual"),
+        _ => return Err(unsupported()),
+    };
+    // the other child must hold the opposite condition, or all remaining observations
+    let (other, other_condition) = if from_first {
+        (second, second_condition)
+    } else {
+        (first, first_condition)
+    };
+    let valid = match other_condition {
+        Some((f, o, t)) => f == feature && o == opposite && t == threshold,
+        None => other.name == "True",
+    };
+    if !valid {
+        return Err(unsupported());
+    }
+    Ok((feature, threshold, first_is_true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataset::generator::make_blobs_with_seed;
+    use crate::ensemble::random_forest_classifier::*;
+    use crate::ensemble::random_forest_regressor::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::pmml::{Document, FromPmml, ToPmml};
+    use crate::tree::decision_tree_classifier::*;
+    use crate::tree::decision_tree_regressor::*;
+    use crate::tree::oblique::SplitType;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn decision_trees() {
+        let blobs = make_blobs_with_seed(30, 3, 3, Some(7));
+        let x = DenseMatrix::from_vec(30, 3, &blobs.data);
+        let y = blobs.target;
+
+        let classifier = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
+        let restored =
+            DecisionTreeClassifier::<f32>::from_pmml(&classifier.to_pmml().unwrap()).unwrap();
+        assert_eq!(
+            classifier.predict(&x).unwrap(),
+            restored.predict(&x).unwrap()
+        );
+        assert_eq!(
+            classifier.predict_proba(&x).unwrap(),
+            restored.predict_proba(&x).unwrap()
+        );
+        assert_eq!(classifier.depth(), restored.depth());
+        assert!(DecisionTreeRegressor::<f32>::from_pmml(&classifier.to_pmml().unwrap()).is_err());
+
+        let regressor = DecisionTreeRegressor::fit(&x, &y, Default::default()).unwrap();
+        let restored =
+            DecisionTreeRegressor::<f32>::from_pmml(&regressor.to_pmml().unwrap()).unwrap();
+        assert_eq!(
+            regressor.predict(&x).unwrap(),
+            restored.predict(&x).unwrap()
+        );
+        assert_eq!(regressor.nodes().count(), restored.nodes().count());
+
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 0.],
+            &[2., 1.],
+            &[3., 2.],
+            &[4., 3.],
+            &[0., 1.],
+            &[1., 2.],
+            &[2., 3.],
+            &[3., 4.],
+        ]);
+        let y = vec![0., 0., 0., 0., 1., 1., 1., 1.];
+        let oblique = DecisionTreeClassifier::fit(
+            &x,
+            &y,
+            DecisionTreeClassifierParameters::default().with_split_type(SplitType::Oblique {
+                max_features: 2,
+                alpha: 1e-3,
+            }),
+        )
+        .unwrap();
+        assert!(oblique.to_pmml().is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn random_forests() {
+        let blobs = make_blobs_with_seed(30, 3, 3, Some(7));
+        let x = DenseMatrix::from_vec(30, 3, &blobs.data);
+        let y = blobs.target;
+
+        let classifier = RandomForestClassifier::fit(
+            &x,
+            &y,
+            RandomForestClassifierParameters::default().with_n_trees(5),
+        )
+        .unwrap();
+        let pmml = classifier.to_pmml().unwrap();
+        assert!(pmml.contains("multipleModelMethod=\"majorityVote\""));
+        let restored = RandomForestClassifier::<f32>::from_pmml(&pmml).unwrap();
+        assert_eq!(
+            classifier.predict(&x).unwrap(),
+            restored.predict(&x).unwrap()
+        );
+
+        let regressor = RandomForestRegressor::fit(
+            &x,
+            &y,
+            RandomForestRegressorParameters::default().with_n_trees(5),
+        )
+        .unwrap();
+        let restored =
+            RandomForestRegressor::<f32>::from_pmml(&regressor.to_pmml().unwrap()).unwrap();
+        for (a, b) in regressor
+            .predict(&x)
+            .unwrap()
+            .iter()
+            .zip(restored.predict(&x).unwrap().iter())
+        {
+            assert!((a - b).abs() < 1e-5);
+        }
+        assert!(RandomForestRegressor::<f32>::from_pmml(&pmml).is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn foreign_tree() {
+        // children listed with the greater than branch first and no node ids
+        let pmml = r#"<PMML version="4.4">
+            <DataDictionary>
+                <DataField name="a" optype="continuous" dataType="double"/>
+                <DataField name="b" optype="continuous" dataType="double"/>
+                <DataField name="label" optype="categorical" dataType="integer">
+                    <Value value="1"/><Value value="2"/>
+                </DataField>
+            </DataDictionary>
+            <TreeModel functionName="classification">
+                <MiningSchema>
+                    <MiningField name="label" usageType="target"/>
+                    <MiningField name="b"/>
+                    <MiningField name="a"/>
+                </MiningSchema>
+                <Node score="1" recordCount="10">
+                    <True/>
+                    <Node score="2" recordCount="4">
+                        <SimplePredicate field="a" operator="greaterThan" value="0.5"/>
+                        <ScoreDistribution value="1" recordCount="1"/>
+                        <ScoreDistribution value="2" recordCount="3"/>
+                    </Node>
+                    <Node score="1" recordCount="6">
+                        <True/>
+                        <ScoreDistribution value="1" recordCount="6"/>
+                        <ScoreDistribution value="2" recordCount="0"/>
+                    </Node>
+                </Node>
+            </TreeModel>
+        </PMML>"#;
+
+        let document = Document::parse(pmml).unwrap();
+        let (model, schema) = document.model("TreeModel", "classification").unwrap();
+        let classes: Vec<f64> = document.classes(&schema).unwrap();
+        let nodes = read_tree(model, &schema, &classes).unwrap();
+
+        assert_eq!(3, nodes.len());
+        assert_eq!(Some((1, 0.5)), nodes[0].split);
+        assert_eq!(Some((2, 1)), nodes[0].children);
+        assert_eq!(vec![0.25, 0.75], nodes[1].probabilities);
+        assert_eq!(vec![1., 0.], nodes[2].probabilities);
+        assert_eq!(6, nodes[2].n_samples);
+
+        let unsupported = pmml.replace("greaterThan", "equal");
+        let document = Document::parse(&unsupported).unwrap();
+        let (model, schema) = document.model("TreeModel", "classification").unwrap();
+        assert!(read_tree(model, &schema, &classes).is_err());
+    }
+}
@@ -0,0 +1,311 @@
+//! Minimal XML reader and writer, enough to read and write PMML documents.
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Write};
+
+use crate::error::Failed;
+
+/// An XML element with its attributes, child elements and text. Namespace prefixes are removed from names when a document is parsed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Element {
+    pub(crate) name: String,
+    pub(crate) attributes: Vec<(String, String)>,
+    pub(crate) children: Vec<Element>,
+    pub(crate) text: String,
+}
+
+impl Element {
+    pub(crate) fn new(name: &str) -> Self {
+        Element {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn attr(mut self, name: &str, value: impl Display) -> Self {
+        self.attributes.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub(crate) fn child(mut self, child: Element) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub(crate) fn children(mut self, children: impl IntoIterator<Item = Element>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    pub(crate) fn text(mut self, text: String) -> Self {
+        self.text = text;
+        self
+    }
+
+    /// Value of an attribute, fails when the attribute is missing.
+    pub(crate) fn attribute(&self, name: &str) -> Result<&str, Failed> {
+        self.optional_attribute(name).ok_or_else(|| {
+            Failed::import(&format!("Element {} has no attribute {}", self.name, name))
+        })
+    }
+
+    pub(crate) fn optional_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// First child element with the given name, fails when there is none.
+    pub(crate) fn find(&self, name: &str) -> Result<&Element, Failed> {
+        self.children
+            .iter()
+            .find(|c| c.name == name)
+            .ok_or_else(|| Failed::import(&format!("Element {} has no child {}", self.name, name)))
+    }
+
+    pub(crate) fn find_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    /// Serializes the element and all its children, every element on its own line.
+    pub(crate) fn write(&self, out: &mut String, indent: usize) {
+        let padding = "  ".repeat(indent);
+        write!(out, "{}<{}", padding, self.name).unwrap();
+        for (name, value) in self.attributes.iter() {
+            write!(out, " {}=\"{}\"", name, escape(value)).unwrap();
+        }
+        if self.children.is_empty() && self.text.is_empty() {
+            out.push_str("/>\n");
+        } else if self.children.is_empty() {
+            writeln!(out, ">{}</{}>", escape(&self.text), self.name).unwrap();
+        } else {
+            out.push_str(">\n");
+            for child in self.children.iter() {
+                child.write(out, indent + 1);
+            }
+            writeln!(out, "{}</{}>", padding, self.name).unwrap();
+        }
+    }
+
+    /// Parses a document and returns its root element.
+    pub(crate) fn parse(xml: &str) -> Result<Element, Failed> {
+        let mut parser = Parser { xml, pos: 0 };
+        parser.skip_prolog()?;
+        let root = parser.element()?;
+        parser.skip_misc()?;
+        if parser.pos < xml.len() {
+            return Err(parser.error("unexpected content after the root element"));
+        }
+        Ok(root)
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Removes the namespace prefix of a name.
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap()
+}
+
+struct Parser<'a> {
+    xml: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.xml[self.pos..]
+    }
+
+    fn error(&self, msg: &str) -> Failed {
+        Failed::import(&format!("Invalid XML at byte {}: {}", self.pos, msg))
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips everything up to and including `end`.
+    fn skip_past(&mut self, end: &str) -> Result<&'a str, Failed> {
+        match self.rest().find(end) {
+            Some(i) => {
+                let skipped = &self.rest()[..i];
+                self.pos += i + end.len();
+                Ok(skipped)
+            }
+            None => Err(self.error(&format!("missing {}", end))),
+        }
+    }
+
+    /// Skips whitespace, comments and processing instructions.
+    fn skip_misc(&mut self) -> Result<(), Failed> {
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if self.rest().starts_with("<?") {
+                self.skip_past("?>")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn skip_prolog(&mut self) -> Result<(), Failed> {
+        self.skip_misc()?;
+        if self.rest().starts_with("<!DOCTYPE") {
+            self.skip_past(">")?;
+            self.skip_misc()?;
+        }
+        Ok(())
+    }
+
+    fn name(&mut self) -> Result<&'a str, Failed> {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '>' || c == '/' || c == '=')
+            .unwrap_or(rest.len());
+        if end == 0 {
+            return Err(self.error("expected a name"));
+        }
+        self.pos += end;
+        Ok(&rest[..end])
+    }
+
+    fn element(&mut self) -> Result<Element, Failed> {
+        if !self.rest().starts_with('<') {
+            return Err(self.error("expected an element"));
+        }
+        self.pos += 1;
+        let name = self.name()?;
+        let mut element = Element::new(local_name(name));
+
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            } else if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let attribute = self.name()?;
+            self.skip_whitespace();
+            if !self.rest().starts_with('=') {
+                return Err(self.error("expected ="));
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(quote) if quote == '"' || quote == '\'' => quote,
+                _ => return Err(self.error("expected a quoted attribute value")),
+            };
+            self.pos += 1;
+            let value = self.skip_past(&quote.to_string())?;
+            // namespace declarations are not needed to read PMML
+            if attribute != "xmlns" && !attribute.starts_with("xmlns:") {
+                element
+                    .attributes
+                    .push((local_name(attribute).to_string(), unescape(value)));
+            }
+        }
+
+        loop {
+            let text_end = self.rest().find('<').unwrap_or(self.rest().len());
+            element.text.push_str(&unescape(&self.rest()[..text_end]));
+            self.pos += text_end;
+            if self.rest().starts_with("</") {
+                self.pos += 2;
+                let closing = self.name()?;
+                if closing != name {
+                    return Err(
+                        self.error(&format!("closing tag {} does not match {}", closing, name))
+                    );
+                }
+                self.skip_whitespace();
+                self.skip_past(">")?;
+                element.text = element.text.trim().to_string();
+                return Ok(element);
+            } else if self.rest().starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if self.rest().starts_with("<![CDATA[") {
+                self.pos += 9;
+                let data = self.skip_past("]]>")?;
+                element.text.push_str(data);
+            } else if self.rest().starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if self.rest().is_empty() {
+                return Err(self.error(&format!("element {} is not closed", name)));
+            } else {
+                let child = self.element()?;
+                element.children.push(child);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn write_and_parse() {
+        let element = Element::new("PMML")
+            .attr("version", "4.4")
+            .child(Element::new("Header").attr("description", "a < b & \"c\""))
+            .child(Element::new("Array").attr("n", 2).text("1 2".to_string()));
+
+        let mut xml = String::new();
+        element.write(&mut xml, 0);
+        assert_eq!(
+            "<PMML version=\"4.4\">\n  <Header description=\"a &lt; b &amp; &quot;c&quot;\"/>\n  <Array n=\"2\">1 2</Array>\n</PMML>\n",
+            xml
+        );
+        assert_eq!(element, Element::parse(&xml).unwrap());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn parse_document() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <!-- exported by another tool -->
+            <pmml:PMML xmlns:pmml="http://www.dmg.org/PMML-4_4" version='4.4'>
+                <pmml:Header/>
+                <pmml:Array n="3"><![CDATA[1 2 3]]></pmml:Array>
+            </pmml:PMML>"#;
+        let root = Element::parse(xml).unwrap();
+        assert_eq!("PMML", root.name);
+        assert_eq!("4.4", root.attribute("version").unwrap());
+        assert!(root.find("Header").is_ok());
+        assert_eq!("1 2 3", root.find("Array").unwrap().text);
+        assert!(root.find("DataDictionary").is_err());
+
+        assert!(Element::parse("<PMML><Header></PMML>").is_err());
+        assert!(Element::parse("<PMML/><PMML/>").is_err());
+    }
+}
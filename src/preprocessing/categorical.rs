@@ -24,14 +24,50 @@
 //! //    &[1.5, 1.0, 0.0, 1.5, 0.0, 0.0, 1.0, 0.0]
 //! //    &[1.5, 0.0, 1.0, 1.5, 0.0, 0.0, 0.0, 1.0]
 //! ```
-use std::iter;
+//!
+//! The indicator columns of a categorical variable always sum to one, which makes them collinear with an intercept.
+//! `with_drop_first` removes the indicator of the first category of every column, its rows are encoded by zeros only.
+//! Categories that were not seen during `fit` are rejected by `transform` unless the encoder ignores them with
+//! [`HandleUnknown::Ignore`](enum.HandleUnknown.html), which encodes them by zeros only as well.
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::preprocessing::categorical::{HandleUnknown, OneHotEncoder, OneHotEncoderParams};
+//! let data = DenseMatrix::from_2d_array(&[
+//!         &[0.5, 1.0],
+//!         &[1.5, 2.0],
+//!         &[2.5, 3.0],
+//!   ]);
+//! let encoder_params = OneHotEncoderParams::from_cat_idx(&[1])
+//!     .with_drop_first(true)
+//!     .with_handle_unknown(HandleUnknown::Ignore);
+//! let encoder = OneHotEncoder::fit(&data, encoder_params).unwrap();
+//!
+//! let new_data = DenseMatrix::from_2d_array(&[&[0.0, 3.0], &[0.0, 7.0]]);
+//! assert_eq!(
+//!     encoder.transform(&new_data).unwrap(),
+//!     DenseMatrix::from_2d_array(&[&[0.0, 0.0, 1.0], &[0.0, 0.0, 0.0]])
+//! );
+//! ```
+use core::iter;
 
+use crate::api::{Transformer, UnsupervisedEstimator};
 use crate::error::Failed;
+use crate::linalg::naive::dense_matrix::DenseMatrix;
 use crate::linalg::Matrix;
 
 use crate::preprocessing::data_traits::{CategoricalFloat, Categorizable};
 use crate::preprocessing::series_encoder::CategoryMapper;
 
+/// What to do with categories that were not seen during `fit`
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum HandleUnknown {
+    #[default]
+    /// Fail the transformation
+    Error,
+    /// Encode the unknown category by zeros in all indicator columns of the variable
+    Ignore,
+}
+
 /// OneHotEncoder Parameters
 #[derive(Debug, Clone)]
 pub struct OneHotEncoderParams {
@@ -39,6 +75,10 @@ pub struct OneHotEncoderParams {
     pub col_idx_categorical: Option<Vec<usize>>,
     /// (Currently not implemented) Try and infer which of the matrix columns are categorical variables
     infer_categorical: bool,
+    /// Drop the indicator column of the first category of every variable
+    pub drop_first: bool,
+    /// What to do with categories that were not seen during `fit`
+    pub handle_unknown: HandleUnknown,
 }
 
 impl OneHotEncoderParams {
@@ -47,8 +87,22 @@ impl OneHotEncoderParams {
         Self {
             col_idx_categorical: Some(categorical_params.to_vec()),
             infer_categorical: false,
+            drop_first: false,
+            handle_unknown: HandleUnknown::default(),
         }
     }
+
+    /// Drop the indicator column of the first category of every variable, the first category is the first one seen during `fit`
+    pub fn with_drop_first(mut self, drop_first: bool) -> Self {
+        self.drop_first = drop_first;
+        self
+    }
+
+    /// What to do with categories that were not seen during `fit`
+    pub fn with_handle_unknown(mut self, handle_unknown: HandleUnknown) -> Self {
+        self.handle_unknown = handle_unknown;
+        self
+    }
 }
 
 /// Calculate the offset to parameters to due introduction of one-hot encoding
@@ -67,9 +121,10 @@ fn find_new_idxs(num_params: usize, cat_sizes: &[usize], cat_idxs: &[usize]) ->
         Some(im)
     });
 
-    // Calculate the offset to parameter idx due to newly intorduced one-hot vectors
-    let offset_ = cat_sizes.iter().scan(0, |a, &v| {
-        *a = *a + v - 1;
+    // Calculate the offset to parameter idx due to newly intorduced one-hot vectors,
+    // negative when a categorical variable is encoded by no columns at all
+    let offset_ = cat_sizes.iter().scan(0isize, |a, &v| {
+        *a = *a + v as isize - 1;
         Some(*a)
     });
     let offset = (0..1).chain(offset_);
@@ -80,7 +135,7 @@ fn find_new_idxs(num_params: usize, cat_sizes: &[usize], cat_idxs: &[usize]) ->
                 .zip(offset)
                 .flat_map(|(r, o)| iter::repeat(o).take(r)),
         )
-        .map(|(idx, ofst)| idx + ofst)
+        .map(|(idx, ofst)| (idx as isize + ofst) as usize)
         .collect();
     new_param_idxs
 }
@@ -99,6 +154,8 @@ fn validate_col_is_categorical<T: Categorizable>(data: &[T]) -> bool {
 pub struct OneHotEncoder {
     category_mappers: Vec<CategoryMapper<CategoricalFloat>>,
     col_idx_categorical: Vec<usize>,
+    drop_first: bool,
+    handle_unknown: HandleUnknown,
 }
 
 impl OneHotEncoder {
@@ -144,6 +201,8 @@ impl OneHotEncoder {
                 Ok(Self {
                     category_mappers: res,
                     col_idx_categorical: idxs,
+                    drop_first: params.drop_first,
+                    handle_unknown: params.handle_unknown,
                 })
             }
 
@@ -160,14 +219,16 @@ impl OneHotEncoder {
         M: Matrix<T>,
     {
         let (nrows, p) = x.shape();
+        let dropped = usize::from(self.drop_first);
         let additional_params: Vec<usize> = self
             .category_mappers
             .iter()
-            .map(|enc| enc.num_categories())
+            .map(|enc| enc.num_categories() - dropped)
             .collect();
 
-        // Eac category of size v adds v-1 params
-        let expandws_p: usize = p + additional_params.iter().fold(0, |cs, &v| cs + v - 1);
+        // Eac category of size v replaces one param with v params
+        let expandws_p: usize =
+            p + additional_params.iter().sum::<usize>() - additional_params.len();
 
         let new_col_idx = find_new_idxs(p, &additional_params[..], &self.col_idx_categorical[..]);
         let mut res = M::zeros(nrows, expandws_p);
@@ -179,15 +240,17 @@ impl OneHotEncoder {
             let oh_series = col_iter.map(|c| sencoder.get_one_hot::<T, Vec<T>>(&c));
 
             for (row, oh_vec) in oh_series.enumerate() {
-                match oh_vec {
-                    None => {
+                match (oh_vec, self.handle_unknown) {
+                    (None, HandleUnknown::Error) => {
                         // Since we support T types, bad value in a series causes in to be invalid
                         let msg = format!("At least one value in column {} doesn't conform to category definition", old_cidx);
                         return Err(Failed::transform(&msg[..]));
                     }
-                    Some(v) => {
+                    // unknown categories keep the zeros of the output matrix
+                    (None, HandleUnknown::Ignore) => {}
+                    (Some(v), _) => {
                         // copy one hot vectors to their place in the data matrix;
-                        for (col_ofst, &val) in v.iter().enumerate() {
+                        for (col_ofst, &val) in v.iter().skip(dropped).enumerate() {
                             res.set(row, cidx + col_ofst, val);
                         }
                     }
@@ -218,6 +281,21 @@ impl OneHotEncoder {
     }
 }
 
+// the encoder is not generic over the element type of the matrix, so the common interfaces are implemented for concrete matrix types
+impl<T: Categorizable> UnsupervisedEstimator<DenseMatrix<T>, OneHotEncoderParams>
+    for OneHotEncoder
+{
+    fn fit(x: &DenseMatrix<T>, parameters: OneHotEncoderParams) -> Result<Self, Failed> {
+        OneHotEncoder::fit(x, parameters)
+    }
+}
+
+impl<T: Categorizable> Transformer<DenseMatrix<T>> for OneHotEncoder {
+    fn transform(&self, x: &DenseMatrix<T>) -> Result<DenseMatrix<T>, Failed> {
+        self.transform(x)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -312,6 +390,54 @@ mod tests {
         assert_eq!(nm, expected_x);
     }
 
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn drop_first() {
+        let (x, _) = build_fake_matrix();
+        let params = OneHotEncoderParams::from_cat_idx(&[1, 3]).with_drop_first(true);
+        let oh_enc = OneHotEncoder::fit(&x, params).unwrap();
+        assert_eq!(
+            oh_enc.transform(&x).unwrap(),
+            DenseMatrix::from_2d_array(&[
+                &[1.5, 0.0, 1.5, 0.0, 0.0, 0.0],
+                &[1.5, 1.0, 1.5, 1.0, 0.0, 0.0],
+                &[1.5, 0.0, 1.5, 0.0, 1.0, 0.0],
+                &[1.5, 1.0, 1.5, 0.0, 0.0, 1.0],
+            ])
+        );
+
+        // a constant categorical variable is dropped entirely
+        let x = DenseMatrix::from_2d_array(&[&[1.0, 0.5], &[1.0, 1.5]]);
+        let params = OneHotEncoderParams::from_cat_idx(&[0]).with_drop_first(true);
+        let oh_enc = OneHotEncoder::fit(&x, params).unwrap();
+        assert_eq!(
+            oh_enc.transform(&x).unwrap(),
+            DenseMatrix::from_2d_array(&[&[0.5], &[1.5]])
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn handle_unknown() {
+        let (x, _) = build_cat_first_and_last();
+        let new_x = DenseMatrix::from_2d_array(&[&[3.0, 1.5, 4.0], &[1.0, 1.5, 9.0]]);
+
+        let params = OneHotEncoderParams::from_cat_idx(&[0, 2]);
+        let oh_enc = OneHotEncoder::fit(&x, params).unwrap();
+        assert!(oh_enc.transform(&new_x).is_err());
+
+        let params =
+            OneHotEncoderParams::from_cat_idx(&[0, 2]).with_handle_unknown(HandleUnknown::Ignore);
+        let oh_enc = OneHotEncoder::fit(&x, params).unwrap();
+        assert_eq!(
+            oh_enc.transform(&new_x).unwrap(),
+            DenseMatrix::from_2d_array(&[
+                &[0.0, 0.0, 1.5, 0.0, 1.0, 0.0, 0.0],
+                &[1.0, 0.0, 1.5, 0.0, 0.0, 0.0, 0.0],
+            ])
+        );
+    }
+
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     #[test]
     fn fail_on_bad_category() {
@@ -0,0 +1,486 @@
+//! # Composite Transformers
+//!
+//! Real-world datasets often mix numerical and categorical columns that need different preprocessing.
+//! `ColumnTransformer` fits a separate transformer to every subset of columns of a data [Matrix](../../linalg/trait.BaseMatrix.html)
+//! and concatenates the transformed subsets horizontally, in the order the transformers were added.
+//! Columns that are not selected by any transformer are dropped, or appended unchanged after the transformed columns
+//! when the remainder is set to [`Remainder::Passthrough`](enum.Remainder.html).
+//!
+//! `FeatureUnion` fits several transformers to all columns and concatenates their outputs, for example to combine the principal components
+//! of the data with the scaled original features.
+//!
+//! Transformers are added as functions that fit a transformer to the selected columns, so any transformer that implements
+//! [`Transformer`](../../api/trait.Transformer.html) can be used.
+//!
+//! ### Usage Example
+//! ```
+//! use smartcore::api::{Transformer, UnsupervisedEstimator};
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::preprocessing::categorical::{OneHotEncoder, OneHotEncoderParams};
+//! use smartcore::preprocessing::compose::{ColumnTransformer, ColumnTransformerParameters};
+//! use smartcore::preprocessing::numerical::{StandardScaler, StandardScalerParameters};
+//! let data = DenseMatrix::from_2d_array(&[
+//!     &[1.0, 10.0, 0.0],
+//!     &[1.0, 30.0, 1.0],
+//!     &[3.0, 30.0, 2.0],
+//!     &[3.0, 10.0, 1.0],
+//! ]);
+//!
+//! let parameters = ColumnTransformerParameters::default()
+//!     .with_transformer(&[0, 1], |x: &DenseMatrix<f64>| {
+//!         StandardScaler::fit(x, StandardScalerParameters::default())
+//!     })
+//!     .with_transformer(&[2], |x: &DenseMatrix<f64>| {
+//!         OneHotEncoder::fit(x, OneHotEncoderParams::from_cat_idx(&[0]))
+//!     });
+//! let transformer = ColumnTransformer::fit(&data, parameters).unwrap();
+//!
+//! assert_eq!(
+//!     transformer.transform(&data).unwrap(),
+//!     DenseMatrix::from_2d_array(&[
+//!         &[-1.0, -1.0, 1.0, 0.0, 0.0],
+//!         &[-1.0, 1.0, 0.0, 1.0, 0.0],
+//!         &[1.0, 1.0, 0.0, 0.0, 1.0],
+//!         &[1.0, -1.0, 0.0, 1.0, 0.0],
+//!     ])
+//! );
+//! ```
+use alloc::boxed::Box;
+#[cfg(not(feature = "parallel"))]
+use alloc::rc::Rc;
+#[cfg(feature = "parallel")]
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Transformer, UnsupervisedEstimator};
+use crate::error::{Failed, FailedError};
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::parallel::MaybeSync;
+
+/// A function that fits a transformer to a data matrix.
+#[cfg(not(feature = "parallel"))]
+pub(crate) type FitTransformer<M> = Rc<dyn Fn(&M) -> Result<Box<dyn Transformer<M>>, Failed>>;
+
+/// A function that fits a transformer to a data matrix, shared between threads.
+#[cfg(feature = "parallel")]
+pub(crate) type FitTransformer<M> =
+    Arc<dyn Fn(&M) -> Result<Box<dyn Transformer<M>>, Failed> + Send + Sync>;
+
+/// What to do with the columns that are not selected by any transformer of a `ColumnTransformer`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum Remainder {
+    #[default]
+    /// Leave the columns out of the transformed matrix
+    Drop,
+    /// Append the columns unchanged after the transformed columns
+    Passthrough,
+}
+
+/// `ColumnTransformer` parameters. Use `Default::default()` to start with no transformers.
+pub struct ColumnTransformerParameters<M> {
+    transformers: Vec<(Vec<usize>, FitTransformer<M>)>,
+    /// What to do with the columns that are not selected by any transformer.
+    pub remainder: Remainder,
+}
+
+/// `FeatureUnion` parameters. Use `Default::default()` to start with no transformers.
+pub struct FeatureUnionParameters<M> {
+    transformers: Vec<FitTransformer<M>>,
+}
+
+/// Applies a separate transformer to every subset of columns and concatenates the results.
+pub struct ColumnTransformer<T: RealNumber, M: Matrix<T>> {
+    transformers: Vec<(Vec<usize>, Box<dyn Transformer<M>>)>,
+    remainder: Vec<usize>,
+    n_features: usize,
+    phantom: PhantomData<T>,
+}
+
+/// Applies several transformers to all columns and concatenates the results.
+pub struct FeatureUnion<T: RealNumber, M: Matrix<T>> {
+    transformers: Vec<Box<dyn Transformer<M>>>,
+    phantom: PhantomData<T>,
+}
+
+/// Erases the type of the transformer returned by `fit`.
+pub(crate) fn boxed<M, E, F>(fit: F) -> FitTransformer<M>
+where
+    E: Transformer<M> + 'static,
+    F: Fn(&M) -> Result<E, Failed> + MaybeSync + 'static,
+{
+    let fit = move |x: &M| fit(x).map(|e| Box::new(e) as Box<dyn Transformer<M>>);
+    #[cfg(not(feature = "parallel"))]
+    {
+        Rc::new(fit)
+    }
+    #[cfg(feature = "parallel")]
+    {
+        Arc::new(fit)
+    }
+}
+
+impl<M> ColumnTransformerParameters<M> {
+    /// Adds a transformer of the columns with indices `columns`.
+    /// * `columns` - indices of the columns passed to the transformer, the transformer sees them in the given order.
+    /// * `fit` - a function that fits the transformer to the selected columns of the training data.
+    pub fn with_transformer<E, F>(mut self, columns: &[usize], fit: F) -> Self
+    where
+        E: Transformer<M> + 'static,
+        F: Fn(&M) -> Result<E, Failed> + MaybeSync + 'static,
+    {
+        self.transformers.push((columns.to_vec(), boxed(fit)));
+        self
+    }
+    /// What to do with the columns that are not selected by any transformer.
+    pub fn with_remainder(mut self, remainder: Remainder) -> Self {
+        self.remainder = remainder;
+        self
+    }
+}
+
+impl<M> Default for ColumnTransformerParameters<M> {
+    fn default() -> Self {
+        ColumnTransformerParameters {
+            transformers: Vec::new(),
+            remainder: Remainder::default(),
+        }
+    }
+}
+
+impl<M> Clone for ColumnTransformerParameters<M> {
+    fn clone(&self) -> Self {
+        ColumnTransformerParameters {
+            transformers: self.transformers.clone(),
+            remainder: self.remainder,
+        }
+    }
+}
+
+impl<M> fmt::Debug for ColumnTransformerParameters<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ColumnTransformerParameters")
+            .field(
+                "columns",
+                &self.transformers.iter().map(|(c, _)| c).collect::<Vec<_>>(),
+            )
+            .field("remainder", &self.remainder)
+            .finish()
+    }
+}
+
+impl<M> FeatureUnionParameters<M> {
+    /// Adds a transformer of all columns.
+    /// * `fit` - a function that fits the transformer to the training data.
+    pub fn with_transformer<E, F>(mut self, fit: F) -> Self
+    where
+        E: Transformer<M> + 'static,
+        F: Fn(&M) -> Result<E, Failed> + MaybeSync + 'static,
+    {
+        self.transformers.push(boxed(fit));
+        self
+    }
+}
+
+impl<M> Default for FeatureUnionParameters<M> {
+    fn default() -> Self {
+        FeatureUnionParameters {
+            transformers: Vec::new(),
+        }
+    }
+}
+
+impl<M> Clone for FeatureUnionParameters<M> {
+    fn clone(&self) -> Self {
+        FeatureUnionParameters {
+            transformers: self.transformers.clone(),
+        }
+    }
+}
+
+impl<M> fmt::Debug for FeatureUnionParameters<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FeatureUnionParameters")
+            .field("n_transformers", &self.transformers.len())
+            .finish()
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> ColumnTransformer<T, M> {
+    /// Fits every transformer to its columns of the training data.
+    /// * `x` - training data.
+    /// * `parameters` - the transformers and their columns.
+    pub fn fit(
+        x: &M,
+        parameters: ColumnTransformerParameters<M>,
+    ) -> Result<ColumnTransformer<T, M>, Failed> {
+        let (_, p) = x.shape();
+
+        let mut selected = vec![false; p];
+        let mut transformers = Vec::with_capacity(parameters.transformers.len());
+        for (columns, fit) in parameters.transformers.iter() {
+            if columns.is_empty() {
+                return Err(Failed::fit(
+                    "every transformer should select at least one column",
+                ));
+            }
+            if let Some(j) = columns.iter().find(|j| **j >= p) {
+                return Err(Failed::fit(&format!(
+                    "column {} is out of range, the data has {} columns",
+                    j, p
+                )));
+            }
+            for j in columns.iter() {
+                selected[*j] = true;
+            }
+            transformers.push((columns.clone(), fit(&x.take(columns, 1))?));
+        }
+
+        let remainder: Vec<usize> = match parameters.remainder {
+            Remainder::Drop => Vec::new(),
+            Remainder::Passthrough => (0..p).filter(|j| !selected[*j]).collect(),
+        };
+
+        if transformers.is_empty() && remainder.is_empty() {
+            return Err(Failed::fit(
+                "the transformed matrix would have no columns, add a transformer or pass the remainder through",
+            ));
+        }
+
+        Ok(ColumnTransformer {
+            transformers,
+            remainder,
+            n_features: p,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Transforms every subset of columns of `x` with its transformer and concatenates the results, followed by the remainder.
+    pub fn transform(&self, x: &M) -> Result<M, Failed> {
+        let (_, p) = x.shape();
+        if p != self.n_features {
+            return Err(Failed::because(
+                FailedError::TransformFailed,
+                &format!("expected {} columns, got {} columns", self.n_features, p),
+            ));
+        }
+
+        let mut parts = Vec::with_capacity(self.transformers.len() + 1);
+        for (columns, transformer) in self.transformers.iter() {
+            parts.push(transformer.transform(&x.take(columns, 1))?);
+        }
+        if !self.remainder.is_empty() {
+            parts.push(x.take(&self.remainder, 1));
+        }
+
+        Ok(h_stack_all(parts))
+    }
+
+    /// Indices of the columns that are appended unchanged after the transformed columns.
+    pub fn remainder(&self) -> &Vec<usize> {
+        &self.remainder
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> FeatureUnion<T, M> {
+    /// Fits every transformer to the training data.
+    /// * `x` - training data.
+    /// * `parameters` - the transformers.
+    pub fn fit(x: &M, parameters: FeatureUnionParameters<M>) -> Result<FeatureUnion<T, M>, Failed> {
+        if parameters.transformers.is_empty() {
+            return Err(Failed::fit(
+                "a feature union needs at least one transformer",
+            ));
+        }
+
+        let transformers = parameters
+            .transformers
+            .iter()
+            .map(|fit| fit(x))
+            .collect::<Result<Vec<_>, Failed>>()?;
+
+        Ok(FeatureUnion {
+            transformers,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Transforms `x` with every transformer and concatenates the results.
+    pub fn transform(&self, x: &M) -> Result<M, Failed> {
+        let parts = self
+            .transformers
+            .iter()
+            .map(|transformer| transformer.transform(x))
+            .collect::<Result<Vec<_>, Failed>>()?;
+
+        Ok(h_stack_all(parts))
+    }
+}
+
+/// Concatenates non-empty list of matrices horizontally.
+fn h_stack_all<T: RealNumber, M: Matrix<T>>(parts: Vec<M>) -> M {
+    let mut parts = parts.into_iter();
+    let first = parts.next().unwrap();
+    parts.fold(first, |result, part| result.h_stack(&part))
+}
+
+impl<T: RealNumber, M: Matrix<T>> fmt::Debug for ColumnTransformer<T, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ColumnTransformer")
+            .field(
+                "columns",
+                &self.transformers.iter().map(|(c, _)| c).collect::<Vec<_>>(),
+            )
+            .field("remainder", &self.remainder)
+            .field("n_features", &self.n_features)
+            .finish()
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> fmt::Debug for FeatureUnion<T, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FeatureUnion")
+            .field("n_transformers", &self.transformers.len())
+            .finish()
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> UnsupervisedEstimator<M, ColumnTransformerParameters<M>>
+    for ColumnTransformer<T, M>
+{
+    fn fit(x: &M, parameters: ColumnTransformerParameters<M>) -> Result<Self, Failed> {
+        ColumnTransformer::fit(x, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Transformer<M> for ColumnTransformer<T, M> {
+    fn transform(&self, x: &M) -> Result<M, Failed> {
+        self.transform(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> UnsupervisedEstimator<M, FeatureUnionParameters<M>>
+    for FeatureUnion<T, M>
+{
+    fn fit(x: &M, parameters: FeatureUnionParameters<M>) -> Result<Self, Failed> {
+        FeatureUnion::fit(x, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Transformer<M> for FeatureUnion<T, M> {
+    fn transform(&self, x: &M) -> Result<M, Failed> {
+        self.transform(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decomposition::pca::{PCAParameters, PCA};
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::linalg::BaseMatrix;
+    use crate::preprocessing::numerical::{MinMaxScaler, MinMaxScalerParameters};
+
+    fn data() -> DenseMatrix<f64> {
+        DenseMatrix::from_2d_array(&[
+            &[1.0, 10.0, 5.0, 0.5],
+            &[2.0, 30.0, 6.0, 1.5],
+            &[3.0, 20.0, 7.0, 2.5],
+        ])
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn column_transformer_remainder() {
+        let x = data();
+        let parameters = ColumnTransformerParameters::default()
+            .with_transformer(&[1, 0], |x: &DenseMatrix<f64>| {
+                MinMaxScaler::fit(x, MinMaxScalerParameters::default())
+            });
+
+        let dropped = ColumnTransformer::fit(&x, parameters.clone()).unwrap();
+        assert!(dropped.remainder().is_empty());
+        assert_eq!(
+            dropped.transform(&x).unwrap(),
+            DenseMatrix::from_2d_array(&[&[0.0, 0.0], &[1.0, 0.5], &[0.5, 1.0]])
+        );
+
+        let passed =
+            ColumnTransformer::fit(&x, parameters.with_remainder(Remainder::Passthrough)).unwrap();
+        assert_eq!(passed.remainder(), &vec![2, 3]);
+        assert_eq!(
+            passed.transform(&x).unwrap(),
+            DenseMatrix::from_2d_array(&[
+                &[0.0, 0.0, 5.0, 0.5],
+                &[1.0, 0.5, 6.0, 1.5],
+                &[0.5, 1.0, 7.0, 2.5]
+            ])
+        );
+
+        // the transformers keep the statistics of the training data
+        let new = DenseMatrix::from_2d_array(&[&[5.0, 40.0, 0.0, 0.0]]);
+        assert_eq!(
+            passed.transform(&new).unwrap(),
+            DenseMatrix::from_2d_array(&[&[1.5, 2.0, 0.0, 0.0]])
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn column_transformer_invalid_input() {
+        let x = data();
+        assert!(ColumnTransformer::fit(&x, ColumnTransformerParameters::default()).is_err());
+
+        let out_of_range = ColumnTransformerParameters::default()
+            .with_transformer(&[4], |x: &DenseMatrix<f64>| {
+                MinMaxScaler::fit(x, MinMaxScalerParameters::default())
+            });
+        assert!(ColumnTransformer::fit(&x, out_of_range).is_err());
+
+        let transformer = ColumnTransformer::fit(
+            &x,
+            ColumnTransformerParameters::default().with_remainder(Remainder::Passthrough),
+        )
+        .unwrap();
+        assert_eq!(transformer.transform(&x).unwrap(), x);
+        assert!(transformer
+            .transform(&DenseMatrix::from_2d_array(&[&[1.0, 2.0]]))
+            .is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn feature_union() {
+        let x = data();
+        let union = FeatureUnion::fit(
+            &x,
+            FeatureUnionParameters::default()
+                .with_transformer(|x: &DenseMatrix<f64>| {
+                    PCA::fit(x, PCAParameters::default().with_n_components(1))
+                })
+                .with_transformer(|x: &DenseMatrix<f64>| {
+                    MinMaxScaler::fit(x, MinMaxScalerParameters::default())
+                }),
+        )
+        .unwrap();
+
+        let transformed = union.transform(&x).unwrap();
+        assert_eq!(transformed.shape(), (3, 5));
+        assert_eq!(
+            transformed.slice(0..3, 1..5),
+            DenseMatrix::from_2d_array(&[
+                &[0.0, 0.0, 0.0, 0.0],
+                &[0.5, 1.0, 0.5, 0.5],
+                &[1.0, 0.5, 1.0, 1.0]
+            ])
+        );
+
+        assert!(FeatureUnion::fit(&x, FeatureUnionParameters::default()).is_err());
+    }
+}
@@ -0,0 +1,565 @@
+//! # Discretization of Numerical Features
+//!
+//! Some models, like naive Bayes classifiers with categorical features or linear models that should capture non-linear effects,
+//! work better with discrete inputs. The `KBinsDiscretizer` splits the range of every column of a data [Matrix](../../linalg/trait.BaseMatrix.html)
+//! into bins and replaces every value with the bin it falls into. The edges of the bins are learned from the data by one of three strategies:
+//!
+//! * `Uniform`, all bins of a column have the same width,
+//! * `Quantile`, all bins of a column hold approximately the same number of values,
+//! * `KMeans`, values of a column are clustered by one-dimensional k-means and the bins are formed around the clusters.
+//!
+//! Bins are encoded either by their index (`Ordinal`) or by indicator columns, one per bin (`OneHot`). Values outside of the range seen during `fit`
+//! fall into the first or the last bin. Bins that would be empty because of repeated edges are removed, so a column might get fewer bins than requested.
+//!
+//! ```
+//! use smartcore::api::{Transformer, UnsupervisedEstimator};
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::preprocessing::discretization::{
+//!     BinEncoding, BinningStrategy, KBinsDiscretizer, KBinsDiscretizerParameters,
+//! };
+//! let data = DenseMatrix::from_2d_array(&[
+//!     &[0.0], &[1.0], &[2.0], &[3.0], &[4.0], &[5.0], &[6.0], &[7.0],
+//! ]);
+//!
+//! let discretizer = KBinsDiscretizer::fit(
+//!     &data,
+//!     KBinsDiscretizerParameters::default()
+//!         .with_n_bins(4)
+//!         .with_strategy(BinningStrategy::Uniform)
+//!         .with_encode(BinEncoding::Ordinal),
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     discretizer.transform(&data).unwrap(),
+//!     DenseMatrix::from_2d_array(&[
+//!         &[0.0], &[0.0], &[1.0], &[1.0], &[2.0], &[2.0], &[3.0], &[3.0],
+//!     ])
+//! );
+//! ```
+//!
+//! The `Binarizer` is the simplest discretization: values above a threshold become one, all other values become zero.
+//! ```
+//! use smartcore::api::Transformer;
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::preprocessing::discretization::Binarizer;
+//! let data = DenseMatrix::from_2d_array(&[&[-1.0, 0.5], &[2.0, 0.0]]);
+//!
+//! assert_eq!(
+//!     Binarizer::new(0.0).transform(&data).unwrap(),
+//!     DenseMatrix::from_2d_array(&[&[0.0, 1.0], &[1.0, 0.0]])
+//! );
+//! ```
+use crate::api::{Transformer, UnsupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+use crate::preprocessing::numerical::{check_n_columns, quantile};
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of iterations of one-dimensional k-means.
+const KMEANS_MAX_ITER: usize = 100;
+
+/// How the edges of the bins are placed.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, Copy, Eq, PartialEq)]
+pub enum BinningStrategy {
+    /// All bins of a column have the same width.
+    Uniform,
+    #[default]
+    /// All bins of a column hold approximately the same number of values.
+    Quantile,
+    /// Bins are formed around the clusters found by one-dimensional k-means.
+    KMeans,
+}
+
+/// How the bins are encoded in the transformed matrix.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, Copy, Eq, PartialEq)]
+pub enum BinEncoding {
+    /// Every value is replaced by the index of its bin.
+    Ordinal,
+    #[default]
+    /// Every column is replaced by indicator columns, one per bin.
+    OneHot,
+}
+
+/// Configure Behaviour of `KBinsDiscretizer`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Copy, Eq, PartialEq)]
+pub struct KBinsDiscretizerParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of bins per column, at least 2.
+    pub n_bins: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// How the edges of the bins are placed.
+    pub strategy: BinningStrategy,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// How the bins are encoded in the transformed matrix.
+    pub encode: BinEncoding,
+}
+
+impl KBinsDiscretizerParameters {
+    /// Number of bins per column, at least 2.
+    pub fn with_n_bins(mut self, n_bins: usize) -> Self {
+        self.n_bins = n_bins;
+        self
+    }
+    /// How the edges of the bins are placed.
+    pub fn with_strategy(mut self, strategy: BinningStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+    /// How the bins are encoded in the transformed matrix.
+    pub fn with_encode(mut self, encode: BinEncoding) -> Self {
+        self.encode = encode;
+        self
+    }
+}
+
+impl Default for KBinsDiscretizerParameters {
+    fn default() -> Self {
+        KBinsDiscretizerParameters {
+            n_bins: 5,
+            strategy: BinningStrategy::default(),
+            encode: BinEncoding::default(),
+        }
+    }
+}
+
+/// Splits every column into bins learned from the data and
+/// replaces the values with their bins.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct KBinsDiscretizer<T: RealNumber> {
+    bin_edges: Vec<Vec<T>>,
+    encode: BinEncoding,
+}
+
+impl<T: RealNumber> KBinsDiscretizer<T> {
+    /// Edges of the bins of every column, from the minimum to the maximum seen during `fit`.
+    pub fn bin_edges(&self) -> &Vec<Vec<T>> {
+        &self.bin_edges
+    }
+
+    /// Number of bins of every column.
+    pub fn n_bins(&self) -> Vec<usize> {
+        self.bin_edges.iter().map(|edges| edges.len() - 1).collect()
+    }
+
+    /// Index of the bin `value` falls into, values outside of the edges fall into the first or the last bin.
+    fn bin(edges: &[T], value: T) -> usize {
+        edges[1..edges.len() - 1]
+            .iter()
+            .filter(|&&edge| value >= edge)
+            .count()
+    }
+
+    /// Replaces every bin with the center of the bin.
+    pub fn inverse_transform<M: Matrix<T>>(&self, x: &M) -> Result<M, Failed> {
+        let (n_rows, n_cols) = x.shape();
+        let expected = match self.encode {
+            BinEncoding::Ordinal => self.bin_edges.len(),
+            BinEncoding::OneHot => self.n_bins().iter().sum(),
+        };
+        check_n_columns(expected, n_cols)?;
+
+        let mut result = M::zeros(n_rows, self.bin_edges.len());
+        for row_index in 0..n_rows {
+            let mut offset = 0;
+            for (column_index, edges) in self.bin_edges.iter().enumerate() {
+                let n_bins = edges.len() - 1;
+                let bin = match self.encode {
+                    BinEncoding::Ordinal => x
+                        .get(row_index, column_index)
+                        .round()
+                        .max(T::zero())
+                        .to_usize()
+                        .unwrap()
+                        .min(n_bins - 1),
+                    BinEncoding::OneHot => (0..n_bins).fold(0, |best, bin| {
+                        if x.get(row_index, offset + bin) > x.get(row_index, offset + best) {
+                            bin
+                        } else {
+                            best
+                        }
+                    }),
+                };
+                offset += n_bins;
+                result.set(
+                    row_index,
+                    column_index,
+                    (edges[bin] + edges[bin + 1]) / T::two(),
+                );
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Edges of `n_bins` bins of a column of sorted values.
+fn column_bin_edges<T: RealNumber>(
+    sorted: &[T],
+    n_bins: usize,
+    strategy: BinningStrategy,
+) -> Vec<T> {
+    let min = sorted[0];
+    let max = sorted[sorted.len() - 1];
+    let n = T::from_usize(n_bins).unwrap();
+
+    let edges: Vec<T> = match strategy {
+        BinningStrategy::Uniform => (0..=n_bins)
+            .map(|i| min + (max - min) * T::from_usize(i).unwrap() / n)
+            .collect(),
+        BinningStrategy::Quantile => (0..=n_bins)
+            .map(|i| quantile(sorted, T::from_usize(i).unwrap() / n))
+            .collect(),
+        BinningStrategy::KMeans => {
+            // centers start in the middle of uniform bins, so the result is deterministic
+            let mut centers: Vec<T> = (0..n_bins)
+                .map(|i| min + (max - min) * (T::from_usize(i).unwrap() + T::half()) / n)
+                .collect();
+            for _ in 0..KMEANS_MAX_ITER {
+                let mut sums = vec![T::zero(); n_bins];
+                let mut counts = vec![0; n_bins];
+                for &value in sorted {
+                    let nearest = (0..n_bins).fold(0, |best, c| {
+                        if (value - centers[c]).abs() < (value - centers[best]).abs() {
+                            c
+                        } else {
+                            best
+                        }
+                    });
+                    sums[nearest] += value;
+                    counts[nearest] += 1;
+                }
+                let mut converged = true;
+                for c in 0..n_bins {
+                    // empty clusters keep their center
+                    if counts[c] > 0 {
+                        let center = sums[c] / T::from_usize(counts[c]).unwrap();
+                        converged &= center == centers[c];
+                        centers[c] = center;
+                    }
+                }
+                if converged {
+                    break;
+                }
+            }
+            centers.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            let mut edges = vec![min];
+            edges.extend(centers.windows(2).map(|w| (w[0] + w[1]) / T::two()));
+            edges.push(max);
+            edges
+        }
+    };
+
+    // repeated edges would leave empty bins
+    let mut unique = vec![edges[0]];
+    for &edge in edges.iter().skip(1) {
+        if edge > unique[unique.len() - 1] {
+            unique.push(edge);
+        }
+    }
+    if unique.len() < 2 {
+        // a constant column has a single bin
+        unique.push(max);
+    }
+    unique
+}
+
+impl<T: RealNumber, M: Matrix<T>> UnsupervisedEstimator<M, KBinsDiscretizerParameters>
+    for KBinsDiscretizer<T>
+{
+    fn fit(x: &M, parameters: KBinsDiscretizerParameters) -> Result<Self, Failed> {
+        let (n_rows, n_cols) = x.shape();
+        if parameters.n_bins < 2 {
+            return Err(Failed::fit(&format!(
+                "n_bins should be at least 2, got {}",
+                parameters.n_bins
+            )));
+        }
+        if n_rows == 0 {
+            return Err(Failed::fit("Cannot discretize a matrix without rows."));
+        }
+
+        let mut bin_edges = Vec::with_capacity(n_cols);
+        for column_index in 0..n_cols {
+            let mut column = x.get_col_as_vec(column_index);
+            if column.iter().any(|value| !value.is_finite()) {
+                return Err(Failed::fit(&format!(
+                    "Column {} contains values that are not finite.",
+                    column_index
+                )));
+            }
+            column.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            bin_edges.push(column_bin_edges(
+                &column,
+                parameters.n_bins,
+                parameters.strategy,
+            ));
+        }
+
+        Ok(KBinsDiscretizer {
+            bin_edges,
+            encode: parameters.encode,
+        })
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Transformer<M> for KBinsDiscretizer<T> {
+    fn transform(&self, x: &M) -> Result<M, Failed> {
+        let (n_rows, n_cols) = x.shape();
+        check_n_columns(self.bin_edges.len(), n_cols)?;
+
+        match self.encode {
+            BinEncoding::Ordinal => {
+                let mut result = M::zeros(n_rows, n_cols);
+                for row_index in 0..n_rows {
+                    for (column_index, edges) in self.bin_edges.iter().enumerate() {
+                        let bin = Self::bin(edges, x.get(row_index, column_index));
+                        result.set(row_index, column_index, T::from_usize(bin).unwrap());
+                    }
+                }
+                Ok(result)
+            }
+            BinEncoding::OneHot => {
+                let mut result = M::zeros(n_rows, self.n_bins().iter().sum());
+                for row_index in 0..n_rows {
+                    let mut offset = 0;
+                    for (column_index, edges) in self.bin_edges.iter().enumerate() {
+                        let bin = Self::bin(edges, x.get(row_index, column_index));
+                        result.set(row_index, offset + bin, T::one());
+                        offset += edges.len() - 1;
+                    }
+                }
+                Ok(result)
+            }
+        }
+    }
+}
+
+/// Configure Behaviour of `Binarizer`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub struct BinarizerParameters<T: RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Values above the threshold become one, all other values become zero.
+    pub threshold: T,
+}
+
+impl<T: RealNumber> BinarizerParameters<T> {
+    /// Values above the threshold become one, all other values become zero.
+    pub fn with_threshold(mut self, threshold: T) -> Self {
+        self.threshold = threshold;
+        self
+    }
+}
+
+impl<T: RealNumber> Default for BinarizerParameters<T> {
+    fn default() -> Self {
+        BinarizerParameters {
+            threshold: T::zero(),
+        }
+    }
+}
+
+/// With the `Binarizer` values above a threshold become one and all other values become zero.
+/// Every value is transformed independently from the others, so `fit` doesn't learn anything from the data.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Binarizer<T: RealNumber> {
+    threshold: T,
+}
+
+impl<T: RealNumber> Default for Binarizer<T> {
+    fn default() -> Self {
+        Binarizer::new(T::zero())
+    }
+}
+
+impl<T: RealNumber> Binarizer<T> {
+    /// Creates a binarizer with the given threshold.
+    pub fn new(threshold: T) -> Self {
+        Binarizer { threshold }
+    }
+
+    /// Values above the threshold become one, all other values become zero.
+    pub fn threshold(&self) -> T {
+        self.threshold
+    }
+}
+
+/// The `Binarizer` is stateless, `fit` only records the threshold.
+impl<T: RealNumber, M: Matrix<T>> UnsupervisedEstimator<M, BinarizerParameters<T>>
+    for Binarizer<T>
+{
+    fn fit(_: &M, parameters: BinarizerParameters<T>) -> Result<Self, Failed> {
+        Ok(Binarizer::new(parameters.threshold))
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Transformer<M> for Binarizer<T> {
+    fn transform(&self, x: &M) -> Result<M, Failed> {
+        let (n_rows, n_cols) = x.shape();
+        let mut result = M::zeros(n_rows, n_cols);
+        for row_index in 0..n_rows {
+            for column_index in 0..n_cols {
+                if x.get(row_index, column_index) > self.threshold {
+                    result.set(row_index, column_index, T::one());
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+
+    fn fit(
+        x: &DenseMatrix<f64>,
+        strategy: BinningStrategy,
+        encode: BinEncoding,
+    ) -> KBinsDiscretizer<f64> {
+        KBinsDiscretizer::fit(
+            x,
+            KBinsDiscretizerParameters::default()
+                .with_n_bins(3)
+                .with_strategy(strategy)
+                .with_encode(encode),
+        )
+        .unwrap()
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn uniform_bins() {
+        let x = DenseMatrix::from_2d_array(&[&[0.0, 5.0], &[1.0, 5.0], &[5.0, 5.0], &[6.0, 5.0]]);
+        let discretizer = fit(&x, BinningStrategy::Uniform, BinEncoding::Ordinal);
+        assert_eq!(
+            discretizer.bin_edges(),
+            &vec![vec![0.0, 2.0, 4.0, 6.0], vec![5.0, 5.0]]
+        );
+        assert_eq!(discretizer.n_bins(), vec![3, 1]);
+        assert_eq!(
+            discretizer.transform(&x).unwrap(),
+            DenseMatrix::from_2d_array(&[&[0.0, 0.0], &[0.0, 0.0], &[2.0, 0.0], &[2.0, 0.0]])
+        );
+        // values outside of the fitted range fall into the outer bins
+        assert_eq!(
+            discretizer
+                .transform(&DenseMatrix::from_2d_array(&[
+                    &[-3.0, 1.0],
+                    &[3.0, 9.0],
+                    &[7.0, 5.0]
+                ]))
+                .unwrap(),
+            DenseMatrix::from_2d_array(&[&[0.0, 0.0], &[1.0, 0.0], &[2.0, 0.0]])
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn quantile_bins() {
+        let x =
+            DenseMatrix::from_2d_array(&[&[1.0], &[2.0], &[3.0], &[4.0], &[5.0], &[6.0], &[100.0]]);
+        let discretizer = fit(&x, BinningStrategy::Quantile, BinEncoding::Ordinal);
+        assert_eq!(discretizer.bin_edges(), &vec![vec![1.0, 3.0, 5.0, 100.0]]);
+        assert_eq!(
+            discretizer.transform(&x).unwrap(),
+            DenseMatrix::from_2d_array(&[&[0.0], &[0.0], &[1.0], &[1.0], &[2.0], &[2.0], &[2.0]])
+        );
+
+        // repeated quantiles are merged
+        let x = DenseMatrix::from_2d_array(&[&[0.0], &[0.0], &[0.0], &[0.0], &[1.0]]);
+        let discretizer = fit(&x, BinningStrategy::Quantile, BinEncoding::Ordinal);
+        assert_eq!(discretizer.bin_edges(), &vec![vec![0.0, 1.0]]);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn kmeans_bins() {
+        let x = DenseMatrix::from_2d_array(&[&[0.0], &[1.0], &[2.0], &[10.0], &[11.0], &[30.0]]);
+        let discretizer = fit(&x, BinningStrategy::KMeans, BinEncoding::Ordinal);
+        assert_eq!(discretizer.bin_edges(), &vec![vec![0.0, 5.75, 20.25, 30.0]]);
+        assert_eq!(
+            discretizer.transform(&x).unwrap(),
+            DenseMatrix::from_2d_array(&[&[0.0], &[0.0], &[0.0], &[1.0], &[1.0], &[2.0]])
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn one_hot_encoding() {
+        let x = DenseMatrix::from_2d_array(&[&[0.0, 1.0], &[3.0, 1.0], &[6.0, 2.0]]);
+        let discretizer = fit(&x, BinningStrategy::Uniform, BinEncoding::OneHot);
+        let encoded = discretizer.transform(&x).unwrap();
+        assert_eq!(
+            encoded,
+            DenseMatrix::from_2d_array(&[
+                &[1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+                &[0.0, 1.0, 0.0, 1.0, 0.0, 0.0],
+                &[0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+            ])
+        );
+        assert_eq!(
+            discretizer.inverse_transform(&encoded).unwrap(),
+            DenseMatrix::from_2d_array(&[&[1.0, 7.0 / 6.0], &[3.0, 7.0 / 6.0], &[5.0, 11.0 / 6.0]])
+        );
+
+        let discretizer = fit(&x, BinningStrategy::Uniform, BinEncoding::Ordinal);
+        assert_eq!(
+            discretizer
+                .inverse_transform(&discretizer.transform(&x).unwrap())
+                .unwrap(),
+            DenseMatrix::from_2d_array(&[&[1.0, 7.0 / 6.0], &[3.0, 7.0 / 6.0], &[5.0, 11.0 / 6.0]])
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn invalid_input() {
+        let x = DenseMatrix::from_2d_array(&[&[0.0, 1.0], &[3.0, f64::NAN]]);
+        assert!(KBinsDiscretizer::<f64>::fit(&x, KBinsDiscretizerParameters::default()).is_err());
+        let x = DenseMatrix::from_2d_array(&[&[0.0], &[3.0]]);
+        assert!(KBinsDiscretizer::<f64>::fit(
+            &x,
+            KBinsDiscretizerParameters::default().with_n_bins(1)
+        )
+        .is_err());
+        let discretizer = fit(&x, BinningStrategy::Uniform, BinEncoding::Ordinal);
+        assert!(discretizer
+            .transform(&DenseMatrix::from_2d_array(&[&[0.0, 1.0]]))
+            .is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn binarizer() {
+        let x = DenseMatrix::from_2d_array(&[&[-1.0, 0.5, 2.0], &[1.0, 0.0, 3.0]]);
+        let binarizer =
+            Binarizer::fit(&x, BinarizerParameters::default().with_threshold(1.0)).unwrap();
+        assert_eq!(binarizer.threshold(), 1.0);
+        assert_eq!(
+            binarizer.transform(&x).unwrap(),
+            DenseMatrix::from_2d_array(&[&[0.0, 0.0, 1.0], &[0.0, 0.0, 1.0]])
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let x = DenseMatrix::from_2d_array(&[&[0.0, 1.0], &[3.0, 1.0], &[6.0, 2.0]]);
+        let discretizer = fit(&x, BinningStrategy::Quantile, BinEncoding::OneHot);
+        let deserialized: KBinsDiscretizer<f64> =
+            serde_json::from_str(&serde_json::to_string(&discretizer).unwrap()).unwrap();
+        assert_eq!(discretizer, deserialized);
+    }
+}
@@ -0,0 +1,555 @@
+//! # Imputation of Missing Values
+//!
+//! Many estimators cannot handle missing values, marked as `NaN` in a data [Matrix](../../linalg/trait.BaseMatrix.html).
+//! `KNNImputer` replaces every missing value with the average value of the same feature in the `k` nearest training rows that have the feature.
+//! Distances between rows are measured with the [NanEuclidian](../../math/distance/nan_euclidian/index.html) distance, which ignores coordinates
+//! where either row has a missing value. Unlike filling in the mean of a column, the imputed values take into account the other features of the row.
+//!
+//! When none of the training rows that have the feature shares a present coordinate with the row, the mean of the feature is used instead.
+//!
+//! ### Usage Example
+//! ```
+//! use smartcore::api::{Transformer, UnsupervisedEstimator};
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::preprocessing::impute::{KNNImputer, KNNImputerParameters};
+//! let data = DenseMatrix::from_2d_array(&[
+//!     &[1.0, 2.0, f64::NAN],
+//!     &[3.0, 4.0, 3.0],
+//!     &[f64::NAN, 6.0, 5.0],
+//!     &[8.0, 9.0, 7.0],
+//! ]);
+//!
+//! let imputer = KNNImputer::fit(&data, KNNImputerParameters::default().with_k(2)).unwrap();
+//! let imputed = imputer.transform(&data).unwrap();
+//! assert_eq!(
+//!     imputed,
+//!     DenseMatrix::from_2d_array(&[
+//!         &[1.0, 2.0, 4.0],
+//!         &[3.0, 4.0, 3.0],
+//!         &[5.5, 6.0, 5.0],
+//!         &[8.0, 9.0, 7.0],
+//!     ])
+//! );
+//! ```
+//!
+//! Missing values often carry information themselves, for example when a measurement is only taken for some kinds of observations.
+//! `MissingIndicator` emits a binary column for every feature that had missing values during `fit`, with ones in the rows where the value is missing.
+//! An imputer fitted with `add_indicator` appends these columns to the imputed matrix, so a model can learn from both the imputed values and the pattern of missingness.
+//!
+//! ```
+//! use smartcore::api::{Transformer, UnsupervisedEstimator};
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::preprocessing::impute::{KNNImputer, KNNImputerParameters, MissingIndicator};
+//! let data = DenseMatrix::from_2d_array(&[
+//!     &[1.0, 2.0, f64::NAN],
+//!     &[3.0, 4.0, 3.0],
+//!     &[f64::NAN, 6.0, 5.0],
+//!     &[8.0, 9.0, 7.0],
+//! ]);
+//!
+//! let indicator = MissingIndicator::fit(&data, Default::default()).unwrap();
+//! assert_eq!(indicator.features(), &vec![0, 2]);
+//! assert_eq!(
+//!     indicator.transform(&data).unwrap(),
+//!     DenseMatrix::from_2d_array(&[&[0.0, 1.0], &[0.0, 0.0], &[1.0, 0.0], &[0.0, 0.0]])
+//! );
+//!
+//! let imputer = KNNImputer::fit(
+//!     &data,
+//!     KNNImputerParameters::default().with_k(2).with_add_indicator(true),
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     imputer.transform(&data).unwrap(),
+//!     DenseMatrix::from_2d_array(&[
+//!         &[1.0, 2.0, 4.0, 0.0, 1.0],
+//!         &[3.0, 4.0, 3.0, 0.0, 0.0],
+//!         &[5.5, 6.0, 5.0, 1.0, 0.0],
+//!         &[8.0, 9.0, 7.0, 0.0, 0.0],
+//!     ])
+//! );
+//! ```
+//!
+//! ## References:
+//! * ["Missing value estimation methods for DNA microarrays", Troyanskaya O. et al., Bioinformatics, 2001](https://doi.org/10.1093/bioinformatics/17.6.520)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::algorithm::neighbour::{KNNAlgorithm, KNNAlgorithmName};
+use crate::api::{Transformer, UnsupervisedEstimator};
+use crate::error::{Failed, FailedError};
+use crate::linalg::Matrix;
+use crate::math::distance::nan_euclidian::NanEuclidian;
+use crate::math::num::RealNumber;
+use crate::neighbors::KNNWeightFunction;
+
+/// `KNNImputer` parameters. Use `Default::default()` for default values.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct KNNImputerParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// number of training rows used to impute a missing value. Default value is 5.
+    pub k: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// weighting function of the neighbours. Default function is `KNNWeightFunction::Uniform`.
+    pub weight: KNNWeightFunction,
+    #[cfg_attr(feature = "serde", serde(default = "default_algorithm"))]
+    /// backend search algorithm. See [`knn search algorithms`](../../algorithm/neighbour/index.html). `LinearSearch` is default,
+    /// `CoverTree` relies on the triangle inequality that does not hold for rows with missing values and might miss some neighbours.
+    pub algorithm: KNNAlgorithmName,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// append the columns of a [`MissingIndicator`] fitted to the training data to the imputed matrix. Default value is `false`.
+    pub add_indicator: bool,
+}
+
+#[cfg(feature = "serde")]
+fn default_algorithm() -> KNNAlgorithmName {
+    KNNAlgorithmName::LinearSearch
+}
+
+impl KNNImputerParameters {
+    /// number of training rows used to impute a missing value. Default value is 5.
+    pub fn with_k(mut self, k: usize) -> Self {
+        self.k = k;
+        self
+    }
+    /// weighting function of the neighbours. Default function is `KNNWeightFunction::Uniform`.
+    pub fn with_weight(mut self, weight: KNNWeightFunction) -> Self {
+        self.weight = weight;
+        self
+    }
+    /// backend search algorithm. See [`knn search algorithms`](../../algorithm/neighbour/index.html). `LinearSearch` is default.
+    pub fn with_algorithm(mut self, algorithm: KNNAlgorithmName) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+    /// append the columns of a [`MissingIndicator`] fitted to the training data to the imputed matrix. Default value is `false`.
+    pub fn with_add_indicator(mut self, add_indicator: bool) -> Self {
+        self.add_indicator = add_indicator;
+        self
+    }
+}
+
+impl Default for KNNImputerParameters {
+    fn default() -> Self {
+        KNNImputerParameters {
+            k: 5,
+            weight: KNNWeightFunction::default(),
+            algorithm: KNNAlgorithmName::LinearSearch,
+            add_indicator: false,
+        }
+    }
+}
+
+/// Imputes missing values with the values of the nearest training rows.
+#[derive(Debug)]
+pub struct KNNImputer<T: RealNumber, M: Matrix<T>> {
+    /// search structure over the training rows that have the feature, one per feature
+    donors: Vec<KNNAlgorithm<T, NanEuclidian>>,
+    /// number of training rows that have the feature
+    n_donors: Vec<usize>,
+    means: Vec<T>,
+    k: usize,
+    weight: KNNWeightFunction,
+    indicator: Option<MissingIndicator<T>>,
+    phantom: PhantomData<M>,
+}
+
+impl<T: RealNumber, M: Matrix<T>> KNNImputer<T, M> {
+    /// Learns the training rows that will be used to impute missing values.
+    /// * `x` - training data, missing values are marked as `NaN`.
+    /// * `parameters` - imputer parameters.
+    pub fn fit(x: &M, parameters: KNNImputerParameters) -> Result<KNNImputer<T, M>, Failed> {
+        let (n, p) = x.shape();
+
+        if parameters.k < 1 {
+            return Err(Failed::fit(&format!(
+                "k should be > 0, k = {}",
+                parameters.k
+            )));
+        }
+
+        let rows: Vec<Vec<T>> = (0..n).map(|i| x.get_row_as_vec(i)).collect();
+
+        let mut donors = Vec::with_capacity(p);
+        let mut n_donors = Vec::with_capacity(p);
+        let mut means = Vec::with_capacity(p);
+        for j in 0..p {
+            let data: Vec<Vec<T>> = rows
+                .iter()
+                .filter(|row| !row[j].is_nan())
+                .cloned()
+                .collect();
+            if data.is_empty() {
+                return Err(Failed::fit(&format!(
+                    "column {} has no values that are not missing",
+                    j
+                )));
+            }
+            let mean =
+                data.iter().map(|row| row[j]).sum::<T>() / T::from_usize(data.len()).unwrap();
+            n_donors.push(data.len());
+            means.push(mean);
+            donors.push(parameters.algorithm.fit(data, NanEuclidian {})?);
+        }
+
+        let indicator = if parameters.add_indicator {
+            Some(MissingIndicator::fit(
+                x,
+                MissingIndicatorParameters::default(),
+            )?)
+        } else {
+            None
+        };
+
+        Ok(KNNImputer {
+            donors,
+            n_donors,
+            means,
+            k: parameters.k,
+            weight: parameters.weight,
+            indicator,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Replaces missing values in `x` with the values of the nearest training rows.
+    /// When the imputer was fitted with `add_indicator`, the columns of the missing indicator are appended to the result.
+    pub fn transform(&self, x: &M) -> Result<M, Failed> {
+        let (n, p) = x.shape();
+        if p != self.donors.len() {
+            return Err(Failed::because(
+                FailedError::TransformFailed,
+                &format!("expected {} columns, got {} columns", self.donors.len(), p),
+            ));
+        }
+
+        let mut result = x.clone();
+        for i in 0..n {
+            let row = x.get_row_as_vec(i);
+            for j in 0..p {
+                if row[j].is_nan() {
+                    result.set(i, j, self.impute(&row, j)?);
+                }
+            }
+        }
+
+        match &self.indicator {
+            Some(indicator) if !indicator.features().is_empty() => {
+                Ok(result.h_stack(&indicator.transform(x)?))
+            }
+            _ => Ok(result),
+        }
+    }
+
+    /// The missing indicator fitted to the training data when the imputer was fitted with `add_indicator`.
+    pub fn indicator(&self) -> Option<&MissingIndicator<T>> {
+        self.indicator.as_ref()
+    }
+
+    fn impute(&self, row: &Vec<T>, j: usize) -> Result<T, Failed> {
+        let k = self.k.min(self.n_donors[j]);
+        // donors that share no present coordinate with the row are infinitely far and carry no information
+        let neighbours: Vec<(T, T)> = self.donors[j]
+            .find(row, k)?
+            .into_iter()
+            .filter(|(_, d, _)| d.is_finite())
+            .map(|(_, d, donor)| (d, donor[j]))
+            .collect();
+
+        if neighbours.is_empty() {
+            return Ok(self.means[j]);
+        }
+
+        let weights = self
+            .weight
+            .calc_weights(neighbours.iter().map(|(d, _)| *d).collect());
+        let w_sum = weights.iter().copied().sum::<T>();
+
+        Ok(neighbours
+            .iter()
+            .zip(weights.iter())
+            .map(|((_, v), w)| *v * *w)
+            .sum::<T>()
+            / w_sum)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> UnsupervisedEstimator<M, KNNImputerParameters>
+    for KNNImputer<T, M>
+{
+    fn fit(x: &M, parameters: KNNImputerParameters) -> Result<Self, Failed> {
+        KNNImputer::fit(x, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Transformer<M> for KNNImputer<T, M> {
+    fn transform(&self, x: &M) -> Result<M, Failed> {
+        self.transform(x)
+    }
+}
+
+/// Features that get a column in the output of [`MissingIndicator`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum MissingIndicatorFeatures {
+    #[default]
+    /// only the features that have missing values in the training data
+    MissingOnly,
+    /// all features
+    All,
+}
+
+/// `MissingIndicator` parameters. Use `Default::default()` for default values.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct MissingIndicatorParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// features that get an indicator column. Default value is `MissingIndicatorFeatures::MissingOnly`.
+    pub features: MissingIndicatorFeatures,
+}
+
+impl MissingIndicatorParameters {
+    /// features that get an indicator column. Default value is `MissingIndicatorFeatures::MissingOnly`.
+    pub fn with_features(mut self, features: MissingIndicatorFeatures) -> Self {
+        self.features = features;
+        self
+    }
+}
+
+/// Marks missing values with binary indicator columns.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingIndicator<T: RealNumber> {
+    features: Vec<usize>,
+    n_features: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<T: RealNumber> MissingIndicator<T> {
+    /// Learns the features that get an indicator column.
+    /// * `x` - training data, missing values are marked as `NaN`.
+    /// * `parameters` - indicator parameters.
+    pub fn fit<M: Matrix<T>>(
+        x: &M,
+        parameters: MissingIndicatorParameters,
+    ) -> Result<MissingIndicator<T>, Failed> {
+        let (n, p) = x.shape();
+        let features = match parameters.features {
+            MissingIndicatorFeatures::MissingOnly => (0..p)
+                .filter(|j| (0..n).any(|i| x.get(i, *j).is_nan()))
+                .collect(),
+            MissingIndicatorFeatures::All => (0..p).collect(),
+        };
+
+        Ok(MissingIndicator {
+            features,
+            n_features: p,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Returns a matrix with one column per indicated feature, with ones where the value in `x` is missing and zeros elsewhere.
+    pub fn transform<M: Matrix<T>>(&self, x: &M) -> Result<M, Failed> {
+        let (n, p) = x.shape();
+        if p != self.n_features {
+            return Err(Failed::because(
+                FailedError::TransformFailed,
+                &format!("expected {} columns, got {} columns", self.n_features, p),
+            ));
+        }
+
+        let mut result = M::zeros(n, self.features.len());
+        for (c, j) in self.features.iter().enumerate() {
+            for i in 0..n {
+                if x.get(i, *j).is_nan() {
+                    result.set(i, c, T::one());
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Indices of the features that get an indicator column, in the order of the columns.
+    pub fn features(&self) -> &Vec<usize> {
+        &self.features
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> UnsupervisedEstimator<M, MissingIndicatorParameters>
+    for MissingIndicator<T>
+{
+    fn fit(x: &M, parameters: MissingIndicatorParameters) -> Result<Self, Failed> {
+        MissingIndicator::fit(x, parameters)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> Transformer<M> for MissingIndicator<T> {
+    fn transform(&self, x: &M) -> Result<M, Failed> {
+        self.transform(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::linalg::BaseMatrix;
+
+    fn data() -> DenseMatrix<f64> {
+        DenseMatrix::from_2d_array(&[
+            &[1.0, 2.0, f64::NAN],
+            &[3.0, 4.0, 3.0],
+            &[f64::NAN, 6.0, 5.0],
+            &[8.0, 9.0, 7.0],
+        ])
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn knn_imputer() {
+        let x = data();
+        let imputer = KNNImputer::fit(&x, KNNImputerParameters::default().with_k(1)).unwrap();
+        let expected = DenseMatrix::from_2d_array(&[
+            &[1.0, 2.0, 3.0],
+            &[3.0, 4.0, 3.0],
+            &[3.0, 6.0, 5.0],
+            &[8.0, 9.0, 7.0],
+        ]);
+        assert_eq!(imputer.transform(&x).unwrap(), expected);
+
+        let imputer = KNNImputer::fit(
+            &x,
+            KNNImputerParameters::default()
+                .with_k(1)
+                .with_algorithm(KNNAlgorithmName::CoverTree),
+        )
+        .unwrap();
+        assert_eq!(imputer.transform(&x).unwrap(), expected);
+
+        // k is larger than the number of rows, all donors are used
+        let imputer = KNNImputer::fit(&x, KNNImputerParameters::default()).unwrap();
+        let imputed = imputer.transform(&x).unwrap();
+        assert!((imputed.get(0, 2) - 5.0).abs() < 1e-8);
+        assert!((imputed.get(2, 0) - 4.0).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn distance_weights() {
+        let x = DenseMatrix::from_2d_array(&[&[0.0, 0.0], &[1.0, 10.0], &[3.0, 30.0]]);
+        let imputer = KNNImputer::fit(
+            &x,
+            KNNImputerParameters::default()
+                .with_k(2)
+                .with_weight(KNNWeightFunction::Distance),
+        )
+        .unwrap();
+        let imputed = imputer
+            .transform(&DenseMatrix::from_2d_array(&[&[2.0, f64::NAN]]))
+            .unwrap();
+        assert!((imputed.get(0, 1) - 20.0).abs() < 1e-8);
+
+        let imputed = imputer
+            .transform(&DenseMatrix::from_2d_array(&[&[1.0, f64::NAN]]))
+            .unwrap();
+        assert!((imputed.get(0, 1) - 10.0).abs() < 1e-8);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn mean_fallback() {
+        let x = data();
+        let imputer = KNNImputer::fit(&x, KNNImputerParameters::default().with_k(1)).unwrap();
+        let imputed = imputer
+            .transform(&DenseMatrix::from_2d_array(&[&[
+                f64::NAN,
+                f64::NAN,
+                f64::NAN,
+            ]]))
+            .unwrap();
+        assert_eq!(imputed, DenseMatrix::from_2d_array(&[&[4.0, 5.25, 5.0]]));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn invalid_input() {
+        let x = data();
+        assert!(KNNImputer::fit(&x, KNNImputerParameters::default().with_k(0)).is_err());
+
+        let empty_column = DenseMatrix::from_2d_array(&[&[1.0, f64::NAN], &[2.0, f64::NAN]]);
+        assert!(KNNImputer::fit(&empty_column, KNNImputerParameters::default()).is_err());
+
+        let imputer = KNNImputer::fit(&x, KNNImputerParameters::default()).unwrap();
+        assert!(imputer
+            .transform(&DenseMatrix::from_2d_array(&[&[1.0, 2.0]]))
+            .is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn missing_indicator() {
+        let x = data();
+        let indicator = MissingIndicator::fit(&x, Default::default()).unwrap();
+        assert_eq!(indicator.features(), &vec![0, 2]);
+
+        // missing values of features without an indicator column are ignored
+        let new = DenseMatrix::from_2d_array(&[&[f64::NAN, f64::NAN, 1.0], &[1.0, 2.0, f64::NAN]]);
+        assert_eq!(
+            indicator.transform(&new).unwrap(),
+            DenseMatrix::from_2d_array(&[&[1.0, 0.0], &[0.0, 1.0]])
+        );
+
+        let indicator = MissingIndicator::fit(
+            &x,
+            MissingIndicatorParameters::default().with_features(MissingIndicatorFeatures::All),
+        )
+        .unwrap();
+        assert_eq!(
+            indicator.transform(&new).unwrap(),
+            DenseMatrix::from_2d_array(&[&[1.0, 1.0, 0.0], &[0.0, 0.0, 1.0]])
+        );
+        assert!(indicator
+            .transform(&DenseMatrix::from_2d_array(&[&[1.0, 2.0]]))
+            .is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn knn_imputer_add_indicator() {
+        let x = data();
+        let imputer = KNNImputer::fit(
+            &x,
+            KNNImputerParameters::default()
+                .with_k(1)
+                .with_add_indicator(true),
+        )
+        .unwrap();
+        assert_eq!(imputer.indicator().unwrap().features(), &vec![0, 2]);
+        assert_eq!(
+            imputer.transform(&x).unwrap(),
+            DenseMatrix::from_2d_array(&[
+                &[1.0, 2.0, 3.0, 0.0, 1.0],
+                &[3.0, 4.0, 3.0, 0.0, 0.0],
+                &[3.0, 6.0, 5.0, 1.0, 0.0],
+                &[8.0, 9.0, 7.0, 0.0, 0.0],
+            ])
+        );
+
+        // without missing training values there is nothing to indicate
+        let complete = DenseMatrix::from_2d_array(&[&[1.0, 2.0], &[3.0, 4.0]]);
+        let imputer = KNNImputer::fit(
+            &complete,
+            KNNImputerParameters::default().with_add_indicator(true),
+        )
+        .unwrap();
+        assert_eq!(imputer.transform(&complete).unwrap(), complete);
+    }
+}
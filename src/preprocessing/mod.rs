@@ -1,7 +1,16 @@
 /// Transform a data matrix by replacing all categorical variables with their one-hot vector equivalents
+#[cfg(feature = "std")]
 pub mod categorical;
+/// Apply different transformers to subsets of columns and concatenate the results.
+pub mod compose;
+#[cfg(feature = "std")]
 mod data_traits;
+/// Discretize numerical features into bins.
+pub mod discretization;
+/// Impute missing values of numerical matrices.
+pub mod impute;
 /// Preprocess numerical matrices.
 pub mod numerical;
 /// Encode a series (column, array) of categorical variables as one-hot vectors
+#[cfg(feature = "std")]
 pub mod series_encoder;
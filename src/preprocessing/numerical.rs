@@ -27,10 +27,90 @@
 //!     ])
 //! );
 //! ```
+//!
+//! The `MinMaxScaler` maps every column linearly to a range, \([0, 1]\) by default, which suits models that expect bounded inputs.
+//! The minimum of every column seen during `fit` is mapped to the lower and the maximum to the upper end of the range.
+//! ```
+//! use smartcore::api::{Transformer, UnsupervisedEstimator};
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::preprocessing::numerical::{MinMaxScaler, MinMaxScalerParameters};
+//! let data = DenseMatrix::from_2d_array(&[
+//!     &[1.0, 10.0],
+//!     &[2.0, 20.0],
+//!     &[3.0, 40.0],
+//! ]);
+//!
+//! let scaler = MinMaxScaler::fit(&data, MinMaxScalerParameters::default().with_feature_range(-1.0, 1.0))
+//!     .unwrap();
+//! let transformed_data = scaler.transform(&data).unwrap();
+//! assert_eq!(
+//!     transformed_data,
+//!     DenseMatrix::from_2d_array(&[
+//!         &[-1.0, -1.0],
+//!         &[0.0, -1.0 / 3.0],
+//!         &[1.0, 1.0],
+//!     ])
+//! );
+//! assert_eq!(scaler.inverse_transform(&transformed_data).unwrap(), data);
+//! ```
+//!
+//! The `RobustScaler` centers every column by its median and scales it by its interquartile range instead of the mean and the standard deviation.
+//! Both statistics are insensitive to a few extreme values, so the scaling of datasets with heavy outliers isn't dominated by the outliers.
+//! ```
+//! use smartcore::api::{Transformer, UnsupervisedEstimator};
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::preprocessing::numerical::{RobustScaler, RobustScalerParameters};
+//! let data = DenseMatrix::from_2d_array(&[
+//!     &[1.0],
+//!     &[2.0],
+//!     &[3.0],
+//!     &[4.0],
+//!     &[1000.0],
+//! ]);
+//!
+//! let scaler = RobustScaler::fit(&data, RobustScalerParameters::default()).unwrap();
+//! assert_eq!(
+//!     scaler.transform(&data).unwrap(),
+//!     DenseMatrix::from_2d_array(&[
+//!         &[-1.0],
+//!         &[-0.5],
+//!         &[0.0],
+//!         &[0.5],
+//!         &[498.5],
+//!     ])
+//! );
+//! ```
+//!
+//! The `Normalizer` scales every row, rather than every column, to unit norm. After L2 normalization the dot product
+//! of two rows is their cosine similarity, which is useful for text data and nearest neighbors search with cosine similarity.
+//! ```
+//! use smartcore::api::{Transformer, UnsupervisedEstimator};
+//! use smartcore::linalg::naive::dense_matrix::DenseMatrix;
+//! use smartcore::preprocessing::numerical::{Normalizer, NormalizerNorm, NormalizerParameters};
+//! let data = DenseMatrix::from_2d_array(&[
+//!     &[3.0, 4.0],
+//!     &[1.0, -1.0],
+//! ]);
+//!
+//! let normalizer = Normalizer::fit(&data, NormalizerParameters::default().with_norm(NormalizerNorm::L1))
+//!     .unwrap();
+//! assert_eq!(
+//!     normalizer.transform(&data).unwrap(),
+//!     DenseMatrix::from_2d_array(&[
+//!         &[3.0 / 7.0, 4.0 / 7.0],
+//!         &[0.5, -0.5],
+//!     ])
+//! );
+//! ```
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
 use crate::api::{Transformer, UnsupervisedEstimator};
 use crate::error::{Failed, FailedError};
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
+#[cfg(feature = "onnx")]
+use crate::onnx::{self, ToOnnx};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -140,6 +220,453 @@ impl<T: RealNumber, M: Matrix<T>> Transformer<M> for StandardScaler<T> {
     }
 }
 
+#[cfg(feature = "onnx")]
+impl<T: RealNumber> ToOnnx for StandardScaler<T> {
+    fn to_onnx(&self) -> Result<Vec<u8>, Failed> {
+        Ok(onnx::scaler(
+            self.means
+                .iter()
+                .map(|mean| self.adjust_column_mean(*mean))
+                .collect(),
+            self.stds
+                .iter()
+                .map(|std| T::one() / self.adjust_column_std(*std))
+                .collect(),
+        ))
+    }
+}
+
+/// Configure Behaviour of `MinMaxScaler`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub struct MinMaxScalerParameters<T: RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Lower and upper bound of the transformed data.
+    pub feature_range: (T, T),
+}
+
+impl<T: RealNumber> MinMaxScalerParameters<T> {
+    /// Lower and upper bound of the transformed data.
+    pub fn with_feature_range(mut self, min: T, max: T) -> Self {
+        self.feature_range = (min, max);
+        self
+    }
+}
+
+impl<T: RealNumber> Default for MinMaxScalerParameters<T> {
+    fn default() -> Self {
+        MinMaxScalerParameters {
+            feature_range: (T::zero(), T::one()),
+        }
+    }
+}
+
+/// With the `MinMaxScaler` every column is mapped linearly
+/// to `feature_range`. Columns that are constant during `fit`
+/// are shifted to the lower bound of the range without scaling,
+/// instead of being divided by zero.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinMaxScaler<T: RealNumber> {
+    data_min: Vec<T>,
+    data_max: Vec<T>,
+    feature_range: (T, T),
+}
+
+impl<T: RealNumber> MinMaxScaler<T> {
+    /// Minimum of every column seen during `fit`.
+    pub fn data_min(&self) -> &Vec<T> {
+        &self.data_min
+    }
+
+    /// Maximum of every column seen during `fit`.
+    pub fn data_max(&self) -> &Vec<T> {
+        &self.data_max
+    }
+
+    /// Factor every column is multiplied by.
+    fn column_scale(&self, column_index: usize) -> T {
+        let data_range = self.data_max[column_index] - self.data_min[column_index];
+        let data_range = if data_range > T::zero() {
+            data_range
+        } else {
+            T::one()
+        };
+        (self.feature_range.1 - self.feature_range.0) / data_range
+    }
+
+    /// Maps transformed data back to the original range of every column.
+    pub fn inverse_transform<M: Matrix<T>>(&self, x: &M) -> Result<M, Failed> {
+        let (n_rows, n_cols) = x.shape();
+        check_n_columns(self.data_min.len(), n_cols)?;
+
+        let mut result = x.clone();
+        for column_index in 0..n_cols {
+            let scale = self.column_scale(column_index);
+            for row_index in 0..n_rows {
+                result.set(
+                    row_index,
+                    column_index,
+                    (x.get(row_index, column_index) - self.feature_range.0) / scale
+                        + self.data_min[column_index],
+                );
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// During `fit` the `MinMaxScaler` computes the minimum and maximum of every column.
+impl<T: RealNumber, M: Matrix<T>> UnsupervisedEstimator<M, MinMaxScalerParameters<T>>
+    for MinMaxScaler<T>
+{
+    fn fit(x: &M, parameters: MinMaxScalerParameters<T>) -> Result<Self, Failed> {
+        let (n_rows, n_cols) = x.shape();
+        if parameters.feature_range.0 >= parameters.feature_range.1 {
+            return Err(Failed::fit(&format!(
+                "Minimum of the feature range {} should be smaller than its maximum {}.",
+                parameters.feature_range.0, parameters.feature_range.1
+            )));
+        }
+        if n_rows == 0 {
+            return Err(Failed::fit("Can't fit a MinMaxScaler to an empty matrix."));
+        }
+
+        let mut data_min = vec![T::infinity(); n_cols];
+        let mut data_max = vec![T::neg_infinity(); n_cols];
+        for row_index in 0..n_rows {
+            for column_index in 0..n_cols {
+                let value = x.get(row_index, column_index);
+                data_min[column_index] = data_min[column_index].min(value);
+                data_max[column_index] = data_max[column_index].max(value);
+            }
+        }
+
+        Ok(Self {
+            data_min,
+            data_max,
+            feature_range: parameters.feature_range,
+        })
+    }
+}
+
+/// During `transform` the `MinMaxScaler` maps the minimum and maximum
+/// computed during `fit` to the bounds of the feature range. Values outside
+/// of the range seen during `fit` are mapped outside of the feature range.
+impl<T: RealNumber, M: Matrix<T>> Transformer<M> for MinMaxScaler<T> {
+    fn transform(&self, x: &M) -> Result<M, Failed> {
+        let (n_rows, n_cols) = x.shape();
+        check_n_columns(self.data_min.len(), n_cols)?;
+
+        let mut result = x.clone();
+        for column_index in 0..n_cols {
+            let scale = self.column_scale(column_index);
+            for row_index in 0..n_rows {
+                result.set(
+                    row_index,
+                    column_index,
+                    (x.get(row_index, column_index) - self.data_min[column_index]) * scale
+                        + self.feature_range.0,
+                );
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "onnx")]
+impl<T: RealNumber> ToOnnx for MinMaxScaler<T> {
+    fn to_onnx(&self) -> Result<Vec<u8>, Failed> {
+        let scale: Vec<T> = (0..self.data_min.len())
+            .map(|column_index| self.column_scale(column_index))
+            .collect();
+        let offset = self
+            .data_min
+            .iter()
+            .zip(scale.iter())
+            .map(|(min, scale)| *min - self.feature_range.0 / *scale)
+            .collect();
+        Ok(onnx::scaler(offset, scale))
+    }
+}
+
+/// Configure Behaviour of `RobustScaler`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub struct RobustScalerParameters<T: RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Subtract the median of every column.
+    pub with_centering: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Divide every column by the range between the quantiles in `quantile_range`.
+    pub with_scaling: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Lower and upper quantile in percent used to calculate the scale, the interquartile range (25, 75) by default.
+    pub quantile_range: (T, T),
+}
+
+impl<T: RealNumber> RobustScalerParameters<T> {
+    /// Subtract the median of every column.
+    pub fn with_centering(mut self, with_centering: bool) -> Self {
+        self.with_centering = with_centering;
+        self
+    }
+    /// Divide every column by the range between the quantiles in `quantile_range`.
+    pub fn with_scaling(mut self, with_scaling: bool) -> Self {
+        self.with_scaling = with_scaling;
+        self
+    }
+    /// Lower and upper quantile in percent used to calculate the scale.
+    pub fn with_quantile_range(mut self, lower: T, upper: T) -> Self {
+        self.quantile_range = (lower, upper);
+        self
+    }
+}
+
+impl<T: RealNumber> Default for RobustScalerParameters<T> {
+    fn default() -> Self {
+        RobustScalerParameters {
+            with_centering: true,
+            with_scaling: true,
+            quantile_range: (T::from_f64(25.0).unwrap(), T::from_f64(75.0).unwrap()),
+        }
+    }
+}
+
+/// With the `RobustScaler` every column is centered by its median
+/// and divided by its interquartile range. Columns with an
+/// interquartile range of zero are not scaled.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RobustScaler<T: RealNumber> {
+    centers: Vec<T>,
+    scales: Vec<T>,
+}
+
+impl<T: RealNumber> RobustScaler<T> {
+    /// Value subtracted from every column, the median or zero when centering is disabled.
+    pub fn centers(&self) -> &Vec<T> {
+        &self.centers
+    }
+
+    /// Value every column is divided by, the interquartile range or one when scaling is disabled.
+    pub fn scales(&self) -> &Vec<T> {
+        &self.scales
+    }
+
+    /// Maps transformed data back to the original location and scale of every column.
+    pub fn inverse_transform<M: Matrix<T>>(&self, x: &M) -> Result<M, Failed> {
+        let (n_rows, n_cols) = x.shape();
+        check_n_columns(self.centers.len(), n_cols)?;
+
+        let mut result = x.clone();
+        for column_index in 0..n_cols {
+            for row_index in 0..n_rows {
+                result.set(
+                    row_index,
+                    column_index,
+                    x.get(row_index, column_index) * self.scales[column_index]
+                        + self.centers[column_index],
+                );
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// During `fit` the `RobustScaler` computes the median and the interquartile range of every column.
+impl<T: RealNumber, M: Matrix<T>> UnsupervisedEstimator<M, RobustScalerParameters<T>>
+    for RobustScaler<T>
+{
+    fn fit(x: &M, parameters: RobustScalerParameters<T>) -> Result<Self, Failed> {
+        let (n_rows, n_cols) = x.shape();
+        let (lower, upper) = parameters.quantile_range;
+        if lower < T::zero() || upper > T::from_f64(100.0).unwrap() || lower >= upper {
+            return Err(Failed::fit(&format!(
+                "Invalid quantile range ({}, {}), should satisfy 0 <= lower < upper <= 100.",
+                lower, upper
+            )));
+        }
+        if n_rows == 0 {
+            return Err(Failed::fit("Can't fit a RobustScaler to an empty matrix."));
+        }
+
+        let mut centers = vec![T::zero(); n_cols];
+        let mut scales = vec![T::one(); n_cols];
+        for column_index in 0..n_cols {
+            let mut column = x.get_col_as_vec(column_index);
+            column.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            if parameters.with_centering {
+                centers[column_index] = quantile(&column, T::half());
+            }
+            if parameters.with_scaling {
+                let percent = T::from_f64(100.0).unwrap();
+                let range = quantile(&column, upper / percent) - quantile(&column, lower / percent);
+                if range > T::zero() {
+                    scales[column_index] = range;
+                }
+            }
+        }
+
+        Ok(Self { centers, scales })
+    }
+}
+
+/// During `transform` the `RobustScaler` subtracts the median
+/// and divides by the interquartile range computed during `fit`.
+impl<T: RealNumber, M: Matrix<T>> Transformer<M> for RobustScaler<T> {
+    fn transform(&self, x: &M) -> Result<M, Failed> {
+        let (n_rows, n_cols) = x.shape();
+        check_n_columns(self.centers.len(), n_cols)?;
+
+        let mut result = x.clone();
+        for column_index in 0..n_cols {
+            for row_index in 0..n_rows {
+                result.set(
+                    row_index,
+                    column_index,
+                    (x.get(row_index, column_index) - self.centers[column_index])
+                        / self.scales[column_index],
+                );
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "onnx")]
+impl<T: RealNumber> ToOnnx for RobustScaler<T> {
+    fn to_onnx(&self) -> Result<Vec<u8>, Failed> {
+        Ok(onnx::scaler(
+            self.centers.clone(),
+            self.scales.iter().map(|scale| T::one() / *scale).collect(),
+        ))
+    }
+}
+
+/// Norm every row is divided by.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, Copy, Eq, PartialEq)]
+pub enum NormalizerNorm {
+    /// Sum of absolute values.
+    L1,
+    #[default]
+    /// Euclidean norm.
+    L2,
+    /// Largest absolute value.
+    Max,
+}
+
+/// Configure Behaviour of `Normalizer`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, Copy, Eq, PartialEq)]
+pub struct NormalizerParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Norm every row is divided by.
+    pub norm: NormalizerNorm,
+}
+
+impl NormalizerParameters {
+    /// Norm every row is divided by.
+    pub fn with_norm(mut self, norm: NormalizerNorm) -> Self {
+        self.norm = norm;
+        self
+    }
+}
+
+/// With the `Normalizer` every row is divided by its norm.
+/// Every row is normalized independently from the others,
+/// so `fit` doesn't learn anything from the data. Rows that
+/// contain zeros only are left unchanged.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Normalizer<T: RealNumber> {
+    norm: NormalizerNorm,
+    phantom: PhantomData<T>,
+}
+
+impl<T: RealNumber> Default for Normalizer<T> {
+    fn default() -> Self {
+        Normalizer::new(NormalizerNorm::default())
+    }
+}
+
+impl<T: RealNumber> Normalizer<T> {
+    /// Creates a normalizer that divides every row by `norm`.
+    pub fn new(norm: NormalizerNorm) -> Self {
+        Normalizer {
+            norm,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Norm every row is divided by.
+    pub fn norm(&self) -> NormalizerNorm {
+        self.norm
+    }
+}
+
+/// The `Normalizer` is stateless, `fit` only records the norm.
+impl<T: RealNumber, M: Matrix<T>> UnsupervisedEstimator<M, NormalizerParameters> for Normalizer<T> {
+    fn fit(_: &M, parameters: NormalizerParameters) -> Result<Self, Failed> {
+        Ok(Normalizer::new(parameters.norm))
+    }
+}
+
+/// During `transform` the `Normalizer` divides every row by its norm.
+impl<T: RealNumber, M: Matrix<T>> Transformer<M> for Normalizer<T> {
+    fn transform(&self, x: &M) -> Result<M, Failed> {
+        let (n_rows, n_cols) = x.shape();
+        let mut result = x.clone();
+        for row_index in 0..n_rows {
+            let row = (0..n_cols).map(|column_index| x.get(row_index, column_index));
+            let norm = match self.norm {
+                NormalizerNorm::L1 => row.fold(T::zero(), |norm, value| norm + value.abs()),
+                NormalizerNorm::L2 => row
+                    .fold(T::zero(), |norm, value| norm + value * value)
+                    .sqrt(),
+                NormalizerNorm::Max => row.fold(T::zero(), |norm, value| norm.max(value.abs())),
+            };
+            if norm > T::zero() {
+                for column_index in 0..n_cols {
+                    result.set(
+                        row_index,
+                        column_index,
+                        x.get(row_index, column_index) / norm,
+                    );
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Quantile `q` in [0, 1] of sorted values, linearly interpolated between the two closest values.
+pub(super) fn quantile<T: RealNumber>(sorted: &[T], q: T) -> T {
+    let position = q * T::from_usize(sorted.len() - 1).unwrap();
+    let below = position.floor();
+    let index = below.to_usize().unwrap();
+    if index + 1 >= sorted.len() {
+        sorted[sorted.len() - 1]
+    } else {
+        sorted[index] + (sorted[index + 1] - sorted[index]) * (position - below)
+    }
+}
+
+/// Make sure the data has as many columns as the data the transformer was fitted to.
+pub(super) fn check_n_columns(expected: usize, n_cols: usize) -> Result<(), Failed> {
+    if n_cols != expected {
+        return Err(Failed::because(
+            FailedError::TransformFailed,
+            &format!(
+                "Expected {} columns, but got {} columns instead.",
+                expected, n_cols,
+            ),
+        ));
+    }
+    Ok(())
+}
+
 /// From a collection of matrices, that contain columns, construct
 /// a matrix by stacking the columns horizontally.
 fn build_matrix_from_columns<T, M>(columns: Vec<M>) -> Option<M>
@@ -444,4 +971,258 @@ mod tests {
             )
         }
     }
+    mod min_max_scaler {
+        use super::super::{MinMaxScaler, MinMaxScalerParameters};
+        use crate::api::{Transformer, UnsupervisedEstimator};
+        use crate::linalg::naive::dense_matrix::DenseMatrix;
+        use crate::linalg::BaseMatrix;
+
+        #[test]
+        fn fit_transform() {
+            let data = DenseMatrix::from_2d_array(&[
+                &[0.1004222429, 0.2194113576, 0.9310663354],
+                &[0.2045493861, 0.1683865411, 0.5071506765],
+                &[0.5708488802, 0.1846414616, 0.9590802982],
+                &[0.8387612750, 0.5754861361, 0.5537109852],
+            ]);
+            let scaler = MinMaxScaler::fit(&data, MinMaxScalerParameters::default()).unwrap();
+
+            assert_eq!(
+                scaler.data_min(),
+                &vec![0.1004222429, 0.1683865411, 0.5071506765]
+            );
+            assert_eq!(
+                scaler.data_max(),
+                &vec![0.8387612750, 0.5754861361, 0.9590802982]
+            );
+            assert!(scaler.transform(&data).unwrap().approximate_eq(
+                &DenseMatrix::from_2d_array(&[
+                    &[0.0, 0.1253374288, 0.9380125545],
+                    &[0.1410289023, 0.0, 0.0],
+                    &[0.6371417694, 0.0399286089, 1.0],
+                    &[1.0, 1.0, 0.1030255740],
+                ]),
+                1e-9
+            ));
+        }
+
+        #[test]
+        fn inverse_transform() {
+            let data = DenseMatrix::from_2d_array(&[&[1.0, -3.0], &[5.0, 2.0], &[2.0, 7.0]]);
+            let scaler = MinMaxScaler::fit(
+                &data,
+                MinMaxScalerParameters::default().with_feature_range(-2.0, 3.0),
+            )
+            .unwrap();
+
+            let transformed = scaler.transform(&data).unwrap();
+            assert_eq!(transformed.get(1, 0), 3.0);
+            assert_eq!(transformed.get(0, 1), -2.0);
+            assert!(scaler
+                .inverse_transform(&transformed)
+                .unwrap()
+                .approximate_eq(&data, 1e-12));
+        }
+
+        /// Constant columns are mapped to the lower bound of the range and can be mapped back.
+        #[test]
+        fn constant_column() {
+            let data = DenseMatrix::from_2d_array(&[&[4.0, 1.0], &[4.0, 2.0], &[4.0, 3.0]]);
+            let scaler = MinMaxScaler::fit(&data, MinMaxScalerParameters::default()).unwrap();
+
+            let transformed = scaler.transform(&data).unwrap();
+            assert_eq!(
+                transformed,
+                DenseMatrix::from_2d_array(&[&[0.0, 0.0], &[0.0, 0.5], &[0.0, 1.0]])
+            );
+            assert_eq!(scaler.inverse_transform(&transformed).unwrap(), data);
+        }
+
+        #[test]
+        fn errors() {
+            let data = DenseMatrix::from_2d_array(&[&[1.0, 2.0], &[3.0, 4.0]]);
+
+            assert!(MinMaxScaler::fit(
+                &data,
+                MinMaxScalerParameters::default().with_feature_range(1.0, 1.0)
+            )
+            .is_err());
+
+            let scaler = MinMaxScaler::fit(&data, MinMaxScalerParameters::default()).unwrap();
+            let wrong_shape = DenseMatrix::from_2d_array(&[&[1.0, 2.0, 3.0]]);
+            assert!(scaler.transform(&wrong_shape).is_err());
+            assert!(scaler.inverse_transform(&wrong_shape).is_err());
+        }
+
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+        #[test]
+        #[cfg(feature = "serde")]
+        fn serde() {
+            let data = DenseMatrix::from_2d_array(&[&[1.0, -3.0], &[5.0, 2.0], &[2.0, 7.0]]);
+            let scaler: MinMaxScaler<f64> =
+                MinMaxScaler::fit(&data, MinMaxScalerParameters::default()).unwrap();
+
+            let deserialized_scaler: MinMaxScaler<f64> =
+                serde_json::from_str(&serde_json::to_string(&scaler).unwrap()).unwrap();
+
+            assert_eq!(scaler, deserialized_scaler);
+        }
+    }
+    mod robust_scaler {
+        use super::super::{quantile, RobustScaler, RobustScalerParameters};
+        use crate::api::{Transformer, UnsupervisedEstimator};
+        use crate::linalg::naive::dense_matrix::DenseMatrix;
+        use crate::linalg::BaseMatrix;
+
+        #[test]
+        fn interpolated_quantiles() {
+            let values = [1.0, 2.0, 4.0, 8.0];
+            assert_eq!(quantile(&values, 0.0), 1.0);
+            assert_eq!(quantile(&values, 0.5), 3.0);
+            assert_eq!(quantile(&values, 0.75), 5.0);
+            assert_eq!(quantile(&values, 1.0), 8.0);
+        }
+
+        #[test]
+        fn fit_transform() {
+            let data = DenseMatrix::from_2d_array(&[
+                &[1.0, -2.0, 5.0],
+                &[2.0, 0.0, 5.0],
+                &[3.0, 10.0, 5.0],
+                &[4.0, 4.0, 5.0],
+                &[100.0, 6.0, 5.0],
+            ]);
+            let scaler = RobustScaler::fit(&data, RobustScalerParameters::default()).unwrap();
+
+            assert_eq!(scaler.centers(), &vec![3.0, 4.0, 5.0]);
+            assert_eq!(scaler.scales(), &vec![2.0, 6.0, 1.0]);
+            assert!(scaler.transform(&data).unwrap().approximate_eq(
+                &DenseMatrix::from_2d_array(&[
+                    &[-1.0, -1.0, 0.0],
+                    &[-0.5, -2.0 / 3.0, 0.0],
+                    &[0.0, 1.0, 0.0],
+                    &[0.5, 0.0, 0.0],
+                    &[48.5, 1.0 / 3.0, 0.0],
+                ]),
+                1e-12
+            ));
+
+            let transformed = scaler.transform(&data).unwrap();
+            assert!(scaler
+                .inverse_transform(&transformed)
+                .unwrap()
+                .approximate_eq(&data, 1e-12));
+        }
+
+        #[test]
+        fn without_centering_and_custom_range() {
+            let data = DenseMatrix::from_2d_array(&[&[0.0], &[10.0], &[20.0], &[30.0], &[40.0]]);
+            let scaler = RobustScaler::fit(
+                &data,
+                RobustScalerParameters::default()
+                    .with_centering(false)
+                    .with_quantile_range(0.0, 100.0),
+            )
+            .unwrap();
+
+            assert_eq!(scaler.centers(), &vec![0.0]);
+            assert_eq!(scaler.scales(), &vec![40.0]);
+
+            let unscaled =
+                RobustScaler::fit(&data, RobustScalerParameters::default().with_scaling(false))
+                    .unwrap();
+            assert_eq!(unscaled.scales(), &vec![1.0]);
+        }
+
+        #[test]
+        fn errors() {
+            let data = DenseMatrix::from_2d_array(&[&[1.0, 2.0], &[3.0, 4.0]]);
+
+            assert!(RobustScaler::fit(
+                &data,
+                RobustScalerParameters::default().with_quantile_range(75.0, 25.0)
+            )
+            .is_err());
+            assert!(RobustScaler::fit(
+                &data,
+                RobustScalerParameters::default().with_quantile_range(0.0, 101.0)
+            )
+            .is_err());
+
+            let scaler = RobustScaler::fit(&data, RobustScalerParameters::default()).unwrap();
+            assert!(scaler
+                .transform(&DenseMatrix::from_2d_array(&[&[1.0]]))
+                .is_err());
+        }
+
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+        #[test]
+        #[cfg(feature = "serde")]
+        fn serde() {
+            let data = DenseMatrix::from_2d_array(&[&[1.0, -3.0], &[5.0, 2.0], &[2.0, 7.0]]);
+            let scaler: RobustScaler<f64> =
+                RobustScaler::fit(&data, RobustScalerParameters::default()).unwrap();
+
+            let deserialized_scaler: RobustScaler<f64> =
+                serde_json::from_str(&serde_json::to_string(&scaler).unwrap()).unwrap();
+
+            assert_eq!(scaler, deserialized_scaler);
+        }
+    }
+    mod normalizer {
+        use super::super::{Normalizer, NormalizerNorm, NormalizerParameters};
+        use crate::api::{Transformer, UnsupervisedEstimator};
+        use crate::linalg::naive::dense_matrix::DenseMatrix;
+        use crate::linalg::BaseMatrix;
+
+        #[test]
+        fn norms() {
+            let data = DenseMatrix::from_2d_array(&[
+                &[3.0, -4.0, 0.0],
+                &[0.0, 0.0, 0.0],
+                &[1.0, 2.0, 2.0],
+            ]);
+
+            let transform = |norm| {
+                Normalizer::fit(&data, NormalizerParameters::default().with_norm(norm))
+                    .unwrap()
+                    .transform(&data)
+                    .unwrap()
+            };
+
+            assert!(transform(NormalizerNorm::L2).approximate_eq(
+                &DenseMatrix::from_2d_array(&[
+                    &[0.6, -0.8, 0.0],
+                    &[0.0, 0.0, 0.0],
+                    &[1.0 / 3.0, 2.0 / 3.0, 2.0 / 3.0]
+                ]),
+                1e-12
+            ));
+            assert!(transform(NormalizerNorm::L1).approximate_eq(
+                &DenseMatrix::from_2d_array(&[
+                    &[3.0 / 7.0, -4.0 / 7.0, 0.0],
+                    &[0.0, 0.0, 0.0],
+                    &[0.2, 0.4, 0.4]
+                ]),
+                1e-12
+            ));
+            assert!(transform(NormalizerNorm::Max).approximate_eq(
+                &DenseMatrix::from_2d_array(&[
+                    &[0.75, -1.0, 0.0],
+                    &[0.0, 0.0, 0.0],
+                    &[0.5, 1.0, 1.0]
+                ]),
+                1e-12
+            ));
+        }
+
+        #[test]
+        fn default_norm_is_l2() {
+            assert_eq!(Normalizer::<f64>::default().norm(), NormalizerNorm::L2);
+            assert_eq!(
+                Normalizer::<f64>::new(NormalizerNorm::Max).norm(),
+                NormalizerNorm::Max
+            );
+        }
+    }
 }
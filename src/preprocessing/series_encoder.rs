@@ -5,8 +5,8 @@
 use crate::error::Failed;
 use crate::linalg::BaseVector;
 use crate::math::num::RealNumber;
+use core::hash::Hash;
 use std::collections::HashMap;
-use std::hash::Hash;
 
 /// ## Bi-directional map category <-> label num.
 /// Turn Hashable objects into a one-hot vectors or ordinal values.
@@ -68,7 +68,7 @@ impl Read for TestingDataSource {
         unimplemented!()
     }
     fn read_to_string(&mut self, buf: &mut String) -> Result<usize, std::io::Error> {
-        <String as std::fmt::Write>::write_str(buf, &self.text).unwrap();
+        <String as core::fmt::Write>::write_str(buf, &self.text).unwrap();
         Ok(0)
     }
     fn read_exact(&mut self, _buf: &mut [u8]) -> Result<(), std::io::Error> {
@@ -31,9 +31,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::linalg::BaseVector;
 use crate::math::num::RealNumber;
+use crate::parallel::MaybeSync;
 
 /// Defines a kernel function
-pub trait Kernel<T: RealNumber, V: BaseVector<T>>: Clone {
+pub trait Kernel<T: RealNumber, V: BaseVector<T>>: Clone + MaybeSync {
     /// Apply kernel function to x_i and x_j
     fn apply(&self, x_i: &V, x_j: &V) -> T;
 }
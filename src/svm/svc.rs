@@ -24,6 +24,9 @@
 //! The optimizer reaches accuracies similar to that of a real SVM after performing two passes through the training examples. You can choose the number of passes
 //! through the data that the algorithm takes by changing the `epoch` parameter of the classifier.
 //!
+//! SVC does not estimate class probabilities on its own. With `with_probability(true)` the classifier also fits a sigmoid to its decision function,
+//! known as Platt scaling, and calibrates it on decision values from 5-fold cross-validation, which makes training about six times slower.
+//!
 //! Example:
 //!
 //! ```
@@ -66,26 +69,32 @@
 //!
 //! * ["Support Vector Machines", Kowalczyk A., 2017](https://www.svm-tutorial.com/2017/10/support-vector-machines-succinctly-released/)
 //! * ["Fast Kernel Classifiers with Online and Active Learning", Bordes A., Ertekin S., Weston J., Bottou L., 2005](https://www.jmlr.org/papers/volume6/bordes05a/bordes05a.pdf)
+//! * ["Probabilistic Outputs for Support Vector Machines and Comparisons to Regularized Likelihood Methods", Platt J., 1999](https://www.researchgate.net/publication/2594015_Probabilistic_Outputs_for_Support_Vector_Machines_and_Comparisons_to_Regularized_Likelihood_Methods)
+//! * ["A Note on Platt's Probabilistic Outputs for Support Vector Machines", Lin H.-T., Lin C.-J., Weng R. C., 2007](https://www.csie.ntu.edu.tw/~cjlin/papers/plattprob.pdf)
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 
-use std::collections::{HashMap, HashSet};
-use std::fmt::Debug;
-use std::marker::PhantomData;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::marker::PhantomData;
 
 use rand::seq::SliceRandom;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::api::{Predictor, SupervisedEstimator};
+use crate::api::{Predictor, PredictorProba, ProbabilisticClassifier, SupervisedEstimator};
+use crate::callback::{FitAction, FitCallback, FitProgress, NoCallback};
+use crate::collections::{HashMap, HashSet};
 use crate::error::Failed;
 use crate::linalg::BaseVector;
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
-use crate::rand::get_rng_impl;
+use crate::parallel::map_indices;
+use crate::rand::{get_rng_impl, RngImpl};
 use crate::svm::{Kernel, Kernels, LinearKernel};
+use crate::validation::{check_fit, check_predict};
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
@@ -109,6 +118,9 @@ pub struct SVCParameters<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>
     #[cfg_attr(feature = "serde", serde(default))]
     /// Controls the pseudo random number generation for shuffling the data for probability estimates
     seed: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to calibrate the decision function for probability estimates.
+    pub probability: bool,
 }
 
 /// SVC grid search parameters
@@ -185,6 +197,7 @@ impl<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> Iterator
             kernel: self.svc_search_parameters.kernel[self.current_kernel].clone(),
             m: PhantomData,
             seed: self.svc_search_parameters.seed[self.current_seed],
+            probability: false,
         };
 
         if self.current_epoch + 1 < self.svc_search_parameters.epoch.len() {
@@ -250,6 +263,8 @@ pub struct SVC<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> {
     instances: Vec<M::RowVector>,
     w: Vec<T>,
     b: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    platt: Option<(T, T)>,
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -282,6 +297,7 @@ struct Optimizer<'a, T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> {
     sv: Vec<SupportVector<T, M::RowVector>>,
     kernel: &'a K,
     recalculate_minmax_grad: bool,
+    rng: RngImpl,
 }
 
 impl<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> SVCParameters<T, M, K> {
@@ -309,6 +325,7 @@ impl<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> SVCParameters<T, M
             kernel,
             m: PhantomData,
             seed: self.seed,
+            probability: self.probability,
         }
     }
 
@@ -317,6 +334,12 @@ impl<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> SVCParameters<T, M
         self.seed = seed;
         self
     }
+
+    /// Whether to calibrate the decision function for probability estimates.
+    pub fn with_probability(mut self, probability: bool) -> Self {
+        self.probability = probability;
+        self
+    }
 }
 
 impl<T: RealNumber, M: Matrix<T>> Default for SVCParameters<T, M, LinearKernel> {
@@ -328,6 +351,7 @@ impl<T: RealNumber, M: Matrix<T>> Default for SVCParameters<T, M, LinearKernel>
             kernel: Kernels::linear(),
             m: PhantomData,
             seed: None,
+            probability: false,
         }
     }
 }
@@ -348,6 +372,20 @@ impl<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> Predictor<M, M::Ro
     }
 }
 
+impl<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> PredictorProba<M> for SVC<T, M, K> {
+    fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.predict_proba(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> ProbabilisticClassifier<T, M>
+    for SVC<T, M, K>
+{
+    fn classes(&self) -> &Vec<T> {
+        self.classes()
+    }
+}
+
 impl<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> SVC<T, M, K> {
     /// Fits SVC to your data.
     /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
@@ -358,6 +396,21 @@ impl<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> SVC<T, M, K> {
         y: &M::RowVector,
         parameters: SVCParameters<T, M, K>,
     ) -> Result<SVC<T, M, K>, Failed> {
+        SVC::fit_with_callback(x, y, parameters, &mut NoCallback)
+    }
+
+    /// Fits SVC like `fit` and calls `callback` after every epoch with the negative dual objective as the loss, see [callbacks](../../callback/index.html).
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - class labels
+    /// * `parameters` - optional parameters, use `Default::default()` to set parameters to default values.
+    /// * `callback` - monitors the training and can stop it
+    pub fn fit_with_callback(
+        x: &M,
+        y: &M::RowVector,
+        parameters: SVCParameters<T, M, K>,
+        callback: &mut dyn FitCallback<T>,
+    ) -> Result<SVC<T, M, K>, Failed> {
+        check_fit(x, y)?;
         let (n, _) = x.shape();
 
         if n != y.len() {
@@ -387,9 +440,16 @@ impl<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> SVC<T, M, K> {
             }
         }
 
+        let platt = if parameters.probability {
+            let decision = SVC::cross_val_decision_function(x, &y, &parameters)?;
+            Some(platt_scaling(&decision, &y.to_vec()))
+        } else {
+            None
+        };
+
         let optimizer = Optimizer::new(x, &y, &parameters.kernel, &parameters);
 
-        let (support_vectors, weight, b) = optimizer.optimize();
+        let (support_vectors, weight, b) = optimizer.optimize(callback);
 
         Ok(SVC {
             classes,
@@ -397,9 +457,50 @@ impl<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> SVC<T, M, K> {
             instances: support_vectors,
             w: weight,
             b,
+            platt,
         })
     }
 
+    /// Decision values of the training samples, every sample is evaluated by a classifier fitted on the other folds of a 5-fold cross-validation.
+    fn cross_val_decision_function(
+        x: &M,
+        y: &M::RowVector,
+        parameters: &SVCParameters<T, M, K>,
+    ) -> Result<Vec<T>, Failed> {
+        let n = y.len();
+        let n_folds = n.min(5);
+        let mut indices: Vec<usize> = (0..n).collect();
+        indices.shuffle(&mut get_rng_impl(parameters.seed));
+
+        let mut decision = vec![T::zero(); n];
+        for fold in 0..n_folds {
+            let (test, train): (Vec<(usize, usize)>, Vec<(usize, usize)>) = indices
+                .iter()
+                .copied()
+                .enumerate()
+                .partition(|(position, _)| position % n_folds == fold);
+            let test: Vec<usize> = test.into_iter().map(|(_, i)| i).collect();
+            let train: Vec<usize> = train.into_iter().map(|(_, i)| i).collect();
+
+            let svc = SVC::fit(
+                &x.take(&train, 0),
+                &y.take(&train),
+                parameters.clone().with_probability(false),
+            )
+            .map_err(|_| {
+                Failed::fit(
+                    "Every fold of the cross-validation for probability estimates should contain both classes",
+                )
+            })?;
+            let fold_decision = svc.decision_function(&x.take(&test, 0))?;
+            for (k, i) in test.iter().enumerate() {
+                decision[*i] = fold_decision.get(k);
+            }
+        }
+
+        Ok(decision)
+    }
+
     /// Predicts estimated class labels from `x`
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
@@ -420,14 +521,38 @@ impl<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> SVC<T, M, K> {
     /// Evaluates the decision function for the rows in `x`
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn decision_function(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
         let (n, _) = x.shape();
-        let mut y_hat = M::RowVector::zeros(n);
+        let y_hat = map_indices(n, |i| self.predict_for_row(x.get_row(i)));
+
+        Ok(M::RowVector::from_array(&y_hat))
+    }
 
+    /// Estimates the probability of both classes for the rows in `x` with a sigmoid of the decision function.
+    /// Only available when the classifier was fitted with `with_probability(true)`. As the sigmoid is calibrated with cross-validation,
+    /// the most probable class can differ from `predict` for observations close to the decision boundary.
+    /// Returns _Kx2_ matrix, columns follow the order of sorted class labels.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        let (a, b) = self.platt.ok_or_else(|| {
+            Failed::predict(
+                "Probability estimates are only available for a classifier fitted with `with_probability(true)`",
+            )
+        })?;
+        let decision = self.decision_function(x)?;
+        let n = decision.len();
+        let mut result = M::zeros(n, 2);
         for i in 0..n {
-            y_hat.set(i, self.predict_for_row(x.get_row(i)));
+            let p = platt_probability(decision.get(i), a, b);
+            result.set(i, 0, T::one() - p);
+            result.set(i, 1, p);
         }
+        Ok(result)
+    }
 
-        Ok(y_hat)
+    /// Class labels, sorted.
+    pub fn classes(&self) -> &Vec<T> {
+        &self.classes
     }
 
     fn predict_for_row(&self, x: M::RowVector) -> T {
@@ -464,6 +589,104 @@ impl<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> PartialEq for SVC<
     }
 }
 
+/// Probability of the positive class, \\(1 / (1 + e^{Af + B})\\), for the decision value \\(f\\).
+fn platt_probability<T: RealNumber>(f: T, a: T, b: T) -> T {
+    let f_apb = f * a + b;
+    // evaluated in a form that does not overflow for large values of the exponent
+    if f_apb >= T::zero() {
+        (-f_apb).exp() / (T::one() + (-f_apb).exp())
+    } else {
+        T::one() / (T::one() + f_apb.exp())
+    }
+}
+
+/// Fits the parameters \\(A\\) and \\(B\\) of the sigmoid to the decision values of samples labeled with -1 or 1,
+/// with Newton's method and a backtracking line search as described by Lin, Lin and Weng.
+fn platt_scaling<T: RealNumber>(decision: &[T], y: &[T]) -> (T, T) {
+    let max_iter = 100;
+    let min_step = T::from_f64(1e-10).unwrap();
+    let sigma = T::from_f64(1e-12).unwrap();
+    let eps = T::from_f64(1e-5).unwrap();
+
+    let prior1 = T::from_usize(y.iter().filter(|y_i| **y_i > T::zero()).count()).unwrap();
+    let prior0 = T::from_usize(y.len()).unwrap() - prior1;
+    // regularized targets, so that the sigmoid does not overfit the training data
+    let hi_target = (prior1 + T::one()) / (prior1 + T::two());
+    let lo_target = T::one() / (prior0 + T::two());
+    let t: Vec<T> = y
+        .iter()
+        .map(|y_i| {
+            if *y_i > T::zero() {
+                hi_target
+            } else {
+                lo_target
+            }
+        })
+        .collect();
+
+    let objective = |a: T, b: T| {
+        decision
+            .iter()
+            .zip(t.iter())
+            .fold(T::zero(), |sum, (f, t_i)| {
+                let f_apb = *f * a + b;
+                if f_apb >= T::zero() {
+                    sum + *t_i * f_apb + (T::one() + (-f_apb).exp()).ln()
+                } else {
+                    sum + (*t_i - T::one()) * f_apb + (T::one() + f_apb.exp()).ln()
+                }
+            })
+    };
+
+    let mut a = T::zero();
+    let mut b = ((prior0 + T::one()) / (prior1 + T::one())).ln();
+    let mut fval = objective(a, b);
+
+    for _ in 0..max_iter {
+        // gradient and Hessian of the objective
+        let (mut h11, mut h22, mut h21, mut g1, mut g2) =
+            (sigma, sigma, T::zero(), T::zero(), T::zero());
+        for (f, t_i) in decision.iter().zip(t.iter()) {
+            let p = platt_probability(*f, a, b);
+            let q = T::one() - p;
+            let d2 = p * q;
+            h11 += *f * *f * d2;
+            h22 += d2;
+            h21 += *f * d2;
+            let d1 = *t_i - p;
+            g1 += *f * d1;
+            g2 += d1;
+        }
+        if g1.abs() < eps && g2.abs() < eps {
+            break;
+        }
+
+        let det = h11 * h22 - h21 * h21;
+        let d_a = -(h22 * g1 - h21 * g2) / det;
+        let d_b = -(-h21 * g1 + h11 * g2) / det;
+        let gd = g1 * d_a + g2 * d_b;
+
+        let mut step = T::one();
+        while step >= min_step {
+            let new_a = a + step * d_a;
+            let new_b = b + step * d_b;
+            let new_f = objective(new_a, new_b);
+            if new_f < fval + T::from_f64(1e-4).unwrap() * step * gd {
+                a = new_a;
+                b = new_b;
+                fval = new_f;
+                break;
+            }
+            step /= T::two();
+        }
+        if step < min_step {
+            break;
+        }
+    }
+
+    (a, b)
+}
+
 impl<T: RealNumber, V: BaseVector<T>> SupportVector<T, V> {
     fn new<K: Kernel<T, V>>(i: usize, x: V, y: T, g: T, c: T, k: &K) -> SupportVector<T, V> {
         let k_v = k.apply(&x, &x);
@@ -534,10 +757,11 @@ impl<'a, T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> Optimizer<'a,
             sv: Vec::with_capacity(n),
             kernel,
             recalculate_minmax_grad: true,
+            rng: get_rng_impl(parameters.seed),
         }
     }
 
-    fn optimize(mut self) -> (Vec<M::RowVector>, Vec<T>, T) {
+    fn optimize(mut self, callback: &mut dyn FitCallback<T>) -> (Vec<M::RowVector>, Vec<T>, T) {
         let (n, _) = self.x.shape();
 
         let mut cache = Cache::new(self.kernel);
@@ -547,7 +771,7 @@ impl<'a, T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> Optimizer<'a,
         let tol = self.parameters.tol;
         let good_enough = T::from_i32(1000).unwrap();
 
-        for _ in 0..self.parameters.epoch {
+        for epoch in 1..=self.parameters.epoch {
             for i in self.permutate(n) {
                 self.process(i, self.x.get_row(i), self.y.get(i), &mut cache);
                 loop {
@@ -558,9 +782,30 @@ impl<'a, T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> Optimizer<'a,
                     }
                 }
             }
+            debug!(
+                "SVC epoch {}/{}: {} support vectors, gradient gap {}",
+                epoch,
+                self.parameters.epoch,
+                self.sv.len(),
+                self.gmax - self.gmin
+            );
+
+            let progress = FitProgress {
+                iteration: epoch,
+                loss: Some(-self.dual_objective()),
+                validation_score: None,
+            };
+            if callback.on_iteration(&progress) == FitAction::Stop {
+                break;
+            }
         }
 
         self.finish(&mut cache);
+        info!(
+            "SVC fitted with {} support vectors, gradient gap {}",
+            self.sv.len(),
+            self.gmax - self.gmin
+        );
 
         let mut support_vectors: Vec<M::RowVector> = Vec::new();
         let mut w: Vec<T> = Vec::new();
@@ -575,6 +820,14 @@ impl<'a, T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> Optimizer<'a,
         (support_vectors, w, b)
     }
 
+    /// Objective of the dual problem, \\(\sum_i \alpha_i y_i - \frac{1}{2}\sum_{i,j} \alpha_i \alpha_j K(x_i, x_j) = \frac{1}{2}\sum_i \alpha_i (y_i + g_i)\\),
+    /// where \\(g_i\\) is the gradient of support vector _i_.
+    fn dual_objective(&self) -> T {
+        self.sv.iter().fold(T::zero(), |sum, v| {
+            sum + v.alpha * (self.y.get(v.index) + v.grad)
+        }) / T::two()
+    }
+
     fn initialize(&mut self, cache: &mut Cache<'_, T, M, K>) {
         let (n, _) = self.x.shape();
         let few = 5;
@@ -706,10 +959,9 @@ impl<'a, T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> Optimizer<'a,
         self.recalculate_minmax_grad = true;
     }
 
-    fn permutate(&self, n: usize) -> Vec<usize> {
-        let mut rng = get_rng_impl(self.parameters.seed);
+    fn permutate(&mut self, n: usize) -> Vec<usize> {
         let mut range: Vec<usize> = (0..n).collect();
-        range.shuffle(&mut rng);
+        range.shuffle(&mut self.rng);
         range
     }
 
@@ -872,6 +1124,7 @@ impl<'a, T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> Optimizer<'a,
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::callback::{FitAction, FitProgress};
     use crate::linalg::naive::dense_matrix::*;
     use crate::metrics::accuracy;
     #[cfg(feature = "serde")]
@@ -944,6 +1197,142 @@ mod tests {
         );
     }
 
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn svc_predict_proba() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[5.1, 3.5, 1.4, 0.2],
+            &[4.9, 3.0, 1.4, 0.2],
+            &[4.7, 3.2, 1.3, 0.2],
+            &[4.6, 3.1, 1.5, 0.2],
+            &[5.0, 3.6, 1.4, 0.2],
+            &[5.4, 3.9, 1.7, 0.4],
+            &[4.6, 3.4, 1.4, 0.3],
+            &[5.0, 3.4, 1.5, 0.2],
+            &[4.4, 2.9, 1.4, 0.2],
+            &[4.9, 3.1, 1.5, 0.1],
+            &[7.0, 3.2, 4.7, 1.4],
+            &[6.4, 3.2, 4.5, 1.5],
+            &[6.9, 3.1, 4.9, 1.5],
+            &[5.5, 2.3, 4.0, 1.3],
+            &[6.5, 2.8, 4.6, 1.5],
+            &[5.7, 2.8, 4.5, 1.3],
+            &[6.3, 3.3, 4.7, 1.6],
+            &[4.9, 2.4, 3.3, 1.0],
+            &[6.6, 2.9, 4.6, 1.3],
+            &[5.2, 2.7, 3.9, 1.4],
+        ]);
+        let y: Vec<f64> = vec![
+            0., 0., 0., 0., 0., 0., 0., 0., 0., 0., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1.,
+        ];
+        let parameters = SVCParameters::default()
+            .with_c(200.0)
+            .with_kernel(Kernels::linear())
+            .with_seed(Some(100));
+
+        let svc = SVC::fit(&x, &y, parameters.clone()).unwrap();
+        assert!(svc.predict_proba(&x).is_err());
+
+        let svc = SVC::fit(&x, &y, parameters.with_probability(true)).unwrap();
+        let proba = svc.predict_proba(&x).unwrap();
+        let y_hat: Vec<f64> = proba
+            .argmax()
+            .iter()
+            .map(|class_index| svc.classes()[*class_index])
+            .collect();
+
+        assert_eq!(proba.shape(), (20, 2));
+        for i in 0..20 {
+            assert!((proba.get(i, 0) + proba.get(i, 1) - 1.).abs() < 1e-8);
+        }
+        assert!(accuracy(&y_hat, &y) >= 0.9);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn svc_fit_with_seed_is_reproducible() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[5.1, 3.5, 1.4, 0.2],
+            &[4.9, 3.0, 1.4, 0.2],
+            &[4.7, 3.2, 1.3, 0.2],
+            &[5.0, 3.6, 1.4, 0.2],
+            &[4.4, 2.9, 1.4, 0.2],
+            &[7.0, 3.2, 4.7, 1.4],
+            &[6.4, 3.2, 4.5, 1.5],
+            &[5.5, 2.3, 4.0, 1.3],
+            &[4.9, 2.4, 3.3, 1.0],
+            &[5.2, 2.7, 3.9, 1.4],
+        ]);
+        let y: Vec<f64> = vec![0., 0., 0., 0., 0., 1., 1., 1., 1., 1.];
+
+        let fit = || {
+            SVC::fit(
+                &x,
+                &y,
+                SVCParameters::default()
+                    .with_epoch(3)
+                    .with_kernel(Kernels::rbf(0.5))
+                    .with_seed(Some(7)),
+            )
+            .and_then(|svc| svc.decision_function(&x))
+            .unwrap()
+        };
+
+        assert_eq!(fit(), fit());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn svc_fit_with_callback() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[5.1, 3.5, 1.4, 0.2],
+            &[4.9, 3.0, 1.4, 0.2],
+            &[4.7, 3.2, 1.3, 0.2],
+            &[5.0, 3.6, 1.4, 0.2],
+            &[4.4, 2.9, 1.4, 0.2],
+            &[7.0, 3.2, 4.7, 1.4],
+            &[6.4, 3.2, 4.5, 1.5],
+            &[5.5, 2.3, 4.0, 1.3],
+            &[4.9, 2.4, 3.3, 1.0],
+            &[5.2, 2.7, 3.9, 1.4],
+        ]);
+        let y: Vec<f64> = vec![0., 0., 0., 0., 0., 1., 1., 1., 1., 1.];
+
+        let mut epochs = Vec::new();
+        SVC::fit_with_callback(
+            &x,
+            &y,
+            SVCParameters::default()
+                .with_epoch(5)
+                .with_kernel(Kernels::linear())
+                .with_seed(Some(7)),
+            &mut |progress: &FitProgress<f64>| {
+                epochs.push(progress.iteration);
+                assert!(progress.loss.unwrap() <= 0.);
+                FitAction::Continue
+            },
+        )
+        .unwrap();
+        assert_eq!(epochs, vec![1, 2, 3, 4, 5]);
+
+        let mut epochs = 0;
+        let svc = SVC::fit_with_callback(
+            &x,
+            &y,
+            SVCParameters::default()
+                .with_epoch(5)
+                .with_kernel(Kernels::linear())
+                .with_seed(Some(7)),
+            &mut |progress: &FitProgress<f64>| {
+                epochs = progress.iteration;
+                FitAction::Stop
+            },
+        )
+        .unwrap();
+        assert_eq!(epochs, 1);
+        assert_eq!(svc.predict(&x).unwrap().len(), 10);
+    }
+
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     #[test]
     fn svc_fit_decision_function() {
@@ -64,9 +64,10 @@
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 
-use std::cell::{Ref, RefCell};
-use std::fmt::Debug;
-use std::marker::PhantomData;
+use alloc::vec::Vec;
+use core::cell::{Ref, RefCell};
+use core::fmt::Debug;
+use core::marker::PhantomData;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -77,6 +78,7 @@ use crate::linalg::BaseVector;
 use crate::linalg::Matrix;
 use crate::math::num::RealNumber;
 use crate::svm::{Kernel, Kernels, LinearKernel};
+use crate::validation::{check_fit, check_predict};
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
@@ -311,6 +313,7 @@ impl<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> SVR<T, M, K> {
         y: &M::RowVector,
         parameters: SVRParameters<T, M, K>,
     ) -> Result<SVR<T, M, K>, Failed> {
+        check_fit(x, y)?;
         let (n, _) = x.shape();
 
         if n != y.len() {
@@ -334,6 +337,7 @@ impl<T: RealNumber, M: Matrix<T>, K: Kernel<T, M::RowVector>> SVR<T, M, K> {
     /// Predict target values from `x`
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
+        check_predict(x)?;
         let (n, _) = x.shape();
 
         let mut y_hat = M::RowVector::zeros(n);
@@ -62,10 +62,12 @@
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
-use std::collections::LinkedList;
-use std::default::Default;
-use std::fmt::Debug;
-use std::marker::PhantomData;
+use alloc::collections::LinkedList;
+#[cfg(feature = "pmml")]
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::default::Default;
+use core::fmt::Debug;
 
 use rand::seq::SliceRandom;
 use rand::Rng;
@@ -73,11 +75,23 @@ use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::algorithm::sort::quick_sort::QuickArgSort;
-use crate::api::{Predictor, SupervisedEstimator};
+use crate::api::{
+    FeatureImportance, Predictor, PredictorProba, ProbabilisticClassifier, SupervisedEstimator,
+    SupervisedEstimatorWeighted,
+};
 use crate::error::Failed;
-use crate::linalg::Matrix;
+use crate::linalg::{BaseVector, Matrix};
 use crate::math::num::RealNumber;
+#[cfg(feature = "onnx")]
+use crate::onnx::{self, tree::TreeEnsemble, ToOnnx};
+use crate::parallel::map_indices;
+#[cfg(feature = "pmml")]
+use crate::pmml::{self, tree, Document, FromPmml, Target, ToPmml};
 use crate::rand::get_rng_impl;
+use crate::tree::node::{feature_importances, node_depths, TreeNode};
+use crate::tree::oblique::{self, SplitType};
+use crate::tree::{pop_best, GrowthStrategy};
+use crate::validation::{check_fit, check_predict};
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
@@ -98,6 +112,15 @@ pub struct DecisionTreeClassifierParameters {
     #[cfg_attr(feature = "serde", serde(default))]
     /// Controls the randomness of the estimator
     pub seed: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether nodes are split on a single feature or on a linear combination of features. See [oblique splits](../oblique/index.html)
+    pub split_type: SplitType,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of leaves in the tree.
+    pub max_leaf_nodes: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Order in which nodes are split. See [tree growth](../index.html)
+    pub growth_strategy: GrowthStrategy,
 }
 
 /// Decision Tree
@@ -109,6 +132,7 @@ pub struct DecisionTreeClassifier<T: RealNumber> {
     num_classes: usize,
     classes: Vec<T>,
     depth: u16,
+    num_features: usize,
 }
 
 /// The function to measure the quality of a split.
@@ -134,9 +158,12 @@ impl Default for SplitCriterion {
 struct Node<T: RealNumber> {
     _index: usize,
     output: usize,
+    n_samples: usize,
+    probabilities: Vec<T>,
     split_feature: usize,
     split_value: Option<T>,
     split_score: Option<T>,
+    split_weights: Option<Vec<(usize, T)>>,
     true_child: Option<usize>,
     false_child: Option<usize>,
 }
@@ -178,6 +205,16 @@ impl<T: RealNumber> PartialEq for Node<T> {
                 (None, None) => true,
                 _ => false,
             }
+            && match (&self.split_weights, &other.split_weights) {
+                (Some(a), Some(b)) => {
+                    a.len() == b.len()
+                        && a.iter()
+                            .zip(b.iter())
+                            .all(|(a, b)| a.0 == b.0 && (a.1 - b.1).abs() < T::epsilon())
+                }
+                (None, None) => true,
+                _ => false,
+            }
     }
 }
 
@@ -202,6 +239,21 @@ impl DecisionTreeClassifierParameters {
         self.min_samples_split = min_samples_split;
         self
     }
+    /// Whether nodes are split on a single feature or on a linear combination of features.
+    pub fn with_split_type(mut self, split_type: SplitType) -> Self {
+        self.split_type = split_type;
+        self
+    }
+    /// The maximum number of leaves in the tree.
+    pub fn with_max_leaf_nodes(mut self, max_leaf_nodes: usize) -> Self {
+        self.max_leaf_nodes = Some(max_leaf_nodes);
+        self
+    }
+    /// Order in which nodes are split.
+    pub fn with_growth_strategy(mut self, growth_strategy: GrowthStrategy) -> Self {
+        self.growth_strategy = growth_strategy;
+        self
+    }
 }
 
 impl Default for DecisionTreeClassifierParameters {
@@ -212,6 +264,9 @@ impl Default for DecisionTreeClassifierParameters {
             min_samples_leaf: 1,
             min_samples_split: 2,
             seed: None,
+            split_type: SplitType::default(),
+            max_leaf_nodes: None,
+            growth_strategy: GrowthStrategy::default(),
         }
     }
 }
@@ -235,6 +290,15 @@ pub struct DecisionTreeClassifierSearchParameters {
     #[cfg_attr(feature = "serde", serde(default))]
     /// Controls the randomness of the estimator
     pub seed: Vec<Option<u64>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Shape of the splits. See [oblique splits](../../tree/oblique/index.html)
+    pub split_type: Vec<SplitType>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of leaves in the tree. See [Decision Tree Classifier](../../tree/decision_tree_classifier/index.html)
+    pub max_leaf_nodes: Vec<Option<usize>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Order in which nodes are split. See [tree growth](../../tree/index.html)
+    pub growth_strategy: Vec<GrowthStrategy>,
 }
 
 /// DecisionTreeClassifier grid search iterator
@@ -245,6 +309,9 @@ pub struct DecisionTreeClassifierSearchParametersIterator {
     current_min_samples_leaf: usize,
     current_min_samples_split: usize,
     current_seed: usize,
+    current_split_type: usize,
+    current_max_leaf_nodes: usize,
+    current_growth_strategy: usize,
 }
 
 impl IntoIterator for DecisionTreeClassifierSearchParameters {
@@ -259,6 +326,9 @@ impl IntoIterator for DecisionTreeClassifierSearchParameters {
             current_min_samples_leaf: 0,
             current_min_samples_split: 0,
             current_seed: 0,
+            current_split_type: 0,
+            current_max_leaf_nodes: 0,
+            current_growth_strategy: 0,
         }
     }
 }
@@ -288,6 +358,21 @@ impl Iterator for DecisionTreeClassifierSearchParametersIterator {
                     .min_samples_split
                     .len()
             && self.current_seed == self.decision_tree_classifier_search_parameters.seed.len()
+            && self.current_split_type
+                == self
+                    .decision_tree_classifier_search_parameters
+                    .split_type
+                    .len()
+            && self.current_max_leaf_nodes
+                == self
+                    .decision_tree_classifier_search_parameters
+                    .max_leaf_nodes
+                    .len()
+            && self.current_growth_strategy
+                == self
+                    .decision_tree_classifier_search_parameters
+                    .growth_strategy
+                    .len()
         {
             return None;
         }
@@ -305,6 +390,16 @@ impl Iterator for DecisionTreeClassifierSearchParametersIterator {
                 .decision_tree_classifier_search_parameters
                 .min_samples_split[self.current_min_samples_split],
             seed: self.decision_tree_classifier_search_parameters.seed[self.current_seed],
+            split_type: self.decision_tree_classifier_search_parameters.split_type
+                [self.current_split_type]
+                .clone(),
+            max_leaf_nodes: self
+                .decision_tree_classifier_search_parameters
+                .max_leaf_nodes[self.current_max_leaf_nodes],
+            growth_strategy: self
+                .decision_tree_classifier_search_parameters
+                .growth_strategy[self.current_growth_strategy]
+                .clone(),
         };
 
         if self.current_criterion + 1
@@ -348,12 +443,54 @@ impl Iterator for DecisionTreeClassifierSearchParametersIterator {
             self.current_min_samples_leaf = 0;
             self.current_min_samples_split = 0;
             self.current_seed += 1;
+        } else if self.current_split_type + 1
+            < self
+                .decision_tree_classifier_search_parameters
+                .split_type
+                .len()
+        {
+            self.current_criterion = 0;
+            self.current_max_depth = 0;
+            self.current_min_samples_leaf = 0;
+            self.current_min_samples_split = 0;
+            self.current_seed = 0;
+            self.current_split_type += 1;
+        } else if self.current_max_leaf_nodes + 1
+            < self
+                .decision_tree_classifier_search_parameters
+                .max_leaf_nodes
+                .len()
+        {
+            self.current_criterion = 0;
+            self.current_max_depth = 0;
+            self.current_min_samples_leaf = 0;
+            self.current_min_samples_split = 0;
+            self.current_seed = 0;
+            self.current_split_type = 0;
+            self.current_max_leaf_nodes += 1;
+        } else if self.current_growth_strategy + 1
+            < self
+                .decision_tree_classifier_search_parameters
+                .growth_strategy
+                .len()
+        {
+            self.current_criterion = 0;
+            self.current_max_depth = 0;
+            self.current_min_samples_leaf = 0;
+            self.current_min_samples_split = 0;
+            self.current_seed = 0;
+            self.current_split_type = 0;
+            self.current_max_leaf_nodes = 0;
+            self.current_growth_strategy += 1;
         } else {
             self.current_criterion += 1;
             self.current_max_depth += 1;
             self.current_min_samples_leaf += 1;
             self.current_min_samples_split += 1;
             self.current_seed += 1;
+            self.current_split_type += 1;
+            self.current_max_leaf_nodes += 1;
+            self.current_growth_strategy += 1;
         }
 
         Some(next)
@@ -370,22 +507,36 @@ impl Default for DecisionTreeClassifierSearchParameters {
             min_samples_leaf: vec![default_params.min_samples_leaf],
             min_samples_split: vec![default_params.min_samples_split],
             seed: vec![default_params.seed],
+            split_type: vec![default_params.split_type],
+            max_leaf_nodes: vec![default_params.max_leaf_nodes],
+            growth_strategy: vec![default_params.growth_strategy],
         }
     }
 }
 
 impl<T: RealNumber> Node<T> {
-    fn new(index: usize, output: usize) -> Self {
+    fn new(index: usize, count: &[T], n_samples: usize) -> Self {
+        let total = count.iter().fold(T::zero(), |acc, c| acc + *c);
         Node {
             _index: index,
-            output,
+            output: which_max(count),
+            n_samples,
+            probabilities: count.iter().map(|c| *c / total).collect(),
             split_feature: 0,
             split_value: Option::None,
             split_score: Option::None,
+            split_weights: Option::None,
             true_child: Option::None,
             false_child: Option::None,
         }
     }
+
+    fn split_input<M: Matrix<T>>(&self, x: &M, row: usize) -> T {
+        match &self.split_weights {
+            Some(weights) => oblique::project(x, row, weights),
+            None => x.get(row, self.split_feature),
+        }
+    }
 }
 
 struct NodeVisitor<'a, T: RealNumber, M: Matrix<T>> {
@@ -393,22 +544,20 @@ struct NodeVisitor<'a, T: RealNumber, M: Matrix<T>> {
     y: &'a [usize],
     node: usize,
     samples: Vec<usize>,
+    sample_weight: &'a [T],
     order: &'a [Vec<usize>],
-    true_child_output: usize,
-    false_child_output: usize,
     level: u16,
-    phantom: PhantomData<&'a T>,
 }
 
-fn impurity<T: RealNumber>(criterion: &SplitCriterion, count: &[usize], n: usize) -> T {
+fn impurity<T: RealNumber>(criterion: &SplitCriterion, count: &[T], n: T) -> T {
     let mut impurity = T::zero();
 
     match criterion {
         SplitCriterion::Gini => {
             impurity = T::one();
             for count_i in count.iter() {
-                if *count_i > 0 {
-                    let p = T::from(*count_i).unwrap() / T::from(n).unwrap();
+                if *count_i > T::zero() {
+                    let p = *count_i / n;
                     impurity -= p * p;
                 }
             }
@@ -416,16 +565,16 @@ fn impurity<T: RealNumber>(criterion: &SplitCriterion, count: &[usize], n: usize
 
         SplitCriterion::Entropy => {
             for count_i in count.iter() {
-                if *count_i > 0 {
-                    let p = T::from(*count_i).unwrap() / T::from(n).unwrap();
+                if *count_i > T::zero() {
+                    let p = *count_i / n;
                     impurity -= p * p.log2();
                 }
             }
         }
         SplitCriterion::ClassificationError => {
             for count_i in count.iter() {
-                if *count_i > 0 {
-                    impurity = impurity.max(T::from(*count_i).unwrap() / T::from(n).unwrap());
+                if *count_i > T::zero() {
+                    impurity = impurity.max(*count_i / n);
                 }
             }
             impurity = (T::one() - impurity).abs();
@@ -439,6 +588,7 @@ impl<'a, T: RealNumber, M: Matrix<T>> NodeVisitor<'a, T, M> {
     fn new(
         node_id: usize,
         samples: Vec<usize>,
+        sample_weight: &'a [T],
         order: &'a [Vec<usize>],
         x: &'a M,
         y: &'a [usize],
@@ -449,16 +599,18 @@ impl<'a, T: RealNumber, M: Matrix<T>> NodeVisitor<'a, T, M> {
             y,
             node: node_id,
             samples,
+            sample_weight,
             order,
-            true_child_output: 0,
-            false_child_output: 0,
             level,
-            phantom: PhantomData,
         }
     }
+
+    fn sample_count(&self, i: usize) -> T {
+        T::from(self.samples[i]).unwrap() * self.sample_weight[i]
+    }
 }
 
-pub(crate) fn which_max(x: &[usize]) -> usize {
+pub(crate) fn which_max<V: PartialOrd + Copy>(x: &[V]) -> usize {
     let mut m = x[0];
     let mut which = 0;
 
@@ -485,12 +637,44 @@ impl<T: RealNumber, M: Matrix<T>>
     }
 }
 
+impl<T: RealNumber, M: Matrix<T>>
+    SupervisedEstimatorWeighted<M, M::RowVector, DecisionTreeClassifierParameters>
+    for DecisionTreeClassifier<T>
+{
+    fn fit_with_weights(
+        x: &M,
+        y: &M::RowVector,
+        sample_weight: &M::RowVector,
+        parameters: DecisionTreeClassifierParameters,
+    ) -> Result<Self, Failed> {
+        DecisionTreeClassifier::fit_with_weights(x, y, sample_weight, parameters)
+    }
+}
+
 impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for DecisionTreeClassifier<T> {
     fn predict(&self, x: &M) -> Result<M::RowVector, Failed> {
         self.predict(x)
     }
 }
 
+impl<T: RealNumber> FeatureImportance<T> for DecisionTreeClassifier<T> {
+    fn feature_importance(&self) -> Vec<T> {
+        self.feature_importances()
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> PredictorProba<M> for DecisionTreeClassifier<T> {
+    fn predict_proba(&self, x: &M) -> Result<M, Failed> {
+        self.predict_proba(x)
+    }
+}
+
+impl<T: RealNumber, M: Matrix<T>> ProbabilisticClassifier<T, M> for DecisionTreeClassifier<T> {
+    fn classes(&self) -> &Vec<T> {
+        self.classes()
+    }
+}
+
 impl<T: RealNumber> DecisionTreeClassifier<T> {
     /// Build a decision tree classifier from the training data.
     /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
@@ -500,17 +684,78 @@ impl<T: RealNumber> DecisionTreeClassifier<T> {
         y: &M::RowVector,
         parameters: DecisionTreeClassifierParameters,
     ) -> Result<DecisionTreeClassifier<T>, Failed> {
+        check_fit(x, y)?;
         let (x_nrows, num_attributes) = x.shape();
         let samples = vec![1; x_nrows];
         DecisionTreeClassifier::fit_weak_learner(x, y, samples, num_attributes, parameters)
     }
 
+    /// Build a decision tree classifier from the training data where each observation has its own weight.
+    /// Class frequencies, and therefore impurities and leaf outputs, are computed from the sum of weights instead of the number of observations.
+    /// Constraints on the number of samples, like `min_samples_leaf`, still count observations.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target class values
+    /// * `sample_weight` - non-negative weight of every observation
+    pub fn fit_with_weights<M: Matrix<T>>(
+        x: &M,
+        y: &M::RowVector,
+        sample_weight: &M::RowVector,
+        parameters: DecisionTreeClassifierParameters,
+    ) -> Result<DecisionTreeClassifier<T>, Failed> {
+        let (x_nrows, num_attributes) = x.shape();
+        let sample_weight = sample_weight.to_vec();
+        if sample_weight.len() != x_nrows {
+            return Err(Failed::fit(&format!(
+                "Size of sample_weight ({}) does not match number of rows of x ({})",
+                sample_weight.len(),
+                x_nrows
+            )));
+        }
+        if sample_weight
+            .iter()
+            .any(|w| !w.is_finite() || *w < T::zero())
+        {
+            return Err(Failed::fit("Sample weights should be non-negative"));
+        }
+        let samples = sample_weight
+            .iter()
+            .map(|w| if *w > T::zero() { 1 } else { 0 })
+            .collect();
+        DecisionTreeClassifier::fit_weighted_learner(
+            x,
+            y,
+            samples,
+            &sample_weight,
+            num_attributes,
+            parameters,
+        )
+    }
+
     pub(crate) fn fit_weak_learner<M: Matrix<T>>(
         x: &M,
         y: &M::RowVector,
         samples: Vec<usize>,
         mtry: usize,
         parameters: DecisionTreeClassifierParameters,
+    ) -> Result<DecisionTreeClassifier<T>, Failed> {
+        let sample_weight = vec![T::one(); samples.len()];
+        DecisionTreeClassifier::fit_weighted_learner(
+            x,
+            y,
+            samples,
+            &sample_weight,
+            mtry,
+            parameters,
+        )
+    }
+
+    fn fit_weighted_learner<M: Matrix<T>>(
+        x: &M,
+        y: &M::RowVector,
+        samples: Vec<usize>,
+        sample_weight: &[T],
+        mtry: usize,
+        parameters: DecisionTreeClassifierParameters,
     ) -> Result<DecisionTreeClassifier<T>, Failed> {
         let y_m = M::from_row_vector(y.clone());
         let (_, y_ncols) = y_m.shape();
@@ -534,12 +779,12 @@ impl<T: RealNumber> DecisionTreeClassifier<T> {
 
         let mut nodes: Vec<Node<T>> = Vec::new();
 
-        let mut count = vec![0; k];
+        let mut count = vec![T::zero(); k];
         for i in 0..y_ncols {
-            count[yi[i]] += samples[i];
+            count[yi[i]] += T::from(samples[i]).unwrap() * sample_weight[i];
         }
 
-        let root = Node::new(0, which_max(&count));
+        let root = Node::new(0, &count, samples.iter().sum());
         nodes.push(root);
         let mut order: Vec<Vec<usize>> = Vec::new();
 
@@ -553,9 +798,10 @@ impl<T: RealNumber> DecisionTreeClassifier<T> {
             num_classes: k,
             classes,
             depth: 0,
+            num_features: num_attributes,
         };
 
-        let mut visitor = NodeVisitor::<T, M>::new(0, samples, &order, x, &yi, 1);
+        let mut visitor = NodeVisitor::<T, M>::new(0, samples, sample_weight, &order, x, &yi, 1);
 
         let mut visitor_queue: LinkedList<NodeVisitor<'_, T, M>> = LinkedList::new();
 
@@ -563,11 +809,40 @@ impl<T: RealNumber> DecisionTreeClassifier<T> {
             visitor_queue.push_back(visitor);
         }
 
-        while tree.depth < tree.parameters.max_depth.unwrap_or(std::u16::MAX) {
-            match visitor_queue.pop_front() {
-                Some(node) => tree.split(node, mtry, &mut visitor_queue, &mut rng),
-                None => break,
-            };
+        let max_depth = tree.parameters.max_depth.unwrap_or(core::u16::MAX);
+        let max_leaf_nodes = tree.parameters.max_leaf_nodes.unwrap_or(usize::MAX);
+        let mut n_leaves = 1;
+
+        match tree.parameters.growth_strategy {
+            GrowthStrategy::BreadthFirst => {
+                while tree.depth < max_depth && n_leaves < max_leaf_nodes {
+                    match visitor_queue.pop_front() {
+                        Some(node) => {
+                            if tree.split(node, mtry, &mut visitor_queue, &mut rng) {
+                                n_leaves += 1;
+                            }
+                        }
+                        None => break,
+                    };
+                }
+            }
+            GrowthStrategy::BestFirst => {
+                while n_leaves < max_leaf_nodes {
+                    match pop_best(&mut visitor_queue, |visitor| {
+                        tree.nodes[visitor.node].split_score.unwrap_or_else(T::zero)
+                            * T::from(tree.nodes[visitor.node].n_samples).unwrap()
+                    }) {
+                        Some(node) => {
+                            if node.level < max_depth
+                                && tree.split(node, mtry, &mut visitor_queue, &mut rng)
+                            {
+                                n_leaves += 1;
+                            }
+                        }
+                        None => break,
+                    };
+                }
+            }
         }
 
         Ok(tree)
@@ -576,18 +851,100 @@ impl<T: RealNumber> DecisionTreeClassifier<T> {
     /// Predict class value for `x`.
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed> {
-        let mut result = M::zeros(1, x.shape().0);
+        check_predict(x)?;
+        let (n, _) = x.shape();
+        let y_hat = map_indices(n, |i| self.classes[self.predict_for_row(x, i)]);
+
+        Ok(M::RowVector::from_array(&y_hat))
+    }
 
+    /// Predict class probabilities for `x`. The probability of a class is the weighted fraction of training samples of that class in the leaf.
+    /// Returns _KxC_ matrix where _C_ is the number of classes, columns follow the order of sorted class labels.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_proba<M: Matrix<T>>(&self, x: &M) -> Result<M, Failed> {
+        check_predict(x)?;
         let (n, _) = x.shape();
+        let mut result = M::zeros(n, self.num_classes);
 
         for i in 0..n {
-            result.set(0, i, self.classes[self.predict_for_row(x, i)]);
+            let node = &self.nodes[self.leaf_for_row(x, i)];
+            for (j, p) in node.probabilities.iter().enumerate() {
+                result.set(i, j, *p);
+            }
         }
 
-        Ok(result.to_row_vector())
+        Ok(result)
+    }
+
+    /// Class labels, sorted.
+    pub fn classes(&self) -> &Vec<T> {
+        &self.classes
+    }
+
+    /// Returns a read-only view of all nodes of the tree. The root node comes first and parents come before their children.
+    /// See [tree inspection](../node/index.html).
+    pub fn nodes(&self) -> impl Iterator<Item = TreeNode<'_, T>> + '_ {
+        let depths = node_depths(
+            self.nodes
+                .iter()
+                .map(|node| (node.true_child, node.false_child)),
+        );
+        self.nodes.iter().enumerate().map(move |(index, node)| {
+            let is_leaf = node.true_child.is_none() && node.false_child.is_none();
+            TreeNode {
+                index,
+                depth: depths[index],
+                n_samples: node.n_samples,
+                output: self.classes[node.output],
+                split_feature: if is_leaf {
+                    None
+                } else {
+                    Some(node.split_feature)
+                },
+                threshold: if is_leaf { None } else { node.split_value },
+                split_weights: if is_leaf {
+                    None
+                } else {
+                    node.split_weights.as_deref()
+                },
+                impurity_decrease: if is_leaf {
+                    None
+                } else {
+                    node.split_score
+                        .map(|score| score * T::from(node.n_samples).unwrap())
+                },
+                true_child: node.true_child,
+                false_child: node.false_child,
+            }
+        })
+    }
+
+    /// Returns the impurity-based importance of every feature: the total decrease of impurity achieved by the splits on the feature,
+    /// normalized so that importances sum to 1. See [tree inspection](../node/index.html).
+    pub fn feature_importances(&self) -> Vec<T> {
+        feature_importances(self.nodes(), self.num_features).0
+    }
+
+    /// Returns the number of nodes split on every feature.
+    pub fn split_counts(&self) -> Vec<usize> {
+        feature_importances(self.nodes(), self.num_features).1
+    }
+
+    /// Number of features of the training set.
+    pub(crate) fn num_features(&self) -> usize {
+        self.num_features
+    }
+
+    /// Returns the depth of the tree, a tree with a single node has depth 1.
+    pub fn depth(&self) -> u16 {
+        u16::max(self.depth, 1)
     }
 
     pub(crate) fn predict_for_row<M: Matrix<T>>(&self, x: &M, row: usize) -> usize {
+        self.nodes[self.leaf_for_row(x, row)].output
+    }
+
+    fn leaf_for_row<M: Matrix<T>>(&self, x: &M, row: usize) -> usize {
         let mut result = 0;
         let mut queue: LinkedList<usize> = LinkedList::new();
 
@@ -598,10 +955,8 @@ impl<T: RealNumber> DecisionTreeClassifier<T> {
                 Some(node_id) => {
                     let node = &self.nodes[node_id];
                     if node.true_child == None && node.false_child == None {
-                        result = node.output;
-                    } else if x.get(row, node.split_feature)
-                        <= node.split_value.unwrap_or_else(T::nan)
-                    {
+                        result = node_id;
+                    } else if node.split_input(x, row) <= node.split_value.unwrap_or_else(T::nan) {
                         queue.push_back(node.true_child.unwrap());
                     } else {
                         queue.push_back(node.false_child.unwrap());
@@ -645,15 +1000,16 @@ impl<T: RealNumber> DecisionTreeClassifier<T> {
             return false;
         }
 
-        let mut count = vec![0; self.num_classes];
-        let mut false_count = vec![0; self.num_classes];
+        let mut count = vec![T::zero(); self.num_classes];
+        let mut false_count = vec![T::zero(); self.num_classes];
         for i in 0..n_rows {
             if visitor.samples[i] > 0 {
-                count[visitor.y[i]] += visitor.samples[i];
+                count[visitor.y[i]] += visitor.sample_count(i);
             }
         }
+        let n_weight = count.iter().fold(T::zero(), |acc, c| acc + *c);
 
-        let parent_impurity = impurity(&self.parameters.criterion, &count, n);
+        let parent_impurity = impurity(&self.parameters.criterion, &count, n_weight);
 
         let mut variables = (0..n_attr).collect::<Vec<_>>();
 
@@ -662,16 +1018,68 @@ impl<T: RealNumber> DecisionTreeClassifier<T> {
         }
 
         for variable in variables.iter().take(mtry) {
+            let x = visitor.x;
+            let order = visitor.order;
             self.find_best_split(
                 visitor,
                 n,
+                n_weight,
                 &count,
                 &mut false_count,
                 parent_impurity,
+                &order[*variable],
+                |i| x.get(i, *variable),
                 *variable,
+                None,
             );
         }
 
+        if let SplitType::Oblique {
+            max_features,
+            alpha,
+        } = self.parameters.split_type
+        {
+            let features: Vec<usize> = variables.iter().take(mtry).copied().collect();
+            let majority = which_max(&count);
+            let target: Vec<T> = visitor
+                .y
+                .iter()
+                .map(|y_i| {
+                    if *y_i == majority {
+                        T::one()
+                    } else {
+                        T::zero()
+                    }
+                })
+                .collect();
+            if let Some(weights) = oblique::ridge_direction(
+                visitor.x,
+                &target,
+                &visitor.samples,
+                &features,
+                max_features,
+                T::from_f64(alpha).unwrap(),
+            ) {
+                let mut projection: Vec<T> = (0..n_rows)
+                    .map(|i| oblique::project(visitor.x, i, &weights))
+                    .collect();
+                let order = projection.quick_argsort_mut();
+                let (x, feature) = (visitor.x, weights[0].0);
+                self.find_best_split(
+                    visitor,
+                    n,
+                    n_weight,
+                    &count,
+                    &mut false_count,
+                    parent_impurity,
+                    &order,
+                    |i| oblique::project(x, i, &weights),
+                    feature,
+                    Some(&weights),
+                );
+            }
+        }
+
         self.nodes[visitor.node].split_score != Option::None
     }
 
@@ -679,31 +1087,38 @@ impl<T: RealNumber> DecisionTreeClassifier<T> {
         &mut self,
         visitor: &mut NodeVisitor<'_, T, M>,
         n: usize,
-        count: &[usize],
-        false_count: &mut [usize],
+        n_weight: T,
+        count: &[T],
+        false_count: &mut [T],
         parent_impurity: T,
+        order: &[usize],
+        value: impl Fn(usize) -> T,
         j: usize,
+        weights: Option<&Vec<(usize, T)>>,
     ) {
-        let mut true_count = vec![0; self.num_classes];
+        let mut true_count = vec![T::zero(); self.num_classes];
+        let mut tc = 0;
         let mut prevx = T::nan();
         let mut prevy = 0;
 
-        for i in visitor.order[j].iter() {
+        for i in order.iter() {
             if visitor.samples[*i] > 0 {
-                if prevx.is_nan() || visitor.x.get(*i, j) == prevx || visitor.y[*i] == prevy {
-                    prevx = visitor.x.get(*i, j);
+                let x_ij = value(*i);
+                if prevx.is_nan() || x_ij == prevx || visitor.y[*i] == prevy {
+                    prevx = x_ij;
                     prevy = visitor.y[*i];
-                    true_count[visitor.y[*i]] += visitor.samples[*i];
+                    true_count[visitor.y[*i]] += visitor.sample_count(*i);
+                    tc += visitor.samples[*i];
                     continue;
                 }
 
-                let tc = true_count.iter().sum();
                 let fc = n - tc;
 
                 if tc < self.parameters.min_samples_leaf || fc < self.parameters.min_samples_leaf {
-                    prevx = visitor.x.get(*i, j);
+                    prevx = x_ij;
                     prevy = visitor.y[*i];
-                    true_count[visitor.y[*i]] += visitor.samples[*i];
+                    true_count[visitor.y[*i]] += visitor.sample_count(*i);
+                    tc += visitor.samples[*i];
                     continue;
                 }
 
@@ -711,28 +1126,25 @@ impl<T: RealNumber> DecisionTreeClassifier<T> {
                     false_count[l] = count[l] - true_count[l];
                 }
 
-                let true_label = which_max(&true_count);
-                let false_label = which_max(false_count);
+                let tw = true_count.iter().fold(T::zero(), |acc, c| acc + *c);
+                let fw = n_weight - tw;
                 let gain = parent_impurity
-                    - T::from(tc).unwrap() / T::from(n).unwrap()
-                        * impurity(&self.parameters.criterion, &true_count, tc)
-                    - T::from(fc).unwrap() / T::from(n).unwrap()
-                        * impurity(&self.parameters.criterion, false_count, fc);
+                    - tw / n_weight * impurity(&self.parameters.criterion, &true_count, tw)
+                    - fw / n_weight * impurity(&self.parameters.criterion, false_count, fw);
 
                 if self.nodes[visitor.node].split_score == Option::None
                     || gain > self.nodes[visitor.node].split_score.unwrap()
                 {
                     self.nodes[visitor.node].split_feature = j;
-                    self.nodes[visitor.node].split_value =
-                        Option::Some((visitor.x.get(*i, j) + prevx) / T::two());
+                    self.nodes[visitor.node].split_value = Option::Some((x_ij + prevx) / T::two());
                     self.nodes[visitor.node].split_score = Option::Some(gain);
-                    visitor.true_child_output = true_label;
-                    visitor.false_child_output = false_label;
+                    self.nodes[visitor.node].split_weights = weights.cloned();
                 }
 
-                prevx = visitor.x.get(*i, j);
+                prevx = x_ij;
                 prevy = visitor.y[*i];
-                true_count[visitor.y[*i]] += visitor.samples[*i];
+                true_count[visitor.y[*i]] += visitor.sample_count(*i);
+                tc += visitor.samples[*i];
             }
         }
     }
@@ -747,18 +1159,22 @@ impl<T: RealNumber> DecisionTreeClassifier<T> {
         let (n, _) = visitor.x.shape();
         let mut tc = 0;
         let mut fc = 0;
+        let mut true_count = vec![T::zero(); self.num_classes];
+        let mut false_count = vec![T::zero(); self.num_classes];
         let mut true_samples: Vec<usize> = vec![0; n];
 
         for (i, true_sample) in true_samples.iter_mut().enumerate().take(n) {
             if visitor.samples[i] > 0 {
-                if visitor.x.get(i, self.nodes[visitor.node].split_feature)
+                if self.nodes[visitor.node].split_input(visitor.x, i)
                     <= self.nodes[visitor.node].split_value.unwrap_or_else(T::nan)
                 {
                     *true_sample = visitor.samples[i];
                     tc += *true_sample;
+                    true_count[visitor.y[i]] += visitor.sample_count(i);
                     visitor.samples[i] = 0;
                 } else {
                     fc += visitor.samples[i];
+                    false_count[visitor.y[i]] += visitor.sample_count(i);
                 }
             }
         }
@@ -767,15 +1183,15 @@ impl<T: RealNumber> DecisionTreeClassifier<T> {
             self.nodes[visitor.node].split_feature = 0;
             self.nodes[visitor.node].split_value = Option::None;
             self.nodes[visitor.node].split_score = Option::None;
+            self.nodes[visitor.node].split_weights = Option::None;
             return false;
         }
 
         let true_child_idx = self.nodes.len();
-        self.nodes
-            .push(Node::new(true_child_idx, visitor.true_child_output));
+        self.nodes.push(Node::new(true_child_idx, &true_count, tc));
         let false_child_idx = self.nodes.len();
         self.nodes
-            .push(Node::new(false_child_idx, visitor.false_child_output));
+            .push(Node::new(false_child_idx, &false_count, fc));
 
         self.nodes[visitor.node].true_child = Some(true_child_idx);
         self.nodes[visitor.node].false_child = Some(false_child_idx);
@@ -785,6 +1201,7 @@ impl<T: RealNumber> DecisionTreeClassifier<T> {
         let mut true_visitor = NodeVisitor::<T, M>::new(
             true_child_idx,
             true_samples,
+            visitor.sample_weight,
             visitor.order,
             visitor.x,
             visitor.y,
@@ -798,6 +1215,7 @@ impl<T: RealNumber> DecisionTreeClassifier<T> {
         let mut false_visitor = NodeVisitor::<T, M>::new(
             false_child_idx,
             visitor.samples,
+            visitor.sample_weight,
             visitor.order,
             visitor.x,
             visitor.y,
@@ -812,10 +1230,112 @@ impl<T: RealNumber> DecisionTreeClassifier<T> {
     }
 }
 
+#[cfg(feature = "onnx")]
+impl<T: RealNumber> DecisionTreeClassifier<T> {
+    /// Adds all nodes of the tree to an ONNX tree ensemble, `leaf_weights` maps the class probabilities of a leaf to its weights.
+    pub(crate) fn add_to_ensemble(
+        &self,
+        ensemble: &mut TreeEnsemble,
+        tree_id: usize,
+        leaf_weights: impl Fn(&[T]) -> Vec<(usize, f32)>,
+    ) -> Result<(), Failed> {
+        ensemble.add_tree(tree_id, self.nodes(), |node| {
+            leaf_weights(&self.nodes[node.index].probabilities)
+        })
+    }
+}
+
+/// The scores of the classes are the class probabilities of the leaf, the same as [`predict_proba`](DecisionTreeClassifier::predict_proba).
+#[cfg(feature = "onnx")]
+impl<T: RealNumber> ToOnnx for DecisionTreeClassifier<T> {
+    fn to_onnx(&self) -> Result<Vec<u8>, Failed> {
+        let labels = onnx::class_labels(&self.classes)?;
+        let mut ensemble = TreeEnsemble::default();
+        self.add_to_ensemble(&mut ensemble, 0, |probabilities| {
+            probabilities
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| **p > T::zero())
+                .map(|(k, p)| (k, p.to_f32().unwrap()))
+                .collect()
+        })?;
+        Ok(ensemble
+            .classifier::<T>(labels, &[], "NONE")
+            .classifier_model(self.num_features, self.num_classes))
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber> DecisionTreeClassifier<T> {
+    /// Builds a tree from the nodes of a PMML tree model, statistics of the splits are not restored.
+    pub(crate) fn from_pmml_nodes(
+        nodes: Vec<tree::PmmlNode<T>>,
+        classes: Vec<T>,
+        num_features: usize,
+    ) -> Result<Self, Failed> {
+        let depth = tree::depth(&nodes);
+        let nodes = nodes
+            .into_iter()
+            .enumerate()
+            .map(|(index, node)| {
+                Ok(Node {
+                    _index: index,
+                    output: tree::class_index(&classes, node.score)?,
+                    n_samples: node.n_samples,
+                    probabilities: node.probabilities,
+                    split_feature: node.split.map_or(0, |(feature, _)| feature),
+                    split_value: node.split.map(|(_, threshold)| threshold),
+                    split_score: None,
+                    split_weights: None,
+                    true_child: node.children.map(|(true_child, _)| true_child),
+                    false_child: node.children.map(|(_, false_child)| false_child),
+                })
+            })
+            .collect::<Result<Vec<Node<T>>, Failed>>()?;
+        Ok(DecisionTreeClassifier {
+            nodes,
+            parameters: DecisionTreeClassifierParameters::default(),
+            num_classes: classes.len(),
+            classes,
+            depth,
+            num_features,
+        })
+    }
+
+    /// Converts the nodes of the tree to PMML nodes.
+    pub(crate) fn pmml_nodes(&self) -> Result<Vec<tree::PmmlNode<T>>, Failed> {
+        tree::pmml_nodes(self.nodes(), |index| {
+            self.nodes[index].probabilities.clone()
+        })
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber> ToPmml for DecisionTreeClassifier<T> {
+    fn to_pmml(&self) -> Result<String, Failed> {
+        let target = Target::Categorical(&self.classes);
+        let model = tree::tree_model(&self.pmml_nodes()?, self.num_features, &target);
+        Ok(pmml::document(self.num_features, &target, model))
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber> FromPmml for DecisionTreeClassifier<T> {
+    fn from_pmml(pmml: &str) -> Result<Self, Failed> {
+        let document = Document::parse(pmml)?;
+        let (model, schema) = document.model("TreeModel", "classification")?;
+        let classes = document.classes(&schema)?;
+        let nodes = tree::read_tree(model, &schema, &classes)?;
+        Self::from_pmml_nodes(nodes, classes, schema.n_features())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::FailedError;
     use crate::linalg::naive::dense_matrix::DenseMatrix;
+    use crate::linalg::BaseMatrix;
 
     #[test]
     fn search_parameters() {
@@ -844,15 +1364,16 @@ mod tests {
     #[test]
     fn gini_impurity() {
         assert!(
-            (impurity::<f64>(&SplitCriterion::Gini, &vec![7, 3], 10) - 0.42).abs()
+            (impurity::<f64>(&SplitCriterion::Gini, &vec![7., 3.], 10.) - 0.42).abs()
                 < std::f64::EPSILON
         );
         assert!(
-            (impurity::<f64>(&SplitCriterion::Entropy, &vec![7, 3], 10) - 0.8812908992306927).abs()
+            (impurity::<f64>(&SplitCriterion::Entropy, &vec![7., 3.], 10.) - 0.8812908992306927)
+                .abs()
                 < std::f64::EPSILON
         );
         assert!(
-            (impurity::<f64>(&SplitCriterion::ClassificationError, &vec![7, 3], 10) - 0.3).abs()
+            (impurity::<f64>(&SplitCriterion::ClassificationError, &vec![7., 3.], 10.) - 0.3).abs()
                 < std::f64::EPSILON
         );
     }
@@ -903,7 +1424,10 @@ mod tests {
                     max_depth: Some(3),
                     min_samples_leaf: 1,
                     min_samples_split: 2,
-                    seed: None
+                    seed: None,
+                    split_type: SplitType::AxisAligned,
+                    max_leaf_nodes: None,
+                    growth_strategy: GrowthStrategy::BreadthFirst,
                 }
             )
             .unwrap()
@@ -911,6 +1435,207 @@ mod tests {
         );
     }
 
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_max_leaf_nodes() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 1., 1., 0.],
+            &[1., 1., 1., 0.],
+            &[1., 1., 1., 1.],
+            &[1., 1., 0., 0.],
+            &[1., 1., 0., 1.],
+            &[1., 0., 1., 0.],
+            &[1., 0., 1., 0.],
+            &[1., 0., 1., 1.],
+            &[1., 0., 0., 0.],
+            &[1., 0., 0., 1.],
+            &[0., 1., 1., 0.],
+            &[0., 1., 1., 0.],
+            &[0., 1., 1., 1.],
+            &[0., 1., 0., 0.],
+            &[0., 1., 0., 1.],
+            &[0., 0., 1., 0.],
+            &[0., 0., 1., 0.],
+            &[0., 0., 1., 1.],
+            &[0., 0., 0., 0.],
+            &[0., 0., 0., 1.],
+        ]);
+        let y = vec![
+            1., 1., 0., 0., 0., 1., 1., 0., 0., 0., 1., 1., 0., 0., 0., 1., 1., 0., 0., 0.,
+        ];
+
+        for growth_strategy in [GrowthStrategy::BreadthFirst, GrowthStrategy::BestFirst] {
+            for max_leaf_nodes in 1..4 {
+                let tree = DecisionTreeClassifier::fit(
+                    &x,
+                    &y,
+                    DecisionTreeClassifierParameters::default()
+                        .with_max_leaf_nodes(max_leaf_nodes)
+                        .with_growth_strategy(growth_strategy.clone()),
+                )
+                .unwrap();
+                assert_eq!(
+                    max_leaf_nodes,
+                    tree.nodes().filter(|node| node.is_leaf()).count()
+                );
+            }
+        }
+
+        let tree = DecisionTreeClassifier::fit(
+            &x,
+            &y,
+            DecisionTreeClassifierParameters::default()
+                .with_growth_strategy(GrowthStrategy::BestFirst),
+        )
+        .unwrap();
+        assert_eq!(y, tree.predict(&x).unwrap());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn inspect_nodes() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 1., 1., 0.],
+            &[1., 1., 1., 0.],
+            &[1., 1., 1., 1.],
+            &[1., 1., 0., 0.],
+            &[1., 1., 0., 1.],
+            &[1., 0., 1., 0.],
+            &[1., 0., 1., 0.],
+            &[1., 0., 1., 1.],
+            &[1., 0., 0., 0.],
+            &[1., 0., 0., 1.],
+            &[0., 1., 1., 0.],
+            &[0., 1., 1., 0.],
+            &[0., 1., 1., 1.],
+            &[0., 1., 0., 0.],
+            &[0., 1., 0., 1.],
+            &[0., 0., 1., 0.],
+            &[0., 0., 1., 0.],
+            &[0., 0., 1., 1.],
+            &[0., 0., 0., 0.],
+            &[0., 0., 0., 1.],
+        ]);
+        let y = vec![
+            1., 1., 0., 0., 0., 1., 1., 0., 0., 0., 1., 1., 0., 0., 0., 1., 1., 0., 0., 0.,
+        ];
+
+        let tree = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
+        let nodes: Vec<_> = tree.nodes().collect();
+
+        assert_eq!(tree.nodes.len(), nodes.len());
+        assert_eq!(20, nodes[0].n_samples);
+        assert_eq!(1, nodes[0].depth);
+        assert_eq!(
+            tree.depth(),
+            nodes.iter().map(|node| node.depth).max().unwrap()
+        );
+
+        for node in nodes.iter() {
+            assert!(node.output == 0. || node.output == 1.);
+            if node.is_leaf() {
+                assert!(node.split_feature.is_none() && node.threshold.is_none());
+            } else {
+                let true_child = &nodes[node.true_child.unwrap()];
+                let false_child = &nodes[node.false_child.unwrap()];
+                assert_eq!(node.n_samples, true_child.n_samples + false_child.n_samples);
+                assert_eq!(node.depth + 1, true_child.depth);
+                assert_eq!(node.depth + 1, false_child.depth);
+                assert!(node.split_feature.unwrap() < 4);
+            }
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_with_weights() {
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.]]);
+        let y: Vec<f64> = vec![0., 1., 0., 1.];
+        let parameters = DecisionTreeClassifierParameters::default().with_max_depth(1);
+
+        let tree = DecisionTreeClassifier::fit_with_weights(
+            &x,
+            &y,
+            &vec![1., 1., 1., 1.],
+            parameters.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            tree,
+            DecisionTreeClassifier::fit(&x, &y, parameters.clone()).unwrap()
+        );
+
+        let tree = DecisionTreeClassifier::fit_with_weights(
+            &x,
+            &y,
+            &vec![1., 1., 1., 10.],
+            parameters.clone(),
+        )
+        .unwrap();
+        assert_eq!(tree.predict(&x).unwrap(), vec![0., 0., 0., 1.]);
+
+        let y_proba = tree.predict_proba(&x).unwrap();
+        assert!((y_proba.get(0, 0) - 2. / 3.).abs() < 1e-8);
+        assert!((y_proba.get(3, 1) - 1.).abs() < 1e-8);
+
+        assert!(DecisionTreeClassifier::fit_with_weights(
+            &x,
+            &y,
+            &vec![1., 1., 1.],
+            parameters.clone()
+        )
+        .is_err());
+        assert!(DecisionTreeClassifier::fit_with_weights(
+            &x,
+            &y,
+            &vec![1., 1., -1., 1.],
+            parameters
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn predict_proba() {
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.], &[5.]]);
+        let y: Vec<f64> = vec![0., 0., 1., 0., 1.];
+
+        let tree = DecisionTreeClassifier::fit(
+            &x,
+            &y,
+            DecisionTreeClassifierParameters::default().with_max_depth(1),
+        )
+        .unwrap();
+        let y_proba = tree.predict_proba(&x).unwrap();
+
+        assert_eq!(y_proba.shape(), (5, 2));
+        for i in 0..5 {
+            assert!((y_proba.get(i, 0) + y_proba.get(i, 1) - 1.).abs() < 1e-8);
+        }
+        assert_eq!(y_proba.get(0, 0), 1.);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn non_finite_values() {
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.]]);
+
+        assert_eq!(
+            DecisionTreeClassifier::fit(&x, &vec![0., f64::NAN, 1., 1.], Default::default())
+                .unwrap_err(),
+            Failed::fit("y contains NaN at index 1")
+        );
+
+        let tree =
+            DecisionTreeClassifier::fit(&x, &vec![0., 0., 1., 1.], Default::default()).unwrap();
+        let x_nan = DenseMatrix::from_2d_array(&[&[1.], &[f64::NAN]]);
+        assert_eq!(
+            tree.predict(&x_nan).unwrap_err().error(),
+            FailedError::PredictFailed
+        );
+        assert!(tree.predict_proba(&x_nan).is_err());
+    }
+
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     #[test]
     fn fit_predict_baloons() {
@@ -948,6 +1673,69 @@ mod tests {
         );
     }
 
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_predict_oblique() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 0.],
+            &[2., 1.],
+            &[3., 2.],
+            &[4., 3.],
+            &[5., 4.],
+            &[0., 1.],
+            &[1., 2.],
+            &[2., 3.],
+            &[3., 4.],
+            &[4., 5.],
+        ]);
+        let y = vec![0., 0., 0., 0., 0., 1., 1., 1., 1., 1.];
+
+        let axis_aligned = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
+        let oblique = DecisionTreeClassifier::fit(
+            &x,
+            &y,
+            DecisionTreeClassifierParameters::default().with_split_type(SplitType::Oblique {
+                max_features: 2,
+                alpha: 1e-3,
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(y, oblique.predict(&x).unwrap());
+        assert_eq!(3, oblique.nodes.len());
+        assert!(oblique.nodes[0].split_weights.is_some());
+        assert_ne!(y, axis_aligned.predict(&x).unwrap());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn feature_importances() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 5., 3.],
+            &[2., 5., 1.],
+            &[3., 5., 2.],
+            &[4., 5., 3.],
+            &[5., 5., 1.],
+            &[6., 5., 2.],
+            &[7., 5., 3.],
+            &[8., 5., 1.],
+        ]);
+        let y: Vec<f64> = vec![0., 0., 0., 1., 1., 1., 0., 0.];
+
+        let tree = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
+        let importances = tree.feature_importances();
+
+        assert_eq!(importances.len(), 3);
+        assert_eq!(importances[1], 0.);
+        assert!((importances.iter().sum::<f64>() - 1.).abs() < 1e-8);
+        assert!(importances[0] > importances[2]);
+        assert_eq!(
+            tree.split_counts().iter().sum::<usize>(),
+            tree.nodes().filter(|node| !node.is_leaf()).count()
+        );
+        assert_eq!(tree.split_counts()[1], 0);
+    }
+
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     #[test]
     #[cfg(feature = "serde")]
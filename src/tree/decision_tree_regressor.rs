@@ -50,6 +50,10 @@
 //! let y_hat = tree.predict(&x).unwrap(); // use the same data for prediction
 //! ```
 //!
+//! When the tree is built with `keep_leaf_values` set to `true` every leaf stores the response values of the training observations that fall into it.
+//! This turns the tree into a quantile regression tree: instead of the mean, [`predict_quantile`](crate::tree::decision_tree_regressor::DecisionTreeRegressor::predict_quantile) returns
+//! an arbitrary quantile of the conditional distribution of the response, e.g. the 5% and 95% quantiles give a 90% prediction interval.
+//!
 //! ## References:
 //!
 //! * ["Classification and regression trees", Breiman, L, Friedman, J H, Olshen, R A, and Stone, C J, 1984](https://www.sciencebase.gov/catalog/item/545d07dfe4b0ba8303f728c1)
@@ -58,9 +62,12 @@
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 
-use std::collections::LinkedList;
-use std::default::Default;
-use std::fmt::Debug;
+use alloc::collections::LinkedList;
+#[cfg(feature = "pmml")]
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::default::Default;
+use core::fmt::Debug;
 
 use rand::seq::SliceRandom;
 use rand::Rng;
@@ -68,11 +75,20 @@ use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::algorithm::sort::quick_sort::QuickArgSort;
-use crate::api::{Predictor, SupervisedEstimator};
-use crate::error::Failed;
-use crate::linalg::Matrix;
+use crate::api::{FeatureImportance, Predictor, SupervisedEstimator};
+use crate::error::{Failed, FailedError};
+use crate::linalg::{BaseVector, Matrix};
 use crate::math::num::RealNumber;
+#[cfg(feature = "onnx")]
+use crate::onnx::{tree::TreeEnsemble, ToOnnx};
+use crate::parallel::map_indices;
+#[cfg(feature = "pmml")]
+use crate::pmml::{self, tree, Document, FromPmml, Target, ToPmml};
 use crate::rand::get_rng_impl;
+use crate::tree::node::{feature_importances, node_depths, TreeNode};
+use crate::tree::oblique::{self, SplitType};
+use crate::tree::{pop_best, GrowthStrategy};
+use crate::validation::{check_fit, check_predict};
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
@@ -90,6 +106,18 @@ pub struct DecisionTreeRegressorParameters {
     #[cfg_attr(feature = "serde", serde(default))]
     /// Controls the randomness of the estimator
     pub seed: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether nodes are split on a single feature or on a linear combination of features. See [oblique splits](../oblique/index.html)
+    pub split_type: SplitType,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of leaves in the tree.
+    pub max_leaf_nodes: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Order in which nodes are split. See [tree growth](../index.html)
+    pub growth_strategy: GrowthStrategy,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to keep the training response values in every leaf. This is required for quantile predictions.
+    pub keep_leaf_values: bool,
 }
 
 /// Regression Tree
@@ -99,6 +127,7 @@ pub struct DecisionTreeRegressor<T: RealNumber> {
     nodes: Vec<Node<T>>,
     parameters: DecisionTreeRegressorParameters,
     depth: u16,
+    num_features: usize,
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -106,11 +135,14 @@ pub struct DecisionTreeRegressor<T: RealNumber> {
 struct Node<T: RealNumber> {
     _index: usize,
     output: T,
+    n_samples: usize,
     split_feature: usize,
     split_value: Option<T>,
     split_score: Option<T>,
+    split_weights: Option<Vec<(usize, T)>>,
     true_child: Option<usize>,
     false_child: Option<usize>,
+    leaf_values: Option<Vec<T>>,
 }
 
 impl DecisionTreeRegressorParameters {
@@ -129,6 +161,26 @@ impl DecisionTreeRegressorParameters {
         self.min_samples_split = min_samples_split;
         self
     }
+    /// Whether nodes are split on a single feature or on a linear combination of features.
+    pub fn with_split_type(mut self, split_type: SplitType) -> Self {
+        self.split_type = split_type;
+        self
+    }
+    /// The maximum number of leaves in the tree.
+    pub fn with_max_leaf_nodes(mut self, max_leaf_nodes: usize) -> Self {
+        self.max_leaf_nodes = Some(max_leaf_nodes);
+        self
+    }
+    /// Order in which nodes are split.
+    pub fn with_growth_strategy(mut self, growth_strategy: GrowthStrategy) -> Self {
+        self.growth_strategy = growth_strategy;
+        self
+    }
+    /// Whether to keep the training response values in every leaf. This is required for quantile predictions.
+    pub fn with_keep_leaf_values(mut self, keep_leaf_values: bool) -> Self {
+        self.keep_leaf_values = keep_leaf_values;
+        self
+    }
 }
 
 impl Default for DecisionTreeRegressorParameters {
@@ -138,6 +190,10 @@ impl Default for DecisionTreeRegressorParameters {
             min_samples_leaf: 1,
             min_samples_split: 2,
             seed: None,
+            split_type: SplitType::default(),
+            max_leaf_nodes: None,
+            growth_strategy: GrowthStrategy::default(),
+            keep_leaf_values: false,
         }
     }
 }
@@ -158,6 +214,18 @@ pub struct DecisionTreeRegressorSearchParameters {
     #[cfg_attr(feature = "serde", serde(default))]
     /// Controls the randomness of the estimator
     pub seed: Vec<Option<u64>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Shape of the splits. See [oblique splits](../../tree/oblique/index.html)
+    pub split_type: Vec<SplitType>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to keep the training response values in every leaf. See [Decision Tree Regressor](../../tree/decision_tree_regressor/index.html)
+    pub keep_leaf_values: Vec<bool>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of leaves in the tree. See [Decision Tree Regressor](../../tree/decision_tree_regressor/index.html)
+    pub max_leaf_nodes: Vec<Option<usize>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Order in which nodes are split. See [tree growth](../../tree/index.html)
+    pub growth_strategy: Vec<GrowthStrategy>,
 }
 
 /// DecisionTreeRegressor grid search iterator
@@ -167,6 +235,10 @@ pub struct DecisionTreeRegressorSearchParametersIterator {
     current_min_samples_leaf: usize,
     current_min_samples_split: usize,
     current_seed: usize,
+    current_split_type: usize,
+    current_keep_leaf_values: usize,
+    current_max_leaf_nodes: usize,
+    current_growth_strategy: usize,
 }
 
 impl IntoIterator for DecisionTreeRegressorSearchParameters {
@@ -180,6 +252,10 @@ impl IntoIterator for DecisionTreeRegressorSearchParameters {
             current_min_samples_leaf: 0,
             current_min_samples_split: 0,
             current_seed: 0,
+            current_split_type: 0,
+            current_keep_leaf_values: 0,
+            current_max_leaf_nodes: 0,
+            current_growth_strategy: 0,
         }
     }
 }
@@ -204,6 +280,26 @@ impl Iterator for DecisionTreeRegressorSearchParametersIterator {
                     .min_samples_split
                     .len()
             && self.current_seed == self.decision_tree_regressor_search_parameters.seed.len()
+            && self.current_split_type
+                == self
+                    .decision_tree_regressor_search_parameters
+                    .split_type
+                    .len()
+            && self.current_keep_leaf_values
+                == self
+                    .decision_tree_regressor_search_parameters
+                    .keep_leaf_values
+                    .len()
+            && self.current_max_leaf_nodes
+                == self
+                    .decision_tree_regressor_search_parameters
+                    .max_leaf_nodes
+                    .len()
+            && self.current_growth_strategy
+                == self
+                    .decision_tree_regressor_search_parameters
+                    .growth_strategy
+                    .len()
         {
             return None;
         }
@@ -218,6 +314,19 @@ impl Iterator for DecisionTreeRegressorSearchParametersIterator {
                 .decision_tree_regressor_search_parameters
                 .min_samples_split[self.current_min_samples_split],
             seed: self.decision_tree_regressor_search_parameters.seed[self.current_seed],
+            split_type: self.decision_tree_regressor_search_parameters.split_type
+                [self.current_split_type]
+                .clone(),
+            keep_leaf_values: self
+                .decision_tree_regressor_search_parameters
+                .keep_leaf_values[self.current_keep_leaf_values],
+            max_leaf_nodes: self
+                .decision_tree_regressor_search_parameters
+                .max_leaf_nodes[self.current_max_leaf_nodes],
+            growth_strategy: self
+                .decision_tree_regressor_search_parameters
+                .growth_strategy[self.current_growth_strategy]
+                .clone(),
         };
 
         if self.current_max_depth + 1
@@ -250,11 +359,65 @@ impl Iterator for DecisionTreeRegressorSearchParametersIterator {
             self.current_min_samples_leaf = 0;
             self.current_min_samples_split = 0;
             self.current_seed += 1;
+        } else if self.current_split_type + 1
+            < self
+                .decision_tree_regressor_search_parameters
+                .split_type
+                .len()
+        {
+            self.current_max_depth = 0;
+            self.current_min_samples_leaf = 0;
+            self.current_min_samples_split = 0;
+            self.current_seed = 0;
+            self.current_split_type += 1;
+        } else if self.current_keep_leaf_values + 1
+            < self
+                .decision_tree_regressor_search_parameters
+                .keep_leaf_values
+                .len()
+        {
+            self.current_max_depth = 0;
+            self.current_min_samples_leaf = 0;
+            self.current_min_samples_split = 0;
+            self.current_seed = 0;
+            self.current_split_type = 0;
+            self.current_keep_leaf_values += 1;
+        } else if self.current_max_leaf_nodes + 1
+            < self
+                .decision_tree_regressor_search_parameters
+                .max_leaf_nodes
+                .len()
+        {
+            self.current_max_depth = 0;
+            self.current_min_samples_leaf = 0;
+            self.current_min_samples_split = 0;
+            self.current_seed = 0;
+            self.current_split_type = 0;
+            self.current_keep_leaf_values = 0;
+            self.current_max_leaf_nodes += 1;
+        } else if self.current_growth_strategy + 1
+            < self
+                .decision_tree_regressor_search_parameters
+                .growth_strategy
+                .len()
+        {
+            self.current_max_depth = 0;
+            self.current_min_samples_leaf = 0;
+            self.current_min_samples_split = 0;
+            self.current_seed = 0;
+            self.current_split_type = 0;
+            self.current_keep_leaf_values = 0;
+            self.current_max_leaf_nodes = 0;
+            self.current_growth_strategy += 1;
         } else {
             self.current_max_depth += 1;
             self.current_min_samples_leaf += 1;
             self.current_min_samples_split += 1;
             self.current_seed += 1;
+            self.current_split_type += 1;
+            self.current_keep_leaf_values += 1;
+            self.current_max_leaf_nodes += 1;
+            self.current_growth_strategy += 1;
         }
 
         Some(next)
@@ -270,20 +433,34 @@ impl Default for DecisionTreeRegressorSearchParameters {
             min_samples_leaf: vec![default_params.min_samples_leaf],
             min_samples_split: vec![default_params.min_samples_split],
             seed: vec![default_params.seed],
+            split_type: vec![default_params.split_type],
+            keep_leaf_values: vec![default_params.keep_leaf_values],
+            max_leaf_nodes: vec![default_params.max_leaf_nodes],
+            growth_strategy: vec![default_params.growth_strategy],
         }
     }
 }
 
 impl<T: RealNumber> Node<T> {
-    fn new(index: usize, output: T) -> Self {
+    fn new(index: usize, output: T, n_samples: usize) -> Self {
         Node {
             _index: index,
             output,
+            n_samples,
             split_feature: 0,
             split_value: Option::None,
             split_score: Option::None,
+            split_weights: Option::None,
             true_child: Option::None,
             false_child: Option::None,
+            leaf_values: Option::None,
+        }
+    }
+
+    fn split_input<M: Matrix<T>>(&self, x: &M, row: usize) -> T {
+        match &self.split_weights {
+            Some(weights) => oblique::project(x, row, weights),
+            None => x.get(row, self.split_feature),
         }
     }
 }
@@ -302,6 +479,26 @@ impl<T: RealNumber> PartialEq for Node<T> {
                 (None, None) => true,
                 _ => false,
             }
+            && match (&self.split_weights, &other.split_weights) {
+                (Some(a), Some(b)) => {
+                    a.len() == b.len()
+                        && a.iter()
+                            .zip(b.iter())
+                            .all(|(a, b)| a.0 == b.0 && (a.1 - b.1).abs() < T::epsilon())
+                }
+                (None, None) => true,
+                _ => false,
+            }
+            && match (&self.leaf_values, &other.leaf_values) {
+                (Some(a), Some(b)) => {
+                    a.len() == b.len()
+                        && a.iter()
+                            .zip(b.iter())
+                            .all(|(a, b)| (*a - *b).abs() < T::epsilon())
+                }
+                (None, None) => true,
+                _ => false,
+            }
     }
 }
 
@@ -372,6 +569,12 @@ impl<T: RealNumber, M: Matrix<T>> Predictor<M, M::RowVector> for DecisionTreeReg
     }
 }
 
+impl<T: RealNumber> FeatureImportance<T> for DecisionTreeRegressor<T> {
+    fn feature_importance(&self) -> Vec<T> {
+        self.feature_importances()
+    }
+}
+
 impl<T: RealNumber> DecisionTreeRegressor<T> {
     /// Build a decision tree regressor from the training data.
     /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
@@ -381,6 +584,7 @@ impl<T: RealNumber> DecisionTreeRegressor<T> {
         y: &M::RowVector,
         parameters: DecisionTreeRegressorParameters,
     ) -> Result<DecisionTreeRegressor<T>, Failed> {
+        check_fit(x, y)?;
         let (x_nrows, num_attributes) = x.shape();
         let samples = vec![1; x_nrows];
         DecisionTreeRegressor::fit_weak_learner(x, y, samples, num_attributes, parameters)
@@ -408,7 +612,7 @@ impl<T: RealNumber> DecisionTreeRegressor<T> {
             sum += T::from(*sample_i).unwrap() * y_m.get(0, i);
         }
 
-        let root = Node::new(0, sum / T::from(n).unwrap());
+        let root = Node::new(0, sum / T::from(n).unwrap(), n);
         nodes.push(root);
         let mut order: Vec<Vec<usize>> = Vec::new();
 
@@ -420,6 +624,13 @@ impl<T: RealNumber> DecisionTreeRegressor<T> {
             nodes,
             parameters,
             depth: 0,
+            num_features: num_attributes,
+        };
+
+        let leaf_samples = if tree.parameters.keep_leaf_values {
+            Some(samples.clone())
+        } else {
+            None
         };
 
         let mut visitor = NodeVisitor::<T, M>::new(0, samples, &order, x, &y_m, 1);
@@ -430,11 +641,56 @@ impl<T: RealNumber> DecisionTreeRegressor<T> {
             visitor_queue.push_back(visitor);
         }
 
-        while tree.depth < tree.parameters.max_depth.unwrap_or(std::u16::MAX) {
-            match visitor_queue.pop_front() {
-                Some(node) => tree.split(node, mtry, &mut visitor_queue, &mut rng),
-                None => break,
-            };
+        let max_depth = tree.parameters.max_depth.unwrap_or(core::u16::MAX);
+        let max_leaf_nodes = tree.parameters.max_leaf_nodes.unwrap_or(usize::MAX);
+        let mut n_leaves = 1;
+
+        match tree.parameters.growth_strategy {
+            GrowthStrategy::BreadthFirst => {
+                while tree.depth < max_depth && n_leaves < max_leaf_nodes {
+                    match visitor_queue.pop_front() {
+                        Some(node) => {
+                            if tree.split(node, mtry, &mut visitor_queue, &mut rng) {
+                                n_leaves += 1;
+                            }
+                        }
+                        None => break,
+                    };
+                }
+            }
+            GrowthStrategy::BestFirst => {
+                while n_leaves < max_leaf_nodes {
+                    match pop_best(&mut visitor_queue, |visitor| {
+                        tree.nodes[visitor.node].split_score.unwrap_or_else(T::zero)
+                    }) {
+                        Some(node) => {
+                            if node.level < max_depth
+                                && tree.split(node, mtry, &mut visitor_queue, &mut rng)
+                            {
+                                n_leaves += 1;
+                            }
+                        }
+                        None => break,
+                    };
+                }
+            }
+        }
+
+        if let Some(leaf_samples) = leaf_samples {
+            for (i, sample_i) in leaf_samples.iter().enumerate() {
+                if *sample_i > 0 {
+                    let leaf = tree.leaf_for_row(x, i);
+                    let leaf_values = tree.nodes[leaf].leaf_values.get_or_insert_with(Vec::new);
+                    for _ in 0..*sample_i {
+                        leaf_values.push(y_m.get(0, i));
+                    }
+                }
+            }
+            for node in tree.nodes.iter_mut() {
+                if let Some(leaf_values) = node.leaf_values.as_mut() {
+                    leaf_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                }
+            }
         }
 
         Ok(tree)
@@ -443,19 +699,112 @@ impl<T: RealNumber> DecisionTreeRegressor<T> {
     /// Predict regression value for `x`.
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn predict<M: Matrix<T>>(&self, x: &M) -> Result<M::RowVector, Failed> {
-        let mut result = M::zeros(1, x.shape().0);
+        check_predict(x)?;
+        let (n, _) = x.shape();
+        let y_hat = map_indices(n, |i| self.predict_for_row(x, i));
+
+        Ok(M::RowVector::from_array(&y_hat))
+    }
+
+    /// Predict quantile `q` of the response for `x`. The tree must be fitted with `keep_leaf_values` set to `true`.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    /// * `q` - quantile to predict, a number between 0 and 1.
+    pub fn predict_quantile<M: Matrix<T>>(&self, x: &M, q: T) -> Result<M::RowVector, Failed> {
+        if !(T::zero()..=T::one()).contains(&q) {
+            return Err(Failed::predict(&format!(
+                "Quantile should be between 0 and 1, got {}",
+                q
+            )));
+        }
+        if !self.parameters.keep_leaf_values {
+            return Err(Failed::because(
+                FailedError::PredictFailed,
+                "Need keep_leaf_values=true for quantile predictions.",
+            ));
+        }
 
         let (n, _) = x.shape();
+        let mut result = M::zeros(1, n);
 
         for i in 0..n {
-            result.set(0, i, self.predict_for_row(x, i));
+            let mut values: Vec<(T, T)> = self
+                .leaf_values_for_row(x, i)
+                .iter()
+                .map(|v| (*v, T::one()))
+                .collect();
+            result.set(0, i, weighted_quantile(&mut values, q));
         }
 
         Ok(result.to_row_vector())
     }
 
+    /// Returns a read-only view of all nodes of the tree. The root node comes first and parents come before their children.
+    /// See [tree inspection](../node/index.html).
+    pub fn nodes(&self) -> impl Iterator<Item = TreeNode<'_, T>> + '_ {
+        let depths = node_depths(
+            self.nodes
+                .iter()
+                .map(|node| (node.true_child, node.false_child)),
+        );
+        self.nodes.iter().enumerate().map(move |(index, node)| {
+            let is_leaf = node.true_child.is_none() && node.false_child.is_none();
+            TreeNode {
+                index,
+                depth: depths[index],
+                n_samples: node.n_samples,
+                output: node.output,
+                split_feature: if is_leaf {
+                    None
+                } else {
+                    Some(node.split_feature)
+                },
+                threshold: if is_leaf { None } else { node.split_value },
+                split_weights: if is_leaf {
+                    None
+                } else {
+                    node.split_weights.as_deref()
+                },
+                impurity_decrease: if is_leaf { None } else { node.split_score },
+                true_child: node.true_child,
+                false_child: node.false_child,
+            }
+        })
+    }
+
+    /// Returns the impurity-based importance of every feature: the total decrease of impurity achieved by the splits on the feature,
+    /// normalized so that importances sum to 1. See [tree inspection](../node/index.html).
+    pub fn feature_importances(&self) -> Vec<T> {
+        feature_importances(self.nodes(), self.num_features).0
+    }
+
+    /// Returns the number of nodes split on every feature.
+    pub fn split_counts(&self) -> Vec<usize> {
+        feature_importances(self.nodes(), self.num_features).1
+    }
+
+    /// Number of features of the training set.
+    pub(crate) fn num_features(&self) -> usize {
+        self.num_features
+    }
+
+    /// Returns the depth of the tree, a tree with a single node has depth 1.
+    pub fn depth(&self) -> u16 {
+        u16::max(self.depth, 1)
+    }
+
     pub(crate) fn predict_for_row<M: Matrix<T>>(&self, x: &M, row: usize) -> T {
-        let mut result = T::zero();
+        self.nodes[self.leaf_for_row(x, row)].output
+    }
+
+    pub(crate) fn leaf_values_for_row<M: Matrix<T>>(&self, x: &M, row: usize) -> &[T] {
+        self.nodes[self.leaf_for_row(x, row)]
+            .leaf_values
+            .as_deref()
+            .unwrap_or(&[])
+    }
+
+    fn leaf_for_row<M: Matrix<T>>(&self, x: &M, row: usize) -> usize {
+        let mut result = 0;
         let mut queue: LinkedList<usize> = LinkedList::new();
 
         queue.push_back(0);
@@ -465,10 +814,8 @@ impl<T: RealNumber> DecisionTreeRegressor<T> {
                 Some(node_id) => {
                     let node = &self.nodes[node_id];
                     if node.true_child == None && node.false_child == None {
-                        result = node.output;
-                    } else if x.get(row, node.split_feature)
-                        <= node.split_value.unwrap_or_else(T::nan)
-                    {
+                        result = node_id;
+                    } else if node.split_input(x, row) <= node.split_value.unwrap_or_else(T::nan) {
                         queue.push_back(node.true_child.unwrap());
                     } else {
                         queue.push_back(node.false_child.unwrap());
@@ -487,7 +834,7 @@ impl<T: RealNumber> DecisionTreeRegressor<T> {
         mtry: usize,
         rng: &mut impl Rng,
     ) -> bool {
-        let (_, n_attr) = visitor.x.shape();
+        let (n_rows, n_attr) = visitor.x.shape();
 
         let n: usize = visitor.samples.iter().sum();
 
@@ -507,7 +854,51 @@ impl<T: RealNumber> DecisionTreeRegressor<T> {
             T::from(n).unwrap() * self.nodes[visitor.node].output * self.nodes[visitor.node].output;
 
         for variable in variables.iter().take(mtry) {
-            self.find_best_split(visitor, n, sum, parent_gain, *variable);
+            let x = visitor.x;
+            let order = visitor.order;
+            self.find_best_split(
+                visitor,
+                n,
+                sum,
+                parent_gain,
+                &order[*variable],
+                |i| x.get(i, *variable),
+                *variable,
+                None,
+            );
+        }
+
+        if let SplitType::Oblique {
+            max_features,
+            alpha,
+        } = self.parameters.split_type
+        {
+            let features: Vec<usize> = variables.iter().take(mtry).copied().collect();
+            let target: Vec<T> = (0..n_rows).map(|i| visitor.y.get(0, i)).collect();
+            if let Some(weights) = oblique::ridge_direction(
+                visitor.x,
+                &target,
+                &visitor.samples,
+                &features,
+                max_features,
+                T::from_f64(alpha).unwrap(),
+            ) {
+                let mut projection: Vec<T> = (0..n_rows)
+                    .map(|i| oblique::project(visitor.x, i, &weights))
+                    .collect();
+                let order = projection.quick_argsort_mut();
+                let (x, feature) = (visitor.x, weights[0].0);
+                self.find_best_split(
+                    visitor,
+                    n,
+                    sum,
+                    parent_gain,
+                    &order,
+                    |i| oblique::project(x, i, &weights),
+                    feature,
+                    Some(&weights),
+                );
+            }
         }
 
         self.nodes[visitor.node].split_score != Option::None
@@ -519,16 +910,20 @@ impl<T: RealNumber> DecisionTreeRegressor<T> {
         n: usize,
         sum: T,
         parent_gain: T,
+        order: &[usize],
+        value: impl Fn(usize) -> T,
         j: usize,
+        weights: Option<&Vec<(usize, T)>>,
     ) {
         let mut true_sum = T::zero();
         let mut true_count = 0;
         let mut prevx = T::nan();
 
-        for i in visitor.order[j].iter() {
+        for i in order.iter() {
             if visitor.samples[*i] > 0 {
-                if prevx.is_nan() || visitor.x.get(*i, j) == prevx {
-                    prevx = visitor.x.get(*i, j);
+                let x_ij = value(*i);
+                if prevx.is_nan() || x_ij == prevx {
+                    prevx = x_ij;
                     true_count += visitor.samples[*i];
                     true_sum += T::from(visitor.samples[*i]).unwrap() * visitor.y.get(0, *i);
                     continue;
@@ -539,7 +934,7 @@ impl<T: RealNumber> DecisionTreeRegressor<T> {
                 if true_count < self.parameters.min_samples_leaf
                     || false_count < self.parameters.min_samples_leaf
                 {
-                    prevx = visitor.x.get(*i, j);
+                    prevx = x_ij;
                     true_count += visitor.samples[*i];
                     true_sum += T::from(visitor.samples[*i]).unwrap() * visitor.y.get(0, *i);
                     continue;
@@ -556,14 +951,14 @@ impl<T: RealNumber> DecisionTreeRegressor<T> {
                     || gain > self.nodes[visitor.node].split_score.unwrap()
                 {
                     self.nodes[visitor.node].split_feature = j;
-                    self.nodes[visitor.node].split_value =
-                        Option::Some((visitor.x.get(*i, j) + prevx) / T::two());
+                    self.nodes[visitor.node].split_value = Option::Some((x_ij + prevx) / T::two());
                     self.nodes[visitor.node].split_score = Option::Some(gain);
+                    self.nodes[visitor.node].split_weights = weights.cloned();
                     visitor.true_child_output = true_mean;
                     visitor.false_child_output = false_mean;
                 }
 
-                prevx = visitor.x.get(*i, j);
+                prevx = x_ij;
                 true_sum += T::from(visitor.samples[*i]).unwrap() * visitor.y.get(0, *i);
                 true_count += visitor.samples[*i];
             }
@@ -584,7 +979,7 @@ impl<T: RealNumber> DecisionTreeRegressor<T> {
 
         for (i, true_sample) in true_samples.iter_mut().enumerate().take(n) {
             if visitor.samples[i] > 0 {
-                if visitor.x.get(i, self.nodes[visitor.node].split_feature)
+                if self.nodes[visitor.node].split_input(visitor.x, i)
                     <= self.nodes[visitor.node].split_value.unwrap_or_else(T::nan)
                 {
                     *true_sample = visitor.samples[i];
@@ -600,15 +995,16 @@ impl<T: RealNumber> DecisionTreeRegressor<T> {
             self.nodes[visitor.node].split_feature = 0;
             self.nodes[visitor.node].split_value = Option::None;
             self.nodes[visitor.node].split_score = Option::None;
+            self.nodes[visitor.node].split_weights = Option::None;
             return false;
         }
 
         let true_child_idx = self.nodes.len();
         self.nodes
-            .push(Node::new(true_child_idx, visitor.true_child_output));
+            .push(Node::new(true_child_idx, visitor.true_child_output, tc));
         let false_child_idx = self.nodes.len();
         self.nodes
-            .push(Node::new(false_child_idx, visitor.false_child_output));
+            .push(Node::new(false_child_idx, visitor.false_child_output, fc));
 
         self.nodes[visitor.node].true_child = Some(true_child_idx);
         self.nodes[visitor.node].false_child = Some(false_child_idx);
@@ -645,6 +1041,94 @@ impl<T: RealNumber> DecisionTreeRegressor<T> {
     }
 }
 
+/// Computes quantile `q` of the empirical distribution given by `(value, weight)` pairs,
+/// the smallest value at which the cumulative weight reaches `q` of the total weight.
+pub(crate) fn weighted_quantile<T: RealNumber>(values: &mut [(T, T)], q: T) -> T {
+    if values.is_empty() {
+        return T::nan();
+    }
+    values.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    let total: T = values.iter().map(|(_, w)| *w).sum();
+    let target = q * total;
+    let mut cumulative = T::zero();
+    for (v, w) in values.iter() {
+        cumulative += *w;
+        if cumulative >= target - T::epsilon() * total {
+            return *v;
+        }
+    }
+
+    values[values.len() - 1].0
+}
+
+#[cfg(feature = "onnx")]
+impl<T: RealNumber> ToOnnx for DecisionTreeRegressor<T> {
+    fn to_onnx(&self) -> Result<Vec<u8>, Failed> {
+        let mut ensemble = TreeEnsemble::default();
+        ensemble.add_tree(0, self.nodes(), |node| {
+            vec![(0, node.output.to_f32().unwrap())]
+        })?;
+        Ok(ensemble
+            .regressor("SUM", 0.)
+            .regressor_model(self.num_features))
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber> DecisionTreeRegressor<T> {
+    /// Builds a tree from the nodes of a PMML tree model, statistics of the splits are not restored.
+    pub(crate) fn from_pmml_nodes(nodes: Vec<tree::PmmlNode<T>>, num_features: usize) -> Self {
+        let depth = tree::depth(&nodes);
+        let nodes = nodes
+            .into_iter()
+            .enumerate()
+            .map(|(index, node)| Node {
+                _index: index,
+                output: node.score,
+                n_samples: node.n_samples,
+                split_feature: node.split.map_or(0, |(feature, _)| feature),
+                split_value: node.split.map(|(_, threshold)| threshold),
+                split_score: None,
+                split_weights: None,
+                true_child: node.children.map(|(true_child, _)| true_child),
+                false_child: node.children.map(|(_, false_child)| false_child),
+                leaf_values: None,
+            })
+            .collect();
+        DecisionTreeRegressor {
+            nodes,
+            parameters: DecisionTreeRegressorParameters::default(),
+            depth,
+            num_features,
+        }
+    }
+
+    /// Converts the nodes of the tree to PMML nodes.
+    pub(crate) fn pmml_nodes(&self) -> Result<Vec<tree::PmmlNode<T>>, Failed> {
+        tree::pmml_nodes(self.nodes(), |_| Vec::new())
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber> ToPmml for DecisionTreeRegressor<T> {
+    fn to_pmml(&self) -> Result<String, Failed> {
+        let target = Target::Continuous;
+        let model = tree::tree_model(&self.pmml_nodes()?, self.num_features, &target);
+        Ok(pmml::document(self.num_features, &target, model))
+    }
+}
+
+#[cfg(feature = "pmml")]
+impl<T: RealNumber> FromPmml for DecisionTreeRegressor<T> {
+    fn from_pmml(pmml: &str) -> Result<Self, Failed> {
+        let document = Document::parse(pmml)?;
+        let (model, schema) = document.model("TreeModel", "regression")?;
+        let nodes = tree::read_tree(model, &schema, &[])?;
+        Ok(Self::from_pmml_nodes(nodes, schema.n_features()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -719,6 +1203,10 @@ mod tests {
                 min_samples_leaf: 2,
                 min_samples_split: 6,
                 seed: None,
+                split_type: SplitType::AxisAligned,
+                max_leaf_nodes: None,
+                growth_strategy: GrowthStrategy::BreadthFirst,
+                keep_leaf_values: false,
             },
         )
         .and_then(|t| t.predict(&x))
@@ -740,6 +1228,10 @@ mod tests {
                 min_samples_leaf: 1,
                 min_samples_split: 3,
                 seed: None,
+                split_type: SplitType::AxisAligned,
+                max_leaf_nodes: None,
+                growth_strategy: GrowthStrategy::BreadthFirst,
+                keep_leaf_values: false,
             },
         )
         .and_then(|t| t.predict(&x))
@@ -750,6 +1242,196 @@ mod tests {
         }
     }
 
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_best_first() {
+        let x =
+            DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.], &[5.], &[6.], &[7.], &[8.]]);
+        let y: Vec<f64> = vec![1., 1.1, 1.2, 1.3, 10., 20., 30., 40.];
+
+        let rss = |tree: &DecisionTreeRegressor<f64>| -> f64 {
+            let y_hat = tree.predict(&x).unwrap();
+            y.iter()
+                .zip(y_hat.iter())
+                .map(|(y, y_hat)| (y - y_hat).powi(2))
+                .sum()
+        };
+
+        let breadth_first = DecisionTreeRegressor::fit(
+            &x,
+            &y,
+            DecisionTreeRegressorParameters::default().with_max_leaf_nodes(3),
+        )
+        .unwrap();
+        let best_first = DecisionTreeRegressor::fit(
+            &x,
+            &y,
+            DecisionTreeRegressorParameters::default()
+                .with_max_leaf_nodes(3)
+                .with_growth_strategy(GrowthStrategy::BestFirst),
+        )
+        .unwrap();
+
+        assert_eq!(3, breadth_first.nodes().filter(|n| n.is_leaf()).count());
+        assert_eq!(3, best_first.nodes().filter(|n| n.is_leaf()).count());
+        assert!(rss(&best_first) < rss(&breadth_first));
+        assert_eq!(Some(5.5), best_first.nodes().next().unwrap().threshold);
+        assert_eq!(Some(6.5), best_first.nodes().nth(2).unwrap().threshold);
+
+        let unbounded = DecisionTreeRegressor::fit(
+            &x,
+            &y,
+            DecisionTreeRegressorParameters::default()
+                .with_growth_strategy(GrowthStrategy::BestFirst)
+                .with_max_depth(3),
+        )
+        .unwrap();
+        assert_eq!(3, unbounded.depth());
+        assert_eq!(4, unbounded.nodes().filter(|n| n.is_leaf()).count());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn inspect_nodes() {
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.], &[5.], &[6.]]);
+        let y: Vec<f64> = vec![1., 1., 1., 4., 4., 4.];
+
+        let tree = DecisionTreeRegressor::fit(
+            &x,
+            &y,
+            DecisionTreeRegressorParameters::default().with_max_depth(2),
+        )
+        .unwrap();
+        let nodes: Vec<_> = tree.nodes().collect();
+
+        assert_eq!(3, nodes.len());
+        assert_eq!(2, tree.depth());
+
+        assert_eq!(6, nodes[0].n_samples);
+        assert!((nodes[0].output - 2.5).abs() < 1e-8);
+        assert_eq!(Some(0), nodes[0].split_feature);
+        assert_eq!(Some(3.5), nodes[0].threshold);
+        assert_eq!(Some(1), nodes[0].true_child);
+        assert_eq!(Some(2), nodes[0].false_child);
+
+        assert!(nodes[1].is_leaf() && nodes[2].is_leaf());
+        assert_eq!(
+            (3, 2, 1.),
+            (nodes[1].n_samples, nodes[1].depth, nodes[1].output)
+        );
+        assert_eq!(
+            (3, 2, 4.),
+            (nodes[2].n_samples, nodes[2].depth, nodes[2].output)
+        );
+        assert!(nodes[1].threshold.is_none());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn fit_predict_oblique() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 0.],
+            &[2., 1.],
+            &[3., 2.],
+            &[4., 3.],
+            &[0., 1.],
+            &[1., 2.],
+            &[2., 3.],
+            &[3., 4.],
+        ]);
+        let y: Vec<f64> = vec![10., 10., 10., 10., 20., 20., 20., 20.];
+
+        let tree = DecisionTreeRegressor::fit(
+            &x,
+            &y,
+            DecisionTreeRegressorParameters::default().with_split_type(SplitType::Oblique {
+                max_features: 2,
+                alpha: 1e-3,
+            }),
+        )
+        .unwrap();
+
+        assert!(tree.nodes[0].split_weights.is_some());
+
+        let y_hat = tree.predict(&x).unwrap();
+        for i in 0..y_hat.len() {
+            assert!((y_hat[i] - y[i]).abs() < 1e-8);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn predict_quantile() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1.],
+            &[1.],
+            &[1.],
+            &[1.],
+            &[1.],
+            &[5.],
+            &[5.],
+            &[5.],
+            &[5.],
+            &[5.],
+        ]);
+        let y: Vec<f64> = vec![1., 2., 3., 4., 5., 10., 20., 30., 40., 50.];
+
+        let tree = DecisionTreeRegressor::fit(&x, &y, Default::default()).unwrap();
+        assert!(tree.predict_quantile(&x, 0.5).is_err());
+
+        let tree = DecisionTreeRegressor::fit(
+            &x,
+            &y,
+            DecisionTreeRegressorParameters::default().with_keep_leaf_values(true),
+        )
+        .unwrap();
+
+        let x_test = DenseMatrix::from_2d_array(&[&[0.], &[6.]]);
+        assert_eq!(vec![1., 10.], tree.predict_quantile(&x_test, 0.).unwrap());
+        assert_eq!(vec![3., 30.], tree.predict_quantile(&x_test, 0.5).unwrap());
+        assert_eq!(vec![5., 50.], tree.predict_quantile(&x_test, 1.).unwrap());
+        assert_eq!(vec![3., 30.], tree.predict(&x_test).unwrap());
+        assert!(tree.predict_quantile(&x_test, 1.5).is_err());
+    }
+
+    #[test]
+    fn weighted_quantile() {
+        let mut values = vec![(4., 1.), (1., 1.), (3., 1.), (2., 1.)];
+        assert_eq!(1., super::weighted_quantile(&mut values, 0.));
+        assert_eq!(2., super::weighted_quantile(&mut values, 0.5));
+        assert_eq!(3., super::weighted_quantile(&mut values, 0.6));
+        assert_eq!(4., super::weighted_quantile(&mut values, 1.));
+
+        let mut values = vec![(1., 0.1), (2., 0.8), (3., 0.1)];
+        assert_eq!(2., super::weighted_quantile(&mut values, 0.2));
+        assert_eq!(2., super::weighted_quantile(&mut values, 0.9));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn feature_importances() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 5., 3.],
+            &[2., 5., 1.],
+            &[3., 5., 2.],
+            &[4., 5., 3.],
+            &[5., 5., 1.],
+            &[6., 5., 2.],
+            &[7., 5., 3.],
+            &[8., 5., 1.],
+        ]);
+        let y: Vec<f64> = vec![1., 1., 1., 1., 5., 5., 5., 5.];
+
+        let tree = DecisionTreeRegressor::fit(&x, &y, Default::default()).unwrap();
+
+        assert_eq!(tree.feature_importances(), vec![1., 0., 0.]);
+        assert_eq!(
+            tree.split_counts().iter().sum::<usize>(),
+            tree.nodes().filter(|node| !node.is_leaf()).count()
+        );
+        assert_eq!(tree.split_counts()[1], 0);
+    }
+
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
     #[test]
     #[cfg(feature = "serde")]
@@ -11,6 +11,10 @@
 //!
 //! SmartCore uses [CART](https://en.wikipedia.org/wiki/Predictive_analytics#Classification_and_regression_trees_.28CART.29) learning technique to build both classification and regression trees.
 //!
+//! By default nodes are split level by level, in the order they are created. With the [best-first](crate::tree::GrowthStrategy::BestFirst) growth strategy
+//! the leaf with the highest reduction of impurity is always split next. Combined with `max_leaf_nodes` this spends a fixed budget of leaves
+//! where it improves the fit the most, the same leaf-wise growth that is used by LightGBM.
+//!
 //! ## References:
 //!
 //! * ["Classification and regression trees", Breiman, L, Friedman, J H, Olshen, R A, and Stone, C J, 1984](https://www.sciencebase.gov/catalog/item/545d07dfe4b0ba8303f728c1)
@@ -19,7 +23,51 @@
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 
+use alloc::collections::LinkedList;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::math::num::RealNumber;
+
 /// Classification tree for dependent variables that take a finite number of unordered values.
 pub mod decision_tree_classifier;
 /// Regression tree for for dependent variables that take continuous or ordered discrete values.
 pub mod decision_tree_regressor;
+/// Read-only view of the nodes of a fitted tree.
+pub mod node;
+/// Oblique splits on sparse linear combinations of features.
+pub mod oblique;
+
+/// Order in which the nodes of a tree are split.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum GrowthStrategy {
+    /// Split nodes level by level, in the order they are created.
+    #[default]
+    BreadthFirst,
+    /// Always split the leaf with the highest reduction of impurity first.
+    BestFirst,
+}
+
+/// Removes the element with the highest `priority` from the queue.
+pub(crate) fn pop_best<T: RealNumber, V>(
+    queue: &mut LinkedList<V>,
+    priority: impl Fn(&V) -> T,
+) -> Option<V> {
+    let best = queue
+        .iter()
+        .enumerate()
+        .fold(None, |best: Option<(usize, T)>, (i, v)| {
+            let p = priority(v);
+            match best {
+                Some((_, best_p)) if best_p >= p => best,
+                _ => Some((i, p)),
+            }
+        })?
+        .0;
+    let mut tail = queue.split_off(best);
+    let result = tail.pop_front();
+    queue.append(&mut tail);
+    result
+}
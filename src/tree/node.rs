@@ -0,0 +1,115 @@
+//! # Tree Inspection
+//!
+//! Fitted [decision trees](../index.html) expose a read-only view of their structure with `nodes()`.
+//! The iterator yields every node of a tree in the order nodes were created, parents always come before their children and the root node comes first.
+//! Use it to compute custom statistics, visualize a tree or convert it to another format.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::tree::decision_tree_regressor::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.]]);
+//! let y = vec![1., 1., 2., 2.];
+//!
+//! let tree = DecisionTreeRegressor::fit(&x, &y, Default::default()).unwrap();
+//!
+//! let root = tree.nodes().next().unwrap();
+//! assert_eq!(root.split_feature, Some(0));
+//! assert_eq!(root.threshold, Some(2.5));
+//!
+//! let n_leaves = tree.nodes().filter(|node| node.is_leaf()).count();
+//! ```
+//!
+//! For a summary of how much every feature contributes to the predictions use `feature_importances()`, the total decrease of impurity
+//! achieved by the splits on a feature normalized to sum to 1, and `split_counts()`, the number of splits on every feature.
+
+use crate::math::num::RealNumber;
+use alloc::vec::Vec;
+
+/// Read-only view of a node of a fitted decision tree.
+#[derive(Debug, Clone)]
+pub struct TreeNode<'a, T: RealNumber> {
+    /// Position of the node in the tree, the root node has index 0.
+    pub index: usize,
+    /// Depth of the node, the root node has depth 1.
+    pub depth: u16,
+    /// Number of training samples that reached the node, counting repeated bootstrap samples.
+    pub n_samples: usize,
+    /// Value predicted for observations that fall into the node: the mean response for regression trees, the majority class for classification trees.
+    pub output: T,
+    /// Feature used to split the node. For an oblique split this is the first feature of the linear combination. `None` for leaves.
+    pub split_feature: Option<usize>,
+    /// Observations with a split value less than or equal to the threshold go to the true child. `None` for leaves.
+    pub threshold: Option<T>,
+    /// Weights of the linear combination of features when the node is split with an [oblique split](../oblique/index.html).
+    pub split_weights: Option<&'a [(usize, T)]>,
+    /// Decrease of the total impurity of the training samples achieved by the split: the decrease of the sum of squared errors for regression trees,
+    /// the decrease of the split criterion weighted by the number of samples for classification trees. `None` for leaves.
+    pub impurity_decrease: Option<T>,
+    /// Index of the child node with observations that satisfy the split condition.
+    pub true_child: Option<usize>,
+    /// Index of the child node with observations that do not satisfy the split condition.
+    pub false_child: Option<usize>,
+}
+
+impl<'a, T: RealNumber> TreeNode<'a, T> {
+    /// Returns `true` if the node has no children.
+    pub fn is_leaf(&self) -> bool {
+        self.true_child.is_none() && self.false_child.is_none()
+    }
+}
+
+/// Computes impurity-based importances, normalized to sum to 1, and the number of splits of every feature.
+/// An oblique split contributes to every feature of its linear combination, the impurity decrease is shared equally between them.
+pub(crate) fn feature_importances<'a, T: RealNumber + 'a>(
+    nodes: impl Iterator<Item = TreeNode<'a, T>>,
+    n_features: usize,
+) -> (Vec<T>, Vec<usize>) {
+    let mut importances = vec![T::zero(); n_features];
+    let mut split_counts = vec![0; n_features];
+
+    for node in nodes.filter(|node| !node.is_leaf()) {
+        let decrease = node.impurity_decrease.unwrap_or_else(T::zero);
+        match node.split_weights {
+            Some(weights) => {
+                let share = decrease / T::from(weights.len()).unwrap();
+                for (j, _) in weights.iter() {
+                    importances[*j] += share;
+                    split_counts[*j] += 1;
+                }
+            }
+            None => {
+                let j = node.split_feature.unwrap();
+                importances[j] += decrease;
+                split_counts[j] += 1;
+            }
+        }
+    }
+
+    let total = importances.iter().fold(T::zero(), |acc, i| acc + *i);
+    if total > T::zero() {
+        for importance in importances.iter_mut() {
+            *importance /= total;
+        }
+    }
+
+    (importances, split_counts)
+}
+
+/// Computes the depth of every node, given the children of each node. Children are expected to be created after their parent.
+pub(crate) fn node_depths(
+    children: impl Iterator<Item = (Option<usize>, Option<usize>)>,
+) -> Vec<u16> {
+    let mut depths = vec![1];
+    for (i, (true_child, false_child)) in children.enumerate() {
+        for child in true_child.iter().chain(false_child.iter()) {
+            if depths.len() <= *child {
+                depths.resize(*child + 1, 0);
+            }
+            depths[*child] = depths[i] + 1;
+        }
+    }
+    depths
+}
@@ -0,0 +1,237 @@
+//! # Oblique Splits
+//!
+//! By default every internal node of a [decision tree](../index.html) splits the predictor space with an axis-aligned hyperplane, \\(x_j \le c\\).
+//! When the classes (or the response) change along a direction that is not parallel to any of the axes, e.g. when features are correlated or the
+//! feature space is rotated, an axis-aligned tree needs many splits to approximate the boundary with a staircase.
+//!
+//! An oblique split compares a sparse linear combination of features to a threshold, \\(\sum_{j \in S} w_j x_j \le c\\), where \\(|S|\\) is small.
+//! At each node SmartCore finds the weights \\(w\\) with a ridge regression fitted to the samples that reach the node, keeps the features with the largest
+//! standardized coefficients, and then searches for the best threshold \\(c\\) along the resulting direction exactly like it does for a single feature.
+//! The oblique candidate is used only when it scores better than the best axis-aligned split.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::tree::decision_tree_classifier::*;
+//! use smartcore::tree::oblique::SplitType;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!            &[1., 0.], &[2., 1.], &[3., 2.], &[4., 3.],
+//!            &[0., 1.], &[1., 2.], &[2., 3.], &[3., 4.],
+//!         ]);
+//! let y = vec![0., 0., 0., 0., 1., 1., 1., 1.];
+//!
+//! let parameters = DecisionTreeClassifierParameters::default()
+//!     .with_split_type(SplitType::Oblique { max_features: 2, alpha: 1e-3 });
+//! let tree = DecisionTreeClassifier::fit(&x, &y, parameters).unwrap();
+//!
+//! let y_hat = tree.predict(&x).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["A System for Induction of Oblique Decision Trees", Murthy S. K., Kasif S., Salzberg S., 1994](https://arxiv.org/abs/cs/9408103)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::linalg::Matrix;
+use crate::math::num::RealNumber;
+
+/// Shape of the hyperplane that is used to split an internal node of a tree.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum SplitType {
+    /// Split on a single feature, \\(x_j \le c\\).
+    #[default]
+    AxisAligned,
+    /// Split on a linear combination of at most `max_features` features, \\(w^Tx \le c\\).
+    /// The weights are estimated with a ridge regression with regularization strength `alpha`.
+    Oblique {
+        /// The maximum number of features with non-zero weight in each split.
+        max_features: usize,
+        /// Regularization strength of the ridge fit, computed on standardized features.
+        alpha: f64,
+    },
+}
+
+/// Evaluates the linear combination `weights` for the row `row` of `x`.
+pub(crate) fn project<T: RealNumber, M: Matrix<T>>(x: &M, row: usize, weights: &[(usize, T)]) -> T {
+    weights
+        .iter()
+        .fold(T::zero(), |acc, (j, w)| acc + *w * x.get(row, *j))
+}
+
+/// Fits a ridge regression of `target` on `features` using the samples with a non-zero count in `samples`
+/// and returns the sparse direction formed by the `max_features` largest standardized coefficients.
+/// The weights are expressed in the original scale of the features.
+/// Returns `None` when the direction would involve less than two features, in which case
+/// an axis-aligned split is at least as good.
+pub(crate) fn ridge_direction<T: RealNumber, M: Matrix<T>>(
+    x: &M,
+    target: &[T],
+    samples: &[usize],
+    features: &[usize],
+    max_features: usize,
+    alpha: T,
+) -> Option<Vec<(usize, T)>> {
+    let n: usize = samples.iter().sum();
+    if n < 2 || features.len() < 2 || max_features < 2 {
+        return None;
+    }
+    let n_t = T::from(n).unwrap();
+
+    let mut target_mean = T::zero();
+    for (i, sample_i) in samples.iter().enumerate() {
+        if *sample_i > 0 {
+            target_mean += T::from(*sample_i).unwrap() * target[i];
+        }
+    }
+    target_mean /= n_t;
+
+    let mut candidates: Vec<(usize, T, T)> = Vec::with_capacity(features.len());
+    for j in features.iter() {
+        let mut mean = T::zero();
+        for (i, sample_i) in samples.iter().enumerate() {
+            if *sample_i > 0 {
+                mean += T::from(*sample_i).unwrap() * x.get(i, *j);
+            }
+        }
+        mean /= n_t;
+        let mut var = T::zero();
+        for (i, sample_i) in samples.iter().enumerate() {
+            if *sample_i > 0 {
+                var += T::from(*sample_i).unwrap() * (x.get(i, *j) - mean).square();
+            }
+        }
+        let std = (var / n_t).sqrt();
+        if std > T::epsilon() {
+            candidates.push((*j, mean, std));
+        }
+    }
+
+    let w = solve_ridge::<T, M>(x, target, target_mean, samples, &candidates, alpha)?;
+
+    if candidates.len() > max_features {
+        let mut ranked: Vec<usize> = (0..candidates.len()).collect();
+        ranked.sort_by(|a, b| w[*b].abs().partial_cmp(&w[*a].abs()).unwrap());
+        ranked.truncate(max_features);
+        ranked.sort_unstable();
+        candidates = ranked.into_iter().map(|k| candidates[k]).collect();
+    }
+
+    let w = solve_ridge::<T, M>(x, target, target_mean, samples, &candidates, alpha)?;
+
+    let weights: Vec<(usize, T)> = candidates
+        .iter()
+        .zip(w.iter())
+        .filter(|(_, w_k)| w_k.abs() > T::epsilon())
+        .map(|((j, _, std), w_k)| (*j, *w_k / *std))
+        .collect();
+
+    if weights.len() < 2 {
+        None
+    } else {
+        Some(weights)
+    }
+}
+
+fn solve_ridge<T: RealNumber, M: Matrix<T>>(
+    x: &M,
+    target: &[T],
+    target_mean: T,
+    samples: &[usize],
+    candidates: &[(usize, T, T)],
+    alpha: T,
+) -> Option<Vec<T>> {
+    let k = candidates.len();
+    if k < 2 {
+        return None;
+    }
+    let n = T::from(samples.iter().sum::<usize>()).unwrap();
+
+    let mut a = M::zeros(k, k);
+    let mut b = M::zeros(k, 1);
+    let mut z = vec![T::zero(); k];
+
+    for (i, sample_i) in samples.iter().enumerate() {
+        if *sample_i > 0 {
+            let s = T::from(*sample_i).unwrap() / n;
+            for (z_l, (j, mean, std)) in z.iter_mut().zip(candidates.iter()) {
+                *z_l = (x.get(i, *j) - *mean) / *std;
+            }
+            let t = target[i] - target_mean;
+            for l in 0..k {
+                b.add_element_mut(l, 0, s * z[l] * t);
+                for m in l..k {
+                    a.add_element_mut(l, m, s * z[l] * z[m]);
+                }
+            }
+        }
+    }
+
+    for l in 0..k {
+        for m in 0..l {
+            a.set(l, m, a.get(m, l));
+        }
+        a.add_element_mut(l, l, alpha);
+    }
+
+    let w = a.cholesky_solve_mut(b).ok()?;
+
+    let w: Vec<T> = (0..k).map(|l| w.get(l, 0)).collect();
+    if w.iter().any(|w_l| !w_l.is_finite()) {
+        None
+    } else {
+        Some(w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn ridge_direction_recovers_rotated_boundary() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 0., 5.],
+            &[2., 1., 3.],
+            &[3., 2., 4.],
+            &[4., 3., 1.],
+            &[0., 1., 2.],
+            &[1., 2., 5.],
+            &[2., 3., 1.],
+            &[3., 4., 3.],
+        ]);
+        let y: Vec<f64> = vec![0., 0., 0., 0., 1., 1., 1., 1.];
+        let samples = vec![1; 8];
+
+        let weights = ridge_direction(&x, &y, &samples, &[0, 1, 2], 2, 1e-6).unwrap();
+
+        assert_eq!(weights.len(), 2);
+        assert_eq!(weights[0].0, 0);
+        assert_eq!(weights[1].0, 1);
+        assert!((weights[0].1 + weights[1].1).abs() < 1e-3);
+
+        let p: Vec<f64> = (0..8).map(|i| project(&x, i, &weights)).collect();
+        assert!(p[..4].iter().all(|p_i| p_i * weights[1].1.signum() < 0.));
+        assert!(p[4..].iter().all(|p_i| p_i * weights[1].1.signum() > 0.));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn ridge_direction_needs_two_features() {
+        let x = DenseMatrix::from_2d_array(&[&[1., 1.], &[2., 1.], &[3., 1.], &[4., 1.]]);
+        let y: Vec<f64> = vec![0., 0., 1., 1.];
+
+        assert!(ridge_direction(&x, &y, &[1; 4], &[0, 1], 2, 1e-3).is_none());
+        assert!(ridge_direction(&x, &y, &[1; 4], &[0], 2, 1e-3).is_none());
+    }
+}
@@ -0,0 +1,170 @@
+//! # Input Validation
+//!
+//! Arithmetic on values that are not finite does not fail, it silently turns coefficients, distances and predictions into `NaN` or into meaningless numbers.
+//! To avoid that, estimators check their input and `fit`, `predict` and `transform` return a [`Failed`](../error/struct.Failed.html) error
+//! that names the first value of `x` or `y` that is `NaN` or infinite.
+//!
+//! `NaN` in a data [Matrix](../linalg/trait.BaseMatrix.html) marks a missing value. A few estimators support missing values explicitly and accept `NaN` in `x`:
+//!
+//! | Estimator | Missing values |
+//! |-|-|
+//! | [Histogram-based gradient boosting](../ensemble/hist_gradient_boosting/index.html) | every split learns on which side they go |
+//! | [K Nearest Neighbors](../neighbors/index.html) and [DBSCAN](../cluster/dbscan/index.html) with the [NanEuclidian](../math/distance/nan_euclidian/index.html) distance | ignored by the distance |
+//! | [KNNImputer](../preprocessing/impute/struct.KNNImputer.html) | replaced with values of the nearest rows |
+//! | [MissingIndicator](../preprocessing/impute/struct.MissingIndicator.html) | marked in binary columns |
+//!
+//! Other estimators need the missing values to be imputed first, e.g. by a [pipeline](../pipeline/index.html) that starts with a `KNNImputer`.
+//! Infinite values in `x` and non-finite targets in `y` are rejected by every estimator.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::ensemble::hist_gradient_boosting::*;
+//! use smartcore::linalg::naive::dense_matrix::*;
+//! use smartcore::linear::linear_regression::*;
+//! use smartcore::validation::find_non_finite;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!     &[1., 2.],
+//!     &[2., f64::NAN],
+//!     &[3., 1.],
+//!     &[4., 3.],
+//! ]);
+//! let y = vec![1., 2., 3., 4.];
+//!
+//! assert_eq!(find_non_finite(&x, false), Some((1, 1)));
+//! assert_eq!(find_non_finite(&x, true), None);
+//!
+//! let error = LinearRegression::fit(&x, &y, Default::default()).unwrap_err();
+//! assert_eq!(
+//!     error.to_string(),
+//!     "Fit failed: x contains NaN at row 1, column 1, impute missing values or use an estimator that supports them"
+//! );
+//!
+//! let parameters = HistGradientBoostingRegressorParameters::default().with_min_samples_leaf(1);
+//! let regressor = HistGradientBoostingRegressor::fit(&x, &y, parameters).unwrap();
+//! let y_hat = regressor.predict(&x).unwrap();
+//! ```
+use crate::error::Failed;
+use crate::linalg::{BaseMatrix, BaseVector};
+use crate::math::num::RealNumber;
+
+/// Returns the row and the column of the first value of `x` that is not finite, or `None` when all values are finite.
+/// * `x` - data matrix
+/// * `allow_missing` - whether `NaN` is a missing value that should be skipped
+pub fn find_non_finite<T: RealNumber, M: BaseMatrix<T>>(
+    x: &M,
+    allow_missing: bool,
+) -> Option<(usize, usize)> {
+    let (n, m) = x.shape();
+    for i in 0..n {
+        for j in 0..m {
+            let x_ij = x.get(i, j);
+            if x_ij.is_infinite() || (x_ij.is_nan() && !allow_missing) {
+                return Some((i, j));
+            }
+        }
+    }
+    None
+}
+
+fn describe<T: RealNumber>(value: T) -> &'static str {
+    if value.is_nan() {
+        "NaN"
+    } else if value < T::zero() {
+        "-inf"
+    } else {
+        "inf"
+    }
+}
+
+/// Checks that `x` has no infinite values and no `NaN` unless `allow_missing` is set, `failed` builds the error.
+pub(crate) fn check_x<T: RealNumber, M: BaseMatrix<T>>(
+    x: &M,
+    allow_missing: bool,
+    failed: fn(&str) -> Failed,
+) -> Result<(), Failed> {
+    match find_non_finite(x, allow_missing) {
+        None => Ok(()),
+        Some((i, j)) => {
+            let value = x.get(i, j);
+            let mut msg = format!("x contains {} at row {}, column {}", describe(value), i, j);
+            if value.is_nan() {
+                msg.push_str(", impute missing values or use an estimator that supports them");
+            }
+            Err(failed(&msg))
+        }
+    }
+}
+
+/// Checks that all targets in `y` are finite, `failed` builds the error.
+pub(crate) fn check_y<T: RealNumber, V: BaseVector<T>>(
+    y: &V,
+    failed: fn(&str) -> Failed,
+) -> Result<(), Failed> {
+    match (0..y.len()).find(|i| !y.get(*i).is_finite()) {
+        None => Ok(()),
+        Some(i) => Err(failed(&format!(
+            "y contains {} at index {}",
+            describe(y.get(i)),
+            i
+        ))),
+    }
+}
+
+/// Checks the training data of an estimator that does not support missing values.
+pub(crate) fn check_fit<T: RealNumber, M: BaseMatrix<T>>(
+    x: &M,
+    y: &M::RowVector,
+) -> Result<(), Failed> {
+    check_x(x, false, Failed::fit)?;
+    check_y(y, Failed::fit)
+}
+
+/// Checks the data passed to `predict` of an estimator that does not support missing values.
+pub(crate) fn check_predict<T: RealNumber, M: BaseMatrix<T>>(x: &M) -> Result<(), Failed> {
+    check_x(x, false, Failed::predict)
+}
+
+/// Checks the data passed to `transform` of an estimator that does not support missing values.
+pub(crate) fn check_transform<T: RealNumber, M: BaseMatrix<T>>(x: &M) -> Result<(), Failed> {
+    check_x(x, false, Failed::transform)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::FailedError;
+    use crate::linalg::naive::dense_matrix::DenseMatrix;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[test]
+    fn non_finite_values() {
+        let x = DenseMatrix::from_2d_array(&[&[1., f64::NAN], &[f64::INFINITY, 2.]]);
+
+        assert_eq!(find_non_finite(&x, false), Some((0, 1)));
+        assert_eq!(find_non_finite(&x, true), Some((1, 0)));
+        assert_eq!(
+            find_non_finite(&DenseMatrix::<f64>::zeros(2, 2), false),
+            None
+        );
+
+        let error = check_predict(&x).unwrap_err();
+        assert_eq!(error.error(), FailedError::PredictFailed);
+        assert_eq!(
+            error.to_string(),
+            "Predict failed: x contains NaN at row 0, column 1, impute missing values or use an estimator that supports them"
+        );
+        assert_eq!(
+            check_x(&x, true, Failed::transform).unwrap_err(),
+            Failed::transform("x contains inf at row 1, column 0")
+        );
+
+        let y = vec![1., f64::NEG_INFINITY];
+        assert_eq!(
+            check_fit(&DenseMatrix::<f64>::zeros(2, 2), &y).unwrap_err(),
+            Failed::fit("y contains -inf at index 1")
+        );
+        assert!(check_fit(&DenseMatrix::<f64>::zeros(2, 2), &vec![1., 2.]).is_ok());
+    }
+}